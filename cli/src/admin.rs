@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use rpassword::prompt_password;
+use url::Url;
+
+use hakanai_lib::models::{PurgePreviewResponse, PurgeResponse, StatsResponse};
+use hakanai_lib::utils::human_size;
+
+use crate::args::{AdminArgs, AdminCommand, PurgeArgs, StatsArgs};
+use crate::helper;
+
+pub async fn admin(args: AdminArgs) -> Result<()> {
+    let admin_token = prompt_password("Enter admin token: ")?;
+    if admin_token.is_empty() {
+        return Err(anyhow!("Admin token cannot be empty"));
+    }
+
+    match args.command {
+        AdminCommand::Stats(stats_args) => stats(&admin_token, stats_args).await,
+        AdminCommand::Purge(purge_args) => purge(&admin_token, purge_args).await,
+    }
+}
+
+async fn stats(admin_token: &str, args: StatsArgs) -> Result<()> {
+    let stats = stats_request(admin_token, &args).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", format_stats(&stats));
+    }
+
+    Ok(())
+}
+
+async fn stats_request(admin_token: &str, args: &StatsArgs) -> Result<StatsResponse> {
+    let client = reqwest::Client::new();
+    let url = args.server.join("api/v1/admin/stats")?;
+
+    let response = client
+        .get(url)
+        .header("User-Agent", helper::get_user_agent_name())
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(anyhow!(
+            "Failed to retrieve stats: {} - {}",
+            status,
+            error_text
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn purge(admin_token: &str, args: PurgeArgs) -> Result<()> {
+    let preview: PurgePreviewResponse = purge_request(admin_token, &args, None).await?;
+    if preview.secrets == 0 {
+        println!("No secrets to purge.");
+        return Ok(());
+    }
+
+    if !args.yes {
+        let scope = match args.older_than {
+            Some(older_than) => format!("older than {}", humantime::format_duration(older_than)),
+            None => "(all stored secrets)".to_string(),
+        };
+        eprint!(
+            "{} Purge {} secrets {scope} from {}? [y/N] ",
+            "Warning:".red().bold(),
+            preview.secrets,
+            args.server
+        );
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        if !helper::is_confirmed(&answer) {
+            return Err(anyhow!("Purge aborted."));
+        }
+    }
+
+    let response: PurgeResponse =
+        purge_request(admin_token, &args, Some(&preview.confirmation_token)).await?;
+    println!("Purged {} secrets.", response.purged.to_string().green());
+
+    Ok(())
+}
+
+async fn purge_request<T: serde::de::DeserializeOwned>(
+    admin_token: &str,
+    args: &PurgeArgs,
+    confirmation_token: Option<&str>,
+) -> Result<T> {
+    let client = reqwest::Client::new();
+    let url = purge_url(args, confirmation_token)?;
+
+    let response = client
+        .delete(url)
+        .header("User-Agent", helper::get_user_agent_name())
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(anyhow!(
+            "Failed to purge secrets: {} - {}",
+            status,
+            error_text
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+fn purge_url(args: &PurgeArgs, confirmation_token: Option<&str>) -> Result<Url> {
+    let mut url = args.server.join("api/v1/admin/secrets")?;
+
+    let mut pairs = Vec::new();
+    if let Some(older_than) = args.older_than {
+        pairs.push(("older_than", format!("{}s", older_than.as_secs())));
+    }
+    if let Some(token) = confirmation_token {
+        pairs.push(("confirm", token.to_string()));
+    }
+    if !pairs.is_empty() {
+        url.query_pairs_mut().extend_pairs(pairs);
+    }
+
+    Ok(url)
+}
+
+fn format_stats(stats: &StatsResponse) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("{}\n", "Secrets".bold()));
+    out.push_str(&format!("  Active:    {}\n", stats.active_secrets));
+    out.push_str(&format!("  Retrieved: {}\n", stats.retrieved_secrets));
+    out.push_str(&format!("  Expired:   {}\n", stats.expired_secrets));
+
+    out.push_str(&format!("\n{}\n", "Per day (UTC)".bold()));
+    out.push_str(&format!(
+        "  {:<10}  {:>8}  {:>9}  {:>7}\n",
+        "Date", "Created", "Retrieved", "Expired"
+    ));
+    for day in &stats.days {
+        out.push_str(&format!(
+            "  {:<10}  {:>8}  {:>9}  {:>7}\n",
+            day.date, day.created, day.retrieved, day.expired
+        ));
+    }
+
+    out.push_str(&format!("\n{}\n", "Secret sizes".bold()));
+    for bucket in &stats.size_histogram {
+        let label = match bucket.max_size {
+            Some(max) => format!("<= {}", human_size::format_size(max)),
+            None => "larger".to_string(),
+        };
+        out.push_str(&format!("  {:<10}  {:>8}\n", label, bucket.count));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hakanai_lib::models::{DailyStats, SizeBucket};
+
+    #[test]
+    fn test_format_stats() {
+        colored::control::set_override(false);
+
+        let stats = StatsResponse {
+            active_secrets: 3,
+            retrieved_secrets: 5,
+            expired_secrets: 1,
+            days: vec![DailyStats {
+                date: "2025-01-01".to_string(),
+                created: 9,
+                retrieved: 5,
+                expired: 1,
+            }],
+            size_histogram: vec![
+                SizeBucket {
+                    max_size: Some(1024),
+                    count: 7,
+                },
+                SizeBucket {
+                    max_size: None,
+                    count: 2,
+                },
+            ],
+        };
+
+        let output = format_stats(&stats);
+        assert!(output.contains("Active:    3"), "Output: {output}");
+        assert!(output.contains("Retrieved: 5"), "Output: {output}");
+        assert!(
+            output.contains("2025-01-01         9          5        1"),
+            "Output: {output}"
+        );
+        assert!(output.contains("<= 1 KiB"), "Output: {output}");
+        assert!(output.contains("larger"), "Output: {output}");
+    }
+
+    #[test]
+    fn test_purge_url() -> Result<()> {
+        let mut args = PurgeArgs {
+            server: Url::parse("https://example.com")?,
+            older_than: None,
+            yes: false,
+        };
+        assert_eq!(
+            purge_url(&args, None)?.as_str(),
+            "https://example.com/api/v1/admin/secrets"
+        );
+
+        args.older_than = Some(std::time::Duration::from_secs(3600));
+        assert_eq!(
+            purge_url(&args, Some("123.abc"))?.as_str(),
+            "https://example.com/api/v1/admin/secrets?older_than=3600s&confirm=123.abc",
+            "Confirmation should be sent with the same criteria"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,441 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::Metadata;
+use std::io::{Cursor, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use zeroize::Zeroizing;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_EMPTY_MAGIC: &[u8] = b"PK\x05\x06";
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Archive formats supported for sending multiple files or directories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ArchiveFormat {
+    /// ZIP archive (compatible with the web interface)
+    #[default]
+    Zip,
+
+    /// Zstandard compressed tar archive (preserves unix permissions)
+    #[value(name = "tar.zst")]
+    TarZst,
+}
+
+impl ArchiveFormat {
+    /// Returns the file extension for the archive format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarZst => "tar.zst",
+        }
+    }
+
+    /// Detects the archive format by its magic bytes.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(ZIP_MAGIC) || bytes.starts_with(ZIP_EMPTY_MAGIC) {
+            Some(ArchiveFormat::Zip)
+        } else if bytes.starts_with(ZSTD_MAGIC) {
+            Some(ArchiveFormat::TarZst)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new in-memory writer for the archive format.
+    pub fn new_writer(&self) -> Result<Box<dyn ArchiveWriter>> {
+        let writer: Box<dyn ArchiveWriter> = match self {
+            ArchiveFormat::Zip => Box::new(ZipArchiveWriter::new()),
+            ArchiveFormat::TarZst => Box::new(TarZstArchiveWriter::new()?),
+        };
+
+        Ok(writer)
+    }
+}
+
+/// Writer adding files and directories from the file system to an in-memory archive.
+pub trait ArchiveWriter {
+    /// Adds a directory entry with the given name to the archive.
+    fn add_directory(&mut self, name: &str, path: &Path) -> Result<()>;
+
+    /// Adds the file at `path` to the archive using the given name.
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<()>;
+
+    /// Adds a symlink with the given name pointing to `target` to the archive.
+    fn add_symlink(&mut self, name: &str, target: &Path, path: &Path) -> Result<()>;
+
+    /// Finishes the archive and returns its bytes.
+    fn finish(self: Box<Self>) -> Result<Vec<u8>>;
+}
+
+struct ZipArchiveWriter {
+    zip: ZipWriter<Cursor<Vec<u8>>>,
+}
+
+impl ZipArchiveWriter {
+    fn new() -> Self {
+        Self {
+            zip: ZipWriter::new(Cursor::new(Vec::new())),
+        }
+    }
+
+    fn options(metadata: &Metadata) -> SimpleFileOptions {
+        let mut options = SimpleFileOptions::default();
+
+        if let Some(mode) = unix_mode(metadata) {
+            options = options.unix_permissions(mode);
+        }
+
+        if let Some(modified) = metadata.modified().ok().and_then(zip_datetime) {
+            options = options.last_modified_time(modified);
+        }
+
+        options
+    }
+}
+
+impl ArchiveWriter for ZipArchiveWriter {
+    fn add_directory(&mut self, name: &str, path: &Path) -> Result<()> {
+        let options = Self::options(&std::fs::metadata(path)?);
+        self.zip.add_directory(format!("{name}/"), options)?;
+        Ok(())
+    }
+
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<()> {
+        let options = Self::options(&std::fs::metadata(path)?);
+        let bytes = Zeroizing::new(std::fs::read(path)?);
+        self.zip.start_file(name, options)?;
+        self.zip.write_all(bytes.as_ref())?;
+        Ok(())
+    }
+
+    fn add_symlink(&mut self, name: &str, target: &Path, path: &Path) -> Result<()> {
+        let options = Self::options(&std::fs::symlink_metadata(path)?);
+        self.zip
+            .add_symlink(name, target.to_string_lossy().to_string(), options)?;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+        Ok(self.zip.finish()?.into_inner())
+    }
+}
+
+struct TarZstArchiveWriter {
+    builder: tar::Builder<zstd::Encoder<'static, Vec<u8>>>,
+}
+
+impl TarZstArchiveWriter {
+    fn new() -> Result<Self> {
+        let encoder = zstd::Encoder::new(Vec::new(), zstd::DEFAULT_COMPRESSION_LEVEL)?;
+        Ok(Self {
+            builder: tar::Builder::new(encoder),
+        })
+    }
+}
+
+impl ArchiveWriter for TarZstArchiveWriter {
+    fn add_directory(&mut self, name: &str, path: &Path) -> Result<()> {
+        self.builder.append_dir(name, path)?;
+        Ok(())
+    }
+
+    fn add_file(&mut self, name: &str, path: &Path) -> Result<()> {
+        self.builder.append_path_with_name(path, name)?;
+        Ok(())
+    }
+
+    fn add_symlink(&mut self, name: &str, target: &Path, path: &Path) -> Result<()> {
+        let metadata = std::fs::symlink_metadata(path)?;
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(unix_mode(&metadata).unwrap_or(0o777));
+        if let Ok(modified) = metadata.modified()
+            && let Ok(mtime) = modified.duration_since(UNIX_EPOCH)
+        {
+            header.set_mtime(mtime.as_secs());
+        }
+
+        self.builder.append_link(&mut header, name, target)?;
+        Ok(())
+    }
+
+    fn finish(self: Box<Self>) -> Result<Vec<u8>> {
+        let encoder = self.builder.into_inner()?;
+        Ok(encoder.finish()?)
+    }
+}
+
+/// Checks if the symlink target stays within the archive root.
+///
+/// `link` is the path of the symlink relative to the archive root. Absolute targets and
+/// targets escaping the archive root via `..` are rejected.
+pub fn is_enclosed_symlink(link: &Path, target: &Path) -> bool {
+    let mut depth: usize = 0;
+    let parent = link.parent().unwrap_or(Path::new(""));
+
+    for component in parent.components().chain(target.components()) {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+
+    !target.as_os_str().is_empty()
+}
+
+/// Checks if the symlink target passes through another symlink before its last component.
+///
+/// [`is_enclosed_symlink`] resolves `..` lexically, which does not hold if a directory on the way
+/// is a symlink itself: with `d -> .`, the target `d/../..` of a link in the root escapes it.
+/// `is_symlink` is called with the paths relative to the archive root.
+pub fn traverses_symlink(link: &Path, target: &Path, is_symlink: impl Fn(&Path) -> bool) -> bool {
+    let mut resolved: PathBuf = link
+        .parent()
+        .unwrap_or(Path::new(""))
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    let mut components = target.components().peekable();
+
+    while let Some(component) = components.next() {
+        match component {
+            Component::Normal(name) => {
+                resolved.push(name);
+                // a symlink as last component is checked on its own
+                if components.peek().is_some() && is_symlink(&resolved) {
+                    return true;
+                }
+            }
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+/// Converts the time to a ZIP timestamp (UTC), returns `None` if out of the supported range.
+pub fn zip_datetime(time: SystemTime) -> Option<zip::DateTime> {
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let secs_of_day = secs % 86400;
+
+    zip::DateTime::from_date_and_time(
+        u16::try_from(year).ok()?,
+        month as u8,
+        day as u8,
+        (secs_of_day / 3600) as u8,
+        (secs_of_day % 3600 / 60) as u8,
+        (secs_of_day % 60) as u8,
+    )
+    .ok()
+}
+
+/// Converts a ZIP timestamp (UTC) to a system time.
+pub fn system_time_from_zip(datetime: &zip::DateTime) -> Option<SystemTime> {
+    let days = days_from_civil(
+        datetime.year() as i64,
+        datetime.month() as u32,
+        datetime.day() as u32,
+    );
+    let secs = days * 86400
+        + datetime.hour() as i64 * 3600
+        + datetime.minute() as i64 * 60
+        + datetime.second() as i64;
+
+    u64::try_from(secs)
+        .ok()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+// see http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = month as i64;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_archive_format() {
+        assert_eq!(
+            ArchiveFormat::detect(b"PK\x03\x04rest"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::detect(b"PK\x05\x06rest"),
+            Some(ArchiveFormat::Zip),
+            "empty zip archives should be detected"
+        );
+        assert_eq!(
+            ArchiveFormat::detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]),
+            Some(ArchiveFormat::TarZst)
+        );
+        assert_eq!(ArchiveFormat::detect(b"plain text"), None);
+        assert_eq!(ArchiveFormat::detect(b""), None);
+    }
+
+    #[test]
+    fn test_is_enclosed_symlink() {
+        assert!(is_enclosed_symlink(
+            Path::new("dir/link"),
+            Path::new("file.txt")
+        ));
+        assert!(is_enclosed_symlink(
+            Path::new("dir/sub/link"),
+            Path::new("../file.txt")
+        ));
+        assert!(
+            !is_enclosed_symlink(Path::new("dir/link"), Path::new("../../file.txt")),
+            "target escaping the root should be rejected"
+        );
+        assert!(
+            !is_enclosed_symlink(Path::new("link"), Path::new("/etc/passwd")),
+            "absolute target should be rejected"
+        );
+        assert!(
+            !is_enclosed_symlink(Path::new("link"), Path::new("")),
+            "empty target should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_traverses_symlink() {
+        let is_symlink = |path: &Path| path == Path::new("d") || path == Path::new("dir/d");
+
+        assert!(is_enclosed_symlink(
+            Path::new("dir/e"),
+            Path::new("d/../..")
+        ));
+        assert!(
+            traverses_symlink(Path::new("dir/e"), Path::new("d/../.."), is_symlink),
+            "target resolved through a symlink should be detected"
+        );
+        assert!(traverses_symlink(
+            Path::new("e"),
+            Path::new("d/.."),
+            is_symlink
+        ));
+        assert!(traverses_symlink(
+            Path::new("dir/sub/e"),
+            Path::new("../d/file.txt"),
+            is_symlink
+        ));
+        assert!(
+            !traverses_symlink(Path::new("e"), Path::new("d"), is_symlink),
+            "symlink as last component should be allowed"
+        );
+        assert!(!traverses_symlink(
+            Path::new("dir/e"),
+            Path::new("../file.txt"),
+            is_symlink
+        ));
+    }
+
+    #[test]
+    fn test_zip_datetime_roundtrip() -> Result<()> {
+        // 2024-02-29 13:37:42 UTC
+        let time = UNIX_EPOCH + Duration::from_secs(1709213862);
+
+        let datetime = zip_datetime(time).ok_or(anyhow::anyhow!("out of range"))?;
+        assert_eq!(
+            (datetime.year(), datetime.month(), datetime.day()),
+            (2024, 2, 29)
+        );
+        assert_eq!(
+            (datetime.hour(), datetime.minute()),
+            (13, 37),
+            "time of day should be converted in UTC"
+        );
+
+        // ZIP timestamps have a resolution of 2 seconds
+        let restored = system_time_from_zip(&datetime).ok_or(anyhow::anyhow!("invalid"))?;
+        let diff = time.duration_since(restored)?;
+        assert!(
+            diff.as_secs() < 2,
+            "Restored time should match, got {diff:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_zip_datetime_out_of_range() {
+        assert!(
+            zip_datetime(UNIX_EPOCH).is_none(),
+            "ZIP timestamps start in 1980"
+        );
+    }
+
+    #[test]
+    fn test_writer_output_is_detected() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let file_path = temp_dir.path().join("file.txt");
+        std::fs::write(&file_path, b"content")?;
+
+        for format in [ArchiveFormat::Zip, ArchiveFormat::TarZst] {
+            let mut writer = format.new_writer()?;
+            writer.add_file("file.txt", &file_path)?;
+            let bytes = writer.finish()?;
+
+            assert_eq!(
+                ArchiveFormat::detect(&bytes),
+                Some(format),
+                "{} archive should be detected by magic bytes",
+                format.extension()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use url::Url;
+
+/// Represents the arguments for the `admin` command.
+#[derive(Debug, Clone, Parser)]
+pub struct AdminArgs {
+    #[command(subcommand)]
+    pub command: AdminCommand,
+}
+
+/// Represents the administrative subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum AdminCommand {
+    /// Show aggregated secret statistics of the server.
+    Stats(StatsArgs),
+
+    /// Delete stored secrets from the server, e.g. in response to an incident.
+    Purge(PurgeArgs),
+}
+
+/// Represents the arguments for the `admin stats` command.
+#[derive(Debug, Clone, Parser)]
+pub struct StatsArgs {
+    #[arg(
+        short,
+        long,
+        default_value = "http://localhost:8080",
+        env = "HAKANAI_SERVER",
+        help = "Hakanai Server URL to request the statistics from (eg. https://hakanai.link)."
+    )]
+    pub server: Url,
+
+    #[arg(
+        long,
+        help = "Print the statistics as JSON instead of a table.",
+        default_value_t = false
+    )]
+    pub json: bool,
+}
+
+/// Represents the arguments for the `admin purge` command.
+#[derive(Debug, Clone, Parser)]
+pub struct PurgeArgs {
+    #[arg(
+        short,
+        long,
+        default_value = "http://localhost:8080",
+        env = "HAKANAI_SERVER",
+        help = "Hakanai Server URL to purge the secrets from (eg. https://hakanai.link)."
+    )]
+    pub server: Url,
+
+    #[arg(
+        long,
+        help = "Only purge secrets created longer ago than this (e.g. 12h), all secrets if not set.",
+        value_parser = humantime::parse_duration,
+    )]
+    pub older_than: Option<Duration>,
+
+    #[arg(
+        short,
+        long,
+        help = "Purge without asking for confirmation.",
+        default_value_t = false
+    )]
+    pub yes: bool,
+}
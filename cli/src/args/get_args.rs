@@ -1,15 +1,52 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Result, anyhow};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use url::Url;
 
+use hakanai_lib::link::LinkFragment;
+use hakanai_lib::shamir::{self, Share};
+use hakanai_lib::utils::{human_duration, human_size};
+
+use crate::integrations::SopsTarget;
+
+/// Handling of existing files when saving a secret.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnConflict {
+    /// Replace the existing file
+    Overwrite,
+
+    /// Save to a new file with a timestamp (or counter) appended to the name
+    #[default]
+    Rename,
+
+    /// Abort with an error
+    Fail,
+}
+
 /// Represents the arguments for the `get` command.
 #[derive(Debug, Clone, Parser)]
 pub struct GetArgs {
-    pub link: Url,
+    #[arg(required_unless_present = "from_file")]
+    pub link: Option<Url>,
+
+    #[arg(
+        long,
+        conflicts_with = "link",
+        value_name = "FILE",
+        help = "Retrieve all secrets of the links listed in this file (one per line, empty lines and lines starting with # are ignored). The secrets are saved to files in the output directory."
+    )]
+    pub from_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value_t = 4,
+        help = "Maximum number of secrets retrieved concurrently when using --from-file."
+    )]
+    pub parallel: usize,
 
     #[arg(
         short,
@@ -24,6 +61,13 @@ pub struct GetArgs {
     )]
     pub ask_key: bool,
 
+    #[arg(
+        long = "share",
+        value_name = "SHARE",
+        help = "Key share of a link created with --split, repeat the option for every share (e.g. --share 2.1.abc --share 2.3.def)."
+    )]
+    pub shares: Vec<Share>,
+
     #[arg(
         long,
         env = "HAKANAI_TO_STDOUT",
@@ -31,6 +75,12 @@ pub struct GetArgs {
     )]
     pub to_stdout: bool,
 
+    #[arg(
+        long,
+        help = "Output binary data or control characters even if stdout is a terminal, which could mess up the terminal."
+    )]
+    pub force: bool,
+
     #[arg(
         short,
         long,
@@ -53,6 +103,30 @@ pub struct GetArgs {
     )]
     pub output_dir: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_enum,
+        default_value = "rename",
+        env = "HAKANAI_ON_CONFLICT",
+        help = "What to do if a file to save the secret to already exists."
+    )]
+    pub on_conflict: OnConflict,
+
+    #[arg(
+        long,
+        value_name = "FILE#KEY",
+        help = "Store the secret under a key of a file encrypted with SOPS (e.g. secrets.enc.yaml#db_password) instead of printing or saving it. The file must exist, the sops CLI is used with its usual configuration."
+    )]
+    pub to_sops: Option<SopsTarget>,
+
+    #[arg(
+        long,
+        default_value = "sops",
+        env = "HAKANAI_SOPS",
+        help = "sops binary used by --to-sops (version 3.9 or later)."
+    )]
+    pub sops: String,
+
     #[arg(
         short,
         long,
@@ -62,6 +136,78 @@ pub struct GetArgs {
 
     #[arg(long, help = "Ask for passphrase protecting the secret.")]
     pub ask_passphrase: bool,
+
+    #[arg(
+        long,
+        help = "If the secret is protected by a PIN, provide the PIN you received separately (e.g. via SMS) here."
+    )]
+    pub pin: Option<String>,
+
+    #[arg(long, help = "Ask for the PIN protecting the secret.")]
+    pub ask_pin: bool,
+
+    #[arg(
+        long,
+        value_name = "HEX",
+        help = "Verify the secret against the expected SHA-256 hash (hex encoded), e.g. received out-of-band from the sender."
+    )]
+    pub verify_hash: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "1024m",
+        env = "HAKANAI_MAX_EXTRACT_SIZE",
+        help = "Maximum total size of files extracted from an archive (e.g., 1024m, 500k).",
+        value_parser = human_size::parse,
+    )]
+    pub max_extract_size: i64,
+
+    #[arg(
+        long,
+        default_value_t = 10_000,
+        env = "HAKANAI_MAX_EXTRACT_ENTRIES",
+        help = "Maximum number of entries extracted from an archive."
+    )]
+    pub max_extract_entries: usize,
+
+    #[arg(
+        long,
+        default_value_t = 100,
+        env = "HAKANAI_MAX_COMPRESSION_RATIO",
+        help = "Maximum ratio between extracted size and archive size, protects against archive bombs."
+    )]
+    pub max_compression_ratio: u64,
+
+    #[arg(
+        long,
+        env = "HAKANAI_LIMIT_RATE",
+        help = "Limit the transfer rate in bytes per second (e.g., 500k, 2m).",
+        value_parser = human_size::parse,
+    )]
+    pub limit_rate: Option<i64>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_MAX_MEMORY",
+        help = "Maximum size of encrypted data kept in memory while transferring (e.g., 64m). Larger secrets are spilled to an encrypted temporary file.",
+        value_parser = human_size::parse,
+    )]
+    pub max_memory: Option<i64>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Reply to the secret with the content of this file, if the sender allowed a reply (`send --allow-reply`). The reply is encrypted with a key chosen by the sender, only the sender can open it."
+    )]
+    pub reply_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "24h",
+        help = "Time after the reply vanishes.",
+        value_parser = human_duration::parse,
+    )]
+    pub reply_ttl: Duration,
 }
 
 impl GetArgs {
@@ -104,6 +250,106 @@ impl GetArgs {
             return Err(anyhow!("The --key option cannot be used with --ask-key."));
         }
 
+        if self.pin.is_some() && self.ask_pin {
+            return Err(anyhow!("The --pin option cannot be used with --ask-pin."));
+        }
+
+        if (self.pin.is_some() || self.ask_pin) && self.from_file.is_some() {
+            return Err(anyhow!("The --pin option cannot be used with --from-file."));
+        }
+
+        if !self.shares.is_empty() && (self.key.is_some() || self.ask_key) {
+            return Err(anyhow!(
+                "The --share option cannot be used with --key or --ask-key."
+            ));
+        }
+
+        if self.max_extract_size <= 0 {
+            return Err(anyhow!(
+                "The --max-extract-size option must be greater than zero."
+            ));
+        }
+
+        if self.max_extract_entries == 0 || self.max_compression_ratio == 0 {
+            return Err(anyhow!(
+                "The --max-extract-entries and --max-compression-ratio options must be greater than zero."
+            ));
+        }
+
+        if let Some(ref hash) = self.verify_hash
+            && (hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(anyhow!(
+                "The --verify-hash option requires a hex encoded SHA-256 hash."
+            ));
+        }
+
+        if self.limit_rate.is_some_and(|rate| rate <= 0) {
+            return Err(anyhow!(
+                "The --limit-rate option must be greater than zero."
+            ));
+        }
+
+        if self.max_memory.is_some_and(|max_memory| max_memory <= 0) {
+            return Err(anyhow!(
+                "The --max-memory option must be greater than zero."
+            ));
+        }
+
+        if self.to_sops.is_some()
+            && (self.to_stdout
+                || self.filename.is_some()
+                || self.extract
+                || self.output_dir.is_some()
+                || self.from_file.is_some())
+        {
+            return Err(anyhow!(
+                "The --to-sops option cannot be used with --to-stdout, --filename, --extract, --output-dir or --from-file."
+            ));
+        }
+
+        if self.from_file.is_some() {
+            if self.to_stdout || self.filename.is_some() || self.extract {
+                return Err(anyhow!(
+                    "The --from-file option cannot be used with --to-stdout, --filename or --extract."
+                ));
+            }
+
+            if self.key.is_some()
+                || self.ask_key
+                || !self.shares.is_empty()
+                || self.verify_hash.is_some()
+            {
+                return Err(anyhow!(
+                    "The --from-file option cannot be used with --key, --ask-key, --share or --verify-hash."
+                ));
+            }
+        }
+
+        if let Some(ref reply_file) = self.reply_file {
+            if self.from_file.is_some() {
+                return Err(anyhow!(
+                    "The --reply-file option cannot be used with --from-file."
+                ));
+            }
+
+            // the secret is gone after retrieval, so the reply has to be readable beforehand
+            if !reply_file.is_file() {
+                return Err(anyhow!(
+                    "Reply file '{}' does not exist or is not a file",
+                    reply_file.display()
+                ));
+            }
+        }
+
+        if self.reply_ttl.is_zero() {
+            return Err(anyhow!("The --reply-ttl option must be greater than zero."));
+        }
+
+        if self.parallel == 0 {
+            return Err(anyhow!("The --parallel option must be greater than zero."));
+        }
+
         Ok(())
     }
 
@@ -126,9 +372,21 @@ impl GetArgs {
     }
 
     pub fn secret_url(&self) -> Result<Url> {
-        let mut url = self.link.clone();
+        let mut url = self.link.clone().ok_or(anyhow!("No link provided"))?;
 
-        if url.fragment().is_some() {
+        if !self.shares.is_empty() {
+            return Ok(shamir::join_link(&url, &self.shares)?);
+        }
+
+        if shamir::is_split_link(&url) {
+            return Err(anyhow!(
+                "The key of this link was split into shares, use --share to provide them."
+            ));
+        }
+
+        let has_key =
+            LinkFragment::from_url(&url)?.is_some_and(|fragment| fragment.key().is_some());
+        if has_key {
             if self.key.is_some() {
                 return Err(anyhow!(
                     "The URL already contains a fragment, but a key was provided as an argument."
@@ -155,18 +413,61 @@ impl GetArgs {
     #[cfg(test)]
     pub fn builder(link: &str) -> Self {
         Self {
-            link: Url::parse(link).expect("Invalid URL"),
+            link: Some(Url::parse(link).expect("Invalid URL")),
+            from_file: None,
+            parallel: 4,
             key: None,
             to_stdout: false,
+            force: false,
             filename: None,
             extract: false,
             output_dir: None,
+            on_conflict: OnConflict::Rename,
+            to_sops: None,
+            sops: "sops".to_string(),
             passphrase: None,
             ask_key: false,
+            shares: Vec::new(),
             ask_passphrase: false,
+            pin: None,
+            ask_pin: false,
+            verify_hash: None,
+            max_extract_size: 1024 * 1024 * 1024,
+            max_extract_entries: 10_000,
+            max_compression_ratio: 100,
+            limit_rate: None,
+            max_memory: None,
+            reply_file: None,
+            reply_ttl: Duration::from_secs(24 * 60 * 60), // 24h
         }
     }
 
+    #[cfg(test)]
+    pub fn with_reply_file(mut self, path: &Path) -> Self {
+        self.reply_file = Some(path.to_path_buf());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_to_sops(mut self, target: &str, sops: &str) -> Self {
+        self.to_sops = Some(target.parse().expect("Invalid SOPS reference"));
+        self.sops = sops.to_string();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_max_memory(mut self, max_memory: i64) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_from_file(mut self, path: &Path) -> Self {
+        self.link = None;
+        self.from_file = Some(path.to_path_buf());
+        self
+    }
+
     #[cfg(test)]
     pub fn with_to_stdout(mut self) -> Self {
         self.to_stdout = true;
@@ -185,6 +486,12 @@ impl GetArgs {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_share(mut self, share: &str) -> Self {
+        self.shares.push(share.parse().expect("Invalid share"));
+        self
+    }
+
     #[cfg(test)]
     pub fn with_extract(mut self) -> Self {
         self.extract = true;
@@ -197,6 +504,12 @@ impl GetArgs {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_on_conflict(mut self, on_conflict: OnConflict) -> Self {
+        self.on_conflict = on_conflict;
+        self
+    }
+
     #[cfg(test)]
     pub fn with_passphrase(mut self, passphrase: &str) -> Self {
         self.passphrase = Some(passphrase.to_string());
@@ -214,6 +527,36 @@ impl GetArgs {
         self.ask_passphrase = true;
         self
     }
+
+    #[cfg(test)]
+    pub fn with_pin(mut self, pin: &str) -> Self {
+        self.pin = Some(pin.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_ask_pin(mut self) -> Self {
+        self.ask_pin = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_max_extract_size(mut self, size: i64) -> Self {
+        self.max_extract_size = size;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_max_extract_entries(mut self, entries: usize) -> Self {
+        self.max_extract_entries = entries;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_verify_hash(mut self, hash: &str) -> Self {
+        self.verify_hash = Some(hash.to_string());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -248,6 +591,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_error_from_file_with_to_stdout() -> Result<()> {
+        let args = GetArgs::builder("https://example.com/s/test#key")
+            .with_from_file(Path::new("urls.txt"))
+            .with_to_stdout();
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+        let error_msg = result.unwrap_err().to_string();
+        assert!(
+            error_msg.contains("--from-file option cannot be used with --to-stdout"),
+            "Error message doesn't contain expected text: {}",
+            error_msg
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_error_from_file_with_key() -> Result<()> {
+        let args = GetArgs::builder("https://example.com/s/test#key")
+            .with_from_file(Path::new("urls.txt"))
+            .with_key("key");
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+        let error_msg = result.unwrap_err().to_string();
+        assert!(
+            error_msg.contains("--from-file option cannot be used with --key"),
+            "Error message doesn't contain expected text: {}",
+            error_msg
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_error_from_file_with_reply_file() {
+        let args = GetArgs::builder("https://example.com/s/test#key")
+            .with_from_file(Path::new("urls.txt"))
+            .with_reply_file(Path::new("Cargo.toml"));
+
+        let result = args.validate();
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("--reply-file option cannot be used")),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_error_nonexistent_reply_file() {
+        let args = GetArgs::builder("https://example.com/s/test#key")
+            .with_reply_file(Path::new("/nonexistent/reply.txt"));
+
+        let result = args.validate();
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("does not exist")),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
     #[test]
     fn test_validate_error_extract_with_filename() -> Result<()> {
         let args = GetArgs::builder("https://example.com/s/test#key")
@@ -414,6 +830,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_pin_conflicting() {
+        let args = GetArgs::builder("https://example.com/s/test")
+            .with_pin("123456")
+            .with_ask_pin();
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--pin option cannot be used with --ask-pin.")
+        );
+    }
+
+    #[test]
+    fn test_validate_to_sops_conflicting() {
+        let link = "https://example.com/s/test#key";
+        let combinations = [
+            GetArgs::builder(link).with_to_stdout(),
+            GetArgs::builder(link).with_filename("secret.txt"),
+            GetArgs::builder(link).with_extract(),
+        ];
+
+        for args in combinations {
+            let result = args
+                .with_to_sops("secrets.enc.yaml#password", "sops")
+                .validate();
+            assert!(
+                result.is_err_and(|e| e.to_string().contains("--to-sops option cannot")),
+                "Other outputs should be rejected with --to-sops"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_max_extract_size_invalid() {
+        let args = GetArgs::builder("https://example.com/s/test#key").with_max_extract_size(0);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+    }
+
+    #[test]
+    fn test_validate_max_memory_invalid() {
+        let args = GetArgs::builder("https://example.com/s/test#key").with_max_memory(0);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+    }
+
+    #[test]
+    fn test_validate_max_extract_entries_invalid() {
+        let args = GetArgs::builder("https://example.com/s/test#key").with_max_extract_entries(0);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+    }
+
+    #[test]
+    fn test_validate_verify_hash_invalid() {
+        let args = GetArgs::builder("https://example.com/s/test#key").with_verify_hash("abc123");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--verify-hash option requires a hex encoded SHA-256 hash")
+        );
+    }
+
+    #[test]
+    fn test_validate_verify_hash_valid() -> Result<()> {
+        let args = GetArgs::builder("https://example.com/s/test#key")
+            .with_verify_hash("2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824");
+        args.validate()?;
+        Ok(())
+    }
+
     #[test]
     fn test_secret_url_with_fragment_in_url() {
         let args = GetArgs::builder("https://example.com/s/test#mykey");
@@ -494,4 +992,42 @@ mod tests {
         let url = args.secret_url().expect("Failed to get secret URL");
         assert_eq!(url.fragment(), Some("key-with_special.chars"));
     }
+
+    #[test]
+    fn test_secret_url_with_shares() -> Result<()> {
+        let link = Url::parse(
+            "https://example.com/s/test#AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8:hash",
+        )?;
+        let (link_without_key, shares) = shamir::split_link(&link, 2, 3)?;
+
+        let args = GetArgs::builder(link_without_key.as_str())
+            .with_share(&shares[0].to_string())
+            .with_share(&shares[2].to_string());
+        assert_eq!(args.secret_url()?, link, "Key should be joined from shares");
+        Ok(())
+    }
+
+    #[test]
+    fn test_secret_url_error_split_link_without_shares() {
+        let args = GetArgs::builder("https://example.com/s/test#:hash");
+        let result = args.secret_url();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(result.unwrap_err().to_string().contains("use --share"));
+    }
+
+    #[test]
+    fn test_validate_error_share_with_key() {
+        let args = GetArgs::builder("https://example.com/s/test#:hash")
+            .with_share("2.1.AAAA")
+            .with_key("key");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--share option cannot be used with --key")
+        );
+    }
 }
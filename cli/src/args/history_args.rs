@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
+use url::Url;
+
+/// Represents the arguments for the `history` command.
+#[derive(Debug, Clone, Parser)]
+pub struct HistoryArgs {
+    #[arg(
+        long,
+        global = true,
+        env = "HAKANAI_HISTORY_FILE",
+        value_name = "FILE",
+        help = "History file to use instead of the default location in the user data directory."
+    )]
+    pub history_file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: HistoryCommand,
+}
+
+/// Represents the subcommands of the `history` command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum HistoryCommand {
+    /// List the secrets recorded in the history.
+    List(HistoryListArgs),
+
+    /// Delete all recorded entries of the history.
+    Clear(HistoryClearArgs),
+}
+
+/// Represents the arguments for the `history list` command.
+#[derive(Debug, Clone, Parser)]
+pub struct HistoryListArgs {
+    #[arg(
+        long,
+        help = "Print the history as JSON instead of a table.",
+        default_value_t = false
+    )]
+    pub json: bool,
+
+    #[arg(
+        long,
+        help = "Ask the server which secrets were not retrieved yet (requires the token used for sending).",
+        default_value_t = false
+    )]
+    pub check: bool,
+
+    #[arg(
+        short,
+        long,
+        default_value = "http://localhost:8080",
+        env = "HAKANAI_SERVER",
+        help = "Hakanai Server URL to check the secrets against (eg. https://hakanai.link)."
+    )]
+    pub server: Url,
+
+    #[arg(
+        env = "HAKANAI_TOKEN",
+        help = "Token used for sending the secrets (environment variable only)."
+    )]
+    pub token: Option<String>,
+
+    #[arg(
+        long = "token-file",
+        help = "File containing the token used for sending the secrets. Environment variable HAKANAI_TOKEN takes precedence.",
+        value_name = "TOKEN_FILE"
+    )]
+    pub token_file: Option<String>,
+}
+
+impl HistoryListArgs {
+    /// Get the processed token, reading from file if needed
+    pub fn token(&self) -> Result<Option<String>> {
+        if let Some(path) = &self.token_file {
+            return match std::fs::read_to_string(path) {
+                Ok(content) => Ok(Some(content.trim().to_string())),
+                Err(e) => Err(anyhow!("Failed to read token file '{path}': {e}")),
+            };
+        }
+
+        Ok(self.token.clone())
+    }
+}
+
+/// Represents the arguments for the `history clear` command.
+#[derive(Debug, Clone, Parser)]
+pub struct HistoryClearArgs {
+    #[arg(
+        short,
+        long,
+        help = "Clear without asking for confirmation.",
+        default_value_t = false
+    )]
+    pub yes: bool,
+}
@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
+use url::Url;
+
+use hakanai_lib::link::LinkFragment;
+
+/// Maximum length of names of Kubernetes objects and keys of secret data.
+const MAX_NAME_LENGTH: usize = 253;
+
+/// Maximum length of names of Kubernetes namespaces.
+const MAX_NAMESPACE_LENGTH: usize = 63;
+
+/// Represents the arguments for the `k8s` command.
+#[derive(Debug, Clone, Parser)]
+pub struct K8sArgs {
+    #[command(subcommand)]
+    pub command: K8sCommand,
+}
+
+/// Represents the Kubernetes subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum K8sCommand {
+    /// Retrieve a secret and store it as Kubernetes Secret, creating or updating it.
+    CreateSecret(K8sCreateSecretArgs),
+}
+
+/// Represents the arguments for the `k8s create-secret` command.
+#[derive(Debug, Clone, Parser)]
+pub struct K8sCreateSecretArgs {
+    pub link: Url,
+
+    #[arg(
+        short,
+        long,
+        help = "Optional base64 encoded secret key to use for decryption if not part of the URL."
+    )]
+    pub key: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "key",
+        help = "Ask for decryption key if the URL does not contain a key in fragment."
+    )]
+    pub ask_key: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "If the secret is protected by a passphrase, provide it here."
+    )]
+    pub passphrase: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "passphrase",
+        help = "Ask for passphrase protecting the secret."
+    )]
+    pub ask_passphrase: bool,
+
+    #[arg(long, help = "PIN sent to you for secrets protected by a PIN.")]
+    pub pin: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "pin",
+        help = "Ask for the PIN protecting the secret."
+    )]
+    pub ask_pin: bool,
+
+    #[arg(long, help = "Name of the Kubernetes Secret to create or update.")]
+    pub name: String,
+
+    #[arg(
+        short,
+        long,
+        help = "Namespace of the Kubernetes Secret, the namespace of the current context if not specified."
+    )]
+    pub namespace: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        help = "Key of the secret in the data of the Kubernetes Secret. Defaults to the filename for files and 'value' for text."
+    )]
+    pub data_key: Option<String>,
+
+    #[arg(
+        long = "type",
+        default_value = "Opaque",
+        help = "Type of the Kubernetes Secret (e.g. kubernetes.io/basic-auth)."
+    )]
+    pub secret_type: String,
+
+    #[arg(long, help = "Kubeconfig context to use instead of the current one.")]
+    pub context: Option<String>,
+
+    #[arg(
+        long,
+        help = "Path to the kubeconfig file, kubectl defaults apply if not specified ($KUBECONFIG or ~/.kube/config)."
+    )]
+    pub kubeconfig: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "kubectl",
+        env = "HAKANAI_KUBECTL",
+        help = "kubectl binary used to apply the Kubernetes Secret."
+    )]
+    pub kubectl: String,
+}
+
+impl K8sCreateSecretArgs {
+    pub fn validate(&self) -> Result<()> {
+        if !is_dns_subdomain(&self.name) {
+            return Err(anyhow!(
+                "Invalid name '{}': must consist of lowercase alphanumeric characters, '-' or '.' and start and end with an alphanumeric character",
+                self.name
+            ));
+        }
+
+        if let Some(ref namespace) = self.namespace
+            && !is_dns_label(namespace)
+        {
+            return Err(anyhow!(
+                "Invalid namespace '{namespace}': must consist of lowercase alphanumeric characters or '-' and start and end with an alphanumeric character"
+            ));
+        }
+
+        if let Some(ref data_key) = self.data_key
+            && !is_valid_data_key(data_key)
+        {
+            return Err(anyhow!(
+                "Invalid data key '{data_key}': must consist of alphanumeric characters, '-', '_' or '.'"
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn secret_url(&self) -> Result<Url> {
+        let mut url = self.link.clone();
+
+        let has_key =
+            LinkFragment::from_url(&url)?.is_some_and(|fragment| fragment.key().is_some());
+        if has_key {
+            if self.key.is_some() {
+                return Err(anyhow!(
+                    "The URL already contains a fragment, but a key was provided as an argument."
+                ));
+            }
+
+            return Ok(url);
+        }
+
+        let key = if self.ask_key {
+            rpassword::prompt_password("Enter decryption key: ")?
+        } else {
+            self.key.clone().unwrap_or_default()
+        };
+
+        if key.is_empty() {
+            return Err(anyhow!("No decryption key provided"));
+        }
+
+        url.set_fragment(Some(&key));
+        Ok(url)
+    }
+
+    #[cfg(test)]
+    pub fn builder(link: &str, name: &str) -> Self {
+        Self {
+            link: Url::parse(link).expect("Invalid URL"),
+            key: None,
+            ask_key: false,
+            passphrase: None,
+            ask_passphrase: false,
+            pin: None,
+            ask_pin: false,
+            name: name.to_string(),
+            namespace: None,
+            data_key: None,
+            secret_type: "Opaque".to_string(),
+            context: None,
+            kubeconfig: None,
+            kubectl: "kubectl".to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_data_key(mut self, data_key: &str) -> Self {
+        self.data_key = Some(data_key.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_context(mut self, context: &str) -> Self {
+        self.context = Some(context.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_kubectl(mut self, kubectl: &str) -> Self {
+        self.kubectl = kubectl.to_string();
+        self
+    }
+}
+
+/// Returns whether the key can be used in the data of a Kubernetes Secret.
+pub(crate) fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_NAME_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Checks for a DNS subdomain as defined in RFC 1123, as required for names of Secrets.
+fn is_dns_subdomain(name: &str) -> bool {
+    name.len() <= MAX_NAME_LENGTH && name.split('.').all(is_label)
+}
+
+/// Checks for a DNS label as defined in RFC 1123, as required for names of namespaces.
+fn is_dns_label(name: &str) -> bool {
+    name.len() <= MAX_NAMESPACE_LENGTH && is_label(name)
+}
+
+fn is_label(label: &str) -> bool {
+    let alphanumeric = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+
+    label.starts_with(alphanumeric)
+        && label.ends_with(alphanumeric)
+        && label.chars().all(|c| alphanumeric(c) || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINK: &str = "https://example.com/s/test123#key";
+
+    #[test]
+    fn test_validate_valid() -> Result<()> {
+        K8sCreateSecretArgs::builder(LINK, "db-credentials.v2")
+            .with_namespace("prod")
+            .with_data_key("tls.crt")
+            .validate()
+    }
+
+    #[test]
+    fn test_validate_invalid_name() {
+        for name in ["", "DB", "db_password", "-db", "db.", "db..password"] {
+            let result = K8sCreateSecretArgs::builder(LINK, name).validate();
+            assert!(result.is_err(), "Name '{name}' should be rejected");
+        }
+
+        let too_long = "a".repeat(MAX_NAME_LENGTH + 1);
+        assert!(
+            K8sCreateSecretArgs::builder(LINK, &too_long)
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_namespace() {
+        for namespace in ["", "Prod", "prod.eu", "prod-"] {
+            let result = K8sCreateSecretArgs::builder(LINK, "db")
+                .with_namespace(namespace)
+                .validate();
+            assert!(
+                result.is_err(),
+                "Namespace '{namespace}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_invalid_data_key() {
+        for data_key in ["", "my key", "../passwd", "key/value"] {
+            let result = K8sCreateSecretArgs::builder(LINK, "db")
+                .with_data_key(data_key)
+                .validate();
+            assert!(result.is_err(), "Data key '{data_key}' should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_secret_url_with_key_argument() -> Result<()> {
+        let mut args = K8sCreateSecretArgs::builder("https://example.com/s/test123", "db");
+        args.key = Some("key".to_string());
+
+        assert_eq!(
+            args.secret_url()?.as_str(),
+            "https://example.com/s/test123#key"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_secret_url_with_key_in_url_and_argument() {
+        let mut args = K8sCreateSecretArgs::builder(LINK, "db");
+        args.key = Some("other".to_string());
+
+        assert!(args.secret_url().is_err());
+    }
+
+    #[test]
+    fn test_secret_url_without_key() {
+        let args = K8sCreateSecretArgs::builder("https://example.com/s/test123", "db");
+
+        assert!(args.secret_url().is_err());
+    }
+}
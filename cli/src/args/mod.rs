@@ -1,9 +1,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod admin_args;
 mod get_args;
+mod history_args;
+mod k8s_args;
+mod offline_args;
 mod send_args;
 mod token_args;
+mod version_args;
 
-pub use get_args::GetArgs;
+pub use admin_args::{AdminArgs, AdminCommand, PurgeArgs, StatsArgs};
+pub use get_args::{GetArgs, OnConflict};
+pub use history_args::{HistoryArgs, HistoryClearArgs, HistoryCommand, HistoryListArgs};
+pub(crate) use k8s_args::is_valid_data_key;
+pub use k8s_args::{K8sArgs, K8sCommand, K8sCreateSecretArgs};
+pub use offline_args::{DecryptArgs, EncryptArgs};
 pub use send_args::SendArgs;
-pub use token_args::TokenArgs;
+pub(crate) use send_args::{MIN_PASSPHRASE_LENGTH, parse_country_code};
+pub use token_args::{TokenArgs, TokenCommand, TokenInfoArgs};
+pub use version_args::VersionArgs;
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use zeroize::Zeroizing;
+
+/// Represents the arguments for the `encrypt` command.
+#[derive(Debug, Clone, Parser)]
+pub struct EncryptArgs {
+    #[arg(
+        short = 'f',
+        long = "file",
+        value_name = "FILE",
+        help = "File to read the secret from. If not specified, reads from stdin."
+    )]
+    pub file: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        help = "File to write the encrypted envelope to (e.g. blob.hakanai)."
+    )]
+    pub out: PathBuf,
+
+    #[arg(long, help = "Overwrite the output file if it already exists.")]
+    pub overwrite: bool,
+}
+
+/// Represents the arguments for the `decrypt` command.
+#[derive(Debug, Clone, Parser)]
+pub struct DecryptArgs {
+    #[arg(value_name = "FILE", help = "File containing the encrypted envelope.")]
+    pub envelope: PathBuf,
+
+    #[arg(
+        short,
+        long,
+        required_unless_present = "ask_key",
+        help = "Key printed by the encrypt command (key:hash)."
+    )]
+    pub key: Option<String>,
+
+    #[arg(long, conflicts_with = "key", help = "Ask for the key.")]
+    pub ask_key: bool,
+
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Save the secret to this file. If not specified, files are saved under their original name and text is printed to stdout."
+    )]
+    pub out: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Output the secret to stdout even if it is a file. This is useful for piping the output to other commands."
+    )]
+    pub to_stdout: bool,
+
+    #[arg(
+        long,
+        help = "Output binary data or control characters even if stdout is a terminal, which could mess up the terminal."
+    )]
+    pub force: bool,
+}
+
+impl DecryptArgs {
+    /// Returns the key given as argument or asks for it if requested.
+    pub fn key(&self) -> Result<Zeroizing<String>> {
+        let key = if self.ask_key {
+            Zeroizing::new(rpassword::prompt_password("Enter decryption key: ")?)
+        } else {
+            Zeroizing::new(self.key.clone().unwrap_or_default())
+        };
+
+        if key.is_empty() {
+            return Err(anyhow!("No decryption key provided"));
+        }
+
+        Ok(key)
+    }
+}
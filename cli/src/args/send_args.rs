@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -10,12 +11,56 @@ use url::Url;
 use hakanai_lib::models::{CountryCode, SecretRestrictions};
 use zeroize::Zeroizing;
 
-use hakanai_lib::utils::ip;
+use hakanai_lib::utils::{asn, human_duration, human_size, ip, wordlist};
+
+use crate::archive::ArchiveFormat;
+use crate::integrations::VaultSource;
 
 #[cfg(test)]
 use hakanai_lib::utils::test::MustParse;
 
-const MIN_PASSPHRASE_LENGTH: usize = 8;
+pub(crate) const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+/// Value of `--require-passphrase` to generate a passphrase.
+const AUTO_PASSPHRASE: &str = "auto";
+
+/// Threshold and number of shares of `--split K/N`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySplit {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+impl FromStr for KeySplit {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (threshold, count) = s
+            .split_once('/')
+            .ok_or_else(|| format!("'{s}' is not in the format K/N"))?;
+        let threshold = threshold
+            .trim()
+            .parse::<u8>()
+            .map_err(|e| format!("invalid threshold '{threshold}': {e}"))?;
+        let count = count
+            .trim()
+            .parse::<u8>()
+            .map_err(|e| format!("invalid number of shares '{count}': {e}"))?;
+
+        if threshold < 2 || threshold > count {
+            return Err(format!(
+                "the threshold must be between 2 and the number of shares, got {threshold}/{count}"
+            ));
+        }
+
+        Ok(Self { threshold, count })
+    }
+}
+
+/// Parses a country code of `--allow-country`, suggesting the code the user probably meant.
+pub(crate) fn parse_country_code(s: &str) -> std::result::Result<CountryCode, String> {
+    CountryCode::parse_assigned(s).map_err(|e| e.message)
+}
 
 /// Represents the arguments for the `send` command.
 #[derive(Debug, Clone, Parser)]
@@ -34,7 +79,7 @@ pub struct SendArgs {
         default_value = "24h",
         env = "HAKANAI_TTL",
         help = "Time after the secret vanishes.",
-        value_parser = humantime::parse_duration,
+        value_parser = human_duration::parse,
     )]
     pub ttl: Duration,
 
@@ -54,11 +99,72 @@ pub struct SendArgs {
     #[arg(
         short = 'f',
         long = "file",
-        help = "File to read the secret from. If not specified, reads from stdin. This can be specified multiple times to send multiple files.",
+        help = "File or directory to read the secret from. If not specified, reads from stdin. This can be specified multiple times to send multiple files. Directories are archived recursively.",
         value_name = "FILE"
     )]
     pub files: Option<Vec<String>>,
 
+    #[arg(
+        long,
+        help = "Send multiple secrets defined in a YAML manifest. Each entry defines the text or files, TTL, restrictions and a label. Options not set in an entry are taken from the command line.",
+        value_name = "MANIFEST"
+    )]
+    pub batch: Option<String>,
+
+    #[arg(
+        long,
+        help = "Print the generated links of a batch as JSON instead of a table."
+    )]
+    pub json: bool,
+
+    #[arg(
+        long,
+        help = "Compose the secret in the editor configured by $VISUAL or $EDITOR instead of reading it from stdin."
+    )]
+    pub edit: bool,
+
+    #[arg(
+        long,
+        help = "Ask for the secret with hidden input instead of reading it from stdin. This keeps the secret out of shell history and terminal scrollback."
+    )]
+    pub prompt: bool,
+
+    #[arg(
+        long,
+        help = "Treat the secret as template and substitute {{env:VAR}} and {{file:path}} placeholders before encryption, e.g. to render a kubeconfig without temporary files."
+    )]
+    pub render: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH#KEY",
+        help = "Read the secret from a field in HashiCorp Vault or OpenBao (e.g. secret/app#password) instead of stdin. The vault CLI is used with its usual configuration (VAULT_ADDR, VAULT_TOKEN)."
+    )]
+    pub from_vault: Option<VaultSource>,
+
+    #[arg(
+        long,
+        default_value = "vault",
+        env = "HAKANAI_VAULT",
+        help = "vault binary used by --from-vault."
+    )]
+    pub vault: String,
+
+    #[arg(
+        long = "exclude",
+        help = "Exclude files matching this pattern (.gitignore style) when sending directories. This can be specified multiple times.",
+        value_name = "PATTERN"
+    )]
+    pub excludes: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        default_value = "zip",
+        help = "Archive format used when sending multiple files or directories.",
+        value_name = "FORMAT"
+    )]
+    pub archive_format: ArchiveFormat,
+
     #[arg(
         short,
         long,
@@ -72,12 +178,66 @@ pub struct SendArgs {
     )]
     pub filename: Option<String>,
 
+    #[arg(
+        short = 'd',
+        long,
+        help = "Short description of the secret shown to the recipient. The description is encrypted along with the secret."
+    )]
+    pub description: Option<String>,
+
+    #[arg(
+        long,
+        help = "MIME content type of the secret (e.g. text/plain, application/pdf). The content type is encrypted along with the secret.",
+        value_name = "MIME_TYPE"
+    )]
+    pub content_type: Option<String>,
+
+    #[arg(
+        long,
+        help = "Embed a SHA-256 checksum of the secret, which is verified after decryption. The checksum is printed to allow out-of-band verification with `get --verify-hash`."
+    )]
+    pub checksum: bool,
+
+    #[arg(
+        long,
+        env = "HAKANAI_PAD",
+        help = "Pad small secrets to a size class (1KB, 10KB or 100KB) before encryption to hide their size from the server and network observers."
+    )]
+    pub pad: bool,
+
+    #[arg(
+        long,
+        env = "HAKANAI_SHORT_ALIAS",
+        help = "Request a short human-friendly link (e.g. /s/blue-otter-42) if supported by the server."
+    )]
+    pub short_alias: bool,
+
+    #[arg(
+        long,
+        help = "Allow the recipient to reply with a secret of their own (e.g. `get --reply-file`). A private link to the reply is printed, it can be opened once the recipient replied."
+    )]
+    pub allow_reply: bool,
+
+    #[arg(
+        long,
+        help = "Scan the secret for well-known credential formats (e.g. AWS keys, private keys, GitHub tokens) and print a summary before sending."
+    )]
+    pub classify: bool,
+
     #[arg(
         long,
         help = "Does not include the key in the URL fragment, but instead prints it to stdout. This is useful for sharing the key separately."
     )]
     pub separate_key: bool,
 
+    #[arg(
+        long,
+        value_name = "K/N",
+        help = "Split the key into N shares of which K are required to open the link (e.g. 2/3). The key is not included in the URL, the shares are printed separately and should be shared via different channels.",
+        value_parser = KeySplit::from_str,
+    )]
+    pub split: Option<KeySplit>,
+
     #[arg(
         short = 'q',
         long = "qr-code",
@@ -89,18 +249,18 @@ pub struct SendArgs {
     #[arg(
         long = "allow-ip",
         env = "HAKANAI_ALLOWED_IPS",
-        help = "Comma-separated list of IP addresses (CIDR notation) that are allowed to access the secret.",
+        help = "Comma-separated list of IP addresses (CIDR notation) that are allowed to access the secret. Use @file for a file with one range per line; hostnames are resolved when sending.",
         value_delimiter = ',',
-        value_parser = ip::parse_ipnet,
+        value_parser = ip::parse_ip_range_source,
     )]
-    pub allowed_ips: Option<Vec<ipnet::IpNet>>,
+    pub allowed_ips: Option<Vec<ip::IpRangeSource>>,
 
     #[arg(
         long = "allow-country",
         env = "HAKANAI_ALLOWED_COUNTRIES",
         help = "Comma-separated list of country codes (ISO 3166-1 alpha-2) that are allowed to access the secret.",
         value_delimiter = ',',
-        value_parser = CountryCode::from_str
+        value_parser = parse_country_code
     )]
     pub allowed_countries: Option<Vec<CountryCode>>,
 
@@ -112,16 +272,77 @@ pub struct SendArgs {
     )]
     pub allowed_asns: Option<Vec<u32>>,
 
+    #[arg(
+        long = "allow-org",
+        alias = "allowed-org",
+        env = "HAKANAI_ALLOWED_ORGS",
+        help = "Comma-separated list of well-known organizations (e.g. Cloudflare) whose autonomous systems are allowed to access the secret.",
+        value_delimiter = ',',
+        value_parser = asn::parse_org,
+    )]
+    pub allowed_orgs: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_LIMIT_RATE",
+        help = "Limit the transfer rate in bytes per second (e.g., 500k, 2m).",
+        value_parser = human_size::parse,
+    )]
+    pub limit_rate: Option<i64>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_MAX_MEMORY",
+        help = "Maximum size of encrypted data kept in memory while transferring (e.g., 64m). Larger secrets are spilled to an encrypted temporary file.",
+        value_parser = human_size::parse,
+    )]
+    pub max_memory: Option<i64>,
+
     #[arg(
         short = 'p',
         long,
-        help = "If set, the passphrase will be required to access the secret. The passphrase is not part of the URL and must be shared separately.",
+        help = "If set, the passphrase will be required to access the secret. The passphrase is not part of the URL and must be shared separately. Use 'auto' to generate a passphrase of random words.",
         env = "HAKANAI_REQUIRE_PASSPHRASE"
     )]
     pub require_passphrase: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "RECIPIENT",
+        env = "HAKANAI_PIN_TO",
+        help = "Require a PIN to access the secret, which the server generates and delivers to RECIPIENT (e.g. a phone number) via its configured out-of-band channel."
+    )]
+    pub pin_to: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_HISTORY",
+        help = "Record the link (without key), creation time, TTL and restrictions of sent secrets in the local history, see `hakanai history`."
+    )]
+    pub history: bool,
+
+    #[arg(
+        long,
+        env = "HAKANAI_HISTORY_FILE",
+        value_name = "FILE",
+        help = "History file to use instead of the default location in the user data directory."
+    )]
+    pub history_file: Option<PathBuf>,
 }
 
 impl SendArgs {
+    /// Replaces `--require-passphrase auto` with a generated passphrase and returns it.
+    pub fn generate_passphrase(&mut self) -> Option<Zeroizing<String>> {
+        if self.require_passphrase.as_deref() != Some(AUTO_PASSPHRASE) {
+            return None;
+        }
+
+        let passphrase =
+            Zeroizing::new(wordlist::generate_passphrase(wordlist::DEFAULT_WORD_COUNT));
+        self.require_passphrase = Some(passphrase.to_string());
+        Some(passphrase)
+    }
+
     pub fn validate(&self) -> Result<()> {
         if let Some(passphrase) = &self.require_passphrase
             && passphrase.trim().chars().count() < MIN_PASSPHRASE_LENGTH
@@ -131,6 +352,80 @@ impl SendArgs {
             )));
         }
 
+        if self.edit && self.files.is_some() {
+            return Err(anyhow!("The --edit option cannot be used with --file."));
+        }
+
+        if self.prompt && self.files.is_some() {
+            return Err(anyhow!("The --prompt option cannot be used with --file."));
+        }
+
+        if self.prompt && self.edit {
+            return Err(anyhow!("The --prompt option cannot be used with --edit."));
+        }
+
+        if self.render && (self.files.is_some() || self.batch.is_some()) {
+            return Err(anyhow!(
+                "The --render option cannot be used with --file or --batch."
+            ));
+        }
+
+        if self.from_vault.is_some()
+            && (self.files.is_some()
+                || self.edit
+                || self.prompt
+                || self.render
+                || self.batch.is_some())
+        {
+            return Err(anyhow!(
+                "The --from-vault option cannot be used with --file, --edit, --prompt, --render or --batch."
+            ));
+        }
+
+        if self.excludes.is_some() && self.files.is_none() {
+            return Err(anyhow!("The --exclude option requires --file."));
+        }
+
+        if self.limit_rate.is_some_and(|rate| rate <= 0) {
+            return Err(anyhow!(
+                "The --limit-rate option must be greater than zero."
+            ));
+        }
+
+        if self.max_memory.is_some_and(|max_memory| max_memory <= 0) {
+            return Err(anyhow!(
+                "The --max-memory option must be greater than zero."
+            ));
+        }
+
+        if self.batch.is_some() {
+            if self.files.is_some() || self.edit || self.prompt {
+                return Err(anyhow!(
+                    "The --batch option cannot be used with --file, --edit or --prompt."
+                ));
+            }
+
+            if self.filename.is_some() || self.separate_key || self.print_qr_code {
+                return Err(anyhow!(
+                    "The --batch option cannot be used with --filename, --separate-key or --qr-code."
+                ));
+            }
+
+            if self.allow_reply {
+                return Err(anyhow!(
+                    "The --batch option cannot be used with --allow-reply."
+                ));
+            }
+        } else if self.json {
+            return Err(anyhow!("The --json option requires --batch."));
+        }
+
+        if self.split.is_some() && (self.separate_key || self.batch.is_some()) {
+            return Err(anyhow!(
+                "The --split option cannot be used with --separate-key or --batch."
+            ));
+        }
+
         Ok(())
     }
 
@@ -138,15 +433,23 @@ impl SendArgs {
         let mut restrictions = SecretRestrictions::default();
 
         if let Some(allowed_ips) = &self.allowed_ips {
-            restrictions = restrictions.with_allowed_ips(allowed_ips.clone());
+            // files and hostnames are replaced by their ranges when sending
+            let allowed_ips = allowed_ips
+                .iter()
+                .filter_map(|source| match source {
+                    ip::IpRangeSource::Net(net) => Some(*net),
+                    _ => None,
+                })
+                .collect();
+            restrictions = restrictions.with_allowed_ips(allowed_ips);
         }
 
         if let Some(allowed_countries) = &self.allowed_countries {
             restrictions = restrictions.with_allowed_countries(allowed_countries.clone());
         }
 
-        if let Some(allowed_asns) = &self.allowed_asns {
-            restrictions = restrictions.with_allowed_asns(allowed_asns.clone());
+        if self.allowed_asns.is_some() || self.allowed_orgs.is_some() {
+            restrictions = restrictions.with_allowed_asns(self.allowed_asns_with_orgs());
         }
 
         if let Some(ref passphrase) = self.require_passphrase
@@ -156,6 +459,12 @@ impl SendArgs {
             restrictions = restrictions.with_passphrase(&bytes);
         }
 
+        if let Some(ref recipient) = self.pin_to
+            && !recipient.trim().is_empty()
+        {
+            restrictions = restrictions.with_pin_recipient(recipient.trim());
+        }
+
         if restrictions.is_empty() {
             None
         } else {
@@ -163,6 +472,24 @@ impl SendArgs {
         }
     }
 
+    /// ASNs given explicitly and those of the given organizations, without duplicates.
+    fn allowed_asns_with_orgs(&self) -> Vec<u32> {
+        let org_asns = self
+            .allowed_orgs
+            .iter()
+            .flatten()
+            .filter_map(|org| asn::asns_for_org(org))
+            .flatten();
+
+        let mut allowed_asns = Vec::new();
+        for number in self.allowed_asns.iter().flatten().chain(org_asns) {
+            if !allowed_asns.contains(number) {
+                allowed_asns.push(*number);
+            }
+        }
+        allowed_asns
+    }
+
     /// Get the processed token, reading from file if needed
     pub fn token(&self) -> Result<Option<String>> {
         if let Some(path) = self.token_file.clone() {
@@ -190,14 +517,37 @@ impl SendArgs {
             token: None,
             token_file: None,
             files: None,
+            batch: None,
+            json: false,
+            edit: false,
+            prompt: false,
+            render: false,
+            from_vault: None,
+            vault: "vault".to_string(),
+            excludes: None,
+            archive_format: ArchiveFormat::Zip,
             as_file: false,
             filename: None,
+            description: None,
+            content_type: None,
+            checksum: false,
+            pad: false,
+            short_alias: false,
+            allow_reply: false,
+            classify: false,
             separate_key: false,
+            split: None,
             print_qr_code: false,
             allowed_ips: None,
             allowed_countries: None,
             allowed_asns: None,
+            allowed_orgs: None,
             require_passphrase: None,
+            pin_to: None,
+            limit_rate: None,
+            max_memory: None,
+            history: false,
+            history_file: None,
         }
     }
 
@@ -231,6 +581,92 @@ impl SendArgs {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_from_vault(mut self, source: &str, vault: &str) -> Self {
+        self.from_vault = Some(source.parse().expect("Invalid Vault reference"));
+        self.vault = vault.to_string();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_batch(mut self, manifest: &str) -> Self {
+        self.batch = Some(manifest.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_allow_reply(mut self) -> Self {
+        self.allow_reply = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_split(mut self, threshold: u8, count: u8) -> Self {
+        self.split = Some(KeySplit { threshold, count });
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_separate_key(mut self) -> Self {
+        self.separate_key = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_history_file(mut self, path: &std::path::Path) -> Self {
+        self.history = true;
+        self.history_file = Some(path.to_path_buf());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_json(mut self) -> Self {
+        self.json = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_limit_rate(mut self, rate: i64) -> Self {
+        self.limit_rate = Some(rate);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_max_memory(mut self, max_memory: i64) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_edit(mut self) -> Self {
+        self.edit = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_render(mut self) -> Self {
+        self.render = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_prompt(mut self) -> Self {
+        self.prompt = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_excludes(mut self, excludes: Vec<String>) -> Self {
+        self.excludes = Some(excludes);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_archive_format(mut self, format: ArchiveFormat) -> Self {
+        self.archive_format = format;
+        self
+    }
+
     #[cfg(test)]
     pub fn with_as_file(mut self) -> Self {
         self.as_file = true;
@@ -243,9 +679,33 @@ impl SendArgs {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_classify(mut self) -> Self {
+        self.classify = true;
+        self
+    }
+
     #[cfg(test)]
     pub fn with_allowed_ips(mut self, allowed_ips: Vec<ipnet::IpNet>) -> Self {
-        self.allowed_ips = Some(allowed_ips);
+        self.allowed_ips = Some(allowed_ips.into_iter().map(Into::into).collect());
         self
     }
 
@@ -261,11 +721,23 @@ impl SendArgs {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_allowed_orgs(mut self, allowed_orgs: Vec<&str>) -> Self {
+        self.allowed_orgs = Some(allowed_orgs.into_iter().map(String::from).collect());
+        self
+    }
+
     #[cfg(test)]
     pub fn with_require_passphrase(mut self, passphrase: &str) -> Self {
         self.require_passphrase = Some(passphrase.to_string());
         self
     }
+
+    #[cfg(test)]
+    pub fn with_pin_to(mut self, recipient: &str) -> Self {
+        self.pin_to = Some(recipient.to_string());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -273,6 +745,34 @@ mod tests {
     use super::*;
     use hakanai_lib::utils::test::MustParse;
 
+    #[test]
+    fn test_generate_passphrase_auto() -> Result<()> {
+        let mut args = SendArgs::builder().with_require_passphrase("auto");
+
+        let passphrase = args
+            .generate_passphrase()
+            .expect("Passphrase should be generated");
+        assert_eq!(
+            passphrase.split(wordlist::SEPARATOR).count(),
+            wordlist::DEFAULT_WORD_COUNT
+        );
+        assert_eq!(
+            args.require_passphrase.as_deref(),
+            Some(passphrase.as_str()),
+            "Generated passphrase should be required"
+        );
+        args.validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_passphrase_explicit() {
+        let mut args = SendArgs::builder().with_require_passphrase("my passphrase");
+
+        assert_eq!(args.generate_passphrase(), None);
+        assert_eq!(args.require_passphrase.as_deref(), Some("my passphrase"));
+    }
+
     #[test]
     fn test_validate_passphrase_exactly_8_chars() -> Result<()> {
         let args = SendArgs::builder().with_require_passphrase("12345678");
@@ -391,6 +891,225 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_edit_with_file_fails() {
+        let args = SendArgs::builder().with_edit().with_file("secret.txt");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--edit option cannot be used with --file")
+        );
+    }
+
+    #[test]
+    fn test_validate_from_vault_with_other_input_fails() {
+        let source = "secret/app#password";
+        let combinations = [
+            SendArgs::builder().with_file("secret.txt"),
+            SendArgs::builder().with_edit(),
+            SendArgs::builder().with_prompt(),
+            SendArgs::builder().with_batch("batch.yaml"),
+        ];
+
+        for args in combinations {
+            let result = args.with_from_vault(source, "vault").validate();
+            assert!(
+                result.is_err_and(|e| e.to_string().contains("--from-vault option cannot")),
+                "Other inputs should be rejected with --from-vault"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_prompt_with_file_fails() {
+        let args = SendArgs::builder().with_prompt().with_file("secret.txt");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--prompt option cannot be used with --file")
+        );
+    }
+
+    #[test]
+    fn test_validate_prompt_with_edit_fails() {
+        let args = SendArgs::builder().with_prompt().with_edit();
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--prompt option cannot be used with --edit")
+        );
+    }
+
+    #[test]
+    fn test_validate_exclude_without_files_fails() {
+        let args = SendArgs::builder().with_excludes(vec!["*.log".to_string()]);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--exclude option requires --file")
+        );
+    }
+
+    #[test]
+    fn test_validate_batch_with_file_fails() {
+        let args = SendArgs::builder()
+            .with_batch("manifest.yaml")
+            .with_file("secret.txt");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--batch option cannot be used with --file")
+        );
+    }
+
+    #[test]
+    fn test_validate_batch_with_allow_reply_fails() {
+        let args = SendArgs::builder()
+            .with_batch("manifest.yaml")
+            .with_allow_reply();
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--batch option cannot be used with --allow-reply")
+        );
+    }
+
+    #[test]
+    fn test_validate_limit_rate_zero_fails() {
+        let args = SendArgs::builder().with_limit_rate(0);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--limit-rate option must be greater than zero")
+        );
+    }
+
+    #[test]
+    fn test_validate_max_memory_negative_fails() {
+        let args = SendArgs::builder().with_max_memory(-1);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--max-memory option must be greater than zero")
+        );
+    }
+
+    #[test]
+    fn test_validate_json_without_batch_fails() {
+        let args = SendArgs::builder().with_json();
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--json option requires --batch")
+        );
+    }
+
+    #[test]
+    fn test_validate_render_with_file_fails() {
+        let args = SendArgs::builder().with_render().with_file("test.txt");
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--render option cannot be used with --file")
+        );
+    }
+
+    #[test]
+    fn test_validate_split_with_separate_key_fails() {
+        let args = SendArgs::builder().with_split(2, 3).with_separate_key();
+
+        let result = args.validate();
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("--split option cannot be used with --separate-key")
+        );
+    }
+
+    #[test]
+    fn test_parse_key_split() {
+        assert_eq!(
+            KeySplit::from_str("2/3"),
+            Ok(KeySplit {
+                threshold: 2,
+                count: 3
+            })
+        );
+        assert_eq!(
+            KeySplit::from_str("3/3"),
+            Ok(KeySplit {
+                threshold: 3,
+                count: 3
+            })
+        );
+
+        for invalid in ["", "2", "1/3", "4/3", "a/3", "2/256"] {
+            assert!(
+                KeySplit::from_str(invalid).is_err(),
+                "'{invalid}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_country_code() {
+        assert_eq!(
+            parse_country_code("DE").map(|c| c.to_string()),
+            Ok("DE".to_string())
+        );
+        assert_eq!(
+            parse_country_code("UK").map(|c| c.to_string()),
+            Err("'UK' is not a valid country code, did you mean 'GB'?".to_string()),
+            "Error should suggest the ISO code"
+        );
+        assert!(
+            parse_country_code("XX").is_err(),
+            "Unassigned codes should be rejected"
+        );
+    }
+
     #[test]
     fn test_get_restrictions_with_all_options() {
         // Test that all restriction types are properly processed when set
@@ -547,6 +1266,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_restrictions_orgs() {
+        let args = SendArgs::builder().with_allowed_orgs(vec!["Cloudflare"]);
+
+        let restrictions = args.get_restrictions().expect("Restrictions should be set");
+        assert_eq!(
+            restrictions.allowed_asns,
+            Some(vec![13335, 209242]),
+            "Organization should expand to its ASNs"
+        );
+    }
+
+    #[test]
+    fn test_get_restrictions_orgs_merged_with_asns() {
+        let args = SendArgs::builder()
+            .with_allowed_asns(vec![13335, 64512])
+            .with_allowed_orgs(vec!["Cloudflare", "Fastly"]);
+
+        let restrictions = args.get_restrictions().expect("Restrictions should be set");
+        assert_eq!(
+            restrictions.allowed_asns,
+            Some(vec![13335, 64512, 209242, 54113]),
+            "Organization ASNs should be appended to explicit ASNs without duplicates"
+        );
+    }
+
+    #[test]
+    fn test_get_restrictions_pin_to() {
+        let args = SendArgs::builder().with_pin_to(" +49123456789 ");
+
+        let restrictions = args.get_restrictions().expect("Restrictions should be set");
+        assert_eq!(
+            restrictions.pin_recipient.as_deref(),
+            Some("+49123456789"),
+            "PIN recipient should be trimmed"
+        );
+        assert!(
+            SendArgs::builder()
+                .with_pin_to(" ")
+                .get_restrictions()
+                .is_none(),
+            "Blank PIN recipient should be ignored"
+        );
+    }
+
     #[test]
     fn test_get_restrictions_only_passphrase() {
         // Test that only passphrase restrictions are processed correctly
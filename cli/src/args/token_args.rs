@@ -2,14 +2,20 @@
 
 use std::time::Duration;
 
-use clap::Parser;
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
 use url::Url;
 
-use hakanai_lib::utils::human_size;
+use hakanai_lib::models::TokenScope;
+use hakanai_lib::utils::{human_duration, human_size, ip};
 
 /// Represents the arguments for the `token` command.
 #[derive(Debug, Clone, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct TokenArgs {
+    #[command(subcommand)]
+    pub command: Option<TokenCommand>,
+
     #[arg(
         short,
         long,
@@ -24,7 +30,7 @@ pub struct TokenArgs {
         default_value = "30d",
         env = "HAKANAI_TOKEN_TTL",
         help = "Time until the token expires.",
-        value_parser = humantime::parse_duration,
+        value_parser = human_duration::parse,
     )]
     pub ttl: Duration,
 
@@ -42,4 +48,92 @@ pub struct TokenArgs {
         default_value_t = false
     )]
     pub one_time: bool,
+
+    #[arg(
+        long,
+        help = "If set, the token is not allowed to create secrets.",
+        default_value_t = false
+    )]
+    pub no_create: bool,
+
+    #[arg(
+        long,
+        help = "Maximum TTL of secrets created with the token.",
+        value_parser = human_duration::parse,
+    )]
+    pub max_secret_ttl: Option<Duration>,
+
+    #[arg(
+        long,
+        help = "If set, secrets created with the token must be protected by a passphrase.",
+        default_value_t = false
+    )]
+    pub require_passphrase: bool,
+
+    #[arg(
+        long = "pin-ip",
+        help = "Comma-separated list of IP addresses (CIDR notation) secrets created with the token are restricted to.",
+        value_delimiter = ',',
+        value_parser = ip::parse_ipnet,
+    )]
+    pub pinned_ips: Option<Vec<ipnet::IpNet>>,
+}
+
+impl TokenArgs {
+    /// Get the scope of the token to create
+    pub fn scope(&self) -> TokenScope {
+        TokenScope {
+            can_create: !self.no_create,
+            allowed_ttl_max: self.max_secret_ttl.map(|ttl| ttl.as_secs()),
+            require_passphrase: self.require_passphrase,
+            allowed_ips: self.pinned_ips.clone(),
+        }
+    }
+}
+
+/// Represents the subcommands of the `token` command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum TokenCommand {
+    /// Show the limits of your user token.
+    Info(TokenInfoArgs),
+}
+
+/// Represents the arguments for the `token info` command.
+#[derive(Debug, Clone, Parser)]
+pub struct TokenInfoArgs {
+    #[arg(
+        short,
+        long,
+        default_value = "http://localhost:8080",
+        env = "HAKANAI_SERVER",
+        help = "Hakanai Server URL the token was issued by (eg. https://hakanai.link)."
+    )]
+    pub server: Url,
+
+    #[arg(
+        env = "HAKANAI_TOKEN",
+        help = "Token to inspect (environment variable only). Prompted for if not set."
+    )]
+    pub token: Option<String>,
+
+    #[arg(
+        long = "token-file",
+        help = "File containing the token to inspect. Environment variable HAKANAI_TOKEN takes precedence.",
+        value_name = "TOKEN_FILE"
+    )]
+    pub token_file: Option<String>,
+}
+
+impl TokenInfoArgs {
+    /// Get the processed token, reading from file if needed
+    pub fn token(&self) -> Result<Option<String>> {
+        if let Some(path) = &self.token_file {
+            return match std::fs::read_to_string(path) {
+                Ok(content) => Ok(Some(content.trim().to_string())),
+                Err(e) => Err(anyhow!("Failed to read token file '{path}': {e}")),
+            };
+        }
+
+        Ok(self.token.clone())
+    }
 }
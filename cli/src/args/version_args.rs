@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use url::Url;
+
+/// Represents the arguments for the `version` command.
+#[derive(Debug, Clone, Parser)]
+pub struct VersionArgs {
+    #[arg(
+        long,
+        value_name = "SERVER",
+        help = "Also show the version of this Hakanai Server and warn about known incompatibilities (eg. https://hakanai.link)."
+    )]
+    pub remote: Option<Url>,
+}
@@ -0,0 +1,533 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use hakanai_lib::client::Client;
+use hakanai_lib::models::{Payload, SecretRestrictions};
+use hakanai_lib::options::SecretSendOptions;
+use hakanai_lib::utils::{human_duration, ip};
+
+use crate::args::{MIN_PASSPHRASE_LENGTH, SendArgs, parse_country_code};
+use crate::exclude::ExcludePatterns;
+use crate::factory::Factory;
+use crate::helper;
+use crate::history;
+use crate::send::{Secret, read_secret_from_files};
+
+/// Manifest defining the secrets to send in a batch.
+///
+/// ```yaml
+/// secrets:
+///   - label: db-password
+///     text: "s3cr3t"
+///     ttl: 7d
+///     allow_ips: [10.0.0.0/8]
+///     passphrase: "shared out-of-band"
+///   - label: tls
+///     files: [certs/tls.key, certs/tls.crt]
+///     description: TLS key pair
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    secrets: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestEntry {
+    label: String,
+    text: Option<String>,
+    files: Option<Vec<String>>,
+    ttl: Option<String>,
+    filename: Option<String>,
+    description: Option<String>,
+    content_type: Option<String>,
+    allow_ips: Option<Vec<String>>,
+    allow_countries: Option<Vec<String>>,
+    allow_asns: Option<Vec<u32>>,
+    passphrase: Option<String>,
+}
+
+/// Source of the secret of a batch entry.
+#[derive(Debug)]
+enum SecretSource {
+    Text(Zeroizing<String>),
+    Files(Vec<String>),
+}
+
+/// A validated batch entry, with the defaults of the command line applied.
+#[derive(Debug)]
+struct BatchEntry {
+    label: String,
+    source: SecretSource,
+    ttl: Duration,
+    filename: Option<String>,
+    description: Option<String>,
+    content_type: Option<String>,
+    restrictions: Option<SecretRestrictions>,
+}
+
+/// Outcome of sending a batch entry.
+#[derive(Debug, Serialize)]
+struct BatchResult {
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Sends all secrets defined in the manifest and prints the generated links.
+///
+/// All entries are validated before the first secret is sent. Failures of single entries do not
+/// abort the batch, they are reported along with the links and result in an error at the end.
+pub async fn send_batch<T: Factory>(
+    factory: T,
+    args: SendArgs,
+    manifest_path: &Path,
+    token: String,
+) -> Result<()> {
+    let entries = load_entries(manifest_path, &args)?;
+
+    let client = factory.new_client();
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let label = entry.label.clone();
+        let result = match send_entry(&factory, &client, &args, entry, &token).await {
+            Ok(link) => BatchResult {
+                label,
+                link: Some(link),
+                error: None,
+            },
+            Err(err) => BatchResult {
+                label,
+                link: None,
+                error: Some(err.to_string()),
+            },
+        };
+        results.push(result);
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        print!("{}", format_results(&results));
+    }
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} secrets could not be sent.",
+            results.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn load_entries(manifest_path: &Path, args: &SendArgs) -> Result<Vec<BatchEntry>> {
+    let content = std::fs::read_to_string(manifest_path)
+        .map_err(|e| anyhow!("Failed to read manifest '{}': {e}", manifest_path.display()))?;
+    let manifest: Manifest = serde_yaml::from_str(&content)
+        .map_err(|e| anyhow!("Invalid manifest '{}': {e}", manifest_path.display()))?;
+
+    if manifest.secrets.is_empty() {
+        return Err(anyhow!("The manifest does not define any secrets."));
+    }
+
+    let base_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    let mut labels = HashSet::new();
+    manifest
+        .secrets
+        .into_iter()
+        .map(|entry| {
+            if !labels.insert(entry.label.clone()) {
+                return Err(anyhow!("Duplicate label '{}' in manifest.", entry.label));
+            }
+
+            let label = entry.label.clone();
+            entry
+                .into_batch_entry(args, base_dir)
+                .map_err(|e| anyhow!("Invalid entry '{label}': {e}"))
+        })
+        .collect()
+}
+
+impl ManifestEntry {
+    fn into_batch_entry(self, args: &SendArgs, base_dir: &Path) -> Result<BatchEntry> {
+        if self.label.trim().is_empty() {
+            return Err(anyhow!("The label must not be empty."));
+        }
+
+        let source = match (self.text, self.files) {
+            (Some(text), None) => SecretSource::Text(Zeroizing::new(text)),
+            (None, Some(files)) if !files.is_empty() => SecretSource::Files(
+                files
+                    .iter()
+                    .map(|file| resolve_path(base_dir, file))
+                    .collect(),
+            ),
+            _ => return Err(anyhow!("Exactly one of text or files must be set.")),
+        };
+
+        let ttl = match &self.ttl {
+            Some(ttl) => human_duration::parse(ttl).map_err(|e| anyhow!("Invalid TTL: {e}"))?,
+            None => args.ttl,
+        };
+        if ttl.as_secs() == 0 {
+            return Err(anyhow!("TTL must be greater than zero seconds."));
+        }
+
+        let mut restrictions = args.get_restrictions().unwrap_or_default();
+        if let Some(allow_ips) = &self.allow_ips {
+            let ips = allow_ips
+                .iter()
+                .map(|s| ip::parse_ipnet(s))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow!(e))?;
+            restrictions = restrictions.with_allowed_ips(ips);
+        }
+
+        if let Some(allow_countries) = &self.allow_countries {
+            let countries = allow_countries
+                .iter()
+                .map(|s| parse_country_code(s))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow!(e))?;
+            restrictions = restrictions.with_allowed_countries(countries);
+        }
+
+        if let Some(allow_asns) = self.allow_asns {
+            restrictions = restrictions.with_allowed_asns(allow_asns);
+        }
+
+        if let Some(passphrase) = self.passphrase {
+            let passphrase = Zeroizing::new(passphrase);
+            if passphrase.trim().chars().count() < MIN_PASSPHRASE_LENGTH {
+                return Err(anyhow!(
+                    "The passphrase must be at least {MIN_PASSPHRASE_LENGTH} characters long if set."
+                ));
+            }
+            restrictions = restrictions.with_passphrase(passphrase.as_bytes());
+        }
+
+        Ok(BatchEntry {
+            label: self.label,
+            source,
+            ttl,
+            filename: self.filename,
+            description: self.description.or_else(|| args.description.clone()),
+            content_type: self.content_type.or_else(|| args.content_type.clone()),
+            restrictions: (!restrictions.is_empty()).then_some(restrictions),
+        })
+    }
+}
+
+/// Resolves paths relative to the directory of the manifest.
+fn resolve_path(base_dir: &Path, file: &str) -> String {
+    let path = PathBuf::from(file);
+    if path.is_absolute() {
+        file.to_string()
+    } else {
+        base_dir.join(path).to_string_lossy().to_string()
+    }
+}
+
+async fn send_entry<T: Factory>(
+    factory: &T,
+    client: &impl Client<Payload>,
+    args: &SendArgs,
+    entry: BatchEntry,
+    token: &str,
+) -> Result<String> {
+    let mut secret = match entry.source {
+        SecretSource::Text(text) => Secret {
+            bytes: Zeroizing::new(text.as_bytes().to_vec()),
+            filename: None,
+        },
+        SecretSource::Files(files) => {
+            read_secret_from_files(files, args.archive_format, &ExcludePatterns::new(&[]))?
+        }
+    };
+    if secret.bytes.is_empty() {
+        return Err(anyhow!("No secret provided."));
+    }
+
+    let mut payload = Payload::from_vec(std::mem::take(&mut *secret.bytes));
+    if let Some(filename) = entry.filename.or(secret.filename) {
+        payload = payload.with_filename(&filename);
+    }
+
+    if let Some(description) = &entry.description {
+        payload = payload.with_description(description);
+    }
+
+    if let Some(content_type) = &entry.content_type {
+        payload = payload.with_content_type(content_type);
+    }
+
+    if args.checksum {
+        payload = payload.with_checksum();
+    }
+
+    let observer = factory.new_observer(&format!("Sending {}...", entry.label))?;
+    let mut opts = SecretSendOptions::default()
+        .with_user_agent(helper::get_user_agent_name())
+        .with_observer(observer)
+        .with_padding(args.pad);
+    if let Some(rate) = args.limit_rate {
+        opts = opts.with_rate_limit(rate as u64);
+    }
+    if let Some(max_memory) = args.max_memory {
+        opts = opts.with_max_memory(max_memory as usize);
+    }
+    if let Some(restrictions) = &entry.restrictions {
+        opts = opts.with_restrictions(restrictions.clone());
+    }
+
+    let link = client
+        .send_secret(
+            args.server.clone(),
+            payload,
+            entry.ttl,
+            token.to_string(),
+            Some(opts),
+        )
+        .await?
+        .url;
+    history::record(args, &link, entry.ttl, entry.restrictions.as_ref());
+
+    Ok(link.to_string())
+}
+
+fn format_results(results: &[BatchResult]) -> String {
+    let width = results
+        .iter()
+        .map(|r| r.label.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max("Label".len());
+
+    let header = format!("{:<width$}", "Label");
+    let mut out = format!("{}  {}\n", header.bold(), "Link".bold());
+    for result in results {
+        let value = match (&result.link, &result.error) {
+            (Some(link), _) => link.cyan(),
+            (None, Some(error)) => error.red(),
+            (None, None) => "".normal(),
+        };
+        out.push_str(&format!("{:<width$}  {value}\n", result.label));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use hakanai_lib::client_mock::MockClient;
+    use hakanai_lib::utils::test::MustParse;
+
+    use crate::factory_mock::test_utils::MockFactory;
+
+    fn write_manifest(dir: &TempDir, content: &str) -> Result<PathBuf> {
+        let path = dir.path().join("manifest.yaml");
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn test_load_entries_applies_defaults() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_manifest(
+            &temp_dir,
+            r#"
+secrets:
+  - label: db
+    text: s3cr3t
+  - label: api
+    text: key
+    ttl: 7d
+    allow_countries: [DE]
+"#,
+        )?;
+        let args = SendArgs::builder()
+            .with_ttl(Duration::from_secs(3600))
+            .with_allowed_ips(vec!["10.0.0.0/8".must_parse()]);
+
+        let entries = load_entries(&path, &args)?;
+
+        assert_eq!(entries.len(), 2, "All entries should be loaded");
+        assert_eq!(entries[0].ttl, Duration::from_secs(3600));
+        assert_eq!(
+            entries[0]
+                .restrictions
+                .as_ref()
+                .and_then(|r| r.allowed_ips.clone()),
+            Some(vec!["10.0.0.0/8".must_parse()]),
+            "Restrictions of the command line should be used as default"
+        );
+        assert_eq!(entries[1].ttl, Duration::from_secs(7 * 24 * 60 * 60));
+        assert_eq!(
+            entries[1]
+                .restrictions
+                .as_ref()
+                .and_then(|r| r.allowed_countries.clone()),
+            Some(vec!["DE".must_parse()]),
+            "Restrictions of the entry should be applied"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_entries_resolves_files_relative_to_manifest() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_manifest(
+            &temp_dir,
+            "secrets:\n  - label: tls\n    files: [certs/tls.key]\n",
+        )?;
+
+        let entries = load_entries(&path, &SendArgs::builder())?;
+
+        match &entries[0].source {
+            SecretSource::Files(files) => assert_eq!(
+                files,
+                &vec![
+                    temp_dir
+                        .path()
+                        .join("certs/tls.key")
+                        .to_string_lossy()
+                        .to_string()
+                ]
+            ),
+            SecretSource::Text(_) => panic!("Expected files source"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_entries_rejects_invalid_entries() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let cases = [
+            ("secrets: []\n", "does not define any secrets"),
+            (
+                "secrets:\n  - label: a\n    text: x\n  - label: a\n    text: y\n",
+                "Duplicate label 'a'",
+            ),
+            (
+                "secrets:\n  - label: a\n    text: x\n    files: [f]\n",
+                "Exactly one of text or files",
+            ),
+            ("secrets:\n  - label: a\n", "Exactly one of text or files"),
+            (
+                "secrets:\n  - label: a\n    text: x\n    passphrase: short\n",
+                "at least 8 characters",
+            ),
+            (
+                "secrets:\n  - label: a\n    text: x\n    ttl: 0s\n",
+                "TTL must be greater than zero",
+            ),
+            (
+                "secrets:\n  - label: a\n    text: x\n    unknown: y\n",
+                "unknown field",
+            ),
+        ];
+
+        for (content, expected) in cases {
+            let path = write_manifest(&temp_dir, content)?;
+            let result = load_entries(&path, &SendArgs::builder());
+
+            let err = result.expect_err(&format!("Expected error for manifest: {content}"));
+            assert!(
+                err.to_string().contains(expected),
+                "Error '{err}' should contain '{expected}'"
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_batch() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("tls.key"), b"private key")?;
+        let path = write_manifest(
+            &temp_dir,
+            "secrets:\n  - label: db\n    text: s3cr3t\n  - label: tls\n    files: [tls.key]\n",
+        )?;
+
+        let client =
+            MockClient::new().with_send_success("https://example.com/s/batch#key".must_parse());
+        let factory = MockFactory::new().with_client(client.clone());
+        let args = SendArgs::builder().with_batch(&path.to_string_lossy());
+
+        send_batch(factory, args, &path, "token".to_string()).await?;
+
+        let payload = client.get_sent_data().ok_or(anyhow!("No data sent"))?;
+        assert_eq!(payload.data, b"private key", "Last entry should be sent");
+        assert_eq!(
+            payload.filename,
+            Some("tls.key".to_string()),
+            "Files should be sent with their filename"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_reports_failures() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_manifest(&temp_dir, "secrets:\n  - label: db\n    text: s3cr3t\n")?;
+
+        let client = MockClient::new().with_send_failure("Network error".to_string());
+        let factory = MockFactory::new().with_client(client);
+        let args = SendArgs::builder().with_batch(&path.to_string_lossy());
+
+        let result = send_batch(factory, args, &path, "token".to_string()).await;
+
+        assert!(
+            result
+                .expect_err("Expected error for failed entry")
+                .to_string()
+                .contains("1 of 1 secrets could not be sent"),
+            "Error should report the number of failed entries"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_results() {
+        colored::control::set_override(false);
+        let results = vec![
+            BatchResult {
+                label: "db".to_string(),
+                link: Some("https://example.com/s/1#key".to_string()),
+                error: None,
+            },
+            BatchResult {
+                label: "certificate".to_string(),
+                link: None,
+                error: Some("Network error".to_string()),
+            },
+        ];
+
+        let output = format_results(&results);
+
+        assert_eq!(
+            output,
+            "Label        Link\n\
+             db           https://example.com/s/1#key\n\
+             certificate  Network error\n"
+        );
+    }
+}
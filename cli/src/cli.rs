@@ -2,7 +2,11 @@
 
 use clap::{Parser, Subcommand};
 
-pub use crate::args::{GetArgs, SendArgs, TokenArgs};
+pub use crate::args::{
+    AdminArgs, DecryptArgs, EncryptArgs, GetArgs, HistoryArgs, K8sArgs, SendArgs, TokenArgs,
+    VersionArgs,
+};
+use crate::i18n::Language;
 
 /// Represents the command-line arguments for the application.
 #[derive(Debug, Parser)]
@@ -11,9 +15,25 @@ pub use crate::args::{GetArgs, SendArgs, TokenArgs};
     name = "hakanai",
     author = "Daniel Brendgen-Czerwonk",
     about = "A minimalist one-time secret sharing web service. Share sensitive data through ephemeral links that self-destruct after a single view. No accounts, no tracking, just a simple way to transmit secrets that vanish like morning mist.",
-    after_help = "LICENSE:\n  Licensed under the Apache License, Version 2.0\n  <https://www.apache.org/licenses/LICENSE-2.0>\n\nSOURCE:\n  <https://github.com/czerwonk/hakanai>"
+    after_help = "EXIT CODES:\n  0  Success\n  1  General failure\n  2  Invalid arguments\n  3  Authentication failed (missing or invalid token)\n  4  Secret not found\n  5  Secret already retrieved\n  6  Access denied by restrictions of the secret (IP, country, ASN or passphrase)\n  7  Network error (server unreachable or timeout)\n\nLICENSE:\n  Licensed under the Apache License, Version 2.0\n  <https://www.apache.org/licenses/LICENSE-2.0>\n\nSOURCE:\n  <https://github.com/czerwonk/hakanai>"
 )]
 pub struct Args {
+    #[arg(
+        long,
+        global = true,
+        env = "HAKANAI_LANG",
+        help = "Language of user-facing messages. Detected from the locale (LC_ALL, LC_MESSAGES, LANG) if not specified."
+    )]
+    pub lang: Option<Language>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "HAKANAI_NOTIFY",
+        help = "Show a desktop notification when the command completes or fails."
+    )]
+    pub notify: bool,
+
     #[clap(subcommand)]
     pub command: Command,
 }
@@ -30,6 +50,25 @@ pub enum Command {
 
     /// Create a new user token (requires admin privileges).
     Token(TokenArgs),
+
+    /// Administrative commands (requires admin privileges).
+    Admin(AdminArgs),
+
+    /// Show or clear the local history of sent secrets (enable recording with send --history).
+    History(HistoryArgs),
+
+    /// Show the version of the CLI and optionally of a server.
+    Version(VersionArgs),
+
+    /// Encrypt a secret into an envelope file without any server interaction.
+    /// The key is printed to stdout, the envelope can be decrypted with the decrypt command.
+    Encrypt(EncryptArgs),
+
+    /// Decrypt an envelope file created by the encrypt command.
+    Decrypt(DecryptArgs),
+
+    /// Kubernetes helpers, using kubectl and the local kubeconfig.
+    K8s(K8sArgs),
 }
 
 #[cfg(test)]
@@ -152,6 +191,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_command_from_file_without_link() {
+        let args = Args::try_parse_from(["hakanai", "get", "--from-file", "urls.txt"])
+            .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Get(get_args) => {
+                assert_eq!(get_args.link, None);
+                assert_eq!(
+                    get_args.from_file,
+                    Some(std::path::PathBuf::from("urls.txt"))
+                );
+            }
+            _ => panic!("expected get command"),
+        }
+    }
+
+    #[test]
+    fn test_get_command_from_file_with_link_fails() {
+        let result = Args::try_parse_from([
+            "hakanai",
+            "get",
+            "https://example.com/secret/abc123#key",
+            "--from-file",
+            "urls.txt",
+        ]);
+        assert!(
+            result.is_err(),
+            "Expected error for link and --from-file, got: {:?}",
+            result
+        );
+    }
+
     // Tests for multi-assignment with separators (prevent regressions)
     #[test]
     fn test_send_command_with_multiple_allowed_ips() {
@@ -224,9 +296,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_send_command_with_allowed_orgs() {
+        let args = Args::try_parse_from(["hakanai", "send", "--allow-org", "cloudflare,Google"])
+            .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Send(send_args) => {
+                assert_eq!(
+                    send_args.allowed_orgs,
+                    Some(vec!["Cloudflare".to_string(), "Google".to_string()]),
+                    "Organization names should be normalized"
+                );
+            }
+            _ => panic!("Expected Send command"),
+        }
+    }
+
+    #[test]
+    fn test_send_command_unknown_org() {
+        let result = Args::try_parse_from(["hakanai", "send", "--allow-org", "Initech"]);
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+    }
+
     #[test]
     fn test_send_command_invalid_ip_address() {
-        let result = Args::try_parse_from(["hakanai", "send", "--allow-ip", "not-an-ip"]);
+        let result = Args::try_parse_from(["hakanai", "send", "--allow-ip", "not an ip!"]);
         assert!(result.is_err(), "Expected error, got: {:?}", result);
     }
 
@@ -258,4 +353,194 @@ mod tests {
         let result = Args::try_parse_from(["hakanai", "send", "--allow-asn", "4294967296"]); // u32::MAX + 1
         assert!(result.is_err(), "Expected error, got: {:?}", result);
     }
+
+    #[test]
+    fn test_token_command_parsing() {
+        let args = Args::try_parse_from(["hakanai", "token", "--ttl", "1d", "--one-time"])
+            .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Token(token_args) => {
+                assert!(token_args.command.is_none(), "Expected token creation");
+                assert_eq!(token_args.ttl, Duration::from_secs(24 * 60 * 60));
+                assert!(token_args.one_time);
+            }
+            _ => panic!("expected token command"),
+        }
+    }
+
+    #[test]
+    fn test_token_command_scope_parsing() {
+        let args = Args::try_parse_from([
+            "hakanai",
+            "token",
+            "--max-secret-ttl",
+            "1h",
+            "--require-passphrase",
+            "--pin-ip",
+            "10.0.0.0/8,192.168.0.0/16",
+        ])
+        .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Token(token_args) => {
+                let scope = token_args.scope();
+                assert!(scope.can_create);
+                assert_eq!(scope.allowed_ttl_max, Some(3600));
+                assert!(scope.require_passphrase);
+                assert_eq!(scope.allowed_ips.map(|ips| ips.len()), Some(2));
+            }
+            _ => panic!("expected token command"),
+        }
+    }
+
+    #[test]
+    fn test_token_info_command_parsing() {
+        let args = Args::try_parse_from([
+            "hakanai",
+            "token",
+            "info",
+            "--server",
+            "https://example.com",
+        ])
+        .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Token(TokenArgs {
+                command: Some(crate::args::TokenCommand::Info(info_args)),
+                ..
+            }) => {
+                assert_eq!(info_args.server.as_str(), "https://example.com/");
+            }
+            _ => panic!("expected token info command"),
+        }
+    }
+
+    #[test]
+    fn test_admin_purge_command_parsing() {
+        let args = Args::try_parse_from([
+            "hakanai",
+            "admin",
+            "purge",
+            "--server",
+            "https://example.com",
+            "--older-than",
+            "12h",
+            "--yes",
+        ])
+        .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Admin(crate::args::AdminArgs {
+                command: crate::args::AdminCommand::Purge(purge_args),
+            }) => {
+                assert_eq!(purge_args.server.as_str(), "https://example.com/");
+                assert_eq!(
+                    purge_args.older_than,
+                    Some(std::time::Duration::from_secs(12 * 3600))
+                );
+                assert!(purge_args.yes);
+            }
+            _ => panic!("expected admin purge command"),
+        }
+    }
+
+    #[test]
+    fn test_admin_purge_command_defaults() {
+        let args =
+            Args::try_parse_from(["hakanai", "admin", "purge"]).expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Admin(crate::args::AdminArgs {
+                command: crate::args::AdminCommand::Purge(purge_args),
+            }) => {
+                assert_eq!(purge_args.older_than, None, "All secrets should be purged");
+                assert!(!purge_args.yes, "Confirmation should be required");
+            }
+            _ => panic!("expected admin purge command"),
+        }
+    }
+
+    #[test]
+    fn test_encrypt_command_parsing() {
+        let args = Args::try_parse_from(["hakanai", "encrypt", "--out", "blob.hakanai"])
+            .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Encrypt(encrypt_args) => {
+                assert_eq!(encrypt_args.out, std::path::PathBuf::from("blob.hakanai"));
+                assert_eq!(encrypt_args.file, None);
+            }
+            _ => panic!("expected encrypt command"),
+        }
+    }
+
+    #[test]
+    fn test_decrypt_command_parsing() {
+        let args =
+            Args::try_parse_from(["hakanai", "decrypt", "blob.hakanai", "--key", "key:hash"])
+                .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::Decrypt(decrypt_args) => {
+                assert_eq!(
+                    decrypt_args.envelope,
+                    std::path::PathBuf::from("blob.hakanai")
+                );
+                assert_eq!(decrypt_args.key.as_deref(), Some("key:hash"));
+            }
+            _ => panic!("expected decrypt command"),
+        }
+    }
+
+    #[test]
+    fn test_k8s_create_secret_command_parsing() {
+        let args = Args::try_parse_from([
+            "hakanai",
+            "k8s",
+            "create-secret",
+            "https://example.com/s/abc123#key",
+            "--name",
+            "my-secret",
+            "--namespace",
+            "prod",
+        ])
+        .expect("Failed to parse arguments");
+
+        match args.command {
+            Command::K8s(K8sArgs {
+                command: crate::args::K8sCommand::CreateSecret(create_args),
+            }) => {
+                assert_eq!(create_args.name, "my-secret");
+                assert_eq!(create_args.namespace.as_deref(), Some("prod"));
+                assert_eq!(create_args.secret_type, "Opaque");
+            }
+            _ => panic!("expected k8s create-secret command"),
+        }
+    }
+
+    #[test]
+    fn test_k8s_create_secret_requires_name() {
+        let result = Args::try_parse_from([
+            "hakanai",
+            "k8s",
+            "create-secret",
+            "https://example.com/s/abc123#key",
+        ]);
+        assert!(
+            result.is_err(),
+            "Expected error for missing name, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_decrypt_command_requires_key() {
+        let result = Args::try_parse_from(["hakanai", "decrypt", "blob.hakanai"]);
+        assert!(
+            result.is_err(),
+            "Expected error for missing key, got: {:?}",
+            result
+        );
+    }
 }
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+use zeroize::Zeroizing;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Returns the editor configured by `$VISUAL` or `$EDITOR`, falling back to vi.
+pub fn default_editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Opens the editor on a temporary file and returns its content after the editor exits.
+///
+/// The temporary file is only accessible by the current user and is overwritten with zeros
+/// before it is deleted. Editors writing backup or swap files might still leave copies on disk.
+pub fn compose_secret(editor: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or(anyhow!("No editor configured"))?;
+
+    let file = tempfile::Builder::new()
+        .prefix("hakanai-")
+        .suffix(".txt")
+        .tempfile()?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(file.path())
+        .status()
+        .map_err(|e| anyhow!("Failed to start editor '{program}': {e}"));
+
+    let content = status.and_then(|status| {
+        if !status.success() {
+            return Err(anyhow!("Editor exited with {status}"));
+        }
+
+        Ok(Zeroizing::new(std::fs::read(file.path())?))
+    });
+
+    shred(file.path())?;
+    content
+}
+
+fn shred(path: &Path) -> Result<()> {
+    let len = std::fs::metadata(path)?.len();
+
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.write_all(&vec![0u8; len as usize])?;
+    file.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    fn editor_script(dir: &Path, script: &str) -> Result<String> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("editor.sh");
+        fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_compose_secret() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let editor = editor_script(
+            temp_dir.path(),
+            r#"printf 'line 1\nline 2' > "$1"; echo "$1" > "$(dirname "$0")/path""#,
+        )?;
+
+        let content = compose_secret(&editor)?;
+        assert_eq!(content.as_slice(), b"line 1\nline 2");
+
+        let temp_file = fs::read_to_string(temp_dir.path().join("path"))?;
+        assert!(
+            !Path::new(temp_file.trim()).exists(),
+            "Temporary file should be deleted"
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_compose_secret_temp_file_permissions() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let editor = editor_script(
+            temp_dir.path(),
+            r#"mode=$(ls -l "$1" | cut -c1-10); echo "$mode" > "$1""#,
+        )?;
+
+        let content = compose_secret(&editor)?;
+        assert_eq!(
+            String::from_utf8_lossy(&content).trim(),
+            "-rw-------",
+            "Temporary file should only be accessible by the owner"
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_compose_secret_editor_fails() {
+        let result = compose_secret("false");
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+    }
+
+    #[test]
+    fn test_compose_secret_editor_not_found() {
+        let result = compose_secret("hakanai-nonexistent-editor");
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to start editor"),
+            "Error should mention the editor"
+        );
+    }
+
+    #[test]
+    fn test_shred() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("secret.txt");
+        fs::write(&path, b"secret")?;
+
+        shred(&path)?;
+
+        assert_eq!(fs::read(&path)?, vec![0u8; 6]);
+        Ok(())
+    }
+}
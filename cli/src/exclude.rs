@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Component, Path};
+
+/// Exclude patterns for directory archiving, following a subset of the .gitignore syntax.
+///
+/// - `*` matches anything except `/`, `?` matches a single character except `/`
+/// - `**` matches anything including `/`
+/// - patterns without `/` match the name of a file or directory at any level
+/// - patterns containing `/` are matched against the path relative to the archived directory
+/// - a trailing `/` restricts the pattern to directories
+#[derive(Debug, Clone, Default)]
+pub struct ExcludePatterns {
+    patterns: Vec<ExcludePattern>,
+}
+
+#[derive(Debug, Clone)]
+struct ExcludePattern {
+    glob: Vec<char>,
+    anchored: bool,
+    dir_only: bool,
+}
+
+impl ExcludePatterns {
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(ExcludePattern::new)
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Checks if the path (relative to the archived directory) is excluded.
+    pub fn is_excluded(&self, relative_path: &Path, is_dir: bool) -> bool {
+        let path = to_slash_path(relative_path);
+        let name = path.rsplit('/').next().unwrap_or_default();
+
+        self.patterns.iter().any(|pattern| {
+            if pattern.dir_only && !is_dir {
+                return false;
+            }
+
+            let text: Vec<char> = if pattern.anchored {
+                path.chars().collect()
+            } else {
+                name.chars().collect()
+            };
+
+            glob_match(&pattern.glob, &text)
+        })
+    }
+}
+
+impl ExcludePattern {
+    fn new(pattern: &str) -> Self {
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+
+        Self {
+            glob: pattern.chars().collect(),
+            anchored,
+            dir_only,
+        }
+    }
+}
+
+/// Joins the normal components of the path with `/`, independent of the platform separator.
+pub fn to_slash_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+
+            // `**/` also matches zero directories
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], text) {
+                return true;
+            }
+
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        Some('*') => {
+            for i in 0..=text.len() {
+                if glob_match(&pattern[1..], &text[i..]) {
+                    return true;
+                }
+
+                if i < text.len() && text[i] == '/' {
+                    break;
+                }
+            }
+
+            false
+        }
+        Some('?') => match text.first() {
+            Some(c) if *c != '/' => glob_match(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some(c) => text.first() == Some(c) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> ExcludePatterns {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        ExcludePatterns::new(&patterns)
+    }
+
+    #[test]
+    fn test_name_pattern_matches_at_any_level() {
+        let excludes = patterns(&["*.log"]);
+
+        assert!(excludes.is_excluded(Path::new("debug.log"), false));
+        assert!(excludes.is_excluded(Path::new("logs/2024/debug.log"), false));
+        assert!(!excludes.is_excluded(Path::new("debug.log.txt"), false));
+    }
+
+    #[test]
+    fn test_anchored_pattern() {
+        let excludes = patterns(&["/build/*.o", "docs/tmp"]);
+
+        assert!(excludes.is_excluded(Path::new("build/main.o"), false));
+        assert!(!excludes.is_excluded(Path::new("src/build/main.o"), false));
+        assert!(!excludes.is_excluded(Path::new("build/sub/main.o"), false));
+        assert!(excludes.is_excluded(Path::new("docs/tmp"), true));
+    }
+
+    #[test]
+    fn test_dir_only_pattern() {
+        let excludes = patterns(&["target/"]);
+
+        assert!(excludes.is_excluded(Path::new("target"), true));
+        assert!(excludes.is_excluded(Path::new("sub/target"), true));
+        assert!(
+            !excludes.is_excluded(Path::new("target"), false),
+            "dir only pattern should not match files"
+        );
+    }
+
+    #[test]
+    fn test_double_star_pattern() {
+        let excludes = patterns(&["src/**/*.bak"]);
+
+        assert!(excludes.is_excluded(Path::new("src/main.bak"), false));
+        assert!(excludes.is_excluded(Path::new("src/a/b/main.bak"), false));
+        assert!(!excludes.is_excluded(Path::new("other/main.bak"), false));
+    }
+
+    #[test]
+    fn test_question_mark_pattern() {
+        let excludes = patterns(&["file?.txt"]);
+
+        assert!(excludes.is_excluded(Path::new("file1.txt"), false));
+        assert!(!excludes.is_excluded(Path::new("file10.txt"), false));
+    }
+
+    #[test]
+    fn test_empty_patterns() {
+        let excludes = patterns(&["", "  "]);
+
+        assert!(!excludes.is_excluded(Path::new("anything"), false));
+    }
+}
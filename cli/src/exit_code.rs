@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exit codes per failure class, so wrappers and cron jobs can react precisely.
+//!
+//! The codes are documented in the help text of the CLI (see `cli.rs`), keep both in sync.
+
+use std::process::ExitCode;
+
+use hakanai_lib::client::ClientError;
+
+/// Failure classes of the CLI with distinct exit codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// Any failure not covered by a more specific class
+    General,
+
+    /// Missing or invalid token
+    Auth,
+
+    /// The secret does not exist
+    NotFound,
+
+    /// The secret was already retrieved
+    Gone,
+
+    /// Access denied by restrictions of the secret (IP, country, ASN or passphrase)
+    RestrictionDenied,
+
+    /// The server could not be reached or did not respond in time
+    Network,
+}
+
+impl Failure {
+    /// Returns the exit code of the failure class, 2 is used by clap for invalid arguments.
+    pub fn code(self) -> u8 {
+        match self {
+            Failure::General => 1,
+            Failure::Auth => 3,
+            Failure::NotFound => 4,
+            Failure::Gone => 5,
+            Failure::RestrictionDenied => 6,
+            Failure::Network => 7,
+        }
+    }
+
+    /// Classifies the error, `receiving` is set for commands retrieving secrets where the server
+    /// answers denied restrictions (e.g. a wrong passphrase) with 401 or 403.
+    pub fn classify(err: &anyhow::Error, receiving: bool) -> Self {
+        for cause in err.chain() {
+            if let Some(client_err) = cause.downcast_ref::<ClientError>() {
+                if let ClientError::Web(web_err) = client_err {
+                    return Self::from_reqwest(web_err, receiving);
+                }
+
+                if let Some(status) = client_err.http_status() {
+                    return Self::from_status(status, receiving);
+                }
+            }
+
+            if let Some(web_err) = cause.downcast_ref::<reqwest::Error>() {
+                return Self::from_reqwest(web_err, receiving);
+            }
+        }
+
+        Failure::General
+    }
+
+    fn from_reqwest(err: &reqwest::Error, receiving: bool) -> Self {
+        match err.status() {
+            Some(status) => Self::from_status(status.as_u16(), receiving),
+            None if err.is_connect() || err.is_timeout() || err.is_request() => Failure::Network,
+            None => Failure::General,
+        }
+    }
+
+    fn from_status(status: u16, receiving: bool) -> Self {
+        match status {
+            401 | 403 if receiving => Failure::RestrictionDenied,
+            401 | 403 => Failure::Auth,
+            404 => Failure::NotFound,
+            410 => Failure::Gone,
+            _ => Failure::General,
+        }
+    }
+}
+
+impl From<Failure> for ExitCode {
+    fn from(failure: Failure) -> Self {
+        ExitCode::from(failure.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::anyhow;
+
+    fn http_error(status: &str) -> anyhow::Error {
+        anyhow::Error::new(ClientError::Http(format!("HTTP error: {status}")))
+    }
+
+    #[test]
+    fn test_classify_http_status() {
+        assert_eq!(
+            Failure::classify(&http_error("404 Not Found"), true),
+            Failure::NotFound
+        );
+        assert_eq!(
+            Failure::classify(&http_error("410 Gone"), true),
+            Failure::Gone
+        );
+        assert_eq!(
+            Failure::classify(&http_error("500 Internal Server Error"), false),
+            Failure::General
+        );
+    }
+
+    #[test]
+    fn test_classify_denied_depends_on_command() {
+        assert_eq!(
+            Failure::classify(&http_error("403 Forbidden"), false),
+            Failure::Auth,
+            "Rejected token should be an auth failure when sending"
+        );
+        assert_eq!(
+            Failure::classify(&http_error("401 Unauthorized"), true),
+            Failure::RestrictionDenied,
+            "Missing passphrase should be a denied restriction when receiving"
+        );
+    }
+
+    #[test]
+    fn test_classify_with_context() {
+        let err = http_error("410 Gone").context("Failed to retrieve secret");
+        assert_eq!(
+            Failure::classify(&err, true),
+            Failure::Gone,
+            "Causes of the error should be inspected"
+        );
+    }
+
+    #[test]
+    fn test_classify_other_errors() {
+        assert_eq!(
+            Failure::classify(&anyhow!("No link provided"), true),
+            Failure::General
+        );
+    }
+
+    #[test]
+    fn test_codes_are_distinct() {
+        let failures = [
+            Failure::General,
+            Failure::Auth,
+            Failure::NotFound,
+            Failure::Gone,
+            Failure::RestrictionDenied,
+            Failure::Network,
+        ];
+        let codes: std::collections::HashSet<u8> = failures.iter().map(|f| f.code()).collect();
+        assert_eq!(codes.len(), failures.len(), "Exit codes should be distinct");
+        assert!(!codes.contains(&2), "Exit code 2 is used by clap");
+    }
+}
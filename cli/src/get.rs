@@ -1,140 +1,748 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
 use std::env::current_dir;
-use std::fs::OpenOptions;
+use std::fs::{self, File, OpenOptions};
 use std::io;
-use std::io::{Cursor, Read, Write};
-use std::path::{Path, PathBuf};
+use std::io::{Cursor, IsTerminal, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, anyhow};
 use colored::Colorize;
+use url::Url;
 use zeroize::Zeroizing;
 use zip::ZipArchive;
 
 use hakanai_lib::client::Client;
-use hakanai_lib::models::Payload;
-use hakanai_lib::options::SecretReceiveOptions;
-use hakanai_lib::utils::timestamp;
+use hakanai_lib::models::{Payload, PayloadDataType, ReplySlot};
+use hakanai_lib::options::{SecretReceiveOptions, SecretSendOptions};
+use hakanai_lib::utils::{hashing, timestamp};
 
-use crate::args::GetArgs;
+use crate::archive::{ArchiveFormat, is_enclosed_symlink, system_time_from_zip, traverses_symlink};
+use crate::args::{GetArgs, OnConflict};
 use crate::factory::Factory;
+use crate::get_many;
 use crate::helper;
+use crate::i18n::{Message, t};
+use crate::integrations::{SopsEncoding, SopsTarget};
+use crate::platform;
+use crate::version;
 
 pub async fn get<T: Factory>(factory: T, args: GetArgs) -> Result<()> {
     args.validate()?;
 
+    if let Some(path) = args.from_file.clone() {
+        return get_many::get_many(factory, args, &path).await;
+    }
+
     let user_agent = helper::get_user_agent_name();
     let observer = factory.new_observer("Receiving secret...")?;
     let mut opts = SecretReceiveOptions::default()
         .with_user_agent(user_agent)
         .with_observer(observer);
 
+    if let Some(passphrase) = read_passphrase(&args)? {
+        opts = opts.with_passphrase(passphrase.as_ref());
+    }
+
+    if let Some(pin) = read_pin(&args)? {
+        opts = opts.with_pin(&pin);
+    }
+
+    if let Some(rate) = args.limit_rate {
+        opts = opts.with_rate_limit(rate as u64);
+    }
+
+    if let Some(max_memory) = args.max_memory {
+        opts = opts.with_max_memory(max_memory as usize);
+    }
+
+    if let Some(ref target) = args.to_sops {
+        target.check(&args.sops)?;
+    }
+
+    let url = args.secret_url()?.clone();
+    let server = url.join("/")?;
+    version::warn_if_incompatible(&factory, server.clone()).await;
+    let mut payload = factory.new_client().receive_secret(url, Some(opts)).await?;
+
+    if let Some(ref expected_hash) = args.verify_hash {
+        verify_hash(&payload, expected_hash)?;
+    }
+
+    let reply = payload.reply.take();
+    print_description(&payload);
+    match args.to_sops {
+        Some(ref target) => output_to_sops(payload, target, &args.sops)?,
+        None => output_secret(payload, args.clone())?,
+    }
+
+    if let Some(ref reply_file) = args.reply_file {
+        let reply = reply.ok_or(anyhow!("The sender did not allow a reply to this secret."))?;
+        send_reply(&factory, server, reply, reply_file, &args).await?;
+    }
+
+    Ok(())
+}
+
+/// Sends the content of the file as reply into the slot reserved by the sender.
+///
+/// The upload token of the slot authorizes the upload, no token of the recipient is required.
+async fn send_reply<T: Factory>(
+    factory: &T,
+    server: Url,
+    reply: ReplySlot,
+    reply_file: &Path,
+    args: &GetArgs,
+) -> Result<()> {
+    let bytes = fs::read(platform::long_path(reply_file))?;
+    let mut payload = Payload::from_vec(bytes);
+    if let Some(name) = reply_file.file_name().and_then(|name| name.to_str()) {
+        payload = payload.with_filename(name);
+    }
+
+    let observer = factory.new_observer("Sending reply...")?;
+    let mut opts = SecretSendOptions::default()
+        .with_user_agent(helper::get_user_agent_name())
+        .with_observer(observer)
+        .with_reply_to(reply);
+
+    if let Some(rate) = args.limit_rate {
+        opts = opts.with_rate_limit(rate as u64);
+    }
+
+    factory
+        .new_client()
+        .send_secret(server, payload, args.reply_ttl, String::new(), Some(opts))
+        .await?;
+
+    eprintln!("{}", t(Message::ReplySent).green());
+    Ok(())
+}
+
+/// Returns the passphrase given as argument or asks for it if requested.
+pub(crate) fn read_passphrase(args: &GetArgs) -> Result<Option<Zeroizing<Vec<u8>>>> {
     if let Some(ref passphrase) = args.passphrase {
-        let bytes = Zeroizing::new(passphrase.bytes().collect::<Vec<u8>>());
-        opts = opts.with_passphrase(bytes.as_ref());
+        return Ok(Some(Zeroizing::new(passphrase.bytes().collect())));
     }
 
     if args.ask_passphrase {
-        let passphrase = rpassword::prompt_password("Passphrase: ")?;
-        let bytes = Zeroizing::new(passphrase.bytes().collect::<Vec<u8>>());
-        opts = opts.with_passphrase(bytes.as_ref());
+        let passphrase = Zeroizing::new(rpassword::prompt_password("Passphrase: ")?);
+        return Ok(Some(Zeroizing::new(passphrase.bytes().collect())));
     }
 
-    let url = args.secret_url()?.clone();
-    let payload = factory.new_client().receive_secret(url, Some(opts)).await?;
+    Ok(None)
+}
+
+/// Returns the PIN given as argument or asks for it if requested.
+fn read_pin(args: &GetArgs) -> Result<Option<Zeroizing<String>>> {
+    if let Some(ref pin) = args.pin {
+        return Ok(Some(Zeroizing::new(pin.clone())));
+    }
+
+    if args.ask_pin {
+        let pin = Zeroizing::new(rpassword::prompt_password("PIN: ")?);
+        return Ok(Some(pin));
+    }
+
+    Ok(None)
+}
 
-    output_secret(payload, args.clone())?;
+/// Stores the secret in the SOPS file, binary secrets are stored base64 encoded.
+fn output_to_sops(payload: Payload, target: &SopsTarget, sops: &str) -> Result<()> {
+    let encoding = target.write(sops, &payload.into_bytes())?;
+    if encoding == SopsEncoding::Base64 {
+        eprintln!(
+            "{}",
+            "The secret is no valid UTF-8 text and was stored base64 encoded.".yellow()
+        );
+    }
 
+    eprintln!("{} {}", t(Message::SavedTo), target.to_string().cyan());
     Ok(())
 }
 
-fn output_secret(payload: Payload, args: GetArgs) -> Result<()> {
-    let bytes = Zeroizing::new(payload.data.clone());
-    let filename = args.filename.or_else(|| payload.filename.clone());
+fn output_secret(mut payload: Payload, args: GetArgs) -> Result<()> {
+    let bytes = payload.take_data();
+    let filename = args
+        .filename
+        .or_else(|| payload.filename.as_deref().map(platform::sanitize_filename));
     let output_directory = match args.output_dir {
         Some(dir) => dir,
         None => current_dir()?,
     };
 
     if args.to_stdout {
-        print_to_stdout(&bytes)?;
+        print_to_stdout(&bytes, args.force)?;
     } else if let Some(name) = payload.filename.clone()
         && args.extract
         && is_archive(&name)
+        && let Some(format) = ArchiveFormat::detect(&bytes)
     {
-        extract_archive(name, &bytes, &output_directory)?;
+        let limits = ExtractLimits {
+            max_size: args.max_extract_size as u64,
+            max_entries: args.max_extract_entries,
+            max_ratio: args.max_compression_ratio,
+        };
+        extract_archive(
+            name,
+            format,
+            &bytes,
+            &output_directory,
+            &limits,
+            args.on_conflict,
+        )?;
     } else if let Some(file) = filename {
         write_to_file(
             file,
             Cursor::<&[u8]>::new(bytes.as_ref()),
             &output_directory,
+            args.on_conflict,
+        )?;
+    } else if payload.data_type() == PayloadDataType::Binary {
+        // binary content should not end up in the terminal, save it to a file instead
+        let file = format!("secret-{}", timestamp::now_string()?);
+        write_to_file(
+            file,
+            Cursor::<&[u8]>::new(bytes.as_ref()),
+            &output_directory,
+            args.on_conflict,
         )?;
     } else {
-        print_to_stdout(&bytes)?;
+        print_to_stdout(&bytes, args.force)?;
+    }
+
+    Ok(())
+}
+
+fn verify_hash(payload: &Payload, expected_hash: &str) -> Result<()> {
+    let actual_hash = hashing::sha256_hex_from_bytes(&payload.data);
+    if !actual_hash.eq_ignore_ascii_case(expected_hash) {
+        return Err(anyhow!(
+            "SHA-256 hash mismatch: expected {expected_hash}, got {actual_hash}"
+        ));
     }
 
+    eprintln!("{}", t(Message::HashVerified).green());
     Ok(())
 }
 
+fn print_description(payload: &Payload) {
+    if let Some(description) = &payload.description {
+        // strip control characters to prevent terminal escape sequences from the sender
+        let sanitized: String = description.chars().filter(|c| !c.is_control()).collect();
+        eprintln!("{} {}", t(Message::Description).bold(), sanitized.cyan());
+    }
+
+    if let Some(content_type) = &payload.content_type {
+        let sanitized: String = content_type.chars().filter(|c| !c.is_control()).collect();
+        eprintln!("{} {}", t(Message::ContentType).bold(), sanitized);
+    }
+}
+
 fn is_archive(filename: &str) -> bool {
-    filename.to_lowercase().ends_with(".zip")
+    let filename = filename.to_lowercase();
+    filename.ends_with(".zip") || filename.ends_with(".tar.zst") || filename.ends_with(".tzst")
+}
+
+pub(crate) fn print_to_stdout(bytes: &[u8], force: bool) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    check_terminal_output(bytes, stdout.is_terminal(), force)?;
+    stdout.write_all(bytes)?;
+    Ok(())
 }
 
-fn print_to_stdout(bytes: &[u8]) -> Result<()> {
-    std::io::stdout().write_all(bytes)?;
+/// Refuses to print binary data or control sequences to a terminal unless forced, like curl does.
+fn check_terminal_output(bytes: &[u8], is_terminal: bool, force: bool) -> Result<()> {
+    if is_terminal && !force && !is_printable(bytes) {
+        return Err(anyhow!(
+            "The secret contains binary data or control characters which could mess up your terminal. Use --force to print it anyway or --filename to save it to a file."
+        ));
+    }
+
     Ok(())
 }
 
-fn extract_archive(filename: String, bytes: &[u8], target_dir: &Path) -> Result<()> {
+/// Returns true if the bytes are UTF-8 text without control characters other than whitespace.
+fn is_printable(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|text| {
+        text.chars()
+            .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+    })
+}
+
+/// Limits protecting against archive bombs filling the disk.
+#[derive(Debug, Clone, Copy)]
+struct ExtractLimits {
+    /// Maximum total size of all extracted entries in bytes.
+    max_size: u64,
+
+    /// Maximum number of entries in the archive.
+    max_entries: usize,
+
+    /// Maximum ratio between total extracted size and archive size.
+    max_ratio: u64,
+}
+
+#[cfg(test)]
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        Self {
+            max_size: 1024 * 1024 * 1024,
+            max_entries: 10_000,
+            max_ratio: 100,
+        }
+    }
+}
+
+/// Tracks the extracted entries against the limits.
+///
+/// Declared sizes are checked before an entry is extracted. Entry headers can understate the size
+/// of the data, so entries are read up to their declared size and the bytes actually written are
+/// counted.
+struct ExtractBudget {
+    limits: ExtractLimits,
+    archive_size: u64,
+    entries: usize,
+    total_size: u64,
+}
+
+impl ExtractBudget {
+    /// The compression ratio is only enforced above this size, small text files compress well.
+    const RATIO_THRESHOLD: u64 = 1024 * 1024;
+
+    fn new(limits: &ExtractLimits, archive_size: usize) -> Self {
+        Self {
+            limits: *limits,
+            archive_size: archive_size as u64,
+            entries: 0,
+            total_size: 0,
+        }
+    }
+
+    fn add_entry(&mut self, declared_size: u64) -> Result<()> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(anyhow!(
+                "Archive contains more than {} entries, aborting extraction",
+                self.limits.max_entries
+            ));
+        }
+
+        self.check_size(self.total_size.saturating_add(declared_size))
+    }
+
+    fn add_written(&mut self, size: u64) -> Result<()> {
+        self.total_size = self.total_size.saturating_add(size);
+        self.check_size(self.total_size)
+    }
+
+    fn check_size(&self, total_size: u64) -> Result<()> {
+        if total_size > self.limits.max_size {
+            return Err(anyhow!(
+                "Archive exceeds the maximum extracted size of {} bytes, aborting extraction",
+                self.limits.max_size
+            ));
+        }
+
+        if total_size > Self::RATIO_THRESHOLD
+            && total_size > self.archive_size.saturating_mul(self.limits.max_ratio)
+        {
+            return Err(anyhow!(
+                "Archive exceeds the maximum compression ratio of {}, aborting extraction",
+                self.limits.max_ratio
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes an archive entry, reading at most one byte more than its declared size.
+///
+/// An entry exceeding its declared size aborts the extraction and its partial file is removed.
+fn write_entry<R: Read>(
+    relative_path: &Path,
+    r: R,
+    declared_size: u64,
+    target_dir: &Path,
+    budget: &mut ExtractBudget,
+    on_conflict: OnConflict,
+) -> Result<PathBuf> {
+    let read_limit = declared_size.saturating_add(1);
+    let mut reader = r.take(read_limit);
+    let path = write_to_file(
+        relative_path.to_string_lossy().to_string(),
+        &mut reader,
+        target_dir,
+        on_conflict,
+    )?;
+
+    let written = read_limit - reader.limit();
+    if written > declared_size {
+        fs::remove_file(platform::long_path(&path))?;
+        return Err(anyhow!(
+            "Archive entry {} exceeds its declared size, aborting extraction",
+            relative_path.display()
+        ));
+    }
+
+    budget.add_written(written)?;
+    Ok(path)
+}
+
+fn extract_archive(
+    filename: String,
+    format: ArchiveFormat,
+    bytes: &[u8],
+    target_dir: &Path,
+    limits: &ExtractLimits,
+    on_conflict: OnConflict,
+) -> Result<()> {
+    println!("{} {}", t(Message::ExtractingArchive), filename.cyan());
+    let budget = ExtractBudget::new(limits, bytes.len());
+    match format {
+        ArchiveFormat::Zip => extract_zip(bytes, target_dir, budget, on_conflict),
+        ArchiveFormat::TarZst => extract_tar_zst(bytes, target_dir, budget, on_conflict),
+    }
+}
+
+fn extract_zip(
+    bytes: &[u8],
+    target_dir: &Path,
+    mut budget: ExtractBudget,
+    on_conflict: OnConflict,
+) -> Result<()> {
     let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+    let mut deferred = DeferredEntries::default();
 
-    println!("Extracting archive: {}", filename.cyan());
     for i in 0..archive.len() {
         let file = archive.by_index(i)?;
+        let declared_size = file.size();
+        budget.add_entry(declared_size)?;
+
+        // enclosed_name rejects absolute paths and paths escaping the target directory
+        let Some(relative_path) = file.enclosed_name() else {
+            return Err(anyhow!("Invalid path in archive: {}", file.name()));
+        };
+
+        let mode = file.unix_mode();
         if file.is_dir() {
-            continue; // skip directories
+            deferred.create_directory(target_dir, relative_path, mode)?;
+            continue;
         }
 
-        let name = file.name().to_string();
+        if file.is_symlink() {
+            let mut target = String::new();
+            file.take(declared_size).read_to_string(&mut target)?;
+            deferred.add_symlink(relative_path, PathBuf::from(target))?;
+            continue;
+        }
 
-        // extract flat, just use the filename
-        let flat_name = PathBuf::from(&name)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&name)
-            .to_string();
-        write_to_file(flat_name, file, target_dir)?;
+        if let Some(parent) = relative_path.parent() {
+            std::fs::create_dir_all(platform::long_path(&target_dir.join(parent)))?;
+        }
+
+        let mtime = file
+            .last_modified()
+            .and_then(|datetime| system_time_from_zip(&datetime));
+        let path = write_entry(
+            &relative_path,
+            file,
+            declared_size,
+            target_dir,
+            &mut budget,
+            on_conflict,
+        )?;
+        restore_metadata(&path, mode, mtime)?;
+    }
+
+    deferred.apply(target_dir)
+}
+
+fn extract_tar_zst(
+    bytes: &[u8],
+    target_dir: &Path,
+    mut budget: ExtractBudget,
+    on_conflict: OnConflict,
+) -> Result<()> {
+    let decoder = zstd::Decoder::new(bytes)?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut deferred = DeferredEntries::default();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let declared_size = entry.size();
+        budget.add_entry(declared_size)?;
+        let entry_path = entry.path()?.into_owned();
+        let entry_type = entry.header().entry_type();
+
+        // archives created with `tar -C dir .` contain the root directory itself
+        if entry_type.is_dir() && entry_path.components().all(|c| c == Component::CurDir) {
+            continue;
+        }
+
+        let Some(relative_path) = enclosed_path(&entry_path) else {
+            return Err(anyhow!("Invalid path in archive: {}", entry_path.display()));
+        };
+
+        let mode = entry.header().mode()?;
+        if entry_type.is_dir() {
+            deferred.create_directory(target_dir, relative_path, Some(mode))?;
+            continue;
+        }
+
+        if entry_type.is_symlink() {
+            let target = entry
+                .link_name()?
+                .ok_or(anyhow!(
+                    "Missing symlink target in archive: {}",
+                    relative_path.display()
+                ))?
+                .into_owned();
+            deferred.add_symlink(relative_path, target)?;
+            continue;
+        }
+
+        if !entry_type.is_file() {
+            let warn_message = format!(
+                "Skipping unsupported archive entry: {}",
+                relative_path.display()
+            );
+            eprintln!("{}", warn_message.yellow());
+            continue;
+        }
+
+        if let Some(parent) = relative_path.parent() {
+            std::fs::create_dir_all(platform::long_path(&target_dir.join(parent)))?;
+        }
+
+        let mtime = UNIX_EPOCH + Duration::from_secs(entry.header().mtime()?);
+        let path = write_entry(
+            &relative_path,
+            &mut entry,
+            declared_size,
+            target_dir,
+            &mut budget,
+            on_conflict,
+        )?;
+        restore_metadata(&path, Some(mode), Some(mtime))?;
+    }
+
+    deferred.apply(target_dir)
+}
+
+/// Archive entries applied after all files are extracted.
+///
+/// Symlinks are created last so they cannot redirect writes of later entries, directory
+/// permissions are applied last so read-only directories do not prevent extracting their contents.
+#[derive(Default)]
+struct DeferredEntries {
+    symlinks: Vec<(PathBuf, PathBuf)>,
+    directory_modes: Vec<(PathBuf, u32)>,
+}
+
+impl DeferredEntries {
+    fn create_directory(
+        &mut self,
+        target_dir: &Path,
+        relative_path: PathBuf,
+        mode: Option<u32>,
+    ) -> Result<()> {
+        let path = target_dir.join(&relative_path);
+        let existed = path.exists();
+        std::fs::create_dir_all(&path)?;
+
+        // permissions of already existing directories are never changed
+        if let Some(mode) = mode
+            && !existed
+        {
+            self.directory_modes.push((relative_path, mode));
+        }
+
+        Ok(())
+    }
+
+    fn add_symlink(&mut self, relative_path: PathBuf, target: PathBuf) -> Result<()> {
+        if !is_enclosed_symlink(&relative_path, &target) {
+            return Err(anyhow!(
+                "Invalid symlink in archive: {} -> {}",
+                relative_path.display(),
+                target.display()
+            ));
+        }
+
+        self.symlinks.push((relative_path, target));
+        Ok(())
+    }
+
+    fn apply(self, target_dir: &Path) -> Result<()> {
+        // symlinks of the archive are checked as a whole, a target may pass through any of them
+        let links: HashSet<PathBuf> = self
+            .symlinks
+            .iter()
+            .filter_map(|(relative_path, _)| enclosed_path(relative_path))
+            .collect();
+        for (relative_path, target) in &self.symlinks {
+            let is_symlink = |path: &Path| {
+                links.contains(path)
+                    || target_dir
+                        .join(path)
+                        .symlink_metadata()
+                        .is_ok_and(|metadata| metadata.file_type().is_symlink())
+            };
+
+            if traverses_symlink(relative_path, target, is_symlink) {
+                return Err(anyhow!(
+                    "Invalid symlink in archive: {} -> {}",
+                    relative_path.display(),
+                    target.display()
+                ));
+            }
+        }
+
+        for (relative_path, target) in self.symlinks {
+            create_symlink(target_dir, &relative_path, &target)?;
+        }
+
+        // apply children first, a read-only parent would prevent changing them
+        for (relative_path, mode) in self.directory_modes.into_iter().rev() {
+            set_permissions(&target_dir.join(relative_path), mode)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn create_symlink(target_dir: &Path, relative_path: &Path, target: &Path) -> Result<()> {
+    // a symlink created through another symlink could point outside of the target directory
+    let has_symlink_ancestor = relative_path
+        .ancestors()
+        .skip(1)
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .any(|ancestor| {
+            target_dir
+                .join(ancestor)
+                .symlink_metadata()
+                .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        });
+
+    let path = target_dir.join(relative_path);
+    if has_symlink_ancestor || path.symlink_metadata().is_ok() {
+        let warn_message = format!("Skipping symlink {}", relative_path.display());
+        eprintln!("{}", warn_message.yellow());
+        return Ok(());
+    }
+
+    if !cfg!(unix) {
+        let warn_message = format!(
+            "Symlinks are not supported on this platform, skipping {}",
+            relative_path.display()
+        );
+        eprintln!("{}", warn_message.yellow());
+        return Ok(());
+    }
+
+    symlink(target, &path)?;
+
+    let success_message = format!(
+        "Linked: {} -> {}",
+        relative_path.display().to_string().cyan(),
+        target.display()
+    );
+    println!("{success_message}");
+
+    Ok(())
+}
+
+/// Returns the path if it is relative and does not escape the target directory.
+fn enclosed_path(path: &Path) -> Option<PathBuf> {
+    let mut enclosed = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => enclosed.push(name),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+
+    if enclosed.as_os_str().is_empty() {
+        return None;
+    }
+
+    Some(enclosed)
+}
+
+fn restore_metadata(path: &Path, mode: Option<u32>, mtime: Option<SystemTime>) -> Result<()> {
+    // set modification time first, the mode might remove write permissions
+    if let Some(mtime) = mtime {
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_modified(mtime)?;
+    }
+
+    if let Some(mode) = mode {
+        set_permissions(path, mode)?;
     }
 
     Ok(())
 }
 
-fn write_to_file<T: Read>(filename: String, mut r: T, target_dir: &Path) -> Result<()> {
+#[cfg(unix)]
+fn set_permissions(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // setuid, setgid and sticky bits from the sender are never applied
+    let permissions = std::fs::Permissions::from_mode(mode & 0o777);
+    std::fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_permissions(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, path: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _path: &Path) -> Result<()> {
+    Err(anyhow!("Symlinks are not supported on this platform"))
+}
+
+pub(crate) fn write_to_file<T: Read>(
+    filename: String,
+    mut r: T,
+    target_dir: &Path,
+    on_conflict: OnConflict,
+) -> Result<PathBuf> {
     if filename.is_empty() {
         return Err(anyhow!("Filename cannot be empty"));
     }
 
     let path = PathBuf::from(&target_dir).join(filename.clone());
-    let file_res = OpenOptions::new()
-        .write(true)
-        .create_new(true) // fail if file exists
-        .open(&path);
-
-    match file_res {
-        Ok(mut f) => io::copy(&mut r, &mut f)?,
-        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
-            return write_to_timestamped_file(filename, r, target_dir);
-        }
-        Err(e) => return Err(e)?,
+    let Some(mut file) = create_output_file(&path, on_conflict)? else {
+        return write_to_timestamped_file(filename, r, target_dir);
     };
+    io::copy(&mut r, &mut file)?;
 
-    let success_message = format!("Saved to: {}", filename.cyan());
+    let success_message = format!("{} {}", t(Message::SavedTo), filename.cyan());
     println!("{success_message}");
 
-    Ok(())
+    Ok(path)
 }
 
-fn write_to_timestamped_file<T: Read>(filename: String, r: T, target_dir: &Path) -> Result<()> {
+fn write_to_timestamped_file<T: Read>(
+    filename: String,
+    r: T,
+    target_dir: &Path,
+) -> Result<PathBuf> {
     let timestamp = timestamp::now_string()?;
     let filename_with_timestamp = format!("{filename}.{timestamp}");
 
@@ -143,7 +751,39 @@ fn write_to_timestamped_file<T: Read>(filename: String, r: T, target_dir: &Path)
     );
     eprintln!("{}", warn_message.yellow());
 
-    write_to_file(filename_with_timestamp, r, target_dir)
+    write_to_file(filename_with_timestamp, r, target_dir, OnConflict::Rename)
+}
+
+/// Creates the file to save a secret to, an existing file is handled according to `on_conflict`.
+///
+/// Returns `None` if the file exists and the secret should be saved under a different name.
+/// Only regular files are overwritten, so a planted symlink cannot redirect the secret.
+pub(crate) fn create_output_file(path: &Path, on_conflict: OnConflict) -> Result<Option<File>> {
+    let path = platform::long_path(path);
+    let err = match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(file) => return Ok(Some(file)),
+        Err(e) => e,
+    };
+
+    if err.kind() != io::ErrorKind::AlreadyExists {
+        return Err(err.into());
+    }
+
+    match on_conflict {
+        OnConflict::Rename => Ok(None),
+        OnConflict::Fail => Err(anyhow!("File '{}' already exists.", path.display())),
+        OnConflict::Overwrite => {
+            if !fs::symlink_metadata(&path)?.file_type().is_file() {
+                return Err(anyhow!(
+                    "Refusing to overwrite '{}', it is not a regular file.",
+                    path.display()
+                ));
+            }
+
+            let file = OpenOptions::new().write(true).truncate(true).open(&path)?;
+            Ok(Some(file))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -238,6 +878,58 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_with_reply_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let reply_file = temp_dir.path().join("answer.txt");
+        fs::write(&reply_file, b"my answer")?;
+
+        let reply = ReplySlot::new(
+            "01KF0SR30C1X5CASYPDAJ0G6GB".parse()?,
+            "key".to_string(),
+            "upload-token".to_string(),
+        );
+        let payload = Payload::from_bytes(b"question").with_reply(reply);
+        let client =
+            MockClient::new().with_success("https://example.com/s/reply".parse()?, payload);
+        let factory = MockFactory::new().with_client(client.clone());
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_to_stdout()
+            .with_reply_file(&reply_file);
+        get(factory, args).await?;
+
+        let sent = client.get_sent_data().expect("Reply should be sent");
+        assert_eq!(sent.data, b"my answer");
+        assert_eq!(sent.filename.as_deref(), Some("answer.txt"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_with_reply_file_not_allowed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let reply_file = temp_dir.path().join("answer.txt");
+        fs::write(&reply_file, b"my answer")?;
+
+        let client = MockClient::new().with_receive_success(Payload::from_bytes(b"question"));
+        let factory = MockFactory::new().with_client(client.clone());
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_to_stdout()
+            .with_reply_file(&reply_file);
+        let result = get(factory, args).await;
+
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.to_string().contains("did not allow a reply")),
+            "Expected error, got: {:?}",
+            result
+        );
+        assert!(!client.was_send_called(), "No reply should be sent");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_client_error() -> Result<()> {
         let client = MockClient::new().with_receive_failure("Network timeout".to_string());
@@ -299,12 +991,200 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_file_on_conflict_overwrite() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("existing.txt");
+        fs::write(&file_path, "existing content that is longer")?;
+
+        let payload = Payload::from_bytes(b"new content");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_filename(file_path.to_string_lossy().as_ref())
+            .with_on_conflict(OnConflict::Overwrite);
+        get(factory, args).await?;
+
+        assert_eq!(fs::read_to_string(&file_path)?, "new content");
+        assert_eq!(
+            fs::read_dir(temp_dir.path())?.count(),
+            1,
+            "No renamed file should be created"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_file_on_conflict_fail() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("existing.txt");
+        fs::write(&file_path, "existing content")?;
+
+        let payload = Payload::from_bytes(b"new content");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_filename(file_path.to_string_lossy().as_ref())
+            .with_on_conflict(OnConflict::Fail);
+        let result = get(factory, args).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("already exists")),
+            "Existing file should result in an error"
+        );
+        assert_eq!(fs::read_to_string(&file_path)?, "existing content");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_output_file_does_not_overwrite_symlink() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let target = temp_dir.path().join("target.txt");
+        fs::write(&target, "target content")?;
+        let link = temp_dir.path().join("link.txt");
+        std::os::unix::fs::symlink(&target, &link)?;
+
+        let result = create_output_file(&link, OnConflict::Overwrite);
+        assert!(result.is_err(), "Symlinks should not be overwritten");
+        assert_eq!(fs::read_to_string(&target)?, "target content");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_sanitizes_payload_filename() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let payload = Payload::from_bytes(b"content").with_filename("../invoice\u{202E}fdp.exe");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_output_dir(temp_dir.path().to_string_lossy().as_ref());
+        get(factory, args).await?;
+
+        assert_eq!(
+            fs::read(temp_dir.path().join(".._invoicefdp.exe"))?,
+            b"content",
+            "Payload filename should be sanitized and stay in the output directory"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_verify_hash_success() -> Result<()> {
+        let payload = Payload::from_bytes(b"hello");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_to_stdout()
+            .with_verify_hash("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        get(factory, args).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_verify_hash_mismatch() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let payload = Payload::from_bytes(b"tampered").with_filename("image.iso");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_output_dir(temp_dir.path().to_string_lossy().as_ref())
+            .with_verify_hash("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+        let result = get(factory, args).await;
+
+        assert!(result.is_err(), "Expected hash mismatch, got: {:?}", result);
+        assert!(
+            !temp_dir.path().join("image.iso").exists(),
+            "File should not be written on hash mismatch"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_binary_content_type_saved_to_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let payload = Payload::from_bytes(b"%PDF-1.7").with_content_type("application/pdf");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_output_dir(temp_dir.path().to_string_lossy().as_ref());
+        get(factory, args).await?;
+
+        let files: Vec<_> = fs::read_dir(temp_dir.path())?
+            .filter_map(|entry| entry.ok())
+            .collect();
+        assert_eq!(files.len(), 1, "Binary content should be saved to a file");
+        assert!(
+            files[0]
+                .file_name()
+                .to_string_lossy()
+                .starts_with("secret-"),
+            "File name should be generated"
+        );
+        assert_eq!(fs::read(files[0].path())?, b"%PDF-1.7");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_text_content_type_printed_to_stdout() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let payload = Payload::from_bytes(b"plain text")
+            .with_content_type("text/plain")
+            .with_description("Some text");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_output_dir(temp_dir.path().to_string_lossy().as_ref());
+        get(factory, args).await?;
+
+        let count = fs::read_dir(temp_dir.path())?.count();
+        assert_eq!(count, 0, "Text content should not be saved to a file");
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_terminal_output() {
+        let binary = [0x00, 0x01, 0xFF, 0xFE];
+        let escape = b"\x1b]0;pwned\x07text";
+        let text = "multi\nline\ttext with ümlauts\r\n".as_bytes();
+
+        assert!(
+            check_terminal_output(&binary, true, false).is_err(),
+            "Binary data should not be printed to a terminal"
+        );
+        assert!(
+            check_terminal_output(escape, true, false).is_err(),
+            "Escape sequences should not be printed to a terminal"
+        );
+        assert!(
+            check_terminal_output(text, true, false).is_ok(),
+            "Text should be printed to a terminal"
+        );
+        assert!(
+            check_terminal_output(&binary, true, true).is_ok(),
+            "Binary data should be printed with --force"
+        );
+        assert!(
+            check_terminal_output(&binary, false, false).is_ok(),
+            "Binary data should be printed if stdout is no terminal"
+        );
+    }
+
     // Tests for archive extraction
     #[test]
     fn test_is_archive() {
         assert!(is_archive("test.zip"));
         assert!(is_archive("archive.ZIP"));
         assert!(is_archive("my-files.zip"));
+        assert!(is_archive("scripts.tar.zst"));
+        assert!(is_archive("scripts.tzst"));
         assert!(!is_archive("test.tar"));
         assert!(!is_archive("test.gz"));
         assert!(!is_archive("test.txt"));
@@ -341,13 +1221,19 @@ mod tests {
         }
 
         // Extract to the temp directory
-        extract_archive("test.zip".to_string(), &zip_data, temp_dir.path())?;
+        extract_archive(
+            "test.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        )?;
 
-        // Verify extracted files - all files are extracted flat (no subdirectories)
+        // Verify extracted files - directory structure is preserved
         assert!(temp_dir.path().join("file1.txt").exists());
         assert!(temp_dir.path().join("file2.txt").exists());
-        // subdir/file3.txt is extracted as just file3.txt
-        assert!(temp_dir.path().join("file3.txt").exists());
+        assert!(temp_dir.path().join("subdir/file3.txt").exists());
 
         let content1 = fs::read_to_string(temp_dir.path().join("file1.txt"))?;
         assert_eq!(content1, "Content of file 1");
@@ -355,12 +1241,368 @@ mod tests {
         let content2 = fs::read_to_string(temp_dir.path().join("file2.txt"))?;
         assert_eq!(content2, "Content of file 2");
 
-        let content3 = fs::read_to_string(temp_dir.path().join("file3.txt"))?;
+        let content3 = fs::read_to_string(temp_dir.path().join("subdir/file3.txt"))?;
         assert_eq!(content3, "Content of file 3 in subdir");
 
         Ok(())
     }
 
+    #[test]
+    fn test_extract_tar_zst_preserves_structure_and_mode() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let mut builder = tar::Builder::new(zstd::Encoder::new(Vec::new(), 0)?);
+        let content = b"#!/bin/sh\necho hello\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, "scripts/run.sh", &content[..])?;
+        let data = builder.into_inner()?.finish()?;
+
+        extract_archive(
+            "scripts.tar.zst".to_string(),
+            ArchiveFormat::TarZst,
+            &data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        )?;
+
+        let path = temp_dir.path().join("scripts/run.sh");
+        assert_eq!(fs::read(&path)?, content);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path)?.permissions().mode();
+            assert_eq!(mode & 0o777, 0o755, "Unix mode should be preserved");
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_zip_restores_metadata_and_symlinks() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        use zip::ZipWriter;
+        use zip::write::SimpleFileOptions;
+
+        let temp_dir = TempDir::new()?;
+
+        // 2024-02-29 13:37:42 UTC
+        let mtime = UNIX_EPOCH + Duration::from_secs(1709213862);
+        let datetime = crate::archive::zip_datetime(mtime).ok_or(anyhow!("invalid time"))?;
+
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            let options = SimpleFileOptions::default()
+                .unix_permissions(0o755)
+                .last_modified_time(datetime);
+            zip.add_directory("bin/", SimpleFileOptions::default())?;
+            zip.start_file("bin/run.sh", options)?;
+            zip.write_all(b"#!/bin/sh")?;
+            zip.add_symlink("bin/start", "run.sh", SimpleFileOptions::default())?;
+            zip.finish()?;
+        }
+
+        extract_archive(
+            "bin.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        )?;
+
+        let script = temp_dir.path().join("bin/run.sh");
+        let metadata = fs::metadata(&script)?;
+        assert_eq!(
+            metadata.permissions().mode() & 0o777,
+            0o755,
+            "Executable bit should be restored"
+        );
+        let diff = mtime.duration_since(metadata.modified()?)?;
+        assert!(
+            diff.as_secs() < 2,
+            "Modification time should be restored, got difference {diff:?}"
+        );
+
+        let link = temp_dir.path().join("bin/start");
+        assert_eq!(fs::read_link(&link)?, PathBuf::from("run.sh"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_zip_rejects_escaping_symlink() -> Result<()> {
+        use zip::ZipWriter;
+        use zip::write::SimpleFileOptions;
+
+        let temp_dir = TempDir::new()?;
+
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            zip.add_symlink("passwd", "/etc/passwd", SimpleFileOptions::default())?;
+            zip.add_symlink("up", "../..", SimpleFileOptions::default())?;
+            zip.finish()?;
+        }
+
+        let result = extract_archive(
+            "evil.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            temp_dir.path().join("passwd").symlink_metadata().is_err(),
+            "Symlink with absolute target must not be created"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_zip_rejects_symlink_chained_through_symlink() -> Result<()> {
+        use zip::ZipWriter;
+        use zip::write::SimpleFileOptions;
+
+        let temp_dir = TempDir::new()?;
+
+        // each target is enclosed on its own, but `d/../..` resolves through `d -> .` to the
+        // parent of the target directory
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            zip.add_directory("dir/", SimpleFileOptions::default())?;
+            zip.add_symlink("dir/e", "d/../..", SimpleFileOptions::default())?;
+            zip.add_symlink("dir/d", ".", SimpleFileOptions::default())?;
+            zip.finish()?;
+        }
+
+        let result = extract_archive(
+            "evil.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            temp_dir.path().join("dir/e").symlink_metadata().is_err(),
+            "Symlink escaping through another symlink must not be created"
+        );
+        Ok(())
+    }
+
+    fn zip_with_files(files: &[(&str, Vec<u8>)]) -> Result<Vec<u8>> {
+        use zip::ZipWriter;
+        use zip::write::SimpleFileOptions;
+
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            for (name, content) in files {
+                zip.start_file(*name, SimpleFileOptions::default())?;
+                zip.write_all(content)?;
+            }
+            zip.finish()?;
+        }
+
+        Ok(zip_data)
+    }
+
+    #[test]
+    fn test_extract_archive_entry_limit() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let zip_data = zip_with_files(&[
+            ("a.txt", b"a".to_vec()),
+            ("b.txt", b"b".to_vec()),
+            ("c.txt", b"c".to_vec()),
+        ])?;
+
+        let limits = ExtractLimits {
+            max_entries: 2,
+            ..ExtractLimits::default()
+        };
+        let result = extract_archive(
+            "many.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &limits,
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("more than 2 entries"),
+            "Error should mention the entry limit"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_archive_size_limit() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let zip_data = zip_with_files(&[("large.bin", vec![0u8; 2048])])?;
+
+        let limits = ExtractLimits {
+            max_size: 1024,
+            ..ExtractLimits::default()
+        };
+        let result = extract_archive(
+            "large.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &limits,
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            !temp_dir.path().join("large.bin").exists(),
+            "File exceeding the limit must not be written"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_archive_rejects_understated_entry_size() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let content = vec![0u8; 64 * 1024];
+        let mut zip_data = zip_with_files(&[("bomb.bin", content.clone())])?;
+
+        // falsify the uncompressed size in the local and the central directory header
+        let actual_size = (content.len() as u32).to_le_bytes();
+        let declared_size = 16u32.to_le_bytes();
+        let mut patched = 0;
+        for i in 0..zip_data.len() - 4 {
+            let offset = match &zip_data[i..i + 4] {
+                [0x50, 0x4b, 0x03, 0x04] => i + 22,
+                [0x50, 0x4b, 0x01, 0x02] => i + 24,
+                _ => continue,
+            };
+            assert_eq!(zip_data[offset..offset + 4], actual_size);
+            zip_data[offset..offset + 4].copy_from_slice(&declared_size);
+            patched += 1;
+        }
+        assert_eq!(patched, 2, "Both headers should be patched");
+
+        let result = extract_archive(
+            "bomb.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("exceeds its declared size"),
+            "Error should mention the declared size"
+        );
+        assert!(
+            !temp_dir.path().join("bomb.bin").exists(),
+            "Partially written file must be removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_extract_archive_compression_ratio_limit() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        // zeros compress extremely well with zstd
+        let mut builder = tar::Builder::new(zstd::Encoder::new(Vec::new(), 0)?);
+        let content = vec![0u8; 4 * 1024 * 1024];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, "zeros.bin", content.as_slice())?;
+        let data = builder.into_inner()?.finish()?;
+
+        let result = extract_archive(
+            "bomb.tar.zst".to_string(),
+            ArchiveFormat::TarZst,
+            &data,
+            temp_dir.path(),
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("maximum compression ratio"),
+            "Error should mention the compression ratio"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_enclosed_path() {
+        assert_eq!(
+            enclosed_path(Path::new("./dir/file.txt")),
+            Some(PathBuf::from("dir/file.txt"))
+        );
+        assert_eq!(enclosed_path(Path::new("../escape.txt")), None);
+        assert_eq!(enclosed_path(Path::new("/etc/passwd")), None);
+        assert_eq!(enclosed_path(Path::new("")), None);
+    }
+
+    #[test]
+    fn test_extract_archive_rejects_path_traversal() -> Result<()> {
+        use std::io::Write;
+        use zip::ZipWriter;
+        use zip::write::FileOptions;
+
+        let temp_dir = TempDir::new()?;
+        let target_dir = temp_dir.path().join("target");
+        fs::create_dir(&target_dir)?;
+
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            zip.start_file("../escape.txt", FileOptions::<()>::default())?;
+            zip.write_all(b"malicious")?;
+            zip.finish()?;
+        }
+
+        let result = extract_archive(
+            "evil.zip".to_string(),
+            ArchiveFormat::Zip,
+            &zip_data,
+            &target_dir,
+            &ExtractLimits::default(),
+            OnConflict::Rename,
+        );
+
+        assert!(result.is_err(), "Expected error, got: {:?}", result);
+        assert!(
+            !temp_dir.path().join("escape.txt").exists(),
+            "File must not be written outside of the target directory"
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_extract_only_for_zip_files() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -479,6 +1721,85 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_with_pin() -> Result<()> {
+        let payload = Payload::from_bytes(b"pin protected secret");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_pin("123456")
+            .with_to_stdout();
+        get(factory, args).await?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_get_to_sops() -> Result<()> {
+        let payload = Payload::from_bytes(b"sops secret");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+
+        let temp_dir = TempDir::new()?;
+        let sops_file = temp_dir.path().join("secrets.enc.yaml");
+        fs::write(&sops_file, "encrypted: true\n")?;
+        let sops = crate::integrations::test_utils::fake_cli(
+            temp_dir.path(),
+            "sops",
+            r#"[ "$1" = "decrypt" ] && exit 0; cat > "$(dirname "$0")/value""#,
+        )?;
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_to_sops(&format!("{}#password", sops_file.display()), &sops);
+        get(factory, args).await?;
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("value"))?,
+            r#""sops secret""#
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_get_to_sops_checked_before_retrieval() -> Result<()> {
+        let client = MockClient::new().with_receive_failure("Secret retrieved".to_string());
+        let factory = MockFactory::new().with_client(client);
+
+        let temp_dir = TempDir::new()?;
+        let sops_file = temp_dir.path().join("secrets.enc.yaml");
+        fs::write(&sops_file, "encrypted: true\n")?;
+        let sops = crate::integrations::test_utils::fake_cli(
+            temp_dir.path(),
+            "sops",
+            "echo 'no key to decrypt' >&2; exit 128",
+        )?;
+
+        let args = GetArgs::builder("https://example.com/s/test123#key")
+            .with_to_sops(&format!("{}#password", sops_file.display()), &sops);
+        let result = get(factory, args).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("no key to decrypt")),
+            "The SOPS file should be checked before the secret is retrieved"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_pin() -> Result<()> {
+        let args = GetArgs::builder("https://example.com/s/test123#key").with_pin("123456");
+        assert_eq!(
+            read_pin(&args)?.as_deref().map(String::as_str),
+            Some("123456")
+        );
+
+        let args = GetArgs::builder("https://example.com/s/test123#key");
+        assert!(read_pin(&args)?.is_none(), "No PIN should be read");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_with_passphrase() -> Result<()> {
         let payload = Payload::from_bytes(b"protected secret");
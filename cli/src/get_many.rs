@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::env::current_dir;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use futures_util::stream::{self, StreamExt};
+use url::Url;
+
+use hakanai_lib::client::Client;
+use hakanai_lib::link::SecretLink;
+use hakanai_lib::models::Payload;
+use hakanai_lib::options::SecretReceiveOptions;
+
+use crate::args::{GetArgs, OnConflict};
+use crate::factory::Factory;
+use crate::get::{create_output_file, read_passphrase};
+use crate::helper;
+use crate::platform;
+
+/// Retrieves the secrets of all links listed in the file and saves them to the output directory.
+///
+/// Up to `--parallel` secrets are retrieved concurrently. Failures of single links do not abort
+/// the retrieval of the others, they are reported per link and result in an error at the end.
+pub async fn get_many<T: Factory>(factory: T, args: GetArgs, path: &Path) -> Result<()> {
+    let links = read_links(path)?;
+    let output_dir = match &args.output_dir {
+        Some(dir) => dir.clone(),
+        None => current_dir()?,
+    };
+
+    let mut opts = SecretReceiveOptions::default().with_user_agent(helper::get_user_agent_name());
+    if let Some(passphrase) = read_passphrase(&args)? {
+        opts = opts.with_passphrase(passphrase.as_ref());
+    }
+
+    if let Some(rate) = args.limit_rate {
+        opts = opts.with_rate_limit(rate as u64);
+    }
+
+    if let Some(max_memory) = args.max_memory {
+        opts = opts.with_max_memory(max_memory as usize);
+    }
+
+    let client = factory.new_client();
+    let on_conflict = args.on_conflict;
+    let results: Vec<(Url, Result<PathBuf>)> = stream::iter(links)
+        .map(|link| {
+            let client = &client;
+            let opts = opts.clone();
+            let output_dir = &output_dir;
+            async move {
+                let result =
+                    receive_to_file(client, link.clone(), opts, output_dir, on_conflict).await;
+                (link, result)
+            }
+        })
+        .buffered(args.parallel)
+        .collect()
+        .await;
+
+    for (link, result) in &results {
+        let link = display_link(link);
+        match result {
+            Ok(path) => println!(
+                "{} {link} -> {}",
+                "OK    ".green(),
+                path.display().to_string().cyan()
+            ),
+            Err(err) => println!("{} {link}: {}", "FAILED".red(), err.to_string().red()),
+        }
+    }
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} secrets could not be retrieved.",
+            results.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reads the links from the file, ignoring empty lines and comments.
+///
+/// All links are validated before any secret is retrieved, as retrieving burns the secret.
+fn read_links(path: &Path) -> Result<Vec<Url>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read links from '{}': {e}", path.display()))?;
+
+    let links = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            let url =
+                Url::parse(line).map_err(|e| anyhow!("Invalid link on line {line_number}: {e}"))?;
+            let link = SecretLink::parse(&url)
+                .map_err(|e| anyhow!("Invalid link on line {line_number}: {e}"))?;
+            if link.key().is_none() {
+                return Err(anyhow!(
+                    "Link on line {line_number} does not contain a decryption key."
+                ));
+            }
+            Ok(url)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if links.is_empty() {
+        return Err(anyhow!("No links found in '{}'.", path.display()));
+    }
+
+    Ok(links)
+}
+
+async fn receive_to_file(
+    client: &impl Client<Payload>,
+    link: Url,
+    opts: SecretReceiveOptions,
+    output_dir: &Path,
+    on_conflict: OnConflict,
+) -> Result<PathBuf> {
+    let mut payload = client.receive_secret(link.clone(), Some(opts)).await?;
+    let data = payload.take_data();
+    write_to_unique_file(
+        output_dir,
+        &output_name(&link, &payload),
+        &data,
+        on_conflict,
+    )
+}
+
+/// Returns the name of the file to save the secret to.
+///
+/// Files keep their name (without any directory components), other secrets are named by their ID.
+fn output_name(link: &Url, payload: &Payload) -> String {
+    let filename = payload
+        .filename
+        .as_deref()
+        .and_then(|name| Path::new(name).file_name())
+        .map(|name| platform::sanitize_filename(&name.to_string_lossy()));
+    if let Some(filename) = filename {
+        return filename;
+    }
+
+    let id = SecretLink::parse(link)
+        .map(|link| link.id().to_string())
+        .unwrap_or_else(|_| "secret".to_string());
+    if payload.content_type.is_none() || payload.is_text_content_type() {
+        format!("{id}.txt")
+    } else {
+        id.to_string()
+    }
+}
+
+/// Writes the data to a new file, appending a counter to the name if the file already exists and
+/// should be renamed.
+fn write_to_unique_file(
+    output_dir: &Path,
+    name: &str,
+    data: &[u8],
+    on_conflict: OnConflict,
+) -> Result<PathBuf> {
+    // keep multi-part extensions like .tar.zst together, ignore the dot of hidden files
+    let (stem, extension) = match name.char_indices().skip(1).find(|(_, c)| *c == '.') {
+        Some((i, _)) => (&name[..i], &name[i..]),
+        None => (name, ""),
+    };
+
+    let mut counter = 0;
+    loop {
+        let candidate = match counter {
+            0 => name.to_string(),
+            n => format!("{stem}-{n}{extension}"),
+        };
+
+        let path = output_dir.join(candidate);
+        match create_output_file(&path, on_conflict)? {
+            Some(mut file) => {
+                file.write_all(data)?;
+                return Ok(path);
+            }
+            None => counter += 1,
+        }
+    }
+}
+
+/// Returns the link without the key for printing.
+fn display_link(link: &Url) -> String {
+    let mut link = link.clone();
+    link.set_fragment(None);
+    link.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use hakanai_lib::client_mock::MockClient;
+    use hakanai_lib::utils::test::MustParse;
+
+    use crate::factory_mock::test_utils::MockFactory;
+
+    fn write_links(dir: &TempDir, content: &str) -> Result<PathBuf> {
+        let path = dir.path().join("urls.txt");
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    #[test]
+    fn test_read_links_skips_comments_and_empty_lines() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_links(
+            &temp_dir,
+            "# rotation 2026-10\nhttps://example.com/s/otter-1#key1\n\n  https://example.com/s/otter-2#key2  \n",
+        )?;
+
+        let links = read_links(&path)?;
+
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/s/otter-1#key1".must_parse(),
+                "https://example.com/s/otter-2#key2".must_parse()
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_links_rejects_invalid_links() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let cases = [
+            (
+                "https://example.com/s/otter-1#key\nnot a url\n",
+                "Invalid link on line 2",
+            ),
+            (
+                "https://example.com/s/otter-1\n",
+                "line 1 does not contain a decryption key",
+            ),
+            (
+                "https://example.com/admin#key\n",
+                "Invalid link on line 1: invalid path",
+            ),
+            ("# only comments\n\n", "No links found"),
+        ];
+
+        for (content, expected) in cases {
+            let path = write_links(&temp_dir, content)?;
+
+            let err = read_links(&path).expect_err(&format!("Expected error for: {content}"));
+            assert!(
+                err.to_string().contains(expected),
+                "Error '{err}' should contain '{expected}'"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_name() {
+        let link: Url = "https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV#key".must_parse();
+
+        let text = Payload::from_bytes(b"text");
+        assert_eq!(output_name(&link, &text), "01ARZ3NDEKTSV4RRFFQ69G5FAV.txt");
+
+        let binary = Payload::from_bytes(b"data").with_content_type("application/pdf");
+        assert_eq!(output_name(&link, &binary), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+
+        let file = Payload::from_bytes(b"data").with_filename("../../etc/passwd");
+        assert_eq!(
+            output_name(&link, &file),
+            "passwd",
+            "Directory components of the filename must be stripped"
+        );
+    }
+
+    #[test]
+    fn test_write_to_unique_file_appends_counter() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let first =
+            write_to_unique_file(temp_dir.path(), "backup.tar.zst", b"1", OnConflict::Rename)?;
+        let second =
+            write_to_unique_file(temp_dir.path(), "backup.tar.zst", b"2", OnConflict::Rename)?;
+        let third =
+            write_to_unique_file(temp_dir.path(), "backup.tar.zst", b"3", OnConflict::Rename)?;
+        let hidden = write_to_unique_file(temp_dir.path(), ".env", b"4", OnConflict::Rename)?;
+        let hidden_second =
+            write_to_unique_file(temp_dir.path(), ".env", b"5", OnConflict::Rename)?;
+
+        assert_eq!(first, temp_dir.path().join("backup.tar.zst"));
+        assert_eq!(second, temp_dir.path().join("backup-1.tar.zst"));
+        assert_eq!(third, temp_dir.path().join("backup-2.tar.zst"));
+        assert_eq!(hidden, temp_dir.path().join(".env"));
+        assert_eq!(hidden_second, temp_dir.path().join(".env-1"));
+        assert_eq!(
+            fs::read(&first)?,
+            b"1",
+            "Existing files must not be overwritten"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_many_saves_all_secrets() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_links(
+            &temp_dir,
+            "https://example.com/s/otter-1#key\nhttps://example.com/s/otter-2#key\nhttps://example.com/s/otter-3#key\n",
+        )?;
+        let output_dir = temp_dir.path().join("secrets");
+        fs::create_dir(&output_dir)?;
+
+        let payload = Payload::from_bytes(b"secret").with_filename("id_rsa");
+        let client = MockClient::new().with_receive_success(payload);
+        let factory = MockFactory::new().with_client(client);
+        let args = GetArgs::builder("https://example.com/s/otter-9#key")
+            .with_from_file(&path)
+            .with_output_dir(&output_dir.to_string_lossy());
+
+        get_many(factory, args, &path).await?;
+
+        for name in ["id_rsa", "id_rsa-1", "id_rsa-2"] {
+            assert_eq!(
+                fs::read(output_dir.join(name))?,
+                b"secret",
+                "Secret should be saved to {name}"
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_many_reports_failures() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = write_links(
+            &temp_dir,
+            "https://example.com/s/otter-1#key\nhttps://example.com/s/otter-2#key\n",
+        )?;
+
+        let client = MockClient::new().with_receive_failure("Secret not found".to_string());
+        let factory = MockFactory::new().with_client(client);
+        let args = GetArgs::builder("https://example.com/s/otter-9#key")
+            .with_from_file(&path)
+            .with_output_dir(&temp_dir.path().to_string_lossy());
+
+        let result = get_many(factory, args, &path).await;
+
+        assert!(
+            result
+                .expect_err("Expected error for failed links")
+                .to_string()
+                .contains("2 of 2 secrets could not be retrieved"),
+            "Error should report the number of failed links"
+        );
+        Ok(())
+    }
+}
@@ -4,3 +4,23 @@
 pub fn get_user_agent_name() -> String {
     format!("hakanai-cli/{}", env!("CARGO_PKG_VERSION"))
 }
+
+/// Checks if the answer to a confirmation prompt is a yes.
+pub fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_confirmed() {
+        for answer in ["y", "Y", "yes", "YES\n", " y \n"] {
+            assert!(is_confirmed(answer), "'{answer}' should confirm");
+        }
+        for answer in ["", "\n", "n", "no", "yess", "j"] {
+            assert!(!is_confirmed(answer), "'{answer}' should not confirm");
+        }
+    }
+}
@@ -0,0 +1,523 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in, append-only local history of sent secrets.
+//!
+//! Only metadata is recorded, one JSON object per line: the link without its fragment (so it never
+//! contains the key), the creation time, the TTL and a summary of the restrictions. Passphrases are
+//! masked in the summary.
+
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use hakanai_lib::link::SecretLink;
+use hakanai_lib::models::{OwnedSecretsResponse, SecretRestrictions};
+use hakanai_lib::utils::timestamp;
+
+use crate::args::{HistoryArgs, HistoryClearArgs, HistoryCommand, HistoryListArgs, SendArgs};
+use crate::helper;
+
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// A sent secret recorded in the history.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    /// Link of the secret without fragment
+    pub link: Url,
+
+    /// Unix timestamp of sending the secret
+    pub created_at: u64,
+
+    /// Time to live of the secret in seconds
+    pub ttl_seconds: u64,
+
+    /// Human-readable summary of the restrictions, passphrases are masked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<String>,
+}
+
+/// Pickup status of a recorded secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PickupStatus {
+    /// Not expired yet, the server was not asked
+    Active,
+
+    /// Not retrieved yet according to the server
+    Pending,
+
+    /// Retrieved (or revoked) before expiring according to the server
+    Retrieved,
+
+    /// TTL elapsed
+    Expired,
+}
+
+impl std::fmt::Display for PickupStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            PickupStatus::Active => "active",
+            PickupStatus::Pending => "pending",
+            PickupStatus::Retrieved => "retrieved",
+            PickupStatus::Expired => "expired",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// History entry with its status as printed by `history list --json`.
+#[derive(Debug, Serialize)]
+struct HistoryListItem<'a> {
+    #[serde(flatten)]
+    entry: &'a HistoryEntry,
+    status: PickupStatus,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for a secret sent just now, the fragment of the link is dropped.
+    pub fn new(
+        link: &Url,
+        ttl: Duration,
+        restrictions: Option<&SecretRestrictions>,
+    ) -> Result<Self> {
+        let mut link = link.clone();
+        link.set_fragment(None);
+
+        Ok(Self {
+            link,
+            created_at: timestamp::now()?,
+            ttl_seconds: ttl.as_secs(),
+            restrictions: restrictions.map(|r| r.to_string()),
+        })
+    }
+
+    /// Returns the ULID of the secret, `None` for aliases and links which cannot be parsed.
+    fn secret_id(&self) -> Option<String> {
+        SecretLink::parse(&self.link)
+            .ok()
+            .and_then(|link| link.secret_id())
+            .map(|id| id.to_string())
+    }
+
+    /// Determines the status at `now`, `outstanding` are the IDs of secrets not retrieved yet
+    /// according to the server if it was asked.
+    fn status(&self, now: u64, outstanding: Option<&HashSet<String>>) -> PickupStatus {
+        if now >= self.created_at.saturating_add(self.ttl_seconds) {
+            return PickupStatus::Expired;
+        }
+
+        // aliases are not reported by the server, their status is unknown
+        match (outstanding, self.secret_id()) {
+            (Some(outstanding), Some(id)) if outstanding.contains(&id) => PickupStatus::Pending,
+            (Some(_), Some(_)) => PickupStatus::Retrieved,
+            _ => PickupStatus::Active,
+        }
+    }
+}
+
+/// Append-only history file.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens the history at the given path or the default location.
+    pub fn open(path: Option<PathBuf>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => default_path(|name| std::env::var(name).ok())?,
+        };
+        Ok(Self { path })
+    }
+
+    /// Appends the entry to the history, the file is created if it does not exist yet.
+    pub fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        if let Some(dir) = self.path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options
+            .open(&self.path)
+            .map_err(|e| anyhow!("Failed to open history file '{}': {e}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// Reads all entries of the history, oldest first.
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(anyhow!(
+                    "Failed to open history file '{}': {e}",
+                    self.path.display()
+                ));
+            }
+        };
+
+        let mut entries = Vec::new();
+        for (number, line) in io::BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry = serde_json::from_str(&line).map_err(|e| {
+                anyhow!(
+                    "Invalid entry in history file '{}' on line {}: {e}",
+                    self.path.display(),
+                    number + 1
+                )
+            })?;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Deletes the history, returns false if there was none.
+    pub fn clear(&self) -> Result<bool> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow!(
+                "Failed to delete history file '{}': {e}",
+                self.path.display()
+            )),
+        }
+    }
+}
+
+/// Returns the default location of the history file in the user data directory.
+fn default_path(env: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    let non_empty = |name: &str| env(name).filter(|value| !value.is_empty());
+
+    let data_dir = if let Some(dir) = non_empty("XDG_DATA_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(home) = non_empty("HOME") {
+        Path::new(&home).join(".local").join("share")
+    } else if let Some(app_data) = non_empty("APPDATA") {
+        PathBuf::from(app_data)
+    } else {
+        return Err(anyhow!(
+            "Unable to determine the user data directory, use --history-file to set the location of the history."
+        ));
+    };
+
+    Ok(data_dir.join("hakanai").join(HISTORY_FILE_NAME))
+}
+
+/// Records a sent secret if the history is enabled. Failures only result in a warning, the secret
+/// has been sent already.
+pub fn record(
+    args: &SendArgs,
+    link: &Url,
+    ttl: Duration,
+    restrictions: Option<&SecretRestrictions>,
+) {
+    if !args.history {
+        return;
+    }
+
+    let result = HistoryEntry::new(link, ttl, restrictions)
+        .and_then(|entry| HistoryStore::open(args.history_file.clone())?.append(&entry));
+    if let Err(e) = result {
+        eprintln!(
+            "{}",
+            format!("Warning: Failed to record history: {e}").yellow()
+        );
+    }
+}
+
+pub async fn history(args: HistoryArgs) -> Result<()> {
+    let store = HistoryStore::open(args.history_file)?;
+
+    match args.command {
+        HistoryCommand::List(list_args) => list(&store, list_args).await,
+        HistoryCommand::Clear(clear_args) => clear(&store, clear_args),
+    }
+}
+
+async fn list(store: &HistoryStore, args: HistoryListArgs) -> Result<()> {
+    let entries = store.entries()?;
+
+    let outstanding = if args.check {
+        let token = args
+            .token()?
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| anyhow!("The --check option requires the token used for sending."))?;
+        Some(outstanding_request(&token, &args.server).await?)
+    } else {
+        None
+    };
+
+    let now = timestamp::now()?;
+    let items: Vec<HistoryListItem> = entries
+        .iter()
+        .map(|entry| {
+            let checked = outstanding
+                .as_ref()
+                .filter(|_| entry.link.origin() == args.server.origin());
+            HistoryListItem {
+                entry,
+                status: entry.status(now, checked),
+            }
+        })
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+    } else {
+        print!("{}", format_items(&items));
+    }
+
+    Ok(())
+}
+
+fn clear(store: &HistoryStore, args: HistoryClearArgs) -> Result<()> {
+    if !args.yes {
+        eprint!(
+            "{} Delete the history of sent secrets? [y/N] ",
+            "Warning:".red().bold()
+        );
+        io::stderr().flush()?;
+
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        if !helper::is_confirmed(&answer) {
+            return Err(anyhow!("Clearing history aborted."));
+        }
+    }
+
+    if store.clear()? {
+        println!("History cleared.");
+    } else {
+        println!("No history to clear.");
+    }
+
+    Ok(())
+}
+
+/// Requests the IDs of the secrets sent with the token which were not retrieved yet.
+async fn outstanding_request(token: &str, server: &Url) -> Result<HashSet<String>> {
+    let client = reqwest::Client::new();
+    let url = server.join("api/v1/my/secrets")?;
+
+    let response = client
+        .get(url)
+        .header("User-Agent", helper::get_user_agent_name())
+        .header("Authorization", format!("Bearer {token}"))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(anyhow!(
+            "Failed to check secrets: {} - {}",
+            status,
+            error_text
+        ));
+    }
+
+    let response: OwnedSecretsResponse = response.json().await?;
+    Ok(response
+        .secrets
+        .iter()
+        .map(|secret| secret.id.to_string().to_uppercase())
+        .collect())
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(timestamp)).to_string()
+}
+
+fn format_items(items: &[HistoryListItem]) -> String {
+    if items.is_empty() {
+        return "No secrets recorded.\n".to_string();
+    }
+
+    let mut out = format!(
+        "{:<20}  {:<20}  {:<9}  {}\n",
+        "Created (UTC)".bold(),
+        "Expires (UTC)".bold(),
+        "Status".bold(),
+        "Link".bold()
+    );
+    for item in items {
+        let expires_at = item.entry.created_at.saturating_add(item.entry.ttl_seconds);
+        out.push_str(&format!(
+            "{:<20}  {:<20}  {:<9}  {}\n",
+            format_timestamp(item.entry.created_at),
+            format_timestamp(expires_at),
+            item.status,
+            item.entry.link
+        ));
+        if let Some(restrictions) = &item.entry.restrictions {
+            out.push_str(&format!("{:<20}  {restrictions}\n", ""));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    fn entry(link: &str, created_at: u64, ttl_seconds: u64) -> HistoryEntry {
+        HistoryEntry {
+            link: Url::parse(link).expect("Invalid URL"),
+            created_at,
+            ttl_seconds,
+            restrictions: None,
+        }
+    }
+
+    #[test]
+    fn test_new_entry_drops_fragment_and_masks_passphrase() -> Result<()> {
+        let link = Url::parse("https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV#key:hash")?;
+        let restrictions = SecretRestrictions::default().with_passphrase(b"secret passphrase");
+
+        let entry = HistoryEntry::new(&link, Duration::from_secs(3600), Some(&restrictions))?;
+        assert_eq!(
+            entry.link.as_str(),
+            "https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV"
+        );
+        assert_eq!(entry.ttl_seconds, 3600);
+
+        let serialized = serde_json::to_string(&entry)?;
+        assert!(
+            !serialized.contains("key:hash") && !serialized.contains("secret passphrase"),
+            "History must not contain sensitive data: {serialized}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_append_and_clear() -> Result<()> {
+        let dir = TempDir::new()?;
+        let store = HistoryStore::open(Some(dir.path().join("nested").join(HISTORY_FILE_NAME)))?;
+        assert!(
+            store.entries()?.is_empty(),
+            "Missing history should be empty"
+        );
+
+        let first = entry("https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV", 100, 60);
+        let second = entry("https://example.com/s/blue-otter-42", 200, 60);
+        store.append(&first)?;
+        store.append(&second)?;
+        assert_eq!(store.entries()?, vec![first, second]);
+
+        assert!(store.clear()?, "Existing history should be cleared");
+        assert!(store.entries()?.is_empty());
+        assert!(
+            !store.clear()?,
+            "Clearing missing history should report false"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_rejects_invalid_lines() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join(HISTORY_FILE_NAME);
+        fs::write(&path, "\nnot json\n")?;
+
+        let result = HistoryStore::open(Some(path))?.entries();
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("line 2")),
+            "Invalid line should be reported with its number"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_status() {
+        let sent = entry("https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV", 100, 60);
+        let alias = entry("https://example.com/s/blue-otter-42", 100, 60);
+        let outstanding: HashSet<String> = ["01ARZ3NDEKTSV4RRFFQ69G5FAV".to_string()].into();
+
+        assert_eq!(sent.status(160, None), PickupStatus::Expired);
+        assert_eq!(sent.status(120, None), PickupStatus::Active);
+        assert_eq!(sent.status(120, Some(&outstanding)), PickupStatus::Pending);
+        assert_eq!(
+            sent.status(120, Some(&HashSet::new())),
+            PickupStatus::Retrieved
+        );
+        assert_eq!(
+            alias.status(120, Some(&HashSet::new())),
+            PickupStatus::Active,
+            "Status of aliases cannot be checked"
+        );
+    }
+
+    #[test]
+    fn test_default_path() -> Result<()> {
+        let xdg = |name: &str| match name {
+            "XDG_DATA_HOME" => Some("/data".to_string()),
+            "HOME" => Some("/home/user".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            default_path(xdg)?,
+            Path::new("/data/hakanai").join(HISTORY_FILE_NAME)
+        );
+
+        let home = |name: &str| match name {
+            "XDG_DATA_HOME" => Some(String::new()),
+            "HOME" => Some("/home/user".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            default_path(home)?,
+            Path::new("/home/user/.local/share/hakanai").join(HISTORY_FILE_NAME)
+        );
+
+        assert!(default_path(|_| None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_items() {
+        colored::control::set_override(false);
+
+        let mut restricted = entry("https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV", 0, 3600);
+        restricted.restrictions = Some("Passphrase: ***".to_string());
+        let items = [HistoryListItem {
+            entry: &restricted,
+            status: PickupStatus::Expired,
+        }];
+
+        let output = format_items(&items);
+        assert!(
+            output.contains("1970-01-01T00:00:00Z  1970-01-01T01:00:00Z  expired"),
+            "Output: {output}"
+        );
+        assert!(output.contains("Passphrase: ***"), "Output: {output}");
+        assert_eq!(format_items(&[]), "No secrets recorded.\n");
+    }
+}
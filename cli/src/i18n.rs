@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+/// Languages of the user-facing messages of the CLI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    #[default]
+    #[value(name = "en")]
+    English,
+
+    #[value(name = "de")]
+    German,
+}
+
+/// Environment variables defining the locale, in order of precedence.
+const LOCALE_ENV_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+impl Language {
+    /// Parses the language of a POSIX locale (e.g. `de_DE.UTF-8`) or language tag (e.g. `de-AT`).
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let primary = locale.split(['_', '-', '.', '@']).next()?;
+        match primary.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Language::English),
+            "de" => Some(Language::German),
+            _ => None,
+        }
+    }
+
+    /// Detects the language from the locale environment variables.
+    ///
+    /// The first non-empty variable defines the locale, unsupported locales fall back to English.
+    pub fn detect<F>(env: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        LOCALE_ENV_VARS
+            .iter()
+            .filter_map(|name| env(name))
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Language::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+/// Sets the language of the user-facing messages, detected from the locale if not specified.
+pub fn init(lang: Option<Language>) {
+    let lang = lang.unwrap_or_else(|| Language::detect(|name| std::env::var(name).ok()));
+    let _ = LANGUAGE.set(lang);
+}
+
+/// User-facing messages of the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    NoTokenWarning,
+    NoSecretProvided,
+    SecretSent,
+    SecretLink,
+    ExpiresAt,
+    ReplyLink,
+    ReplyNotReserved,
+    ReplySent,
+    KubernetesSecretApplied,
+    Key,
+    KeyShare,
+    ShareKeySharesSeparately,
+    NoCredentialsDetected,
+    Detected,
+    AccessRestricted,
+    GeneratedPassphrase,
+    SharePassphraseSeparately,
+    HashVerified,
+    Description,
+    ContentType,
+    ExtractingArchive,
+    SavedTo,
+    TokenCreated,
+    UserToken,
+    PhaseEncrypting,
+    PhaseUploading,
+    PhaseWaiting,
+    PhaseDownloading,
+    PhaseDecrypting,
+    CommandSucceeded,
+    CommandFailed,
+}
+
+impl Message {
+    /// Returns the message in the given language.
+    pub fn text(self, lang: Language) -> &'static str {
+        match lang {
+            Language::English => self.english(),
+            Language::German => self.german(),
+        }
+    }
+
+    fn english(self) -> &'static str {
+        match self {
+            Message::NoTokenWarning => "Warning: No token provided.",
+            Message::NoSecretProvided => "No secret provided. Please input a secret to send.",
+            Message::SecretSent => "Secret sent successfully!",
+            Message::SecretLink => "Secret link:",
+            Message::ExpiresAt => "Expires:",
+            Message::ReplyLink => "Reply link:",
+            Message::ReplyNotReserved => {
+                "Warning: The server does not support replies, the recipient cannot reply."
+            }
+            Message::ReplySent => "Reply sent successfully!",
+            Message::KubernetesSecretApplied => "Kubernetes Secret created or updated:",
+            Message::Key => "Key:",
+            Message::KeyShare => "Key share",
+            Message::ShareKeySharesSeparately => {
+                "Share each key share via a different channel than the link."
+            }
+            Message::NoCredentialsDetected => "No well-known credential formats detected.",
+            Message::Detected => "Detected:",
+            Message::AccessRestricted => "Access to secret is restricted: ",
+            Message::GeneratedPassphrase => "Passphrase:",
+            Message::SharePassphraseSeparately => {
+                "Share the passphrase via a different channel than the link."
+            }
+            Message::HashVerified => "SHA-256 hash verified.",
+            Message::Description => "Description:",
+            Message::ContentType => "Content type:",
+            Message::ExtractingArchive => "Extracting archive:",
+            Message::SavedTo => "Saved to:",
+            Message::TokenCreated => "Token created successfully!",
+            Message::UserToken => "User token:",
+            Message::PhaseEncrypting => "encrypting",
+            Message::PhaseUploading => "uploading",
+            Message::PhaseWaiting => "waiting for server",
+            Message::PhaseDownloading => "downloading",
+            Message::PhaseDecrypting => "decrypting",
+            Message::CommandSucceeded => "Command completed successfully.",
+            Message::CommandFailed => "Command failed:",
+        }
+    }
+
+    fn german(self) -> &'static str {
+        match self {
+            Message::NoTokenWarning => "Warnung: Kein Token angegeben.",
+            Message::NoSecretProvided => {
+                "Kein Secret angegeben. Bitte ein Secret zum Senden eingeben."
+            }
+            Message::SecretSent => "Secret erfolgreich gesendet!",
+            Message::SecretLink => "Secret-Link:",
+            Message::ExpiresAt => "Läuft ab:",
+            Message::ReplyLink => "Antwort-Link:",
+            Message::ReplyNotReserved => {
+                "Warnung: Der Server unterstützt keine Antworten, der Empfänger kann nicht antworten."
+            }
+            Message::ReplySent => "Antwort erfolgreich gesendet!",
+            Message::KubernetesSecretApplied => "Kubernetes Secret erstellt oder aktualisiert:",
+            Message::Key => "Schlüssel:",
+            Message::KeyShare => "Schlüsselteil",
+            Message::ShareKeySharesSeparately => {
+                "Jeden Schlüsselteil über einen anderen Kanal als den Link weitergeben."
+            }
+            Message::NoCredentialsDetected => "Keine bekannten Formate von Zugangsdaten erkannt.",
+            Message::Detected => "Erkannt:",
+            Message::AccessRestricted => "Der Zugriff auf das Secret ist eingeschränkt: ",
+            Message::GeneratedPassphrase => "Passphrase:",
+            Message::SharePassphraseSeparately => {
+                "Die Passphrase über einen anderen Kanal als den Link weitergeben."
+            }
+            Message::HashVerified => "SHA-256-Hash verifiziert.",
+            Message::Description => "Beschreibung:",
+            Message::ContentType => "Inhaltstyp:",
+            Message::ExtractingArchive => "Archiv wird entpackt:",
+            Message::SavedTo => "Gespeichert unter:",
+            Message::TokenCreated => "Token erfolgreich erstellt!",
+            Message::UserToken => "Benutzer-Token:",
+            Message::PhaseEncrypting => "verschlüsseln",
+            Message::PhaseUploading => "hochladen",
+            Message::PhaseWaiting => "warten auf Server",
+            Message::PhaseDownloading => "herunterladen",
+            Message::PhaseDecrypting => "entschlüsseln",
+            Message::CommandSucceeded => "Befehl erfolgreich ausgeführt.",
+            Message::CommandFailed => "Befehl fehlgeschlagen:",
+        }
+    }
+}
+
+/// Returns the message in the language of the CLI (English if not initialized).
+pub fn t(message: Message) -> &'static str {
+    message.text(LANGUAGE.get().copied().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), Some(Language::German));
+        assert_eq!(Language::from_locale("de-AT"), Some(Language::German));
+        assert_eq!(Language::from_locale("en_US"), Some(Language::English));
+        assert_eq!(Language::from_locale("C.UTF-8"), Some(Language::English));
+        assert_eq!(Language::from_locale("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_detect_precedence() {
+        let env = |name: &str| match name {
+            "LC_ALL" => Some(String::new()),
+            "LC_MESSAGES" => Some("de_DE.UTF-8".to_string()),
+            "LANG" => Some("en_US.UTF-8".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            Language::detect(env),
+            Language::German,
+            "LC_MESSAGES should take precedence over LANG and empty LC_ALL should be ignored"
+        );
+    }
+
+    #[test]
+    fn test_detect_fallback() {
+        assert_eq!(Language::detect(|_| None), Language::English);
+        assert_eq!(
+            Language::detect(|_| Some("fr_FR.UTF-8".to_string())),
+            Language::English,
+            "Unsupported locales should fall back to English"
+        );
+    }
+
+    #[test]
+    fn test_message_text() {
+        assert_eq!(
+            Message::SecretSent.text(Language::English),
+            "Secret sent successfully!"
+        );
+        assert_eq!(
+            Message::SecretSent.text(Language::German),
+            "Secret erfolgreich gesendet!"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adapters moving secrets between Hakanai and existing secret stores.
+//!
+//! The stores are accessed through their CLIs (`vault`, `sops`), so their authentication and
+//! configuration apply as usual. Secrets are exchanged through pipes only, they never show up as
+//! arguments in the process list or in temporary files.
+
+mod sops;
+mod vault;
+
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{Result, anyhow};
+use zeroize::Zeroizing;
+
+pub use sops::{SopsEncoding, SopsTarget};
+pub use vault::VaultSource;
+
+/// Splits a reference of the form `location#key`, as used by `--from-vault` and `--to-sops`.
+fn split_reference<'a>(reference: &'a str, example: &str) -> Result<(&'a str, &'a str), String> {
+    match reference.rsplit_once('#') {
+        Some((location, key)) if !location.is_empty() && !key.is_empty() => Ok((location, key)),
+        _ => Err(format!("Expected a reference like '{example}'")),
+    }
+}
+
+/// Runs the command with the input on stdin and returns its output.
+fn run(mut command: Command, input: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let program = command.get_program().to_string_lossy().to_string();
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start '{program}': {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(input) {
+            Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    let stdout = Zeroizing::new(output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!(
+            "{program} exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    Ok(stdout)
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod test_utils {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    use anyhow::Result;
+
+    /// Creates an executable shell script standing in for the CLI of a secret store.
+    pub fn fake_cli(dir: &Path, name: &str, script: &str) -> Result<String> {
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+        Ok(path.to_string_lossy().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_reference() {
+        assert_eq!(
+            split_reference("secret/app#password", "path#key"),
+            Ok(("secret/app", "password"))
+        );
+        assert_eq!(
+            split_reference("a#b#c", "path#key"),
+            Ok(("a#b", "c")),
+            "Only the last # should separate the key"
+        );
+        assert!(split_reference("secret/app", "path#key").is_err());
+        assert!(split_reference("secret/app#", "path#key").is_err());
+        assert!(split_reference("#password", "path#key").is_err());
+    }
+
+    #[test]
+    fn test_run_not_found() {
+        let result = run(Command::new("hakanai-nonexistent-cli"), b"");
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("Failed to start")),
+            "Error should mention the program"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_failure() {
+        let mut command = Command::new("/bin/sh");
+        command.args(["-c", "echo 'permission denied' >&2; exit 2"]);
+
+        let result = run(command, b"");
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("permission denied")),
+            "Error should contain stderr"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_passes_input() -> Result<()> {
+        let mut command = Command::new("/bin/sh");
+        command.args(["-c", "cat"]);
+
+        let output = run(command, b"s3cr3t")?;
+
+        assert_eq!(output.as_slice(), b"s3cr3t");
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use base64::Engine;
+use zeroize::Zeroizing;
+
+use super::{run, split_reference};
+
+/// Top-level key in a file encrypted with SOPS, e.g. `secrets.enc.yaml#db_password`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SopsTarget {
+    /// File encrypted with SOPS, which must already exist.
+    pub file: PathBuf,
+
+    /// Key the secret is stored under.
+    pub key: String,
+}
+
+/// How a secret was stored in the SOPS file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SopsEncoding {
+    /// The secret is stored as is.
+    Text,
+
+    /// The secret is not valid UTF-8 and was stored base64 encoded.
+    Base64,
+}
+
+impl SopsTarget {
+    /// Checks that the file exists and can be decrypted, before the secret is retrieved.
+    ///
+    /// Retrieving burns the secret, so problems with the file should surface before.
+    pub fn check(&self, program: &str) -> Result<()> {
+        if !self.file.is_file() {
+            return Err(anyhow!(
+                "SOPS file '{}' does not exist, create it with sops first",
+                self.file.display()
+            ));
+        }
+
+        let mut command = Command::new(program);
+        command.arg("decrypt").arg(&self.file);
+        run(command, b"")?;
+
+        Ok(())
+    }
+
+    /// Stores the value under the key with `sops set`, passing it on stdin.
+    pub fn write(&self, program: &str, value: &[u8]) -> Result<SopsEncoding> {
+        let (text, encoding) = match std::str::from_utf8(value) {
+            Ok(text) => (Zeroizing::new(text.to_string()), SopsEncoding::Text),
+            Err(_) => (
+                Zeroizing::new(base64::prelude::BASE64_STANDARD.encode(value)),
+                SopsEncoding::Base64,
+            ),
+        };
+
+        let json = Zeroizing::new(serde_json::to_vec(text.as_str())?);
+        let index = serde_json::to_string(&[&self.key])?;
+
+        let mut command = Command::new(program);
+        command
+            .args(["set", "--value-stdin"])
+            .arg(&self.file)
+            .arg(index);
+        run(command, &json)?;
+
+        Ok(encoding)
+    }
+}
+
+impl FromStr for SopsTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (file, key) = split_reference(s, "secrets.enc.yaml#key")?;
+
+        Ok(Self {
+            file: PathBuf::from(file),
+            key: key.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for SopsTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.file.display(), self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), String> {
+        let target: SopsTarget = "deploy/secrets.enc.yaml#db_password".parse()?;
+
+        assert_eq!(target.file, PathBuf::from("deploy/secrets.enc.yaml"));
+        assert_eq!(target.key, "db_password");
+        assert_eq!(target.to_string(), "deploy/secrets.enc.yaml#db_password");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_without_key() {
+        assert!("secrets.enc.yaml".parse::<SopsTarget>().is_err());
+    }
+
+    #[test]
+    fn test_check_missing_file() {
+        let target = SopsTarget {
+            file: PathBuf::from("hakanai-nonexistent.enc.yaml"),
+            key: "password".to_string(),
+        };
+
+        let result = target.check("sops");
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("does not exist")),
+            "Missing files should be reported before retrieval"
+        );
+    }
+
+    #[cfg(unix)]
+    mod unix {
+        use super::*;
+        use std::fs;
+
+        use tempfile::TempDir;
+
+        use crate::integrations::test_utils::fake_cli;
+
+        fn target(dir: &TempDir) -> Result<SopsTarget> {
+            let file = dir.path().join("secrets.enc.yaml");
+            fs::write(&file, "encrypted: true\n")?;
+
+            Ok(SopsTarget {
+                file,
+                key: "db_password".to_string(),
+            })
+        }
+
+        fn recording_sops(dir: &TempDir) -> Result<String> {
+            fake_cli(
+                dir.path(),
+                "sops",
+                r#"dir="$(dirname "$0")"; echo "$@" > "$dir/args"; cat > "$dir/value""#,
+            )
+        }
+
+        #[test]
+        fn test_write_text() -> Result<()> {
+            let temp_dir = TempDir::new()?;
+            let target = target(&temp_dir)?;
+            let sops = recording_sops(&temp_dir)?;
+
+            let encoding = target.write(&sops, b"s3\"cr3t\n")?;
+
+            assert_eq!(encoding, SopsEncoding::Text);
+            assert_eq!(
+                fs::read_to_string(temp_dir.path().join("value"))?,
+                r#""s3\"cr3t\n""#,
+                "Value should be passed as JSON string"
+            );
+            let args = fs::read_to_string(temp_dir.path().join("args"))?;
+            assert_eq!(
+                args.trim(),
+                format!(
+                    r#"set --value-stdin {} ["db_password"]"#,
+                    target.file.display()
+                )
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn test_write_binary() -> Result<()> {
+            let temp_dir = TempDir::new()?;
+            let target = target(&temp_dir)?;
+            let sops = recording_sops(&temp_dir)?;
+
+            let encoding = target.write(&sops, &[0xff, 0xfe, 0x00])?;
+
+            assert_eq!(encoding, SopsEncoding::Base64);
+            assert_eq!(
+                fs::read_to_string(temp_dir.path().join("value"))?,
+                r#""//4A""#
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn test_check_decrypt_fails() -> Result<()> {
+            let temp_dir = TempDir::new()?;
+            let target = target(&temp_dir)?;
+            let sops = fake_cli(
+                temp_dir.path(),
+                "sops",
+                "echo 'Failed to get the data key' >&2; exit 128",
+            )?;
+
+            let result = target.check(&sops);
+
+            assert!(
+                result.is_err_and(|e| e.to_string().contains("Failed to get the data key")),
+                "Error of sops should be reported"
+            );
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::process::Command;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use zeroize::Zeroizing;
+
+use super::{run, split_reference};
+
+/// Field of a secret in HashiCorp Vault (or OpenBao), e.g. `secret/app#password`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultSource {
+    /// Path of the secret in a KV secrets engine (version 1 or 2).
+    pub path: String,
+
+    /// Field of the secret to read.
+    pub field: String,
+}
+
+impl VaultSource {
+    /// Reads the field with `vault kv get`, using the address and token configured for vault.
+    pub fn read(&self, program: &str) -> Result<Zeroizing<Vec<u8>>> {
+        let mut command = Command::new(program);
+        command
+            .args(["kv", "get"])
+            .arg(format!("-field={}", self.field))
+            .arg(&self.path);
+
+        let value = run(command, b"")?;
+        if value.is_empty() {
+            return Err(anyhow!("The field '{}' in Vault is empty", self));
+        }
+
+        Ok(value)
+    }
+}
+
+impl FromStr for VaultSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (path, field) = split_reference(s, "secret/path#key")?;
+
+        Ok(Self {
+            path: path.to_string(),
+            field: field.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for VaultSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.path, self.field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> Result<(), String> {
+        let source: VaultSource = "secret/team/db#password".parse()?;
+
+        assert_eq!(source.path, "secret/team/db");
+        assert_eq!(source.field, "password");
+        assert_eq!(source.to_string(), "secret/team/db#password");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_without_field() {
+        assert!("secret/team/db".parse::<VaultSource>().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read() -> Result<()> {
+        use crate::integrations::test_utils::fake_cli;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let vault = fake_cli(
+            temp_dir.path(),
+            "vault",
+            r#"echo "$@" > "$(dirname "$0")/args"; printf 's3cr3t'"#,
+        )?;
+        let source: VaultSource = "secret/db#password".parse().map_err(|e| anyhow!("{e}"))?;
+
+        let value = source.read(&vault)?;
+
+        assert_eq!(value.as_slice(), b"s3cr3t");
+        let args = std::fs::read_to_string(temp_dir.path().join("args"))?;
+        assert_eq!(args.trim(), "kv get -field=password secret/db");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_empty_field() -> Result<()> {
+        use crate::integrations::test_utils::fake_cli;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let vault = fake_cli(temp_dir.path(), "vault", "exit 0")?;
+        let source: VaultSource = "secret/db#password".parse().map_err(|e| anyhow!("{e}"))?;
+
+        assert!(source.read(&vault).is_err(), "Empty secrets should fail");
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{Result, anyhow};
+use base64::Engine;
+use colored::Colorize;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use hakanai_lib::client::Client;
+use hakanai_lib::models::Payload;
+use hakanai_lib::options::SecretReceiveOptions;
+
+use crate::args::{K8sArgs, K8sCommand, K8sCreateSecretArgs, is_valid_data_key};
+use crate::factory::Factory;
+use crate::helper;
+use crate::i18n::{Message, t};
+
+/// Key of the secret data if neither `--data-key` nor a filename is available.
+const DEFAULT_DATA_KEY: &str = "value";
+
+/// Field manager of the server-side apply, identifying the fields owned by Hakanai.
+const FIELD_MANAGER: &str = "hakanai";
+
+pub async fn k8s<T: Factory>(factory: T, args: K8sArgs) -> Result<()> {
+    match args.command {
+        K8sCommand::CreateSecret(create_args) => create_secret(factory, create_args).await,
+    }
+}
+
+/// Retrieves the secret and applies it as Kubernetes Secret via kubectl.
+///
+/// The manifest is passed to kubectl on stdin, so the secret never shows up in the process list
+/// or on disk. The server-side apply creates or updates the Secret without storing a copy of the
+/// data in the `last-applied-configuration` annotation.
+async fn create_secret<T: Factory>(factory: T, args: K8sCreateSecretArgs) -> Result<()> {
+    args.validate()?;
+
+    let observer = factory.new_observer("Receiving secret...")?;
+    let mut opts = SecretReceiveOptions::default()
+        .with_user_agent(helper::get_user_agent_name())
+        .with_observer(observer);
+
+    if let Some(passphrase) = read_passphrase(&args)? {
+        opts = opts.with_passphrase(passphrase.as_bytes());
+    }
+
+    if let Some(pin) = read_pin(&args)? {
+        opts = opts.with_pin(&pin);
+    }
+
+    let url = args.secret_url()?;
+    let payload = factory.new_client().receive_secret(url, Some(opts)).await?;
+
+    let data_key = data_key(&args, &payload)?;
+    let manifest = secret_manifest(&args, &data_key, payload.into_bytes().as_slice())?;
+    apply(&args, &manifest)?;
+
+    let target = match args.namespace {
+        Some(ref namespace) => format!("{namespace}/{}", args.name),
+        None => args.name.clone(),
+    };
+    eprintln!("{} {target}", t(Message::KubernetesSecretApplied).green());
+    Ok(())
+}
+
+fn read_passphrase(args: &K8sCreateSecretArgs) -> Result<Option<Zeroizing<String>>> {
+    if let Some(ref passphrase) = args.passphrase {
+        return Ok(Some(Zeroizing::new(passphrase.clone())));
+    }
+
+    if args.ask_passphrase {
+        let passphrase = Zeroizing::new(rpassword::prompt_password("Passphrase: ")?);
+        return Ok(Some(passphrase));
+    }
+
+    Ok(None)
+}
+
+fn read_pin(args: &K8sCreateSecretArgs) -> Result<Option<Zeroizing<String>>> {
+    if let Some(ref pin) = args.pin {
+        return Ok(Some(Zeroizing::new(pin.clone())));
+    }
+
+    if args.ask_pin {
+        let pin = Zeroizing::new(rpassword::prompt_password("PIN: ")?);
+        return Ok(Some(pin));
+    }
+
+    Ok(None)
+}
+
+/// Returns the key of the secret data, which is the filename for file secrets by default.
+fn data_key(args: &K8sCreateSecretArgs, payload: &Payload) -> Result<String> {
+    if let Some(ref data_key) = args.data_key {
+        return Ok(data_key.clone());
+    }
+
+    let Some(ref filename) = payload.filename else {
+        return Ok(DEFAULT_DATA_KEY.to_string());
+    };
+
+    if !is_valid_data_key(filename) {
+        return Err(anyhow!(
+            "The filename '{filename}' cannot be used as key of the Kubernetes Secret, use --data-key to choose one."
+        ));
+    }
+
+    Ok(filename.clone())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretManifest<'a> {
+    api_version: &'static str,
+    kind: &'static str,
+    metadata: SecretMetadata<'a>,
+    #[serde(rename = "type")]
+    secret_type: &'a str,
+    data: BTreeMap<&'a str, &'a str>,
+}
+
+#[derive(Serialize)]
+struct SecretMetadata<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<&'a str>,
+}
+
+/// Creates the JSON manifest of the Kubernetes Secret containing the data under the key.
+fn secret_manifest(
+    args: &K8sCreateSecretArgs,
+    data_key: &str,
+    data: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let encoded = Zeroizing::new(base64::prelude::BASE64_STANDARD.encode(data));
+
+    let manifest = SecretManifest {
+        api_version: "v1",
+        kind: "Secret",
+        metadata: SecretMetadata {
+            name: &args.name,
+            namespace: args.namespace.as_deref(),
+        },
+        secret_type: &args.secret_type,
+        data: BTreeMap::from([(data_key, encoded.as_str())]),
+    };
+
+    Ok(Zeroizing::new(serde_json::to_vec(&manifest)?))
+}
+
+/// Applies the manifest with kubectl, which uses the local kubeconfig.
+fn apply(args: &K8sCreateSecretArgs, manifest: &[u8]) -> Result<()> {
+    let mut command = Command::new(&args.kubectl);
+    if let Some(ref kubeconfig) = args.kubeconfig {
+        command.arg("--kubeconfig").arg(kubeconfig);
+    }
+
+    if let Some(ref context) = args.context {
+        command.arg("--context").arg(context);
+    }
+
+    let mut child = command
+        .args(["apply", "--server-side", "--force-conflicts"])
+        .arg(format!("--field-manager={FIELD_MANAGER}"))
+        .args(["-f", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start '{}': {e}", args.kubectl))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(manifest) {
+            Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!(
+            "kubectl exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    use hakanai_lib::client_mock::MockClient;
+    use tempfile::TempDir;
+
+    use crate::factory_mock::test_utils::MockFactory;
+
+    const LINK: &str = "https://example.com/s/test123#key";
+
+    #[cfg(unix)]
+    fn kubectl_script(dir: &Path, script: &str) -> Result<String> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("kubectl.sh");
+        fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_create_secret() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let kubectl = kubectl_script(
+            temp_dir.path(),
+            r#"dir="$(dirname "$0")"; echo "$@" > "$dir/args"; cat > "$dir/manifest""#,
+        )?;
+
+        let client = MockClient::new().with_receive_success(Payload::from_bytes(b"s3cr3t"));
+        let factory = MockFactory::new().with_client(client);
+        let args = K8sCreateSecretArgs::builder(LINK, "db-password")
+            .with_namespace("prod")
+            .with_context("staging")
+            .with_kubectl(&kubectl);
+
+        create_secret(factory, args).await?;
+
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&fs::read(temp_dir.path().join("manifest"))?)?;
+        assert_eq!(manifest["kind"], "Secret");
+        assert_eq!(manifest["metadata"]["name"], "db-password");
+        assert_eq!(manifest["metadata"]["namespace"], "prod");
+        assert_eq!(manifest["type"], "Opaque");
+        assert_eq!(manifest["data"]["value"], "czNjcjN0");
+
+        let kubectl_args = fs::read_to_string(temp_dir.path().join("args"))?;
+        assert_eq!(
+            kubectl_args.trim(),
+            "--context staging apply --server-side --force-conflicts --field-manager=hakanai -f -"
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_create_secret_kubectl_fails() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let kubectl = kubectl_script(
+            temp_dir.path(),
+            r#"cat > /dev/null; echo "namespaces \"prod\" not found" >&2; exit 1"#,
+        )?;
+
+        let client = MockClient::new().with_receive_success(Payload::from_bytes(b"s3cr3t"));
+        let factory = MockFactory::new().with_client(client);
+        let args = K8sCreateSecretArgs::builder(LINK, "db-password").with_kubectl(&kubectl);
+
+        let result = create_secret(factory, args).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("namespaces \"prod\" not found")),
+            "Error should contain the output of kubectl"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_secret_kubectl_not_found() {
+        let client = MockClient::new().with_receive_success(Payload::from_bytes(b"s3cr3t"));
+        let factory = MockFactory::new().with_client(client);
+        let args = K8sCreateSecretArgs::builder(LINK, "db-password")
+            .with_kubectl("hakanai-nonexistent-kubectl");
+
+        let result = create_secret(factory, args).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("Failed to start")),
+            "Error should mention kubectl"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_secret_receive_failure() {
+        let client = MockClient::new().with_receive_failure("Secret not found".to_string());
+        let factory = MockFactory::new().with_client(client);
+        let args = K8sCreateSecretArgs::builder(LINK, "db-password")
+            .with_kubectl("hakanai-nonexistent-kubectl");
+
+        let result = create_secret(factory, args).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("Secret not found")),
+            "Retrieval error should be returned before kubectl is run"
+        );
+    }
+
+    #[test]
+    fn test_secret_manifest_without_namespace() -> Result<()> {
+        let args = K8sCreateSecretArgs::builder(LINK, "db-password");
+
+        let manifest = secret_manifest(&args, "password", b"s3cr3t")?;
+        let manifest: serde_json::Value = serde_json::from_slice(&manifest)?;
+
+        assert_eq!(manifest["apiVersion"], "v1");
+        assert!(
+            manifest["metadata"].get("namespace").is_none(),
+            "Namespace of the current context should be used"
+        );
+        assert_eq!(manifest["data"]["password"], "czNjcjN0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_key() -> Result<()> {
+        let args = K8sCreateSecretArgs::builder(LINK, "tls");
+        let text = Payload::from_bytes(b"s3cr3t");
+        let file = Payload::from_bytes(b"cert").with_filename("tls.crt");
+
+        assert_eq!(data_key(&args, &text)?, DEFAULT_DATA_KEY);
+        assert_eq!(data_key(&args, &file)?, "tls.crt");
+        assert_eq!(
+            data_key(&args.with_data_key("ca.crt"), &file)?,
+            "ca.crt",
+            "Data key argument should take precedence over the filename"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_key_invalid_filename() {
+        let args = K8sCreateSecretArgs::builder(LINK, "tls");
+        let file = Payload::from_bytes(b"cert").with_filename("my cert.pem");
+
+        let result = data_key(&args, &file);
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("--data-key")),
+            "Error should suggest --data-key"
+        );
+    }
+}
@@ -1,14 +1,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod admin;
+mod archive;
 mod args;
+mod batch;
 mod cli;
+mod editor;
+mod exclude;
+mod exit_code;
 mod factory;
 mod factory_mock;
 mod get;
+mod get_many;
 mod helper;
+mod history;
+mod i18n;
+mod integrations;
+mod k8s;
+mod notification;
 mod observer;
+mod offline;
+mod platform;
 mod send;
+mod template;
 mod token;
+mod version;
 
 use std::process::ExitCode;
 
@@ -16,18 +32,34 @@ use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
 
+use crate::admin::admin;
 use crate::cli::Args;
+use crate::exit_code::Failure;
 use crate::get::get;
+use crate::history::history;
+use crate::k8s::k8s;
+use crate::offline::{decrypt, encrypt};
 use crate::send::send;
 use crate::token::token;
+use crate::version::version;
 
 #[tokio::main]
 async fn main() -> ExitCode {
+    platform::init_console();
     let args = Args::parse();
+    i18n::init(args.lang);
 
-    if let Err(err) = process_command(args).await {
+    let notify = args.notify;
+    let receiving = matches!(args.command, cli::Command::Get(_) | cli::Command::K8s(_));
+
+    let result = process_command(args).await;
+    if notify {
+        notification::notify(&result);
+    }
+
+    if let Err(err) = result {
         eprintln!("{}", err.to_string().red());
-        ExitCode::FAILURE
+        Failure::classify(&err, receiving).into()
     } else {
         ExitCode::SUCCESS
     }
@@ -39,5 +71,11 @@ async fn process_command(args: Args) -> Result<()> {
         cli::Command::Get(get_args) => get(app_factory, get_args).await,
         cli::Command::Send(send_args) => send(app_factory, send_args).await,
         cli::Command::Token(token_args) => token(token_args).await,
+        cli::Command::Admin(admin_args) => admin(admin_args).await,
+        cli::Command::History(history_args) => history(history_args).await,
+        cli::Command::Encrypt(encrypt_args) => encrypt(encrypt_args),
+        cli::Command::Decrypt(decrypt_args) => decrypt(decrypt_args),
+        cli::Command::K8s(k8s_args) => k8s(app_factory, k8s_args).await,
+        cli::Command::Version(version_args) => version(app_factory, version_args).await,
     }
 }
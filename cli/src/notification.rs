@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use colored::Colorize;
+use notify_rust::Notification;
+
+use crate::i18n::{Message, t};
+
+const SUMMARY: &str = "Hakanai";
+
+/// Shows a desktop notification with the outcome of the command. Failures to notify only result
+/// in a warning, the outcome of the command is not affected.
+pub fn notify(result: &Result<()>) {
+    let body = notification_body(result);
+    if let Err(e) = Notification::new().summary(SUMMARY).body(&body).show() {
+        eprintln!(
+            "{}",
+            format!("Warning: Failed to show desktop notification: {e}").yellow()
+        );
+    }
+}
+
+fn notification_body(result: &Result<()>) -> String {
+    match result {
+        Ok(()) => t(Message::CommandSucceeded).to_string(),
+        Err(err) => format!("{} {err}", t(Message::CommandFailed)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_notification_body() {
+        assert_eq!(
+            notification_body(&Ok(())),
+            "Command completed successfully."
+        );
+        assert_eq!(
+            notification_body(&Err(anyhow!("Secret not found"))),
+            "Command failed: Secret not found"
+        );
+    }
+}
@@ -1,28 +1,46 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 use anyhow::Result;
 use async_trait::async_trait;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
+
+use hakanai_lib::observer::{DataTransferObserver, TransferPhase, TransferStats};
+use hakanai_lib::utils::human_size;
+
+use crate::i18n::{Message, t};
 
-use hakanai_lib::observer::DataTransferObserver;
+const TEMPLATE: &str = "{prefix}\n{spinner:.green} [{elapsed_precise}] [{bar:40.white/gray}] {bytes}/{total_bytes} ({percent}%) {msg}";
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
 
 /// A progress observer that displays a progress bar in the terminal.
+///
+/// The label is shown together with the current phase of the transfer, the throughput and the
+/// estimated remaining time are shown next to the bar.
 pub struct ProgressObserver {
     progress_bar: ProgressBar,
+    label: String,
 }
 
 impl ProgressObserver {
     /// Creates a new `ProgressObserver` with the given label.
     pub fn new(label: &str) -> Result<Self> {
-        let progress_bar = ProgressBar::new(0);
+        Self::with_progress_bar(ProgressBar::new(0), label)
+    }
+
+    fn with_progress_bar(progress_bar: ProgressBar, label: &str) -> Result<Self> {
         progress_bar.set_style(
             ProgressStyle::default_bar()
-            .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{bar:40.white/gray}] {bytes}/{total_bytes} ({percent}%) {bytes_per_sec} ETA: {eta}")?
-            .progress_chars("██▓▒░  ")
+                .template(TEMPLATE)?
+                .progress_chars("██▓▒░  "),
         );
-        progress_bar.set_message(label.to_string());
+        progress_bar.set_prefix(label.to_string());
 
-        Ok(Self { progress_bar })
+        Ok(Self {
+            progress_bar,
+            label: label.to_string(),
+        })
     }
 }
 
@@ -34,11 +52,26 @@ impl DataTransferObserver for ProgressObserver {
         }
 
         self.progress_bar.set_position(bytes_transferred);
+    }
 
-        if bytes_transferred >= total_bytes {
-            self.progress_bar.finish_and_clear();
+    async fn on_phase(&self, phase: TransferPhase) {
+        self.progress_bar
+            .set_prefix(format!("{} ({})", self.label, phase_text(phase)));
+
+        match phase {
+            // no progress is reported in these phases, keep the spinner moving
+            TransferPhase::Encrypting | TransferPhase::Waiting | TransferPhase::Decrypting => {
+                self.progress_bar.enable_steady_tick(TICK_INTERVAL);
+            }
+            TransferPhase::Uploading | TransferPhase::Downloading => {
+                self.progress_bar.disable_steady_tick();
+            }
         }
     }
+
+    async fn on_stats(&self, stats: &TransferStats) {
+        self.progress_bar.set_message(stats_text(stats));
+    }
 }
 
 impl Drop for ProgressObserver {
@@ -49,29 +82,40 @@ impl Drop for ProgressObserver {
     }
 }
 
+fn phase_text(phase: TransferPhase) -> &'static str {
+    match phase {
+        TransferPhase::Encrypting => t(Message::PhaseEncrypting),
+        TransferPhase::Uploading => t(Message::PhaseUploading),
+        TransferPhase::Waiting => t(Message::PhaseWaiting),
+        TransferPhase::Downloading => t(Message::PhaseDownloading),
+        TransferPhase::Decrypting => t(Message::PhaseDecrypting),
+    }
+}
+
+fn stats_text(stats: &TransferStats) -> String {
+    let rate = human_size::format_size(stats.bytes_per_sec as u64);
+    match stats.eta {
+        Some(eta) if stats.bytes_transferred < stats.total_bytes => {
+            format!("{rate}/s ETA: {}", HumanDuration(eta))
+        }
+        _ => format!("{rate}/s"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn create_test_observer(label: &str) -> Result<ProgressObserver> {
         // Create a hidden progress bar for testing to avoid terminal interference
-        let progress_bar = ProgressBar::hidden();
-        progress_bar.set_style(
-            ProgressStyle::default_bar()
-            .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{bar:40.white/gray}] {bytes}/{total_bytes} ({percent}%) {bytes_per_sec} ETA: {eta}")
-            .expect("invalid template")
-            .progress_chars("██▓▒░  ")
-        );
-        progress_bar.set_message(label.to_string());
-
-        Ok(ProgressObserver { progress_bar })
+        ProgressObserver::with_progress_bar(ProgressBar::hidden(), label)
     }
 
     #[test]
     fn test_progress_observer_creation() -> Result<()> {
         // Test that the public constructor works (this may create a visible progress bar briefly)
         let observer = ProgressObserver::new("Test message")?;
-        assert_eq!(observer.progress_bar.message(), "Test message");
+        assert_eq!(observer.progress_bar.prefix(), "Test message");
         assert_eq!(observer.progress_bar.length(), Some(0));
         Ok(())
     }
@@ -80,10 +124,58 @@ mod tests {
     fn test_progress_observer_creation_with_empty_label() -> Result<()> {
         // Use hidden progress bar to avoid terminal interference in tests
         let observer = create_test_observer("")?;
-        assert_eq!(observer.progress_bar.message(), "");
+        assert_eq!(observer.progress_bar.prefix(), "");
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_on_phase_shows_phase_next_to_label() -> Result<()> {
+        let observer = create_test_observer("Test")?;
+
+        observer.on_phase(TransferPhase::Uploading).await;
+        assert_eq!(
+            observer.progress_bar.prefix(),
+            format!("Test ({})", t(Message::PhaseUploading)),
+            "Prefix should contain the label and the phase"
+        );
+
+        observer.on_phase(TransferPhase::Waiting).await;
+        assert_eq!(
+            observer.progress_bar.prefix(),
+            format!("Test ({})", t(Message::PhaseWaiting)),
+            "Prefix should be updated on phase change"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_on_stats_shows_rate_and_eta() -> Result<()> {
+        let observer = create_test_observer("Test")?;
+
+        let stats = TransferStats::new(1024 * 1024, 3 * 1024 * 1024, Duration::from_secs(1));
+        observer.on_stats(&stats).await;
+        assert_eq!(observer.progress_bar.message(), "1 MiB/s ETA: 2 seconds");
+
+        let stats = TransferStats::new(3 * 1024 * 1024, 3 * 1024 * 1024, Duration::from_secs(3));
+        observer.on_stats(&stats).await;
+        assert_eq!(
+            observer.progress_bar.message(),
+            "1 MiB/s",
+            "Completed transfers should not show an ETA"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_text_without_throughput() {
+        let stats = TransferStats::new(0, 100, Duration::ZERO);
+        assert_eq!(
+            stats_text(&stats),
+            "0 B/s",
+            "Unknown throughput should not show an ETA"
+        );
+    }
+
     #[tokio::test]
     async fn test_on_progress_sets_length_on_first_call() -> Result<()> {
         let observer = create_test_observer("Test")?;
@@ -121,8 +213,9 @@ mod tests {
         observer.on_progress(100, 100).await;
         assert_eq!(observer.progress_bar.position(), 100);
 
-        // Progress bar should be finished but we can't easily test finish_and_clear()
-        // since it modifies terminal state
+        // The progress bar is cleared when the observer is dropped, so the following phases
+        // are still shown
+        assert!(!observer.progress_bar.is_finished());
         Ok(())
     }
 
@@ -130,7 +223,7 @@ mod tests {
     async fn test_on_progress_over_completion() -> Result<()> {
         let observer = create_test_observer("Test")?;
 
-        // Progress beyond total should still trigger completion
+        // Progress beyond total should not panic
         observer.on_progress(150, 100).await;
         // For hidden progress bars, position is not clamped
         assert_eq!(observer.progress_bar.position(), 150);
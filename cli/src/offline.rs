@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline encryption and decryption of envelopes, without any server interaction.
+
+use std::env::current_dir;
+use std::fs;
+use std::io::{self, Cursor, Read, Write};
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use zeroize::Zeroizing;
+
+use hakanai_lib::envelope;
+use hakanai_lib::models::{Payload, PayloadDataType};
+use hakanai_lib::utils::timestamp;
+
+use crate::args::{DecryptArgs, EncryptArgs, OnConflict};
+use crate::get::{create_output_file, print_to_stdout, write_to_file};
+use crate::i18n::{Message, t};
+use crate::platform;
+
+/// Encrypts the secret into an envelope file and prints the key to stdout.
+pub fn encrypt(args: EncryptArgs) -> Result<()> {
+    let payload = read_payload(&args)?;
+    if payload.data.is_empty() {
+        return Err(anyhow!(t(Message::NoSecretProvided)));
+    }
+
+    let sealed = envelope::seal(&payload)?;
+
+    let on_conflict = if args.overwrite {
+        OnConflict::Overwrite
+    } else {
+        OnConflict::Fail
+    };
+    let mut file = create_output_file(&args.out, on_conflict)?
+        .ok_or(anyhow!("File '{}' already exists.", args.out.display()))?;
+    file.write_all(&sealed.data)?;
+
+    eprintln!(
+        "{} {}",
+        t(Message::SavedTo),
+        args.out.display().to_string().cyan()
+    );
+    println!("{}", sealed.key.as_str());
+
+    Ok(())
+}
+
+/// Decrypts an envelope file created by [`encrypt`] (or downloaded from a server).
+pub fn decrypt(args: DecryptArgs) -> Result<()> {
+    let key = args.key()?;
+    let data = fs::read(&args.envelope)?;
+    let mut payload = envelope::open(data, &key)?;
+
+    let bytes = payload.take_data();
+    if args.to_stdout {
+        print_to_stdout(&bytes, args.force)?;
+    } else if let Some(out) = &args.out {
+        let mut file = create_output_file(out, OnConflict::Fail)?
+            .ok_or(anyhow!("File '{}' already exists.", out.display()))?;
+        file.write_all(&bytes)?;
+        println!(
+            "{} {}",
+            t(Message::SavedTo),
+            out.display().to_string().cyan()
+        );
+    } else if let Some(filename) = payload.filename.as_deref() {
+        let filename = platform::sanitize_filename(filename);
+        write_to_file(
+            filename,
+            Cursor::<&[u8]>::new(bytes.as_ref()),
+            &current_dir()?,
+            OnConflict::Rename,
+        )?;
+    } else if payload.data_type() == PayloadDataType::Binary {
+        let filename = format!("secret-{}", timestamp::now_string()?);
+        write_to_file(
+            filename,
+            Cursor::<&[u8]>::new(bytes.as_ref()),
+            &current_dir()?,
+            OnConflict::Rename,
+        )?;
+    } else {
+        print_to_stdout(&bytes, args.force)?;
+    }
+
+    Ok(())
+}
+
+fn read_payload(args: &EncryptArgs) -> Result<Payload> {
+    let Some(path) = &args.file else {
+        let mut bytes = Zeroizing::new(Vec::new());
+        io::stdin().read_to_end(&mut bytes)?;
+        return Ok(Payload::from_vec(std::mem::take(&mut *bytes)));
+    };
+
+    let bytes = Zeroizing::new(fs::read(path)?);
+    let payload = Payload::from_bytes(&bytes);
+    match file_name(path) {
+        Some(name) => Ok(payload.with_filename(&name)),
+        None => Ok(payload),
+    }
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_encrypt_writes_envelope() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("credentials.txt");
+        fs::write(&input, b"offline secret")?;
+
+        let out = temp_dir.path().join("blob.hakanai");
+        encrypt(EncryptArgs {
+            file: Some(input),
+            out: out.clone(),
+            overwrite: false,
+        })?;
+
+        let data = fs::read(&out)?;
+        assert!(!data.is_empty(), "Envelope should be written");
+        assert!(
+            !data.windows(14).any(|w| w == b"offline secret"),
+            "Envelope should not contain the plaintext"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypt_does_not_overwrite() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let input = temp_dir.path().join("credentials.txt");
+        fs::write(&input, b"offline secret")?;
+        let out = temp_dir.path().join("blob.hakanai");
+        fs::write(&out, b"existing")?;
+
+        let result = encrypt(EncryptArgs {
+            file: Some(input),
+            out: out.clone(),
+            overwrite: false,
+        });
+
+        assert!(result.is_err(), "Existing file should not be overwritten");
+        assert_eq!(fs::read(&out)?, b"existing");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_to_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let sealed = envelope::seal(&Payload::from_bytes(b"offline secret"))?;
+        let envelope_path = temp_dir.path().join("blob.hakanai");
+        fs::write(&envelope_path, &sealed.data)?;
+
+        let out = temp_dir.path().join("secret.txt");
+        decrypt(DecryptArgs {
+            envelope: envelope_path,
+            key: Some(sealed.key.to_string()),
+            ask_key: false,
+            out: Some(out.clone()),
+            to_stdout: false,
+            force: false,
+        })?;
+
+        assert_eq!(fs::read(&out)?, b"offline secret");
+        Ok(())
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let sealed = envelope::seal(&Payload::from_bytes(b"offline secret"))?;
+        let other = envelope::seal(&Payload::from_bytes(b"other secret"))?;
+        let envelope_path = temp_dir.path().join("blob.hakanai");
+        fs::write(&envelope_path, &sealed.data)?;
+
+        let out = temp_dir.path().join("secret.txt");
+        let result = decrypt(DecryptArgs {
+            envelope: envelope_path,
+            key: Some(other.key.to_string()),
+            ask_key: false,
+            out: Some(out.clone()),
+            to_stdout: false,
+            force: false,
+        });
+
+        assert!(result.is_err(), "Decryption with a wrong key should fail");
+        assert!(!out.exists(), "No output should be written");
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Platform specifics of file handling and console output, mostly for Windows.
+
+use std::path::{Path, PathBuf};
+
+/// Paths longer than this need the extended-length prefix on Windows.
+#[cfg(windows)]
+const MAX_PATH: usize = 260;
+
+#[cfg_attr(not(windows), allow(dead_code))]
+const EXTENDED_LENGTH_PREFIX: &str = r"\\?\";
+
+/// Characters not allowed in filenames on Windows, `/` is a path separator everywhere.
+const ILLEGAL_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Invisible formatting characters which can disguise the extension of a file, e.g. the
+/// right-to-left override in `invoice\u{202E}fdp.exe` displayed as `invoiceexe.pdf`.
+const INVISIBLE_CHARS: &[char] = &[
+    '\u{200B}', '\u{200C}', '\u{200D}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}',
+    '\u{202D}', '\u{202E}', '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}', '\u{FEFF}',
+];
+
+/// Maximum length of a filename in bytes on common file systems.
+const MAX_FILENAME_LENGTH: usize = 255;
+
+/// Extensions up to this length (including the dot) are kept when shortening filenames.
+const MAX_EXTENSION_LENGTH: usize = 16;
+
+/// Device names reserved on Windows, also with any extension (e.g. `con.txt`).
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Prepares the console for colored output. Windows terminals need virtual terminal processing
+/// enabled to interpret ANSI escape sequences, colors are disabled if this is not supported.
+pub fn init_console() {
+    #[cfg(windows)]
+    if colored::control::set_virtual_terminal(true).is_err() {
+        colored::control::set_override(false);
+    }
+}
+
+/// Returns the path usable for file operations. On Windows long paths get the extended-length
+/// prefix, so they are not limited to `MAX_PATH` characters. Other platforms use the path as is.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        if path.as_os_str().len() >= MAX_PATH {
+            let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+            return PathBuf::from(extended_length(&absolute.to_string_lossy()));
+        }
+    }
+
+    path.to_path_buf()
+}
+
+/// Adds the extended-length prefix to an absolute Windows path, UNC paths use `\\?\UNC\`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn extended_length(path: &str) -> String {
+    if path.starts_with(EXTENDED_LENGTH_PREFIX) {
+        return path.to_string();
+    }
+
+    match path.strip_prefix(r"\\") {
+        Some(unc) => format!(r"{EXTENDED_LENGTH_PREFIX}UNC\{unc}"),
+        None => format!("{EXTENDED_LENGTH_PREFIX}{path}"),
+    }
+}
+
+/// Makes a filename received with a secret safe to use on all platforms: path separators, characters
+/// illegal on Windows and control characters are replaced, invisible formatting characters and
+/// trailing dots and spaces removed, reserved device names prefixed and long names shortened.
+pub fn sanitize_filename(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .filter(|c| !INVISIBLE_CHARS.contains(c))
+        .map(|c| {
+            if ILLEGAL_CHARS.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let sanitized = truncate_filename(sanitized.trim_start().trim_end_matches(['.', ' ']));
+
+    if sanitized.is_empty() {
+        return "secret".to_string();
+    }
+
+    let stem = sanitized.split('.').next().unwrap_or_default();
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem.trim_end()))
+    {
+        return format!("_{sanitized}");
+    }
+
+    sanitized
+}
+
+/// Shortens the name to `MAX_FILENAME_LENGTH` bytes, keeping a short extension.
+fn truncate_filename(name: &str) -> String {
+    if name.len() <= MAX_FILENAME_LENGTH {
+        return name.to_string();
+    }
+
+    let extension = name
+        .rfind('.')
+        .filter(|i| *i > 0 && name.len() - i <= MAX_EXTENSION_LENGTH)
+        .map_or("", |i| &name[i..]);
+
+    let mut end = MAX_FILENAME_LENGTH - extension.len();
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{extension}", &name[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_filename_keeps_valid_names() {
+        for name in [
+            "document.pdf",
+            "archive.tar.zst",
+            ".env",
+            "Übersicht 2025.txt",
+        ] {
+            assert_eq!(sanitize_filename(name), name, "'{name}' should be kept");
+        }
+    }
+
+    #[test]
+    fn test_sanitize_filename_replaces_illegal_chars() {
+        assert_eq!(
+            sanitize_filename("a<b>c:d\"e|f?g*h.txt"),
+            "a_b_c_d_e_f_g_h.txt"
+        );
+        assert_eq!(sanitize_filename("../../etc/passwd"), ".._.._etc_passwd");
+        assert_eq!(sanitize_filename("dir\\file.txt"), "dir_file.txt");
+        assert_eq!(sanitize_filename("bell\u{7}.txt"), "bell_.txt");
+    }
+
+    #[test]
+    fn test_sanitize_filename_trailing_dots_and_spaces() {
+        assert_eq!(sanitize_filename("report.pdf. . "), "report.pdf");
+        assert_eq!(sanitize_filename(".."), "secret");
+        assert_eq!(sanitize_filename(""), "secret");
+    }
+
+    #[test]
+    fn test_sanitize_filename_removes_invisible_chars() {
+        assert_eq!(
+            sanitize_filename("invoice\u{202E}fdp.exe"),
+            "invoicefdp.exe"
+        );
+        assert_eq!(sanitize_filename("\u{FEFF} report.pdf"), "report.pdf");
+    }
+
+    #[test]
+    fn test_sanitize_filename_shortens_long_names() {
+        let name = format!("{}.pdf", "ä".repeat(200));
+        let sanitized = sanitize_filename(&name);
+        assert!(
+            sanitized.len() <= MAX_FILENAME_LENGTH,
+            "Name should be shortened to {MAX_FILENAME_LENGTH} bytes, got {}",
+            sanitized.len()
+        );
+        assert!(sanitized.ends_with("ä.pdf"), "Extension should be kept");
+    }
+
+    #[test]
+    fn test_sanitize_filename_reserved_names() {
+        assert_eq!(sanitize_filename("CON"), "_CON");
+        assert_eq!(sanitize_filename("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize_filename("com1.tar.gz"), "_com1.tar.gz");
+        assert_eq!(sanitize_filename("console.txt"), "console.txt");
+    }
+
+    #[test]
+    fn test_extended_length() {
+        assert_eq!(
+            extended_length(r"C:\Users\user\file.txt"),
+            r"\\?\C:\Users\user\file.txt"
+        );
+        assert_eq!(
+            extended_length(r"\\server\share\file.txt"),
+            r"\\?\UNC\server\share\file.txt"
+        );
+        assert_eq!(
+            extended_length(r"\\?\C:\file.txt"),
+            r"\\?\C:\file.txt",
+            "Prefixed paths should be kept"
+        );
+    }
+
+    #[test]
+    fn test_long_path_keeps_short_paths() {
+        let path = Path::new("output").join("file.txt");
+        assert_eq!(long_path(&path), path);
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rendering of secret templates read from stdin with `send --render`.
+//!
+//! Supported placeholders are `{{env:VAR}}` (value of an environment variable) and
+//! `{{file:path}}` (content of a file, a single trailing newline is removed). Whitespace inside
+//! the braces is ignored. Unknown placeholders, missing variables or files are errors, so a
+//! secret is never sent half rendered.
+
+use std::fs;
+
+use anyhow::{Result, anyhow};
+use zeroize::Zeroizing;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+/// Renders the template with values of the environment and the file system.
+pub fn render(template: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    render_with(
+        template,
+        |name| std::env::var(name).ok(),
+        |path| fs::read(path).map_err(|e| anyhow!("Failed to read file '{path}': {e}")),
+    )
+}
+
+fn render_with(
+    template: &[u8],
+    env: impl Fn(&str) -> Option<String>,
+    read_file: impl Fn(&str) -> Result<Vec<u8>>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    let template =
+        std::str::from_utf8(template).map_err(|_| anyhow!("The template must be valid UTF-8."))?;
+
+    let mut rendered = Zeroizing::new(Vec::with_capacity(template.len()));
+    let mut rest = template;
+    while let Some(start) = rest.find(OPEN) {
+        rendered.extend_from_slice(&rest.as_bytes()[..start]);
+
+        let after_open = &rest[start + OPEN.len()..];
+        let end = after_open
+            .find(CLOSE)
+            .ok_or_else(|| anyhow!("Unterminated placeholder in template."))?;
+        let placeholder = after_open[..end].trim();
+
+        let value = match placeholder.split_once(':') {
+            Some(("env", name)) => {
+                let name = name.trim();
+                Zeroizing::new(
+                    env(name)
+                        .ok_or_else(|| anyhow!("Environment variable '{name}' is not set."))?
+                        .into_bytes(),
+                )
+            }
+            Some(("file", path)) => {
+                let mut content = Zeroizing::new(read_file(path.trim())?);
+                strip_trailing_newline(&mut content);
+                content
+            }
+            _ => {
+                return Err(anyhow!(
+                    "Unknown placeholder '{{{{{placeholder}}}}}', expected {{{{env:VAR}}}} or {{{{file:path}}}}."
+                ));
+            }
+        };
+        rendered.extend_from_slice(&value);
+
+        rest = &after_open[end + CLOSE.len()..];
+    }
+    rendered.extend_from_slice(rest.as_bytes());
+
+    Ok(rendered)
+}
+
+fn strip_trailing_newline(content: &mut Vec<u8>) {
+    if content.last() == Some(&b'\n') {
+        content.pop();
+        if content.last() == Some(&b'\r') {
+            content.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_test(template: &str) -> Result<String> {
+        let rendered = render_with(
+            template.as_bytes(),
+            |name| (name == "TOKEN").then(|| "s3cr3t".to_string()),
+            |path| match path {
+                "ca.pem" => Ok(b"-----BEGIN CERTIFICATE-----\r\n".to_vec()),
+                _ => Err(anyhow!("Failed to read file '{path}'")),
+            },
+        )?;
+        Ok(String::from_utf8(rendered.to_vec())?)
+    }
+
+    #[test]
+    fn test_render_placeholders() -> Result<()> {
+        assert_eq!(
+            render_test("token: {{env:TOKEN}}\nca: {{ file: ca.pem }}\n")?,
+            "token: s3cr3t\nca: -----BEGIN CERTIFICATE-----\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_without_placeholders() -> Result<()> {
+        assert_eq!(render_test("plain { text }")?, "plain { text }");
+        assert_eq!(render_test("")?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_errors() {
+        for (template, expected) in [
+            ("{{env:MISSING}}", "'MISSING' is not set"),
+            ("{{file:missing.txt}}", "missing.txt"),
+            ("{{secret:x}}", "Unknown placeholder '{{secret:x}}'"),
+            ("{{env:TOKEN", "Unterminated placeholder"),
+        ] {
+            let result = render_test(template);
+            assert!(
+                result
+                    .as_ref()
+                    .is_err_and(|e| e.to_string().contains(expected)),
+                "'{template}' should fail with '{expected}', got: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_rejects_invalid_utf8() {
+        let result = render_with(&[0xff, 0xfe], |_| None, |_| Ok(Vec::new()));
+        assert!(result.is_err(), "Invalid UTF-8 should be rejected");
+    }
+}
@@ -4,12 +4,23 @@ use anyhow::{Result, anyhow};
 use colored::Colorize;
 use rpassword::prompt_password;
 
-use hakanai_lib::models::{CreateTokenRequest, CreateTokenResponse};
+use std::time::Duration;
 
-use crate::args::TokenArgs;
+use hakanai_lib::models::{CreateTokenRequest, CreateTokenResponse, TokenInfoResponse};
+use hakanai_lib::utils::human_size;
+
+use crate::args::{TokenArgs, TokenCommand, TokenInfoArgs};
 use crate::helper;
+use crate::i18n::{Message, t};
 
 pub async fn token(args: TokenArgs) -> Result<()> {
+    match args.command.clone() {
+        Some(TokenCommand::Info(info_args)) => token_info(info_args).await,
+        None => create_token(args).await,
+    }
+}
+
+async fn create_token(args: TokenArgs) -> Result<()> {
     let admin_token = prompt_password("Enter admin token: ")?;
     if admin_token.is_empty() {
         return Err(anyhow!("Admin token cannot be empty"));
@@ -17,8 +28,8 @@ pub async fn token(args: TokenArgs) -> Result<()> {
 
     let resp = create_token_request(&admin_token, &args).await?;
 
-    println!("\n{}", "Token created successfully!".green().bold());
-    println!("\n{}", "User token:".bold());
+    println!("\n{}", t(Message::TokenCreated).green().bold());
+    println!("\n{}", t(Message::UserToken).bold());
     println!("{}", resp.token.cyan());
     Ok(())
 }
@@ -27,6 +38,7 @@ async fn create_token_request(admin_token: &str, args: &TokenArgs) -> Result<Cre
     let mut request = CreateTokenRequest::new(args.ttl.as_secs());
     request.upload_size_limit = args.limit;
     request.one_time = args.one_time;
+    request.scope = args.scope();
 
     let client = reqwest::Client::new();
     let url = args.server.join("api/v1/admin/tokens")?;
@@ -54,3 +66,123 @@ async fn create_token_request(admin_token: &str, args: &TokenArgs) -> Result<Cre
 
     Ok(response.json().await?)
 }
+
+async fn token_info(args: TokenInfoArgs) -> Result<()> {
+    let token = match args.token()? {
+        Some(token) => token,
+        None => prompt_password("Enter token: ")?,
+    };
+    if token.is_empty() {
+        return Err(anyhow!("Token cannot be empty"));
+    }
+
+    let info = token_info_request(&token, &args).await?;
+    print!("{}", format_token_info(&info));
+    Ok(())
+}
+
+async fn token_info_request(token: &str, args: &TokenInfoArgs) -> Result<TokenInfoResponse> {
+    let client = reqwest::Client::new();
+    let url = args.server.join("api/v1/token/self")?;
+
+    let response = client
+        .get(url)
+        .header("User-Agent", helper::get_user_agent_name())
+        .header("Authorization", format!("Bearer {token}"))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(anyhow!(
+            "Failed to retrieve token info: {} - {}",
+            status,
+            error_text
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+fn format_token_info(info: &TokenInfoResponse) -> String {
+    let limit = match info.upload_size_limit {
+        Some(limit) => human_size::format_size(limit.max(0) as u64),
+        None => "server default".to_string(),
+    };
+    let expires = match info.ttl_remaining_seconds {
+        Some(secs) => humantime::format_duration(Duration::from_secs(secs)).to_string(),
+        None => "never".to_string(),
+    };
+    let one_time = if info.one_time { "yes" } else { "no" };
+
+    let mut out = String::new();
+    out.push_str(&format!("{}\n", "Token".bold()));
+    out.push_str(&format!("  Upload size limit: {limit}\n"));
+    out.push_str(&format!("  One-time use:      {one_time}\n"));
+    out.push_str(&format!("  Expires in:        {expires}\n"));
+
+    let scope = &info.scope;
+    if !scope.can_create {
+        out.push_str("  Create secrets:    no\n");
+    }
+    if let Some(ttl_max) = scope.allowed_ttl_max {
+        let ttl_max = humantime::format_duration(Duration::from_secs(ttl_max));
+        out.push_str(&format!("  Max secret TTL:    {ttl_max}\n"));
+    }
+    if scope.require_passphrase {
+        out.push_str("  Passphrase:        required\n");
+    }
+    if let Some(ips) = &scope.allowed_ips {
+        let ips = ips.iter().map(|ip| ip.to_string()).collect::<Vec<_>>();
+        out.push_str(&format!("  Pinned IPs:        {}\n", ips.join(", ")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_token_info() {
+        let info = TokenInfoResponse {
+            upload_size_limit: Some(1024 * 1024),
+            one_time: true,
+            ttl_remaining_seconds: Some(3600),
+            scope: Default::default(),
+        };
+
+        let output = format_token_info(&info);
+        assert!(output.contains("1 MiB"), "Output: {output}");
+        assert!(output.contains("yes"), "Output: {output}");
+        assert!(output.contains("1h"), "Output: {output}");
+    }
+
+    #[test]
+    fn test_format_token_info_defaults() {
+        let output = format_token_info(&TokenInfoResponse::default());
+        assert!(output.contains("server default"), "Output: {output}");
+        assert!(output.contains("never"), "Output: {output}");
+        assert!(
+            !output.contains("Passphrase"),
+            "Unrestricted scope should not be shown: {output}"
+        );
+    }
+
+    #[test]
+    fn test_format_token_info_with_scope() {
+        let mut info = TokenInfoResponse::default();
+        info.scope.allowed_ttl_max = Some(600);
+        info.scope.require_passphrase = true;
+        info.scope.allowed_ips = Some(vec!["10.0.0.0/8".parse().expect("valid range")]);
+
+        let output = format_token_info(&info);
+        assert!(output.contains("10m"), "Output: {output}");
+        assert!(output.contains("required"), "Output: {output}");
+        assert!(output.contains("10.0.0.0/8"), "Output: {output}");
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use colored::Colorize;
+use url::Url;
+
+use hakanai_lib::client::Client;
+use hakanai_lib::utils::version::compatibility_warnings;
+
+use crate::args::VersionArgs;
+use crate::factory::Factory;
+
+const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub async fn version<T: Factory>(factory: T, args: VersionArgs) -> Result<()> {
+    println!("hakanai {CLIENT_VERSION}");
+
+    let Some(server) = args.remote else {
+        return Ok(());
+    };
+
+    let config = factory
+        .new_client()
+        .get_server_config(server.clone())
+        .await?;
+    match &config.version {
+        Some(server_version) => {
+            println!("Server {server}: {server_version}");
+            print_warnings(&compatibility_warnings(CLIENT_VERSION, server_version));
+        }
+        None => println!("Server {server}: unknown (not reported by the server)"),
+    }
+
+    Ok(())
+}
+
+/// Prints warnings about known incompatibilities with the server at `base_url`.
+///
+/// Servers not reporting their version or not reachable are skipped, errors are reported by the
+/// actual request.
+pub async fn warn_if_incompatible<T: Factory>(factory: &T, base_url: Url) {
+    print_warnings(&server_warnings(factory, base_url).await);
+}
+
+async fn server_warnings<T: Factory>(factory: &T, base_url: Url) -> Vec<String> {
+    let Ok(config) = factory.new_client().get_server_config(base_url).await else {
+        return vec![];
+    };
+
+    config
+        .version
+        .map(|server_version| compatibility_warnings(CLIENT_VERSION, &server_version))
+        .unwrap_or_default()
+}
+
+fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("{}", format!("Warning: {warning}").yellow());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hakanai_lib::client_mock::MockClient;
+    use hakanai_lib::models::ServerConfig;
+    use hakanai_lib::utils::test::MustParse;
+
+    use crate::factory_mock::test_utils::MockFactory;
+
+    fn factory_with_server_version(version: Option<&str>) -> MockFactory {
+        let config = ServerConfig {
+            version: version.map(str::to_string),
+            ..Default::default()
+        };
+        MockFactory::new().with_client(MockClient::new().with_server_config(config))
+    }
+
+    #[tokio::test]
+    async fn test_server_warnings_compatible() {
+        let factory = factory_with_server_version(Some(CLIENT_VERSION));
+
+        let warnings = server_warnings(&factory, "https://example.com".must_parse()).await;
+        assert!(warnings.is_empty(), "Same version should be compatible");
+    }
+
+    #[tokio::test]
+    async fn test_server_warnings_incompatible() {
+        let factory = factory_with_server_version(Some("2.9.0"));
+
+        let warnings = server_warnings(&factory, "https://example.com".must_parse()).await;
+        assert_eq!(
+            warnings.len(),
+            1,
+            "Server before 3.0 should be incompatible"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_warnings_unknown_version() {
+        let factory = factory_with_server_version(None);
+        let warnings = server_warnings(&factory, "https://example.com".must_parse()).await;
+        assert!(warnings.is_empty());
+
+        let factory = MockFactory::new();
+        let warnings = server_warnings(&factory, "https://example.com".must_parse()).await;
+        assert!(warnings.is_empty(), "Unreachable server should be skipped");
+    }
+
+    #[tokio::test]
+    async fn test_version_remote() -> Result<()> {
+        let factory = factory_with_server_version(Some(CLIENT_VERSION));
+        let args = VersionArgs {
+            remote: Some("https://example.com".must_parse()),
+        };
+
+        version(factory, args).await
+    }
+}
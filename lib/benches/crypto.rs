@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the client-side encryption and decryption, run with
+//! `cargo bench -p hakanai-lib --features testing`.
+//!
+//! The secrets are sent to a mock transport, so the numbers cover serialization, encryption and
+//! encoding only, without any network overhead.
+
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use tokio::runtime::Runtime;
+use url::Url;
+
+use hakanai_lib::client::{self, Client};
+use hakanai_lib::client_mock::MockClient;
+use hakanai_lib::models::Payload;
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+const TTL: Duration = Duration::from_secs(3600);
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn base_url() -> Url {
+    Url::parse("https://example.com").expect("URL should be valid")
+}
+
+/// Encrypts a payload of the given size, returning the ciphertext and the link to decrypt it.
+fn encrypt_once(runtime: &Runtime, size: usize) -> (Vec<u8>, Url) {
+    let transport = MockClient::<Vec<u8>>::new();
+    let client = client::with_transport(Box::new(transport.clone()));
+
+    let url = runtime
+        .block_on(client.send_secret(
+            base_url(),
+            Payload::from_bytes(&test_data(size)),
+            TTL,
+            String::new(),
+            None,
+        ))
+        .expect("Encryption should succeed")
+        .url;
+    let ciphertext = transport
+        .get_sent_data()
+        .expect("Ciphertext should be sent");
+
+    (ciphertext, url)
+}
+
+fn encrypt(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("Runtime should be created");
+    let client = client::with_transport(Box::new(MockClient::<Vec<u8>>::new()));
+
+    let mut group = c.benchmark_group("crypto/encrypt");
+    for size in SIZES {
+        let payload = Payload::from_bytes(&test_data(size));
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            b.iter(|| {
+                runtime
+                    .block_on(client.send_secret(
+                        base_url(),
+                        payload.clone(),
+                        TTL,
+                        String::new(),
+                        None,
+                    ))
+                    .expect("Encryption should succeed")
+            })
+        });
+    }
+    group.finish();
+}
+
+fn decrypt(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("Runtime should be created");
+
+    let mut group = c.benchmark_group("crypto/decrypt");
+    for size in SIZES {
+        let (ciphertext, url) = encrypt_once(&runtime, size);
+        let client = client::with_transport(Box::new(
+            MockClient::<Vec<u8>>::new().with_receive_success(ciphertext),
+        ));
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &url, |b, url| {
+            b.iter(|| {
+                runtime
+                    .block_on(client.receive_secret(url.clone(), None))
+                    .expect("Decryption should succeed")
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encrypt, decrypt);
+criterion_main!(benches);
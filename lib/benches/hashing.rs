@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the SHA-256 hashing of secrets, run with `cargo bench -p hakanai-lib`.
+//!
+//! The SHA-256 implementation uses the SHA extensions of the CPU if available (detected at
+//! runtime), so results differ considerably between machines.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use hakanai_lib::utils::hashing;
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn sha256(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hashing/sha256");
+    for size in SIZES {
+        let data = test_data(size);
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| hashing::sha256_truncated_base64_from_bytes(black_box(data)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sha256);
+criterion_main!(benches);
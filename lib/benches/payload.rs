@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the serialization of payloads, run with `cargo bench -p hakanai-lib`.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use hakanai_lib::models::Payload;
+
+const SIZES: [usize; 3] = [1024, 64 * 1024, 1024 * 1024];
+
+fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("payload/serialize");
+    for size in SIZES {
+        let payload = Payload::from_bytes(&test_data(size)).with_filename("bench.bin");
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            b.iter(|| payload.serialize().expect("Serialization should succeed"))
+        });
+    }
+    group.finish();
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("payload/deserialize");
+    for size in SIZES {
+        let bytes = Payload::from_bytes(&test_data(size))
+            .with_filename("bench.bin")
+            .serialize()
+            .expect("Serialization should succeed");
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &bytes, |b, bytes| {
+            b.iter(|| {
+                Payload::deserialize(black_box(bytes)).expect("Deserialization should succeed")
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, serialize, deserialize);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use hakanai_lib::compat::{TestVector, TestVectorPayload};
+use libfuzzer_sys::fuzz_target;
+
+// envelope and link fragment as received by the recipient, split at the first newline
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    let (envelope, fragment) = input.split_once('\n').unwrap_or((&input, ""));
+    let (key, hash) = fragment.split_once(':').unwrap_or((fragment, ""));
+
+    let vector = TestVector {
+        description: "fuzz".to_string(),
+        key: key.to_string(),
+        nonce: String::new(),
+        payload: TestVectorPayload {
+            data: String::new(),
+            filename: None,
+            content_type: None,
+            description: None,
+            checksum: None,
+        },
+        padded: false,
+        plaintext: String::new(),
+        hash: hash.to_string(),
+        envelope: envelope.to_string(),
+        fragment: fragment.to_string(),
+    };
+
+    let _ = hakanai_lib::compat::verify_vector(&vector);
+});
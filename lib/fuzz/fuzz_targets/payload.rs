@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use hakanai_lib::models::Payload;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(payload) = Payload::deserialize(data) {
+        let _ = payload.verify_checksum();
+        let _ = payload.serialize();
+    }
+});
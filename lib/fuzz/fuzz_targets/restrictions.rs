@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use hakanai_lib::models::{PostSecretRequest, SecretRestrictions};
+use libfuzzer_sys::fuzz_target;
+
+// request bodies and restriction headers are provided by clients and parsed by the server
+fuzz_target!(|data: &[u8]| {
+    if let Ok(restrictions) = serde_json::from_slice::<SecretRestrictions>(data) {
+        let _ = restrictions.to_string();
+        let _ = restrictions.is_empty();
+    }
+
+    let _ = serde_json::from_slice::<PostSecretRequest>(data);
+});
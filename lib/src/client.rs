@@ -1,14 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use thiserror::Error;
+use ulid::Ulid;
 use url::Url;
 
 use crate::crypto::CryptoClient;
-use crate::models::Payload;
+use crate::models::{Payload, PostSecretResponse, SecretRestrictions, ServerConfig};
 use crate::options::{SecretReceiveOptions, SecretSendOptions};
+#[cfg(feature = "http-client")]
+use crate::spill::SpillBuffer;
+#[cfg(feature = "http-client")]
 use crate::web::WebClient;
 
 /// Defines the asynchronous interface for a client that can send and receive secrets.
@@ -22,7 +26,7 @@ use crate::web::WebClient;
 /// ## Adding Validation to Client Operations
 ///
 /// ```
-/// use hakanai_lib::{client, client::{Client, ClientError}, models::Payload};
+/// use hakanai_lib::{client, client::{Client, ClientError, SentSecret}, models::{Payload, ServerConfig}};
 /// use hakanai_lib::options::{SecretSendOptions, SecretReceiveOptions};
 /// use async_trait::async_trait;
 /// use url::Url;
@@ -49,7 +53,7 @@ use crate::web::WebClient;
 ///         ttl: Duration,
 ///         token: String,
 ///         opts: Option<SecretSendOptions>,
-///     ) -> Result<Url, ClientError> {
+///     ) -> Result<SentSecret, ClientError> {
 ///         // Validate payload size before sending
 ///         if payload.data.len() > self.max_size {
 ///             return Err(ClientError::Custom(format!(
@@ -80,6 +84,11 @@ use crate::web::WebClient;
 ///         // Pass through to inner client
 ///         self.inner.receive_secret(url, opts).await
 ///     }
+///
+///     async fn get_server_config(&self, base_url: Url) -> Result<ServerConfig, ClientError> {
+///         // Pass through to inner client
+///         self.inner.get_server_config(base_url).await
+///     }
 /// }
 ///
 /// // Usage: wrap the default client with validation
@@ -90,7 +99,7 @@ use crate::web::WebClient;
 /// let payload = Payload::from_bytes(b"test secret");
 ///
 /// // This will validate before sending
-/// let url = validating_client.send_secret(
+/// let sent = validating_client.send_secret(
 ///     Url::parse("https://api.example.com")?,
 ///     payload,
 ///     Duration::from_secs(3600),
@@ -115,7 +124,8 @@ pub trait Client<T>: Send + Sync {
     /// # Returns
     ///
     /// A `Result` which is:
-    /// - `Ok(Url)` containing the URL of the stored secret.
+    /// - `Ok(SentSecret)` containing the URL of the stored secret and the details reported by the
+    ///   server.
     /// - `Err(ClientError)` with an error message if the operation fails.
     async fn send_secret(
         &self,
@@ -124,7 +134,7 @@ pub trait Client<T>: Send + Sync {
         ttl: Duration,
         token: String,
         opts: Option<SecretSendOptions>,
-    ) -> Result<Url, ClientError>;
+    ) -> Result<SentSecret, ClientError>;
 
     /// Retrieves a secret from the store using its URL.
     ///
@@ -143,6 +153,111 @@ pub trait Client<T>: Send + Sync {
         url: Url,
         opts: Option<SecretReceiveOptions>,
     ) -> Result<T, ClientError>;
+
+    /// Retrieves the capabilities and limits advertised by the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the service.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is:
+    /// - `Ok(ServerConfig)` containing the server configuration.
+    /// - `Err(ClientError)` if the configuration could not be retrieved or parsed.
+    async fn get_server_config(&self, base_url: Url) -> Result<ServerConfig, ClientError>;
+
+    /// Returns the transport streaming secrets larger than the memory limit, if supported.
+    ///
+    /// Without it, the memory limit of the options (`max_memory`) does not apply.
+    #[cfg(feature = "http-client")]
+    fn streaming(&self) -> Option<&dyn StreamingTransport> {
+        None
+    }
+}
+
+/// Transport streaming the ciphertext of large secrets from and to a [`SpillBuffer`].
+///
+/// Secrets exceeding the memory limit (`max_memory` of the options) are handed over as
+/// ciphertext in a buffer spilled to an encrypted temporary file. The transport encodes and
+/// decodes the ciphertext in chunks while transferring it, so its encoding is never held in
+/// memory as a whole.
+#[cfg(feature = "http-client")]
+#[async_trait]
+pub trait StreamingTransport: Send + Sync {
+    /// Sends the ciphertext in the buffer, streaming it from the temporary file.
+    async fn send_stream(
+        &self,
+        base_url: Url,
+        ciphertext: SpillBuffer,
+        ttl: Duration,
+        token: String,
+        opts: Option<SecretSendOptions>,
+    ) -> Result<SentSecret, ClientError>;
+
+    /// Retrieves the ciphertext of a secret into a buffer limited to `max_memory` of the options.
+    async fn receive_stream(
+        &self,
+        url: Url,
+        opts: Option<SecretReceiveOptions>,
+    ) -> Result<SpillBuffer, ClientError>;
+}
+
+/// A secret stored by the server, as returned by [`Client::send_secret`].
+///
+/// Besides the URL the server reports when the secret expires, the restrictions it enforces and
+/// the size class of the stored ciphertext. Older servers don't report these details.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SentSecret {
+    /// The URL to retrieve the secret, including the key in the fragment if encrypted by the
+    /// client.
+    pub url: Url,
+
+    /// The time at which the secret expires.
+    pub expires_at: Option<SystemTime>,
+
+    /// The restrictions enforced by the server, without the passphrase hash.
+    pub restrictions: Option<SecretRestrictions>,
+
+    /// The size class of the stored ciphertext, see [`crate::models::secret::size_class`].
+    pub size_class: Option<u64>,
+
+    /// The ID the server reserved for the reply of the recipient, if a reply was allowed.
+    pub reply_id: Option<Ulid>,
+
+    /// The URL to retrieve the reply once the recipient sent it, including the key in the
+    /// fragment if encrypted by the client.
+    pub reply_url: Option<Url>,
+}
+
+impl SentSecret {
+    /// Creates a new `SentSecret` without any details of the server.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            expires_at: None,
+            restrictions: None,
+            size_class: None,
+            reply_id: None,
+            reply_url: None,
+        }
+    }
+
+    /// Sets the details reported by the server when storing the secret.
+    pub fn with_response(mut self, res: &PostSecretResponse) -> Self {
+        self.expires_at = res
+            .expires_at
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        self.restrictions = res.effective_restrictions.clone();
+        self.size_class = res.size_class;
+        self.reply_id = res.reply_id;
+        self
+    }
+
+    /// Returns the time until the secret expires, `None` if unknown or already expired.
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_at?.duration_since(SystemTime::now()).ok()
+    }
 }
 
 /// Represents errors that can occur during client operations.
@@ -168,7 +283,7 @@ pub trait Client<T>: Send + Sync {
 ///     "auth-token".to_string(),
 ///     None,
 /// ).await {
-///     Ok(url) => println!("Secret stored at: {}", url),
+///     Ok(sent) => println!("Secret stored at: {}", sent.url),
 ///     Err(ClientError::Web(e)) => eprintln!("Network error: {}", e),
 ///     Err(ClientError::Http(msg)) => eprintln!("Server error: {}", msg),
 ///     Err(ClientError::CryptoError(msg)) => eprintln!("Decryption failed: {}", msg),
@@ -184,6 +299,7 @@ pub enum ClientError {
     /// This error occurs when the underlying HTTP client (reqwest) encounters
     /// a network-level error such as connection timeout, DNS resolution failure,
     /// or inability to establish a connection.
+    #[cfg(feature = "http-client")]
     #[error("web request failed")]
     Web(#[from] reqwest::Error),
 
@@ -191,6 +307,7 @@ pub enum ClientError {
     ///
     /// This error occurs when the response from the server cannot be parsed
     /// as valid JSON, or when serializing the payload to JSON fails.
+    #[cfg(feature = "http-client")]
     #[error("parsing JSON failed")]
     Json(#[from] serde_json::Error),
 
@@ -213,6 +330,12 @@ pub enum ClientError {
     #[error("invalid URL")]
     Url(#[from] url::ParseError),
 
+    /// The secret link is invalid.
+    ///
+    /// This error occurs when the link does not point to a secret, see [`crate::link`].
+    #[error("invalid link: {0}")]
+    InvalidLink(#[from] crate::link::LinkError),
+
     /// HTTP-level error from the server.
     ///
     /// This error represents HTTP status code errors (4xx, 5xx) returned by
@@ -241,6 +364,32 @@ pub enum ClientError {
 
     #[error("decrypted data does not match expected hash")]
     HashValidationError(),
+
+    /// The checksum embedded in the payload does not match the decrypted data.
+    #[error("decrypted data does not match embedded checksum")]
+    ChecksumMismatch,
+
+    /// The secret exceeds the size limit advertised by the server.
+    #[error("secret size of {size} bytes exceeds the server limit of {limit} bytes")]
+    PayloadTooLarge { size: u64, limit: u64 },
+
+    /// The server does not support a requested feature.
+    #[error("not supported by server: {0}")]
+    Unsupported(String),
+
+    /// Reading or writing a temporary file failed, e.g. when spilling large transfers to disk.
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+}
+
+impl ClientError {
+    /// Returns the HTTP status code if the server responded with an error status.
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            ClientError::Http(msg) => msg.strip_prefix("HTTP error: ")?.get(..3)?.parse().ok(),
+            _ => None,
+        }
+    }
 }
 
 impl From<aes_gcm::Error> for ClientError {
@@ -268,7 +417,7 @@ impl From<aes_gcm::Error> for ClientError {
 /// let client = client::new();
 ///
 /// // Send a secret
-/// let url = client.send_secret(
+/// let sent = client.send_secret(
 ///     Url::parse("https://api.example.com")?,
 ///     Payload::from_bytes(b"my secret data"),
 ///     Duration::from_secs(3600),
@@ -277,10 +426,80 @@ impl From<aes_gcm::Error> for ClientError {
 /// ).await?;
 ///
 /// // The URL contains the encryption key and hash in the fragment (#key:hash)
-/// println!("Share this URL: {}", url);
+/// println!("Share this URL: {}", sent.url);
 /// # Ok(())
 /// # }
 /// ```
+#[cfg(feature = "http-client")]
 pub fn new() -> impl Client<Payload> {
-    CryptoClient::new(Box::new(WebClient::new()))
+    with_transport(Box::new(WebClient::new()))
+}
+
+/// Creates a new client instance encrypting secrets before handing them to the transport client.
+///
+/// This allows embedding the client-side encryption without the HTTP/TLS stack of
+/// `client::new()` (feature `crypto-only`), e.g. to transfer the encrypted secrets via
+/// a custom transport. The transport client receives the ciphertext only.
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::{client, client::Client, models::Payload};
+///
+/// // e.g. a client transferring the secrets via a message queue
+/// fn encrypting_client(transport: Box<dyn Client<Vec<u8>>>) -> impl Client<Payload> {
+///     client::with_transport(transport)
+/// }
+/// ```
+pub fn with_transport(transport: Box<dyn Client<Vec<u8>>>) -> impl Client<Payload> {
+    CryptoClient::new(transport)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sent_secret_with_response() {
+        let url = Url::parse("https://example.com/s/abc").unwrap();
+        let restrictions = SecretRestrictions::default().with_allowed_asns(vec![13335]);
+        let res = PostSecretResponse::new(Ulid::r#gen())
+            .with_expires_at(1_700_000_000)
+            .with_effective_restrictions(&restrictions)
+            .with_size_class(2048);
+
+        let sent = SentSecret::new(url.clone()).with_response(&res);
+
+        assert_eq!(sent.url, url);
+        assert_eq!(
+            sent.expires_at,
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(sent.restrictions, Some(restrictions));
+        assert_eq!(sent.size_class, Some(2048));
+        assert_eq!(sent.expires_in(), None, "Expiry is in the past");
+    }
+
+    #[test]
+    fn test_sent_secret_from_old_server() {
+        let url = Url::parse("https://example.com/s/abc").unwrap();
+        let sent = SentSecret::new(url).with_response(&PostSecretResponse::new(Ulid::r#gen()));
+
+        assert_eq!(sent.expires_at, None);
+        assert_eq!(sent.restrictions, None);
+        assert_eq!(sent.size_class, None);
+        assert_eq!(sent.reply_id, None);
+    }
+
+    #[test]
+    fn test_http_status() {
+        let err = ClientError::Http("HTTP error: 410 Gone\nSecret was already accessed".into());
+        assert_eq!(err.http_status(), Some(410));
+
+        assert_eq!(ClientError::Http("unexpected".into()).http_status(), None);
+        assert_eq!(
+            ClientError::Custom("HTTP error: 404".into()).http_status(),
+            None
+        );
+    }
 }
@@ -11,7 +11,9 @@ use async_trait::async_trait;
 use url::Url;
 
 #[cfg(any(test, feature = "testing"))]
-use crate::client::{Client, ClientError};
+use crate::client::{Client, ClientError, SentSecret};
+#[cfg(any(test, feature = "testing"))]
+use crate::models::ServerConfig;
 #[cfg(any(test, feature = "testing"))]
 use crate::options::{SecretReceiveOptions, SecretSendOptions};
 #[cfg(any(test, feature = "testing"))]
@@ -47,6 +49,7 @@ pub struct MockClient<T> {
     // Response configuration
     response_url: Option<Url>,
     response_data: Option<T>,
+    server_config: Option<ServerConfig>,
 
     // Error configuration
     send_should_fail: bool,
@@ -71,6 +74,7 @@ where
             sent_data: Arc::new(Mutex::new(None)),
             response_url: Some("https://example.com/secret/123".must_parse()),
             response_data: None,
+            server_config: None,
             send_should_fail: false,
             send_error_message: None,
             receive_should_fail: false,
@@ -115,6 +119,14 @@ where
         self
     }
 
+    /// Configure the server configuration returned by `get_server_config`.
+    ///
+    /// Without a configured server config, `get_server_config` fails.
+    pub fn with_server_config(mut self, config: ServerConfig) -> Self {
+        self.server_config = Some(config);
+        self
+    }
+
     /// Configure the mock to fail all operations with the given error message.
     pub fn with_all_failures(mut self, error_message: String) -> Self {
         self.send_should_fail = true;
@@ -171,7 +183,7 @@ where
         _ttl: Duration,
         _token: String,
         _opts: Option<SecretSendOptions>,
-    ) -> Result<Url, ClientError> {
+    ) -> Result<SentSecret, ClientError> {
         // Capture the sent data
         *self.get_sent_data_mut() = Some(data);
 
@@ -183,10 +195,11 @@ where
                 .unwrap_or_else(|| "Mock send error".to_string());
             Err(ClientError::Custom(error_msg))
         } else {
-            Ok(self
+            let url = self
                 .response_url
                 .clone()
-                .unwrap_or_else(|| "https://example.com/secret/default".must_parse()))
+                .unwrap_or_else(|| "https://example.com/secret/default".must_parse());
+            Ok(SentSecret::new(url))
         }
     }
 
@@ -207,6 +220,12 @@ where
                 .ok_or_else(|| ClientError::Custom("No response data configured".to_string()))
         }
     }
+
+    async fn get_server_config(&self, _base_url: Url) -> Result<ServerConfig, ClientError> {
+        self.server_config
+            .clone()
+            .ok_or_else(|| ClientError::Custom("No server config configured".to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -235,7 +254,7 @@ mod tests {
             )
             .await;
 
-        let url = result?;
+        let url = result?.url;
         assert_eq!(url.as_str(), "https://test.com/secret/456");
         assert_eq!(mock.get_sent_data(), Some(test_data));
         Ok(())
@@ -328,7 +347,7 @@ mod tests {
             )
             .await;
 
-        let send_url = send_result?;
+        let send_url = send_result?.url;
         assert_eq!(send_url, test_url);
 
         // Test receive
@@ -416,7 +435,7 @@ mod tests {
             )
             .await;
 
-        let url = result?;
+        let url = result?.url;
         assert_eq!(url.as_str(), "https://example.com/secret/123");
         Ok(())
     }
@@ -0,0 +1,299 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic test vectors of the wire format for cross-implementation compatibility tests.
+//!
+//! The vectors published in [`TEST_VECTORS_JSON`] are generated by this library with fixed keys
+//! and nonces. Other implementations (e.g. the TypeScript client and the WASM build) verify
+//! against them that they can decrypt secrets created by the Rust client.
+//!
+//! The fixture file `lib/fixtures/test-vectors.json` is verified by the tests of this module and
+//! can be regenerated by running them with `HAKANAI_UPDATE_TEST_VECTORS=1` set.
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::compat;
+//! use hakanai_lib::models::Payload;
+//!
+//! # fn example() -> Result<(), hakanai_lib::client::ClientError> {
+//! let payload = Payload::from_bytes(b"my secret").with_filename("secret.txt");
+//! let vector = compat::generate_vector("file", &payload, &[7u8; 32], &[9u8; 12], false)?;
+//!
+//! let decrypted = compat::verify_vector(&vector)?;
+//! assert_eq!(decrypted, payload);
+//! # Ok(())
+//! # }
+//! ```
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::client::ClientError;
+use crate::crypto;
+use crate::crypto::aes::{AESCryptoContext, AESCryptoContextFactory};
+use crate::crypto::crypto_context::{CryptoContext, CryptoContextFactory};
+use crate::models::Payload;
+
+/// Test vectors published as fixture, generated by [`default_vectors`].
+pub const TEST_VECTORS_JSON: &str = include_str!("../fixtures/test-vectors.json");
+
+/// A secret encrypted with a fixed key and nonce, including all intermediate values.
+///
+/// Binary values are encoded as standard base64, unless noted otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestVector {
+    /// Short description of the case covered by the vector.
+    pub description: String,
+
+    /// The encryption key, encoded as URL-safe base64 without padding (as in the link).
+    pub key: String,
+
+    /// The AES-GCM nonce.
+    pub nonce: String,
+
+    /// The payload of the secret.
+    pub payload: TestVectorPayload,
+
+    /// Whether the serialized payload is padded to the next size class.
+    pub padded: bool,
+
+    /// The serialized (and padded) payload, the plaintext of the encryption.
+    pub plaintext: String,
+
+    /// The truncated SHA-256 hash of the plaintext appended to the link.
+    pub hash: String,
+
+    /// The nonce and ciphertext as sent to the server.
+    pub envelope: String,
+
+    /// The fragment of the link (`key:hash`).
+    pub fragment: String,
+}
+
+/// The payload of a [`TestVector`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestVectorPayload {
+    /// The data of the secret.
+    pub data: String,
+
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub description: Option<String>,
+    pub checksum: Option<String>,
+}
+
+impl From<&Payload> for TestVectorPayload {
+    fn from(payload: &Payload) -> Self {
+        Self {
+            data: base64::prelude::BASE64_STANDARD.encode(&payload.data),
+            filename: payload.filename.clone(),
+            content_type: payload.content_type.clone(),
+            description: payload.description.clone(),
+            checksum: payload.checksum.clone(),
+        }
+    }
+}
+
+impl TryFrom<&TestVectorPayload> for Payload {
+    type Error = ClientError;
+
+    fn try_from(payload: &TestVectorPayload) -> Result<Self, Self::Error> {
+        Ok(Payload {
+            data: base64::prelude::BASE64_STANDARD.decode(&payload.data)?,
+            filename: payload.filename.clone(),
+            content_type: payload.content_type.clone(),
+            description: payload.description.clone(),
+            checksum: payload.checksum.clone(),
+            reply: None,
+        })
+    }
+}
+
+/// Encrypts the payload with the given key and nonce the same way `client::new()` does.
+///
+/// Fixed nonces must never be used for real secrets, this is meant for test vectors only.
+pub fn generate_vector(
+    description: &str,
+    payload: &Payload,
+    key: &[u8],
+    nonce: &[u8],
+    padded: bool,
+) -> Result<TestVector, ClientError> {
+    let mut crypto_context = AESCryptoContext::from_key_and_nonce(key, nonce)?;
+
+    let plaintext = crypto::serialize(payload, padded, None)?;
+    let encoded_plaintext = base64::prelude::BASE64_STANDARD.encode(&plaintext);
+    let (envelope, hash) = crypto::encrypt(plaintext, &mut crypto_context, &[])?;
+
+    let key = crypto_context.key_as_base64();
+    Ok(TestVector {
+        description: description.to_string(),
+        fragment: format!("{key}:{hash}"),
+        key,
+        nonce: base64::prelude::BASE64_STANDARD.encode(nonce),
+        payload: payload.into(),
+        padded,
+        plaintext: encoded_plaintext,
+        hash,
+        envelope: String::from_utf8(envelope)?,
+    })
+}
+
+/// Decrypts the envelope of the vector and verifies it against the expected payload.
+///
+/// Returns the decrypted payload.
+pub fn verify_vector(vector: &TestVector) -> Result<Payload, ClientError> {
+    let crypto_context = AESCryptoContextFactory.generate_from_key_base64(&vector.key)?;
+    let payload = crypto::decrypt(
+        vector.envelope.as_bytes().to_vec(),
+        crypto_context,
+        Some(vector.hash.clone()),
+        &[],
+    )?;
+
+    let expected = Payload::try_from(&vector.payload)?;
+    if payload != expected {
+        return Err(ClientError::Custom(format!(
+            "decrypted payload does not match test vector '{}'",
+            vector.description
+        )));
+    }
+
+    Ok(payload)
+}
+
+/// Generates the test vectors published in [`TEST_VECTORS_JSON`].
+pub fn default_vectors() -> Result<Vec<TestVector>, ClientError> {
+    let key: Vec<u8> = (0u8..32).collect();
+    let binary: Vec<u8> = (0u8..=255).collect();
+
+    Ok(vec![
+        generate_vector(
+            "text message",
+            &Payload::from_bytes(b"Hello, Hakanai!"),
+            &key,
+            &[0xa0; 12],
+            false,
+        )?,
+        generate_vector(
+            "binary file",
+            &Payload::from_bytes(&binary).with_filename("data.bin"),
+            &key,
+            &[0xa1; 12],
+            false,
+        )?,
+        generate_vector(
+            "metadata and checksum",
+            &Payload::from_bytes("Grüße aus 東京 🔐".as_bytes())
+                .with_content_type("text/plain; charset=utf-8")
+                .with_description("Database credentials")
+                .with_checksum(),
+            &key,
+            &[0xa2; 12],
+            false,
+        )?,
+        generate_vector(
+            "padded text message",
+            &Payload::from_bytes(b"short secret"),
+            &key,
+            &[0xa3; 12],
+            true,
+        )?,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Vec<TestVector> {
+        serde_json::from_str(TEST_VECTORS_JSON).expect("Failed to parse test vectors")
+    }
+
+    #[test]
+    fn test_fixture_matches_generated_vectors() -> Result<(), ClientError> {
+        let generated = default_vectors()?;
+
+        if std::env::var_os("HAKANAI_UPDATE_TEST_VECTORS").is_some() {
+            let json =
+                serde_json::to_string_pretty(&generated).expect("Failed to serialize") + "\n";
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/test-vectors.json");
+            std::fs::write(path, json).expect("Failed to write test vectors");
+            return Ok(());
+        }
+
+        assert_eq!(
+            fixture(),
+            generated,
+            "Fixture is outdated, regenerate with HAKANAI_UPDATE_TEST_VECTORS=1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixture_vectors_decrypt() -> Result<(), ClientError> {
+        for vector in fixture() {
+            let payload = verify_vector(&vector)?;
+            assert!(
+                payload.verify_checksum(),
+                "Checksum of '{}' should be valid",
+                vector.description
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_padded_vector() -> Result<(), ClientError> {
+        let vector = generate_vector(
+            "padded",
+            &Payload::from_bytes(b"secret"),
+            &[1u8; 32],
+            &[2u8; 12],
+            true,
+        )?;
+
+        let plaintext = base64::prelude::BASE64_STANDARD.decode(&vector.plaintext)?;
+        assert_eq!(
+            plaintext.len(),
+            1024,
+            "Plaintext should be padded to the smallest size class"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_vector_detects_tampering() -> Result<(), ClientError> {
+        let mut vector = generate_vector(
+            "tampered",
+            &Payload::from_bytes(b"secret"),
+            &[1u8; 32],
+            &[2u8; 12],
+            false,
+        )?;
+        vector.payload.data = base64::prelude::BASE64_STANDARD.encode(b"other");
+
+        assert!(
+            verify_vector(&vector).is_err(),
+            "Mismatching payload should be detected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_vector_rejects_invalid_nonce() {
+        let result = generate_vector(
+            "invalid",
+            &Payload::from_bytes(b"x"),
+            &[1u8; 32],
+            &[2u8; 8],
+            false,
+        );
+        assert!(
+            result.is_err(),
+            "Nonce of invalid length should be rejected"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
-use aes_gcm::aead::Aead;
-use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use aes_gcm::aead::{Aead, Nonce, Payload};
+use aes_gcm::{AeadInOut, Aes256Gcm, Key, KeyInit, Tag};
 use base64::Engine;
 use rand::Rng;
 use zeroize::{Zeroize, Zeroizing};
@@ -10,6 +10,7 @@ use crate::crypto::crypto_context::{CryptoContext, CryptoContextFactory};
 
 const AES_GCM_KEY_SIZE: usize = 32; // AES-256 requires a 32-byte key
 const AES_GCM_NONCE_SIZE: usize = 12; // AES-GCM uses a 12-byte nonce
+const AES_GCM_TAG_SIZE: usize = 16;
 
 /// AESCryptoContext is the AES256-GCM implementation for CryptoContext (which was used prior post quantum crypto)
 pub struct AESCryptoContext {
@@ -46,6 +47,60 @@ impl AESCryptoContext {
         })
     }
 
+    /// Creates a context for encryption with the given key and a freshly generated nonce, e.g.
+    /// for replies encrypted with a key chosen by the sender of the original secret.
+    pub fn with_key_base64(fragment: &str) -> Result<Self, ClientError> {
+        let mut ctx = Self::from_key_base64(fragment)?;
+        rand::rng().fill_bytes(ctx.nonce.as_mut_slice());
+        Ok(ctx)
+    }
+
+    /// Creates a context with the given key and nonce, e.g. to generate deterministic test vectors.
+    ///
+    /// The nonce must never be reused for encryption with the same key.
+    pub fn from_key_and_nonce(key: &[u8], nonce: &[u8]) -> Result<Self, ClientError> {
+        if key.len() != AES_GCM_KEY_SIZE {
+            return Err(ClientError::CryptoError("Invalid key length".to_string()));
+        }
+        if nonce.len() != AES_GCM_NONCE_SIZE {
+            return Err(ClientError::CryptoError("Invalid nonce length".to_string()));
+        }
+
+        Ok(Self {
+            key: key.to_vec(),
+            nonce: nonce.to_vec(),
+            used: false,
+        })
+    }
+
+    /// Marks the context as used, failing if it was used for encryption before.
+    fn mark_used(&mut self) -> Result<(), ClientError> {
+        if self.used {
+            return Err(ClientError::CryptoError(
+                  "CryptoContext has already been used for encryption. Create a new context to prevent nonce reuse."
+                      .to_string(),
+              ));
+        }
+
+        // Mark context as used to prevent nonce reuse
+        self.used = true;
+        Ok(())
+    }
+
+    fn cipher(&self) -> Result<Aes256Gcm, ClientError> {
+        let key: &Key<Aes256Gcm> = self
+            .key
+            .as_slice()
+            .try_into()
+            .map_err(|_| ClientError::CryptoError("Invalid key length".to_string()))?;
+        Ok(Aes256Gcm::new(key))
+    }
+
+    fn nonce(&self) -> Result<Nonce<Aes256Gcm>, ClientError> {
+        Nonce::<Aes256Gcm>::try_from(self.nonce.as_slice())
+            .map_err(|_| ClientError::CryptoError("Invalid nonce length".to_string()))
+    }
+
     #[cfg(test)]
     pub fn key(&self) -> &[u8] {
         &self.key
@@ -63,6 +118,10 @@ impl CryptoContextFactory for AESCryptoContextFactory {
     fn generate_from_key_base64(&self, key: &str) -> Result<Box<dyn CryptoContext>, ClientError> {
         Ok(Box::new(AESCryptoContext::from_key_base64(key)?))
     }
+
+    fn generate_with_key_base64(&self, key: &str) -> Result<Box<dyn CryptoContext>, ClientError> {
+        Ok(Box::new(AESCryptoContext::with_key_base64(key)?))
+    }
 }
 
 impl CryptoContext for AESCryptoContext {
@@ -85,42 +144,60 @@ impl CryptoContext for AESCryptoContext {
         result
     }
 
-    fn encrypt(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, ClientError> {
-        if self.used {
-            return Err(ClientError::CryptoError(
-                  "CryptoContext has already been used for encryption. Create a new context to prevent nonce reuse."
-                      .to_string(),
-              ));
-        }
+    fn encrypt(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError> {
+        self.mark_used()?;
 
-        // Mark context as used to prevent nonce reuse
-        self.used = true;
+        Ok(self.cipher()?.encrypt(
+            &self.nonce()?,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )?)
+    }
 
-        let key: &Key<Aes256Gcm> = self
-            .key
-            .as_slice()
-            .try_into()
-            .map_err(|_| ClientError::CryptoError("Invalid key length".to_string()))?;
-        let cipher = Aes256Gcm::new(key);
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError> {
+        Ok(self.cipher()?.decrypt(
+            &self.nonce()?,
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )?)
+    }
 
-        let mut nonce = Nonce::default();
-        nonce.copy_from_slice(&self.nonce);
+    fn seal_in_place(&mut self, buffer: &mut Vec<u8>, aad: &[u8]) -> Result<(), ClientError> {
+        self.mark_used()?;
 
-        Ok(cipher.encrypt(&nonce, plaintext)?)
-    }
+        let tag = self.cipher()?.encrypt_inout_detached(
+            &self.nonce()?,
+            aad,
+            buffer.as_mut_slice().into(),
+        )?;
+        buffer.extend_from_slice(&tag);
+        buffer.splice(0..0, self.nonce.iter().copied());
 
-    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, ClientError> {
-        let key: &Key<Aes256Gcm> = self
-            .key
-            .as_slice()
-            .try_into()
-            .map_err(|_| ClientError::CryptoError("Invalid key length".to_string()))?;
-        let cipher = Aes256Gcm::new(key);
+        Ok(())
+    }
 
-        let mut nonce = Nonce::default();
-        nonce.copy_from_slice(&self.nonce);
+    fn open_in_place(&mut self, buffer: &mut Vec<u8>, aad: &[u8]) -> Result<(), ClientError> {
+        self.import_nonce(buffer)?;
+        if buffer.len() < AES_GCM_NONCE_SIZE + AES_GCM_TAG_SIZE {
+            return Err(aes_gcm::Error.into());
+        }
 
-        Ok(cipher.decrypt(&nonce, ciphertext)?)
+        let tag_offset = buffer.len() - AES_GCM_TAG_SIZE;
+        let tag = Tag::try_from(&buffer[tag_offset..]).map_err(|_| aes_gcm::Error)?;
+        self.cipher()?.decrypt_inout_detached(
+            &self.nonce()?,
+            aad,
+            (&mut buffer[AES_GCM_NONCE_SIZE..tag_offset]).into(),
+            &tag,
+        )?;
+
+        buffer.truncate(tag_offset);
+        buffer.drain(..AES_GCM_NONCE_SIZE);
+        Ok(())
     }
 
     fn nonce_size(&self) -> usize {
@@ -161,7 +238,7 @@ mod tests {
         // 12-byte nonce + 4 bytes of garbage — valid structure but wrong ciphertext
         let ctx = AESCryptoContext::generate();
         let bad_data = vec![0u8; 4];
-        let result = ctx.decrypt(&bad_data);
+        let result = ctx.decrypt(&bad_data, &[]);
         assert!(
             matches!(result, Err(ClientError::CryptoError(ref msg)) if msg.contains("AES-GCM error")),
             "Expected AES-GCM error, got: {:?}",
@@ -179,12 +256,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_key_base64_uses_fresh_nonce() {
+        let key = AESCryptoContext::generate().key_as_base64();
+        let mut ctx = AESCryptoContext::with_key_base64(&key).expect("key import should succeed");
+        let mut ctx2 = AESCryptoContext::with_key_base64(&key).expect("key import should succeed");
+
+        assert_eq!(ctx.key(), ctx2.key());
+        assert_ne!(ctx.nonce, ctx2.nonce, "Nonces must not be reused");
+        assert_ne!(
+            ctx.nonce,
+            vec![0u8; AES_GCM_NONCE_SIZE],
+            "Nonce must not be the zero nonce used for decryption"
+        );
+
+        let first = ctx
+            .encrypt(b"reply", &[])
+            .expect("encryption should succeed");
+        let second = ctx2
+            .encrypt(b"reply", &[])
+            .expect("encryption should succeed");
+        assert_ne!(first, second, "Ciphertexts should not be correlated");
+    }
+
     #[test]
     fn test_encrypt_decrypt_roundtrip() {
         let mut ctx = AESCryptoContext::generate();
         let plaintext = b"secret message for roundtrip test";
 
-        let ciphertext = ctx.encrypt(plaintext).expect("encryption should succeed");
+        let ciphertext = ctx
+            .encrypt(plaintext, &[])
+            .expect("encryption should succeed");
         let wire = ctx.prepend_nonce_to_ciphertext(&ciphertext);
 
         let mut ctx2 = AESCryptoContext::from_key_base64(&ctx.key_as_base64())
@@ -192,7 +294,7 @@ mod tests {
         ctx2.import_nonce(&wire)
             .expect("nonce import should succeed");
         let recovered = ctx2
-            .decrypt(&wire[ctx2.nonce_size()..])
+            .decrypt(&wire[ctx2.nonce_size()..], &[])
             .expect("decryption should succeed");
 
         assert_eq!(
@@ -201,6 +303,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decrypt_with_different_aad_fails() {
+        let mut ctx = AESCryptoContext::generate();
+        let ciphertext = ctx
+            .encrypt(b"bound secret", b"id-1")
+            .expect("encryption should succeed");
+
+        assert_eq!(
+            ctx.decrypt(&ciphertext, b"id-1")
+                .expect("decryption with the same associated data should succeed"),
+            b"bound secret"
+        );
+        assert!(
+            ctx.decrypt(&ciphertext, b"id-2").is_err(),
+            "Decryption with different associated data must fail"
+        );
+        assert!(
+            ctx.decrypt(&ciphertext, &[]).is_err(),
+            "Decryption without associated data must fail"
+        );
+    }
+
+    #[test]
+    fn test_seal_in_place_matches_encrypt() {
+        let key = [7u8; AES_GCM_KEY_SIZE];
+        let nonce = [9u8; AES_GCM_NONCE_SIZE];
+        let mut ctx = AESCryptoContext::from_key_and_nonce(&key, &nonce)
+            .expect("context creation should succeed");
+        let ciphertext = ctx
+            .encrypt(b"in place", b"id")
+            .expect("encryption should succeed");
+
+        let mut ctx = AESCryptoContext::from_key_and_nonce(&key, &nonce)
+            .expect("context creation should succeed");
+        let mut buffer = b"in place".to_vec();
+        ctx.seal_in_place(&mut buffer, b"id")
+            .expect("encryption should succeed");
+
+        assert_eq!(buffer, ctx.prepend_nonce_to_ciphertext(&ciphertext));
+        assert!(
+            ctx.seal_in_place(&mut buffer, b"id").is_err(),
+            "Context must not be reused for encryption"
+        );
+    }
+
+    #[test]
+    fn test_seal_open_in_place_roundtrip() {
+        let mut ctx = AESCryptoContext::generate();
+        let mut buffer = b"secret message for in place roundtrip".to_vec();
+        ctx.seal_in_place(&mut buffer, b"id")
+            .expect("encryption should succeed");
+
+        let mut ctx2 = AESCryptoContext::from_key_base64(&ctx.key_as_base64())
+            .expect("key import should succeed");
+        ctx2.open_in_place(&mut buffer, b"id")
+            .expect("decryption should succeed");
+
+        assert_eq!(buffer, b"secret message for in place roundtrip");
+    }
+
+    #[test]
+    fn test_open_in_place_rejects_invalid_data() {
+        let mut ctx = AESCryptoContext::generate();
+        let mut buffer = b"tampered".to_vec();
+        ctx.seal_in_place(&mut buffer, &[])
+            .expect("encryption should succeed");
+        buffer[AES_GCM_NONCE_SIZE] ^= 1;
+
+        let mut ctx2 = AESCryptoContext::from_key_base64(&ctx.key_as_base64())
+            .expect("key import should succeed");
+        assert!(
+            ctx2.open_in_place(&mut buffer.clone(), &[]).is_err(),
+            "Modified ciphertext must be rejected"
+        );
+        assert!(
+            ctx2.open_in_place(&mut vec![0u8; AES_GCM_NONCE_SIZE + 4], &[])
+                .is_err(),
+            "Data shorter than the tag must be rejected"
+        );
+    }
+
     #[test]
     fn test_import_nonce_payload_too_short() {
         let mut ctx = AESCryptoContext::generate();
@@ -1,4 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
+use zeroize::Zeroize;
+
 use crate::client::ClientError;
 
 /// Abstraction over a symmetric authenticated encryption context.
@@ -6,11 +8,14 @@ use crate::client::ClientError;
 /// Implementors manage a key and nonce and expose encrypt/decrypt operations
 /// along with helpers for nonce serialisation used in the wire format.
 pub trait CryptoContext: Send + Sync {
-    /// Encrypts `plaintext` and returns the raw ciphertext (without nonce).
-    fn encrypt(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, ClientError>;
+    /// Encrypts `plaintext` authenticating the associated data `aad` and returns the raw
+    /// ciphertext (without nonce).
+    fn encrypt(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError>;
 
     /// Decrypts `payload` (raw ciphertext without nonce) and returns the plaintext.
-    fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, ClientError>;
+    ///
+    /// Fails if `aad` does not match the associated data used for encryption.
+    fn decrypt(&self, payload: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError>;
 
     /// Returns the encryption key encoded as URL-safe Base64 (no padding).
     fn key_as_base64(&self) -> String;
@@ -24,6 +29,27 @@ pub trait CryptoContext: Send + Sync {
 
     /// Returns the nonce length in bytes expected by this context.
     fn nonce_size(&self) -> usize;
+
+    /// Encrypts the plaintext in `buffer`, replacing it with the wire-format blob (nonce and
+    /// ciphertext).
+    ///
+    /// The default implementation copies the data, implementors should encrypt in place so
+    /// large secrets are not held in memory twice.
+    fn seal_in_place(&mut self, buffer: &mut Vec<u8>, aad: &[u8]) -> Result<(), ClientError> {
+        let ciphertext = self.encrypt(buffer, aad)?;
+        buffer.zeroize();
+        *buffer = self.prepend_nonce_to_ciphertext(&ciphertext);
+        Ok(())
+    }
+
+    /// Decrypts the wire-format blob in `buffer`, replacing it with the plaintext.
+    ///
+    /// The default implementation copies the data, implementors should decrypt in place.
+    fn open_in_place(&mut self, buffer: &mut Vec<u8>, aad: &[u8]) -> Result<(), ClientError> {
+        self.import_nonce(buffer)?;
+        *buffer = self.decrypt(&buffer[self.nonce_size()..], aad)?;
+        Ok(())
+    }
 }
 
 /// Creates [`CryptoContext`] instances for a specific algorithm.
@@ -35,4 +61,8 @@ pub trait CryptoContextFactory: Send + Sync {
 
     /// Restores a context from a URL-safe Base64-encoded key.
     fn generate_from_key_base64(&self, key: &str) -> Result<Box<dyn CryptoContext>, ClientError>;
+
+    /// Returns a new context for encryption with a URL-safe Base64-encoded key chosen
+    /// beforehand and a freshly generated nonce.
+    fn generate_with_key_base64(&self, key: &str) -> Result<Box<dyn CryptoContext>, ClientError>;
 }
@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Standard base64 encoding and decoding within the buffer holding the data.
+//!
+//! Envelopes of large secrets would otherwise exist twice in memory, once encoded and once
+//! decoded. The data is processed in chunks, which are copied to a small buffer on the stack
+//! before being written back, so unprocessed input is never overwritten.
+//!
+//! With the feature `simd` the chunks are encoded and decoded with SIMD instructions (detected
+//! at runtime). The WASM module does not encrypt secrets, so it is not built with this feature.
+
+#[cfg(feature = "http-client")]
+use std::io::{self, Read, Write};
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+#[cfg(feature = "simd")]
+use base64_simd::AsOut;
+
+/// Size of the chunks being encoded, a multiple of 3 so only the last chunk is padded.
+const ENCODE_CHUNK_SIZE: usize = 3 * 1024;
+
+/// Size of the chunks being decoded, a multiple of 4 so only the last chunk may be padded.
+const DECODE_CHUNK_SIZE: usize = 4 * 1024;
+
+/// Returns the length of the base64 encoding of `len` bytes (with padding).
+pub(crate) fn encoded_len(len: usize) -> usize {
+    base64::encoded_len(len, true).unwrap_or(usize::MAX)
+}
+
+/// Replaces the data in the buffer with its standard base64 encoding.
+///
+/// The buffer only grows without reallocation if its capacity is at least
+/// [`encoded_len`] of its length.
+pub(crate) fn encode_in_place(buffer: &mut Vec<u8>) {
+    let len = buffer.len();
+    buffer.resize(encoded_len(len), 0);
+
+    // the encoding is longer than the data, chunks are encoded from the end so the output of a
+    // chunk never overlaps the data of the chunks before it
+    let mut chunk = [0u8; ENCODE_CHUNK_SIZE];
+    for start in (0..len).step_by(ENCODE_CHUNK_SIZE).rev() {
+        let end = (start + ENCODE_CHUNK_SIZE).min(len);
+        let chunk = &mut chunk[..end - start];
+        chunk.copy_from_slice(&buffer[start..end]);
+
+        encode_chunk(chunk, &mut buffer[start / 3 * 4..]);
+    }
+}
+
+/// Replaces the standard base64 encoded data in the buffer with the decoded data.
+pub(crate) fn decode_in_place(buffer: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+    let len = buffer.len();
+    let mut written = 0;
+
+    // the decoded data is shorter than the encoding, so the output of a chunk never overlaps
+    // the encoded data of the chunks after it
+    let mut chunk = [0u8; DECODE_CHUNK_SIZE];
+    for start in (0..len).step_by(DECODE_CHUNK_SIZE) {
+        let end = (start + DECODE_CHUNK_SIZE).min(len);
+        let chunk = &mut chunk[..end - start];
+        chunk.copy_from_slice(&buffer[start..end]);
+
+        let decoded = decode_chunk(chunk, &mut buffer[written..])?;
+        written += decoded;
+
+        // padding is only allowed at the end of the encoding
+        if end < len && decoded != chunk.len() / 4 * 3 {
+            return Err(base64::DecodeError::InvalidPadding);
+        }
+    }
+
+    buffer.truncate(written);
+    Ok(())
+}
+
+/// Reader encoding the data of the inner reader to standard base64, chunk by chunk.
+#[cfg(feature = "http-client")]
+pub(crate) struct EncodingReader<R> {
+    inner: R,
+    encoded: Vec<u8>,
+    pos: usize,
+}
+
+#[cfg(feature = "http-client")]
+impl<R: Read> EncodingReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        EncodingReader {
+            inner,
+            encoded: Vec::with_capacity(encoded_len(ENCODE_CHUNK_SIZE)),
+            pos: 0,
+        }
+    }
+}
+
+#[cfg(feature = "http-client")]
+impl<R: Read> Read for EncodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.encoded.len() {
+            // chunks are filled completely, so only the last chunk is padded
+            let mut chunk = [0u8; ENCODE_CHUNK_SIZE];
+            let mut len = 0;
+            while len < chunk.len() {
+                match self.inner.read(&mut chunk[len..]) {
+                    Ok(0) => break,
+                    Ok(n) => len += n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+
+            self.encoded.resize(encoded_len(len), 0);
+            encode_chunk(&chunk[..len], &mut self.encoded);
+            self.pos = 0;
+        }
+
+        let n = (&self.encoded[self.pos..]).read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Writer decoding the standard base64 written to it into the inner writer, chunk by chunk.
+///
+/// The last chunk is only decoded by [`DecodingWriter::finish`].
+#[cfg(feature = "http-client")]
+pub(crate) struct DecodingWriter<W> {
+    inner: W,
+    pending: Vec<u8>,
+    padded: bool,
+}
+
+#[cfg(feature = "http-client")]
+impl<W: Write> DecodingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        DecodingWriter {
+            inner,
+            pending: Vec::with_capacity(DECODE_CHUNK_SIZE),
+            padded: false,
+        }
+    }
+
+    /// Decodes the last chunk and returns the inner writer.
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        self.decode_pending()?;
+        Ok(self.inner)
+    }
+
+    fn decode_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        // padding is only allowed at the end of the encoding
+        if self.padded {
+            return Err(invalid_data(base64::DecodeError::InvalidPadding));
+        }
+
+        let mut output = [0u8; DECODE_CHUNK_SIZE / 4 * 3];
+        let decoded = decode_chunk(&self.pending, &mut output).map_err(invalid_data)?;
+        self.padded = decoded != self.pending.len() / 4 * 3;
+        self.pending.clear();
+
+        self.inner.write_all(&output[..decoded])
+    }
+}
+
+#[cfg(feature = "http-client")]
+impl<W: Write> Write for DecodingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(DECODE_CHUNK_SIZE - self.pending.len());
+        self.pending.extend_from_slice(&buf[..n]);
+
+        if self.pending.len() == DECODE_CHUNK_SIZE {
+            self.decode_pending()?;
+        }
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "http-client")]
+fn invalid_data(err: base64::DecodeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Encodes the chunk into the output, which must be large enough for the encoding.
+fn encode_chunk(chunk: &[u8], output: &mut [u8]) {
+    #[cfg(feature = "simd")]
+    let _ = base64_simd::STANDARD.encode(chunk, output.as_out());
+
+    #[cfg(not(feature = "simd"))]
+    BASE64_STANDARD
+        .encode_slice(chunk, output)
+        .expect("output is large enough for the encoding");
+}
+
+/// Decodes the chunk into the output, returning the number of decoded bytes.
+fn decode_chunk(chunk: &[u8], output: &mut [u8]) -> Result<usize, base64::DecodeError> {
+    #[cfg(feature = "simd")]
+    if let Ok(decoded) = base64_simd::STANDARD.decode(chunk, output.as_out()) {
+        return Ok(decoded.len());
+    }
+
+    // invalid chunks are decoded again to report the error of the base64 crate
+    BASE64_STANDARD
+        .decode_slice(chunk, output)
+        .map_err(|err| match err {
+            base64::DecodeSliceError::DecodeError(err) => err,
+            base64::DecodeSliceError::OutputSliceTooSmall => {
+                base64::DecodeError::InvalidLength(chunk.len())
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_encode_in_place_matches_encode() {
+        for len in [
+            0,
+            1,
+            2,
+            3,
+            1000,
+            ENCODE_CHUNK_SIZE,
+            3 * ENCODE_CHUNK_SIZE + 2,
+        ] {
+            let data = test_data(len);
+
+            let mut buffer = data.clone();
+            encode_in_place(&mut buffer);
+
+            assert_eq!(
+                buffer,
+                BASE64_STANDARD.encode(&data).into_bytes(),
+                "Encoding of {len} bytes should match"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_in_place_without_reallocation() {
+        let mut buffer = Vec::with_capacity(encoded_len(10_000));
+        buffer.extend_from_slice(&test_data(10_000));
+        let ptr = buffer.as_ptr();
+
+        encode_in_place(&mut buffer);
+
+        assert_eq!(buffer.as_ptr(), ptr, "Buffer should not be reallocated");
+    }
+
+    #[test]
+    fn test_decode_in_place_matches_decode() {
+        for len in [
+            0,
+            1,
+            2,
+            3,
+            1000,
+            DECODE_CHUNK_SIZE,
+            3 * DECODE_CHUNK_SIZE + 2,
+        ] {
+            let data = test_data(len);
+
+            let mut buffer = BASE64_STANDARD.encode(&data).into_bytes();
+            decode_in_place(&mut buffer).expect("Decoding should succeed");
+
+            assert_eq!(buffer, data, "Decoding of {len} bytes should match");
+        }
+    }
+
+    #[test]
+    fn test_decode_in_place_invalid() {
+        let mut buffer = b"not base64!".to_vec();
+
+        assert!(decode_in_place(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_decode_in_place_rejects_padding_within_data() {
+        // a padded chunk followed by further data, accepted if chunks were decoded independently
+        let mut buffer = BASE64_STANDARD
+            .encode(test_data(DECODE_CHUNK_SIZE / 4 * 3 - 1))
+            .into_bytes();
+        buffer.extend_from_slice(b"AAAA");
+
+        assert_eq!(
+            decode_in_place(&mut buffer),
+            Err(base64::DecodeError::InvalidPadding)
+        );
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_encoding_reader_matches_encode() -> io::Result<()> {
+        for len in [0, 1, 1000, ENCODE_CHUNK_SIZE, 3 * ENCODE_CHUNK_SIZE + 2] {
+            let data = test_data(len);
+
+            let mut encoded = Vec::new();
+            EncodingReader::new(data.as_slice()).read_to_end(&mut encoded)?;
+
+            assert_eq!(
+                encoded,
+                BASE64_STANDARD.encode(&data).into_bytes(),
+                "Encoding of {len} bytes should match"
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_decoding_writer_matches_decode() -> io::Result<()> {
+        for len in [0, 1, 1000, DECODE_CHUNK_SIZE, 3 * DECODE_CHUNK_SIZE + 2] {
+            let data = test_data(len);
+            let encoded = BASE64_STANDARD.encode(&data).into_bytes();
+
+            // writes of odd sizes, as received from the network
+            let mut writer = DecodingWriter::new(Vec::new());
+            for chunk in encoded.chunks(1001) {
+                writer.write_all(chunk)?;
+            }
+
+            assert_eq!(
+                writer.finish()?,
+                data,
+                "Decoding of {len} bytes should match"
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_decoding_writer_rejects_padding_within_data() -> io::Result<()> {
+        let mut encoded = BASE64_STANDARD
+            .encode(test_data(DECODE_CHUNK_SIZE / 4 * 3 - 1))
+            .into_bytes();
+        encoded.extend_from_slice(b"AAAA");
+
+        let mut writer = DecodingWriter::new(Vec::new());
+        writer.write_all(&encoded)?;
+
+        assert!(writer.finish().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn test_decoding_writer_invalid() {
+        let mut writer = DecodingWriter::new(Vec::new());
+
+        let result = writer
+            .write_all(b"not base64!")
+            .and_then(|_| writer.finish());
+
+        assert!(result.is_err_and(|e| e.kind() == io::ErrorKind::InvalidData));
+    }
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline encryption of secrets into envelopes, without any server interaction.
+//!
+//! An envelope is exactly what the server stores for a secret: the payload serialized as
+//! MessagePack, encrypted with AES-256-GCM without associated data (envelope version 1) and
+//! encoded as standard base64 of `nonce || ciphertext || tag`. Envelopes can be exchanged as
+//! files, e.g. for air-gapped workflows, and uploaded to a server later on.
+//!
+//! The key of an envelope is formatted like the fragment of a secret link, `{key}:{hash}` with
+//! the AES key encoded as base64url and the truncated SHA-256 hash of the plaintext.
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::envelope;
+//! use hakanai_lib::models::Payload;
+//!
+//! # fn example() -> Result<(), hakanai_lib::client::ClientError> {
+//! let sealed = envelope::seal(&Payload::from_bytes(b"My secret message"))?;
+//!
+//! let payload = envelope::open(sealed.data, &sealed.key)?;
+//! assert_eq!(payload.data, b"My secret message");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use zeroize::Zeroizing;
+
+use crate::client::ClientError;
+use crate::crypto::aes::AESCryptoContextFactory;
+use crate::crypto::crypto_context::CryptoContextFactory;
+use crate::models::Payload;
+
+/// An encrypted envelope and the key to open it.
+pub struct SealedEnvelope {
+    /// The base64 encoded envelope.
+    pub data: Vec<u8>,
+
+    /// The key in the format `{key}:{hash}`.
+    pub key: Zeroizing<String>,
+}
+
+impl fmt::Debug for SealedEnvelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SealedEnvelope")
+            .field("data", &format!("{} bytes", self.data.len()))
+            .field("key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Encrypts the payload with a freshly generated key.
+pub fn seal(payload: &Payload) -> Result<SealedEnvelope, ClientError> {
+    let mut crypto_context = AESCryptoContextFactory.generate();
+
+    let data = super::serialize(payload, false, None)?;
+    let (data, hash) = super::encrypt(data, &mut *crypto_context, &[])?;
+
+    let key = Zeroizing::new(format!("{}:{hash}", crypto_context.key_as_base64()));
+    Ok(SealedEnvelope { data, key })
+}
+
+/// Decrypts the envelope with the key in the format `{key}:{hash}`.
+///
+/// Trailing whitespace of the envelope (e.g. a newline added by an editor) is ignored.
+pub fn open(mut data: Vec<u8>, key: &str) -> Result<Payload, ClientError> {
+    let (key, hash) = key
+        .trim()
+        .split_once(':')
+        .ok_or(ClientError::Custom("Missing hash in key".to_string()))?;
+
+    while data.last().is_some_and(u8::is_ascii_whitespace) {
+        data.pop();
+    }
+
+    let crypto_context = AESCryptoContextFactory.generate_from_key_base64(key)?;
+    super::decrypt(data, crypto_context, Some(hash.to_string()), &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() -> Result<(), ClientError> {
+        let payload = Payload::from_bytes(b"offline secret").with_filename("secret.txt");
+
+        let sealed = seal(&payload)?;
+        let opened = open(sealed.data, &sealed.key)?;
+
+        assert_eq!(opened.data, b"offline secret");
+        assert_eq!(opened.filename.as_deref(), Some("secret.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_seal_uses_fresh_key() -> Result<(), ClientError> {
+        let payload = Payload::from_bytes(b"offline secret");
+
+        let first = seal(&payload)?;
+        let second = seal(&payload)?;
+
+        assert_ne!(first.key, second.key, "Keys should be random");
+        assert_ne!(
+            first.data, second.data,
+            "Envelopes should not be correlated"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_ignores_trailing_whitespace() -> Result<(), ClientError> {
+        let sealed = seal(&Payload::from_bytes(b"offline secret"))?;
+
+        let mut data = sealed.data;
+        data.extend_from_slice(b"\r\n");
+        let opened = open(data, &format!("{}\n", *sealed.key))?;
+
+        assert_eq!(opened.data, b"offline secret");
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_with_wrong_key() -> Result<(), ClientError> {
+        let sealed = seal(&Payload::from_bytes(b"offline secret"))?;
+        let other = seal(&Payload::from_bytes(b"offline secret"))?;
+
+        let result = open(sealed.data, &other.key);
+
+        assert!(
+            matches!(result, Err(ClientError::CryptoError(_))),
+            "Expected decryption error, got {result:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_with_wrong_hash() -> Result<(), ClientError> {
+        let sealed = seal(&Payload::from_bytes(b"offline secret"))?;
+        let (key, _) = sealed.key.split_once(':').expect("key has a hash");
+
+        let result = open(sealed.data, &format!("{key}:wronghash"));
+
+        assert!(
+            matches!(result, Err(ClientError::HashValidationError())),
+            "Expected hash validation error, got {result:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_without_hash() -> Result<(), ClientError> {
+        let sealed = seal(&Payload::from_bytes(b"offline secret"))?;
+        let (key, _) = sealed.key.split_once(':').expect("key has a hash");
+
+        let result = open(sealed.data, key);
+
+        assert!(
+            matches!(result, Err(ClientError::Custom(_))),
+            "Expected missing hash error, got {result:?}"
+        );
+        Ok(())
+    }
+}
@@ -10,9 +10,10 @@ const FIXED_KEY: &[u8] = b"mock_key_for_test";
 
 /// A deterministic [`CryptoContext`] for testing.
 ///
-/// Encryption produces `base64(key || plaintext)`. Decryption verifies the key
-/// prefix and returns the remainder, so using the wrong key yields a
-/// [`ClientError::CryptoError`] without needing a separate failure flag.
+/// Encryption produces `base64(key || aad || plaintext)`. Decryption verifies the
+/// key and associated data prefix and returns the remainder, so using the wrong key
+/// or associated data yields a [`ClientError::CryptoError`] without needing a
+/// separate failure flag.
 /// A fixed-size nonce is prepended to the wire payload so the full nonce
 /// lifecycle is exercised without coupling tests to a specific algorithm.
 pub(super) struct MockCryptoContext {
@@ -21,18 +22,26 @@ pub(super) struct MockCryptoContext {
 }
 
 impl CryptoContext for MockCryptoContext {
-    fn encrypt(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, ClientError> {
+    fn encrypt(&mut self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError> {
         let mut buf = self.key.clone();
+        buf.extend_from_slice(aad);
         buf.extend_from_slice(plaintext);
         Ok(base64::prelude::BASE64_STANDARD.encode(&buf).into_bytes())
     }
 
-    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, ClientError> {
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, ClientError> {
         let buf = base64::prelude::BASE64_STANDARD.decode(ciphertext)?;
         if buf.get(..self.key.len()) != Some(self.key.as_slice()) {
             return Err(ClientError::CryptoError("mock: wrong key".to_string()));
         }
-        Ok(buf[self.key.len()..].to_vec())
+
+        let prefix_len = self.key.len() + aad.len();
+        if buf.get(self.key.len()..prefix_len) != Some(aad) {
+            return Err(ClientError::CryptoError(
+                "mock: wrong associated data".to_string(),
+            ));
+        }
+        Ok(buf[prefix_len..].to_vec())
     }
 
     fn key_as_base64(&self) -> String {
@@ -76,4 +85,8 @@ impl CryptoContextFactory for MockCryptoContextFactory {
             nonce: [0u8; MOCK_NONCE_SIZE],
         }))
     }
+
+    fn generate_with_key_base64(&self, key: &str) -> Result<Box<dyn CryptoContext>, ClientError> {
+        self.generate_from_key_base64(key)
+    }
 }
@@ -1,23 +1,46 @@
 // SPDX-License-Identifier: Apache-2.0
-mod aes;
-mod crypto_context;
+pub(crate) mod aes;
+pub(crate) mod crypto_context;
+pub(crate) mod encoding;
+pub mod envelope;
 #[cfg(test)]
 mod mock;
+mod padding;
+pub mod shamir;
+#[cfg(feature = "http-client")]
+pub mod spill;
 
+#[cfg(feature = "http-client")]
+use std::io::{Read, Write};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use base64::Engine;
-use reqwest::Url;
+use rand::Rng;
+use ulid::Ulid;
+use url::Url;
 use zeroize::{Zeroize, Zeroizing};
 
-use crate::client::{Client, ClientError};
+use crate::client::{Client, ClientError, SentSecret};
 use crate::crypto::aes::AESCryptoContextFactory;
 use crate::crypto::crypto_context::{CryptoContext, CryptoContextFactory};
-use crate::models::Payload;
+use crate::link::{self, LinkFragment};
+use crate::models::{Payload, ReplyReservation, ReplySlot, ServerConfig};
+use crate::observer::{ProgressTracker, TransferPhase};
 use crate::options::{SecretReceiveOptions, SecretSendOptions};
+#[cfg(feature = "http-client")]
+use crate::spill::SpillBuffer;
 use crate::utils::hashing;
 
+/// Prefix of the associated data of envelope version 2, followed by the secret ID.
+const ENVELOPE_V2_AAD_PREFIX: &str = "hakanai:v2:";
+
+/// Bytes added to the serialized payload by the encryption (nonce and tag of AES-256-GCM).
+const ENVELOPE_OVERHEAD: usize = 12 + 16;
+
+/// Size of the upload tokens of reply slots in bytes.
+const REPLY_UPLOAD_TOKEN_SIZE: usize = 32;
+
 /// A [`Client<Payload>`] that wraps a transport client to add transparent
 /// encryption and decryption of secrets.
 ///
@@ -26,6 +49,17 @@ use crate::utils::hashing;
 /// embedded in the URL fragment as `#key:hash` so that recipients can decrypt
 /// and verify the secret without the server ever seeing the plaintext.
 ///
+/// If the server accepts client proposed IDs, the ID of the secret is generated
+/// before encryption and bound to the ciphertext as associated data (envelope
+/// version 2). The ID is appended to the fragment (`#key:hash:id`), so a server
+/// cannot serve the ciphertext under a different ID unnoticed. Links without ID
+/// are decrypted without associated data (envelope version 1).
+///
+/// If the sender allows a reply, a reply slot (reserved ID, key and upload token)
+/// is embedded in the payload and the link to the reply is returned right away.
+/// As the reply does not exist yet, its link carries no hash (`#key::id`), the
+/// ciphertext is authenticated by the ID bound as associated data instead.
+///
 /// On receive, the key and hash are extracted from the URL fragment, the
 /// ciphertext is decrypted, the hash is verified, and the payload is
 /// deserialised before being returned to the caller.
@@ -43,7 +77,7 @@ use crate::utils::hashing;
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let client = client::new();
 ///
-/// let secret_url = client.send_secret(
+/// let sent = client.send_secret(
 ///     Url::parse("https://api.example.com")?,
 ///     Payload::from_bytes(b"My secret message"),
 ///     Duration::from_secs(3600),
@@ -51,7 +85,7 @@ use crate::utils::hashing;
 ///     None,
 /// ).await?;
 ///
-/// let received = client.receive_secret(secret_url, None).await?;
+/// let received = client.receive_secret(sent.url, None).await?;
 /// assert_eq!(received.data, b"My secret message");
 /// # Ok(())
 /// # }
@@ -81,6 +115,71 @@ impl CryptoClient {
             factory,
         }
     }
+
+    /// Generates a reply slot with a fresh ID, key and upload token.
+    fn generate_reply_slot(&self) -> ReplySlot {
+        let key = self.factory.generate().key_as_base64();
+
+        let mut upload_token = Zeroizing::new([0u8; REPLY_UPLOAD_TOKEN_SIZE]);
+        rand::rng().fill_bytes(upload_token.as_mut_slice());
+        let upload_token = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(upload_token.as_slice());
+
+        ReplySlot::new(Ulid::r#gen(), key, upload_token)
+    }
+
+    /// Sends the ciphertext base64 encoded, or streamed from a spill file if its encoding exceeds
+    /// the memory limit and the transport supports streaming.
+    async fn send_ciphertext(
+        &self,
+        base_url: Url,
+        mut ciphertext: Vec<u8>,
+        ttl: Duration,
+        token: String,
+        opts: Option<SecretSendOptions>,
+    ) -> Result<SentSecret, ClientError> {
+        #[cfg(feature = "http-client")]
+        if let Some(max_memory) = opts.as_ref().and_then(|o| o.max_memory)
+            && encoding::encoded_len(ciphertext.len()) > max_memory
+            && let Some(transport) = self.inner_client.streaming()
+        {
+            let mut buffer = SpillBuffer::new(max_memory);
+            buffer.write_all(&ciphertext)?;
+            drop(ciphertext);
+
+            return transport
+                .send_stream(base_url, buffer, ttl, token, opts)
+                .await;
+        }
+
+        encoding::encode_in_place(&mut ciphertext);
+        self.inner_client
+            .send_secret(base_url, ciphertext, ttl, token, opts)
+            .await
+    }
+
+    /// Receives the ciphertext, decoded from a spill file if the transport supports streaming
+    /// and a memory limit is set.
+    async fn receive_ciphertext(
+        &self,
+        url: Url,
+        opts: Option<SecretReceiveOptions>,
+    ) -> Result<Zeroizing<Vec<u8>>, ClientError> {
+        #[cfg(feature = "http-client")]
+        if opts.as_ref().is_some_and(|o| o.max_memory.is_some())
+            && let Some(transport) = self.inner_client.streaming()
+        {
+            let buffer = transport.receive_stream(url, opts).await?;
+
+            let capacity = usize::try_from(buffer.len()).unwrap_or_default();
+            let mut data = Zeroizing::new(Vec::with_capacity(capacity));
+            buffer.into_reader()?.read_to_end(&mut data)?;
+            return Ok(data);
+        }
+
+        let mut data = Zeroizing::new(self.inner_client.receive_secret(url, opts).await?);
+        encoding::decode_in_place(&mut data)?;
+        Ok(data)
+    }
 }
 
 #[async_trait]
@@ -92,29 +191,88 @@ impl Client<Payload> for CryptoClient {
         ttl: Duration,
         token: String,
         opts: Option<SecretSendOptions>,
-    ) -> Result<Url, ClientError> {
-        let mut crypto_context = self.factory.generate();
-
-        let data = Zeroizing::new(payload.serialize()?);
-        let hash = hashing::sha256_truncated_base64_from_bytes(&data);
-
-        let ciphertext = crypto_context.encrypt(&data)?;
-
-        let payload = crypto_context.prepend_nonce_to_ciphertext(&ciphertext);
-
-        let encoded_data = base64::prelude::BASE64_STANDARD
-            .encode(&payload)
-            .as_bytes()
-            .to_vec();
+    ) -> Result<SentSecret, ClientError> {
+        let mut opts = opts;
+        let mut payload = payload;
+
+        // replies are uploaded under the ID reserved by the sender of the original secret
+        let reply_to = opts.as_ref().and_then(|o| o.reply_to.clone());
+        if let Some(ref reply_to) = reply_to {
+            opts = Some(
+                opts.unwrap_or_default()
+                    .with_secret_id(reply_to.id)
+                    .with_upload_token(reply_to.upload_token.clone()),
+            );
+        }
 
-        let res = self
+        // validation is skipped if the server configuration cannot be retrieved (e.g. older
+        // servers), the server remains the authority in any case
+        let config = self
             .inner_client
-            .send_secret(base_url, encoded_data, ttl, token, opts)
+            .get_server_config(base_url.clone())
+            .await
+            .ok();
+
+        let mut size_limit = None;
+        if let Some(ref config) = config {
+            size_limit =
+                validate_against_server_config(config, &payload, ttl, &token, opts.as_ref())?;
+
+            if config.features.raw_upload {
+                opts = Some(opts.unwrap_or_default().with_raw_upload(true));
+            }
+
+            if config.features.client_ids && opts.as_ref().is_none_or(|o| o.secret_id.is_none()) {
+                opts = Some(opts.unwrap_or_default().with_secret_id(Ulid::r#gen()));
+            }
+
+            if config.features.secret_metadata && payload.filename.is_some() {
+                opts = Some(opts.unwrap_or_default().with_file(true));
+            }
+        }
+
+        let reply = if opts.as_ref().is_some_and(|o| o.allow_reply) {
+            let reply = self.generate_reply_slot();
+            let reservation = ReplyReservation::new(
+                reply.id,
+                hashing::sha256_hex_from_string(&reply.upload_token),
+            );
+            opts = Some(opts.unwrap_or_default().with_reply_reservation(reservation));
+            payload = payload.with_reply(reply.clone());
+            Some(reply)
+        } else {
+            None
+        };
+        let secret_id = opts.as_ref().and_then(|o| o.secret_id);
+
+        ProgressTracker::new(opts.as_ref().and_then(|o| o.observer.clone()))
+            .phase(TransferPhase::Encrypting)
+            .await;
+        let mut crypto_context = match reply_to {
+            Some(ref reply_to) => self.factory.generate_with_key_base64(&reply_to.key)?,
+            None => self.factory.generate(),
+        };
+
+        let with_padding = opts.as_ref().is_some_and(|o| o.padding);
+        let data = serialize(&payload, with_padding, size_limit)?;
+        let aad = associated_data(secret_id);
+        // the plaintext is replaced by the ciphertext, it is not kept during the upload
+        let (ciphertext, hash) = seal(data, &mut *crypto_context, &aad)?;
+
+        let mut sent = self
+            .send_ciphertext(base_url.clone(), ciphertext, ttl, token, opts)
             .await?;
 
-        let url = append_to_link(res, &*crypto_context, &hash);
+        sent.url = append_to_link(sent.url, &*crypto_context, &hash, secret_id);
+
+        // servers ignoring the reservation don't report the reply ID, no reply can be sent then
+        if let Some(reply) = reply
+            && sent.reply_id == Some(reply.id)
+        {
+            sent.reply_url = Some(reply_link(&base_url, &reply)?);
+        }
 
-        Ok(url)
+        Ok(sent)
     }
 
     async fn receive_secret(
@@ -122,30 +280,114 @@ impl Client<Payload> for CryptoClient {
         url: Url,
         opts: Option<SecretReceiveOptions>,
     ) -> Result<Payload, ClientError> {
-        let parts = url
-            .fragment()
-            .ok_or(ClientError::Custom("No key in URL".to_string()))?
-            .split(':')
-            .collect::<Vec<&str>>();
+        // only the fragment is parsed, custom transports may use links of their own
+        let fragment = LinkFragment::from_url(&url)?
+            .ok_or(ClientError::Custom("No key in URL".to_string()))?;
+        let key = fragment
+            .key()
+            .ok_or(ClientError::Custom("No key in URL".to_string()))?;
+
+        let crypto_context = self.factory.generate_from_key_base64(key)?;
+        let secret_id = fragment.bound_id();
+        // links of replies are created before the reply exists and carry no hash, the bound ID
+        // authenticates the ciphertext as associated data
+        let hash = match (fragment.hash(), secret_id) {
+            (Some(hash), _) => Some(hash.to_string()),
+            (None, Some(_)) => None,
+            (None, None) => {
+                return Err(ClientError::Custom(
+                    "Missing hash in URL fragment".to_string(),
+                ));
+            }
+        };
+
+        let tracker = ProgressTracker::new(opts.as_ref().and_then(|o| o.observer.clone()));
+        let data = self.receive_ciphertext(url, opts).await?;
+
+        tracker.phase(TransferPhase::Decrypting).await;
+        open(data, crypto_context, hash, &associated_data(secret_id))
+    }
 
-        let crypto_context = self.factory.generate_from_key_base64(parts[0])?;
-        let hash = parts
-            .get(1)
-            .ok_or(ClientError::Custom(
-                "Missing hash in URL fragment".to_string(),
-            ))?
-            .to_string();
+    async fn get_server_config(&self, base_url: Url) -> Result<ServerConfig, ClientError> {
+        self.inner_client.get_server_config(base_url).await
+    }
+}
 
-        let encoded_data = self.inner_client.receive_secret(url, opts).await?;
-        decrypt(encoded_data, crypto_context, hash)
+/// Checks the secret against the limits and capabilities advertised by the server.
+///
+/// This provides more helpful errors than the generic HTTP errors returned by the server.
+///
+/// Returns the size limit applying to the secret, if any.
+fn validate_against_server_config(
+    config: &ServerConfig,
+    payload: &Payload,
+    ttl: Duration,
+    token: &str,
+    opts: Option<&SecretSendOptions>,
+) -> Result<Option<usize>, ClientError> {
+    if !config.is_ttl_allowed(ttl) {
+        if let Some(min_ttl) = config.min_ttl.filter(|min_ttl| ttl < *min_ttl) {
+            return Err(ClientError::Unsupported(format!(
+                "TTL of {}s is below the minimum TTL of {}s",
+                ttl.as_secs(),
+                min_ttl.as_secs()
+            )));
+        }
+
+        let max_ttl = config.max_ttl.unwrap_or_default();
+        return Err(ClientError::Unsupported(format!(
+            "TTL of {}s exceeds the maximum TTL of {}s",
+            ttl.as_secs(),
+            max_ttl.as_secs()
+        )));
+    }
+
+    if let Some(restrictions) = opts.and_then(|o| o.restrictions.as_ref())
+        && let Some(restriction) = config.unsupported_restriction(restrictions)
+    {
+        return Err(ClientError::Unsupported(format!(
+            "{restriction} restrictions are not enabled on this server"
+        )));
+    }
+
+    if opts.is_some_and(|o| o.allow_reply) && !config.features.replies {
+        return Err(ClientError::Unsupported(
+            "replies are not enabled on this server".to_string(),
+        ));
+    }
+
+    // the advertised limit only applies to requests without a token
+    if !token.is_empty() {
+        return Ok(None);
+    }
+
+    match config.secret_size_limit {
+        Some(0) => Err(ClientError::Unsupported(
+            "anonymous secrets are not allowed, a token is required".to_string(),
+        )),
+        Some(limit) if !config.is_size_allowed(payload.data.len() as u64) => {
+            Err(ClientError::PayloadTooLarge {
+                size: payload.data.len() as u64,
+                limit,
+            })
+        }
+        limit => Ok(limit.map(|limit| limit as usize)),
     }
 }
 
-fn append_to_link(url: Url, crypto_context: &dyn CryptoContext, hash: &str) -> Url {
+fn append_to_link(
+    url: Url,
+    crypto_context: &dyn CryptoContext,
+    hash: &str,
+    secret_id: Option<Ulid>,
+) -> Url {
     let mut link = url.clone();
 
     let mut fragment = crypto_context.key_as_base64();
     fragment.push_str(&format!(":{hash}"));
+    if let Some(secret_id) = secret_id {
+        fragment.push_str(&format!(":{secret_id}"));
+    }
 
     link.set_fragment(Some(&fragment));
     fragment.zeroize();
@@ -153,21 +395,114 @@ fn append_to_link(url: Url, crypto_context: &dyn CryptoContext, hash: &str) -> U
     link
 }
 
-fn decrypt(
+/// Returns the link to the reply: `/s/{id}#{key}::{id}`, without hash as the reply does not
+/// exist yet.
+fn reply_link(base_url: &Url, reply: &ReplySlot) -> Result<Url, ClientError> {
+    let mut link = base_url.join(&format!("{}/{}", link::SHORT_PATH, reply.id))?;
+
+    let mut fragment = format!("{}::{}", reply.key, reply.id);
+    link.set_fragment(Some(&fragment));
+    fragment.zeroize();
+
+    Ok(link)
+}
+
+/// Returns the associated data of the envelope: none for version 1, the prefixed secret ID for
+/// version 2.
+fn associated_data(secret_id: Option<Ulid>) -> Vec<u8> {
+    secret_id
+        .map(|id| format!("{ENVELOPE_V2_AAD_PREFIX}{id}").into_bytes())
+        .unwrap_or_default()
+}
+
+/// Serializes the payload, padded to the next size class (up to the size limit) if requested.
+///
+/// The buffer is allocated large enough to be encrypted and encoded in place by [`encrypt`].
+pub(crate) fn serialize(
+    payload: &Payload,
+    with_padding: bool,
+    size_limit: Option<usize>,
+) -> Result<Zeroizing<Vec<u8>>, ClientError> {
+    let mut len = payload.max_serialized_len();
+    if with_padding {
+        len = padding::padded_len(len, size_limit);
+    }
+
+    let capacity = encoding::encoded_len(len + ENVELOPE_OVERHEAD);
+    let mut data = Zeroizing::new(Vec::with_capacity(capacity));
+    payload.serialize_into(&mut data)?;
+    if with_padding {
+        let padded_len = padding::padded_len(data.len(), size_limit);
+        padding::pad(&mut data, padded_len);
+    }
+
+    Ok(data)
+}
+
+/// Encrypts the serialized payload in place.
+///
+/// Returns the base64 encoded nonce and ciphertext as sent to the server and the hash of the
+/// plaintext to be appended to the link.
+pub(crate) fn encrypt(
+    data: Zeroizing<Vec<u8>>,
+    crypto_context: &mut dyn CryptoContext,
+    aad: &[u8],
+) -> Result<(Vec<u8>, String), ClientError> {
+    let (mut data, hash) = seal(data, crypto_context, aad)?;
+    encoding::encode_in_place(&mut data);
+
+    Ok((data, hash))
+}
+
+/// Encrypts the serialized payload in place, returning the nonce and ciphertext without encoding
+/// and the hash of the plaintext.
+fn seal(
+    mut data: Zeroizing<Vec<u8>>,
+    crypto_context: &mut dyn CryptoContext,
+    aad: &[u8],
+) -> Result<(Vec<u8>, String), ClientError> {
+    let hash = hashing::sha256_truncated_base64_from_bytes(&data);
+
+    crypto_context.seal_in_place(&mut data, aad)?;
+
+    // the buffer holds the ciphertext only from here on, no need to zeroize it
+    Ok((std::mem::take(&mut *data), hash))
+}
+
+/// Decodes and decrypts the data received from the server in place and deserializes the payload.
+///
+/// Without hash, the associated data has to bind the ciphertext to the secret ID, which
+/// authenticates it as well.
+pub(crate) fn decrypt(
     encoded_data: Vec<u8>,
+    crypto_context: Box<dyn CryptoContext>,
+    hash: Option<String>,
+    aad: &[u8],
+) -> Result<Payload, ClientError> {
+    let mut data = Zeroizing::new(encoded_data);
+    encoding::decode_in_place(&mut data)?;
+
+    open(data, crypto_context, hash, aad)
+}
+
+/// Decrypts the nonce and ciphertext in place and deserializes the payload.
+fn open(
+    mut data: Zeroizing<Vec<u8>>,
     mut crypto_context: Box<dyn CryptoContext>,
-    hash: String,
+    hash: Option<String>,
+    aad: &[u8],
 ) -> Result<Payload, ClientError> {
-    let payload = Zeroizing::new(base64::prelude::BASE64_STANDARD.decode(encoded_data)?);
+    crypto_context.open_in_place(&mut data, aad)?;
 
-    crypto_context.import_nonce(&payload)?;
-    let nonce_size = crypto_context.nonce_size();
-    let ciphertext = &payload[nonce_size..];
-    let plaintext = Zeroizing::new(crypto_context.decrypt(ciphertext)?);
+    if let Some(hash) = hash {
+        verify_hash(&data, &hash)?;
+    }
 
-    verify_hash(&plaintext, &hash)?;
+    let payload = Payload::deserialize(&data)?;
+    if !payload.verify_checksum() {
+        return Err(ClientError::ChecksumMismatch);
+    }
 
-    let payload = Payload::deserialize(&plaintext)?;
     Ok(payload)
 }
 
@@ -183,13 +518,18 @@ fn verify_hash(plaintext: &[u8], expected_hash: &str) -> Result<(), ClientError>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::aes::AESCryptoContext;
     use crate::crypto::mock::MockCryptoContextFactory;
     use base64::Engine;
+    use proptest::prelude::*;
     use std::error::Error;
 
     use url::Url;
 
     use crate::client_mock::MockClient;
+    use crate::link::LinkError;
+    use crate::models::SecretRestrictions;
+    use crate::utils::test::MustParse;
 
     type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
@@ -300,7 +640,8 @@ mod tests {
                 "token".to_string(),
                 None,
             )
-            .await?;
+            .await?
+            .url;
         let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
 
         // Receive with a different (wrong) key — decrypt should fail
@@ -332,7 +673,7 @@ mod tests {
         let ctx = MockCryptoContextFactory.generate();
         let key_b64 = ctx.key_as_base64();
 
-        let result = append_to_link(url.clone(), &*ctx, "xyz");
+        let result = append_to_link(url.clone(), &*ctx, "xyz", None);
 
         assert!(
             result
@@ -363,7 +704,8 @@ mod tests {
                 "test_token".to_string(),
                 None,
             )
-            .await?;
+            .await?
+            .url;
 
         let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
         let receive_result = mock_client_with_receive_data(encrypted_data)
@@ -377,6 +719,89 @@ mod tests {
         Ok(())
     }
 
+    /// Records the reported phases.
+    #[derive(Default)]
+    struct PhaseRecorder {
+        phases: std::sync::Mutex<Vec<TransferPhase>>,
+    }
+
+    #[async_trait]
+    impl crate::observer::DataTransferObserver for PhaseRecorder {
+        async fn on_progress(&self, _bytes_transferred: u64, _total_bytes: u64) {}
+
+        async fn on_phase(&self, phase: TransferPhase) {
+            self.phases.lock().expect("lock poisoned").push(phase);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reports_encrypting_and_decrypting_phases() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_send_url(Url::parse("https://example.com/secret/test123")?);
+        let observer = std::sync::Arc::new(PhaseRecorder::default());
+
+        let link = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"observed"),
+                Duration::from_secs(3600),
+                "test_token".to_string(),
+                Some(SecretSendOptions::new().with_observer(observer.clone())),
+            )
+            .await?
+            .url;
+
+        let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
+        mock_client_with_receive_data(encrypted_data)
+            .receive_secret(
+                link,
+                Some(SecretReceiveOptions::new().with_observer(observer.clone())),
+            )
+            .await?;
+
+        assert_eq!(
+            *observer.phases.lock().expect("lock poisoned"),
+            vec![TransferPhase::Encrypting, TransferPhase::Decrypting],
+            "Phases of the crypto layer should be reported in order"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_end_to_end_with_padding() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_send_url(Url::parse("https://example.com/secret/test123")?);
+
+        let secret_data = b"short secret";
+        let send_result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(secret_data),
+                Duration::from_secs(3600),
+                "test_token".to_string(),
+                Some(SecretSendOptions::new().with_padding(true)),
+            )
+            .await?
+            .url;
+
+        let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
+        let decoded = base64::prelude::BASE64_STANDARD.decode(&encrypted_data)?;
+        assert!(
+            decoded.len() >= 1024,
+            "Encrypted payload should be padded, got {} bytes",
+            decoded.len()
+        );
+
+        let receive_result = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(send_result, None)
+            .await?;
+        assert_eq!(
+            receive_result.data, secret_data,
+            "Padding should be ignored on decryption",
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_receive_secret_with_invalid_hash() -> Result<()> {
         let (crypto_client, transport) =
@@ -390,7 +815,8 @@ mod tests {
                 "test_token".to_string(),
                 None,
             )
-            .await?;
+            .await?
+            .url;
 
         let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
 
@@ -415,6 +841,66 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_receive_secret_with_checksum() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_send_url(Url::parse("https://example.com/secret/test123")?);
+
+        let send_result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"Test secret with checksum").with_checksum(),
+                Duration::from_secs(3600),
+                "test_token".to_string(),
+                None,
+            )
+            .await?
+            .url;
+
+        let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
+        let payload = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(send_result, None)
+            .await?;
+
+        assert!(
+            payload.checksum.is_some(),
+            "Checksum should be part of the decrypted payload"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_secret_with_checksum_mismatch() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_send_url(Url::parse("https://example.com/secret/test123")?);
+
+        let mut payload = Payload::from_bytes(b"Test secret with checksum");
+        payload.checksum = Some("0".repeat(64));
+
+        let send_result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                payload,
+                Duration::from_secs(3600),
+                "test_token".to_string(),
+                None,
+            )
+            .await?
+            .url;
+
+        let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
+        let result = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(send_result, None)
+            .await;
+
+        assert!(
+            matches!(result, Err(ClientError::ChecksumMismatch)),
+            "Expected ChecksumMismatch, got: {:?}",
+            result,
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_receive_secret_without_hash_fails() -> Result<()> {
         let (crypto_client, transport) =
@@ -428,7 +914,8 @@ mod tests {
                 "test_token".to_string(),
                 None,
             )
-            .await?;
+            .await?
+            .url;
 
         let encrypted_data = transport.get_sent_data().ok_or("No sent data")?;
 
@@ -452,4 +939,474 @@ mod tests {
         );
         Ok(())
     }
+
+    fn mock_client_with_server_config(config: ServerConfig) -> (CryptoClient, MockClient<Vec<u8>>) {
+        let mock_client = MockClient::new().with_server_config(config);
+        let crypto_client = CryptoClient::with_factory(
+            Box::new(mock_client.clone()),
+            Box::new(MockCryptoContextFactory),
+        );
+        (crypto_client, mock_client)
+    }
+
+    fn server_config_with_size_limit(limit: u64) -> ServerConfig {
+        ServerConfig {
+            secret_size_limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    fn server_config_with_client_ids() -> ServerConfig {
+        ServerConfig {
+            features: crate::models::ServerFeatures {
+                client_ids: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Sends a secret bound to a client generated ID, returns the link and the sent data.
+    async fn send_bound_secret(data: &[u8]) -> Result<(Url, Vec<u8>)> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_client_ids());
+
+        let link = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(data),
+                Duration::from_secs(3600),
+                "token".to_string(),
+                None,
+            )
+            .await?
+            .url;
+
+        let sent_data = transport.get_sent_data().ok_or("No sent data")?;
+        Ok((link, sent_data))
+    }
+
+    #[tokio::test]
+    async fn test_end_to_end_bound_to_secret_id() -> Result<()> {
+        let (link, encrypted_data) = send_bound_secret(b"bound secret").await?;
+
+        let parts: Vec<&str> = link.fragment().ok_or("No fragment")?.split(':').collect();
+        assert_eq!(parts.len(), 3, "Fragment should contain key, hash and ID");
+        assert!(
+            Ulid::from_string(parts[2]).is_ok(),
+            "Third part of the fragment should be the secret ID: {}",
+            parts[2]
+        );
+
+        let payload = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(link, None)
+            .await?;
+        assert_eq!(payload.data, b"bound secret");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_bound_secret_with_other_id_fails() -> Result<()> {
+        let (link, encrypted_data) = send_bound_secret(b"bound secret").await?;
+
+        let fragment = link.fragment().ok_or("No fragment")?;
+        let (key_and_hash, _) = fragment.rsplit_once(':').ok_or("No ID in fragment")?;
+
+        let mut other_id = link.clone();
+        other_id.set_fragment(Some(&format!("{key_and_hash}:{}", Ulid::r#gen())));
+        let result = mock_client_with_receive_data(encrypted_data.clone())
+            .receive_secret(other_id, None)
+            .await;
+        assert!(
+            matches!(result, Err(ClientError::CryptoError(_))),
+            "Ciphertext bound to another ID should be rejected, got: {result:?}"
+        );
+
+        let mut without_id = link.clone();
+        without_id.set_fragment(Some(key_and_hash));
+        let result = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(without_id, None)
+            .await;
+        assert!(
+            result.is_err(),
+            "Bound ciphertext should not decrypt as version 1 envelope, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_bound_secret_with_mismatching_link_fails() -> Result<()> {
+        let (link, encrypted_data) = send_bound_secret(b"bound secret").await?;
+
+        let mut url = Url::parse(&format!("https://example.com/s/{}", Ulid::r#gen()))?;
+        url.set_fragment(link.fragment());
+
+        let result = mock_client_with_receive_data(encrypted_data)
+            .receive_secret(url, None)
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(ClientError::InvalidLink(LinkError::BoundIdMismatch))
+            ),
+            "Link with another secret ID should be rejected, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_secret_invalid_bound_id() -> Result<()> {
+        let crypto_client = mock_client_with_receive_data(b"data".to_vec());
+
+        let mut url = Url::parse("https://example.com/s/abc123")?;
+        url.set_fragment(Some(&format!("{}:hash:not-an-id", mock_key_base64())));
+
+        let result = crypto_client.receive_secret(url, None).await;
+        assert!(
+            matches!(
+                result,
+                Err(ClientError::InvalidLink(LinkError::InvalidBoundId))
+            ),
+            "Expected invalid secret ID error, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    fn server_config_with_replies() -> ServerConfig {
+        ServerConfig {
+            features: crate::models::ServerFeatures {
+                client_ids: true,
+                replies: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_with_reply_embeds_slot() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_replies());
+
+        let sent = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"question"),
+                Duration::from_secs(3600),
+                "token".to_string(),
+                Some(SecretSendOptions::new().with_allow_reply(true)),
+            )
+            .await?;
+        assert_eq!(
+            sent.reply_url, None,
+            "Reply link should require the server to confirm the reservation"
+        );
+
+        let sent_data = transport.get_sent_data().ok_or("No sent data")?;
+        let payload = mock_client_with_receive_data(sent_data)
+            .receive_secret(sent.url, None)
+            .await?;
+        let reply = payload.reply.as_ref().ok_or("No reply slot")?;
+        assert_eq!(reply.key, mock_key_base64());
+        assert!(
+            !reply.upload_token.is_empty(),
+            "Upload token should be generated"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_with_reply_unsupported() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_client_ids());
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"question"),
+                Duration::from_secs(3600),
+                "token".to_string(),
+                Some(SecretSendOptions::new().with_allow_reply(true)),
+            )
+            .await;
+        assert!(
+            matches!(result, Err(ClientError::Unsupported(ref msg)) if msg.contains("replies")),
+            "Expected unsupported replies error, got: {result:?}"
+        );
+        assert!(!transport.was_send_called(), "Secret should not be sent");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_reply_into_slot() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_client_ids());
+        let base_url = Url::parse("https://example.com")?;
+
+        let reply = ReplySlot::new(Ulid::r#gen(), mock_key_base64(), "upload-token".to_string());
+        let reply_id = reply.id;
+        let link_of_sender = reply_link(&base_url, &reply)?;
+        assert_eq!(
+            link_of_sender.fragment(),
+            Some(format!("{}::{reply_id}", mock_key_base64()).as_str())
+        );
+
+        let link = crypto_client
+            .send_secret(
+                base_url,
+                Payload::from_bytes(b"answer"),
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(SecretSendOptions::new().with_reply_to(reply)),
+            )
+            .await?
+            .url;
+        assert!(
+            link.fragment()
+                .is_some_and(|fragment| fragment.ends_with(&reply_id.to_string())),
+            "Reply should be bound to the reserved ID: {link}"
+        );
+
+        // the sender only knows the key and the ID, not the hash of the reply
+        let sent_data = transport.get_sent_data().ok_or("No sent data")?;
+        let payload = mock_client_with_receive_data(sent_data)
+            .receive_secret(link_of_sender, None)
+            .await?;
+        assert_eq!(payload.data, b"answer");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_exceeds_server_size_limit() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_size_limit(4));
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"too large"),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(
+            matches!(
+                result,
+                Err(ClientError::PayloadTooLarge { size: 9, limit: 4 })
+            ),
+            "Expected PayloadTooLarge error, got: {:?}",
+            result,
+        );
+        assert!(
+            !transport.was_send_called(),
+            "Secret should not be sent when exceeding the size limit"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_size_limit_ignored_with_token() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_server_config(server_config_with_size_limit(4));
+
+        crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"too large"),
+                Duration::from_secs(3600),
+                "token".to_string(),
+                None,
+            )
+            .await?;
+
+        assert!(
+            transport.was_send_called(),
+            "Secret should be sent when a token is provided"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_anonymous_not_allowed() -> Result<()> {
+        let (crypto_client, _) = mock_client_with_server_config(server_config_with_size_limit(0));
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(ClientError::Unsupported(ref msg)) if msg.contains("token is required")),
+            "Expected Unsupported error, got: {:?}",
+            result,
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_unsupported_restriction() -> Result<()> {
+        let (crypto_client, transport) = mock_client_with_server_config(ServerConfig::default());
+
+        let restrictions =
+            SecretRestrictions::default().with_allowed_countries(vec!["DE".must_parse()]);
+        let opts = SecretSendOptions::default().with_restrictions(restrictions);
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(3600),
+                "token".to_string(),
+                Some(opts),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(ClientError::Unsupported(ref msg)) if msg.contains("country")),
+            "Expected Unsupported error for country restrictions, got: {:?}",
+            result,
+        );
+        assert!(
+            !transport.was_send_called(),
+            "Secret should not be sent with unsupported restrictions"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_without_server_config() -> Result<()> {
+        let (crypto_client, transport) =
+            mock_client_with_send_url(Url::parse("https://example.com/secret/test123")?);
+
+        crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await?;
+
+        assert!(
+            transport.was_send_called(),
+            "Secret should be sent if the server config is unavailable"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_exceeds_max_ttl() -> Result<()> {
+        let config = ServerConfig {
+            max_ttl: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let (crypto_client, transport) = mock_client_with_server_config(config);
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(7200),
+                "token".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(ClientError::Unsupported(ref msg)) if msg.contains("maximum TTL")),
+            "Expected Unsupported error for TTL, got: {:?}",
+            result,
+        );
+        assert!(
+            !transport.was_send_called(),
+            "Secret should not be sent when exceeding max TTL"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_below_min_ttl() -> Result<()> {
+        let config = ServerConfig {
+            min_ttl: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let (crypto_client, transport) = mock_client_with_server_config(config);
+
+        let result = crypto_client
+            .send_secret(
+                Url::parse("https://example.com")?,
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(300),
+                "token".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(ClientError::Unsupported(ref msg)) if msg.contains("minimum TTL")),
+            "Expected Unsupported error for TTL, got: {:?}",
+            result,
+        );
+        assert!(
+            !transport.was_send_called(),
+            "Secret should not be sent when below min TTL"
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_decrypt_arbitrary_envelope_does_not_panic(
+            envelope in proptest::collection::vec(any::<u8>(), 0..256),
+            hash in ".{0,32}",
+        ) {
+            let encoded = base64::prelude::BASE64_STANDARD.encode(&envelope).into_bytes();
+            let crypto_context = AESCryptoContextFactory
+                .generate_from_key_base64(&AESCryptoContext::generate().key_as_base64())
+                .expect("Failed to create crypto context");
+
+            prop_assert!(decrypt(encoded, crypto_context, Some(hash), &[]).is_err());
+        }
+
+        #[test]
+        fn prop_decrypt_arbitrary_base64_does_not_panic(encoded in ".{0,256}", hash in ".{0,32}") {
+            let crypto_context = AESCryptoContextFactory
+                .generate_from_key_base64(&AESCryptoContext::generate().key_as_base64())
+                .expect("Failed to create crypto context");
+
+            let _ = decrypt(encoded.into_bytes(), crypto_context, Some(hash), &[]);
+        }
+
+        #[test]
+        fn prop_key_parsing_does_not_panic(key in ".{0,64}") {
+            let _ = AESCryptoContextFactory.generate_from_key_base64(&key);
+        }
+
+        #[test]
+        fn prop_encrypt_decrypt_roundtrip(
+            data in proptest::collection::vec(any::<u8>(), 0..2048),
+            with_padding in any::<bool>(),
+        ) {
+            let payload = Payload::from_bytes(&data).with_checksum();
+            let mut crypto_context = AESCryptoContext::generate();
+
+            let plaintext = serialize(&payload, with_padding, None).expect("Failed to serialize");
+            let (encoded, hash) =
+                encrypt(plaintext, &mut crypto_context, &[]).expect("Failed to encrypt");
+
+            let crypto_context = AESCryptoContextFactory
+                .generate_from_key_base64(&crypto_context.key_as_base64())
+                .expect("Failed to create crypto context");
+            let decrypted = decrypt(encoded, crypto_context, Some(hash), &[]).expect("Failed to decrypt");
+            prop_assert_eq!(decrypted, payload);
+        }
+    }
 }
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Length padding of serialized payloads to hide the size of small secrets.
+//!
+//! The padding is appended after the MessagePack encoded payload and is ignored when the
+//! payload is deserialized, since only the first value of the plaintext is decoded.
+
+/// Size classes in bytes the serialized payload is padded to.
+const PADDING_BUCKETS: &[usize] = &[
+    1024,       // 1KB
+    10 * 1024,  // 10KB
+    100 * 1024, // 100KB
+];
+
+/// Returns the length the serialized payload should be padded to.
+///
+/// The length is rounded up to the next size class, but never exceeds `max_len` (e.g. the size
+/// limit of the server). Payloads larger than the largest size class are not padded.
+pub fn padded_len(len: usize, max_len: Option<usize>) -> usize {
+    let Some(bucket) = PADDING_BUCKETS
+        .iter()
+        .copied()
+        .find(|bucket| len <= *bucket)
+    else {
+        return len;
+    };
+
+    match max_len {
+        Some(max_len) => bucket.min(max_len).max(len),
+        None => bucket,
+    }
+}
+
+/// Extends the data to the padded length, filling the remainder with zeros.
+pub fn pad(data: &mut Vec<u8>, padded_len: usize) {
+    data.resize(padded_len.max(data.len()), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padded_len_buckets() {
+        assert_eq!(padded_len(1, None), 1024);
+        assert_eq!(padded_len(1024, None), 1024, "Bounds are inclusive");
+        assert_eq!(padded_len(1025, None), 10 * 1024);
+        assert_eq!(padded_len(50 * 1024, None), 100 * 1024);
+    }
+
+    #[test]
+    fn test_padded_len_large_payload() {
+        let len = 100 * 1024 + 1;
+        assert_eq!(padded_len(len, None), len, "Large payloads are not padded");
+    }
+
+    #[test]
+    fn test_padded_len_limited() {
+        assert_eq!(padded_len(2000, Some(4096)), 4096);
+        assert_eq!(
+            padded_len(5000, Some(4096)),
+            5000,
+            "Data exceeding the limit should not be truncated"
+        );
+    }
+
+    #[test]
+    fn test_pad() {
+        let mut padded = b"abc".to_vec();
+        pad(&mut padded, 8);
+        assert_eq!(padded, b"abc\0\0\0\0\0");
+
+        pad(&mut padded, 4);
+        assert_eq!(padded.len(), 8, "Data should not be truncated");
+    }
+}
@@ -0,0 +1,429 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shamir secret sharing of the decryption key for split-knowledge links.
+//!
+//! The key of a secret link is split into `n` shares, any `k` of them recover the key while
+//! fewer reveal nothing about it. The link itself only keeps the content hash in its fragment,
+//! so it is insufficient to decrypt the secret on its own.
+//!
+//! Shares are encoded as `k.x.data` with the threshold `k`, the share index `x` and the share
+//! data encoded as base64url.
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::shamir;
+//! use url::Url;
+//!
+//! # fn example() -> Result<(), shamir::ShamirError> {
+//! let link = Url::parse("https://hakanai.example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV#AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8:hash").unwrap();
+//!
+//! let (link_without_key, shares) = shamir::split_link(&link, 2, 3)?;
+//! assert_eq!(link_without_key.fragment(), Some(":hash"));
+//!
+//! let joined = shamir::join_link(&link_without_key, &shares[1..])?;
+//! assert_eq!(joined, link);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use base64::Engine;
+use rand::Rng;
+use thiserror::Error;
+use url::Url;
+use zeroize::Zeroizing;
+
+/// Errors of splitting or combining shares.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ShamirError {
+    /// The threshold or number of shares is out of range.
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(String),
+
+    /// A share could not be parsed.
+    #[error("invalid share: {0}")]
+    InvalidShare(String),
+
+    /// Fewer shares than required by the threshold were provided.
+    #[error("{required} shares are required, but only {provided} were provided")]
+    NotEnoughShares { required: u8, provided: usize },
+
+    /// The shares do not belong to the same key.
+    #[error("shares do not belong together")]
+    InconsistentShares,
+
+    /// The link does not have the expected fragment.
+    #[error("invalid link: {0}")]
+    InvalidLink(String),
+}
+
+/// A share of a split key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+    /// Number of shares required to recover the key
+    pub threshold: u8,
+
+    /// Index of the share (x coordinate), never 0
+    pub index: u8,
+
+    /// Share data, one byte per byte of the key
+    pub data: Zeroizing<Vec<u8>>,
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("data", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = Zeroizing::new(base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&*self.data));
+        write!(f, "{}.{}.{}", self.threshold, self.index, *data)
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShamirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '.');
+        let (Some(threshold), Some(index), Some(data)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ShamirError::InvalidShare(
+                "expected format k.x.data".to_string(),
+            ));
+        };
+
+        let threshold = threshold
+            .parse::<u8>()
+            .ok()
+            .filter(|threshold| *threshold >= 2)
+            .ok_or_else(|| ShamirError::InvalidShare("invalid threshold".to_string()))?;
+        let index = index
+            .parse::<u8>()
+            .ok()
+            .filter(|index| *index > 0)
+            .ok_or_else(|| ShamirError::InvalidShare("invalid index".to_string()))?;
+        let data = base64::prelude::BASE64_URL_SAFE_NO_PAD
+            .decode(data)
+            .map_err(|e| ShamirError::InvalidShare(format!("invalid data: {e}")))?;
+        if data.is_empty() {
+            return Err(ShamirError::InvalidShare("empty data".to_string()));
+        }
+
+        Ok(Self {
+            threshold,
+            index,
+            data: Zeroizing::new(data),
+        })
+    }
+}
+
+/// Splits the secret into `count` shares, any `threshold` of them recover the secret.
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>, ShamirError> {
+    if threshold < 2 || threshold > count {
+        return Err(ShamirError::InvalidParameters(format!(
+            "threshold must be between 2 and the number of shares, got {threshold}/{count}"
+        )));
+    }
+    if secret.is_empty() {
+        return Err(ShamirError::InvalidParameters(
+            "secret must not be empty".to_string(),
+        ));
+    }
+
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|index| Share {
+            threshold,
+            index,
+            data: Zeroizing::new(Vec::with_capacity(secret.len())),
+        })
+        .collect();
+
+    // a random polynomial of degree threshold - 1 per byte with the byte as constant term
+    let mut coefficients = Zeroizing::new(vec![0u8; threshold as usize]);
+    let mut rng = rand::rng();
+    for byte in secret {
+        coefficients[0] = *byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+
+        for share in &mut shares {
+            share.data.push(evaluate(&coefficients, share.index));
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Recovers the secret from at least `threshold` shares.
+pub fn combine(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>, ShamirError> {
+    let Some(first) = shares.first() else {
+        return Err(ShamirError::NotEnoughShares {
+            required: 2,
+            provided: 0,
+        });
+    };
+
+    let threshold = first.threshold;
+    let len = first.data.len();
+    if shares
+        .iter()
+        .any(|share| share.threshold != threshold || share.data.len() != len)
+    {
+        return Err(ShamirError::InconsistentShares);
+    }
+
+    let mut unique: Vec<&Share> = Vec::with_capacity(shares.len());
+    for share in shares {
+        match unique.iter().find(|other| other.index == share.index) {
+            Some(other) if other.data != share.data => {
+                return Err(ShamirError::InconsistentShares);
+            }
+            Some(_) => {}
+            None => unique.push(share),
+        }
+    }
+
+    if unique.len() < threshold as usize {
+        return Err(ShamirError::NotEnoughShares {
+            required: threshold,
+            provided: unique.len(),
+        });
+    }
+    let shares = &unique[..threshold as usize];
+
+    // Lagrange interpolation at x = 0, subtraction is XOR in GF(2^8)
+    let mut secret = Zeroizing::new(vec![0u8; len]);
+    for (i, share) in shares.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, other) in shares.iter().enumerate() {
+            if i != j {
+                basis = mul(basis, div(other.index, other.index ^ share.index));
+            }
+        }
+
+        for (byte, y) in secret.iter_mut().zip(share.data.iter()) {
+            *byte ^= mul(*y, basis);
+        }
+    }
+
+    Ok(secret)
+}
+
+/// Splits the key in the fragment (`#key:hash`) of a secret link into shares.
+///
+/// Returns the link with the key removed from the fragment (`#:hash`) and the shares.
+pub fn split_link(link: &Url, threshold: u8, count: u8) -> Result<(Url, Vec<Share>), ShamirError> {
+    let (key, hash) = link
+        .fragment()
+        .and_then(|fragment| fragment.split_once(':'))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or_else(|| ShamirError::InvalidLink("missing key in fragment".to_string()))?;
+
+    let key = Zeroizing::new(
+        base64::prelude::BASE64_URL_SAFE_NO_PAD
+            .decode(key)
+            .map_err(|e| ShamirError::InvalidLink(format!("invalid key: {e}")))?,
+    );
+    let shares = split(&key, threshold, count)?;
+
+    let mut link_without_key = link.clone();
+    link_without_key.set_fragment(Some(&format!(":{hash}")));
+    Ok((link_without_key, shares))
+}
+
+/// Restores the key in the fragment of a link created by [`split_link`] from the shares.
+pub fn join_link(link: &Url, shares: &[Share]) -> Result<Url, ShamirError> {
+    let hash = link
+        .fragment()
+        .and_then(|fragment| fragment.strip_prefix(':'))
+        .ok_or_else(|| {
+            ShamirError::InvalidLink("fragment must not contain a key (#:hash)".to_string())
+        })?;
+
+    let key = combine(shares)?;
+    let mut fragment = Zeroizing::new(base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&*key));
+    fragment.push(':');
+    fragment.push_str(hash);
+
+    let mut link = link.clone();
+    link.set_fragment(Some(&fragment));
+    Ok(link)
+}
+
+/// Checks if the link was created by [`split_link`], i.e. the key has to be joined from shares.
+pub fn is_split_link(link: &Url) -> bool {
+    link.fragment()
+        .is_some_and(|fragment| fragment.starts_with(':'))
+}
+
+/// Evaluates the polynomial at `x` using Horner's method.
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0u8, |acc, coefficient| mul(acc, x) ^ coefficient)
+}
+
+/// Multiplies in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1, without branches on
+/// the operands.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Divides in GF(2^8), `b` must not be 0.
+fn div(a: u8, b: u8) -> u8 {
+    // b^254 is the multiplicative inverse of b
+    let mut inverse = 1u8;
+    let mut base = b;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = mul(inverse, base);
+        }
+        base = mul(base, base);
+        exponent >>= 1;
+    }
+    mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    const KEY: [u8; 32] = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31,
+    ];
+
+    #[test]
+    fn test_field_arithmetic() {
+        assert_eq!(mul(0x57, 0x83), 0xc1, "Multiplication example of FIPS-197");
+        assert_eq!(mul(0x57, 0x13), 0xfe, "Multiplication example of FIPS-197");
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, div(1, a)), 1, "Inverse of {a} should be correct");
+        }
+    }
+
+    #[test]
+    fn test_split_and_combine_all_subsets() -> Result<()> {
+        let shares = split(&KEY, 3, 5)?;
+        assert_eq!(shares.len(), 5);
+
+        for a in 0..5 {
+            for b in (a + 1)..5 {
+                for c in (b + 1)..5 {
+                    let subset = [shares[a].clone(), shares[b].clone(), shares[c].clone()];
+                    assert_eq!(
+                        combine(&subset)?.as_slice(),
+                        KEY,
+                        "Shares {a}, {b} and {c} should recover the key"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_combine_not_enough_shares() -> Result<()> {
+        let shares = split(&KEY, 3, 5)?;
+
+        let result = combine(&[shares[0].clone(), shares[1].clone(), shares[1].clone()]);
+        assert_eq!(
+            result.err(),
+            Some(ShamirError::NotEnoughShares {
+                required: 3,
+                provided: 2
+            }),
+            "Duplicate shares should not count twice"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_combine_inconsistent_shares() -> Result<()> {
+        let shares = split(&KEY, 2, 3)?;
+        let other = split(&KEY, 3, 3)?;
+
+        assert_eq!(
+            combine(&[shares[0].clone(), other[1].clone()]).err(),
+            Some(ShamirError::InconsistentShares)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_invalid_parameters() {
+        assert!(split(&KEY, 1, 3).is_err(), "Threshold 1 should be rejected");
+        assert!(
+            split(&KEY, 4, 3).is_err(),
+            "Threshold above count should be rejected"
+        );
+        assert!(split(&[], 2, 3).is_err(), "Empty secret should be rejected");
+    }
+
+    #[test]
+    fn test_share_string_roundtrip() -> Result<()> {
+        let shares = split(&KEY, 2, 3)?;
+
+        for share in shares {
+            let encoded = share.to_string();
+            assert!(encoded.starts_with(&format!("2.{}.", share.index)));
+            assert_eq!(encoded.parse::<Share>()?, share);
+        }
+
+        for invalid in ["", "2.1", "1.1.AAAA", "2.0.AAAA", "2.1.", "2.1.!!"] {
+            assert!(
+                invalid.parse::<Share>().is_err(),
+                "'{invalid}' should be rejected"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_share_debug_redacts_data() -> Result<()> {
+        let share = &split(&KEY, 2, 2)?[0];
+        let debug = format!("{share:?}");
+        assert!(debug.contains("REDACTED"), "Share data should be redacted");
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_and_join_link() -> Result<()> {
+        let key = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(KEY);
+        let link = Url::parse(&format!("https://example.com/s/abc#{key}:hash"))?;
+
+        let (link_without_key, shares) = split_link(&link, 2, 3)?;
+        assert_eq!(link_without_key.fragment(), Some(":hash"));
+        assert!(is_split_link(&link_without_key));
+        assert!(!is_split_link(&link));
+
+        let joined = join_link(&link_without_key, &[shares[2].clone(), shares[0].clone()])?;
+        assert_eq!(joined, link);
+
+        assert!(
+            join_link(&link, &shares).is_err(),
+            "Link still containing the key should be rejected"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,382 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Buffers for transfers larger than the memory budget of the client.
+//!
+//! A [`SpillBuffer`] keeps data in memory up to a configurable limit. Beyond that limit the data
+//! is moved to an anonymous temporary file, which is encrypted with AES-256-GCM under an
+//! ephemeral key that only exists in memory. Data left behind on disk (e.g. after a crash or in
+//! swap-like storage) can therefore not be recovered.
+//!
+//! The file is written in records of [`RECORD_SIZE`] bytes, each sealed with its own nonce
+//! derived from the record index, so records cannot be reordered or replaced unnoticed.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::{Read, Write};
+//! use hakanai_lib::spill::SpillBuffer;
+//!
+//! # fn example() -> std::io::Result<()> {
+//! let mut buffer = SpillBuffer::new(1024);
+//! buffer.write_all(&[42u8; 4096])?;
+//! assert!(buffer.is_spilled());
+//!
+//! let mut data = Vec::new();
+//! buffer.into_reader()?.read_to_end(&mut data)?;
+//! assert_eq!(data, vec![42u8; 4096]);
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use aes_gcm::aead::{Aead, Nonce};
+use aes_gcm::{Aes256Gcm, Key, KeyInit};
+use rand::Rng;
+use zeroize::Zeroizing;
+
+/// Size of the plaintext records the temporary file is encrypted in.
+pub const RECORD_SIZE: usize = 64 * 1024; // 64 KB
+
+const KEY_SIZE: usize = 32;
+const TAG_SIZE: usize = 16;
+
+/// A write buffer which moves its contents to an encrypted temporary file once they exceed the
+/// memory limit.
+///
+/// Once spilled, at most one record ([`RECORD_SIZE`]) is kept in memory, regardless of the limit.
+pub struct SpillBuffer {
+    max_memory: usize,
+    memory: Zeroizing<Vec<u8>>,
+    file: Option<SpillFile>,
+    len: u64,
+}
+
+impl SpillBuffer {
+    /// Creates an empty buffer keeping up to `max_memory` bytes in memory.
+    pub fn new(max_memory: usize) -> Self {
+        SpillBuffer {
+            max_memory,
+            memory: Zeroizing::new(Vec::new()),
+            file: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of bytes written to the buffer.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if no bytes have been written to the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the contents have been moved to a temporary file.
+    pub fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Finishes writing and returns a reader over the contents of the buffer.
+    pub fn into_reader(mut self) -> io::Result<SpillReader> {
+        let Some(mut file) = self.file.take() else {
+            return Ok(SpillReader::Memory {
+                data: std::mem::take(&mut self.memory),
+                pos: 0,
+            });
+        };
+
+        if !self.memory.is_empty() {
+            file.write_record(&self.memory)?;
+        }
+        file.file.seek(SeekFrom::Start(0))?;
+
+        Ok(SpillReader::File {
+            file,
+            remaining: self.len,
+            record: Zeroizing::new(Vec::new()),
+            pos: 0,
+        })
+    }
+
+    /// Moves all complete records from memory to the file.
+    fn flush_records(&mut self) -> io::Result<()> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+
+        let mut offset = 0;
+        while self.memory.len() - offset >= RECORD_SIZE {
+            file.write_record(&self.memory[offset..offset + RECORD_SIZE])?;
+            offset += RECORD_SIZE;
+        }
+        self.memory.drain(..offset);
+
+        Ok(())
+    }
+}
+
+impl Write for SpillBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // large writes are taken record by record, so they are not copied to memory as a whole
+        let buf = &buf[..buf.len().min(RECORD_SIZE)];
+        self.memory.extend_from_slice(buf);
+        self.len += buf.len() as u64;
+
+        if self.file.is_none() && self.memory.len() > self.max_memory {
+            self.file = Some(SpillFile::new()?);
+        }
+        self.flush_records()?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reader over the contents of a [`SpillBuffer`], decrypting spilled records on the fly.
+pub enum SpillReader {
+    /// The contents never exceeded the memory limit.
+    Memory {
+        data: Zeroizing<Vec<u8>>,
+        pos: usize,
+    },
+    /// The contents are read record by record from the temporary file.
+    File {
+        file: SpillFile,
+        remaining: u64,
+        record: Zeroizing<Vec<u8>>,
+        pos: usize,
+    },
+}
+
+impl Read for SpillReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SpillReader::Memory { data, pos } => {
+                let n = (&data[*pos..]).read(buf)?;
+                *pos += n;
+                Ok(n)
+            }
+            SpillReader::File {
+                file,
+                remaining,
+                record,
+                pos,
+            } => {
+                if *pos == record.len() {
+                    if *remaining == 0 {
+                        return Ok(0);
+                    }
+
+                    let record_len = usize::try_from(*remaining)
+                        .unwrap_or(RECORD_SIZE)
+                        .min(RECORD_SIZE);
+                    *record = file.read_record(record_len)?;
+                    *remaining -= record_len as u64;
+                    *pos = 0;
+                }
+
+                let n = (&record[*pos..]).read(buf)?;
+                *pos += n;
+                Ok(n)
+            }
+        }
+    }
+}
+
+/// Anonymous temporary file holding the encrypted records, removed by the OS once closed.
+pub struct SpillFile {
+    file: File,
+    key: Zeroizing<[u8; KEY_SIZE]>,
+    records_written: u64,
+    records_read: u64,
+}
+
+impl SpillFile {
+    fn new() -> io::Result<Self> {
+        let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+        rand::rng().fill_bytes(key.as_mut_slice());
+
+        Ok(SpillFile {
+            file: tempfile::tempfile()?,
+            key,
+            records_written: 0,
+            records_read: 0,
+        })
+    }
+
+    fn write_record(&mut self, plaintext: &[u8]) -> io::Result<()> {
+        let nonce = record_nonce(self.records_written);
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| io::Error::other("encrypting spilled data failed"))?;
+
+        self.file.write_all(&ciphertext)?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    fn read_record(&mut self, len: usize) -> io::Result<Zeroizing<Vec<u8>>> {
+        let mut ciphertext = vec![0u8; len + TAG_SIZE];
+        self.file.read_exact(&mut ciphertext)?;
+
+        let nonce = record_nonce(self.records_read);
+        let plaintext = self
+            .cipher()
+            .decrypt(&nonce, ciphertext.as_slice())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "spilled data was modified"))?;
+
+        self.records_read += 1;
+        Ok(Zeroizing::new(plaintext))
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        let key: &Key<Aes256Gcm> = (&self.key[..])
+            .try_into()
+            .expect("spill key has the AES-256 key size");
+        Aes256Gcm::new(key)
+    }
+}
+
+/// Nonce of a record, unique per record as every buffer uses its own key.
+fn record_nonce(index: u64) -> Nonce<Aes256Gcm> {
+    let mut nonce = Nonce::<Aes256Gcm>::default();
+    nonce[4..].copy_from_slice(&index.to_be_bytes());
+    nonce
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(buffer: SpillBuffer) -> Vec<u8> {
+        let mut data = Vec::new();
+        buffer
+            .into_reader()
+            .expect("Reader should be created")
+            .read_to_end(&mut data)
+            .expect("Buffer should be readable");
+        data
+    }
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_below_limit_stays_in_memory() {
+        let mut buffer = SpillBuffer::new(1024);
+        buffer.write_all(b"secret").expect("Write should succeed");
+
+        assert!(!buffer.is_spilled(), "Small data should not be spilled");
+        assert_eq!(buffer.len(), 6);
+        assert_eq!(read_all(buffer), b"secret");
+    }
+
+    #[test]
+    fn test_above_limit_spills_to_file() {
+        let data = test_data(3 * RECORD_SIZE + 123);
+
+        let mut buffer = SpillBuffer::new(1024);
+        for chunk in data.chunks(1000) {
+            buffer.write_all(chunk).expect("Write should succeed");
+        }
+
+        assert!(
+            buffer.is_spilled(),
+            "Data above the limit should be spilled"
+        );
+        assert!(
+            buffer.memory.len() < RECORD_SIZE,
+            "At most one record should be kept in memory"
+        );
+        assert_eq!(buffer.len(), data.len() as u64);
+        assert_eq!(read_all(buffer), data);
+    }
+
+    #[test]
+    fn test_exact_record_boundary() {
+        let data = test_data(2 * RECORD_SIZE);
+
+        let mut buffer = SpillBuffer::new(0);
+        buffer.write_all(&data).expect("Write should succeed");
+
+        assert_eq!(read_all(buffer), data);
+    }
+
+    #[test]
+    fn test_empty_buffer() {
+        let buffer = SpillBuffer::new(0);
+
+        assert!(buffer.is_empty());
+        assert!(
+            !buffer.is_spilled(),
+            "Empty buffer should not create a file"
+        );
+        assert!(read_all(buffer).is_empty());
+    }
+
+    #[test]
+    fn test_file_is_encrypted() {
+        let data = vec![b'A'; 2 * RECORD_SIZE];
+
+        let mut buffer = SpillBuffer::new(0);
+        buffer.write_all(&data).expect("Write should succeed");
+
+        let file = &mut buffer.file.as_mut().expect("Buffer should be spilled").file;
+        let mut on_disk = Vec::new();
+        file.seek(SeekFrom::Start(0)).expect("Seek should succeed");
+        file.read_to_end(&mut on_disk).expect("Read should succeed");
+
+        assert_eq!(on_disk.len(), 2 * (RECORD_SIZE + TAG_SIZE));
+        assert!(
+            !on_disk.windows(64).any(|w| w.iter().all(|b| *b == b'A')),
+            "Plaintext should not be written to disk"
+        );
+    }
+
+    #[test]
+    fn test_modified_file_is_rejected() {
+        let mut buffer = SpillBuffer::new(0);
+        buffer
+            .write_all(&test_data(RECORD_SIZE))
+            .expect("Write should succeed");
+
+        let file = &mut buffer.file.as_mut().expect("Buffer should be spilled").file;
+        file.seek(SeekFrom::Start(10)).expect("Seek should succeed");
+        file.write_all(&[0xff]).expect("Write should succeed");
+        file.seek(SeekFrom::End(0)).expect("Seek should succeed");
+
+        let mut data = Vec::new();
+        let result = buffer
+            .into_reader()
+            .expect("Reader should be created")
+            .read_to_end(&mut data);
+        assert!(
+            matches!(result, Err(ref e) if e.kind() == io::ErrorKind::InvalidData),
+            "Modified records should be rejected, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_large_write_is_not_copied_to_memory() {
+        let data = test_data(16 * RECORD_SIZE);
+
+        let mut buffer = SpillBuffer::new(1024);
+        buffer.write_all(&data).expect("Write should succeed");
+
+        assert!(
+            buffer.memory.capacity() <= 2 * RECORD_SIZE,
+            "Memory should stay bounded, got a capacity of {} bytes",
+            buffer.memory.capacity()
+        );
+        assert_eq!(read_all(buffer), data);
+    }
+}
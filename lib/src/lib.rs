@@ -27,7 +27,7 @@
 //! let client = client::new();
 //!
 //! // Send a text secret
-//! let secret_url = client.send_secret(
+//! let sent = client.send_secret(
 //!     Url::parse("https://example.com")?,
 //!     hakanai_lib::models::Payload::from_bytes(b"My secret message"),
 //!     Duration::from_secs(3600), // 1 hour TTL
@@ -35,11 +35,14 @@
 //!     None, // No custom options
 //! ).await?;
 //!
-//! println!("Secret URL: {}", secret_url);
+//! println!("Secret URL: {}", sent.url);
+//! if let Some(expires_in) = sent.expires_in() {
+//!     println!("Expires in {} seconds", expires_in.as_secs());
+//! }
 //!
 //! // Receive the secret (normally done by recipient)
-//! let payload = client.receive_secret(secret_url, None).await?;
-//! println!("Retrieved: {:#?}", payload.data);
+//! let payload = client.receive_secret(sent.url, None).await?;
+//! println!("Retrieved: {}", payload.as_text()?);
 //! # Ok(())
 //! # }
 //! ```
@@ -59,7 +62,7 @@
 //! let payload = Payload::from_bytes(&file_contents).with_filename("document.pdf");
 //!
 //! // Send the file
-//! let secret_url = client.send_secret(
+//! let sent = client.send_secret(
 //!     Url::parse("https://example.com")?,
 //!     payload,
 //!     Duration::from_secs(86400), // 24 hour TTL
@@ -67,7 +70,7 @@
 //!     None,
 //! ).await?;
 //!
-//! println!("File shared at: {}", secret_url);
+//! println!("File shared at: {}", sent.url);
 //! # Ok(())
 //! # }
 //! ```
@@ -105,17 +108,48 @@
 //! # }
 //! ```
 //!
-//!
-//!
+//! # Features
+//!
+//! - `http-client` (default) - Complete client including HTTP communication via `reqwest`
+//! - `crypto-only` - Client-side encryption via `client::with_transport()` for custom transports,
+//!   without an HTTP/TLS stack, and offline encryption of envelopes (`envelope`)
+//! - `models-only` - Models and utilities only, e.g. for services embedding the wire format
+//! - `link` - Parsing of secret links (`link::SecretLink`), included in `crypto-only`
+//! - `simd` - SIMD accelerated base64 encoding and decoding of encrypted secrets, which speeds
+//!   up large files
+//! - `testing` - `MockClient` and an in-process mock server (`testing::MockServer`) for
+//!   integration tests
+//!
+//! ```toml
+//! [dependencies]
+//! hakanai-lib = { version = "3", default-features = false, features = ["crypto-only"] }
+//! ```
 
+#[cfg(feature = "crypto-only")]
 pub mod client;
+#[cfg(feature = "crypto-only")]
+pub mod compat;
+#[cfg(feature = "link")]
+pub mod link;
 pub mod models;
 pub mod observer;
 pub mod options;
 pub mod utils;
 
-#[cfg(any(test, feature = "testing"))]
+#[cfg(all(feature = "crypto-only", any(test, feature = "testing")))]
 pub mod client_mock;
+#[cfg(all(feature = "http-client", any(test, feature = "testing")))]
+pub mod testing;
 
+#[cfg(feature = "crypto-only")]
 mod crypto;
+#[cfg(feature = "crypto-only")]
+pub use crypto::envelope;
+#[cfg(feature = "crypto-only")]
+pub use crypto::shamir;
+#[cfg(feature = "http-client")]
+pub use crypto::spill;
+#[cfg(feature = "http-client")]
+mod throttle;
+#[cfg(feature = "http-client")]
 mod web;
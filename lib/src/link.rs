@@ -0,0 +1,392 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of secret links.
+//!
+//! Links point to `/s/{id}` on the server, links of older clients to the API endpoint
+//! (`/api/v1/secret/{id}`). The ID is the ULID or the short alias of the secret.
+//!
+//! The fragment holds the key encoded as base64url, the hash of the plaintext and, if the
+//! ciphertext is bound to the secret ID, the ULID of the secret: `#{key}:{hash}:{id}`. Links of
+//! older clients only carry the key and hash, links split with [`crate::shamir`] the hash only.
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::link::SecretLink;
+//! use url::Url;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let url = Url::parse("https://hakanai.example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV#a2V5:aGFzaA")?;
+//! let link = SecretLink::parse(&url)?;
+//!
+//! assert_eq!(link.id(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+//! assert_eq!(link.key(), Some("a2V5"));
+//! assert_eq!(link.hash(), Some("aGFzaA"));
+//! assert_eq!(link.server().as_str(), "https://hakanai.example.com/");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+use ulid::Ulid;
+use url::Url;
+use zeroize::Zeroizing;
+
+use crate::models::secret;
+
+/// Path of the secret links.
+pub const SHORT_PATH: &str = "s";
+
+/// Path of the API endpoint used by links of older clients.
+pub const API_PATH: &str = "api/v1/secret";
+
+/// Errors of parsing a secret link.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LinkError {
+    /// The link is not a valid URL.
+    #[error("invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+
+    /// The link does not use HTTP(S).
+    #[error("unsupported scheme: {0}")]
+    UnsupportedScheme(String),
+
+    /// The link has no host.
+    #[error("missing host")]
+    MissingHost,
+
+    /// The path is neither `/s/{id}` nor `/api/v1/secret/{id}`.
+    #[error("invalid path, expected /s/{{id}}")]
+    InvalidPath,
+
+    /// The last segment of the path is neither a ULID nor a short alias.
+    #[error("invalid secret ID")]
+    InvalidSecretId,
+
+    /// The secret ID bound to the key is not a ULID.
+    #[error("invalid secret ID in URL fragment")]
+    InvalidBoundId,
+
+    /// The secret ID bound to the key differs from the ID in the path.
+    #[error("secret ID of the link does not match the ID bound to the key")]
+    BoundIdMismatch,
+}
+
+/// The format of the path of a secret link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkFormat {
+    /// `/s/{id}`
+    Short,
+
+    /// `/api/v1/secret/{id}`, used by older clients
+    Api,
+}
+
+/// The fragment of a secret link (`{key}:{hash}:{id}`).
+#[derive(Clone, PartialEq, Eq)]
+pub struct LinkFragment {
+    key: Zeroizing<String>,
+    hash: Option<String>,
+    bound_id: Option<Ulid>,
+}
+
+impl LinkFragment {
+    /// Parses the fragment of the URL, `None` if it has no (or an empty) fragment.
+    ///
+    /// Links with the full ID in their path must refer to the ID bound to the key, links with a
+    /// short alias cannot be checked before the download and rely on the associated data only.
+    pub fn from_url(url: &Url) -> Result<Option<Self>, LinkError> {
+        let Some(fragment) = url.fragment().filter(|fragment| !fragment.is_empty()) else {
+            return Ok(None);
+        };
+
+        let mut parts = fragment.splitn(3, ':');
+        let key = Zeroizing::new(parts.next().unwrap_or_default().to_string());
+        let hash = parts
+            .next()
+            .filter(|hash| !hash.is_empty())
+            .map(str::to_string);
+        let bound_id = parts
+            .next()
+            .map(|id| Ulid::from_string(id).map_err(|_| LinkError::InvalidBoundId))
+            .transpose()?;
+
+        if let Some(bound_id) = bound_id
+            && last_segment(url)
+                .and_then(|segment| Ulid::from_string(segment).ok())
+                .is_some_and(|path_id| path_id != bound_id)
+        {
+            return Err(LinkError::BoundIdMismatch);
+        }
+
+        Ok(Some(Self {
+            key,
+            hash,
+            bound_id,
+        }))
+    }
+
+    /// Returns the key encoded as base64url, `None` if the key was split into shares.
+    pub fn key(&self) -> Option<&str> {
+        Some(self.key.as_str()).filter(|key| !key.is_empty())
+    }
+
+    /// Returns the hash of the plaintext, `None` for links of old clients.
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// Returns the secret ID bound to the key, if any.
+    pub fn bound_id(&self) -> Option<Ulid> {
+        self.bound_id
+    }
+}
+
+impl fmt::Debug for LinkFragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkFragment")
+            .field("key", &"[REDACTED]")
+            .field("hash", &self.hash)
+            .field("bound_id", &self.bound_id)
+            .finish()
+    }
+}
+
+/// A parsed and validated secret link.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretLink {
+    url: Url,
+    id: String,
+    format: LinkFormat,
+    fragment: Option<LinkFragment>,
+}
+
+impl SecretLink {
+    /// Parses the link, checking its scheme, host, path and fragment.
+    ///
+    /// Links without key are accepted, e.g. when the key is provided separately.
+    pub fn parse(url: &Url) -> Result<Self, LinkError> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(LinkError::UnsupportedScheme(url.scheme().to_string()));
+        }
+        if url.host_str().is_none_or(str::is_empty) {
+            return Err(LinkError::MissingHost);
+        }
+
+        let path = url.path().trim_start_matches('/');
+        let (format, id) = if let Some(id) = path.strip_prefix(&format!("{SHORT_PATH}/")) {
+            (LinkFormat::Short, id)
+        } else if let Some(id) = path.strip_prefix(&format!("{API_PATH}/")) {
+            (LinkFormat::Api, id)
+        } else {
+            return Err(LinkError::InvalidPath);
+        };
+        if !secret::is_valid_secret_id(id) {
+            return Err(LinkError::InvalidSecretId);
+        }
+
+        Ok(Self {
+            url: url.clone(),
+            id: id.to_string(),
+            format,
+            fragment: LinkFragment::from_url(url)?,
+        })
+    }
+
+    /// Returns the ID of the secret as in the link, the ULID or the short alias.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the ULID of the secret, `None` for links with a short alias.
+    pub fn secret_id(&self) -> Option<Ulid> {
+        Ulid::from_string(&self.id).ok()
+    }
+
+    /// Returns the format of the path.
+    pub fn format(&self) -> LinkFormat {
+        self.format
+    }
+
+    /// Returns the key encoded as base64url, `None` if the link carries no key.
+    pub fn key(&self) -> Option<&str> {
+        self.fragment.as_ref().and_then(LinkFragment::key)
+    }
+
+    /// Returns the hash of the plaintext, if included in the link.
+    pub fn hash(&self) -> Option<&str> {
+        self.fragment.as_ref().and_then(LinkFragment::hash)
+    }
+
+    /// Returns the secret ID bound to the key, if any.
+    pub fn bound_id(&self) -> Option<Ulid> {
+        self.fragment.as_ref().and_then(LinkFragment::bound_id)
+    }
+
+    /// Returns the base URL of the server the secret is stored on.
+    pub fn server(&self) -> Url {
+        let mut server = self.url.clone();
+        server.set_path("/");
+        server.set_query(None);
+        server.set_fragment(None);
+        server
+    }
+
+    /// Returns the link as parsed.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the link without the fragment, e.g. to be printed or stored.
+    pub fn without_key(&self) -> Url {
+        let mut url = self.url.clone();
+        url.set_fragment(None);
+        url
+    }
+}
+
+impl FromStr for SecretLink {
+    type Err = LinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(&Url::parse(s.trim())?)
+    }
+}
+
+impl fmt::Debug for SecretLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretLink")
+            .field("url", &self.without_key().as_str())
+            .field("id", &self.id)
+            .field("format", &self.format)
+            .field("fragment", &self.fragment)
+            .finish()
+    }
+}
+
+fn last_segment(url: &Url) -> Option<&str> {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
+    fn parse(link: &str) -> Result<SecretLink, LinkError> {
+        SecretLink::parse(&Url::parse(link).expect("URL should be valid"))
+    }
+
+    #[test]
+    fn test_parse_short_link() -> Result<(), LinkError> {
+        let link = parse(&format!("https://example.com/s/{ID}#key:hash:{ID}"))?;
+
+        assert_eq!(link.id(), ID);
+        assert_eq!(link.secret_id(), Some(Ulid::from_string(ID).unwrap()));
+        assert_eq!(link.format(), LinkFormat::Short);
+        assert_eq!(link.key(), Some("key"));
+        assert_eq!(link.hash(), Some("hash"));
+        assert_eq!(link.bound_id(), link.secret_id());
+        assert_eq!(link.server().as_str(), "https://example.com/");
+        assert_eq!(
+            link.without_key().as_str(),
+            format!("https://example.com/s/{ID}")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_legacy_api_link() -> Result<(), LinkError> {
+        let link = parse(&format!("http://localhost:8080/api/v1/secret/{ID}#key"))?;
+
+        assert_eq!(link.format(), LinkFormat::Api);
+        assert_eq!(link.key(), Some("key"));
+        assert_eq!(link.hash(), None, "Links of old clients have no hash");
+        assert_eq!(link.server().as_str(), "http://localhost:8080/");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_alias_link() -> Result<(), LinkError> {
+        let link = parse(&format!(
+            "https://example.com/s/blue-otter-42#key:hash:{ID}"
+        ))?;
+
+        assert_eq!(link.id(), "blue-otter-42");
+        assert_eq!(link.secret_id(), None);
+        assert_eq!(
+            link.bound_id(),
+            Some(Ulid::from_string(ID).unwrap()),
+            "Bound ID of alias links cannot be checked before the download"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_link_without_key() -> Result<(), LinkError> {
+        assert_eq!(parse(&format!("https://example.com/s/{ID}"))?.key(), None);
+        assert_eq!(parse(&format!("https://example.com/s/{ID}#"))?.key(), None);
+
+        let split = parse(&format!("https://example.com/s/{ID}#:hash"))?;
+        assert_eq!(split.key(), None, "Key of split links is not available");
+        assert_eq!(split.hash(), Some("hash"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_links() {
+        assert_eq!(
+            parse(&format!("ftp://example.com/s/{ID}")),
+            Err(LinkError::UnsupportedScheme("ftp".to_string()))
+        );
+        assert_eq!(
+            parse(&format!("https://example.com/secret/{ID}")),
+            Err(LinkError::InvalidPath)
+        );
+        assert_eq!(
+            parse(&format!("https://example.com/s/{ID}/extra")),
+            Err(LinkError::InvalidSecretId)
+        );
+        assert_eq!(
+            parse("https://example.com/s/..%2Fadmin"),
+            Err(LinkError::InvalidSecretId)
+        );
+        assert_eq!(
+            parse(&format!("https://example.com/s/{ID}#key:hash:not-an-id")),
+            Err(LinkError::InvalidBoundId)
+        );
+        assert_eq!(
+            parse(&format!(
+                "https://example.com/s/{ID}#key:hash:{}",
+                Ulid::r#gen()
+            )),
+            Err(LinkError::BoundIdMismatch)
+        );
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), LinkError> {
+        let link: SecretLink = format!(" https://example.com/s/{ID}#key:hash ").parse()?;
+        assert_eq!(link.id(), ID);
+
+        assert!(matches!(
+            "not a link".parse::<SecretLink>(),
+            Err(LinkError::InvalidUrl(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fragment_debug_redacts_key() -> Result<(), LinkError> {
+        let link = parse(&format!("https://example.com/s/{ID}#secretkey:hash"))?;
+
+        let debug = format!("{link:?}");
+        assert!(!debug.contains("secretkey"), "Key should be redacted");
+        Ok(())
+    }
+}
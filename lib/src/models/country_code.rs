@@ -4,8 +4,12 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+use super::country_data::COUNTRIES;
 use super::errors::ValidationError;
 
+/// Codes commonly used instead of the ISO 3166-1 code, e.g. by the EU.
+const ALIASES: &[(&str, &str)] = &[("UK", "GB"), ("EL", "GR")];
+
 /// A struct representing a country code following the ISO 3166-1 alpha-2 standard.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(try_from = "String", into = "String")]
@@ -22,6 +26,70 @@ impl CountryCode {
         }
     }
 
+    /// Parses a code which must be assigned in ISO 3166-1.
+    ///
+    /// The error suggests the code the user probably meant, e.g. `GB` for `UK` or `DE` for
+    /// `de`, `DEU` or `Germany`.
+    pub fn parse_assigned(code: &str) -> Result<Self, ValidationError> {
+        let code = code.trim();
+        if let Ok(country_code) = CountryCode::new(code)
+            && country_code.is_assigned()
+        {
+            return Ok(country_code);
+        }
+
+        let message = match CountryCode::suggest(code) {
+            Some(suggestion) => {
+                format!("'{code}' is not a valid country code, did you mean '{suggestion}'?")
+            }
+            None => format!("'{code}' is not a valid ISO 3166-1 alpha-2 country code"),
+        };
+        Err(ValidationError::new(message))
+    }
+
+    /// Suggests the assigned code matching an alias, lowercase code, alpha-3 code or name.
+    pub fn suggest(input: &str) -> Option<CountryCode> {
+        let input = input.trim().to_uppercase();
+        let alias = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == input)
+            .map(|(_, code)| *code);
+
+        alias
+            .or_else(|| {
+                COUNTRIES
+                    .iter()
+                    .find(|(alpha2, alpha3, name)| {
+                        *alpha2 == input || *alpha3 == input || name.to_uppercase() == input
+                    })
+                    .map(|(alpha2, _, _)| *alpha2)
+            })
+            .map(|code| CountryCode(code.to_string()))
+    }
+
+    /// All codes assigned in ISO 3166-1, sorted by code.
+    pub fn all() -> impl Iterator<Item = CountryCode> {
+        COUNTRIES
+            .iter()
+            .map(|(code, _, _)| CountryCode(code.to_string()))
+    }
+
+    /// English short name of the country, `None` if the code is not assigned in ISO 3166-1.
+    pub fn name(&self) -> Option<&'static str> {
+        COUNTRIES
+            .binary_search_by(|(code, _, _)| (*code).cmp(self.as_str()))
+            .ok()
+            .map(|idx| COUNTRIES[idx].2)
+    }
+
+    /// Whether the code is assigned in ISO 3166-1.
+    ///
+    /// Codes like `XK` (Kosovo) are used by geolocation databases without being assigned, so
+    /// [`CountryCode::new`] only checks the format.
+    pub fn is_assigned(&self) -> bool {
+        self.name().is_some()
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
@@ -68,7 +136,7 @@ mod tests {
             let result = CountryCode::new(code);
             assert_eq!(
                 result
-                    .expect(&format!("Expected {code} to be valid"))
+                    .unwrap_or_else(|_| panic!("Expected {code} to be valid"))
                     .as_str(),
                 code
             );
@@ -210,4 +278,74 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_name() {
+        let country_code: CountryCode = "DE".must_parse();
+        assert_eq!(country_code.name(), Some("Germany"));
+
+        let country_code: CountryCode = "XK".must_parse();
+        assert_eq!(
+            country_code.name(),
+            None,
+            "Unassigned codes should have no name"
+        );
+        assert!(
+            !country_code.is_assigned(),
+            "XK is not assigned in ISO 3166-1"
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        let all: Vec<CountryCode> = CountryCode::all().collect();
+        assert_eq!(all.len(), 249, "All ISO 3166-1 codes should be listed");
+        assert!(
+            all.windows(2).all(|w| w[0].as_str() < w[1].as_str()),
+            "Codes should be sorted for lookups"
+        );
+        assert!(
+            all.iter().all(CountryCode::is_assigned),
+            "All codes should have a name"
+        );
+    }
+
+    #[test]
+    fn test_parse_assigned() {
+        let result = CountryCode::parse_assigned("GB");
+        assert_eq!(
+            result.map(|c| c.to_string()).ok(),
+            Some("GB".to_string()),
+            "Assigned codes should be accepted"
+        );
+    }
+
+    #[test]
+    fn test_parse_assigned_suggestions() {
+        let cases = [
+            ("UK", "GB"),
+            ("de", "DE"),
+            ("DEU", "DE"),
+            ("germany", "DE"),
+            ("United States", "US"),
+        ];
+
+        for (input, expected) in cases {
+            let error = CountryCode::parse_assigned(input).expect_err("Input should be rejected");
+            assert_eq!(
+                error.message,
+                format!("'{input}' is not a valid country code, did you mean '{expected}'?"),
+                "Error for '{input}' should suggest {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_assigned_without_suggestion() {
+        let error = CountryCode::parse_assigned("ZZ").expect_err("ZZ should be rejected");
+        assert_eq!(
+            error.message,
+            "'ZZ' is not a valid ISO 3166-1 alpha-2 country code"
+        );
+    }
 }
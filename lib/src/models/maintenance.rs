@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Maintenance mode of a server instance, read and toggled via admin API
+///
+/// In maintenance mode the server rejects new secrets, existing secrets can still be retrieved.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceStatus {
+    /// Whether the maintenance mode is enabled
+    pub enabled: bool,
+}
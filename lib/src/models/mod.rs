@@ -8,23 +8,41 @@
 //!
 //! # Submodules
 //!
-//! - [`country_code`] - ISO 3166-1 alpha-2 country code validation and representation
+//! - [`country_code`] - ISO 3166-1 alpha-2 country code validation, representation and names
 //! - [`errors`] - Common validation error types for model data structures
+//! - [`maintenance`] - Maintenance mode of a server instance for admin API
 //! - [`payload`] - Core payload structure for secrets (text/binary data with optional filename)
+//! - [`purge`] - Purging of stored secrets via admin API
 //! - [`restrictions`] - Access restriction models (IP-based and geo-location filtering)
 //! - [`secret`] - API request/response models for secret creation and retrieval
+//! - [`server_config`] - Server capabilities and limits as advertised by `/config.json`
+//! - [`stats`] - Aggregated secret statistics for admin API
 //! - [`token`] - Token management structures for admin API
 
 pub mod country_code;
+mod country_data;
 pub mod errors;
+pub mod maintenance;
 pub mod payload;
+pub mod purge;
 pub mod restrictions;
 pub mod secret;
+pub mod server_config;
+pub mod stats;
 pub mod token;
 
 pub use country_code::CountryCode;
 pub use errors::ValidationError;
-pub use payload::Payload;
+pub use maintenance::MaintenanceStatus;
+pub use payload::{Payload, PayloadDataType, ReplySlot};
+pub use purge::{PurgePreviewResponse, PurgeResponse};
 pub use restrictions::SecretRestrictions;
-pub use secret::{PostSecretRequest, PostSecretResponse};
-pub use token::{CreateTokenRequest, CreateTokenResponse};
+pub use secret::{
+    OwnedSecret, OwnedSecretsResponse, PostSecretRequest, PostSecretResponse, ReplyReservation,
+    ReserveSecretRequest, ReserveSecretResponse, SecretMetadata,
+};
+pub use server_config::{RestrictionFeatures, ServerConfig, ServerFeatures};
+pub use stats::{DailyStats, SizeBucket, StatsResponse};
+pub use token::{
+    CreateTokenRequest, CreateTokenResponse, TokenCountResponse, TokenInfoResponse, TokenScope,
+};
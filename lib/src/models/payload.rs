@@ -1,17 +1,120 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use serde::{Deserialize, Serialize};
-use zeroize::Zeroize;
+use std::fmt;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use ulid::Ulid;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::utils::hashing;
+
+/// Maximum length of the MessagePack header of an array or string (marker and 32 bit length).
+const MAX_HEADER_LEN: usize = 5;
+
+/// The kind of data a payload carries, as declared by its sender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadDataType {
+    /// A text message, expected to be UTF-8 (see [`Payload::as_text`]).
+    Text,
+
+    /// A file or data with a non-text content type.
+    Binary,
+}
 
 /// Represents the data payload of a secret, which can be either a text message
 /// or a file with optional metadata.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+///
+/// The payload is serialized as MessagePack array `[data, filename]`. If any metadata
+/// (content type or description) is set, it is appended: `[data, filename, content_type, description]`.
+/// An optional checksum is appended as fifth element: `[data, filename, content_type, description, checksum]`.
+/// A reply slot is appended as sixth element, after all other fields.
+/// This keeps payloads without metadata compatible with older clients.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 pub struct Payload {
-    /// The base64-encoded data of the secret.
+    /// The raw data of the secret.
     pub data: Vec<u8>,
 
     /// The filename of the file, if not set data is assumed to be a text message.
     pub filename: Option<String>,
+
+    /// The MIME content type of the data (e.g. "text/plain", "application/pdf").
+    #[serde(default)]
+    pub content_type: Option<String>,
+
+    /// A short description of the secret shown to the recipient before saving it.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The hex encoded SHA-256 digest of the data, used to verify integrity after decryption.
+    #[serde(default)]
+    pub checksum: Option<String>,
+
+    /// A reserved secret the recipient can reply with, if the sender allowed a reply.
+    #[serde(default)]
+    pub reply: Option<ReplySlot>,
+}
+
+/// A secret ID reserved by the sender for the reply of the recipient.
+///
+/// The sender keeps the key to open the reply, the recipient encrypts the reply with it and
+/// uploads it under the reserved ID. The upload token authorizes a single upload, the server
+/// only knows its hash.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReplySlot {
+    /// The ID reserved for the reply.
+    pub id: Ulid,
+
+    /// The key to encrypt the reply with, encoded as base64url.
+    pub key: String,
+
+    /// The token authorizing the upload of the reply.
+    pub upload_token: String,
+}
+
+impl ReplySlot {
+    /// Creates a new `ReplySlot`.
+    pub fn new(id: Ulid, key: String, upload_token: String) -> Self {
+        Self {
+            id,
+            key,
+            upload_token,
+        }
+    }
+
+    /// Returns an upper bound of the length of the serialized slot.
+    fn max_serialized_len(&self) -> usize {
+        let id_len = ulid::ULID_LEN + MAX_HEADER_LEN;
+        1 + MAX_HEADER_LEN
+            + id_len
+            + self.key.len()
+            + MAX_HEADER_LEN
+            + self.upload_token.len()
+            + MAX_HEADER_LEN
+    }
+}
+
+impl fmt::Debug for ReplySlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplySlot")
+            .field("id", &self.id)
+            .field("key", &"[REDACTED]")
+            .field("upload_token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl Zeroize for ReplySlot {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        self.upload_token.zeroize();
+    }
+}
+
+impl Drop for ReplySlot {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
 impl Payload {
@@ -28,6 +131,53 @@ impl Payload {
         Self {
             data: bytes.to_vec(),
             filename: None,
+            content_type: None,
+            description: None,
+            checksum: None,
+            reply: None,
+        }
+    }
+
+    /// Creates a new `Payload` taking ownership of the data, avoiding a copy of large secrets.
+    pub fn from_vec(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            filename: None,
+            content_type: None,
+            description: None,
+            checksum: None,
+            reply: None,
+        }
+    }
+
+    /// Moves the data out of the payload, leaving it empty.
+    ///
+    /// The data remains zeroized on drop, but is not copied as cloning `data` would.
+    pub fn take_data(&mut self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(std::mem::take(&mut self.data))
+    }
+
+    /// Consumes the payload and returns its data without copying it.
+    pub fn into_bytes(mut self) -> Zeroizing<Vec<u8>> {
+        self.take_data()
+    }
+
+    /// Returns the data as text, failing if it is not valid UTF-8.
+    pub fn as_text(&self) -> Result<&str, std::str::Utf8Error> {
+        std::str::from_utf8(&self.data)
+    }
+
+    /// Returns whether the payload is a text message or binary data.
+    ///
+    /// Payloads with a filename or a non-text content type are binary, all others are text
+    /// messages. The data itself is not inspected, so text messages may still fail
+    /// [`Payload::as_text`] if the sender did not send UTF-8.
+    pub fn data_type(&self) -> PayloadDataType {
+        let is_binary_content = self.content_type.is_some() && !self.is_text_content_type();
+        if self.filename.is_some() || is_binary_content {
+            PayloadDataType::Binary
+        } else {
+            PayloadDataType::Text
         }
     }
 
@@ -37,21 +187,147 @@ impl Payload {
         self
     }
 
+    /// Sets the MIME content type of the payload.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Sets a description of the payload for the recipient.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Embeds a reserved secret the recipient can reply with.
+    pub fn with_reply(mut self, reply: ReplySlot) -> Self {
+        self.reply = Some(reply);
+        self
+    }
+
+    /// Returns true if the content type indicates text data (e.g. "text/plain", "application/json").
+    pub fn is_text_content_type(&self) -> bool {
+        self.content_type.as_deref().is_some_and(|content_type| {
+            let mime = content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            mime.starts_with("text/")
+                || mime == "application/json"
+                || mime == "application/xml"
+                || mime.ends_with("+json")
+                || mime.ends_with("+xml")
+        })
+    }
+
+    /// Embeds the SHA-256 digest of the data into the payload.
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = Some(hashing::sha256_hex_from_bytes(&self.data));
+        self
+    }
+
+    /// Verifies the embedded checksum against the data.
+    ///
+    /// Returns true if no checksum is embedded, since older clients do not set one.
+    pub fn verify_checksum(&self) -> bool {
+        self.checksum.as_deref().is_none_or(|checksum| {
+            checksum.eq_ignore_ascii_case(&hashing::sha256_hex_from_bytes(&self.data))
+        })
+    }
+
+    fn field_count(&self) -> usize {
+        if self.reply.is_some() {
+            6
+        } else if self.checksum.is_some() {
+            5
+        } else if self.content_type.is_some() || self.description.is_some() {
+            4
+        } else {
+            2
+        }
+    }
+
     pub fn serialize(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
-        rmp_serde::to_vec(self)
+        let mut buffer = Vec::with_capacity(self.max_serialized_len());
+        self.serialize_into(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Appends the serialized payload to the buffer.
+    pub fn serialize_into(&self, buffer: &mut Vec<u8>) -> Result<(), rmp_serde::encode::Error> {
+        rmp_serde::encode::write(buffer, self)
     }
 
+    /// Returns an upper bound of the length of the serialized payload, used to allocate the
+    /// buffer for it up front instead of growing it repeatedly.
+    pub fn max_serialized_len(&self) -> usize {
+        // the data is encoded as array of integers, bytes above 127 take two bytes
+        let data_len: usize = self
+            .data
+            .iter()
+            .map(|b| if *b < 0x80 { 1 } else { 2 })
+            .sum();
+        let strings_len: usize = [
+            self.filename.as_deref(),
+            self.content_type.as_deref(),
+            self.description.as_deref(),
+            self.checksum.as_deref(),
+        ]
+        .into_iter()
+        .map(|s| s.map_or(1, |s| s.len() + MAX_HEADER_LEN))
+        .sum();
+        let reply_len = self.reply.as_ref().map_or(1, ReplySlot::max_serialized_len);
+
+        1 + MAX_HEADER_LEN + data_len + strings_len + reply_len
+    }
+
+    /// Deserializes the payload, ignoring any bytes after it (e.g. padding).
     pub fn deserialize(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
         rmp_serde::from_slice(bytes)
     }
 }
 
+impl Serialize for Payload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = self.field_count();
+
+        let mut state = serializer.serialize_struct("Payload", len)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("filename", &self.filename)?;
+        if len > 2 {
+            state.serialize_field("content_type", &self.content_type)?;
+            state.serialize_field("description", &self.description)?;
+        }
+        if len > 4 {
+            state.serialize_field("checksum", &self.checksum)?;
+        }
+        if len > 5 {
+            state.serialize_field("reply", &self.reply)?;
+        }
+        state.end()
+    }
+}
+
 impl Zeroize for Payload {
     fn zeroize(&mut self) {
         self.data.zeroize();
         if let Some(ref mut filename) = self.filename {
             filename.zeroize();
         }
+        if let Some(ref mut content_type) = self.content_type {
+            content_type.zeroize();
+        }
+        if let Some(ref mut description) = self.description {
+            description.zeroize();
+        }
+        if let Some(ref mut checksum) = self.checksum {
+            checksum.zeroize();
+        }
+        if let Some(ref mut reply) = self.reply {
+            reply.zeroize();
+        }
     }
 }
 
@@ -64,6 +340,7 @@ impl Drop for Payload {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use std::error::Error;
 
     type Result<T> = std::result::Result<T, Box<dyn Error>>;
@@ -77,6 +354,88 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_payload_from_vec() {
+        let payload = Payload::from_vec(b"Hello, world!".to_vec());
+        assert_eq!(payload, Payload::from_bytes(b"Hello, world!"));
+    }
+
+    #[test]
+    fn test_payload_take_data() {
+        let mut payload = Payload::from_bytes(b"secret").with_filename("secret.txt");
+
+        let data = payload.take_data();
+
+        assert_eq!(data.as_slice(), b"secret");
+        assert!(payload.data.is_empty(), "Data should be moved out");
+        assert_eq!(payload.filename.as_deref(), Some("secret.txt"));
+    }
+
+    #[test]
+    fn test_payload_into_bytes() {
+        let payload = Payload::from_bytes(b"secret").with_filename("secret.txt");
+        assert_eq!(payload.into_bytes().as_slice(), b"secret");
+    }
+
+    #[test]
+    fn test_payload_as_text() {
+        assert_eq!(
+            Payload::from_bytes("Grüße".as_bytes()).as_text(),
+            Ok("Grüße")
+        );
+        assert!(
+            Payload::from_bytes(&[0xff, 0xfe]).as_text().is_err(),
+            "Invalid UTF-8 should not be returned as text"
+        );
+    }
+
+    #[test]
+    fn test_payload_data_type() {
+        assert_eq!(
+            Payload::from_bytes(b"text").data_type(),
+            PayloadDataType::Text
+        );
+        assert_eq!(
+            Payload::from_bytes(b"{}")
+                .with_content_type("application/json")
+                .data_type(),
+            PayloadDataType::Text
+        );
+        assert_eq!(
+            Payload::from_bytes(b"text")
+                .with_filename("notes.txt")
+                .data_type(),
+            PayloadDataType::Binary,
+            "Files should be binary regardless of their content"
+        );
+        assert_eq!(
+            Payload::from_bytes(b"%PDF")
+                .with_content_type("application/pdf")
+                .data_type(),
+            PayloadDataType::Binary
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_max_serialized_len_is_upper_bound(
+            data in proptest::collection::vec(any::<u8>(), 0..1024),
+            filename in proptest::option::of(".{0,64}"),
+            description in proptest::option::of(".{0,300}"),
+            with_checksum in any::<bool>(),
+        ) {
+            let mut payload = Payload::from_vec(data);
+            payload.filename = filename;
+            payload.description = description;
+            if with_checksum {
+                payload = payload.with_checksum();
+            }
+
+            let serialized = payload.serialize().expect("serialization should succeed");
+            prop_assert!(serialized.len() <= payload.max_serialized_len());
+        }
+    }
+
     #[test]
     fn test_payload_serialization_roundtrip() -> Result<()> {
         let payload = Payload::from_bytes(b"test data").with_filename("test.txt");
@@ -230,4 +589,220 @@ mod tests {
             "serialized bytes should match expected msgpack format"
         );
     }
+
+    #[test]
+    fn test_serialize_with_metadata() {
+        let payload = Payload::from_bytes(b"ab")
+            .with_content_type("text/csv")
+            .with_description("d");
+
+        let serialized = payload.serialize().expect("serialization should succeed");
+
+        // MessagePack format: fixarray(4) + fixarray(2) + data + nil + fixstr(8) + "text/csv" + fixstr(1) + "d"
+        let expected: Vec<u8> = vec![
+            148, // fixarray with 4 elements
+            146, 97, 98,  // "ab"
+            192, // nil (None for filename)
+            168, 116, 101, 120, 116, 47, 99, 115, 118, // "text/csv"
+            161, 100, // "d"
+        ];
+        assert_eq!(
+            serialized, expected,
+            "serialized bytes should match expected msgpack format"
+        );
+    }
+
+    #[test]
+    fn test_payload_metadata_roundtrip() -> Result<()> {
+        let payload = Payload::from_bytes(b"%PDF")
+            .with_filename("report.pdf")
+            .with_content_type("application/pdf")
+            .with_description("Quarterly report");
+
+        let deserialized = Payload::deserialize(&payload.serialize()?)?;
+
+        assert_eq!(deserialized, payload);
+        Ok(())
+    }
+
+    #[test]
+    fn test_payload_description_only_roundtrip() -> Result<()> {
+        let payload = Payload::from_bytes(b"secret").with_description("Database password");
+
+        let deserialized = Payload::deserialize(&payload.serialize()?)?;
+
+        assert_eq!(deserialized.content_type, None);
+        assert_eq!(
+            deserialized.description,
+            Some("Database password".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_legacy_payload_without_metadata() -> Result<()> {
+        let legacy: Vec<u8> = vec![
+            146, // fixarray with 2 elements
+            146, 97, 98,  // "ab"
+            192, // nil (None for filename)
+        ];
+
+        let payload = Payload::deserialize(&legacy)?;
+
+        assert_eq!(payload.data, b"ab");
+        assert_eq!(payload.content_type, None);
+        assert_eq!(payload.description, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_ignores_trailing_padding() -> Result<()> {
+        let payload = Payload::from_bytes(b"hello").with_filename("test.txt");
+        let mut bytes = payload.serialize()?;
+        bytes.resize(1024, 0);
+
+        let deserialized = Payload::deserialize(&bytes)?;
+        assert_eq!(
+            deserialized, payload,
+            "Padding after the payload should be ignored"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_payload_checksum_roundtrip() -> Result<()> {
+        let payload = Payload::from_bytes(b"hello").with_checksum();
+        assert_eq!(
+            payload.checksum.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+
+        let deserialized = Payload::deserialize(&payload.serialize()?)?;
+
+        assert_eq!(deserialized, payload);
+        assert!(
+            deserialized.verify_checksum(),
+            "checksum should match after roundtrip"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_payload_reply_roundtrip() -> Result<()> {
+        let reply = ReplySlot::new(Ulid::r#gen(), "a2V5".to_string(), "dG9rZW4".to_string());
+        let payload = Payload::from_bytes(b"hello").with_reply(reply.clone());
+
+        let serialized = payload.serialize()?;
+        assert!(serialized.len() <= payload.max_serialized_len());
+        assert_eq!(serialized[0], 0x96, "Reply should be the sixth element");
+
+        let deserialized = Payload::deserialize(&serialized)?;
+        assert_eq!(deserialized.reply, Some(reply));
+        assert_eq!(deserialized.checksum, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_slot_debug_is_redacted() {
+        let reply = ReplySlot::new(Ulid::r#gen(), "a2V5".to_string(), "dG9rZW4".to_string());
+
+        let debug = format!("{reply:?}");
+        assert!(!debug.contains("a2V5"), "Key should be redacted");
+        assert!(
+            !debug.contains("dG9rZW4"),
+            "Upload token should be redacted"
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum_mismatch() {
+        let mut payload = Payload::from_bytes(b"hello").with_checksum();
+        payload.data = b"tampered".to_vec();
+
+        assert!(
+            !payload.verify_checksum(),
+            "checksum should not match modified data"
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum_missing() {
+        let payload = Payload::from_bytes(b"hello");
+        assert!(
+            payload.verify_checksum(),
+            "payload without checksum should be accepted"
+        );
+    }
+
+    #[test]
+    fn test_is_text_content_type() {
+        let text_types = [
+            "text/plain",
+            "text/csv; charset=utf-8",
+            "application/json",
+            "application/ld+json",
+            "Text/HTML",
+        ];
+        for content_type in text_types {
+            let payload = Payload::from_bytes(b"").with_content_type(content_type);
+            assert!(
+                payload.is_text_content_type(),
+                "{content_type} should be detected as text"
+            );
+        }
+
+        let binary_types = ["application/pdf", "image/png", "application/octet-stream"];
+        for content_type in binary_types {
+            let payload = Payload::from_bytes(b"").with_content_type(content_type);
+            assert!(
+                !payload.is_text_content_type(),
+                "{content_type} should not be detected as text"
+            );
+        }
+
+        assert!(
+            !Payload::from_bytes(b"").is_text_content_type(),
+            "missing content type should not be detected as text"
+        );
+    }
+
+    #[test]
+    fn test_payload_zeroize_metadata() {
+        let mut payload = Payload::from_bytes(b"data")
+            .with_content_type("text/plain")
+            .with_description("description");
+
+        payload.zeroize();
+
+        assert_eq!(payload.content_type, Some("".to_string()));
+        assert_eq!(payload.description, Some("".to_string()));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_deserialize_arbitrary_bytes_does_not_panic(
+            bytes in proptest::collection::vec(any::<u8>(), 0..1024)
+        ) {
+            let _ = Payload::deserialize(&bytes);
+        }
+
+        #[test]
+        fn prop_serialize_roundtrip(
+            data in proptest::collection::vec(any::<u8>(), 0..1024),
+            filename in proptest::option::of(".{0,64}"),
+            description in proptest::option::of(".{0,64}"),
+            padding_len in 0usize..64,
+        ) {
+            let mut payload = Payload::from_bytes(&data);
+            payload.filename = filename;
+            payload.description = description;
+
+            let mut serialized = payload.serialize().expect("serialization should succeed");
+            serialized.resize(serialized.len() + padding_len, 0);
+
+            let deserialized =
+                Payload::deserialize(&serialized).expect("deserialization should succeed");
+            prop_assert_eq!(deserialized, payload);
+        }
+    }
 }
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Response model for a purge request without confirmation via admin API
+///
+/// Nothing is deleted yet, the confirmation token has to be passed to a second request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PurgePreviewResponse {
+    /// Number of secrets which would be deleted
+    pub secrets: u64,
+    /// Token confirming the purge, bound to the admin token and the request parameters
+    pub confirmation_token: String,
+    /// Seconds until the confirmation token expires
+    pub expires_in_seconds: u64,
+}
+
+/// Response model for a confirmed purge request via admin API
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PurgeResponse {
+    /// Number of secrets which were deleted
+    pub purged: u64,
+}
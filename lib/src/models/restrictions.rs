@@ -8,6 +8,8 @@ use super::CountryCode;
 use crate::utils::hashing;
 
 pub const PASSPHRASE_HEADER_NAME: &str = "X-Secret-Passphrase";
+pub const RESTRICTIONS_HEADER_NAME: &str = "X-Secret-Restrictions";
+pub const PIN_HEADER_NAME: &str = "X-Secret-PIN";
 
 /// Represents access restrictions for a secret.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
@@ -25,6 +27,23 @@ pub struct SecretRestrictions {
 
     /// Optional passphrase hash for additional security
     pub passphrase_hash: Option<String>,
+
+    /// Salt of the passphrase hash, set by the server when storing the restrictions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase_salt: Option<String>,
+
+    /// Recipient of the retrieval PIN generated by the server (e.g. a phone number), passed to
+    /// the configured PIN delivery and never stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_recipient: Option<String>,
+
+    /// Hash of the retrieval PIN, set by the server when storing the restrictions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_hash: Option<String>,
+
+    /// Salt of the PIN hash, set by the server when storing the restrictions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_salt: Option<String>,
 }
 
 impl SecretRestrictions {
@@ -53,6 +72,18 @@ impl SecretRestrictions {
         self
     }
 
+    /// Requests a retrieval PIN, which the server delivers to the recipient out-of-band
+    pub fn with_pin_recipient(mut self, recipient: &str) -> Self {
+        self.pin_recipient = Some(recipient.to_string());
+        self
+    }
+
+    /// Checks if retrieving the secret requires a PIN
+    pub fn requires_pin(&self) -> bool {
+        let has = |v: &Option<String>| v.as_ref().is_some_and(|v| !v.is_empty());
+        has(&self.pin_recipient) || has(&self.pin_hash)
+    }
+
     /// Checks if any restrictions are set
     pub fn is_empty(&self) -> bool {
         let any_ips = self.allowed_ips.as_ref().is_some_and(|v| !v.is_empty());
@@ -77,6 +108,10 @@ impl SecretRestrictions {
             return false;
         }
 
+        if self.requires_pin() {
+            return false;
+        }
+
         true
     }
 }
@@ -109,6 +144,10 @@ impl Display for SecretRestrictions {
             write!(f, "Passphrase: ***")?;
         }
 
+        if self.requires_pin() {
+            write!(f, "PIN: ***")?;
+        }
+
         Ok(())
     }
 }
@@ -117,6 +156,7 @@ impl Display for SecretRestrictions {
 mod tests {
     use super::*;
     use ipnet::IpNet;
+    use proptest::prelude::*;
 
     use crate::models::CountryCode;
     use crate::utils::test::MustParse;
@@ -599,8 +639,10 @@ mod tests {
 
     #[test]
     fn test_is_empty_with_empty_string_passphrase_hash() {
-        let mut restrictions = SecretRestrictions::default();
-        restrictions.passphrase_hash = Some(String::new());
+        let restrictions = SecretRestrictions {
+            passphrase_hash: Some(String::new()),
+            ..Default::default()
+        };
         assert!(
             restrictions.is_empty(),
             "Empty string passphrase hash should be considered empty"
@@ -609,8 +651,10 @@ mod tests {
 
     #[test]
     fn test_is_empty_with_whitespace_passphrase_hash() {
-        let mut restrictions = SecretRestrictions::default();
-        restrictions.passphrase_hash = Some("   ".to_string());
+        let restrictions = SecretRestrictions {
+            passphrase_hash: Some("   ".to_string()),
+            ..Default::default()
+        };
         assert!(
             !restrictions.is_empty(),
             "Whitespace passphrase hash should not be considered empty"
@@ -651,6 +695,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_pin_recipient() {
+        let restrictions = SecretRestrictions::default().with_pin_recipient("+49123456789");
+
+        assert!(restrictions.requires_pin());
+        assert!(
+            !restrictions.is_empty(),
+            "PIN recipient should not be considered empty"
+        );
+
+        let display = restrictions.to_string();
+        assert!(
+            display.contains("PIN: ***"),
+            "Display should show masked PIN"
+        );
+        assert!(
+            !display.contains("+49123456789"),
+            "Display should not show the PIN recipient"
+        );
+    }
+
+    #[test]
+    fn test_requires_pin_with_stored_hash() {
+        let restrictions = SecretRestrictions {
+            pin_hash: Some("hash".to_string()),
+            ..Default::default()
+        };
+        assert!(restrictions.requires_pin());
+
+        let restrictions = SecretRestrictions {
+            pin_recipient: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(
+            !restrictions.requires_pin(),
+            "Empty recipient should be ignored"
+        );
+        assert!(restrictions.is_empty());
+    }
+
+    #[test]
+    fn test_serialization_without_pin() {
+        let serialized =
+            serde_json::to_string(&SecretRestrictions::default()).expect("Failed to serialize");
+
+        assert!(
+            !serialized.contains("pin_"),
+            "Unset PIN fields should be omitted: {serialized}"
+        );
+    }
+
     #[test]
     fn test_serialization_with_passphrase() {
         let restrictions = SecretRestrictions::default().with_passphrase(b"test");
@@ -713,4 +808,42 @@ mod tests {
             "Deserialized passphrase hash should match expected value"
         );
     }
+
+    proptest! {
+        #[test]
+        fn prop_deserialize_arbitrary_json_does_not_panic(json in ".{0,256}") {
+            let _ = serde_json::from_str::<SecretRestrictions>(&json);
+        }
+
+        #[test]
+        fn prop_deserialize_malformed_fields_does_not_panic(
+            ips in proptest::collection::vec(".{0,48}", 0..8),
+            countries in proptest::collection::vec(".{0,4}", 0..8),
+            asns in proptest::collection::vec(any::<i64>(), 0..8),
+        ) {
+            let json = serde_json::json!({
+                "allowed_ips": ips,
+                "allowed_countries": countries,
+                "allowed_asns": asns,
+            });
+            let _ = serde_json::from_value::<SecretRestrictions>(json);
+        }
+
+        #[test]
+        fn prop_serialize_roundtrip(
+            asns in proptest::option::of(proptest::collection::vec(any::<u32>(), 0..8)),
+            passphrase_hash in proptest::option::of("[0-9a-f]{64}"),
+        ) {
+            let restrictions = SecretRestrictions {
+                allowed_asns: asns,
+                passphrase_hash,
+                ..Default::default()
+            };
+
+            let json = serde_json::to_string(&restrictions).expect("Failed to serialize");
+            let deserialized: SecretRestrictions =
+                serde_json::from_str(&json).expect("Failed to parse JSON");
+            prop_assert_eq!(deserialized, restrictions);
+        }
+    }
 }
@@ -8,6 +8,27 @@ use ulid::Ulid;
 
 use super::restrictions::SecretRestrictions;
 
+/// Header carrying the TTL in seconds of secrets uploaded as binary.
+pub const TTL_HEADER_NAME: &str = "X-Secret-TTL";
+
+/// Header requesting a short alias for secrets uploaded as binary.
+pub const SHORT_ALIAS_HEADER_NAME: &str = "X-Secret-Short-Alias";
+
+/// Header carrying the client proposed ID of secrets uploaded as binary.
+pub const ID_HEADER_NAME: &str = "X-Secret-Id";
+
+/// Header declaring secrets uploaded as binary as files.
+pub const FILE_HEADER_NAME: &str = "X-Secret-File";
+
+/// Header carrying the upload token of a reserved secret ID.
+pub const UPLOAD_TOKEN_HEADER_NAME: &str = "X-Secret-Upload-Token";
+
+/// Header carrying the reply reservation (JSON) of secrets uploaded as binary.
+pub const REPLY_HEADER_NAME: &str = "X-Secret-Reply";
+
+/// Smallest size class reported in the metadata of secrets.
+pub const MIN_SIZE_CLASS: u64 = 1024; // 1 KB
+
 /// Represents the request to create a new secret.
 #[serde_as]
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -22,6 +43,22 @@ pub struct PostSecretRequest {
     /// Access restrictions for the secret
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restrictions: Option<SecretRestrictions>,
+
+    /// Whether a short human-friendly alias should be minted for the secret.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub short_alias: bool,
+
+    /// ID proposed by the client, which bound the ciphertext to it before uploading.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Ulid>,
+
+    /// Whether the sender declared the secret as file, shown to the recipient before retrieval.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub file: bool,
+
+    /// ID to reserve for the reply of the recipient, together with the secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply: Option<ReplyReservation>,
 }
 
 impl PostSecretRequest {
@@ -36,6 +73,10 @@ impl PostSecretRequest {
             data,
             expires_in,
             restrictions: None,
+            short_alias: false,
+            id: None,
+            file: false,
+            reply: None,
         }
     }
 
@@ -44,6 +85,54 @@ impl PostSecretRequest {
         self.restrictions = Some(restrictions);
         self
     }
+
+    /// Requests a short human-friendly alias for the secret (if supported by the server)
+    pub fn with_short_alias(mut self) -> Self {
+        self.short_alias = true;
+        self
+    }
+
+    /// Proposes the ID of the secret (if supported by the server)
+    pub fn with_id(mut self, id: Ulid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Declares the secret as file in its metadata (if supported by the server)
+    pub fn with_file(mut self) -> Self {
+        self.file = true;
+        self
+    }
+
+    /// Reserves an ID for the reply of the recipient (if supported by the server)
+    pub fn with_reply(mut self, reply: ReplyReservation) -> Self {
+        self.reply = Some(reply);
+        self
+    }
+}
+
+/// Reservation of the ID of a reply, requested by the sender together with the secret.
+///
+/// The sender generates the ID and the upload token and passes both to the recipient inside
+/// the encrypted payload. The server only learns the SHA-256 hash of the token, so it cannot
+/// upload a reply itself.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReplyReservation {
+    /// The ID reserved for the reply.
+    pub id: Ulid,
+
+    /// The hex encoded SHA-256 hash of the upload token.
+    pub upload_token_hash: String,
+}
+
+impl ReplyReservation {
+    /// Creates a new `ReplyReservation`.
+    pub fn new(id: Ulid, upload_token_hash: String) -> Self {
+        Self {
+            id,
+            upload_token_hash,
+        }
+    }
 }
 
 /// Represents the response after creating a new secret.
@@ -51,6 +140,143 @@ impl PostSecretRequest {
 pub struct PostSecretResponse {
     /// The unique identifier of the created secret.
     pub id: Ulid,
+
+    /// Short human-friendly alias of the secret, if requested and supported by the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// Unix timestamp (seconds) at which the secret expires, not reported by older servers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+
+    /// Restrictions applied by the server, including those enforced by the scope of the token.
+    ///
+    /// The passphrase is never returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_restrictions: Option<SecretRestrictions>,
+
+    /// Size class of the stored ciphertext (see [`size_class`]), not reported by older servers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_class: Option<u64>,
+
+    /// ID reserved for the reply, if requested and supported by the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_id: Option<Ulid>,
+}
+
+/// Represents the request to reserve the ID of a secret uploaded later.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReserveSecretRequest {
+    /// The duration until the reservation expires if no secret is uploaded.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub expires_in: Duration,
+}
+
+impl ReserveSecretRequest {
+    /// Creates a new `ReserveSecretRequest`.
+    pub fn new(expires_in: Duration) -> Self {
+        Self { expires_in }
+    }
+}
+
+/// Represents the response after reserving the ID of a secret.
+///
+/// The secret is uploaded with the ID as proposed ID and the upload token in the
+/// [`UPLOAD_TOKEN_HEADER_NAME`] header. Until then, requests for the secret are answered with
+/// `425 Too Early`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ReserveSecretResponse {
+    /// The reserved ID of the secret.
+    pub id: Ulid,
+
+    /// The token authorizing the upload of the secret, valid for a single upload.
+    pub upload_token: String,
+
+    /// Unix timestamp (seconds) at which the reservation expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl ReserveSecretResponse {
+    /// Creates a new `ReserveSecretResponse`.
+    pub fn new(id: Ulid, upload_token: String) -> Self {
+        Self {
+            id,
+            upload_token,
+            expires_at: None,
+        }
+    }
+
+    /// Sets the Unix timestamp (seconds) at which the reservation expires.
+    pub fn with_expires_at(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+/// Non-sensitive metadata of a secret, available before the one-time retrieval.
+///
+/// Lets recipients decide whether to retrieve the secret now, e.g. because it is a large file or
+/// requires a passphrase they still have to ask for.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SecretMetadata {
+    /// Whether the sender declared the secret as file.
+    pub file: bool,
+
+    /// Size of the ciphertext in bytes, rounded up to the next power of two.
+    pub size_class: u64,
+
+    /// Whether a passphrase is required to retrieve the secret.
+    pub has_passphrase: bool,
+}
+
+impl SecretMetadata {
+    /// Creates the metadata of a secret with the given ciphertext size in bytes.
+    pub fn new(ciphertext_size: u64) -> Self {
+        Self {
+            file: false,
+            size_class: size_class(ciphertext_size),
+            has_passphrase: false,
+        }
+    }
+
+    /// Sets whether the secret was declared as file.
+    pub fn with_file(mut self, file: bool) -> Self {
+        self.file = file;
+        self
+    }
+
+    /// Sets whether a passphrase is required.
+    pub fn with_passphrase(mut self, has_passphrase: bool) -> Self {
+        self.has_passphrase = has_passphrase;
+        self
+    }
+}
+
+/// Rounds the size up to the next power of two (at least [`MIN_SIZE_CLASS`]), so the metadata
+/// doesn't reveal the exact size of the secret.
+pub fn size_class(size: u64) -> u64 {
+    size.max(MIN_SIZE_CLASS)
+        .checked_next_power_of_two()
+        .unwrap_or(u64::MAX)
+}
+
+/// Represents an outstanding secret created by the requesting user.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OwnedSecret {
+    /// The unique identifier of the secret.
+    pub id: Ulid,
+
+    /// Remaining time in seconds until the secret expires.
+    pub ttl_remaining_seconds: u64,
+}
+
+/// Represents the response listing the outstanding secrets of the requesting user.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OwnedSecretsResponse {
+    /// Secrets not retrieved or expired yet, ordered by expiration.
+    pub secrets: Vec<OwnedSecret>,
 }
 
 impl PostSecretResponse {
@@ -60,6 +286,195 @@ impl PostSecretResponse {
     ///
     /// * `id` - The unique identifier of the secret.
     pub fn new(id: Ulid) -> Self {
-        Self { id }
+        Self {
+            id,
+            alias: None,
+            expires_at: None,
+            effective_restrictions: None,
+            size_class: None,
+            reply_id: None,
+        }
+    }
+
+    /// Sets the short alias of the secret.
+    pub fn with_alias(mut self, alias: String) -> Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    /// Sets the Unix timestamp (seconds) at which the secret expires.
+    pub fn with_expires_at(mut self, expires_at: u64) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Sets the restrictions applied by the server, the passphrase and PIN are removed.
+    pub fn with_effective_restrictions(mut self, restrictions: &SecretRestrictions) -> Self {
+        let mut restrictions = restrictions.clone();
+        restrictions.passphrase_hash = None;
+        restrictions.passphrase_salt = None;
+        restrictions.pin_hash = None;
+        restrictions.pin_salt = None;
+        self.effective_restrictions = Some(restrictions).filter(|r| !r.is_empty());
+        self
+    }
+
+    /// Sets the size class of the stored ciphertext.
+    pub fn with_size_class(mut self, size_class: u64) -> Self {
+        self.size_class = Some(size_class);
+        self
+    }
+
+    /// Sets the ID reserved for the reply.
+    pub fn with_reply_id(mut self, reply_id: Ulid) -> Self {
+        self.reply_id = Some(reply_id);
+        self
+    }
+
+    /// Returns the identifier to use in secret links, the alias if available.
+    pub fn link_id(&self) -> String {
+        self.alias.clone().unwrap_or_else(|| self.id.to_string())
+    }
+}
+
+/// Checks if the value is a short secret alias like `blue-otter-42`.
+///
+/// Aliases consist of lowercase words followed by a number, separated by dashes.
+pub fn is_secret_alias(value: &str) -> bool {
+    let mut parts: Vec<&str> = value.split('-').collect();
+    let Some(number) = parts.pop() else {
+        return false;
+    };
+
+    !parts.is_empty()
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+        && parts
+            .iter()
+            .all(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// Checks if the value identifies a secret, either by its ULID or by a short alias.
+pub fn is_valid_secret_id(value: &str) -> bool {
+    Ulid::from_string(value).is_ok() || is_secret_alias(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_secret_alias() {
+        assert!(is_secret_alias("blue-otter-42"));
+        assert!(is_secret_alias("otter-7"));
+
+        for invalid in [
+            "",
+            "42",
+            "blue-otter",
+            "Blue-otter-42",
+            "blue--otter-42",
+            "blue-otter-4a",
+        ] {
+            assert!(
+                !is_secret_alias(invalid),
+                "'{invalid}' should not be an alias"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_valid_secret_id() {
+        assert!(is_valid_secret_id(&Ulid::r#gen().to_string()));
+        assert!(is_valid_secret_id("blue-otter-42"));
+        assert!(
+            !is_valid_secret_id("../admin"),
+            "Path traversal should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_size_class() {
+        assert_eq!(size_class(0), MIN_SIZE_CLASS);
+        assert_eq!(size_class(1024), 1024);
+        assert_eq!(size_class(1025), 2048);
+        assert_eq!(size_class(3 * 1024 * 1024), 4 * 1024 * 1024);
+        assert_eq!(size_class(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_short_alias_serialization() -> Result<(), serde_json::Error> {
+        let req = PostSecretRequest::new("data".to_string(), Duration::from_secs(60));
+        let json = serde_json::to_string(&req)?;
+        assert!(
+            !json.contains("short_alias"),
+            "Flag should be omitted by default"
+        );
+
+        let json = serde_json::to_string(&req.with_short_alias())?;
+        let req: PostSecretRequest = serde_json::from_str(&json)?;
+        assert!(req.short_alias, "Flag should survive a roundtrip");
+
+        let res: PostSecretResponse =
+            serde_json::from_str(r#"{"id":"01ARZ3NDEKTSV4RRFFQ69G5FAV"}"#)?;
+        assert_eq!(res.alias, None, "Responses of older servers have no alias");
+        assert_eq!(res.link_id(), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+        assert_eq!(res.expires_at, None);
+        assert_eq!(res.effective_restrictions, None);
+        assert_eq!(res.size_class, None);
+        assert_eq!(res.reply_id, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_effective_restrictions_omit_passphrase() {
+        let restrictions = SecretRestrictions::default()
+            .with_allowed_asns(vec![64496])
+            .with_passphrase(b"secret");
+
+        let res = PostSecretResponse::new(Ulid::r#gen()).with_effective_restrictions(&restrictions);
+        let effective = res
+            .effective_restrictions
+            .expect("Restrictions should be set");
+        assert_eq!(effective.allowed_asns, Some(vec![64496]));
+        assert_eq!(
+            effective.passphrase_hash, None,
+            "Passphrase should be omitted"
+        );
+
+        let passphrase_only = SecretRestrictions::default().with_passphrase(b"secret");
+        let res =
+            PostSecretResponse::new(Ulid::r#gen()).with_effective_restrictions(&passphrase_only);
+        assert_eq!(res.effective_restrictions, None);
+    }
+
+    #[test]
+    fn test_reserve_secret_serialization() -> Result<(), serde_json::Error> {
+        let req: ReserveSecretRequest = serde_json::from_str(r#"{"expires_in":3600}"#)?;
+        assert_eq!(req, ReserveSecretRequest::new(Duration::from_secs(3600)));
+
+        let res = ReserveSecretResponse::new(Ulid::r#gen(), "token".to_string());
+        let json = serde_json::to_string(&res)?;
+        assert!(
+            !json.contains("expires_at"),
+            "Expiry should be omitted if not set"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reply_serialization() -> Result<(), serde_json::Error> {
+        let req = PostSecretRequest::new("data".to_string(), Duration::from_secs(60));
+        let json = serde_json::to_string(&req)?;
+        assert!(
+            !json.contains("reply"),
+            "Reply should be omitted by default"
+        );
+
+        let reply = ReplyReservation::new(Ulid::r#gen(), "ab".repeat(32));
+        let json = serde_json::to_string(&req.with_reply(reply.clone()))?;
+        let req: PostSecretRequest = serde_json::from_str(&json)?;
+        assert_eq!(req.reply, Some(reply), "Reply should survive a roundtrip");
+        Ok(())
     }
 }
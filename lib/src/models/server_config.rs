@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::restrictions::SecretRestrictions;
+
+/// Capabilities and limits advertised by a Hakanai server via `/config.json`.
+#[serde_as]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerConfig {
+    /// Whether the web UI should display the token input.
+    #[serde(default)]
+    pub show_token_input: bool,
+
+    /// Maximum secret size in bytes for the requesting client (0 if uploads require a token).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_size_limit: Option<u64>,
+
+    /// Minimum TTL accepted by the server (not reported by older servers).
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ttl: Option<Duration>,
+
+    /// Maximum TTL accepted by the server.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ttl: Option<Duration>,
+
+    /// TTL presets offered by the server, all of them are accepted.
+    #[serde_as(as = "Vec<serde_with::DurationSeconds<u64>>")]
+    #[serde(default)]
+    pub ttl_presets: Vec<Duration>,
+
+    /// Whether secrets can be created without a token (not reported by older servers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anonymous_allowed: Option<bool>,
+
+    /// API versions supported by the server.
+    #[serde(default)]
+    pub api_versions: Vec<String>,
+
+    /// Optional features enabled on the server.
+    #[serde(default)]
+    pub features: ServerFeatures,
+
+    /// Version of the server (not reported by older servers).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Optional features enabled on a Hakanai server.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerFeatures {
+    /// Whether an impressum page is available.
+    #[serde(default)]
+    pub impressum: bool,
+
+    /// Whether a privacy policy page is available.
+    #[serde(default)]
+    pub privacy: bool,
+
+    /// Restriction types supported by the server.
+    #[serde(default)]
+    pub restrictions: RestrictionFeatures,
+
+    /// Whether secrets can be uploaded as binary via `POST /api/v1/secret/raw`.
+    #[serde(default)]
+    pub raw_upload: bool,
+
+    /// Whether secrets can be uploaded as multipart form via `POST /api/v1/secret/multipart`.
+    #[serde(default)]
+    pub multipart_upload: bool,
+
+    /// Whether short human-friendly aliases can be requested for secret links.
+    #[serde(default)]
+    pub short_aliases: bool,
+
+    /// Whether clients can propose the ID of new secrets, so the ciphertext can be bound to it.
+    #[serde(default)]
+    pub client_ids: bool,
+
+    /// Whether non-sensitive secret metadata is available via `GET /api/v1/secret/{id}/meta`.
+    #[serde(default)]
+    pub secret_metadata: bool,
+
+    /// Whether an ID for the reply of the recipient can be reserved together with a secret.
+    #[serde(default)]
+    pub replies: bool,
+}
+
+/// Restriction types supported by a Hakanai server.
+///
+/// IP and passphrase restrictions are always supported, country and ASN restrictions
+/// require the server to be configured with the corresponding headers, PINs with a PIN
+/// delivery.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestrictionFeatures {
+    /// Whether country restrictions are supported.
+    #[serde(default)]
+    pub country: bool,
+
+    /// Whether ASN restrictions are supported.
+    #[serde(default)]
+    pub asn: bool,
+
+    /// Whether retrieval PINs delivered out-of-band are supported.
+    #[serde(default)]
+    pub pin: bool,
+}
+
+impl ServerConfig {
+    /// Checks whether all restrictions in `restrictions` are supported by the server.
+    ///
+    /// Returns the name of the first unsupported restriction type, if any.
+    pub fn unsupported_restriction(
+        &self,
+        restrictions: &SecretRestrictions,
+    ) -> Option<&'static str> {
+        if restrictions.allowed_countries.is_some() && !self.features.restrictions.country {
+            return Some("country");
+        }
+
+        if restrictions.allowed_asns.is_some() && !self.features.restrictions.asn {
+            return Some("ASN");
+        }
+
+        if restrictions.requires_pin() && !self.features.restrictions.pin {
+            return Some("PIN");
+        }
+
+        None
+    }
+
+    /// Checks whether the TTL is accepted by the server.
+    ///
+    /// Servers not reporting a minimum or maximum TTL are assumed to accept any TTL.
+    pub fn is_ttl_allowed(&self, ttl: Duration) -> bool {
+        self.min_ttl.is_none_or(|min_ttl| ttl >= min_ttl)
+            && self.max_ttl.is_none_or(|max_ttl| ttl <= max_ttl)
+    }
+
+    /// Checks whether a secret of the given size in bytes fits the size limit advertised for
+    /// requests without a token.
+    ///
+    /// Servers not reporting a size limit are assumed to accept any size.
+    pub fn is_size_allowed(&self, size: u64) -> bool {
+        self.secret_size_limit.is_none_or(|limit| size <= limit)
+    }
+
+    /// Checks whether the server supports the given API version (e.g. "v1").
+    ///
+    /// Servers not reporting any API versions are assumed to support v1 only.
+    pub fn supports_api_version(&self, version: &str) -> bool {
+        if self.api_versions.is_empty() {
+            return version == "v1";
+        }
+
+        self.api_versions.iter().any(|v| v == version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    use crate::utils::test::MustParse;
+
+    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+    #[test]
+    fn test_deserialize_full_config() -> Result<()> {
+        let json = r#"{
+            "showTokenInput": true,
+            "features": {
+                "impressum": true,
+                "privacy": false,
+                "restrictions": { "country": true, "asn": false },
+                "rawUpload": true,
+                "multipartUpload": true,
+                "shortAliases": true
+            },
+            "secretSizeLimit": 32768,
+            "minTtl": 300,
+            "maxTtl": 604800,
+            "ttlPresets": [300, 3600],
+            "anonymousAllowed": true,
+            "apiVersions": ["v1"],
+            "version": "3.1.0"
+        }"#;
+
+        let config: ServerConfig = serde_json::from_str(json)?;
+        assert!(config.show_token_input, "showTokenInput should be parsed");
+        assert_eq!(config.secret_size_limit, Some(32768));
+        assert_eq!(config.min_ttl, Some(Duration::from_secs(300)));
+        assert_eq!(config.max_ttl, Some(Duration::from_secs(604800)));
+        assert_eq!(
+            config.ttl_presets,
+            vec![Duration::from_secs(300), Duration::from_secs(3600)]
+        );
+        assert_eq!(config.anonymous_allowed, Some(true));
+        assert_eq!(config.api_versions, vec!["v1".to_string()]);
+        assert_eq!(config.version.as_deref(), Some("3.1.0"));
+        assert!(config.features.impressum, "impressum should be enabled");
+        assert!(!config.features.privacy, "privacy should be disabled");
+        assert!(
+            config.features.restrictions.country,
+            "country should be enabled"
+        );
+        assert!(!config.features.restrictions.asn, "asn should be disabled");
+        assert!(config.features.raw_upload, "raw upload should be enabled");
+        assert!(
+            config.features.multipart_upload,
+            "multipart upload should be enabled"
+        );
+        assert!(
+            config.features.short_aliases,
+            "short aliases should be enabled"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_minimal_config() -> Result<()> {
+        let config: ServerConfig = serde_json::from_str(r#"{"showTokenInput": false}"#)?;
+        assert_eq!(config, ServerConfig::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_restriction_none() {
+        let config = ServerConfig::default();
+        let restrictions = SecretRestrictions::default()
+            .with_allowed_ips(vec!["10.0.0.0/8".must_parse()])
+            .with_passphrase(b"secret");
+
+        assert_eq!(config.unsupported_restriction(&restrictions), None);
+    }
+
+    #[test]
+    fn test_unsupported_restriction_country() {
+        let config = ServerConfig::default();
+        let restrictions =
+            SecretRestrictions::default().with_allowed_countries(vec!["DE".must_parse()]);
+
+        assert_eq!(
+            config.unsupported_restriction(&restrictions),
+            Some("country")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_restriction_asn() {
+        let mut config = ServerConfig::default();
+        config.features.restrictions.country = true;
+        let restrictions = SecretRestrictions::default()
+            .with_allowed_countries(vec!["DE".must_parse()])
+            .with_allowed_asns(vec![202739]);
+
+        assert_eq!(config.unsupported_restriction(&restrictions), Some("ASN"));
+    }
+
+    #[test]
+    fn test_unsupported_restriction_pin() {
+        let mut config = ServerConfig::default();
+        let restrictions = SecretRestrictions::default().with_pin_recipient("+49123456789");
+
+        assert_eq!(config.unsupported_restriction(&restrictions), Some("PIN"));
+
+        config.features.restrictions.pin = true;
+        assert_eq!(config.unsupported_restriction(&restrictions), None);
+    }
+
+    #[test]
+    fn test_is_ttl_allowed() {
+        let mut config = ServerConfig::default();
+        assert!(
+            config.is_ttl_allowed(Duration::from_secs(u64::MAX)),
+            "Any TTL should be allowed without max TTL"
+        );
+
+        config.max_ttl = Some(Duration::from_secs(3600));
+        assert!(
+            config.is_ttl_allowed(Duration::from_secs(3600)),
+            "TTL equal to max TTL should be allowed"
+        );
+        assert!(
+            !config.is_ttl_allowed(Duration::from_secs(3601)),
+            "TTL exceeding max TTL should not be allowed"
+        );
+
+        config.min_ttl = Some(Duration::from_secs(300));
+        assert!(
+            config.is_ttl_allowed(Duration::from_secs(300)),
+            "TTL equal to min TTL should be allowed"
+        );
+        assert!(
+            !config.is_ttl_allowed(Duration::from_secs(299)),
+            "TTL below min TTL should not be allowed"
+        );
+    }
+
+    #[test]
+    fn test_is_size_allowed() {
+        let mut config = ServerConfig::default();
+        assert!(
+            config.is_size_allowed(u64::MAX),
+            "Any size should be allowed without size limit"
+        );
+
+        config.secret_size_limit = Some(1024);
+        assert!(
+            config.is_size_allowed(1024),
+            "Size equal to the limit should be allowed"
+        );
+        assert!(
+            !config.is_size_allowed(1025),
+            "Size exceeding the limit should not be allowed"
+        );
+    }
+
+    #[test]
+    fn test_supports_api_version() {
+        let mut config = ServerConfig::default();
+        assert!(config.supports_api_version("v1"), "v1 should be assumed");
+        assert!(
+            !config.supports_api_version("v2"),
+            "v2 should not be assumed"
+        );
+
+        config.api_versions = vec!["v1".to_string(), "v2".to_string()];
+        assert!(config.supports_api_version("v2"), "v2 should be supported");
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Response model for aggregated secret statistics via admin API
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsResponse {
+    /// Number of secrets which are neither retrieved nor expired
+    pub active_secrets: u64,
+    /// Number of secrets which have been retrieved
+    pub retrieved_secrets: u64,
+    /// Number of secrets which expired without being retrieved
+    pub expired_secrets: u64,
+    /// Secrets created, retrieved and expired per day (UTC), ordered by date
+    pub days: Vec<DailyStats>,
+    /// Distribution of secret sizes (only secrets with recorded size)
+    pub size_histogram: Vec<SizeBucket>,
+}
+
+/// Number of secrets created, retrieved and expired on a single day (UTC)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyStats {
+    /// Date in format YYYY-MM-DD
+    pub date: String,
+    pub created: u64,
+    pub retrieved: u64,
+    pub expired: u64,
+}
+
+/// Number of secrets with a size up to the upper bound of the bucket
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SizeBucket {
+    /// Upper bound (inclusive) in bytes, `None` for all larger secrets
+    pub max_size: Option<u64>,
+    pub count: u64,
+}
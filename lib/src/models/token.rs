@@ -13,6 +13,9 @@ pub struct CreateTokenRequest {
     /// Wether it is a one time use token
     #[serde(default)]
     pub one_time: bool,
+    /// Operations and restrictions the token is scoped to
+    #[serde(default)]
+    pub scope: TokenScope,
 }
 
 impl CreateTokenRequest {
@@ -22,6 +25,7 @@ impl CreateTokenRequest {
             upload_size_limit: None,
             ttl_seconds,
             one_time: false,
+            scope: TokenScope::default(),
         }
     }
 
@@ -40,6 +44,47 @@ impl CreateTokenRequest {
     }
 }
 
+/// Scope of a user token, restricting the operations allowed and the restrictions enforced for
+/// secrets created with the token
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TokenScope {
+    /// Whether the token is allowed to create secrets
+    #[serde(default = "default_can_create")]
+    pub can_create: bool,
+    /// Maximum TTL in seconds for secrets created with the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_ttl_max: Option<u64>,
+    /// Whether secrets created with the token must be protected by a passphrase
+    #[serde(default)]
+    pub require_passphrase: bool,
+    /// IP ranges secrets created with the token are pinned to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "crate::utils::ip::deserialize_ip_nets")]
+    pub allowed_ips: Option<Vec<ipnet::IpNet>>,
+}
+
+fn default_can_create() -> bool {
+    true
+}
+
+impl Default for TokenScope {
+    fn default() -> Self {
+        Self {
+            can_create: default_can_create(),
+            allowed_ttl_max: None,
+            require_passphrase: false,
+            allowed_ips: None,
+        }
+    }
+}
+
+impl TokenScope {
+    /// Checks if the scope is not restricting anything
+    pub fn is_unrestricted(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Response model for creating user tokens via admin API
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateTokenResponse {
@@ -58,3 +103,56 @@ impl Drop for CreateTokenResponse {
         self.zeroize();
     }
 }
+
+/// Response model for the number of active user tokens via admin API
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct TokenCountResponse {
+    /// Number of active user tokens
+    pub user_tokens: usize,
+}
+
+/// Response model for the introspection of the own user token
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct TokenInfoResponse {
+    /// Optional upload size limit in bytes, the server default applies if not set
+    pub upload_size_limit: Option<i64>,
+    /// Whether it is a one time use token
+    pub one_time: bool,
+    /// Remaining lifetime of the token in seconds, not set if the token does not expire
+    pub ttl_remaining_seconds: Option<u64>,
+    /// Operations and restrictions the token is scoped to
+    #[serde(default)]
+    pub scope: TokenScope,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_scope_defaults_when_missing() -> Result<(), serde_json::Error> {
+        let request: CreateTokenRequest = serde_json::from_str(r#"{"ttl_seconds": 60}"#)?;
+        assert!(
+            request.scope.can_create,
+            "Creating secrets should be allowed by default"
+        );
+        assert!(request.scope.is_unrestricted());
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_scope_deserialization() -> Result<(), serde_json::Error> {
+        let scope: TokenScope = serde_json::from_str(
+            r#"{"can_create": false, "allowed_ttl_max": 3600, "require_passphrase": true, "allowed_ips": ["10.0.0.1"]}"#,
+        )?;
+        assert!(!scope.can_create);
+        assert_eq!(scope.allowed_ttl_max, Some(3600));
+        assert!(scope.require_passphrase);
+        assert_eq!(
+            scope.allowed_ips.map(|ips| ips[0].to_string()),
+            Some("10.0.0.1/32".to_string()),
+            "Single IPs should be converted to host ranges"
+        );
+        Ok(())
+    }
+}
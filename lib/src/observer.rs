@@ -1,5 +1,88 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt;
+#[cfg(feature = "crypto-only")]
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "crypto-only")]
+use std::time::Instant;
+
+/// Phases of sending or receiving a secret, reported to [`DataTransferObserver::on_phase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferPhase {
+    /// The payload is serialized and encrypted.
+    Encrypting,
+
+    /// The encrypted secret is uploaded to the server.
+    Uploading,
+
+    /// Waiting for the response of the server.
+    Waiting,
+
+    /// The encrypted secret is downloaded from the server.
+    Downloading,
+
+    /// The downloaded secret is decrypted and deserialized.
+    Decrypting,
+}
+
+impl fmt::Display for TransferPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TransferPhase::Encrypting => "encrypting",
+            TransferPhase::Uploading => "uploading",
+            TransferPhase::Waiting => "waiting",
+            TransferPhase::Downloading => "downloading",
+            TransferPhase::Decrypting => "decrypting",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Throughput and estimated remaining time of a transfer, reported to
+/// [`DataTransferObserver::on_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferStats {
+    /// The total number of bytes transferred so far.
+    pub bytes_transferred: u64,
+
+    /// The total size of the transfer in bytes.
+    pub total_bytes: u64,
+
+    /// The time since the transfer started.
+    pub elapsed: Duration,
+
+    /// The average throughput since the transfer started.
+    pub bytes_per_sec: f64,
+
+    /// The estimated remaining time, `None` until the throughput is known.
+    pub eta: Option<Duration>,
+}
+
+impl TransferStats {
+    /// Calculates the throughput and the remaining time from the bytes transferred so far.
+    pub fn new(bytes_transferred: u64, total_bytes: u64, elapsed: Duration) -> Self {
+        let secs = elapsed.as_secs_f64();
+        let bytes_per_sec = if secs > 0.0 {
+            bytes_transferred as f64 / secs
+        } else {
+            0.0
+        };
+
+        let remaining = total_bytes.saturating_sub(bytes_transferred);
+        let eta = (bytes_per_sec > 0.0)
+            .then(|| Duration::from_secs_f64(remaining as f64 / bytes_per_sec));
+
+        Self {
+            bytes_transferred,
+            total_bytes,
+            elapsed,
+            bytes_per_sec,
+            eta,
+        }
+    }
+}
+
 /// A trait for observing the progress of upload operations.
 ///
 /// Implementors of this trait can receive real-time notifications about upload progress,
@@ -44,4 +127,86 @@ pub trait DataTransferObserver: Send + Sync {
     /// - `bytes_uploaded` will always be ≤ `total_bytes`
     /// - The final call will have `bytes_uploaded == total_bytes`
     async fn on_progress(&self, bytes_transferred: u64, total_bytes: u64);
+
+    /// Called when the transfer enters a new phase, e.g. from uploading to waiting for the server.
+    ///
+    /// The default implementation ignores phase changes.
+    async fn on_phase(&self, _phase: TransferPhase) {}
+
+    /// Called along with [`on_progress`](Self::on_progress) with the throughput and the
+    /// estimated remaining time of the transfer.
+    ///
+    /// The default implementation ignores the statistics.
+    async fn on_stats(&self, _stats: &TransferStats) {}
+}
+
+/// Reports the phases and the progress of a transfer to an optional observer.
+#[cfg(feature = "crypto-only")]
+pub(crate) struct ProgressTracker {
+    observer: Option<Arc<dyn DataTransferObserver>>,
+    start: Instant,
+}
+
+#[cfg(feature = "crypto-only")]
+impl ProgressTracker {
+    pub(crate) fn new(observer: Option<Arc<dyn DataTransferObserver>>) -> Self {
+        Self {
+            observer,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) async fn phase(&self, phase: TransferPhase) {
+        if let Some(ref observer) = self.observer {
+            observer.on_phase(phase).await;
+        }
+    }
+
+    /// Reports the progress, the statistics are calculated from the time the tracker was created.
+    pub(crate) async fn progress(&self, bytes_transferred: u64, total_bytes: u64) {
+        if let Some(ref observer) = self.observer {
+            observer.on_progress(bytes_transferred, total_bytes).await;
+
+            let stats = TransferStats::new(bytes_transferred, total_bytes, self.start.elapsed());
+            observer.on_stats(&stats).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_stats() {
+        let stats = TransferStats::new(500, 2000, Duration::from_secs(2));
+
+        assert_eq!(stats.bytes_per_sec, 250.0);
+        assert_eq!(
+            stats.eta,
+            Some(Duration::from_secs(6)),
+            "Remaining 1500 bytes at 250 B/s should take 6s"
+        );
+    }
+
+    #[test]
+    fn test_transfer_stats_without_elapsed_time() {
+        let stats = TransferStats::new(0, 2000, Duration::ZERO);
+
+        assert_eq!(stats.bytes_per_sec, 0.0);
+        assert_eq!(stats.eta, None, "ETA should be unknown without throughput");
+    }
+
+    #[test]
+    fn test_transfer_stats_completed() {
+        let stats = TransferStats::new(2000, 2000, Duration::from_secs(1));
+
+        assert_eq!(stats.eta, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_transfer_phase_display() {
+        assert_eq!(TransferPhase::Encrypting.to_string(), "encrypting");
+        assert_eq!(TransferPhase::Waiting.to_string(), "waiting");
+    }
 }
@@ -3,7 +3,9 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::models::SecretRestrictions;
+use ulid::Ulid;
+
+use crate::models::{ReplyReservation, ReplySlot, SecretRestrictions};
 use crate::observer::DataTransferObserver;
 use crate::utils::hashing;
 
@@ -52,6 +54,46 @@ pub struct SecretSendOptions {
 
     /// Optional access restrictions for the secret.
     pub restrictions: Option<SecretRestrictions>,
+
+    /// Whether to pad the payload to a size class before encryption.
+    pub padding: bool,
+
+    /// Whether to upload the encrypted payload as binary instead of base64 encoded JSON.
+    pub raw_upload: bool,
+
+    /// Whether to upload the encrypted payload as multipart form, takes precedence over
+    /// `raw_upload`.
+    pub multipart_upload: bool,
+
+    /// An optional limit of the upload rate in bytes per second.
+    pub rate_limit: Option<u64>,
+
+    /// Whether to request a short human-friendly alias for the secret link.
+    pub short_alias: bool,
+
+    /// An optional limit of the encrypted data kept in memory during the upload, larger
+    /// uploads are spilled to an encrypted temporary file.
+    pub max_memory: Option<usize>,
+
+    /// An optional ID proposed for the secret, set by the encryption layer when the ciphertext
+    /// is bound to the ID.
+    pub secret_id: Option<Ulid>,
+
+    /// Whether to declare the secret as file in the metadata shown to the recipient before
+    /// retrieval.
+    pub file: bool,
+
+    /// Whether to let the recipient reply with a secret of their own.
+    pub allow_reply: bool,
+
+    /// The ID to reserve for the reply, set by the encryption layer when a reply is allowed.
+    pub reply_reservation: Option<ReplyReservation>,
+
+    /// The reply slot of a received secret, sends the secret as reply into it.
+    pub reply_to: Option<ReplySlot>,
+
+    /// An optional token authorizing the upload under a reserved secret ID.
+    pub upload_token: Option<String>,
 }
 
 impl SecretSendOptions {
@@ -89,6 +131,103 @@ impl SecretSendOptions {
         self.restrictions = Some(restrictions);
         self
     }
+
+    /// Pads small payloads to a size class (1KB, 10KB or 100KB) before encryption, so the
+    /// server and network observers cannot infer the size of the secret.
+    pub fn with_padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Uploads the encrypted payload as binary, avoiding the base64 overhead of the JSON API.
+    ///
+    /// Enabled automatically when the server advertises support for binary uploads.
+    pub fn with_raw_upload(mut self, raw_upload: bool) -> Self {
+        self.raw_upload = raw_upload;
+        self
+    }
+
+    /// Uploads the encrypted payload as binary file of a multipart form, for transports which
+    /// handle forms better than raw bodies (e.g. proxies of mobile platforms).
+    pub fn with_multipart_upload(mut self, multipart_upload: bool) -> Self {
+        self.multipart_upload = multipart_upload;
+        self
+    }
+
+    /// Limits the upload rate to the given number of bytes per second, 0 means unlimited.
+    ///
+    /// Unless a timeout is set explicitly, the default timeout is extended by the time the
+    /// upload takes at this rate.
+    pub fn with_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    /// Requests a short human-friendly alias (e.g. `/s/blue-otter-42`) for the secret link.
+    ///
+    /// Servers without short alias support return links with the full identifier.
+    pub fn with_short_alias(mut self, short_alias: bool) -> Self {
+        self.short_alias = short_alias;
+        self
+    }
+
+    /// Limits the encrypted data kept in memory while uploading to the given number of bytes.
+    ///
+    /// Larger uploads are streamed from an encrypted temporary file (see `hakanai_lib::spill`)
+    /// and base64 encoded chunk by chunk. The ciphertext is only held in memory while being
+    /// encrypted, as AES-GCM seals the secret as a whole.
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Proposes the ID of the secret to the server.
+    ///
+    /// The upload fails if the server assigns a different ID, e.g. because it does not support
+    /// client proposed IDs.
+    pub fn with_secret_id(mut self, secret_id: Ulid) -> Self {
+        self.secret_id = Some(secret_id);
+        self
+    }
+
+    /// Declares the secret as file in the metadata the recipient sees before retrieval.
+    ///
+    /// Set by the encryption layer for file payloads, if the server supports secret metadata.
+    pub fn with_file(mut self, file: bool) -> Self {
+        self.file = file;
+        self
+    }
+
+    /// Lets the recipient reply with a secret of their own.
+    ///
+    /// An ID for the reply is reserved together with the secret and passed to the recipient
+    /// inside the encrypted payload, the link to the reply is returned to the sender right away.
+    pub fn with_allow_reply(mut self, allow_reply: bool) -> Self {
+        self.allow_reply = allow_reply;
+        self
+    }
+
+    /// Reserves the ID of the reply together with the secret.
+    ///
+    /// Set by the encryption layer if a reply is allowed.
+    pub fn with_reply_reservation(mut self, reservation: ReplyReservation) -> Self {
+        self.reply_reservation = Some(reservation);
+        self
+    }
+
+    /// Sends the secret as reply into the slot embedded in a received secret.
+    ///
+    /// The secret is encrypted with the key of the slot and uploaded under its reserved ID.
+    pub fn with_reply_to(mut self, reply: ReplySlot) -> Self {
+        self.reply_to = Some(reply);
+        self
+    }
+
+    /// Authorizes the upload under a reserved secret ID, see `POST /api/v1/secret/reserve`.
+    pub fn with_upload_token(mut self, upload_token: String) -> Self {
+        self.upload_token = Some(upload_token);
+        self
+    }
 }
 
 /// Options for receiving a secret.
@@ -132,6 +271,16 @@ pub struct SecretReceiveOptions {
 
     /// An optional passphrase hash required to access the secret.
     pub passphrase_hash: Option<String>,
+
+    /// An optional PIN required to access the secret, delivered out-of-band by the server.
+    pub pin: Option<String>,
+
+    /// An optional limit of the download rate in bytes per second.
+    pub rate_limit: Option<u64>,
+
+    /// An optional limit of the encrypted data kept in memory during the download, larger
+    /// downloads are spilled to an encrypted temporary file.
+    pub max_memory: Option<usize>,
 }
 
 impl SecretReceiveOptions {
@@ -168,6 +317,36 @@ impl SecretReceiveOptions {
         self.passphrase_hash = Some(hash);
         self
     }
+
+    /// Sets the PIN the recipient received out-of-band for accessing the secret
+    pub fn with_pin(mut self, pin: &str) -> Self {
+        let pin = pin.trim();
+        if pin.is_empty() {
+            return self;
+        }
+
+        self.pin = Some(pin.to_string());
+        self
+    }
+
+    /// Limits the download rate to the given number of bytes per second, 0 means unlimited.
+    ///
+    /// The size of the secret is not known before the download starts, so the default timeout
+    /// does not apply to rate limited downloads. Set a timeout explicitly to bound them.
+    pub fn with_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    /// Limits the encrypted data kept in memory while downloading to the given number of bytes.
+    ///
+    /// Larger downloads are decoded chunk by chunk into an encrypted temporary file (see
+    /// `hakanai_lib::spill`), only the ciphertext is read back for decryption once the transfer
+    /// has completed.
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -175,6 +354,15 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn test_secret_receive_options_with_pin() {
+        let opts = SecretReceiveOptions::new().with_pin(" 123456\n");
+        assert_eq!(opts.pin.as_deref(), Some("123456"));
+
+        let opts = SecretReceiveOptions::new().with_pin("  ");
+        assert!(opts.pin.is_none(), "Empty PIN should be ignored");
+    }
+
     // Tests for SecretReceiveOptions passphrase functionality
     #[test]
     fn test_secret_receive_options_with_passphrase() {
@@ -0,0 +1,780 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Utilities for integration tests of applications using this library (feature `testing`).
+//!
+//! [`MockServer`] implements the secret API of a Hakanai server in-process, so clients can be
+//! tested end-to-end without a real deployment. Latencies, failures and size limits can be
+//! configured via [`MockServerOptions`].
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::testing::{MockServer, MockServerOptions};
+//! use hakanai_lib::{client, client::Client, models::Payload};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start(MockServerOptions::new().with_token("my-token")).await?;
+//!
+//! let client = client::new();
+//! let url = client
+//!     .send_secret(
+//!         server.url(),
+//!         Payload::from_bytes(b"my secret"),
+//!         Duration::from_secs(60),
+//!         "my-token".to_string(),
+//!         None,
+//!     )
+//!     .await?
+//!     .url;
+//!
+//! let payload = client.receive_secret(url, None).await?;
+//! assert_eq!(payload.data, b"my secret");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::Engine;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use ulid::Ulid;
+use url::Url;
+
+use crate::models::{
+    PostSecretRequest, PostSecretResponse, ReplyReservation, SecretRestrictions, ServerConfig,
+    restrictions, secret,
+};
+use crate::utils::hashing;
+
+/// Maximum size of the request line and headers.
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+
+/// Maximum size of request bodies, independent of the configured size limit.
+const MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+/// Options of the [`MockServer`].
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::testing::MockServerOptions;
+/// use std::time::Duration;
+///
+/// let options = MockServerOptions::new()
+///     .with_latency(Duration::from_millis(200))
+///     .with_secret_size_limit(1024)
+///     .with_failures(503, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockServerOptions {
+    latency: Duration,
+    secret_size_limit: Option<u64>,
+    token: Option<String>,
+    failures: Option<(u16, usize)>,
+    config: ServerConfig,
+}
+
+impl MockServerOptions {
+    /// Creates options for a server accepting all requests without delay.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delays every response by the latency.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Limits the size of secrets created without a token, the limit is advertised in
+    /// `/config.json`.
+    ///
+    /// Larger secrets are rejected with `413 Payload Too Large`, accounting for the encoding
+    /// overhead like the Hakanai server does.
+    pub fn with_secret_size_limit(mut self, limit: u64) -> Self {
+        self.secret_size_limit = Some(limit);
+        self
+    }
+
+    /// Requires the token to create secrets, other requests are rejected with `401 Unauthorized`.
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Responds to the next `count` requests of the secret API with the HTTP status.
+    ///
+    /// Requests of `/config.json` are not affected.
+    pub fn with_failures(mut self, status: u16, count: usize) -> Self {
+        self.failures = Some((status, count));
+        self
+    }
+
+    /// Sets the configuration served via `/config.json`.
+    pub fn with_config(mut self, config: ServerConfig) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+/// In-process HTTP server implementing the secret API of Hakanai.
+///
+/// Secrets are kept in memory and deleted after being retrieved once. IDs reserved for replies
+/// are answered with `425 Too Early` until the reply is uploaded with its upload token. The
+/// server listens on a random port of the loopback interface and stops when dropped.
+pub struct MockServer {
+    url: Url,
+    state: Arc<State>,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts the server with the options.
+    pub async fn start(options: MockServerOptions) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url =
+            Url::parse(&format!("http://{}/", listener.local_addr()?)).map_err(io::Error::other)?;
+
+        let state = Arc::new(State::new(options));
+        let handle = tokio::spawn(accept(listener, state.clone()));
+
+        Ok(Self { url, state, handle })
+    }
+
+    /// Returns the base URL of the server.
+    pub fn url(&self) -> Url {
+        self.url.clone()
+    }
+
+    /// Returns the number of secrets stored and not retrieved yet.
+    pub fn secret_count(&self) -> usize {
+        self.state
+            .secrets
+            .lock()
+            .map(|s| s.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of requests handled by the server.
+    pub fn request_count(&self) -> usize {
+        self.state.request_count.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+struct StoredSecret {
+    data: String,
+    restrictions: Option<SecretRestrictions>,
+}
+
+/// A secret parsed from an upload, with the proposed ID and the reply reservation, if any.
+struct Upload {
+    secret: StoredSecret,
+    id: Option<Ulid>,
+    reply: Option<ReplyReservation>,
+}
+
+struct State {
+    options: MockServerOptions,
+    secrets: Mutex<HashMap<Ulid, StoredSecret>>,
+    reservations: Mutex<HashMap<Ulid, String>>,
+    remaining_failures: AtomicUsize,
+    request_count: AtomicUsize,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn json<T: serde::Serialize>(value: &T) -> Self {
+        match serde_json::to_vec(value) {
+            Ok(body) => Self {
+                status: 200,
+                content_type: "application/json",
+                body,
+            },
+            Err(_) => Self::text(500, "Internal Server Error"),
+        }
+    }
+}
+
+impl State {
+    fn new(options: MockServerOptions) -> Self {
+        let failures = options.failures.map(|(_, count)| count).unwrap_or_default();
+        Self {
+            options,
+            secrets: Mutex::new(HashMap::new()),
+            reservations: Mutex::new(HashMap::new()),
+            remaining_failures: AtomicUsize::new(failures),
+            request_count: AtomicUsize::new(0),
+        }
+    }
+
+    fn handle(&self, request: &Request) -> Response {
+        let path = request.path.split('?').next().unwrap_or_default();
+        if request.method == "GET" && path == "/config.json" {
+            return Response::json(&self.config());
+        }
+
+        if let Some(status) = self.take_failure() {
+            return Response::text(status, "Simulated failure");
+        }
+
+        match (request.method.as_str(), path) {
+            ("POST", "/api/v1/secret") => self.create_secret(request, false),
+            ("POST", "/api/v1/secret/raw") => self.create_secret(request, true),
+            ("GET", path) => match path
+                .strip_prefix("/s/")
+                .or_else(|| path.strip_prefix("/api/v1/secret/"))
+            {
+                Some(id) => self.retrieve_secret(id, request),
+                None => Response::text(404, "Not Found"),
+            },
+            _ => Response::text(404, "Not Found"),
+        }
+    }
+
+    fn config(&self) -> ServerConfig {
+        let mut config = self.options.config.clone();
+        if self.options.secret_size_limit.is_some() {
+            config.secret_size_limit = self.options.secret_size_limit;
+        }
+        config
+    }
+
+    fn take_failure(&self) -> Option<u16> {
+        let (status, _) = self.options.failures?;
+        self.remaining_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .ok()
+            .map(|_| status)
+    }
+
+    fn create_secret(&self, request: &Request, raw: bool) -> Response {
+        let token = request
+            .header("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let upload_token = request.header(secret::UPLOAD_TOKEN_HEADER_NAME);
+        // the upload token of a reservation authorizes the upload on its own
+        if let Some(ref required) = self.options.token
+            && upload_token.is_none()
+            && token != Some(required.as_str())
+        {
+            return Response::text(401, "Unauthorized");
+        }
+
+        if token.is_none()
+            && let Some(limit) = self.body_size_limit(raw)
+            && request.body.len() as u64 > limit
+        {
+            return Response::text(413, "Payload Too Large");
+        }
+
+        let upload = if raw {
+            parse_raw_secret(request)
+        } else {
+            serde_json::from_slice::<PostSecretRequest>(&request.body)
+                .map(|req| Upload {
+                    secret: StoredSecret {
+                        data: req.data,
+                        restrictions: req.restrictions,
+                    },
+                    id: req.id,
+                    reply: req.reply,
+                })
+                .map_err(|_| "Invalid request body")
+        };
+
+        match (upload, self.secrets.lock(), self.reservations.lock()) {
+            (Ok(upload), Ok(mut secrets), Ok(mut reservations)) => {
+                let id = upload.id.unwrap_or_else(Ulid::r#gen);
+                match upload_token {
+                    Some(upload_token) => {
+                        let token_hash = hashing::sha256_hex_from_string(upload_token);
+                        if reservations.get(&id) != Some(&token_hash) {
+                            return Response::text(403, "Forbidden");
+                        }
+                        reservations.remove(&id);
+                    }
+                    None if secrets.contains_key(&id) || reservations.contains_key(&id) => {
+                        return Response::text(409, "Conflict");
+                    }
+                    None => {}
+                }
+                secrets.insert(id, upload.secret);
+
+                let mut res = PostSecretResponse::new(id);
+                if let Some(reply) = upload.reply {
+                    reservations.insert(reply.id, reply.upload_token_hash);
+                    res = res.with_reply_id(reply.id);
+                }
+                Response::json(&res)
+            }
+            (Err(msg), _, _) => Response::text(400, msg),
+            _ => Response::text(500, "Internal Server Error"),
+        }
+    }
+
+    /// Returns the maximum body size for requests without token, mirroring the server which
+    /// accounts for the base64 and encryption overhead (factor 1.5, 3/4 of it for raw uploads).
+    fn body_size_limit(&self, raw: bool) -> Option<u64> {
+        let limit = self.options.secret_size_limit?.saturating_mul(3) / 2;
+        Some(if raw { limit / 4 * 3 } else { limit })
+    }
+
+    fn retrieve_secret(&self, id: &str, request: &Request) -> Response {
+        let Ok(id) = Ulid::from_string(id) else {
+            return Response::text(404, "Not Found");
+        };
+
+        let Ok(mut secrets) = self.secrets.lock() else {
+            return Response::text(500, "Internal Server Error");
+        };
+
+        let Some(secret) = secrets.get(&id) else {
+            if self
+                .reservations
+                .lock()
+                .is_ok_and(|reservations| reservations.contains_key(&id))
+            {
+                return Response::text(425, "Too Early");
+            }
+            return Response::text(404, "Not Found");
+        };
+
+        if let Some(hash) = secret
+            .restrictions
+            .as_ref()
+            .and_then(|r| r.passphrase_hash.as_deref())
+            && request.header(restrictions::PASSPHRASE_HEADER_NAME) != Some(hash)
+        {
+            return Response::text(401, "Unauthorized");
+        }
+
+        match secrets.remove(&id) {
+            Some(secret) => Response::text(200, &secret.data),
+            None => Response::text(404, "Not Found"),
+        }
+    }
+}
+
+/// Parses a binary upload, the metadata is sent in headers.
+fn parse_raw_secret(request: &Request) -> Result<Upload, &'static str> {
+    request
+        .header(secret::TTL_HEADER_NAME)
+        .and_then(|ttl| ttl.parse::<u64>().ok())
+        .ok_or("Missing or invalid TTL header")?;
+
+    let restrictions = request
+        .header(restrictions::RESTRICTIONS_HEADER_NAME)
+        .map(serde_json::from_str::<SecretRestrictions>)
+        .transpose()
+        .map_err(|_| "Invalid restrictions header")?;
+
+    let id = request
+        .header(secret::ID_HEADER_NAME)
+        .map(Ulid::from_string)
+        .transpose()
+        .map_err(|_| "Invalid ID header")?;
+
+    let reply = request
+        .header(secret::REPLY_HEADER_NAME)
+        .map(serde_json::from_str::<ReplyReservation>)
+        .transpose()
+        .map_err(|_| "Invalid reply header")?;
+
+    let secret = StoredSecret {
+        data: base64::prelude::BASE64_STANDARD.encode(&request.body),
+        restrictions,
+    };
+    Ok(Upload { secret, id, reply })
+}
+
+async fn accept(listener: TcpListener, state: Arc<State>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, &state).await;
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: &State) -> io::Result<()> {
+    let request = read_request(&mut stream).await?;
+    state.request_count.fetch_add(1, Ordering::Relaxed);
+
+    let response = state.handle(&request);
+    if !state.options.latency.is_zero() {
+        tokio::time::sleep(state.options.latency).await;
+    }
+
+    write_response(&mut stream, &response).await
+}
+
+async fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "headers too large",
+            ));
+        }
+
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|len| len.parse::<usize>().ok())
+        .unwrap_or_default();
+    if content_length > MAX_BODY_SIZE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "body too large"));
+    }
+
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+async fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.content_type,
+        response.body.len()
+    );
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        425 => "Too Early",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::time::Instant;
+
+    use crate::client::{self, Client, ClientError};
+    use crate::models::Payload;
+    use crate::options::{SecretReceiveOptions, SecretSendOptions};
+
+    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+    async fn send(
+        server: &MockServer,
+        data: &[u8],
+        token: &str,
+    ) -> std::result::Result<Url, ClientError> {
+        client::new()
+            .send_secret(
+                server.url(),
+                Payload::from_bytes(data),
+                Duration::from_secs(60),
+                token.to_string(),
+                None,
+            )
+            .await
+            .map(|sent| sent.url)
+    }
+
+    #[tokio::test]
+    async fn test_send_and_receive() -> Result<()> {
+        let server = MockServer::start(MockServerOptions::new()).await?;
+
+        let url = send(&server, b"secret", "").await?;
+        assert_eq!(server.secret_count(), 1, "Secret should be stored");
+
+        let payload = client::new().receive_secret(url.clone(), None).await?;
+        assert_eq!(payload.data, b"secret");
+        assert_eq!(server.secret_count(), 0, "Secret should be deleted");
+
+        let result = client::new().receive_secret(url, None).await;
+        assert!(result.is_err(), "Secret should only be retrievable once");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raw_upload() -> Result<()> {
+        let config = ServerConfig {
+            features: crate::models::ServerFeatures {
+                raw_upload: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let server = MockServer::start(MockServerOptions::new().with_config(config)).await?;
+
+        let url = send(&server, b"raw secret", "").await?;
+
+        let payload = client::new().receive_secret(url, None).await?;
+        assert_eq!(payload.data, b"raw secret");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_ids() -> Result<()> {
+        let config = ServerConfig {
+            features: crate::models::ServerFeatures {
+                raw_upload: true,
+                client_ids: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let server = MockServer::start(MockServerOptions::new().with_config(config)).await?;
+
+        let url = send(&server, b"bound secret", "").await?;
+        let fragment = url.fragment().unwrap_or_default();
+        assert!(
+            fragment.ends_with(url.path().trim_start_matches("/s/")),
+            "Fragment should end with the proposed ID of the link: {url}"
+        );
+
+        let payload = client::new().receive_secret(url, None).await?;
+        assert_eq!(payload.data, b"bound secret");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_token_required() -> Result<()> {
+        let server = MockServer::start(MockServerOptions::new().with_token("token")).await?;
+
+        let result = send(&server, b"secret", "wrong").await;
+        assert!(
+            matches!(result, Err(ClientError::Http(ref msg)) if msg.contains("401")),
+            "Invalid token should be rejected: {result:?}"
+        );
+
+        send(&server, b"secret", "token").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_secret_size_limit() -> Result<()> {
+        let server = MockServer::start(MockServerOptions::new().with_secret_size_limit(16)).await?;
+
+        let result = send(&server, &[0u8; 32], "").await;
+        assert!(
+            matches!(result, Err(ClientError::PayloadTooLarge { limit: 16, .. })),
+            "Advertised limit should be enforced: {result:?}"
+        );
+
+        send(&server, &[0u8; 32], "token").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failures() -> Result<()> {
+        let server = MockServer::start(MockServerOptions::new().with_failures(503, 1)).await?;
+
+        let result = send(&server, b"secret", "").await;
+        assert!(
+            matches!(result, Err(ClientError::Http(ref msg)) if msg.contains("503")),
+            "First request should fail: {result:?}"
+        );
+
+        send(&server, b"secret", "").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_latency() -> Result<()> {
+        let latency = Duration::from_millis(100);
+        let server = MockServer::start(MockServerOptions::new().with_latency(latency)).await?;
+
+        let start = Instant::now();
+        send(&server, b"secret", "").await?;
+        assert!(
+            start.elapsed() >= latency,
+            "Response should be delayed by the latency"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_passphrase() -> Result<()> {
+        let server = MockServer::start(MockServerOptions::new()).await?;
+        let restrictions = SecretRestrictions::default().with_passphrase(b"passphrase");
+
+        let url = client::new()
+            .send_secret(
+                server.url(),
+                Payload::from_bytes(b"secret"),
+                Duration::from_secs(60),
+                String::new(),
+                Some(SecretSendOptions::new().with_restrictions(restrictions)),
+            )
+            .await?
+            .url;
+
+        let result = client::new().receive_secret(url.clone(), None).await;
+        assert!(result.is_err(), "Secret should require the passphrase");
+
+        let opts = SecretReceiveOptions::new().with_passphrase(b"passphrase");
+        let payload = client::new().receive_secret(url, Some(opts)).await?;
+        assert_eq!(payload.data, b"secret");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reply() -> Result<()> {
+        let config = ServerConfig {
+            features: crate::models::ServerFeatures {
+                raw_upload: true,
+                client_ids: true,
+                replies: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let options = MockServerOptions::new()
+            .with_config(config)
+            .with_token("token");
+        let server = MockServer::start(options).await?;
+
+        let sent = client::new()
+            .send_secret(
+                server.url(),
+                Payload::from_bytes(b"question"),
+                Duration::from_secs(60),
+                "token".to_string(),
+                Some(SecretSendOptions::new().with_allow_reply(true)),
+            )
+            .await?;
+        let reply_url = sent.reply_url.ok_or("No reply link")?;
+
+        let result = client::new().receive_secret(reply_url.clone(), None).await;
+        assert!(
+            matches!(result, Err(ClientError::Http(ref msg)) if msg.contains("425")),
+            "Reply should not exist yet: {result:?}"
+        );
+
+        let mut payload = client::new().receive_secret(sent.url, None).await?;
+        let reply = payload.reply.take().ok_or("No reply slot")?;
+
+        // the recipient needs no token, the upload token of the slot authorizes the reply
+        client::new()
+            .send_secret(
+                server.url(),
+                Payload::from_bytes(b"answer"),
+                Duration::from_secs(60),
+                String::new(),
+                Some(SecretSendOptions::new().with_reply_to(reply.clone())),
+            )
+            .await?;
+
+        let answer = client::new().receive_secret(reply_url, None).await?;
+        assert_eq!(answer.data, b"answer");
+
+        let result = client::new()
+            .send_secret(
+                server.url(),
+                Payload::from_bytes(b"another answer"),
+                Duration::from_secs(60),
+                String::new(),
+                Some(SecretSendOptions::new().with_reply_to(reply)),
+            )
+            .await;
+        assert!(
+            matches!(result, Err(ClientError::Http(ref msg)) if msg.contains("403")),
+            "Upload token should only be valid once: {result:?}"
+        );
+        Ok(())
+    }
+}
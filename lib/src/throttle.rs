@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+/// Limits the average rate of a transfer by delaying after each chunk.
+///
+/// The delay is calculated from the total number of bytes transferred since the start, so short
+/// bursts are evened out and the average rate never exceeds the limit.
+pub(crate) struct Throttle {
+    bytes_per_sec: u64,
+    start: Instant,
+    transferred: u64,
+}
+
+impl Throttle {
+    /// Creates a new throttle, returns `None` if the rate is unlimited (0).
+    pub(crate) fn new(bytes_per_sec: Option<u64>) -> Option<Self> {
+        bytes_per_sec
+            .filter(|rate| *rate > 0)
+            .map(|bytes_per_sec| Self {
+                bytes_per_sec,
+                start: Instant::now(),
+                transferred: 0,
+            })
+    }
+
+    /// Waits until the transfer of the chunk complies with the rate limit.
+    pub(crate) async fn throttle(&mut self, chunk_len: usize) {
+        let delay = self.delay(chunk_len, self.start.elapsed());
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn delay(&mut self, chunk_len: usize, elapsed: Duration) -> Duration {
+        self.transferred = self.transferred.saturating_add(chunk_len as u64);
+        expected_duration(self.transferred, self.bytes_per_sec).saturating_sub(elapsed)
+    }
+}
+
+/// Returns the time needed to transfer the bytes at the given rate.
+pub(crate) fn expected_duration(bytes: u64, bytes_per_sec: u64) -> Duration {
+    Duration::from_secs_f64(bytes as f64 / bytes_per_sec as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_unlimited() {
+        assert!(Throttle::new(None).is_none(), "No rate should not throttle");
+        assert!(
+            Throttle::new(Some(0)).is_none(),
+            "Rate 0 should not throttle"
+        );
+        assert!(Throttle::new(Some(1024)).is_some());
+    }
+
+    #[test]
+    fn test_delay_keeps_average_rate() {
+        let mut throttle = Throttle::new(Some(1000)).expect("throttle should be created");
+
+        assert_eq!(
+            throttle.delay(500, Duration::ZERO),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            throttle.delay(500, Duration::from_millis(600)),
+            Duration::from_millis(400),
+            "Delay should account for the time already elapsed"
+        );
+        assert_eq!(
+            throttle.delay(100, Duration::from_secs(5)),
+            Duration::ZERO,
+            "Slow transfers should not be delayed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_throttle_waits() {
+        let mut throttle = Throttle::new(Some(10_000)).expect("throttle should be created");
+        let start = Instant::now();
+
+        throttle.throttle(1000).await;
+
+        assert!(
+            start.elapsed() >= Duration::from_millis(100),
+            "Transfer of 1000 bytes at 10000 B/s should take at least 100ms"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lookup of well-known autonomous system numbers (ASNs) by organization.
+//!
+//! This module embeds a small, curated table of networks operated by large cloud, CDN and
+//! hosting providers. It allows restricting secrets to an organization by name instead of
+//! listing its ASNs, and annotating ASNs with a readable name in audit logs.
+//!
+//! The table is intentionally not exhaustive: organizations operate many more networks than
+//! listed here, so restrictions built from it should be double-checked for critical use.
+
+/// Well-known organizations and the ASNs they operate, sorted by name.
+const ORGANIZATIONS: &[(&str, &[u32])] = &[
+    ("Akamai", &[20940, 16625, 21342, 32787]),
+    ("Alibaba", &[45102, 37963]),
+    ("Amazon", &[16509, 14618, 8987]),
+    ("Apple", &[714, 6185]),
+    ("Cloudflare", &[13335, 209242]),
+    ("DigitalOcean", &[14061]),
+    ("Fastly", &[54113]),
+    ("GitHub", &[36459]),
+    ("Google", &[15169, 396982, 36040, 19527]),
+    ("Hetzner", &[24940, 213230]),
+    ("Linode", &[63949]),
+    ("Meta", &[32934, 63293]),
+    ("Microsoft", &[8075, 8068, 8069, 12076]),
+    ("Oracle", &[31898, 792]),
+    ("OVH", &[16276]),
+    ("Scaleway", &[12876]),
+    ("Vultr", &[20473]),
+];
+
+/// Returns the name of the organization operating the given ASN, if it is known.
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::utils::asn::org_name;
+///
+/// assert_eq!(org_name(13335), Some("Cloudflare"));
+/// assert_eq!(org_name(64512), None);
+/// ```
+pub fn org_name(asn: u32) -> Option<&'static str> {
+    ORGANIZATIONS
+        .iter()
+        .find(|(_, asns)| asns.contains(&asn))
+        .map(|(name, _)| *name)
+}
+
+/// Returns the ASNs of the organization with the given name (case insensitive).
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::utils::asn::asns_for_org;
+///
+/// assert_eq!(asns_for_org("cloudflare"), Some(&[13335, 209242][..]));
+/// assert_eq!(asns_for_org("Unknown Corp"), None);
+/// ```
+pub fn asns_for_org(name: &str) -> Option<&'static [u32]> {
+    let name = name.trim();
+    ORGANIZATIONS
+        .iter()
+        .find(|(org, _)| org.eq_ignore_ascii_case(name))
+        .map(|(_, asns)| *asns)
+}
+
+/// Returns the names of all known organizations.
+pub fn org_names() -> impl Iterator<Item = &'static str> {
+    ORGANIZATIONS.iter().map(|(name, _)| *name)
+}
+
+/// Parses an organization name into its canonical spelling from the table.
+///
+/// Intended as a value parser for command line arguments; the ASNs can then be looked up
+/// with [`asns_for_org`].
+///
+/// # Returns
+///
+/// * `Ok(String)` - The canonical name of the organization
+/// * `Err(String)` - An error message listing the known organizations
+pub fn parse_org(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    org_names()
+        .find(|org| org.eq_ignore_ascii_case(name))
+        .map(str::to_string)
+        .ok_or_else(|| {
+            format!(
+                "Unknown organization '{name}'. Known organizations: {}",
+                org_names().collect::<Vec<_>>().join(", ")
+            )
+        })
+}
+
+/// Formats an ASN for logs, including the organization name if it is known.
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::utils::asn::describe;
+///
+/// assert_eq!(describe(15169), "AS15169 (Google)");
+/// assert_eq!(describe(64512), "AS64512");
+/// ```
+pub fn describe(asn: u32) -> String {
+    match org_name(asn) {
+        Some(name) => format!("AS{asn} ({name})"),
+        None => format!("AS{asn}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_table_is_sorted_and_unique() {
+        let names: Vec<_> = org_names().map(str::to_lowercase).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(names, sorted, "Organizations should be sorted and unique");
+
+        let mut seen = HashSet::new();
+        for (name, asns) in ORGANIZATIONS {
+            assert!(!asns.is_empty(), "{name} should have at least one ASN");
+            for asn in *asns {
+                assert!(seen.insert(asn), "AS{asn} should only be listed once");
+            }
+        }
+    }
+
+    #[test]
+    fn test_org_name() {
+        assert_eq!(org_name(13335), Some("Cloudflare"));
+        assert_eq!(org_name(396982), Some("Google"));
+        assert_eq!(org_name(8075), Some("Microsoft"));
+        assert_eq!(org_name(0), None, "Unknown ASNs should have no name");
+    }
+
+    #[test]
+    fn test_asns_for_org() {
+        assert_eq!(asns_for_org("Amazon"), Some(&[16509, 14618, 8987][..]));
+        assert_eq!(
+            asns_for_org("  GOOGLE "),
+            asns_for_org("Google"),
+            "Lookup should ignore case and surrounding whitespace"
+        );
+        assert_eq!(asns_for_org(""), None);
+        assert_eq!(
+            asns_for_org("Cloud"),
+            None,
+            "Partial names should not match"
+        );
+    }
+
+    #[test]
+    fn test_parse_org() {
+        assert_eq!(
+            parse_org(" cloudflare"),
+            Ok("Cloudflare".to_string()),
+            "Names should be normalized to the canonical spelling"
+        );
+
+        let err = parse_org("Initech").expect_err("Unknown organization should fail");
+        assert!(
+            err.contains("'Initech'") && err.contains("Cloudflare"),
+            "Error should name the input and list known organizations: {err}"
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(13335), "AS13335 (Cloudflare)");
+        assert_eq!(describe(64512), "AS64512");
+    }
+}
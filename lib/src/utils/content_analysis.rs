@@ -19,12 +19,103 @@
 //! assert!(!is_binary(text_data));
 //! assert!(is_binary(binary_data));
 //! ```
+//!
+//! Text in UTF-8 and UTF-16 is recognized with and without byte order mark, [`analyze`]
+//! additionally returns the detected encoding and the confidence of the classification.
+
+/// Byte order mark of UTF-8 encoded text.
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Byte order mark of UTF-16 little endian encoded text.
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+
+/// Byte order mark of UTF-16 big endian encoded text.
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Share of control characters above which content without a known encoding is binary.
+const MAX_CONTROL_RATIO: f32 = 0.1;
+
+/// Share of null bytes at the high byte positions required to detect UTF-16 without BOM.
+const MIN_UTF16_NULL_RATIO: f32 = 0.7;
+
+/// Unicode encoding of text content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+/// Result of the analysis of content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentAnalysis {
+    /// Whether the content appears to be binary data.
+    pub binary: bool,
+
+    /// Detected Unicode encoding of text content, `None` for binary content and text in
+    /// unknown (e.g. legacy 8-bit) encodings.
+    pub encoding: Option<TextEncoding>,
+
+    /// Whether the text starts with a byte order mark.
+    pub bom: bool,
+
+    /// Confidence of the classification from `0.0` (guess) to `1.0` (certain).
+    pub confidence: f32,
+}
+
+impl ContentAnalysis {
+    fn text(encoding: TextEncoding, bom: bool, confidence: f32) -> Self {
+        Self {
+            binary: false,
+            encoding: Some(encoding),
+            bom,
+            confidence,
+        }
+    }
+}
+
+/// Analyzes whether the content is binary data or text and in which encoding.
+///
+/// The detection works in the following order:
+/// 1. A UTF-8 or UTF-16 byte order mark followed by valid text is text with full confidence.
+/// 2. Valid UTF-8 without null bytes is text, the confidence drops with the share of
+///    control characters.
+/// 3. Content with null bytes is UTF-16 text if the null bytes are mostly at the high byte
+///    positions and it decodes to valid text, otherwise binary.
+/// 4. Anything else is text in an unknown encoding if it contains few control characters,
+///    otherwise binary.
+///
+/// # Example
+///
+/// ```
+/// use hakanai_lib::utils::content_analysis::{TextEncoding, analyze};
+///
+/// let analysis = analyze(b"\xFF\xFEH\x00i\x00");
+/// assert!(!analysis.binary);
+/// assert_eq!(analysis.encoding, Some(TextEncoding::Utf16Le));
+/// assert!(analysis.bom);
+/// ```
+pub fn analyze(content: &[u8]) -> ContentAnalysis {
+    if let Some(analysis) = analyze_bom(content) {
+        return analysis;
+    }
+
+    if !content.contains(&0) {
+        if let Ok(text) = std::str::from_utf8(content) {
+            let confidence = 1.0 - control_ratio(text.chars());
+            return ContentAnalysis::text(TextEncoding::Utf8, false, confidence);
+        }
+    } else if let Some(analysis) = analyze_utf16(content) {
+        return analysis;
+    }
+
+    analyze_unknown(content)
+}
 
 /// Checks if the given content is binary data.
 ///
-/// This function uses a simple but effective heuristic: the presence of null bytes.
-/// Most text encodings (UTF-8, ASCII, etc.) don't contain null bytes, while binary
-/// formats (executables, images, compressed files) commonly do.
+/// See [`analyze`] for the heuristics. Text in UTF-8 and UTF-16 (with or without byte order
+/// mark) is never considered binary, even though UTF-16 text contains null bytes.
 ///
 /// # Arguments
 ///
@@ -38,11 +129,8 @@
 /// # Limitations
 ///
 /// This is a heuristic approach and may have edge cases:
-/// - Some text files with special encodings might contain null bytes
-/// - Some binary formats might not contain null bytes in their header
-///
-/// For more robust detection, consider additional checks like UTF-8 validation
-/// or magic byte detection for specific file formats.
+/// - Short binary data without null bytes may look like text in a legacy encoding
+/// - UTF-16 text without byte order mark is only detected for mostly Latin scripts
 ///
 /// # Example
 ///
@@ -52,14 +140,149 @@
 /// // Text content
 /// assert!(!is_binary(b"Hello, world!"));
 /// assert!(!is_binary(b"UTF-8 text: \xE2\x9C\x93")); // ✓
+/// assert!(!is_binary(b"\xFF\xFEH\x00i\x00")); // UTF-16LE with BOM
 ///
 /// // Binary content
 /// assert!(is_binary(b"\x00\x01\x02"));
 /// assert!(is_binary(b"PNG\x00header"));
 /// ```
 pub fn is_binary(content: &[u8]) -> bool {
-    // Check for null bytes, which are common in binary files
-    content.contains(&0)
+    analyze(content).binary
+}
+
+/// Decodes text content in any of the detected Unicode encodings, without byte order mark.
+///
+/// Returns `None` for binary content and text in unknown encodings.
+///
+/// # Example
+///
+/// ```
+/// use hakanai_lib::utils::content_analysis::decode_text;
+///
+/// assert_eq!(decode_text(b"\xEF\xBB\xBFHi"), Some("Hi".to_string()));
+/// assert_eq!(decode_text(b"\xFE\xFF\x00H\x00i"), Some("Hi".to_string()));
+/// assert_eq!(decode_text(b"\x00\x01\x02"), None);
+/// ```
+pub fn decode_text(content: &[u8]) -> Option<String> {
+    let analysis = analyze(content);
+    let content = if analysis.bom {
+        let bom_len = match analysis.encoding? {
+            TextEncoding::Utf8 => UTF8_BOM.len(),
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => UTF16_LE_BOM.len(),
+        };
+        &content[bom_len..]
+    } else {
+        content
+    };
+
+    match analysis.encoding? {
+        TextEncoding::Utf8 => String::from_utf8(content.to_vec()).ok(),
+        TextEncoding::Utf16Le => decode_utf16(content, u16::from_le_bytes),
+        TextEncoding::Utf16Be => decode_utf16(content, u16::from_be_bytes),
+    }
+}
+
+fn analyze_bom(content: &[u8]) -> Option<ContentAnalysis> {
+    if let Some(rest) = content.strip_prefix(UTF8_BOM) {
+        return std::str::from_utf8(rest)
+            .is_ok()
+            .then(|| ContentAnalysis::text(TextEncoding::Utf8, true, 1.0));
+    }
+
+    let (rest, encoding, from_bytes): (_, _, fn([u8; 2]) -> u16) =
+        if let Some(rest) = content.strip_prefix(UTF16_LE_BOM) {
+            (rest, TextEncoding::Utf16Le, u16::from_le_bytes)
+        } else if let Some(rest) = content.strip_prefix(UTF16_BE_BOM) {
+            (rest, TextEncoding::Utf16Be, u16::from_be_bytes)
+        } else {
+            return None;
+        };
+
+    decode_utf16(rest, from_bytes).map(|_| ContentAnalysis::text(encoding, true, 1.0))
+}
+
+fn analyze_utf16(content: &[u8]) -> Option<ContentAnalysis> {
+    if !content.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let pairs = content.len() / 2;
+    let nulls_at = |offset: usize| {
+        content
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|b| **b == 0)
+            .count() as f32
+            / pairs as f32
+    };
+    let (even_nulls, odd_nulls) = (nulls_at(0), nulls_at(1));
+
+    let (encoding, from_bytes, null_ratio): (_, fn([u8; 2]) -> u16, _) =
+        if odd_nulls >= MIN_UTF16_NULL_RATIO && even_nulls < 1.0 - MIN_UTF16_NULL_RATIO {
+            (TextEncoding::Utf16Le, u16::from_le_bytes, odd_nulls)
+        } else if even_nulls >= MIN_UTF16_NULL_RATIO && odd_nulls < 1.0 - MIN_UTF16_NULL_RATIO {
+            (TextEncoding::Utf16Be, u16::from_be_bytes, even_nulls)
+        } else {
+            return None;
+        };
+
+    let text = decode_utf16(content, from_bytes)?;
+    let control_ratio = control_ratio(text.chars());
+    if control_ratio > MAX_CONTROL_RATIO {
+        return None;
+    }
+
+    Some(ContentAnalysis::text(
+        encoding,
+        false,
+        null_ratio * (1.0 - control_ratio),
+    ))
+}
+
+fn analyze_unknown(content: &[u8]) -> ContentAnalysis {
+    let control_ratio = control_ratio(content.iter().map(|b| *b as char));
+    let binary = content.contains(&0) || control_ratio > MAX_CONTROL_RATIO;
+    let confidence = if binary {
+        (0.5 + control_ratio).min(1.0)
+    } else {
+        0.5 * (1.0 - control_ratio)
+    };
+
+    ContentAnalysis {
+        binary,
+        encoding: None,
+        bom: false,
+        confidence,
+    }
+}
+
+fn decode_utf16(content: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+    if !content.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let units = content
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .ok()
+        .filter(|text| !text.contains('\0'))
+}
+
+/// Share of control characters other than common whitespace and escape sequences.
+fn control_ratio(chars: impl Iterator<Item = char>) -> f32 {
+    let (total, control) = chars.fold((0usize, 0usize), |(total, control), c| {
+        let is_control = c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0C' | '\x1B');
+        (total + 1, control + usize::from(is_control))
+    });
+
+    if total == 0 {
+        0.0
+    } else {
+        control as f32 / total as f32
+    }
 }
 
 #[cfg(test)]
@@ -83,4 +306,92 @@ mod tests {
             "Binary content should be detected as binary"
         );
     }
+
+    #[test]
+    fn test_is_binary_with_legacy_encoding() {
+        assert!(
+            !is_binary(b"caf\xE9 cr\xE8me"),
+            "Latin-1 text should not be binary"
+        );
+    }
+
+    #[test]
+    fn test_analyze_utf8() {
+        let analysis = analyze("Grüße\n".as_bytes());
+        assert_eq!(analysis.encoding, Some(TextEncoding::Utf8));
+        assert!(!analysis.binary);
+        assert!(!analysis.bom);
+        assert_eq!(analysis.confidence, 1.0, "Plain text should be certain");
+    }
+
+    #[test]
+    fn test_analyze_utf8_bom() {
+        let analysis = analyze(b"\xEF\xBB\xBFHello");
+        assert_eq!(analysis.encoding, Some(TextEncoding::Utf8));
+        assert!(analysis.bom, "UTF-8 BOM should be detected");
+        assert!(!analysis.binary);
+    }
+
+    #[test]
+    fn test_analyze_utf16_bom() {
+        let le = analyze(&utf16_le("\u{FEFF}Hello, world!"));
+        assert_eq!(le.encoding, Some(TextEncoding::Utf16Le));
+        assert!(le.bom && !le.binary, "UTF-16LE with BOM should be text");
+
+        let be = analyze(&utf16_be("\u{FEFF}Hello, world!"));
+        assert_eq!(be.encoding, Some(TextEncoding::Utf16Be));
+        assert!(be.bom && !be.binary, "UTF-16BE with BOM should be text");
+    }
+
+    #[test]
+    fn test_analyze_utf16_without_bom() {
+        let le = analyze(&utf16_le("password=secret\r\n"));
+        assert_eq!(le.encoding, Some(TextEncoding::Utf16Le));
+        assert!(!le.binary, "UTF-16LE without BOM should be text");
+        assert!(le.confidence >= MIN_UTF16_NULL_RATIO);
+
+        let be = analyze(&utf16_be("password=secret\r\n"));
+        assert_eq!(be.encoding, Some(TextEncoding::Utf16Be));
+        assert!(!be.binary, "UTF-16BE without BOM should be text");
+    }
+
+    #[test]
+    fn test_analyze_binary() {
+        let analysis = analyze(b"\x89PNG\r\n\x1A\n\x00\x00\x00\x0DIHDR\x00\x00\x01\x00");
+        assert!(analysis.binary, "PNG header should be binary");
+        assert_eq!(analysis.encoding, None);
+        assert!(analysis.confidence > 0.5, "Binary should be likely");
+    }
+
+    #[test]
+    fn test_analyze_invalid_utf16_bom() {
+        // BOM followed by an unpaired surrogate
+        let analysis = analyze(b"\xFF\xFE\x00\xD8\x01\x02");
+        assert_ne!(
+            analysis.encoding,
+            Some(TextEncoding::Utf16Le),
+            "Invalid UTF-16 should not be detected as text"
+        );
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let mut content = b"\xFF\xFE".to_vec();
+        content.extend(utf16_le("Grüße"));
+        assert_eq!(decode_text(&content), Some("Grüße".to_string()));
+        assert_eq!(decode_text(&utf16_be("Hi")), Some("Hi".to_string()));
+        assert_eq!(
+            decode_text(b"\x00\x01\x02"),
+            None,
+            "Binary should not decode"
+        );
+    }
+
+    fn utf16_le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn utf16_be(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+    }
 }
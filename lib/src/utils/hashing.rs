@@ -1,5 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
+//! SHA-256 hashing of tokens and secrets.
+//!
+//! The SHA extensions of x86 and ARMv8 CPUs are used if available (detected at runtime), WASM
+//! builds use the `simd128` backend if built with that target feature (see `wasm/build.sh`).
+
 use base64::Engine;
 use sha2::{Digest, Sha256};
 
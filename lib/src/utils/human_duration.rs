@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Duration parsing utilities for human-readable input.
+//!
+//! This module provides the parser for TTLs and other durations given on the command line,
+//! in environment variables or config files, supporting plain seconds as well as compound
+//! values like `2h30m`.
+
+use std::time::Duration;
+
+/// Parse a duration from a human-readable string.
+///
+/// # Supported Formats
+///
+/// - **Plain numbers**: Interpreted as seconds (e.g., `"3600"` → 1 hour)
+/// - **Units**: Numbers followed by a unit (e.g., `"90m"`, `"2h"`, `"7d"`)
+/// - **Compound values**: Multiple components are added up (e.g., `"2h30m"`, `"1d 12h"`)
+/// - **Whitespace**: Leading and trailing whitespace and whitespace between components is ignored
+///
+/// # Units
+///
+/// - `s`, `sec`, `secs`, `second`, `seconds`
+/// - `m`, `min`, `mins`, `minute`, `minutes`
+/// - `h`, `hr`, `hrs`, `hour`, `hours`
+/// - `d`, `day`, `days`
+/// - `w`, `week`, `weeks`
+///
+/// # Arguments
+///
+/// * `s` - A string slice containing the duration specification
+///
+/// # Returns
+///
+/// * `Ok(Duration)` - The parsed duration
+/// * `Err(String)` - An error message describing why parsing failed
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use hakanai_lib::utils::human_duration::parse;
+///
+/// assert_eq!(parse("3600"), Ok(Duration::from_secs(3600)));
+/// assert_eq!(parse("90m"), Ok(Duration::from_secs(5400)));
+/// assert_eq!(parse("2h30m"), Ok(Duration::from_secs(9000)));
+/// assert_eq!(parse("1d12h"), Ok(Duration::from_secs(129600)));
+/// assert_eq!(parse("1 week"), Ok(Duration::from_secs(604800)));
+///
+/// // Error cases
+/// assert!(parse("").is_err());
+/// assert!(parse("1x").is_err());
+/// assert!(parse("h").is_err());
+/// ```
+pub fn parse(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("Duration must not be empty".to_string());
+    }
+
+    // Handle plain numbers (assume seconds)
+    if let Ok(secs) = s.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(format!("Invalid duration '{s}': expected a number"));
+        }
+        let number: u64 = rest[..digits]
+            .parse()
+            .map_err(|_| format!("Invalid duration '{s}': number too large"))?;
+        rest = rest[digits..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        let factor = unit_secs(unit).ok_or_else(|| {
+            format!("Invalid duration '{s}': unknown unit '{unit}'. Use s, m, h, d or w")
+        })?;
+        rest = rest[unit_len..].trim_start();
+
+        total = number
+            .checked_mul(factor)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("Invalid duration '{s}': duration too large"))?;
+    }
+
+    Ok(Duration::from_secs(total))
+}
+
+fn unit_secs(unit: &str) -> Option<u64> {
+    match unit.to_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(60),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(60 * 60),
+        "d" | "day" | "days" => Some(24 * 60 * 60),
+        "w" | "week" | "weeks" => Some(7 * 24 * 60 * 60),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(
+            parse("3600"),
+            Ok(Duration::from_secs(3600)),
+            "Plain numbers should be seconds"
+        );
+        assert_eq!(parse("0"), Ok(Duration::ZERO), "Zero should be allowed");
+        assert_eq!(
+            parse("  60  "),
+            Ok(Duration::from_secs(60)),
+            "Whitespace should be trimmed"
+        );
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(parse("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse("90m"), Ok(Duration::from_secs(90 * 60)));
+        assert_eq!(parse("2h"), Ok(Duration::from_secs(2 * 3600)));
+        assert_eq!(parse("7d"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse("1w"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(
+            parse("5 minutes"),
+            Ok(Duration::from_secs(300)),
+            "Long unit names should be supported"
+        );
+        assert_eq!(
+            parse("1H"),
+            Ok(Duration::from_secs(3600)),
+            "Units should be case insensitive"
+        );
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        assert_eq!(
+            parse("2h30m"),
+            Ok(Duration::from_secs(9000)),
+            "Components should be added up"
+        );
+        assert_eq!(
+            parse("1d12h"),
+            Ok(Duration::from_secs(129600)),
+            "Days and hours should be added up"
+        );
+        assert_eq!(
+            parse("1d 2h 3m 4s"),
+            Ok(Duration::from_secs(86400 + 7200 + 180 + 4)),
+            "Components separated by whitespace should be supported"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in ["", "   ", "abc", "h", "1x", "1.5h", "-1h", "1h30", "2h m"] {
+            assert!(parse(input).is_err(), "'{input}' should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        let result = parse(&format!("{}w", u64::MAX));
+        assert!(
+            result.is_err(),
+            "Overflowing durations should be rejected, got: {result:?}"
+        );
+    }
+}
@@ -3,7 +3,11 @@
 //! Size parsing utilities for human-readable input.
 //!
 //! This module provides functionality to parse size values from human-readable strings,
-//! supporting bytes, kilobytes, and megabytes with intuitive suffix notation.
+//! supporting bytes, kilobytes, and megabytes with intuitive suffix notation, and to format
+//! byte counts for output.
+
+/// Binary units used by [`format_size`].
+const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
 
 /// Parse a size limit from a human-readable string.
 ///
@@ -93,6 +97,44 @@ pub fn parse(s: &str) -> Result<i64, String> {
     }
 }
 
+/// Format a byte count as human-readable size.
+///
+/// Sizes below 1 KiB are shown in bytes, larger sizes in the largest binary (1024-based) unit
+/// with one decimal place, which is omitted for whole numbers. The output does not depend on
+/// the locale, the decimal separator is always a dot.
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::utils::human_size::format_size;
+///
+/// assert_eq!(format_size(512), "512 B");
+/// assert_eq!(format_size(4096), "4 KiB");
+/// assert_eq!(format_size(1536), "1.5 KiB");
+/// assert_eq!(format_size(1468006), "1.4 MiB");
+/// assert_eq!(format_size(10 * 1024 * 1024), "10 MiB");
+/// ```
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    // switch units based on the rounded value, so 1023.96 KiB is shown as 1 MiB
+    while (value * 10.0).round() / 10.0 >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    let rounded = (value * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{rounded:.0} {}", UNITS[unit])
+    } else {
+        format!("{rounded:.1} {}", UNITS[unit])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +336,50 @@ mod tests {
             "Large kilobyte values should parse correctly"
         );
     }
+
+    #[test]
+    fn test_format_size_bytes() {
+        assert_eq!(format_size(0), "0 B", "Zero should be shown in bytes");
+        assert_eq!(
+            format_size(1023),
+            "1023 B",
+            "Sizes below 1 KiB should be shown in bytes"
+        );
+    }
+
+    #[test]
+    fn test_format_size_units() {
+        assert_eq!(format_size(1024), "1 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(1468006), "1.4 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3 GiB");
+        assert_eq!(
+            format_size(u64::MAX),
+            "16777216 TiB",
+            "Largest unit should be used for huge sizes"
+        );
+    }
+
+    #[test]
+    fn test_format_size_rounding() {
+        assert_eq!(
+            format_size(1024 * 1024 - 1),
+            "1 MiB",
+            "Values rounding to 1024 should switch to the next unit"
+        );
+        assert_eq!(
+            format_size(1024 + 50),
+            "1 KiB",
+            "Values should be rounded to one decimal place"
+        );
+    }
+
+    #[test]
+    fn test_format_size_roundtrip() {
+        assert_eq!(
+            parse("1.5k").map(|b| format_size(b as u64)),
+            Ok("1.5 KiB".to_string()),
+            "Formatted sizes should match parsed sizes"
+        );
+    }
 }
@@ -5,8 +5,13 @@
 //! This module provides utilities for parsing IP addresses and CIDR notation,
 //! supporting both IPv4 and IPv6 addresses. Single IP addresses are automatically
 //! converted to /32 (IPv4) or /128 (IPv6) CIDR ranges.
+//!
+//! IP ranges given by users can also reference files with one CIDR per line (`@file`) or
+//! hostnames, see [`IpRangeSource`].
 
-use std::net::IpAddr;
+use std::collections::BTreeSet;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::path::PathBuf;
 
 use serde::{Deserialize, Deserializer};
 
@@ -67,6 +72,161 @@ pub fn parse_ipnet(s: &str) -> Result<ipnet::IpNet, String> {
     Err(format!("Invalid IP address or CIDR notation: {}", s))
 }
 
+/// Source of IP ranges given by a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpRangeSource {
+    /// IP address or CIDR range.
+    Net(ipnet::IpNet),
+
+    /// File with one IP address or CIDR range per line (`@path`).
+    File(PathBuf),
+
+    /// Hostname whose addresses are allowed.
+    Host(String),
+}
+
+impl From<ipnet::IpNet> for IpRangeSource {
+    fn from(net: ipnet::IpNet) -> Self {
+        IpRangeSource::Net(net)
+    }
+}
+
+impl std::fmt::Display for IpRangeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IpRangeSource::Net(net) => write!(f, "{net}"),
+            IpRangeSource::File(path) => write!(f, "@{}", path.display()),
+            IpRangeSource::Host(host) => write!(f, "{host}"),
+        }
+    }
+}
+
+impl IpRangeSource {
+    /// Returns the IP ranges of the source, reading the file or resolving the hostname.
+    ///
+    /// Hostnames are resolved via the system resolver, the addresses are converted to host
+    /// routes. Later changes of the DNS records are not reflected in the result.
+    pub fn resolve(&self) -> Result<Vec<ipnet::IpNet>, String> {
+        match self {
+            IpRangeSource::Net(net) => Ok(vec![*net]),
+            IpRangeSource::File(path) => {
+                let content = std::fs::read_to_string(path).map_err(|e| {
+                    format!("Failed to read IP ranges from {}: {e}", path.display())
+                })?;
+                parse_ipnet_list(&content)
+                    .map_err(|e| format!("Invalid IP ranges in {}: {e}", path.display()))
+            }
+            IpRangeSource::Host(host) => resolve_host(host),
+        }
+    }
+}
+
+/// Parses an IP range source from a string.
+///
+/// Values starting with `@` reference a file (see [`parse_ipnet_list`] for the format),
+/// IP addresses and CIDR ranges are parsed by [`parse_ipnet`] and anything else that is a
+/// syntactically valid hostname is resolved later by [`IpRangeSource::resolve`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+/// use hakanai_lib::utils::ip::{IpRangeSource, parse_ip_range_source};
+///
+/// assert!(matches!(parse_ip_range_source("10.0.0.0/8"), Ok(IpRangeSource::Net(_))));
+/// assert_eq!(
+///     parse_ip_range_source("@office.txt"),
+///     Ok(IpRangeSource::File(PathBuf::from("office.txt")))
+/// );
+/// assert_eq!(
+///     parse_ip_range_source("vpn.example.com"),
+///     Ok(IpRangeSource::Host("vpn.example.com".to_string()))
+/// );
+/// assert!(parse_ip_range_source("192.168.1.256").is_err());
+/// ```
+pub fn parse_ip_range_source(s: &str) -> Result<IpRangeSource, String> {
+    let s = s.trim();
+    if let Some(path) = s.strip_prefix('@') {
+        if path.is_empty() {
+            return Err("File name of IP ranges must not be empty".to_string());
+        }
+        return Ok(IpRangeSource::File(PathBuf::from(path)));
+    }
+
+    if let Ok(net) = parse_ipnet(s) {
+        return Ok(IpRangeSource::Net(net));
+    }
+
+    if is_hostname(s) {
+        return Ok(IpRangeSource::Host(s.to_lowercase()));
+    }
+
+    Err(format!(
+        "Invalid IP address, CIDR notation, hostname or @file: {s}"
+    ))
+}
+
+/// Parses a list of IP addresses or CIDR ranges, one per line.
+///
+/// Empty lines and comments starting with `#` are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use hakanai_lib::utils::ip::parse_ipnet_list;
+///
+/// let nets = parse_ipnet_list("# office\n10.0.0.0/8\n\n192.168.1.1 # printer\n")
+///     .expect("Failed to parse list");
+/// assert_eq!(nets.len(), 2);
+/// ```
+pub fn parse_ipnet_list(content: &str) -> Result<Vec<ipnet::IpNet>, String> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            (!line.is_empty()).then_some((idx + 1, line))
+        })
+        .map(|(line_no, line)| parse_ipnet(line).map_err(|e| format!("line {line_no}: {e}")))
+        .collect()
+}
+
+fn resolve_host(host: &str) -> Result<Vec<ipnet::IpNet>, String> {
+    let addresses: BTreeSet<IpAddr> = (host, 0)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .map(|addr| addr.ip())
+        .collect();
+
+    if addresses.is_empty() {
+        return Err(format!("Hostname {host} has no addresses"));
+    }
+
+    Ok(addresses.into_iter().map(ipnet::IpNet::from).collect())
+}
+
+/// Checks the syntax of a hostname, the last label must not be numeric to avoid mistaking
+/// malformed IPv4 addresses for hostnames.
+fn is_hostname(s: &str) -> bool {
+    if s.is_empty() || s.len() > 253 {
+        return false;
+    }
+
+    let labels: Vec<&str> = s.trim_end_matches('.').split('.').collect();
+    let valid_labels = labels.iter().all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+
+    valid_labels
+        && labels
+            .last()
+            .is_some_and(|tld| tld.chars().any(|c| c.is_ascii_alphabetic()))
+}
+
 /// Custom deserializer for converting JSON string arrays to Vec<ipnet::IpNet>
 pub fn deserialize_ip_nets<'de, D>(deserializer: D) -> Result<Option<Vec<ipnet::IpNet>>, D::Error>
 where
@@ -140,7 +300,7 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let result = parse_ipnet(input).expect(&format!("Failed to parse {input}"));
+            let result = parse_ipnet(input).unwrap_or_else(|_| panic!("Failed to parse {input}"));
             assert_eq!(
                 result.to_string(),
                 expected,
@@ -219,7 +379,7 @@ mod tests {
         ];
 
         for (input, expected) in cases {
-            let result = parse_ipnet(input).expect(&format!("Failed to parse {input}"));
+            let result = parse_ipnet(input).unwrap_or_else(|_| panic!("Failed to parse {input}"));
             assert_eq!(
                 result.to_string(),
                 expected,
@@ -228,4 +388,99 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_ip_range_source() {
+        assert_eq!(
+            parse_ip_range_source("10.0.0.0/8"),
+            Ok(IpRangeSource::Net(
+                "10.0.0.0/8".parse().expect("valid CIDR")
+            )),
+            "CIDR ranges should be parsed directly"
+        );
+        assert_eq!(
+            parse_ip_range_source("@/etc/hakanai/office.txt"),
+            Ok(IpRangeSource::File(PathBuf::from(
+                "/etc/hakanai/office.txt"
+            ))),
+            "@ should reference a file"
+        );
+        assert_eq!(
+            parse_ip_range_source("VPN.Example.com"),
+            Ok(IpRangeSource::Host("vpn.example.com".to_string())),
+            "Hostnames should be normalized to lowercase"
+        );
+    }
+
+    #[test]
+    fn test_parse_ip_range_source_invalid() {
+        for invalid in [
+            "",
+            "@",
+            "192.168.1.256",
+            "192.168.1",
+            "host_name",
+            "-host",
+            "a..b",
+        ] {
+            let result = parse_ip_range_source(invalid);
+            assert!(
+                result.is_err(),
+                "'{invalid}' should be rejected, got: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_ipnet_list() {
+        let nets =
+            parse_ipnet_list("# office\n10.0.0.0/8\n\n  192.168.1.1  # printer\n2001:db8::/32")
+                .expect("Failed to parse list");
+        let nets: Vec<String> = nets.iter().map(|net| net.to_string()).collect();
+        assert_eq!(nets, vec!["10.0.0.0/8", "192.168.1.1/32", "2001:db8::/32"]);
+    }
+
+    #[test]
+    fn test_parse_ipnet_list_invalid_line() {
+        let result = parse_ipnet_list("10.0.0.0/8\nnot-an-ip\n");
+        assert!(
+            result.as_ref().is_err_and(|e| e.starts_with("line 2:")),
+            "Error should contain the line number, got: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_resolve_file() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!("hakanai-ip-test-{}.txt", std::process::id()));
+        std::fs::write(&path, "10.0.0.0/8\n172.16.0.0/12\n")?;
+
+        let result = IpRangeSource::File(path.clone()).resolve();
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(
+            result?.len(),
+            2,
+            "All ranges of the file should be returned"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_missing_file() {
+        let result = IpRangeSource::File(PathBuf::from("/nonexistent/ranges.txt")).resolve();
+        assert!(result.is_err(), "Missing file should be rejected");
+    }
+
+    #[test]
+    fn test_resolve_localhost() {
+        let result = IpRangeSource::Host("localhost".to_string())
+            .resolve()
+            .expect("Failed to resolve localhost");
+        assert!(
+            result
+                .iter()
+                .all(|net| net.addr().is_loopback() && net.prefix_len() == net.max_prefix_len()),
+            "localhost should resolve to loopback host routes, got: {result:?}"
+        );
+    }
 }
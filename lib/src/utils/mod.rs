@@ -8,17 +8,32 @@
 //!
 //! # Submodules
 //!
+//! - [`asn`] - Functions for looking up well-known ASNs by organization (feature `asn-db`)
 //! - [`content_analysis`] - Functions for analyzing content types (binary vs text detection)
 //! - [`hashing`] - Functions for hashing data
 //! - [`ip`] - Functions for parsing IP addresses and CIDR notation
+//! - [`human_duration`] - Functions for parsing human-readable durations such as TTLs
 //! - [`human_size`] - Functions for parsing human-readable size strings
+//! - [`qr_code`] - Functions for generating QR codes of secret links (feature `qr`)
+//! - [`secret_detection`] - Functions for detecting well-known credential formats
 //! - [`timestamp`] - Functions for handling and formatting timestamps
+//! - [`version`] - Functions for comparing client and server versions
+//! - [`wordlist`] - Functions for generating diceware-style passphrases (feature `wordlist`)
 //!
 
+#[cfg(feature = "asn-db")]
+pub mod asn;
 pub mod content_analysis;
 pub mod hashing;
+pub mod human_duration;
 pub mod human_size;
 pub mod ip;
+#[cfg(feature = "qr")]
+pub mod qr_code;
+pub mod secret_detection;
 pub mod timestamp;
+pub mod version;
+#[cfg(feature = "wordlist")]
+pub mod wordlist;
 
 pub mod test;
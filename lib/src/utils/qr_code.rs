@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! QR code generation for secret links.
+//!
+//! Used by the web interface (via WASM) and the server, so both render identical codes.
+
+use std::fmt::Write;
+
+use image::codecs::png::PngEncoder;
+use image::{ExtendedColorType, ImageEncoder};
+use qrcode::{Color, EcLevel, QrCode, Version};
+use thiserror::Error;
+
+/// Maximum input length, the capacity of the largest QR code (version 40, low error correction).
+pub const MAX_TEXT_LENGTH: usize = 2953;
+
+/// Maximum size of the rendered QR code in pixels.
+pub const MAX_SIZE: u32 = 2048;
+
+/// Maximum margin (quiet zone) in modules.
+pub const MAX_MARGIN: u32 = 16;
+
+/// Margin recommended by the QR code specification in modules.
+pub const DEFAULT_MARGIN: u32 = 4;
+
+/// Largest version preferred to keep the modules large enough for reliable scanning.
+///
+/// Higher error correction levels are only used if the code still fits into this version.
+const PREFERRED_MAX_VERSION: i16 = 10;
+
+/// Error correction levels in order of preference.
+const EC_LEVELS: [EcLevel; 4] = [EcLevel::H, EcLevel::Q, EcLevel::M, EcLevel::L];
+
+/// Errors that can occur while generating a QR code.
+#[derive(Debug, Error, PartialEq)]
+pub enum QrCodeError {
+    #[error("Input text is too long")]
+    TextTooLong,
+
+    #[error("Size is out of range")]
+    SizeOutOfRange,
+
+    #[error("Margin is too large")]
+    MarginTooLarge,
+
+    #[error("Failed to generate QR code: data too long")]
+    DataTooLong,
+
+    #[error("Failed to encode PNG: {0}")]
+    Png(String),
+}
+
+/// Generates a QR code of the text as SVG with the size in pixels and the margin in modules.
+pub fn to_svg(text: &str, size: u32, margin: u32) -> Result<String, QrCodeError> {
+    ensure_margin_is_valid(margin)?;
+    let code = encode(text, size)?;
+    Ok(render_svg(&code, size, margin))
+}
+
+/// Generates a QR code of the text as PNG with at least the size in pixels and the margin in
+/// modules.
+pub fn to_png(text: &str, size: u32, margin: u32) -> Result<Vec<u8>, QrCodeError> {
+    ensure_margin_is_valid(margin)?;
+    let code = encode(text, size)?;
+    render_png(&code, size, margin)
+}
+
+/// Checks the margin (quiet zone) is not larger than [`MAX_MARGIN`].
+pub fn ensure_margin_is_valid(margin: u32) -> Result<(), QrCodeError> {
+    if margin > MAX_MARGIN {
+        return Err(QrCodeError::MarginTooLarge);
+    }
+
+    Ok(())
+}
+
+/// Encodes the text with the highest error correction level fitting the preferred version.
+///
+/// Falls back to the smallest code if the text does not fit the preferred version at all.
+fn encode(text: &str, size: u32) -> Result<QrCode, QrCodeError> {
+    if text.len() > MAX_TEXT_LENGTH {
+        return Err(QrCodeError::TextTooLong);
+    }
+
+    if size == 0 || size > MAX_SIZE {
+        return Err(QrCodeError::SizeOutOfRange);
+    }
+
+    let mut fallback = None;
+    for level in EC_LEVELS {
+        let Ok(code) = QrCode::with_error_correction_level(text, level) else {
+            continue;
+        };
+
+        if is_preferred_version(code.version()) {
+            return Ok(code);
+        }
+        fallback = Some(code);
+    }
+
+    fallback.ok_or(QrCodeError::DataTooLong)
+}
+
+fn is_preferred_version(version: Version) -> bool {
+    match version {
+        Version::Normal(v) => v <= PREFERRED_MAX_VERSION,
+        Version::Micro(_) => true,
+    }
+}
+
+/// Returns the dimension of the code including the margin in modules.
+fn dimension(code: &QrCode, margin: u32) -> u32 {
+    code.width() as u32 + 2 * margin
+}
+
+/// Renders the code as SVG scaled to the size, dark modules are drawn as a single path.
+fn render_svg(code: &QrCode, size: u32, margin: u32) -> String {
+    let dimension = dimension(code, margin);
+    let width = code.width();
+
+    let mut path = String::new();
+    for (i, color) in code.to_colors().iter().enumerate() {
+        if *color == Color::Dark {
+            let x = (i % width) as u32 + margin;
+            let y = (i / width) as u32 + margin;
+            let _ = write!(path, "M{x} {y}h1v1h-1z");
+        }
+    }
+
+    format!(
+        concat!(
+            r#"<?xml version="1.0" standalone="yes"?>"#,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{size}" height="{size}" viewBox="0 0 {dim} {dim}" shape-rendering="crispEdges">"#,
+            r##"<rect width="{dim}" height="{dim}" fill="#fff"/>"##,
+            r##"<path d="{path}" fill="#000"/>"##,
+            "</svg>"
+        ),
+        size = size,
+        dim = dimension,
+        path = path
+    )
+}
+
+/// Renders the code as grayscale PNG with at least the size in pixels.
+///
+/// Modules are scaled by whole pixels to keep the edges sharp.
+fn render_png(code: &QrCode, size: u32, margin: u32) -> Result<Vec<u8>, QrCodeError> {
+    let dimension = dimension(code, margin);
+    let scale = size.div_ceil(dimension).max(1);
+    let pixels = dimension * scale;
+
+    let width = code.width();
+    let colors = code.to_colors();
+    let mut image = vec![255u8; (pixels * pixels) as usize];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+
+        let x = ((i % width) as u32 + margin) * scale;
+        let y = ((i / width) as u32 + margin) * scale;
+        for row in y..y + scale {
+            let start = (row * pixels + x) as usize;
+            image[start..start + scale as usize].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(&image, pixels, pixels, ExtendedColorType::L8)
+        .map_err(|e| QrCodeError::Png(e.to_string()))?;
+
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_short_text_uses_high_error_correction() -> Result<(), QrCodeError> {
+        let code = encode("https://example.com/s/abc#key", 200)?;
+        assert_eq!(
+            code.error_correction_level(),
+            EcLevel::H,
+            "Short texts should use the highest error correction level"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_long_text() -> Result<(), QrCodeError> {
+        let text = format!("https://example.com/s/abc#{}", "k".repeat(1500));
+
+        let code = encode(&text, 200)?;
+        assert_eq!(
+            code.error_correction_level(),
+            EcLevel::L,
+            "Long texts should fall back to the smallest code"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_limits() {
+        assert!(encode(&"a".repeat(MAX_TEXT_LENGTH + 1), 200).is_err());
+        assert!(encode("text", MAX_SIZE + 1).is_err());
+        assert!(encode("text", 0).is_err());
+    }
+
+    #[test]
+    fn test_render_svg_margin() -> Result<(), QrCodeError> {
+        let code = encode("text", 200)?;
+        let width = code.width() as u32;
+
+        let svg = render_svg(&code, 200, 0);
+        assert!(svg.contains(&format!(r#"viewBox="0 0 {width} {width}""#)));
+
+        let svg = render_svg(&code, 200, 2);
+        let dimension = width + 4;
+        assert!(
+            svg.contains(&format!(r#"viewBox="0 0 {dimension} {dimension}""#)),
+            "Margin should be added on both sides"
+        );
+        assert!(svg.contains(r#"width="200" height="200""#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_png() -> Result<(), QrCodeError> {
+        let code = encode("text", 200)?;
+
+        let png = render_png(&code, 200, DEFAULT_MARGIN)?;
+        assert_eq!(
+            &png[..8],
+            b"\x89PNG\r\n\x1a\n",
+            "Output should be a PNG image"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_margin_limit() {
+        assert_eq!(
+            to_svg("text", 200, MAX_MARGIN + 1),
+            Err(QrCodeError::MarginTooLarge),
+            "Margins larger than the maximum should be rejected"
+        );
+        assert!(to_png("text", 200, MAX_MARGIN).is_ok());
+    }
+}
@@ -2,9 +2,13 @@
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Returns the current timestamp in seconds since the Unix epoch.
+pub fn now() -> Result<u64, std::time::SystemTimeError> {
+    let duration = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    Ok(duration.as_secs())
+}
+
 /// Returns the current timestamp in seconds since the Unix epoch.
 pub fn now_string() -> Result<String, std::time::SystemTimeError> {
-    let now = SystemTime::now();
-    let duration = now.duration_since(UNIX_EPOCH)?;
-    Ok(format!("{}", duration.as_secs()))
+    Ok(format!("{}", now()?))
 }
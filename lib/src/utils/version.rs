@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Version comparison between clients and servers.
+//!
+//! Servers report their version via `/config.json`. Clients compare it against their own
+//! version to warn about known incompatibilities, e.g. changes of the envelope format.
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::utils::version;
+//!
+//! let warnings = version::compatibility_warnings("3.1.0", "2.9.1");
+//! assert!(!warnings.is_empty());
+//! assert!(version::compatibility_warnings("3.1.0", "3.0.7").is_empty());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A semantic version (`MAJOR.MINOR.PATCH`), pre-release and build metadata are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let core = s
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default();
+
+        let mut parts = core.split('.').map(|part| part.parse::<u64>());
+        let (Some(Ok(major)), minor, patch, None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(format!("invalid version '{s}'"));
+        };
+
+        let minor = minor
+            .transpose()
+            .map_err(|_| format!("invalid version '{s}'"))?;
+        let patch = patch
+            .transpose()
+            .map_err(|_| format!("invalid version '{s}'"))?;
+        Ok(Self::new(major, minor.unwrap_or(0), patch.unwrap_or(0)))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A change making clients and servers on different sides of a version incompatible.
+struct KnownIncompatibility {
+    /// First version with the change
+    since: Version,
+
+    /// Description of the change
+    reason: &'static str,
+}
+
+const KNOWN_INCOMPATIBILITIES: &[KnownIncompatibility] = &[KnownIncompatibility {
+    since: Version::new(3, 0, 0),
+    reason: "secret identifiers changed from UUIDs to ULIDs and secrets are encoded with MessagePack instead of JSON",
+}];
+
+/// Returns warnings about known incompatibilities between the client and server version.
+///
+/// Unparsable versions yield a warning, a newer major version of the server yields a warning
+/// recommending to update the client.
+pub fn compatibility_warnings(client_version: &str, server_version: &str) -> Vec<String> {
+    let (client, server) = match (
+        client_version.parse::<Version>(),
+        server_version.parse::<Version>(),
+    ) {
+        (Ok(client), Ok(server)) => (client, server),
+        (Err(e), _) | (_, Err(e)) => return vec![format!("Unable to compare versions: {e}")],
+    };
+
+    let mut warnings: Vec<String> = KNOWN_INCOMPATIBILITIES
+        .iter()
+        .filter(|incompatibility| {
+            (client < incompatibility.since) != (server < incompatibility.since)
+        })
+        .map(|incompatibility| {
+            format!(
+                "Client {client} and server {server} are incompatible: since {} {}",
+                incompatibility.since, incompatibility.reason
+            )
+        })
+        .collect();
+
+    if warnings.is_empty() && server.major > client.major {
+        warnings.push(format!(
+            "Server {server} is a newer major version than client {client}, please update the client"
+        ));
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() -> Result<(), String> {
+        assert_eq!("3.0.7".parse::<Version>()?, Version::new(3, 0, 7));
+        assert_eq!("v3.1".parse::<Version>()?, Version::new(3, 1, 0));
+        assert_eq!(
+            "3.2.0-beta.1+abc".parse::<Version>()?,
+            Version::new(3, 2, 0)
+        );
+        assert!("".parse::<Version>().is_err());
+        assert!("3.x".parse::<Version>().is_err());
+        assert!("1.2.3.4".parse::<Version>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compatibility_warnings_compatible() {
+        assert!(compatibility_warnings("3.0.7", "3.0.7").is_empty());
+        assert!(compatibility_warnings("3.1.0", "3.0.0").is_empty());
+        assert!(
+            compatibility_warnings("3.0.0", "3.5.0").is_empty(),
+            "Newer minor versions of the server should be compatible"
+        );
+    }
+
+    #[test]
+    fn test_compatibility_warnings_known_incompatibility() {
+        for (client, server) in [("3.0.7", "2.9.1"), ("2.9.1", "3.0.7")] {
+            let warnings = compatibility_warnings(client, server);
+            assert_eq!(warnings.len(), 1, "{client} vs {server} should warn");
+            assert!(
+                warnings[0].contains("MessagePack"),
+                "Warning should explain the incompatibility: {}",
+                warnings[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_compatibility_warnings_newer_major_server() {
+        let warnings = compatibility_warnings("3.0.7", "4.0.0");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("update the client"));
+    }
+
+    #[test]
+    fn test_compatibility_warnings_invalid_version() {
+        assert_eq!(compatibility_warnings("3.0.7", "unknown").len(), 1);
+    }
+}
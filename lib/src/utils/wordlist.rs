@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Diceware-style passphrase generation.
+//!
+//! Passphrases consist of randomly chosen words of a list with 1024 short English words, each word
+//! adds 10 bits of entropy. The generator is shared by the CLI and the web interface (via WASM).
+//!
+//! # Examples
+//!
+//! ```
+//! use hakanai_lib::utils::wordlist;
+//!
+//! let passphrase = wordlist::generate_passphrase(wordlist::DEFAULT_WORD_COUNT);
+//! assert_eq!(passphrase.split(wordlist::SEPARATOR).count(), 6);
+//! ```
+
+use rand::Rng;
+
+/// Number of words of generated passphrases by default (60 bits of entropy).
+pub const DEFAULT_WORD_COUNT: usize = 6;
+
+/// Separator between the words of generated passphrases.
+pub const SEPARATOR: char = '-';
+
+/// Words passphrases are generated from, the length is a power of two to avoid modulo bias.
+pub const WORDS: &[&str] = &[
+    "abacus", "abbey", "able", "absorb", "abyss", "accent", "acid", "acorn", "acre", "act",
+    "actor", "adapt", "add", "adjust", "adobe", "adult", "advice", "aerial", "affair", "affix",
+    "afraid", "after", "agenda", "agent", "agile", "aging", "agree", "ahead", "aim", "air",
+    "aisle", "alarm", "album", "alcove", "alert", "alibi", "alien", "align", "alike", "alive",
+    "alley", "allow", "alloy", "ally", "almond", "aloe", "alpaca", "alpha", "alpine", "amber",
+    "ample", "amulet", "amuse", "anchor", "angel", "anger", "angle", "angry", "ankle", "answer",
+    "antler", "anvil", "apex", "apple", "apron", "arcade", "arch", "archer", "arctic", "arena",
+    "argue", "arm", "armada", "armor", "army", "aroma", "arrow", "art", "artist", "ash", "aside",
+    "ask", "aspen", "asset", "astro", "atlas", "atom", "attic", "audio", "aunt", "autumn",
+    "avenue", "avid", "avoid", "awake", "award", "awning", "axis", "bacon", "badge", "badger",
+    "bagel", "baker", "bald", "ball", "ballad", "ballet", "bamboo", "banana", "band", "banjo",
+    "bank", "banner", "barley", "barn", "baron", "barrel", "basalt", "basil", "basin", "basket",
+    "batch", "bath", "bazaar", "beach", "beacon", "bead", "beam", "bean", "bear", "beard", "beast",
+    "beaver", "bed", "bee", "beef", "beet", "beetle", "begin", "bell", "belt", "bench", "beret",
+    "berry", "bike", "bind", "birch", "bird", "bison", "bistro", "bite", "black", "blade", "blank",
+    "blast", "blaze", "blend", "bless", "blimp", "blink", "bliss", "block", "bloom", "blue",
+    "blunt", "blush", "board", "boat", "bobcat", "body", "bold", "bolt", "bone", "bongo", "bonus",
+    "book", "boost", "boot", "border", "boss", "bottle", "bounce", "bowl", "box", "brain", "brake",
+    "branch", "brass", "brave", "bread", "break", "breeze", "brick", "bride", "bridge", "brief",
+    "bright", "brim", "brink", "brisk", "broad", "broom", "brown", "brush", "bubble", "bucket",
+    "buckle", "buddy", "budget", "bugle", "build", "bulb", "bulk", "bunch", "bunny", "burger",
+    "burst", "bush", "butter", "button", "buzz", "cabin", "cable", "cactus", "cadet", "cage",
+    "cake", "calm", "camel", "camera", "camp", "camper", "canal", "canary", "candle", "candy",
+    "cannon", "canoe", "canvas", "canyon", "cape", "card", "cargo", "carpet", "carrot", "cart",
+    "carve", "case", "cash", "cashew", "casino", "castle", "cat", "catch", "cause", "cave",
+    "cavern", "cedar", "celery", "cellar", "cello", "cement", "cereal", "chair", "chalk", "champ",
+    "change", "chant", "chapel", "charm", "chart", "chase", "cheek", "chef", "chess", "chest",
+    "chief", "child", "chili", "chimp", "chip", "choir", "chord", "cider", "city", "civic",
+    "claim", "clam", "clap", "clay", "clean", "clerk", "click", "cliff", "climb", "clip", "cloak",
+    "clock", "close", "cloth", "cloud", "clown", "club", "coach", "coast", "cobra", "cocoa",
+    "code", "coin", "cold", "comb", "comet", "comic", "coral", "cord", "core", "corn", "couch",
+    "count", "cover", "cow", "crab", "craft", "crane", "cream", "creek", "crew", "crisp", "crop",
+    "cross", "crowd", "crown", "crumb", "crush", "cube", "cup", "curb", "cure", "curl", "curve",
+    "cycle", "dairy", "daisy", "dance", "dash", "data", "dawn", "deal", "debut", "decor", "decoy",
+    "deer", "delta", "denim", "depth", "derby", "desk", "dial", "diary", "diet", "digit", "dingo",
+    "dish", "ditch", "dive", "dock", "dog", "dome", "door", "dose", "dot", "dough", "dove",
+    "drama", "draw", "dream", "dress", "drift", "drill", "drink", "drum", "duck", "dune", "dust",
+    "duty", "eagle", "early", "earth", "easel", "east", "echo", "edge", "eel", "egg", "eight",
+    "elbow", "elder", "elk", "elm", "ember", "empty", "emu", "enjoy", "enter", "entry", "envoy",
+    "equal", "erase", "essay", "ether", "event", "exact", "exit", "extra", "fable", "face", "fact",
+    "fade", "fair", "fairy", "faith", "fame", "fancy", "farm", "fault", "feast", "fence", "fern",
+    "ferry", "fever", "fiber", "field", "fig", "film", "final", "finch", "fire", "firm", "fish",
+    "five", "flag", "flame", "flash", "flask", "fleet", "flint", "float", "flock", "flood",
+    "floor", "flour", "fluid", "fluke", "flute", "foam", "focus", "fog", "folk", "font", "food",
+    "fork", "form", "fort", "fox", "frame", "fresh", "frog", "frost", "fruit", "fudge", "fuel",
+    "fun", "fur", "game", "gate", "gauge", "gear", "gecko", "gem", "giant", "gift", "gizmo",
+    "glad", "glass", "globe", "glove", "glow", "glue", "goat", "gold", "golf", "gong", "goose",
+    "grain", "grape", "graph", "grass", "gravy", "great", "green", "grid", "grill", "grin",
+    "grove", "guard", "guava", "guest", "guide", "gull", "gumbo", "gust", "habit", "hall", "halo",
+    "hand", "hard", "harp", "hat", "haven", "hawk", "hazel", "head", "heart", "heat", "hedge",
+    "help", "herb", "hero", "heron", "hill", "hint", "hippo", "hobby", "holly", "home", "honey",
+    "hood", "hook", "hope", "horn", "horse", "host", "hotel", "hour", "house", "hub", "hug",
+    "humor", "hunt", "husky", "ice", "icon", "idea", "igloo", "image", "inch", "index", "ink",
+    "inlet", "input", "iris", "iron", "ivory", "ivy", "jam", "jar", "jazz", "jeans", "jelly",
+    "jewel", "job", "jog", "join", "joke", "jolly", "joy", "judge", "juice", "jump", "jury",
+    "kale", "kayak", "kebab", "keep", "key", "kid", "kind", "king", "kiosk", "kite", "kiwi",
+    "knee", "knife", "knob", "knot", "koala", "label", "lace", "lady", "lake", "lamb", "lamp",
+    "lane", "large", "laser", "latch", "lava", "lawn", "layer", "leaf", "lemon", "lens", "level",
+    "lever", "light", "lilac", "lily", "lime", "linen", "lion", "list", "llama", "loaf", "lobby",
+    "local", "lock", "lodge", "logic", "lotus", "loud", "lunar", "lunch", "lynx", "lyric", "macaw",
+    "magic", "maid", "mail", "major", "mango", "manor", "maple", "march", "mask", "match", "medal",
+    "melon", "memo", "menu", "merit", "mesa", "metal", "mile", "milk", "mill", "mimic", "mind",
+    "mint", "mist", "mixer", "model", "modem", "mole", "month", "moon", "moose", "moss", "motel",
+    "motor", "mount", "mouse", "mouth", "movie", "mule", "music", "myth", "nail", "name", "navy",
+    "nest", "net", "never", "new", "night", "ninja", "noble", "noise", "nomad", "north", "nose",
+    "note", "novel", "nurse", "nut", "oak", "oasis", "ocean", "olive", "omega", "onion", "opal",
+    "open", "opera", "orbit", "orca", "order", "organ", "otter", "ounce", "outer", "oval", "oven",
+    "owl", "owner", "pace", "page", "paint", "palm", "panda", "panel", "paper", "park", "party",
+    "pasta", "patch", "path", "pause", "peach", "peak", "pear", "pecan", "pedal", "pen", "petal",
+    "piano", "piece", "pilot", "pine", "pink", "pipe", "pitch", "pizza", "place", "plant", "plate",
+    "play", "plaza", "plot", "plum", "poem", "poet", "point", "polar", "pole", "polka", "pond",
+    "pony", "pool", "poppy", "porch", "post", "power", "press", "prism", "prize", "proof", "proud",
+    "pulse", "pump", "pupil", "puppy", "quail", "queen", "quest", "quick", "quiet", "quilt",
+    "quiz", "race", "radar", "radio", "raft", "rail", "rain", "ramp", "ranch", "range", "rapid",
+    "raven", "razor", "ready", "realm", "reef", "relay", "relic", "rice", "ridge", "rifle", "ring",
+    "river", "road", "robin", "robot", "rodeo", "roof", "room", "root", "rope", "rose", "rotor",
+    "round", "route", "royal", "ruby", "rugby", "ruler", "rumor", "saga", "sail", "salad", "salsa",
+    "salt", "sand", "satin", "sauce", "scale", "scarf", "scene", "scout", "seat", "seed", "shark",
+    "shelf", "shell", "shine", "ship", "shirt", "shoe", "shore", "silk", "siren", "ski", "skunk",
+    "sky", "slate", "sled", "slice", "slide", "slope", "sloth", "smile", "smoke", "snack", "snail",
+    "snake", "snow", "soap", "sock", "sofa", "solar", "solid", "sonic", "soup", "south", "space",
+    "spark", "spice", "spike", "spin", "spoon", "sport", "spray", "squid", "staff", "stage",
+    "stamp", "star", "steam", "steel", "stem", "step", "stick", "stone", "stool", "storm", "story",
+    "stove", "strap", "straw", "style", "sugar", "suit", "sun", "super", "surf", "swamp", "swan",
+    "swing", "sword", "syrup", "table", "taco", "tail", "tango", "tank", "tape", "taxi", "tea",
+    "team", "tempo", "tent", "term", "test", "theme", "thumb", "tide", "tiger", "tile", "time",
+    "tiny", "title", "toast", "today", "token", "tonic", "tool", "topaz", "torch", "total",
+    "tower", "town", "toy", "track", "trade", "trail", "train", "tray", "treat", "tree", "trend",
+    "trial", "tribe", "trick", "trout", "truck", "trunk", "tulip", "tuna", "tutor", "twig", "twin",
+    "type", "ultra", "uncle", "union", "unit", "upper", "urban", "usage", "valve", "vapor", "vase",
+    "vault", "venue", "verse", "vest", "video", "view", "villa", "vine", "visa", "visit", "vital",
+    "vivid", "vocal", "voice", "wafer", "wagon", "waist", "wand", "water", "wave", "wax", "way",
+    "web", "wedge", "week", "well", "whale", "wheat", "wheel", "white", "wick", "wide", "width",
+    "wild", "wind", "wine", "wing", "wire", "wise", "wolf", "wood", "wool", "word", "work",
+    "world", "worm", "wrap", "wren", "wrist", "yacht", "yard", "yarn", "year", "yeti", "yodel",
+    "yoga", "young", "zebra", "zero", "zinc", "zone", "zoom",
+];
+
+/// Generates a passphrase of `word_count` random words separated by `-`.
+pub fn generate_passphrase(word_count: usize) -> String {
+    let mut rng = rand::rng();
+    (0..word_count)
+        .map(|_| WORDS[rng.next_u32() as usize % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(&SEPARATOR.to_string())
+}
+
+/// Returns the entropy in bits of a generated passphrase with `word_count` words.
+pub fn entropy_bits(word_count: usize) -> u32 {
+    word_count as u32 * WORDS.len().ilog2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_word_list() {
+        assert!(
+            WORDS.len().is_power_of_two(),
+            "Word count should be a power of two to avoid modulo bias"
+        );
+
+        let unique: HashSet<_> = WORDS.iter().collect();
+        assert_eq!(unique.len(), WORDS.len(), "Words should be unique");
+
+        for word in WORDS {
+            assert!(
+                word.len() >= 3 && word.chars().all(|c| c.is_ascii_lowercase()),
+                "'{word}' should consist of at least 3 lowercase letters"
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_passphrase() {
+        let passphrase = generate_passphrase(4);
+        let words: Vec<&str> = passphrase.split(SEPARATOR).collect();
+
+        assert_eq!(words.len(), 4);
+        assert!(
+            words.iter().all(|word| WORDS.contains(word)),
+            "All words should be part of the word list: {passphrase}"
+        );
+        assert_ne!(
+            generate_passphrase(DEFAULT_WORD_COUNT),
+            generate_passphrase(DEFAULT_WORD_COUNT),
+            "Passphrases should be random"
+        );
+    }
+
+    #[test]
+    fn test_entropy_bits() {
+        assert_eq!(entropy_bits(DEFAULT_WORD_COUNT), 60);
+        assert_eq!(entropy_bits(0), 0);
+    }
+}
@@ -1,27 +1,36 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::io::{Cursor, Read, Write};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use reqwest::{Body, Url};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, RequestBuilder, Url};
 use uuid::Uuid;
 
-use crate::client::{Client, ClientError};
-use crate::models::{PostSecretRequest, PostSecretResponse, restrictions};
-use crate::observer::DataTransferObserver;
+use crate::client::{Client, ClientError, SentSecret, StreamingTransport};
+use crate::crypto::encoding::{self, DecodingWriter, EncodingReader};
+use crate::link::{self, SecretLink};
+use crate::models::{PostSecretRequest, PostSecretResponse, ServerConfig, restrictions, secret};
+use crate::observer::{DataTransferObserver, ProgressTracker, TransferPhase};
 use crate::options::{SecretReceiveOptions, SecretSendOptions};
+use crate::spill::SpillBuffer;
+use crate::throttle::{self, Throttle};
 
-const SHORT_SECRET_PATH: &str = "s";
-const API_SECRET_PATH: &str = "api/v1/secret";
+const API_RAW_SECRET_PATH: &str = "api/v1/secret/raw";
+const API_MULTIPART_SECRET_PATH: &str = "api/v1/secret/multipart";
+const CONFIG_PATH: &str = "config.json";
 const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 const DEFAULT_USER_AGENT: &str = "hakanai-client";
 const DEFAULT_CHUNK_SIZE: usize = 8192; // 8 KB
 
+/// Start of the JSON of a secret, its data being the first field of [`PostSecretRequest`].
+const JSON_DATA_FIELD: &[u8] = br#"{"data":""#;
+
 pub struct WebClient {
     web_client: reqwest::Client,
-    upload_observer: Option<Arc<dyn DataTransferObserver>>,
 }
 
 impl WebClient {
@@ -29,11 +38,34 @@ impl WebClient {
     pub fn new() -> Self {
         WebClient {
             web_client: reqwest::Client::new(),
-            upload_observer: None,
         }
     }
 }
 
+/// Secret to be uploaded, either the base64 encoded envelope in memory or the ciphertext in a
+/// spill buffer.
+enum Upload {
+    Encoded(Vec<u8>),
+    Spilled(SpillBuffer),
+}
+
+impl Upload {
+    fn len(&self) -> usize {
+        match self {
+            Upload::Encoded(data) => data.len(),
+            Upload::Spilled(buffer) => buffer.len() as usize,
+        }
+    }
+}
+
+/// Source of the chunks of an upload body.
+enum BodySource {
+    /// Chunks are slices of the same buffer instead of copies.
+    Memory(Bytes),
+    /// Chunks are read one at a time, e.g. from a spill file.
+    Reader(Box<dyn Read + Send>),
+}
+
 #[async_trait]
 impl Client<Vec<u8>> for WebClient {
     async fn send_secret(
@@ -43,37 +75,127 @@ impl Client<Vec<u8>> for WebClient {
         ttl: Duration,
         token: String,
         opts: Option<SecretSendOptions>,
-    ) -> Result<Url, ClientError> {
-        let url = base_url.join(API_SECRET_PATH)?;
-
-        let secret = String::from_utf8(data)?;
-        let mut req = PostSecretRequest::new(secret, ttl);
+    ) -> Result<SentSecret, ClientError> {
+        self.send(base_url, Upload::Encoded(data), ttl, token, opts)
+            .await
+    }
 
+    async fn receive_secret(
+        &self,
+        url: Url,
+        opts: Option<SecretReceiveOptions>,
+    ) -> Result<Vec<u8>, ClientError> {
         let opt = opts.unwrap_or_default();
-        if let Some(restrictions) = opt.restrictions.clone() {
-            req = req.with_restrictions(restrictions);
+        let mut resp = self.get_secret(url, &opt).await?;
+
+        let mut secret = Vec::with_capacity(resp.content_length().unwrap_or(0) as usize);
+        self.read_body_in_chunks(&mut resp, &mut secret, opt.observer, opt.rate_limit)
+            .await?;
+
+        Ok(secret)
+    }
+
+    async fn get_server_config(&self, base_url: Url) -> Result<ServerConfig, ClientError> {
+        let url = base_url.join(CONFIG_PATH)?;
+        let request_id = Uuid::new_v4().to_string();
+
+        let resp = self
+            .web_client
+            .get(url)
+            .header("User-Agent", DEFAULT_USER_AGENT)
+            .header("X-Request-Id", request_id)
+            .timeout(DEFAULT_REQUEST_TIMEOUT)
+            .send()
+            .await?;
+
+        if resp.status() != reqwest::StatusCode::OK {
+            return Err(ClientError::Http(format!("HTTP error: {}", resp.status())));
         }
 
-        let (body, content_length) = self.post_secret_body_from_req(req, &opt)?;
+        let config = resp.json::<ServerConfig>().await?;
+        Ok(config)
+    }
+
+    fn streaming(&self) -> Option<&dyn StreamingTransport> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl StreamingTransport for WebClient {
+    async fn send_stream(
+        &self,
+        base_url: Url,
+        ciphertext: SpillBuffer,
+        ttl: Duration,
+        token: String,
+        opts: Option<SecretSendOptions>,
+    ) -> Result<SentSecret, ClientError> {
+        self.send(base_url, Upload::Spilled(ciphertext), ttl, token, opts)
+            .await
+    }
+
+    async fn receive_stream(
+        &self,
+        url: Url,
+        opts: Option<SecretReceiveOptions>,
+    ) -> Result<SpillBuffer, ClientError> {
+        let opt = opts.unwrap_or_default();
+        let mut resp = self.get_secret(url, &opt).await?;
+
+        // the encoding is decoded while downloading, only the ciphertext is spilled
+        let buffer = SpillBuffer::new(opt.max_memory.unwrap_or(usize::MAX));
+        let mut writer = DecodingWriter::new(buffer);
+        self.read_body_in_chunks(&mut resp, &mut writer, opt.observer, opt.rate_limit)
+            .await?;
+
+        Ok(writer.finish()?)
+    }
+}
+
+impl WebClient {
+    async fn send(
+        &self,
+        base_url: Url,
+        data: Upload,
+        ttl: Duration,
+        token: String,
+        opts: Option<SecretSendOptions>,
+    ) -> Result<SentSecret, ClientError> {
+        let opt = opts.unwrap_or_default();
+        let timeout = opt
+            .timeout
+            .unwrap_or_else(|| send_timeout(data.len(), opt.rate_limit));
+
+        let req = if opt.multipart_upload {
+            self.multipart_secret_request(
+                base_url.join(API_MULTIPART_SECRET_PATH)?,
+                data,
+                ttl,
+                &opt,
+            )?
+        } else if opt.raw_upload {
+            self.raw_secret_request(base_url.join(API_RAW_SECRET_PATH)?, data, ttl, &opt)?
+        } else {
+            self.json_secret_request(base_url.join(link::API_PATH)?, data, ttl, &opt)?
+        };
 
-        let timeout = opt.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT);
         let user_agent = opt.user_agent.unwrap_or(DEFAULT_USER_AGENT.to_string());
         let request_id = Uuid::new_v4().to_string();
 
-        let mut req = self
-            .web_client
-            .post(url.to_string())
+        let mut req = req
             .header("User-Agent", user_agent)
-            .header("Content-Type", "application/json")
-            .header("Content-Length", content_length.to_string())
             .header("X-Request-Id", request_id)
-            .body(body)
             .timeout(timeout);
 
         if !token.is_empty() {
             req = req.bearer_auth(token);
         }
 
+        if let Some(ref upload_token) = opt.upload_token {
+            req = req.header(secret::UPLOAD_TOKEN_HEADER_NAME, upload_token);
+        }
+
         let resp = req.send().await?;
 
         if resp.status() != reqwest::StatusCode::OK {
@@ -87,39 +209,57 @@ impl Client<Vec<u8>> for WebClient {
         }
 
         let res = resp.json::<PostSecretResponse>().await?;
+        if let Some(secret_id) = opt.secret_id
+            && res.id != secret_id
+        {
+            return Err(ClientError::Custom(format!(
+                "Server assigned ID {} instead of the proposed ID {secret_id}",
+                res.id
+            )));
+        }
 
-        let secret_url = base_url.join(&format!("{}/{}", SHORT_SECRET_PATH, res.id))?;
-        Ok(secret_url)
+        let secret_url = base_url.join(&format!("{}/{}", link::SHORT_PATH, res.link_id()))?;
+        Ok(SentSecret::new(secret_url).with_response(&res))
     }
 
-    async fn receive_secret(
+    /// Requests the secret, returning the response once the server started sending it.
+    async fn get_secret(
         &self,
         url: Url,
-        opts: Option<SecretReceiveOptions>,
-    ) -> Result<Vec<u8>, ClientError> {
-        if !url.path().starts_with(&format!("/{SHORT_SECRET_PATH}/"))
-            && !url.path().starts_with(&format!("/{API_SECRET_PATH}/"))
-        {
-            return Err(ClientError::Custom("Invalid API path".to_string()));
-        }
+        opt: &SecretReceiveOptions,
+    ) -> Result<reqwest::Response, ClientError> {
+        SecretLink::parse(&url)?;
 
-        let opt = opts.unwrap_or_default();
-        let user_agent = opt.user_agent.unwrap_or(DEFAULT_USER_AGENT.to_string());
-        let timeout = opt.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+        let user_agent = opt.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
         let request_id = Uuid::new_v4().to_string();
 
         let mut req = self
             .web_client
             .get(url)
             .header("User-Agent", user_agent)
-            .header("X-Request-Id", request_id)
-            .timeout(timeout);
+            .header("X-Request-Id", request_id);
+
+        // the duration of rate limited downloads depends on the unknown size of the secret
+        let rate_limited = opt.rate_limit.is_some_and(|rate| rate > 0);
+        if let Some(timeout) = opt
+            .timeout
+            .or((!rate_limited).then_some(DEFAULT_REQUEST_TIMEOUT))
+        {
+            req = req.timeout(timeout);
+        }
 
         if let Some(ref hash) = opt.passphrase_hash {
             req = req.header(restrictions::PASSPHRASE_HEADER_NAME, hash)
         }
 
-        let mut resp = req.send().await?;
+        if let Some(ref pin) = opt.pin {
+            req = req.header(restrictions::PIN_HEADER_NAME, pin)
+        }
+
+        ProgressTracker::new(opt.observer.clone())
+            .phase(TransferPhase::Waiting)
+            .await;
+        let resp = req.send().await?;
 
         if resp.status() != reqwest::StatusCode::OK {
             let mut err_msg = format!("HTTP error: {}", resp.status());
@@ -131,19 +271,16 @@ impl Client<Vec<u8>> for WebClient {
             return Err(ClientError::Http(err_msg));
         }
 
-        let observer = opt.observer.clone();
-        let secret = self.read_body_in_chunks(&mut resp, observer).await?;
-
-        Ok(secret)
+        Ok(resp)
     }
-}
 
-impl WebClient {
     async fn read_body_in_chunks(
         &self,
         resp: &mut reqwest::Response,
+        writer: &mut (impl Write + Send),
         observer: Option<Arc<dyn DataTransferObserver>>,
-    ) -> Result<Vec<u8>, ClientError> {
+        rate_limit: Option<u64>,
+    ) -> Result<(), ClientError> {
         let total_size = resp.content_length().unwrap_or(0);
         if total_size == 0 {
             return Err(ClientError::Custom(
@@ -151,126 +288,692 @@ impl WebClient {
             ));
         }
 
-        let mut result = Vec::with_capacity(total_size as usize);
+        let tracker = ProgressTracker::new(observer);
+        tracker.phase(TransferPhase::Downloading).await;
+
         let mut bytes_read = 0u64;
+        let mut throttle = Throttle::new(rate_limit);
 
         while let Some(chunk) = resp.chunk().await? {
-            result.extend_from_slice(&chunk);
+            writer.write_all(&chunk)?;
             bytes_read += chunk.len() as u64;
 
-            if let Some(ref obs) = observer {
-                obs.on_progress(bytes_read, total_size).await;
+            if let Some(ref mut throttle) = throttle {
+                throttle.throttle(chunk.len()).await;
+            }
+
+            tracker.progress(bytes_read, total_size).await;
+        }
+
+        Ok(())
+    }
+
+    /// Builds the request posting the base64 encoded secret as JSON.
+    fn json_secret_request(
+        &self,
+        url: Url,
+        data: Upload,
+        ttl: Duration,
+        opts: &SecretSendOptions,
+    ) -> Result<RequestBuilder, ClientError> {
+        let mut req = PostSecretRequest::new(String::new(), ttl);
+        if let Some(restrictions) = opts.restrictions.clone() {
+            req = req.with_restrictions(restrictions);
+        }
+        if opts.short_alias {
+            req = req.with_short_alias();
+        }
+        if let Some(secret_id) = opts.secret_id {
+            req = req.with_id(secret_id);
+        }
+        if opts.file {
+            req = req.with_file();
+        }
+        if let Some(reply) = opts.reply_reservation.clone() {
+            req = req.with_reply(reply);
+        }
+
+        let (body, content_length) = match data {
+            Upload::Encoded(data) => {
+                req.data = String::from_utf8(data)?;
+                let json = serde_json::to_vec(&req)?;
+                let len = json.len();
+                self.upload_body(BodySource::Memory(Bytes::from(json)), len, opts)?
+            }
+            Upload::Spilled(buffer) => {
+                // the ciphertext is encoded into the data field while uploading
+                let mut json = serde_json::to_vec(&req)?;
+                if !json.starts_with(JSON_DATA_FIELD) {
+                    return Err(ClientError::Custom(
+                        "Data is not the first field of the secret".to_string(),
+                    ));
+                }
+                let rest = json.split_off(JSON_DATA_FIELD.len());
+
+                let len = JSON_DATA_FIELD.len()
+                    + encoding::encoded_len(buffer.len() as usize)
+                    + rest.len();
+                let reader = JSON_DATA_FIELD
+                    .chain(EncodingReader::new(buffer.into_reader()?))
+                    .chain(Cursor::new(rest));
+                self.upload_body(BodySource::Reader(Box::new(reader)), len, opts)?
             }
+        };
+
+        Ok(self
+            .web_client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .header("Content-Length", content_length.to_string())
+            .body(body))
+    }
+
+    /// Builds the request posting the secret as binary, the metadata is sent in headers.
+    fn raw_secret_request(
+        &self,
+        url: Url,
+        data: Upload,
+        ttl: Duration,
+        opts: &SecretSendOptions,
+    ) -> Result<RequestBuilder, ClientError> {
+        let (body, content_length) = self.binary_body(data, opts)?;
+
+        let mut req = self
+            .web_client
+            .post(url)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", content_length.to_string())
+            .header(secret::TTL_HEADER_NAME, ttl.as_secs().to_string())
+            .body(body);
+
+        if let Some(ref restrictions) = opts.restrictions {
+            req = req.header(
+                restrictions::RESTRICTIONS_HEADER_NAME,
+                serde_json::to_string(restrictions)?,
+            );
+        }
+
+        if opts.short_alias {
+            req = req.header(secret::SHORT_ALIAS_HEADER_NAME, "true");
+        }
+
+        if let Some(secret_id) = opts.secret_id {
+            req = req.header(secret::ID_HEADER_NAME, secret_id.to_string());
+        }
+
+        if opts.file {
+            req = req.header(secret::FILE_HEADER_NAME, "true");
+        }
+
+        if let Some(ref reply) = opts.reply_reservation {
+            req = req.header(secret::REPLY_HEADER_NAME, serde_json::to_string(reply)?);
+        }
+
+        Ok(req)
+    }
+
+    /// Builds the request posting the secret as file of a multipart form, the metadata is sent
+    /// in the fields `ttl`, `restrictions`, `short_alias`, `id`, `file` and `reply`.
+    fn multipart_secret_request(
+        &self,
+        url: Url,
+        data: Upload,
+        ttl: Duration,
+        opts: &SecretSendOptions,
+    ) -> Result<RequestBuilder, ClientError> {
+        let (body, content_length) = self.binary_body(data, opts)?;
+
+        let secret_part = Part::stream_with_length(body, content_length as u64)
+            .file_name("secret.bin")
+            .mime_str("application/octet-stream")?;
+        let mut form = Form::new()
+            .text("ttl", ttl.as_secs().to_string())
+            .part("secret", secret_part);
+
+        if let Some(ref restrictions) = opts.restrictions {
+            form = form.text("restrictions", serde_json::to_string(restrictions)?);
+        }
+
+        if opts.short_alias {
+            form = form.text("short_alias", "true");
         }
 
-        Ok(result)
+        if let Some(secret_id) = opts.secret_id {
+            form = form.text("id", secret_id.to_string());
+        }
+
+        if opts.file {
+            form = form.text("file", "true");
+        }
+
+        if let Some(ref reply) = opts.reply_reservation {
+            form = form.text("reply", serde_json::to_string(reply)?);
+        }
+
+        Ok(self.web_client.post(url).multipart(form))
+    }
+
+    /// Creates the body of binary uploads, which carry the ciphertext without encoding.
+    fn binary_body(
+        &self,
+        data: Upload,
+        opts: &SecretSendOptions,
+    ) -> Result<(Body, usize), ClientError> {
+        match data {
+            Upload::Encoded(mut secret) => {
+                encoding::decode_in_place(&mut secret)?;
+                let len = secret.len();
+                self.upload_body(BodySource::Memory(Bytes::from(secret)), len, opts)
+            }
+            Upload::Spilled(buffer) => {
+                let len = buffer.len() as usize;
+                let reader = buffer.into_reader()?;
+                self.upload_body(BodySource::Reader(Box::new(reader)), len, opts)
+            }
+        }
     }
 
-    fn post_secret_body_from_req(
+    fn upload_body(
         &self,
-        req: PostSecretRequest,
+        source: BodySource,
+        len: usize,
         opts: &SecretSendOptions,
     ) -> Result<(Body, usize), ClientError> {
-        let json_bytes = serde_json::to_vec(&req)?;
-        let json_len = json_bytes.len();
+        let chunk_size = opts.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+        if chunk_size == 0 {
+            return Err(ClientError::Custom(
+                "Chunk size must be greater than 0".to_string(),
+            ));
+        }
+
+        let mut source = source;
+        let mut bytes_uploaded = 0u64;
+        let observer = opts.observer.clone();
+        let rate_limit = opts.rate_limit;
+        let stream = async_stream::stream! {
+            let mut offset = 0;
+            let mut throttle = Throttle::new(rate_limit);
+            let tracker = ProgressTracker::new(observer);
+            tracker.phase(TransferPhase::Uploading).await;
+
+            while offset < len {
+                let end = std::cmp::min(offset + chunk_size, len);
+                let chunk = match source {
+                    BodySource::Memory(ref bytes) => bytes.slice(offset..end),
+                    BodySource::Reader(ref mut reader) => {
+                        let mut chunk = vec![0u8; end - offset];
+                        if let Err(e) = reader.read_exact(&mut chunk) {
+                            yield Err(e);
+                            break;
+                        }
+                        Bytes::from(chunk)
+                    }
+                };
+
+                bytes_uploaded += chunk.len() as u64;
+
+                if let Some(ref mut throttle) = throttle {
+                    throttle.throttle(chunk.len()).await;
+                }
+
+                tracker.progress(bytes_uploaded, len as u64).await;
+
+                yield Ok::<_, std::io::Error>(chunk);
+                offset = end;
+            }
+
+            tracker.phase(TransferPhase::Waiting).await;
+        };
+
+        Ok((Body::wrap_stream(stream), len))
+    }
+}
+
+/// Returns the default timeout for sending, extended by the duration of a rate limited upload.
+fn send_timeout(upload_size: usize, rate_limit: Option<u64>) -> Duration {
+    match rate_limit.filter(|rate| *rate > 0) {
+        Some(rate) => {
+            DEFAULT_REQUEST_TIMEOUT + throttle::expected_duration(upload_size as u64, rate)
+        }
+        None => DEFAULT_REQUEST_TIMEOUT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::time::Duration;
+
+    use base64::Engine;
+    use ulid::Ulid;
+    use url::Url;
+
+    use crate::link::LinkError;
+    use crate::models::{ReplyReservation, SecretRestrictions};
+
+    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+    #[tokio::test]
+    async fn test_send_secret_success() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let result = client
+            .send_secret(
+                base_url.clone(),
+                b"test_secret".to_vec(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await;
+
+        let url = result?.url;
+        assert_eq!(url.as_str(), format!("{base_url}s/{secret_id}"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_reports_details() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"id":"{secret_id}","expires_at":1700000000,"effective_restrictions":{{"allowed_asns":[13335]}},"size_class":2048}}"#
+            ))
+            .create_async()
+            .await;
+
+        let sent = client
+            .send_secret(
+                Url::parse(&server.url())?,
+                b"test_secret".to_vec(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await?;
+
+        assert_eq!(
+            sent.expires_at,
+            Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(
+            sent.restrictions,
+            Some(SecretRestrictions::default().with_allowed_asns(vec![13335]))
+        );
+        assert_eq!(sent.size_class, Some(2048));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_server_error() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let _m = server
+            .mock("POST", "/api/v1/secret")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let result = client
+            .send_secret(
+                base_url,
+                b"test_secret".to_vec(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await;
+
+        assert!(
+            result.is_err(),
+            "Expected error for server error, got: {:?}",
+            result
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_multipart_upload() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret/multipart")
+            .match_header(
+                "content-type",
+                mockito::Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+            )
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::Regex(r#"name="ttl"\r\n\r\n3600\r\n"#.to_string()),
+                mockito::Matcher::Regex(r#"name="short_alias"\r\n\r\ntrue\r\n"#.to_string()),
+                mockito::Matcher::Regex(
+                    r#"name="secret"; filename="secret.bin"\r\nContent-Type: application/octet-stream\r\n\r\ntest_secret\r\n"#
+                        .to_string(),
+                ),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
+
+        let opts = SecretSendOptions::new()
+            .with_multipart_upload(true)
+            .with_raw_upload(true)
+            .with_short_alias(true);
+        let base_url = Url::parse(&server.url())?;
+        let url = client
+            .send_secret(
+                base_url.clone(),
+                base64::prelude::BASE64_STANDARD
+                    .encode(b"test_secret")
+                    .into_bytes(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(opts),
+            )
+            .await?
+            .url;
+
+        assert_eq!(
+            url.as_str(),
+            format!("{base_url}s/{secret_id}"),
+            "Multipart upload should take precedence over raw upload"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_secret_raw_upload() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret/raw")
+            .match_header("content-type", "application/octet-stream")
+            .match_header("x-secret-ttl", "3600")
+            .match_header(
+                "x-secret-restrictions",
+                r#"{"allowed_countries":null,"allowed_asns":[64512],"passphrase_hash":null}"#,
+            )
+            .match_body(b"test_secret".to_vec())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
+
+        let opts = SecretSendOptions::new()
+            .with_raw_upload(true)
+            .with_restrictions(SecretRestrictions::default().with_allowed_asns(vec![64512]));
+        let base_url = Url::parse(&server.url())?;
+        let url = client
+            .send_secret(
+                base_url.clone(),
+                base64::prelude::BASE64_STANDARD
+                    .encode(b"test_secret")
+                    .into_bytes(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(opts),
+            )
+            .await?
+            .url;
+
+        assert_eq!(url.as_str(), format!("{base_url}s/{secret_id}"));
+        Ok(())
+    }
+
+    fn spilled_ciphertext(data: &[u8]) -> Result<SpillBuffer> {
+        let mut buffer = SpillBuffer::new(1024);
+        buffer.write_all(data)?;
+        assert!(
+            buffer.is_spilled(),
+            "Ciphertext should exceed the memory limit"
+        );
+        Ok(buffer)
+    }
+
+    #[tokio::test]
+    async fn test_send_stream_json() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let ciphertext: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret")
+            .match_header(
+                "content-length",
+                (base64::encoded_len(ciphertext.len(), true).unwrap_or_default() + 29)
+                    .to_string()
+                    .as_str(),
+            )
+            .match_body(mockito::Matcher::Exact(format!(
+                r#"{{"data":"{}","expires_in":3600}}"#,
+                base64::prelude::BASE64_STANDARD.encode(&ciphertext)
+            )))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
 
-        let chunk_size = opts.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
-        if chunk_size == 0 {
-            return Err(ClientError::Custom(
-                "Chunk size must be greater than 0".to_string(),
-            ));
-        }
+        let base_url = Url::parse(&server.url())?;
+        let sent = client
+            .send_stream(
+                base_url.clone(),
+                spilled_ciphertext(&ciphertext)?,
+                Duration::from_secs(3600),
+                "".to_string(),
+                None,
+            )
+            .await?;
 
-        let mut bytes_uploaded = 0u64;
-        let upload_observer = self.upload_observer.clone();
-        let stream = async_stream::stream! {
-            let mut offset = 0;
+        assert_eq!(
+            sent.url.as_str(),
+            format!("{base_url}s/{secret_id}"),
+            "Encoding should be streamed into the JSON"
+        );
+        Ok(())
+    }
 
-            while offset < json_len {
-                let end = std::cmp::min(offset + chunk_size, json_bytes.len());
-                let chunk = Bytes::copy_from_slice(&json_bytes[offset..end]);
-                bytes_uploaded += chunk.len() as u64;
+    #[tokio::test]
+    async fn test_send_stream_raw_upload() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
 
-                if let Some(ref observer) = upload_observer {
-                    observer.on_progress(bytes_uploaded, json_len as u64).await;
-                }
+        let ciphertext: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret/raw")
+            .match_body(ciphertext.clone())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
 
-                yield Ok::<_, std::io::Error>(chunk);
-                offset = end;
-            }
-        };
+        let opts = SecretSendOptions::new().with_raw_upload(true);
+        let base_url = Url::parse(&server.url())?;
+        let sent = client
+            .send_stream(
+                base_url.clone(),
+                spilled_ciphertext(&ciphertext)?,
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(opts),
+            )
+            .await?;
 
-        Ok((Body::wrap_stream(stream), json_len))
+        assert_eq!(
+            sent.url.as_str(),
+            format!("{base_url}s/{secret_id}"),
+            "Spilled upload should send the complete ciphertext"
+        );
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::error::Error;
-    use std::time::Duration;
+    #[tokio::test]
+    async fn test_send_secret_short_alias() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
 
-    use ulid::Ulid;
-    use url::Url;
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("POST", "/api/v1/secret")
+            .match_body(mockito::Matcher::PartialJsonString(
+                r#"{"short_alias":true}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}","alias":"blue-otter-42"}}"#))
+            .create_async()
+            .await;
 
-    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+        let base_url = Url::parse(&server.url())?;
+        let url = client
+            .send_secret(
+                base_url.clone(),
+                b"test_secret".to_vec(),
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(SecretSendOptions::new().with_short_alias(true)),
+            )
+            .await?
+            .url;
+
+        assert_eq!(url.as_str(), format!("{base_url}s/blue-otter-42"));
+        Ok(())
+    }
 
     #[tokio::test]
-    async fn test_send_secret_success() -> Result<()> {
+    async fn test_send_secret_raw_upload_file() -> Result<()> {
         let mut server = mockito::Server::new_async().await;
         let client = WebClient::new();
 
         let secret_id = Ulid::r#gen();
         let _m = server
-            .mock("POST", "/api/v1/secret")
+            .mock("POST", "/api/v1/secret/raw")
+            .match_header("x-secret-file", "true")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
             .create_async()
             .await;
 
+        let opts = SecretSendOptions::new()
+            .with_raw_upload(true)
+            .with_file(true);
         let base_url = Url::parse(&server.url())?;
-        let result = client
+        let url = client
             .send_secret(
                 base_url.clone(),
-                b"test_secret".to_vec(),
+                base64::prelude::BASE64_STANDARD
+                    .encode(b"test_file")
+                    .into_bytes(),
                 Duration::from_secs(3600),
                 "".to_string(),
-                None,
+                Some(opts),
             )
-            .await;
+            .await?
+            .url;
 
-        let url = result?;
         assert_eq!(url.as_str(), format!("{base_url}s/{secret_id}"));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_send_secret_server_error() -> Result<()> {
+    async fn test_send_secret_raw_upload_with_reply() -> Result<()> {
         let mut server = mockito::Server::new_async().await;
         let client = WebClient::new();
 
+        let (secret_id, reply_id) = (Ulid::r#gen(), Ulid::r#gen());
+        let reply = ReplyReservation::new(reply_id, "ab".repeat(32));
         let _m = server
-            .mock("POST", "/api/v1/secret")
-            .with_status(500)
+            .mock("POST", "/api/v1/secret/raw")
+            .match_header("x-secret-upload-token", "upload-token")
+            .match_header("x-secret-reply", serde_json::to_string(&reply)?.as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}","reply_id":"{reply_id}"}}"#))
             .create_async()
             .await;
 
-        let base_url = Url::parse(&server.url())?;
-        let result = client
+        let opts = SecretSendOptions::new()
+            .with_raw_upload(true)
+            .with_upload_token("upload-token".to_string())
+            .with_reply_reservation(reply);
+        let sent = client
             .send_secret(
-                base_url,
-                b"test_secret".to_vec(),
+                Url::parse(&server.url())?,
+                base64::prelude::BASE64_STANDARD
+                    .encode(b"test_secret")
+                    .into_bytes(),
                 Duration::from_secs(3600),
                 "".to_string(),
-                None,
+                Some(opts),
             )
+            .await?;
+
+        assert_eq!(sent.reply_id, Some(reply_id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_secret_by_alias() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let _m = server
+            .mock("GET", "/s/blue-otter-42")
+            .with_status(200)
+            .with_body(b"my_secret_data")
+            .create_async()
             .await;
 
+        let url = Url::parse(&server.url())?.join("/s/blue-otter-42")?;
+        let data = client.receive_secret(url, None).await?;
+
+        assert_eq!(data, b"my_secret_data");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_secret_invalid_id() -> Result<()> {
+        let client = WebClient::new();
+
+        let url = Url::parse("https://example.com/s/not_a_secret")?;
+        let result = client.receive_secret(url, None).await;
+
         assert!(
-            result.is_err(),
-            "Expected error for server error, got: {:?}",
-            result
+            matches!(
+                result,
+                Err(ClientError::InvalidLink(LinkError::InvalidSecretId))
+            ),
+            "Expected invalid secret ID error, got: {result:?}"
         );
         Ok(())
     }
@@ -299,6 +1002,147 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_receive_stream() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let ciphertext: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+
+        let _m = server
+            .mock("GET", format!("/s/{secret_id}").as_str())
+            .with_status(200)
+            .with_body(base64::prelude::BASE64_STANDARD.encode(&ciphertext))
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let url = base_url.join(&format!("/s/{secret_id}"))?;
+        let opts = SecretReceiveOptions::new().with_max_memory(1024);
+        let buffer = client.receive_stream(url, Some(opts)).await?;
+
+        assert!(
+            buffer.is_spilled(),
+            "Download above the memory limit should be spilled"
+        );
+        let mut data = Vec::new();
+        buffer.into_reader()?.read_to_end(&mut data)?;
+        assert_eq!(data, ciphertext, "Download should be decoded");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_stream_invalid_encoding() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let _m = server
+            .mock("GET", format!("/s/{secret_id}").as_str())
+            .with_status(200)
+            .with_body("not base64!")
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let url = base_url.join(&format!("/s/{secret_id}"))?;
+        let opts = SecretReceiveOptions::new().with_max_memory(1024);
+        let result = client.receive_stream(url, Some(opts)).await;
+
+        assert!(result.is_err(), "Invalid encoding should be rejected");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_exceeding_max_memory() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = crate::client::new();
+
+        let uploaded = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let secret_id = Ulid::r#gen();
+        let upload = uploaded.clone();
+        let _post = server
+            .mock("POST", "/api/v1/secret")
+            .match_request(move |req| {
+                let body = req.body().cloned().unwrap_or_default();
+                *upload.lock().expect("Lock should not be poisoned") = body;
+                true
+            })
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"id":"{secret_id}"}}"#))
+            .create_async()
+            .await;
+
+        let secret: Vec<u8> = (0..500_000).map(|i| (i % 251) as u8).collect();
+        let opts = SecretSendOptions::new().with_max_memory(4096);
+        let sent = client
+            .send_secret(
+                Url::parse(&server.url())?,
+                crate::models::Payload::from_bytes(&secret),
+                Duration::from_secs(3600),
+                "".to_string(),
+                Some(opts),
+            )
+            .await?;
+
+        let request: PostSecretRequest =
+            serde_json::from_slice(&uploaded.lock().expect("Lock should not be poisoned"))?;
+        let _get = server
+            .mock("GET", format!("/s/{secret_id}").as_str())
+            .with_status(200)
+            .with_body(request.data)
+            .create_async()
+            .await;
+
+        let opts = SecretReceiveOptions::new().with_max_memory(4096);
+        let payload = client.receive_secret(sent.url, Some(opts)).await?;
+
+        assert_eq!(payload.data, secret, "Secret should survive the round trip");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_secret_with_rate_limit() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let secret_data = vec![b'x'; 2000];
+
+        let _m = server
+            .mock("GET", format!("/s/{secret_id}").as_str())
+            .with_status(200)
+            .with_body(&secret_data)
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let url = base_url.join(&format!("/s/{secret_id}"))?;
+        let opts = SecretReceiveOptions::new().with_rate_limit(10_000);
+        let start = std::time::Instant::now();
+        let data = client.receive_secret(url, Some(opts)).await?;
+
+        assert_eq!(data, secret_data);
+        assert!(
+            start.elapsed() >= Duration::from_millis(200),
+            "Download of 2000 bytes at 10000 B/s should take at least 200ms"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_send_timeout() {
+        assert_eq!(send_timeout(1024 * 1024, None), DEFAULT_REQUEST_TIMEOUT);
+        assert_eq!(send_timeout(1024 * 1024, Some(0)), DEFAULT_REQUEST_TIMEOUT);
+        assert_eq!(
+            send_timeout(1024 * 1024, Some(1024)),
+            DEFAULT_REQUEST_TIMEOUT + Duration::from_secs(1024),
+            "Timeout should be extended by the duration of the upload"
+        );
+    }
+
     #[tokio::test]
     async fn test_receive_secret_not_found() -> Result<()> {
         let mut server = mockito::Server::new_async().await;
@@ -542,4 +1386,83 @@ mod tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_receive_secret_with_pin() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let secret_id = Ulid::r#gen();
+        let secret_data = b"pin_protected_secret";
+
+        let _m = server
+            .mock("GET", format!("/s/{secret_id}").as_str())
+            .match_header(restrictions::PIN_HEADER_NAME, "123456")
+            .with_status(200)
+            .with_body(secret_data)
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let url = base_url.join(&format!("/s/{secret_id}"))?;
+
+        let opts = SecretReceiveOptions::new().with_pin("123456");
+        let data = client.receive_secret(url, Some(opts)).await?;
+
+        assert_eq!(data, secret_data, "Should send the PIN as header");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_server_config_success() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let _m = server
+            .mock("GET", "/config.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"showTokenInput":false,"features":{"impressum":false,"privacy":false,"restrictions":{"country":true,"asn":false}},"secretSizeLimit":32768,"apiVersions":["v1"]}"#,
+            )
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let config = client.get_server_config(base_url).await?;
+
+        assert_eq!(config.secret_size_limit, Some(32768));
+        assert!(
+            config.features.restrictions.country,
+            "Country restrictions should be supported"
+        );
+        assert!(
+            !config.features.restrictions.asn,
+            "ASN restrictions should not be supported"
+        );
+        assert!(config.supports_api_version("v1"), "v1 should be supported");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_server_config_not_found() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let client = WebClient::new();
+
+        let _m = server
+            .mock("GET", "/config.json")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let base_url = Url::parse(&server.url())?;
+        let result = client.get_server_config(base_url).await;
+
+        assert!(
+            matches!(result, Err(ClientError::Http(_))),
+            "Expected HTTP error for 404, got: {:?}",
+            result
+        );
+        Ok(())
+    }
 }
@@ -7,10 +7,12 @@ use anyhow::{Context, Result};
 mod build {
     pub mod assets;
     pub mod cache_buster;
+    pub mod compression;
     pub mod static_pages;
 }
 
 use build::assets;
+use build::compression;
 use build::static_pages;
 
 /// Auto-detect and register files with given extension for recompilation tracking
@@ -51,6 +53,7 @@ fn main() -> Result<()> {
     let start = std::time::Instant::now();
     assets::build()?;
     static_pages::generate_html_files()?;
+    compression::compress_assets()?;
     println!("cargo:warning=Build completed in {:?}", start.elapsed());
 
     Ok(())
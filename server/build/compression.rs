@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+/// File extensions of the assets served pre-compressed.
+const COMPRESSED_EXTENSIONS: &[&str] = &["js", "css", "wasm"];
+
+// Pre-compress asset files (WASM, JS, CSS) with brotli and gzip
+//
+// The compressed files are written to OUT_DIR as `<name>.br` and `<name>.gz`, so they can be
+// embedded next to the uncompressed assets.
+pub fn compress_assets() -> Result<()> {
+    println!("cargo:warning=Compressing assets...");
+
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").context("OUT_DIR not set")?);
+    let includes_dir = Path::new("includes");
+
+    let entries = fs::read_dir(includes_dir)
+        .with_context(|| format!("Failed to read directory: {}", includes_dir.display()))?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let is_compressible = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| COMPRESSED_EXTENSIONS.contains(&ext));
+        if !path.is_file() || !is_compressible {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+        let content = fs::read(&path).with_context(|| format!("failed to read {path:?}"))?;
+
+        fs::write(out_dir.join(format!("{name}.br")), brotli(&content)?)
+            .with_context(|| format!("failed to write brotli variant of {name}"))?;
+        fs::write(out_dir.join(format!("{name}.gz")), gzip(&content)?)
+            .with_context(|| format!("failed to write gzip variant of {name}"))?;
+    }
+
+    println!("cargo:warning=Assets compressed");
+    Ok(())
+}
+
+fn brotli(content: &[u8]) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut output, 4096, 11, 22);
+        writer.write_all(content)?;
+    }
+    Ok(output)
+}
+
+fn gzip(content: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(content)?;
+    Ok(encoder.finish()?)
+}
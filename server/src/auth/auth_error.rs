@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+/// Errors of the authentication with external identity providers.
+#[derive(Debug, Error)]
+pub enum AuthError {
+    /// Request to the identity provider failed.
+    #[error("identity provider request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The identity provider metadata could not be used.
+    #[error("identity provider discovery failed: {0}")]
+    Discovery(String),
+
+    /// The token is not valid for this server.
+    #[error("token is invalid: {0}")]
+    InvalidToken(String),
+}
+
+impl From<jsonwebtoken::errors::Error> for AuthError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        AuthError::InvalidToken(err.to_string())
+    }
+}
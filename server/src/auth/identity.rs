@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+
+/// Identity of a user authenticated by an external identity provider.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Identity {
+    /// Subject identifier (`sub` claim), unique per identity provider
+    #[serde(rename = "sub")]
+    pub subject: String,
+
+    /// E-mail address of the user, if provided by the identity provider
+    #[serde(default)]
+    pub email: Option<String>,
+
+    /// Username preferred by the user, if provided by the identity provider
+    #[serde(default)]
+    pub preferred_username: Option<String>,
+}
+
+impl Identity {
+    /// Returns the most readable identifier of the user.
+    pub fn display_name(&self) -> &str {
+        self.preferred_username
+            .as_deref()
+            .or(self.email.as_deref())
+            .unwrap_or(&self.subject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_name_fallback() {
+        let mut identity = Identity {
+            subject: "1234".to_string(),
+            email: Some("jane@example.com".to_string()),
+            preferred_username: Some("jane".to_string()),
+        };
+        assert_eq!(identity.display_name(), "jane");
+
+        identity.preferred_username = None;
+        assert_eq!(identity.display_name(), "jane@example.com");
+
+        identity.email = None;
+        assert_eq!(
+            identity.display_name(),
+            "1234",
+            "Subject should be used if no other claims are present"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+
+use super::{AuthError, Identity};
+
+#[async_trait]
+pub trait IdentityValidator: Send + Sync {
+    /// Validate a bearer token issued by an identity provider and return the identity.
+    async fn validate_identity(&self, token: &str) -> Result<Identity, AuthError>;
+
+    /// Checks if the token was issued by an identity provider rather than Hakanai.
+    fn is_identity_token(&self, token: &str) -> bool {
+        // JWTs consist of three base64url encoded parts, the first being a JSON object
+        token.starts_with("eyJ") && token.split('.').count() == 3
+    }
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use super::{AuthError, Identity, IdentityValidator};
+
+/// Mock implementation of the `IdentityValidator` trait accepting the configured tokens.
+#[derive(Clone, Default)]
+pub struct MockIdentityValidator {
+    identities: HashMap<String, Identity>,
+}
+
+impl MockIdentityValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept the token for a user with the given subject.
+    pub fn with_identity(mut self, token: &str, subject: &str) -> Self {
+        self.identities.insert(
+            token.to_string(),
+            Identity {
+                subject: subject.to_string(),
+                email: None,
+                preferred_username: None,
+            },
+        );
+        self
+    }
+}
+
+#[async_trait]
+impl IdentityValidator for MockIdentityValidator {
+    async fn validate_identity(&self, token: &str) -> Result<Identity, AuthError> {
+        self.identities
+            .get(token)
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidToken("unknown token".to_string()))
+    }
+}
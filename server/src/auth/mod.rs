@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Authentication with external identity providers.
+//!
+//! Allows users to authenticate with bearer tokens issued by an OpenID Connect provider
+//! (e.g. the SSO of an organization) instead of Hakanai user tokens.
+
+mod auth_error;
+mod identity;
+mod identity_validator;
+mod oidc_validator;
+
+#[cfg(test)]
+mod mock_identity_validator;
+
+pub use auth_error::AuthError;
+pub use identity::Identity;
+pub use identity_validator::IdentityValidator;
+pub use oidc_validator::OidcValidator;
+
+#[cfg(test)]
+pub use mock_identity_validator::MockIdentityValidator;
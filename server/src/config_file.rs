@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Config file for the server (`--config`).
+//!
+//! The config file is a YAML or TOML file (by extension `.toml`) containing server options
+//! keyed by their long flag name, in snake or kebab case, e.g.
+//!
+//! ```yaml
+//! port: 8080
+//! redis_dsn: redis://redis:6379/
+//! upload_size_limit: 10m
+//! trusted_ip_ranges:
+//!   - 10.0.0.0/8
+//! webhook_url: https://audit.example.com/hakanai
+//! ```
+//!
+//! The settings are converted into command line arguments for all options not set by
+//! environment variables or flags, so the precedence is file < environment < flags and the
+//! values are validated by the same parsers as the flags.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::Path;
+
+use clap::Command;
+use clap::error::ErrorKind;
+use serde_json::Value;
+
+/// Options which trigger one-time actions and must not be set permanently in a file.
+const COMMAND_LINE_ONLY: &[&str] = &[
+    "config",
+    "help",
+    "version",
+    "reset_admin_token",
+    "reset_user_tokens",
+    "migrate_redis_keys",
+];
+
+/// Settings of the config file, keyed by argument id.
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    settings: BTreeMap<String, Value>,
+}
+
+impl ConfigFile {
+    /// Reads and parses the config file, TOML if the extension is `.toml`, YAML otherwise.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {e}", path.display()))?;
+
+        let is_toml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+        let settings = if is_toml {
+            Self::parse_toml(&content)
+        } else {
+            Self::parse_yaml(&content)
+        };
+
+        settings.map_err(|e| format!("invalid config file {}: {e}", path.display()))
+    }
+
+    fn parse_yaml(content: &str) -> Result<Self, String> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let settings = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+        Ok(Self::from_settings(settings))
+    }
+
+    fn parse_toml(content: &str) -> Result<Self, String> {
+        let settings = toml::from_str(content).map_err(|e| e.to_string())?;
+        Ok(Self::from_settings(settings))
+    }
+
+    fn from_settings(settings: BTreeMap<String, Value>) -> Self {
+        let settings = settings
+            .into_iter()
+            .map(|(key, value)| (key.replace('-', "_"), value))
+            .collect();
+        Self { settings }
+    }
+
+    /// Converts the settings into command line arguments of the command.
+    ///
+    /// Settings for which `is_explicit` returns true are skipped, so environment variables and
+    /// flags take precedence. Errors name the offending key.
+    pub fn to_args(
+        &self,
+        command: &Command,
+        is_explicit: impl Fn(&str) -> bool,
+    ) -> Result<Vec<OsString>, String> {
+        let mut args = Vec::new();
+        let mut validator = command
+            .clone()
+            .mut_args(|arg| arg.required(false).env(None::<&str>));
+
+        for (key, value) in &self.settings {
+            let Some(arg) = command.get_arguments().find(|arg| {
+                arg.get_long()
+                    .is_some_and(|long| long.replace('-', "_") == *key)
+                    || (arg.get_id().as_str() == key && arg.get_long().is_some())
+            }) else {
+                return Err(format!("unknown key '{key}'"));
+            };
+
+            let id = arg.get_id().as_str();
+            if COMMAND_LINE_ONLY.contains(&id) {
+                return Err(format!("'{key}' can only be set on the command line"));
+            }
+
+            if is_explicit(id) || value.is_null() {
+                continue;
+            }
+
+            let flag = format!("--{}", arg.get_long().unwrap_or(key));
+            if !arg.get_action().takes_values() {
+                match value {
+                    Value::Bool(true) => args.push(flag.into()),
+                    Value::Bool(false) => {}
+                    _ => return Err(format!("invalid value for '{key}': expected true or false")),
+                }
+                continue;
+            }
+
+            let values = match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                let value = scalar_to_string(value)
+                    .ok_or_else(|| format!("invalid value for '{key}': expected a single value"))?;
+                validate_value(&mut validator, &flag, &value)
+                    .map_err(|e| format!("invalid value for '{key}': {}", error_reason(&e)))?;
+
+                args.push(flag.clone().into());
+                args.push(value.into());
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Validates the value with the parser of the flag, ignoring errors unrelated to the value.
+fn validate_value(validator: &mut Command, flag: &str, value: &str) -> Result<(), clap::Error> {
+    let name = validator.get_name().to_string();
+    match validator.try_get_matches_from_mut([name.as_str(), flag, value]) {
+        Err(e)
+            if matches!(
+                e.kind(),
+                ErrorKind::InvalidValue | ErrorKind::ValueValidation
+            ) =>
+        {
+            Err(e)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Extracts the reason of a clap error, without the usage hints meant for the command line.
+fn error_reason(error: &clap::Error) -> String {
+    let message = error.to_string();
+    message
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("error: ")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    use clap::CommandFactory;
+
+    use crate::options::Args;
+
+    fn to_args(config: &ConfigFile) -> Result<Vec<String>, String> {
+        let args = config.to_args(&Args::command(), |_| false)?;
+        Ok(args
+            .iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect())
+    }
+
+    #[test]
+    fn test_to_args() -> Result<(), String> {
+        let config = ConfigFile::parse_yaml(
+            "port: 9090\nupload-size-limit: 5m\nallow_anonymous: true\nenable_admin_token: false\ntrusted_ip_ranges:\n  - 10.0.0.0/8\n  - 192.168.1.1\nwebhook_token: ~\n",
+        )?;
+
+        assert_eq!(
+            to_args(&config)?,
+            vec![
+                "--allow-anonymous",
+                "--port",
+                "9090",
+                "--trusted-ip-ranges",
+                "10.0.0.0/8",
+                "--trusted-ip-ranges",
+                "192.168.1.1",
+                "--upload-size-limit",
+                "5m",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_args_skips_explicit_settings() -> Result<(), String> {
+        let config = ConfigFile::parse_yaml("port: 9090\nredis_dsn: redis://redis:6379/")?;
+
+        let args = config.to_args(&Args::command(), |key| key == "port")?;
+
+        assert_eq!(
+            args,
+            vec![
+                OsString::from("--redis-dsn"),
+                OsString::from("redis://redis:6379/")
+            ],
+            "Settings of environment or flags should not be overridden"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_args_errors_name_key() -> Result<(), String> {
+        let invalid = [
+            ("unknown_setting: 1", "unknown_setting"),
+            ("port: not-a-port", "port"),
+            ("upload_size_limit: huge", "upload_size_limit"),
+            ("trusted_ip_ranges: [not-an-ip]", "trusted_ip_ranges"),
+            ("allow_anonymous: yes please", "allow_anonymous"),
+            ("port: {nested: 1}", "port"),
+            ("reset_admin_token: true", "reset_admin_token"),
+            ("config: other.yaml", "config"),
+        ];
+
+        for (content, key) in invalid {
+            let config = ConfigFile::parse_yaml(content)?;
+            let error = to_args(&config).err().unwrap_or_default();
+            assert!(
+                error.contains(&format!("'{key}'")),
+                "Error for '{content}' should name the key, got: {error}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_toml() -> Result<(), String> {
+        let config = ConfigFile::parse_toml(
+            "port = 9090\nallow-anonymous = true\ntrusted_ip_ranges = [\"10.0.0.0/8\"]\n",
+        )?;
+
+        assert_eq!(
+            to_args(&config)?,
+            vec![
+                "--allow-anonymous",
+                "--port",
+                "9090",
+                "--trusted-ip-ranges",
+                "10.0.0.0/8",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_by_extension() -> Result<(), String> {
+        let mut yaml = tempfile::Builder::new()
+            .suffix(".yaml")
+            .tempfile()
+            .map_err(|e| e.to_string())?;
+        write!(yaml, "port: 9090").map_err(|e| e.to_string())?;
+        let mut toml = tempfile::Builder::new()
+            .suffix(".toml")
+            .tempfile()
+            .map_err(|e| e.to_string())?;
+        write!(toml, "port = 9090").map_err(|e| e.to_string())?;
+
+        for path in [yaml.path(), toml.path()] {
+            let config = ConfigFile::load(path)?;
+            assert_eq!(to_args(&config)?, vec!["--port", "9090"]);
+        }
+
+        assert!(ConfigFile::load(Path::new("/nonexistent/config.yaml")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Certificate and private key files for serving TLS on a listener.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TlsFiles {
+    /// PEM file with the certificate chain, leaf certificate first
+    pub cert_file: PathBuf,
+
+    /// PEM file with the private key
+    pub key_file: PathBuf,
+}
+
+/// Address the server listens on, optionally serving TLS.
+///
+/// Format: `HOST[:PORT][,tls-cert=PATH,tls-key=PATH]`, IPv6 addresses with port have to be
+/// enclosed in brackets (e.g. `[::1]:8080`). Without port the port of `--port` is used.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListenAddress {
+    /// Hostname or IP address (without brackets)
+    pub host: String,
+
+    /// Port, `None` to use the default port
+    pub port: Option<u16>,
+
+    /// TLS settings, `None` for plain HTTP
+    pub tls: Option<TlsFiles>,
+}
+
+impl FromStr for ListenAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',');
+        let address = parts.next().unwrap_or_default().trim();
+        let (host, port) = split_host_port(address)?;
+
+        let mut cert_file = None;
+        let mut key_file = None;
+        for option in parts {
+            match option.trim().split_once('=') {
+                Some(("tls-cert", path)) if !path.is_empty() => cert_file = Some(path.into()),
+                Some(("tls-key", path)) if !path.is_empty() => key_file = Some(path.into()),
+                _ => {
+                    return Err(format!(
+                        "invalid listener option '{option}', expected tls-cert=PATH or tls-key=PATH"
+                    ));
+                }
+            }
+        }
+
+        let tls = match (cert_file, key_file) {
+            (Some(cert_file), Some(key_file)) => Some(TlsFiles {
+                cert_file,
+                key_file,
+            }),
+            (None, None) => None,
+            _ => return Err("tls-cert and tls-key must be set together".to_string()),
+        };
+
+        Ok(Self { host, port, tls })
+    }
+}
+
+impl fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.host.contains(':'), self.port) {
+            (true, Some(port)) => write!(f, "[{}]:{port}", self.host)?,
+            (false, Some(port)) => write!(f, "{}:{port}", self.host)?,
+            (_, None) => write!(f, "{}", self.host)?,
+        }
+
+        if self.tls.is_some() {
+            write!(f, " (TLS)")?;
+        }
+        Ok(())
+    }
+}
+
+impl ListenAddress {
+    /// Returns the address with the default port applied if no port is set.
+    pub fn with_default_port(&self, port: u16) -> Self {
+        Self {
+            port: self.port.or(Some(port)),
+            ..self.clone()
+        }
+    }
+}
+
+fn split_host_port(address: &str) -> Result<(String, Option<u16>), String> {
+    if address.is_empty() {
+        return Err("listen address must not be empty".to_string());
+    }
+
+    if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| format!("invalid listen address '{address}': missing ']'"))?;
+        let port = match rest {
+            "" => None,
+            rest => {
+                let port = rest
+                    .strip_prefix(':')
+                    .ok_or_else(|| format!("invalid listen address '{address}'"))?;
+                Some(parse_port(address, port)?)
+            }
+        };
+        return Ok((host.to_string(), port));
+    }
+
+    match address.split_once(':') {
+        // more than one colon is an IPv6 address without port
+        Some((_, rest)) if rest.contains(':') => Ok((address.to_string(), None)),
+        Some((host, port)) => Ok((host.to_string(), Some(parse_port(address, port)?))),
+        None => Ok((address.to_string(), None)),
+    }
+}
+
+fn parse_port(address: &str, port: &str) -> Result<u16, String> {
+    port.parse()
+        .map_err(|_| format!("invalid port in listen address '{address}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<(String, Option<u16>), String> {
+        let address: ListenAddress = s.parse()?;
+        Ok((address.host, address.port))
+    }
+
+    #[test]
+    fn test_parse_host_and_port() -> Result<(), String> {
+        assert_eq!(parse("127.0.0.1")?, ("127.0.0.1".to_string(), None));
+        assert_eq!(parse("0.0.0.0:8080")?, ("0.0.0.0".to_string(), Some(8080)));
+        assert_eq!(parse("localhost:80")?, ("localhost".to_string(), Some(80)));
+        assert_eq!(parse("[::1]:8080")?, ("::1".to_string(), Some(8080)));
+        assert_eq!(parse("[::]")?, ("::".to_string(), None));
+        assert_eq!(
+            parse("::1")?,
+            ("::1".to_string(), None),
+            "IPv6 address without brackets should not be split"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tls() -> Result<(), String> {
+        let address: ListenAddress =
+            "[::]:8443,tls-cert=/etc/hakanai/cert.pem,tls-key=/etc/hakanai/key.pem".parse()?;
+
+        assert_eq!(
+            address.tls,
+            Some(TlsFiles {
+                cert_file: PathBuf::from("/etc/hakanai/cert.pem"),
+                key_file: PathBuf::from("/etc/hakanai/key.pem"),
+            })
+        );
+        assert_eq!(address.to_string(), "[::]:8443 (TLS)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let invalid = [
+            "",
+            "0.0.0.0:http",
+            "0.0.0.0:70000",
+            "[::1:8080",
+            "[::1]8080",
+            "0.0.0.0:8443,tls-cert=/cert.pem",
+            "0.0.0.0:8443,tls-key=/key.pem",
+            "0.0.0.0:8443,tls=on",
+        ];
+
+        for address in invalid {
+            assert!(
+                address.parse::<ListenAddress>().is_err(),
+                "'{address}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_default_port() -> Result<(), String> {
+        let address: ListenAddress = "::1".parse()?;
+        assert_eq!(address.with_default_port(8080).to_string(), "[::1]:8080");
+
+        let address: ListenAddress = "127.0.0.1:9090".parse()?;
+        assert_eq!(
+            address.with_default_port(8080).to_string(),
+            "127.0.0.1:9090"
+        );
+        Ok(())
+    }
+}
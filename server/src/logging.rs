@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Log output of the server with redaction of sensitive values.
+//!
+//! Every formatted log line passes through [`RedactingMakeWriter`] before it is written, which
+//! masks the values of sensitive fields (tokens, passphrase hashes), bearer credentials and the
+//! key fragments of secret links. This applies to messages, event fields and span fields in the
+//! text and the JSON format alike.
+//!
+//! Exports to OpenTelemetry receive the raw fields, so spans and events declaring a sensitive
+//! field are excluded from the export by [`export_filter`].
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use tracing::{Metadata, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::{FilterFn, filter_fn};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::options::LogFormat;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Names of fields whose values must never be logged.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "token",
+    "token_hash",
+    "admin_token",
+    "user_token",
+    "default_token",
+    "passphrase",
+    "passphrase_hash",
+    "authorization",
+    "x-secret-passphrase",
+];
+
+/// Optional ANSI escape sequences the text format uses to style field names.
+const ANSI: &str = r"(?:\x1b\[[0-9;]*m)*";
+
+/// Start of a field name, styled names directly follow an escape sequence without word boundary.
+const FIELD_START: &str = r"(?:\x1b\[[0-9;]*m|\b)";
+
+/// A quoted value of a sensitive field, e.g. `token_hash="..."`, `"token":"..."` or
+/// `passphrase_hash: Some("...")`. Quotes may be escaped if the value is part of a JSON string.
+static QUOTED_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(?P<key>{FIELD_START}(?:{})\b\\?"?{ANSI}\s*[=:]\s*{ANSI}(?:Some\()?)(?:(?P<q>")(?:[^"\\]|\\.)*"|(?P<eq>\\")(?:[^"\\]|\\[^"])*\\")"#,
+        sensitive_field_pattern()
+    ))
+    .expect("invalid quoted field pattern")
+});
+
+/// An unquoted value of a sensitive field in the text format, e.g. `token_hash=...`.
+static BARE_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(?P<key>{FIELD_START}(?:{})\b{ANSI}={ANSI})[^\s"\\\x1b,;]+"#,
+        sensitive_field_pattern()
+    ))
+    .expect("invalid bare field pattern")
+});
+
+static BEARER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(?P<scheme>Bearer\s+)[^\s"\\,;]+"#).expect("invalid bearer pattern")
+});
+
+/// The fragment of a link, which holds the key of a secret.
+static LINK_FRAGMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?P<url>https?://[^\s"'\\#<>]+)#[^\s"'\\<>]+"#)
+        .expect("invalid link fragment pattern")
+});
+
+fn sensitive_field_pattern() -> String {
+    SENSITIVE_FIELDS
+        .iter()
+        .map(|field| regex::escape(field))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Masks all sensitive values in a formatted log line.
+pub fn redact(line: &str) -> Cow<'_, str> {
+    let patterns: [(&Regex, String); 4] = [
+        (
+            &QUOTED_FIELD,
+            format!("${{key}}${{q}}${{eq}}{REDACTED}${{q}}${{eq}}"),
+        ),
+        (&BARE_FIELD, format!("${{key}}{REDACTED}")),
+        (&BEARER, format!("${{scheme}}{REDACTED}")),
+        (&LINK_FRAGMENT, format!("${{url}}#{REDACTED}")),
+    ];
+
+    let mut redacted = Cow::Borrowed(line);
+    for (pattern, replacement) in patterns {
+        let replaced = match pattern.replace_all(&redacted, replacement.as_str()) {
+            Cow::Owned(replaced) => Some(replaced),
+            Cow::Borrowed(_) => None,
+        };
+        if let Some(replaced) = replaced {
+            redacted = Cow::Owned(replaced);
+        }
+    }
+
+    redacted
+}
+
+fn is_sensitive_field(name: &str) -> bool {
+    SENSITIVE_FIELDS
+        .iter()
+        .any(|field| field.eq_ignore_ascii_case(name))
+}
+
+fn is_exportable(metadata: &Metadata<'_>) -> bool {
+    !metadata
+        .fields()
+        .iter()
+        .any(|field| is_sensitive_field(field.name()))
+}
+
+/// Filter for layers exporting telemetry, which excludes spans and events with sensitive fields.
+pub fn export_filter() -> FilterFn<impl Fn(&Metadata<'_>) -> bool> {
+    filter_fn(is_exportable)
+}
+
+/// Creates the layer writing the logs to stdout in the given format.
+pub fn fmt_layer<S>(format: LogFormat) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fmt_layer_with_writer(format, io::stdout)
+}
+
+fn fmt_layer_with_writer<S, W>(format: LogFormat, make_writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(RedactingMakeWriter::new(make_writer));
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// Wraps a [`MakeWriter`] to redact every log line before it is written.
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+        }
+    }
+}
+
+/// A writer redacting the data before passing it to the inner writer.
+///
+/// The formatter writes each event with a single call, so a line is never split across writes.
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        self.inner.write_all(redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{info, info_span, warn};
+    use tracing_subscriber::prelude::*;
+
+    const TOKEN: &str = "tok_3f9a1c7e5b";
+    const TOKEN_HASH: &str = "9f86d081884c7d659a2feaa0c55ad015";
+    const PASSPHRASE_HASH: &str = "salted$4a5e1e4baab89f3a";
+    const SECRET_KEY: &str = "q8LmV2xZrT0aYkPj";
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().expect("buffer poisoned")).to_string()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0
+                .lock()
+                .expect("buffer poisoned")
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn emit_sensitive_logs() {
+        let span = info_span!("get_token", token_hash = TOKEN_HASH);
+        let _guard = span.enter();
+
+        info!(passphrase_hash = ?Some(PASSPHRASE_HASH), "restrictions stored");
+        warn!(token = %TOKEN, "token rejected");
+        info!(
+            "webhook request: Authorization: Bearer {TOKEN}, link https://hakanai.example/s/01J9ZK#{SECRET_KEY}"
+        );
+    }
+
+    fn capture_logs(format: LogFormat) -> String {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(fmt_layer_with_writer(format, move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, emit_sensitive_logs);
+
+        buffer.contents()
+    }
+
+    fn assert_no_sensitive_markers(logs: &str) {
+        for marker in [TOKEN, TOKEN_HASH, PASSPHRASE_HASH, SECRET_KEY] {
+            assert!(
+                !logs.contains(marker),
+                "Logs must not contain '{marker}':\n{logs}"
+            );
+        }
+        assert!(logs.contains(REDACTED), "Logs should show redacted values");
+    }
+
+    #[test]
+    fn test_redact_text_fields() {
+        assert_eq!(
+            redact(r#"get_token{token_hash="abc123"}: token=abc123 ok"#),
+            r#"get_token{token_hash="[REDACTED]"}: token=[REDACTED] ok"#
+        );
+        assert_eq!(
+            redact(r#"restrictions=SecretRestrictions { passphrase_hash: Some("abc123") }"#),
+            r#"restrictions=SecretRestrictions { passphrase_hash: Some("[REDACTED]") }"#
+        );
+    }
+
+    #[test]
+    fn test_redact_ansi_styled_fields() {
+        let line = "\x1b[3mtoken_hash\x1b[0m\x1b[2m=\x1b[0m\"abc123\" \x1b[3mtoken\x1b[0m\x1b[2m=\x1b[0mabc123";
+
+        let redacted = redact(line);
+
+        assert!(
+            !redacted.contains("abc123"),
+            "Styled fields should be redacted: {redacted}"
+        );
+    }
+
+    #[test]
+    fn test_redact_json_fields() {
+        assert_eq!(
+            redact(r#"{"fields":{"token":"abc123","message":"x"}}"#),
+            r#"{"fields":{"token":"[REDACTED]","message":"x"}}"#
+        );
+        assert_eq!(
+            redact(r#"{"message":"stored passphrase_hash: Some(\"abc123\")"}"#),
+            r#"{"message":"stored passphrase_hash: Some(\"[REDACTED]\")"}"#,
+            "Escaped quotes must be kept to produce valid JSON"
+        );
+    }
+
+    #[test]
+    fn test_redact_bearer_and_link_fragments() {
+        assert_eq!(
+            redact("Authorization: Bearer abc123"),
+            "Authorization: Bearer [REDACTED]"
+        );
+        assert_eq!(
+            redact("link https://example.com/s/01J9ZK#key123 sent"),
+            "link https://example.com/s/01J9ZK#[REDACTED] sent"
+        );
+    }
+
+    #[test]
+    fn test_redact_keeps_harmless_lines() {
+        for line in [
+            "Failed to validate token: invalid signature",
+            "Default user token count: 3",
+            "Connecting to Redis",
+            "request_id=abc123 user_type=anonymous",
+        ] {
+            assert!(
+                matches!(redact(line), Cow::Borrowed(_)),
+                "Line should not be changed: {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_text_logs_contain_no_sensitive_markers() {
+        assert_no_sensitive_markers(&capture_logs(LogFormat::Text));
+    }
+
+    #[test]
+    fn test_json_logs_contain_no_sensitive_markers() {
+        let logs = capture_logs(LogFormat::Json);
+
+        assert_no_sensitive_markers(&logs);
+        for line in logs.lines() {
+            assert!(
+                serde_json::from_str::<serde_json::Value>(line).is_ok(),
+                "Redacted line should be valid JSON: {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_export_filter_excludes_sensitive_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(move || writer.clone())
+                .with_filter(export_filter()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            info!(token = TOKEN, "with token");
+            info!(request_id = "abc", "without token");
+        });
+
+        let logs = buffer.contents();
+        assert!(
+            !logs.contains("with token"),
+            "Events with sensitive fields must not be exported"
+        );
+        assert!(logs.contains("without token"));
+    }
+}
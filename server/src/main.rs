@@ -1,11 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod auth;
+mod config_file;
+mod listen_address;
+mod logging;
 mod metrics;
 mod observer;
 mod options;
 mod otel;
+mod policy;
+mod pool;
 mod secret;
 mod stats;
+mod tenant;
 mod token;
 mod user_type;
 mod web;
@@ -14,26 +21,26 @@ use std::io::Result;
 use std::sync::Arc;
 use std::time::Duration;
 
-use clap::Parser;
-use redis::aio::{ConnectionManager, ConnectionManagerConfig};
-use tokio::time::timeout;
+use redis::aio::ConnectionManagerConfig;
 use tracing::{debug, info, warn};
 
-use crate::metrics::{EventMetrics, MetricsCollector};
+use crate::metrics::{EventMetrics, MetricsCollector, RedisPoolMetrics};
 use crate::options::Args;
-use crate::secret::RedisSecretStore;
+use crate::pool::{CircuitBreaker, RedisPool};
+use crate::secret::{EncryptedSecretStore, RedisSecretStore};
 use crate::stats::RedisStatsStore;
+use crate::tenant::Tenant;
 use crate::token::{RedisTokenStore, TokenManager, TokenStore};
 
 #[actix_web::main]
 async fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = Args::parse_layered();
     if let Err(e) = args.validate() {
         eprintln!("Invalid config: {e}");
         return Err(std::io::Error::other(e));
     }
 
-    let otel_handler = match otel::init() {
+    let otel_handler = match otel::init(args.log_format) {
         Ok(handler) => handler,
         Err(err) => {
             warn!("Failed to initialize OpenTelemetry: {}", err);
@@ -43,7 +50,7 @@ async fn main() -> Result<()> {
 
     info!("Hakanai Server (v{})", env!("CARGO_PKG_VERSION"));
 
-    let redis_con = match connect_to_redis(&args).await {
+    let redis_con = match connect_to_redis(&args, otel_handler.is_some()).await {
         Ok(con) => con,
         Err(e) => {
             eprintln!("Failed to connect to Redis: {e}");
@@ -52,34 +59,83 @@ async fn main() -> Result<()> {
         }
     };
 
-    let secret_store = RedisSecretStore::new(redis_con.clone(), args.max_ttl);
+    let key_prefix = args.redis_key_prefix();
+    if args.migrate_redis_keys {
+        return match pool::migrate_keys(&redis_con, &key_prefix).await {
+            Ok(_) => Ok(()), // do not start server on migration
+            Err(e) => {
+                eprintln!("Failed to migrate Redis keys: {e}");
+                Err(std::io::Error::other(e))
+            }
+        };
+    }
+
+    let secret_store =
+        RedisSecretStore::new(redis_con.clone(), args.max_ttl).with_key_prefix(&key_prefix);
+    let storage_encryption_key = match args.load_storage_encryption_key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to load storage encryption key: {e}");
+            return Err(e);
+        }
+    };
+
+    let tenants = match &args.tenants_file {
+        Some(path) => match tenant::load_tenants(path) {
+            Ok(tenants) => tenants,
+            Err(e) => {
+                eprintln!("Invalid tenants: {e}");
+                return Err(std::io::Error::other(e));
+            }
+        },
+        None => Vec::new(),
+    };
 
-    let token_store = token::RedisTokenStore::new(redis_con.clone());
+    let token_store = token::RedisTokenStore::new(redis_con.clone()).with_key_prefix(&key_prefix);
     let token_manager = token::TokenManager::new(token_store.clone());
-    if args.reset_admin_token
-        && let Err(e) = reset_admin_token(&token_manager).await
-    {
-        eprintln!("Failed to reset admin token: {e}");
-        return Err(std::io::Error::other(e));
-    }
+    let tenant_token_managers: Vec<_> = tenants
+        .into_iter()
+        .map(|tenant| {
+            let tenant_token_store = token::RedisTokenStore::new(redis_con.clone())
+                .with_key_prefix(&tenant.redis_key_prefix(&key_prefix));
+            (Arc::new(tenant), TokenManager::new(tenant_token_store))
+        })
+        .collect();
+    let namespaces = std::iter::once((None, &token_manager)).chain(
+        tenant_token_managers
+            .iter()
+            .map(|(tenant, token_manager)| (Some(tenant.as_ref()), token_manager)),
+    );
 
-    if args.reset_user_tokens
-        && let Err(e) = reset_user_tokens(&token_manager).await
-    {
-        eprintln!("Failed to reset user tokens: {e}");
-        return Err(std::io::Error::other(e));
+    for (tenant, token_manager) in namespaces.clone() {
+        if args.reset_admin_token
+            && let Err(e) = reset_admin_token(token_manager, tenant).await
+        {
+            eprintln!("Failed to reset admin token: {e}");
+            return Err(std::io::Error::other(e));
+        }
+
+        if args.reset_user_tokens
+            && let Err(e) = reset_user_tokens(token_manager, tenant).await
+        {
+            eprintln!("Failed to reset user tokens: {e}");
+            return Err(std::io::Error::other(e));
+        }
     }
 
     if args.reset_user_tokens || args.reset_admin_token {
         return Ok(()); // do not start server on reset
     }
 
-    if let Err(e) = initialize_tokens(&token_manager, &args).await {
-        eprintln!("Failed to initialize tokens: {e}");
-        return Err(std::io::Error::other(e));
+    for (tenant, token_manager) in namespaces {
+        if let Err(e) = initialize_tokens(token_manager, tenant, &args).await {
+            eprintln!("Failed to initialize tokens: {e}");
+            return Err(std::io::Error::other(e));
+        }
     }
 
-    let stats_store = RedisStatsStore::new(redis_con.clone(), args.stats_ttl);
+    let stats_store =
+        RedisStatsStore::new(redis_con.clone(), args.stats_ttl).with_key_prefix(&key_prefix);
 
     if otel_handler.is_some() {
         initialize_metrics(&token_store, &stats_store);
@@ -91,7 +147,16 @@ async fn main() -> Result<()> {
         options = options.with_event_metrics(EventMetrics::new());
     }
 
-    let res = web::run_server(secret_store, token_manager, options).await;
+    let res = match storage_encryption_key {
+        Some(key) => {
+            info!("Encryption at rest enabled");
+            let secret_store = EncryptedSecretStore::new(secret_store, &key).map_err(|e| {
+                std::io::Error::other(format!("Invalid storage encryption key: {e}"))
+            })?;
+            web::run_server(secret_store, token_manager, tenant_token_managers, options).await
+        }
+        None => web::run_server(secret_store, token_manager, tenant_token_managers, options).await,
+    };
 
     if let Some(handler) = otel_handler {
         handler.shutdown()
@@ -100,7 +165,7 @@ async fn main() -> Result<()> {
     res
 }
 
-async fn connect_to_redis(args: &Args) -> anyhow::Result<ConnectionManager> {
+async fn connect_to_redis(args: &Args, with_metrics: bool) -> anyhow::Result<RedisPool> {
     info!("Connecting to Redis");
 
     let client = redis::Client::open(args.redis_dsn.clone())?;
@@ -108,44 +173,61 @@ async fn connect_to_redis(args: &Args) -> anyhow::Result<ConnectionManager> {
         .set_connection_timeout(Some(args.redis_connection_timeout))
         .set_max_delay(args.redis_reconnection_max_delay)
         .set_response_timeout(args.redis_response_timeout);
-    let con = timeout(
+    let breaker = CircuitBreaker::new(
+        args.redis_circuit_breaker_threshold,
+        args.redis_circuit_breaker_timeout,
+    );
+
+    let mut pool = RedisPool::new(
+        client,
+        config,
+        args.redis_pool_size,
         args.redis_connection_timeout,
-        redis::aio::ConnectionManager::new_with_config(client, config),
-    )
-    .await
-    .map_err(|_| anyhow::anyhow!("Timed out connecting to Redis"))??;
+        breaker,
+    );
+    if with_metrics {
+        pool = pool.with_metrics(RedisPoolMetrics::new());
+    }
 
-    Ok(con)
+    Ok(pool.connect().await?)
 }
 
-async fn reset_user_tokens<T: TokenStore>(token_manager: &TokenManager<T>) -> anyhow::Result<()> {
+async fn reset_user_tokens<T: TokenStore>(
+    token_manager: &TokenManager<T>,
+    tenant: Option<&Tenant>,
+) -> anyhow::Result<()> {
     let default_token = token_manager.reset_user_tokens().await?;
-    info!("Default user token: {default_token}");
+    print_generated_token("Default user token", tenant, &default_token);
     Ok(())
 }
 
-async fn reset_admin_token<T: TokenStore>(token_manager: &TokenManager<T>) -> anyhow::Result<()> {
+async fn reset_admin_token<T: TokenStore>(
+    token_manager: &TokenManager<T>,
+    tenant: Option<&Tenant>,
+) -> anyhow::Result<()> {
     let admin_token = token_manager.create_admin_token().await?;
-    info!("Admin token: {admin_token}");
+    print_generated_token("Admin token", tenant, &admin_token);
     Ok(())
 }
 
 async fn initialize_tokens<T: TokenStore>(
     token_manager: &TokenManager<T>,
+    tenant: Option<&Tenant>,
     args: &Args,
 ) -> anyhow::Result<()> {
     if args.enable_admin_token {
-        initialize_admin_token(token_manager).await?;
+        initialize_admin_token(token_manager, tenant).await?;
     }
 
-    initialize_user_tokens(token_manager).await
+    initialize_user_tokens(token_manager, tenant).await
 }
 
 async fn initialize_user_tokens<T: TokenStore>(
     token_manager: &TokenManager<T>,
+    tenant: Option<&Tenant>,
 ) -> anyhow::Result<()> {
     if let Some(default_token) = token_manager.create_default_token_if_none().await? {
-        info!("Default user token: {default_token}");
+        print_generated_token("Default user token", tenant, &default_token);
     }
 
     Ok(())
@@ -153,14 +235,25 @@ async fn initialize_user_tokens<T: TokenStore>(
 
 async fn initialize_admin_token<T: TokenStore>(
     token_manager: &TokenManager<T>,
+    tenant: Option<&Tenant>,
 ) -> anyhow::Result<()> {
     if let Some(admin_token) = token_manager.create_admin_token_if_none().await? {
-        info!("Admin token: {admin_token}");
+        print_generated_token("Admin token", tenant, &admin_token);
     };
 
     Ok(())
 }
 
+/// Prints a generated token to stderr, labeled with the tenant if it belongs to one.
+///
+/// Tokens are not passed to the logging, so they never end up in structured logs or exports.
+fn print_generated_token(label: &str, tenant: Option<&Tenant>, token: &str) {
+    match tenant {
+        Some(tenant) => eprintln!("{label} (tenant {}): {token}", tenant.id),
+        None => eprintln!("{label}: {token}"),
+    }
+}
+
 fn initialize_metrics(redis_token_store: &RedisTokenStore, redis_stats_store: &RedisStatsStore) {
     info!("Initializing metrics collection with 30s interval");
     let token_store = Arc::new(redis_token_store.clone());
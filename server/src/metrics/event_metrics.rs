@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use opentelemetry::global;
 use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::{KeyValue, global};
 
 // Histogram bucket definitions
 const SECRET_SIZE_BUCKETS: &[f64] = &[
@@ -44,6 +44,55 @@ const SECRET_LIFETIME_BUCKETS: &[f64] = &[
     604800.0, // 1 week
 ];
 
+const OBSERVER_LAG_BUCKETS: &[f64] = &[
+    1.0,     // 1 millisecond
+    5.0,     // 5 milliseconds
+    10.0,    // 10 milliseconds
+    50.0,    // 50 milliseconds
+    100.0,   // 100 milliseconds
+    500.0,   // 500 milliseconds
+    1000.0,  // 1 second
+    5000.0,  // 5 seconds
+    10000.0, // 10 seconds (default observer timeout)
+];
+
+/// Cause of a denied access to a secret, used as `cause` label of the denial counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessDenial {
+    /// The client IP is not in the allowed ranges.
+    IpDenied,
+    /// The client country is not allowed.
+    CountryDenied,
+    /// The client ASN is not allowed.
+    AsnDenied,
+    /// The secret requires a passphrase, but none was sent.
+    PassphraseMissing,
+    /// The sent passphrase is wrong.
+    PassphraseWrong,
+    /// The secret requires a PIN, but none was sent.
+    PinMissing,
+    /// The sent PIN is wrong.
+    PinWrong,
+    /// The secret was already retrieved.
+    Gone,
+}
+
+impl AccessDenial {
+    /// Returns the value of the `cause` label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessDenial::IpDenied => "ip_denied",
+            AccessDenial::CountryDenied => "country_denied",
+            AccessDenial::AsnDenied => "asn_denied",
+            AccessDenial::PassphraseMissing => "passphrase_missing",
+            AccessDenial::PassphraseWrong => "passphrase_wrong",
+            AccessDenial::PinMissing => "pin_missing",
+            AccessDenial::PinWrong => "pin_wrong",
+            AccessDenial::Gone => "gone",
+        }
+    }
+}
+
 /// Event-driven metrics for secret lifecycle events.
 ///
 /// This struct contains counters and histograms that are updated
@@ -67,6 +116,18 @@ pub struct EventMetrics {
 
     /// Histogram for tracking secret lifetime from creation to retrieval
     pub secret_lifetime_histogram: Histogram<u64>,
+
+    /// Histogram for tracking the time from an event until an observer handled it
+    pub observer_lag_histogram: Histogram<u64>,
+
+    /// Counter for events dropped because an observer exceeded its timeout
+    pub observer_timeouts_counter: Counter<u64>,
+
+    /// Counter for events dropped because an observer panicked
+    pub observer_panics_counter: Counter<u64>,
+
+    /// Counter for denied accesses to secrets, labeled by cause
+    pub access_denied_counter: Counter<u64>,
 }
 
 impl EventMetrics {
@@ -107,6 +168,59 @@ impl EventMetrics {
                 .with_description("Time from secret creation to retrieval in seconds")
                 .with_boundaries(SECRET_LIFETIME_BUCKETS.to_vec())
                 .build(),
+
+            observer_lag_histogram: meter
+                .u64_histogram("hakanai_observer_lag_milliseconds")
+                .with_description(
+                    "Time from a secret event until an observer handled it in milliseconds",
+                )
+                .with_boundaries(OBSERVER_LAG_BUCKETS.to_vec())
+                .build(),
+
+            observer_timeouts_counter: meter
+                .u64_counter("hakanai_observer_timeouts_total")
+                .with_description("Total number of events dropped because an observer timed out")
+                .build(),
+
+            observer_panics_counter: meter
+                .u64_counter("hakanai_observer_panics_total")
+                .with_description("Total number of events dropped because an observer panicked")
+                .build(),
+
+            access_denied_counter: meter
+                .u64_counter("hakanai_access_denied_total")
+                .with_description("Total number of denied accesses to secrets by cause")
+                .build(),
         }
     }
+
+    /// Counts a denied access to a secret.
+    pub fn record_access_denied(&self, cause: AccessDenial) {
+        self.access_denied_counter
+            .add(1, &[KeyValue::new("cause", cause.as_str())]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_denial_labels_are_unique() {
+        let causes = [
+            AccessDenial::IpDenied,
+            AccessDenial::CountryDenied,
+            AccessDenial::AsnDenied,
+            AccessDenial::PassphraseMissing,
+            AccessDenial::PassphraseWrong,
+            AccessDenial::PinMissing,
+            AccessDenial::PinWrong,
+            AccessDenial::Gone,
+        ];
+
+        let mut labels: Vec<&str> = causes.iter().map(AccessDenial::as_str).collect();
+        labels.sort();
+        labels.dedup();
+        assert_eq!(labels.len(), causes.len(), "Labels should be unique");
+    }
 }
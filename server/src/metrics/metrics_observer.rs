@@ -30,6 +30,10 @@ impl MetricsObserver {
 
 #[async_trait]
 impl SecretObserver for MetricsObserver {
+    fn name(&self) -> &'static str {
+        "metrics"
+    }
+
     #[instrument(skip(self, context))]
     async fn on_secret_created(&self, _secret_id: Ulid, context: &SecretEventContext) {
         // Determine user type for labeling
@@ -82,6 +86,7 @@ impl SecretObserver for MetricsObserver {
 /// - Bit 1 (2): Country restrictions
 /// - Bit 2 (4): ASN restrictions
 /// - Bit 3 (8): Passphrase protection
+/// - Bit 4 (16): Retrieval PIN
 fn bitfield_value_for_restrictions(restrictions: &SecretRestrictions) -> u32 {
     let mut bitfield = 0;
 
@@ -101,6 +106,10 @@ fn bitfield_value_for_restrictions(restrictions: &SecretRestrictions) -> u32 {
         bitfield |= 1 << 3;
     }
 
+    if restrictions.pin_hash.is_some() {
+        bitfield |= 1 << 4;
+    }
+
     bitfield
 }
 
@@ -141,6 +150,16 @@ mod tests {
         assert_eq!(value, 8);
     }
 
+    #[test]
+    fn test_bitfield_value_for_restrictions_pin_only() {
+        let restrictions = SecretRestrictions {
+            pin_hash: Some("hash".to_string()),
+            ..Default::default()
+        };
+        let value = bitfield_value_for_restrictions(&restrictions);
+        assert_eq!(value, 16);
+    }
+
     #[test]
     fn test_bitfield_value_for_restrictions_all_set() {
         let restrictions = SecretRestrictions::default()
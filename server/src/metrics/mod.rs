@@ -10,7 +10,9 @@ pub const METER_NAME: &str = "hakanai-server";
 mod event_metrics;
 mod metrics_collector;
 mod metrics_observer;
+mod redis_pool_metrics;
 
-pub use event_metrics::EventMetrics;
+pub use event_metrics::{AccessDenial, EventMetrics};
 pub use metrics_collector::MetricsCollector;
 pub use metrics_observer::MetricsObserver;
+pub use redis_pool_metrics::RedisPoolMetrics;
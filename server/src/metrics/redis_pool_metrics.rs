@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use opentelemetry::global;
+use opentelemetry::metrics::{Counter, Gauge};
+
+/// Metrics for the state of the Redis connection pool.
+#[derive(Clone)]
+pub struct RedisPoolMetrics {
+    /// Gauge for tracking the number of open connections in the pool
+    pub open_connections_gauge: Gauge<u64>,
+
+    /// Gauge for tracking the circuit breaker state (1 = open, 0 = closed)
+    pub circuit_open_gauge: Gauge<u64>,
+
+    /// Counter for connections retired after connection errors
+    pub retired_connections_counter: Counter<u64>,
+
+    /// Counter for requests rejected by the open circuit breaker
+    pub rejected_requests_counter: Counter<u64>,
+}
+
+impl RedisPoolMetrics {
+    /// Create a new set of pool metrics using the global meter provider.
+    pub fn new() -> Self {
+        let meter = global::meter(super::METER_NAME);
+
+        Self {
+            open_connections_gauge: meter
+                .u64_gauge("hakanai_redis_pool_open_connections")
+                .with_description("Number of open connections in the Redis pool")
+                .build(),
+
+            circuit_open_gauge: meter
+                .u64_gauge("hakanai_redis_circuit_open")
+                .with_description("Whether the Redis circuit breaker is open (1) or closed (0)")
+                .build(),
+
+            retired_connections_counter: meter
+                .u64_counter("hakanai_redis_pool_retired_connections_total")
+                .with_description("Total number of Redis connections retired after errors")
+                .build(),
+
+            rejected_requests_counter: meter
+                .u64_counter("hakanai_redis_circuit_rejected_requests_total")
+                .with_description("Total number of Redis requests rejected by the circuit breaker")
+                .build(),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use serde_json::json;
+use tracing::{instrument, warn};
+use ulid::Ulid;
+
+use super::{SecretEventContext, SecretObserver};
+
+/// Secret event a chat notification can be sent for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NotifyEvent {
+    /// A secret was created.
+    Created,
+
+    /// A secret was retrieved.
+    Retrieved,
+
+    /// Stored secrets were purged by an admin.
+    Purged,
+}
+
+/// Chat service receiving the notifications.
+#[derive(Clone, Debug)]
+pub enum ChatTarget {
+    /// Incoming webhook of a Slack channel.
+    Slack { webhook_url: String },
+
+    /// Matrix room, messages are sent as the user of the access token.
+    Matrix {
+        homeserver_url: String,
+        room_id: String,
+        access_token: String,
+    },
+}
+
+/// Posts human readable messages about secret events to a chat.
+///
+/// IDs of secrets are never posted, everybody in the chat could use them to burn the secret.
+#[derive(Clone)]
+pub struct ChatNotifierObserver {
+    target: ChatTarget,
+    events: Vec<NotifyEvent>,
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl SecretObserver for ChatNotifierObserver {
+    fn name(&self) -> &'static str {
+        match self.target {
+            ChatTarget::Slack { .. } => "slack",
+            ChatTarget::Matrix { .. } => "matrix",
+        }
+    }
+
+    #[instrument(skip(self, context))]
+    async fn on_secret_created(&self, _secret_id: Ulid, context: &SecretEventContext) {
+        if let Some(message) = self.message(NotifyEvent::Created, context, None) {
+            self.send(message).await;
+        }
+    }
+
+    #[instrument(skip(self, context))]
+    async fn on_secret_retrieved(&self, _secret_id: Ulid, context: &SecretEventContext) {
+        if let Some(message) = self.message(NotifyEvent::Retrieved, context, None) {
+            self.send(message).await;
+        }
+    }
+
+    #[instrument(skip(self, context))]
+    async fn on_secrets_purged(
+        &self,
+        count: usize,
+        older_than: Option<Duration>,
+        context: &SecretEventContext,
+    ) {
+        if let Some(message) = self.message(NotifyEvent::Purged, context, Some((count, older_than)))
+        {
+            self.send(message).await;
+        }
+    }
+}
+
+impl ChatNotifierObserver {
+    /// Creates a new notifier posting the given events to the target.
+    pub fn new(target: ChatTarget, events: Vec<NotifyEvent>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        Ok(ChatNotifierObserver {
+            target,
+            events,
+            client,
+        })
+    }
+
+    /// Formats the message for the event, `None` if the event is not enabled.
+    fn message(
+        &self,
+        event: NotifyEvent,
+        context: &SecretEventContext,
+        purged: Option<(usize, Option<Duration>)>,
+    ) -> Option<String> {
+        if !self.events.contains(&event) {
+            return None;
+        }
+
+        let mut message = match (event, purged) {
+            (NotifyEvent::Purged, Some((count, Some(older_than)))) => format!(
+                "{count} secret(s) older than {} were purged by an admin",
+                humantime::format_duration(older_than)
+            ),
+            (NotifyEvent::Purged, Some((count, None))) => {
+                format!("{count} secret(s) were purged by an admin")
+            }
+            (NotifyEvent::Retrieved, _) => "A secret was retrieved".to_string(),
+            _ => "A secret was created".to_string(),
+        };
+
+        let mut details = Vec::new();
+        if event == NotifyEvent::Created {
+            if let Some(user_type) = &context.user_type {
+                details.push(format!("user: {user_type}"));
+            }
+            if let Some(ttl) = context.ttl {
+                details.push(format!("ttl: {}", humantime::format_duration(ttl)));
+            }
+        }
+        if let Some(tenant) = &context.tenant {
+            details.push(format!("tenant: {tenant}"));
+        }
+
+        if !details.is_empty() {
+            message.push_str(&format!(" ({})", details.join(", ")));
+        }
+
+        Some(message)
+    }
+
+    #[instrument(skip(self))]
+    async fn send(&self, message: String) {
+        let req = match &self.target {
+            ChatTarget::Slack { webhook_url } => self
+                .client
+                .post(webhook_url)
+                .json(&json!({ "text": message })),
+            ChatTarget::Matrix {
+                homeserver_url,
+                room_id,
+                access_token,
+            } => match matrix_send_url(homeserver_url, room_id, &Ulid::r#gen().to_string()) {
+                Ok(url) => self
+                    .client
+                    .put(url)
+                    .bearer_auth(access_token)
+                    .json(&json!({ "msgtype": "m.text", "body": message })),
+                Err(e) => {
+                    warn!("Invalid Matrix homeserver URL: {e}");
+                    return;
+                }
+            },
+        };
+
+        if let Err(e) = req.send().await.and_then(|res| res.error_for_status()) {
+            warn!("Chat notification to {} failed: {e}", self.name());
+        }
+    }
+}
+
+/// URL of the Matrix client API to send a message to the room, `txn_id` makes retries idempotent.
+fn matrix_send_url(homeserver_url: &str, room_id: &str, txn_id: &str) -> Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(homeserver_url)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("URL cannot be a base"))?
+        .pop_if_empty()
+        .extend([
+            "_matrix",
+            "client",
+            "v3",
+            "rooms",
+            room_id,
+            "send",
+            "m.room.message",
+            txn_id,
+        ]);
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::HeaderMap;
+
+    use crate::user_type::UserType;
+
+    fn slack_notifier(events: Vec<NotifyEvent>) -> ChatNotifierObserver {
+        let target = ChatTarget::Slack {
+            webhook_url: "https://hooks.slack.com/services/T/B/X".to_string(),
+        };
+        ChatNotifierObserver::new(target, events).expect("Failed to create notifier")
+    }
+
+    #[test]
+    fn test_message_created() {
+        let notifier = slack_notifier(vec![NotifyEvent::Created]);
+        let context = SecretEventContext::new(HeaderMap::new())
+            .with_user_type(UserType::Authenticated)
+            .with_ttl(Duration::from_secs(3600))
+            .with_tenant("acme".to_string());
+
+        assert_eq!(
+            notifier.message(NotifyEvent::Created, &context, None),
+            Some("A secret was created (user: authenticated, ttl: 1h, tenant: acme)".to_string()),
+            "Created message should contain user type, TTL and tenant"
+        );
+    }
+
+    #[test]
+    fn test_message_purged() {
+        let notifier = slack_notifier(vec![NotifyEvent::Purged]);
+        let context = SecretEventContext::new(HeaderMap::new());
+
+        assert_eq!(
+            notifier.message(
+                NotifyEvent::Purged,
+                &context,
+                Some((3, Some(Duration::from_secs(86400))))
+            ),
+            Some("3 secret(s) older than 1day were purged by an admin".to_string()),
+            "Purged message should contain count and age"
+        );
+        assert_eq!(
+            notifier.message(NotifyEvent::Purged, &context, Some((5, None))),
+            Some("5 secret(s) were purged by an admin".to_string()),
+            "Purged message without age should only contain count"
+        );
+    }
+
+    #[test]
+    fn test_message_event_not_enabled() {
+        let notifier = slack_notifier(vec![NotifyEvent::Created]);
+        let context = SecretEventContext::new(HeaderMap::new());
+
+        assert_eq!(
+            notifier.message(NotifyEvent::Retrieved, &context, None),
+            None,
+            "Events not enabled should not be posted"
+        );
+    }
+
+    #[test]
+    fn test_matrix_send_url() {
+        let url = matrix_send_url("https://matrix.example.org/", "!room:example.org", "txn1")
+            .expect("Failed to build URL");
+
+        assert_eq!(
+            url.as_str(),
+            "https://matrix.example.org/_matrix/client/v3/rooms/!room:example.org/send/m.room.message/txn1",
+            "Room ID should be appended as path segment"
+        );
+    }
+
+    #[test]
+    fn test_matrix_send_url_invalid() {
+        assert!(
+            matrix_send_url("not a url", "!room:example.org", "txn1").is_err(),
+            "Invalid homeserver URL should be rejected"
+        );
+    }
+}
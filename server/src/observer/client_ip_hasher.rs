@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pseudonymization of client IP addresses for observers.
+//!
+//! IPs are hashed with HMAC-SHA256 keyed with a random salt, which is kept in memory only and
+//! replaced every day (UTC). Events of the same client can be correlated within a day, but the
+//! hashes can neither be reversed by brute forcing the IPv4 address space nor be linked across
+//! days or restarts.
+
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, KeyInit, Mac};
+use rand::Rng;
+use sha2::Sha256;
+
+/// Period after which the salt is replaced.
+pub const SALT_ROTATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+const SALT_SIZE: usize = 32;
+
+/// Number of bytes of the HMAC used for the hash, enough to tell clients apart.
+const HASH_SIZE: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hashes client IPs with a rotating salt, shared by all workers of the instance.
+#[derive(Clone)]
+pub struct ClientIpHasher {
+    rotation: Duration,
+    salt: Arc<Mutex<Salt>>,
+}
+
+struct Salt {
+    period: u64,
+    key: [u8; SALT_SIZE],
+}
+
+impl ClientIpHasher {
+    /// Creates a hasher replacing its salt after every `rotation`.
+    pub fn new(rotation: Duration) -> Self {
+        Self {
+            rotation,
+            salt: Arc::new(Mutex::new(Salt {
+                period: u64::MAX,
+                key: [0u8; SALT_SIZE],
+            })),
+        }
+    }
+
+    /// Returns the hex encoded hash of the IP with the salt of the current period.
+    pub fn hash(&self, ip: IpAddr) -> String {
+        self.hash_at(ip, SystemTime::now())
+    }
+
+    fn hash_at(&self, ip: IpAddr, now: SystemTime) -> String {
+        let key = self.salt_at(now);
+        let mut mac = HmacSha256::new_from_slice(&key).expect("HMAC accepts keys of any size");
+        mac.update(ip.to_string().as_bytes());
+
+        let hash = mac.finalize().into_bytes();
+        hash[..HASH_SIZE]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    /// Returns the salt of the period `now` falls into, a new one is generated if the period
+    /// changed since the last call.
+    fn salt_at(&self, now: SystemTime) -> [u8; SALT_SIZE] {
+        let secs = now
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let period = secs / self.rotation.as_secs().max(1);
+
+        let mut salt = self.salt.lock().expect("Failed to acquire lock");
+        if salt.period != period {
+            rand::rng().fill_bytes(&mut salt.key);
+            salt.period = period;
+        }
+
+        salt.key
+    }
+}
+
+impl Default for ClientIpHasher {
+    fn default() -> Self {
+        Self::new(SALT_ROTATION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+    const OTHER_CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+
+    #[test]
+    fn test_hash_is_stable_within_period() {
+        let hasher = ClientIpHasher::default();
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let hash = hasher.hash_at(CLIENT, now);
+        assert_eq!(hash.len(), HASH_SIZE * 2);
+        assert_eq!(
+            hash,
+            hasher.hash_at(CLIENT, now + Duration::from_secs(60)),
+            "Hash should be stable within a period"
+        );
+        assert_ne!(
+            hash,
+            hasher.hash_at(OTHER_CLIENT, now),
+            "Clients should have different hashes"
+        );
+        assert!(!hash.contains("192.168"), "Hash should not contain the IP");
+    }
+
+    #[test]
+    fn test_hash_changes_after_rotation() {
+        let hasher = ClientIpHasher::default();
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let hash = hasher.hash_at(CLIENT, now);
+        assert_ne!(
+            hash,
+            hasher.hash_at(CLIENT, now + SALT_ROTATION),
+            "Hash should change with the salt"
+        );
+    }
+
+    #[test]
+    fn test_hash_differs_between_instances() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        assert_ne!(
+            ClientIpHasher::default().hash_at(CLIENT, now),
+            ClientIpHasher::default().hash_at(CLIENT, now),
+            "Salts should be random"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use actix_web::http::header::HeaderMap;
 use async_trait::async_trait;
@@ -8,6 +9,9 @@ use ulid::Ulid;
 
 use super::{SecretEventContext, SecretObserver};
 
+/// Number of purged secrets and their age, `None` if all secrets were purged.
+type PurgeEvent = (usize, Option<Duration>);
+
 /// Mock implementation of SecretObserver trait for testing.
 ///
 /// This mock allows tracking secret creation and retrieval events
@@ -16,6 +20,9 @@ use super::{SecretEventContext, SecretObserver};
 pub struct MockObserver {
     created_events: Arc<Mutex<Vec<(Ulid, HeaderMap)>>>,
     retrieved_events: Arc<Mutex<Vec<(Ulid, HeaderMap)>>>,
+    purged_events: Arc<Mutex<Vec<PurgeEvent>>>,
+    delay: Option<Duration>,
+    panics: bool,
 }
 
 impl MockObserver {
@@ -23,6 +30,46 @@ impl MockObserver {
         MockObserver {
             created_events: Arc::new(Mutex::new(Vec::new())),
             retrieved_events: Arc::new(Mutex::new(Vec::new())),
+            purged_events: Arc::new(Mutex::new(Vec::new())),
+            delay: None,
+            panics: false,
+        }
+    }
+
+    /// Delays handling of every event, events are recorded afterwards.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Panics instead of handling events.
+    pub fn panicking(mut self) -> Self {
+        self.panics = true;
+        self
+    }
+
+    /// Waits until `count` events have been recorded, observers are notified in background tasks.
+    pub async fn wait_for_events(&self, count: usize) {
+        for _ in 0..100 {
+            if self.event_count() >= count {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    fn event_count(&self) -> usize {
+        self.get_created_events_mut().len()
+            + self.get_retrieved_events_mut().len()
+            + self.get_purged_events().len()
+    }
+
+    async fn handle_event(&self) {
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+        if self.panics {
+            panic!("mock observer panicked");
         }
     }
 
@@ -44,17 +91,43 @@ impl MockObserver {
     pub fn get_retrieved_events(&self) -> Vec<(Ulid, HeaderMap)> {
         self.get_retrieved_events_mut().clone()
     }
+
+    pub fn get_purged_events(&self) -> Vec<PurgeEvent> {
+        self.purged_events
+            .lock()
+            .expect("Failed to acquire lock")
+            .clone()
+    }
 }
 
 #[async_trait]
 impl SecretObserver for MockObserver {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
     async fn on_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
+        self.handle_event().await;
         self.get_created_events_mut()
             .push((secret_id, context.headers.clone()));
     }
 
     async fn on_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext) {
+        self.handle_event().await;
         self.get_retrieved_events_mut()
             .push((secret_id, context.headers.clone()));
     }
+
+    async fn on_secrets_purged(
+        &self,
+        count: usize,
+        older_than: Option<Duration>,
+        _context: &SecretEventContext,
+    ) {
+        self.handle_event().await;
+        self.purged_events
+            .lock()
+            .expect("Failed to acquire lock")
+            .push((count, older_than));
+    }
 }
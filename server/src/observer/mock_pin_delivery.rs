@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+
+use super::{PinDelivery, PinMessage};
+
+/// Mock implementation of PinDelivery trait for testing.
+///
+/// Records the delivered PINs, so tests can retrieve secrets with them.
+#[derive(Clone, Default)]
+pub struct MockPinDelivery {
+    messages: Arc<Mutex<Vec<PinMessage>>>,
+    fails: bool,
+}
+
+impl MockPinDelivery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails every delivery.
+    pub fn failing(mut self) -> Self {
+        self.fails = true;
+        self
+    }
+
+    /// Get all delivered PINs for testing verification
+    pub fn get_messages(&self) -> Vec<PinMessage> {
+        self.messages
+            .lock()
+            .expect("Failed to acquire lock")
+            .clone()
+    }
+}
+
+#[async_trait]
+impl PinDelivery for MockPinDelivery {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn deliver(&self, message: &PinMessage) -> Result<()> {
+        if self.fails {
+            return Err(anyhow!("delivery failed"));
+        }
+
+        self.messages
+            .lock()
+            .expect("Failed to acquire lock")
+            .push(message.clone());
+        Ok(())
+    }
+}
@@ -1,18 +1,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod chat_notifier_observer;
+mod client_ip_hasher;
 mod observer_manager;
+mod pin_delivery;
+mod reloadable_observer;
 mod secret_event_context;
 mod webhook_observer;
+mod webhook_signature;
 
 #[cfg(test)]
 mod mock_observer;
+#[cfg(test)]
+mod mock_pin_delivery;
 
+pub use chat_notifier_observer::{ChatNotifierObserver, ChatTarget, NotifyEvent};
+pub use client_ip_hasher::ClientIpHasher;
 pub use observer_manager::ObserverManager;
+pub use pin_delivery::{PinDelivery, PinDeliveryTarget, PinMessage, build_pin_delivery};
+pub use reloadable_observer::ReloadableObserver;
 pub use secret_event_context::SecretEventContext;
 pub use webhook_observer::WebhookObserver;
 
 #[cfg(test)]
 pub use mock_observer::MockObserver;
+#[cfg(test)]
+pub use mock_pin_delivery::MockPinDelivery;
+
+use std::time::Duration;
 
 use async_trait::async_trait;
 use ulid::Ulid;
@@ -20,9 +35,23 @@ use ulid::Ulid;
 /// Observer for secret lifecycle events.
 #[async_trait]
 pub trait SecretObserver: Send + Sync {
+    /// Name of the observer, used in logs and metrics.
+    fn name(&self) -> &'static str;
+
     /// Called when a secret is created.
     async fn on_secret_created(&self, secret_id: Ulid, context: &SecretEventContext);
 
     /// Called when a secret is retrieved.
     async fn on_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext);
+
+    /// Called when an admin purged stored secrets.
+    ///
+    /// `older_than` is the age of the purged secrets, `None` if all secrets were purged.
+    async fn on_secrets_purged(
+        &self,
+        _count: usize,
+        _older_than: Option<Duration>,
+        _context: &SecretEventContext,
+    ) {
+    }
 }
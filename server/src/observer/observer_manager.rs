@@ -1,38 +1,151 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use tracing::instrument;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::FutureExt;
+use opentelemetry::KeyValue;
+use tracing::{Instrument, error, instrument, warn};
 use ulid::Ulid;
 
 use super::{SecretEventContext, SecretObserver};
+use crate::metrics::EventMetrics;
+
+/// Default time an observer may take to handle a single event.
+const DEFAULT_OBSERVER_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Dispatches secret events to the registered observers.
+///
+/// Each observer handles an event in its own background task, so a slow or failing observer
+/// neither delays the request which triggered the event nor the other observers. Observers
+/// exceeding the timeout are cancelled, panics are logged and do not affect the server.
 pub struct ObserverManager {
-    observers: Vec<Box<dyn SecretObserver>>,
+    observers: Vec<Arc<dyn SecretObserver>>,
+    timeout: Duration,
+    event_metrics: Option<EventMetrics>,
+}
+
+/// A secret event to be dispatched to the observers.
+#[derive(Clone, Debug)]
+enum SecretEvent {
+    Created(Ulid),
+    Retrieved(Ulid),
+    Purged(usize, Option<Duration>),
+}
+
+impl SecretEvent {
+    async fn dispatch(&self, observer: &dyn SecretObserver, context: &SecretEventContext) {
+        match self {
+            SecretEvent::Created(secret_id) => {
+                observer.on_secret_created(*secret_id, context).await
+            }
+            SecretEvent::Retrieved(secret_id) => {
+                observer.on_secret_retrieved(*secret_id, context).await
+            }
+            SecretEvent::Purged(count, older_than) => {
+                observer
+                    .on_secrets_purged(*count, *older_than, context)
+                    .await
+            }
+        }
+    }
 }
 
 impl ObserverManager {
     pub fn new() -> Self {
         ObserverManager {
             observers: Vec::new(),
+            timeout: DEFAULT_OBSERVER_TIMEOUT,
+            event_metrics: None,
         }
     }
 
+    /// Sets the time an observer may take to handle a single event.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Records observer lag, timeouts and panics.
+    pub fn with_event_metrics(mut self, event_metrics: EventMetrics) -> Self {
+        self.event_metrics = Some(event_metrics);
+        self
+    }
+
     pub fn register_observer(&mut self, observer: Box<dyn SecretObserver>) {
-        self.observers.push(observer);
+        self.observers.push(Arc::from(observer));
     }
 
     /// Notify observers when a secret is created.
     #[instrument(skip(self, context))]
-    pub async fn notify_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
-        for observer in &self.observers {
-            observer.on_secret_created(secret_id, context).await;
-        }
+    pub fn notify_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
+        self.dispatch(SecretEvent::Created(secret_id), context);
     }
 
     /// Notify observers when a secret is retrieved.
     #[instrument(skip(self, context))]
-    pub async fn notify_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext) {
+    pub fn notify_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext) {
+        self.dispatch(SecretEvent::Retrieved(secret_id), context);
+    }
+
+    /// Notify observers when secrets are purged by an admin.
+    #[instrument(skip(self, context))]
+    pub fn notify_secrets_purged(
+        &self,
+        count: usize,
+        older_than: Option<Duration>,
+        context: &SecretEventContext,
+    ) {
+        self.dispatch(SecretEvent::Purged(count, older_than), context);
+    }
+
+    fn dispatch(&self, event: SecretEvent, context: &SecretEventContext) {
+        let emitted_at = Instant::now();
+        let context = Arc::new(context.clone());
+
         for observer in &self.observers {
-            observer.on_secret_retrieved(secret_id, context).await;
+            let observer = observer.clone();
+            let event = event.clone();
+            let context = context.clone();
+            let timeout = self.timeout;
+            let event_metrics = self.event_metrics.clone();
+
+            tokio::spawn(
+                async move {
+                    let name = observer.name();
+                    let labels = [KeyValue::new("observer", name)];
+                    let handled = AssertUnwindSafe(event.dispatch(observer.as_ref(), &context))
+                        .catch_unwind();
+
+                    match tokio::time::timeout(timeout, handled).await {
+                        Ok(Ok(())) => {
+                            if let Some(metrics) = &event_metrics {
+                                metrics
+                                    .observer_lag_histogram
+                                    .record(emitted_at.elapsed().as_millis() as u64, &labels);
+                            }
+                        }
+                        Ok(Err(_)) => {
+                            error!(
+                                observer = name,
+                                ?event,
+                                "Observer panicked while handling event"
+                            );
+                            if let Some(metrics) = &event_metrics {
+                                metrics.observer_panics_counter.add(1, &labels);
+                            }
+                        }
+                        Err(_) => {
+                            warn!(observer = name, ?event, "Observer timed out, event dropped");
+                            if let Some(metrics) = &event_metrics {
+                                metrics.observer_timeouts_counter.add(1, &labels);
+                            }
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
         }
     }
 }
@@ -58,7 +171,9 @@ mod tests {
         let secret_id = Ulid::r#gen();
         let context = SecretEventContext::new(HeaderMap::new());
 
-        manager.notify_secret_created(secret_id, &context).await;
+        manager.notify_secret_created(secret_id, &context);
+        observer1_ref.wait_for_events(1).await;
+        observer2_ref.wait_for_events(1).await;
 
         let created_events_1 = observer1_ref.get_created_events();
         let created_events_2 = observer2_ref.get_created_events();
@@ -97,7 +212,9 @@ mod tests {
         let secret_id = Ulid::r#gen();
         let context = SecretEventContext::new(HeaderMap::new());
 
-        manager.notify_secret_retrieved(secret_id, &context).await;
+        manager.notify_secret_retrieved(secret_id, &context);
+        observer1_ref.wait_for_events(1).await;
+        observer2_ref.wait_for_events(1).await;
 
         let retrieved_events_1 = observer1_ref.get_retrieved_events();
         let retrieved_events_2 = observer2_ref.get_retrieved_events();
@@ -121,4 +238,58 @@ mod tests {
             "Second observer should receive correct secret ID"
         );
     }
+
+    #[tokio::test]
+    async fn test_notify_does_not_wait_for_slow_observer() {
+        let mut manager = ObserverManager::new().with_timeout(Duration::from_millis(50));
+        let slow = MockObserver::new().with_delay(Duration::from_secs(10));
+        let fast = MockObserver::new();
+        manager.register_observer(Box::new(slow.clone()));
+        manager.register_observer(Box::new(fast.clone()));
+
+        let started = Instant::now();
+        manager.notify_secret_created(Ulid::r#gen(), &SecretEventContext::new(HeaderMap::new()));
+        assert!(
+            started.elapsed() < Duration::from_secs(1),
+            "Notifying should not wait for observers"
+        );
+
+        fast.wait_for_events(1).await;
+        assert_eq!(
+            fast.get_created_events().len(),
+            1,
+            "Fast observer should not be delayed by slow observer"
+        );
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(
+            slow.get_created_events().is_empty(),
+            "Slow observer should be cancelled after the timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_isolates_panicking_observer() {
+        let mut manager = ObserverManager::new();
+        let panicking = MockObserver::new().panicking();
+        let healthy = MockObserver::new();
+        manager.register_observer(Box::new(panicking));
+        manager.register_observer(Box::new(healthy.clone()));
+
+        let context = SecretEventContext::new(HeaderMap::new());
+        manager.notify_secret_created(Ulid::r#gen(), &context);
+        manager.notify_secret_retrieved(Ulid::r#gen(), &context);
+
+        healthy.wait_for_events(2).await;
+        assert_eq!(
+            healthy.get_created_events().len(),
+            1,
+            "Healthy observer should receive events despite panicking observer"
+        );
+        assert_eq!(
+            healthy.get_retrieved_events().len(),
+            1,
+            "Events after a panic should still be delivered"
+        );
+    }
 }
@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Out-of-band delivery of retrieval PINs, e.g. via an SMS gateway.
+//!
+//! Unlike observers, deliveries are awaited when a secret is created: a secret whose PIN could
+//! not be delivered cannot be retrieved, so its creation fails. The PIN is sent as JSON:
+//!
+//! ```json
+//! {"secret_id": "01J...", "recipient": "+49123456789", "pin": "123456", "tenant": "acme"}
+//! ```
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::instrument;
+use ulid::Ulid;
+
+use super::webhook_observer;
+use super::webhook_signature::SIGNATURE_HEADER_NAME;
+
+/// Delivers retrieval PINs to their recipients.
+#[async_trait]
+pub trait PinDelivery: Send + Sync {
+    /// Name of the delivery, used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Delivers the PIN, returns an error if the recipient will not receive it.
+    async fn deliver(&self, message: &PinMessage) -> Result<()>;
+}
+
+/// PIN of a secret and its recipient.
+#[derive(Clone, Serialize)]
+pub struct PinMessage {
+    /// Unique identifier of the secret.
+    pub secret_id: Ulid,
+
+    /// Recipient requested by the sender (e.g. a phone number).
+    pub recipient: String,
+
+    /// The PIN required to retrieve the secret.
+    pub pin: String,
+
+    /// Tenant the secret was created for, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+}
+
+impl std::fmt::Debug for PinMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PinMessage")
+            .field("secret_id", &self.secret_id)
+            .field("recipient", &"[REDACTED]")
+            .field("pin", &"[REDACTED]")
+            .field("tenant", &self.tenant)
+            .finish()
+    }
+}
+
+/// Service delivering the PINs.
+#[derive(Clone, Debug)]
+pub enum PinDeliveryTarget {
+    /// HTTP endpoint (e.g. an SMS gateway) receiving the PIN via POST.
+    Webhook {
+        url: String,
+        token: Option<String>,
+        secret: Option<String>,
+    },
+
+    /// Command receiving the PIN on stdin, exit code 0 confirms the delivery.
+    Command { program: PathBuf },
+}
+
+/// Creates the delivery for the target, which has to finish within the timeout.
+pub fn build_pin_delivery(
+    target: PinDeliveryTarget,
+    timeout: Duration,
+) -> Result<Arc<dyn PinDelivery>> {
+    Ok(match target {
+        PinDeliveryTarget::Webhook { url, token, secret } => {
+            Arc::new(WebhookPinDelivery::new(url, token, secret, timeout)?)
+        }
+        PinDeliveryTarget::Command { program } => {
+            Arc::new(CommandPinDelivery::new(program, timeout))
+        }
+    })
+}
+
+/// Posts PINs to an HTTP endpoint, signed like webhook events if a secret is configured.
+pub struct WebhookPinDelivery {
+    url: String,
+    auth_token: Option<String>,
+    signing_secret: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookPinDelivery {
+    pub fn new(
+        url: String,
+        auth_token: Option<String>,
+        signing_secret: Option<String>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+
+        Ok(Self {
+            url,
+            auth_token,
+            signing_secret,
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl PinDelivery for WebhookPinDelivery {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    #[instrument(skip(self), err)]
+    async fn deliver(&self, message: &PinMessage) -> Result<()> {
+        let body = serde_json::to_vec(message)?;
+
+        let mut req = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        if let Some(token) = &self.auth_token {
+            req = req.bearer_auth(token);
+        }
+
+        if let Some(secret) = &self.signing_secret {
+            let signature = webhook_observer::signature(secret, &body).map_err(|e| anyhow!(e))?;
+            req = req.header(SIGNATURE_HEADER_NAME, signature);
+        }
+
+        req.body(body).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Passes PINs to an external command, e.g. a script calling the API of an SMS provider.
+#[derive(Clone, Debug)]
+pub struct CommandPinDelivery {
+    program: PathBuf,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl CommandPinDelivery {
+    pub fn new(program: PathBuf, timeout: Duration) -> Self {
+        Self {
+            program,
+            args: Vec::new(),
+            timeout,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_args(mut self, args: &[&str]) -> Self {
+        self.args = args.iter().map(|arg| arg.to_string()).collect();
+        self
+    }
+
+    async fn run(&self, input: &[u8]) -> Result<()> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            match stdin.write_all(input).await {
+                Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(anyhow!("{} failed: {status}", self.program.display()));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PinDelivery for CommandPinDelivery {
+    fn name(&self) -> &'static str {
+        "command"
+    }
+
+    #[instrument(skip(self), err)]
+    async fn deliver(&self, message: &PinMessage) -> Result<()> {
+        let input = serde_json::to_vec(message)?;
+
+        tokio::time::timeout(self.timeout, self.run(&input))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "{} timed out after {:?}",
+                    self.program.display(),
+                    self.timeout
+                )
+            })?
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn message() -> PinMessage {
+        PinMessage {
+            secret_id: Ulid::r#gen(),
+            recipient: "+49123456789".to_string(),
+            pin: "123456".to_string(),
+            tenant: None,
+        }
+    }
+
+    fn shell_delivery(script: &str) -> CommandPinDelivery {
+        CommandPinDelivery::new(PathBuf::from("/bin/sh"), Duration::from_secs(5))
+            .with_args(&["-c", script])
+    }
+
+    #[tokio::test]
+    async fn test_command_receives_message() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let output = dir.path().join("message.json");
+        let delivery = shell_delivery(&format!("cat > {}", output.display()));
+
+        let message = message();
+        delivery.deliver(&message).await?;
+
+        let written: serde_json::Value = serde_json::from_slice(&std::fs::read(&output)?)?;
+        assert_eq!(written["secret_id"], message.secret_id.to_string());
+        assert_eq!(written["recipient"], "+49123456789");
+        assert_eq!(written["pin"], "123456");
+        assert!(written.get("tenant").is_none(), "Unset tenant is omitted");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_command_failure() {
+        let delivery = shell_delivery("exit 1");
+
+        assert!(delivery.deliver(&message()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_command_timeout() {
+        let delivery = CommandPinDelivery::new(PathBuf::from("/bin/sh"), Duration::from_millis(50))
+            .with_args(&["-c", "sleep 5"]);
+
+        let result = delivery.deliver(&message()).await;
+
+        assert!(
+            result.is_err_and(|e| e.to_string().contains("timed out")),
+            "Delivery should time out"
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_pin() {
+        let debug = format!("{:?}", message());
+
+        assert!(!debug.contains("123456"), "PIN should be redacted");
+        assert!(
+            !debug.contains("+49123456789"),
+            "Recipient should be redacted"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwapOption;
+use async_trait::async_trait;
+use ulid::Ulid;
+
+use super::{SecretEventContext, SecretObserver};
+
+/// Delegates events to an observer which can be replaced at runtime, e.g. on configuration
+/// reload. Clones share the observer, so replacing it affects all workers.
+pub struct ReloadableObserver<T> {
+    current: Arc<ArcSwapOption<T>>,
+}
+
+impl<T> Clone for ReloadableObserver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<T: SecretObserver> ReloadableObserver<T> {
+    pub fn new(observer: Option<T>) -> Self {
+        Self {
+            current: Arc::new(ArcSwapOption::from(observer.map(Arc::new))),
+        }
+    }
+
+    /// Replaces the observer, events are dropped if `None`.
+    pub fn replace(&self, observer: Option<T>) {
+        self.current.store(observer.map(Arc::new));
+    }
+}
+
+#[async_trait]
+impl<T: SecretObserver> SecretObserver for ReloadableObserver<T> {
+    fn name(&self) -> &'static str {
+        self.current
+            .load()
+            .as_ref()
+            .map_or("reloadable", |observer| observer.name())
+    }
+
+    async fn on_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
+        if let Some(observer) = self.current.load_full() {
+            observer.on_secret_created(secret_id, context).await;
+        }
+    }
+
+    async fn on_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext) {
+        if let Some(observer) = self.current.load_full() {
+            observer.on_secret_retrieved(secret_id, context).await;
+        }
+    }
+
+    async fn on_secrets_purged(
+        &self,
+        count: usize,
+        older_than: Option<Duration>,
+        context: &SecretEventContext,
+    ) {
+        if let Some(observer) = self.current.load_full() {
+            observer.on_secrets_purged(count, older_than, context).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::HeaderMap;
+
+    use crate::observer::MockObserver;
+
+    #[tokio::test]
+    async fn test_replace_observer() {
+        let first = MockObserver::new();
+        let second = MockObserver::new();
+        let observer = ReloadableObserver::new(Some(first.clone()));
+        let worker = observer.clone();
+        let context = SecretEventContext::new(HeaderMap::new());
+
+        worker.on_secret_created(Ulid::r#gen(), &context).await;
+        observer.replace(Some(second.clone()));
+        worker.on_secret_created(Ulid::r#gen(), &context).await;
+        observer.replace(None);
+        worker.on_secret_created(Ulid::r#gen(), &context).await;
+
+        assert_eq!(first.get_created_events().len(), 1);
+        assert_eq!(
+            second.get_created_events().len(),
+            1,
+            "Events should be sent to the replacing observer in all clones"
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use actix_web::http::header::HeaderMap;
 
-use hakanai_lib::models::SecretRestrictions;
+use hakanai_lib::models::{CountryCode, SecretRestrictions};
 
 use crate::user_type::UserType;
 
@@ -17,10 +17,20 @@ pub struct SecretEventContext {
     pub headers: HeaderMap,
     /// User type associated with the secret event, if any.
     pub user_type: Option<UserType>,
+    /// Name of the user authenticated by an identity provider, if any.
+    pub user_name: Option<String>,
     /// Restrictions associated with the secret event, if any.
     pub restrictions: Option<SecretRestrictions>,
     /// Size of the secret, if known.
     pub size: Option<usize>,
+    /// Identifier of the tenant the event occurred in, `None` for the default tenant.
+    pub tenant: Option<String>,
+    /// Hash of the client IP with a daily rotating salt (see `ClientIpHasher`), if resolved.
+    pub client_ip_hash: Option<String>,
+    /// Country of the client, if a country header is configured and sent.
+    pub country: Option<CountryCode>,
+    /// ASN of the client, if an ASN header is configured and sent.
+    pub asn: Option<u32>,
 }
 
 impl SecretEventContext {
@@ -28,9 +38,14 @@ impl SecretEventContext {
         SecretEventContext {
             headers,
             user_type: None,
+            user_name: None,
             restrictions: None,
             ttl: None,
             size: None,
+            tenant: None,
+            client_ip_hash: None,
+            country: None,
+            asn: None,
         }
     }
 
@@ -39,6 +54,11 @@ impl SecretEventContext {
         self
     }
 
+    pub fn with_user_name(mut self, user_name: String) -> Self {
+        self.user_name = Some(user_name);
+        self
+    }
+
     pub fn with_restrictions(mut self, restrictions: SecretRestrictions) -> Self {
         self.restrictions = Some(restrictions);
         self
@@ -53,4 +73,24 @@ impl SecretEventContext {
         self.size = Some(size);
         self
     }
+
+    pub fn with_tenant(mut self, tenant: String) -> Self {
+        self.tenant = Some(tenant);
+        self
+    }
+
+    pub fn with_client_ip_hash(mut self, client_ip_hash: String) -> Self {
+        self.client_ip_hash = Some(client_ip_hash);
+        self
+    }
+
+    pub fn with_country(mut self, country: CountryCode) -> Self {
+        self.country = Some(country);
+        self
+    }
+
+    pub fn with_asn(mut self, asn: u32) -> Self {
+        self.asn = Some(asn);
+        self
+    }
 }
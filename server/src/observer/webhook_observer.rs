@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use actix_web::http::header::HeaderMap;
 use anyhow::Result;
@@ -10,6 +10,9 @@ use serde::{Deserialize, Serialize};
 use tracing::{instrument, warn};
 use ulid::Ulid;
 
+use hakanai_lib::utils::asn;
+
+use super::webhook_signature::{self, SIGNATURE_HEADER_NAME};
 use super::{SecretEventContext, SecretObserver};
 
 /// Webhook action types.
@@ -17,13 +20,15 @@ use super::{SecretEventContext, SecretObserver};
 pub enum WebhookAction {
     Created,
     Retrieved,
+    Purged,
 }
 
 /// Webhook notification payload.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WebhookPayload {
-    /// Unique identifier of the secret.
-    pub secret_id: Ulid,
+    /// Unique identifier of the secret (not set for events affecting multiple secrets).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_id: Option<Ulid>,
     /// Action that triggered the webhook.
     pub action: WebhookAction,
     /// Additional details about the request (e.g. exctracted from headers)
@@ -34,21 +39,34 @@ pub struct WebhookPayload {
 pub struct WebhookObserver {
     url: String,
     auth_token: Option<String>,
+    signing_secret: Option<String>,
     client: reqwest::Client,
     header_names: Vec<String>,
 }
 
 #[async_trait]
 impl SecretObserver for WebhookObserver {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
     #[instrument(skip(self, context))]
     async fn on_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
-        let mut details = self.filter_headers(&context.headers);
+        let mut details = self.context_details(context);
         if let Some(user_type) = &context.user_type {
             details.insert("user_type".to_string(), user_type.to_string());
         }
 
+        if let Some(user_name) = &context.user_name {
+            details.insert("user_name".to_string(), user_name.clone());
+        }
+
         if let Some(restrictions) = &context.restrictions {
             details.insert("restrictions".to_string(), restrictions.to_string());
+
+            if let Some(allowed_asns) = &restrictions.allowed_asns {
+                details.insert("allowed_asns".to_string(), describe_asns(allowed_asns));
+            }
         }
 
         if let Some(ttl) = context.ttl {
@@ -63,7 +81,7 @@ impl SecretObserver for WebhookObserver {
         }
 
         let payload = WebhookPayload {
-            secret_id,
+            secret_id: Some(secret_id),
             action: WebhookAction::Created,
             details,
         };
@@ -73,9 +91,33 @@ impl SecretObserver for WebhookObserver {
     #[instrument(skip(self, context))]
     async fn on_secret_retrieved(&self, secret_id: Ulid, context: &SecretEventContext) {
         let payload = WebhookPayload {
-            secret_id,
+            secret_id: Some(secret_id),
             action: WebhookAction::Retrieved,
-            details: self.filter_headers(&context.headers),
+            details: self.context_details(context),
+        };
+        self.send_webhook(payload).await;
+    }
+
+    #[instrument(skip(self, context))]
+    async fn on_secrets_purged(
+        &self,
+        count: usize,
+        older_than: Option<Duration>,
+        context: &SecretEventContext,
+    ) {
+        let mut details = self.context_details(context);
+        details.insert("count".to_string(), count.to_string());
+        if let Some(older_than) = older_than {
+            details.insert(
+                "older_than".to_string(),
+                humantime::format_duration(older_than).to_string(),
+            );
+        }
+
+        let payload = WebhookPayload {
+            secret_id: None,
+            action: WebhookAction::Purged,
+            details,
         };
         self.send_webhook(payload).await;
     }
@@ -91,19 +133,49 @@ impl WebhookObserver {
         Ok(WebhookObserver {
             url,
             auth_token,
+            signing_secret: None,
             client,
             header_names: header_names.iter().map(|h| h.to_lowercase()).collect(),
         })
     }
 
+    /// Signs the requests with the secret, so receivers can authenticate them.
+    pub fn with_signing_secret(mut self, secret: String) -> Self {
+        self.signing_secret = Some(secret);
+        self
+    }
+
     #[instrument(skip(self))]
     async fn send_webhook(&self, payload: WebhookPayload) {
-        let mut req = self.client.post(&self.url).json(&payload);
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to serialize webhook payload: {e}");
+                return;
+            }
+        };
+
+        let mut req = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
 
         if let Some(token) = &self.auth_token {
             req = req.bearer_auth(token);
         }
 
+        if let Some(secret) = &self.signing_secret {
+            match signature(secret, &body) {
+                Ok(signature) => req = req.header(SIGNATURE_HEADER_NAME, signature),
+                Err(e) => {
+                    warn!("Failed to sign webhook: {e}");
+                    return;
+                }
+            }
+        }
+
+        let req = req.body(body);
+
         tokio::spawn(async move {
             if let Err(e) = req.send().await {
                 warn!("Webhook failed: {e}");
@@ -111,6 +183,28 @@ impl WebhookObserver {
         });
     }
 
+    /// Details of all events: the configured headers, the tenant and the resolved client, if any.
+    fn context_details(&self, context: &SecretEventContext) -> HashMap<String, String> {
+        let mut details = self.filter_headers(&context.headers);
+        if let Some(tenant) = &context.tenant {
+            details.insert("tenant".to_string(), tenant.clone());
+        }
+
+        if let Some(client_ip_hash) = &context.client_ip_hash {
+            details.insert("client_ip_hash".to_string(), client_ip_hash.clone());
+        }
+
+        if let Some(country) = &context.country {
+            details.insert("country".to_string(), country.to_string());
+        }
+
+        if let Some(asn) = context.asn {
+            details.insert("asn".to_string(), asn::describe(asn));
+        }
+
+        details
+    }
+
     fn filter_headers(&self, headers: &HeaderMap) -> HashMap<String, String> {
         let mut filtered = HashMap::new();
 
@@ -128,3 +222,21 @@ impl WebhookObserver {
         filtered
     }
 }
+
+/// Lists the ASNs with the names of the organizations operating them, if known.
+fn describe_asns(asns: &[u32]) -> String {
+    asns.iter()
+        .map(|number| asn::describe(*number))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Signs the body with the current time and a unique nonce.
+pub(super) fn signature(secret: &str, body: &[u8]) -> Result<String, String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+    let nonce = Ulid::r#gen().to_string();
+    webhook_signature::header_value(secret, timestamp, &nonce, body)
+}
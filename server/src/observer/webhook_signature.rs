@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signatures of webhook requests, so receivers can authenticate events and reject replays.
+//!
+//! The `X-Hakanai-Signature` header has the format `t=<timestamp>,nonce=<nonce>,v1=<signature>`
+//! where the signature is the hex encoded HMAC-SHA256 over `<timestamp>.<nonce>.<body>` keyed
+//! with the webhook secret. Receivers should reject requests with a timestamp outside of a
+//! tolerance (e.g. 5 minutes) and nonces already seen within this tolerance.
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+/// Name of the header containing the signature.
+pub const SIGNATURE_HEADER_NAME: &str = "X-Hakanai-Signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Creates the value of the signature header for the body sent at `timestamp` (UNIX timestamp in
+/// seconds). The nonce has to be unique for every request.
+pub fn header_value(
+    secret: &str,
+    timestamp: u64,
+    nonce: &str,
+    body: &[u8],
+) -> Result<String, String> {
+    let signature = sign(secret, timestamp, nonce, body)?;
+    Ok(format!("t={timestamp},nonce={nonce},v1={signature}"))
+}
+
+fn sign(secret: &str, timestamp: u64, nonce: &str, body: &[u8]) -> Result<String, String> {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).map_err(|e| e.to_string())?;
+    mac.update(format!("{timestamp}.{nonce}.").as_bytes());
+    mac.update(body);
+
+    let hash = mac.finalize().into_bytes();
+    Ok(hash.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use subtle::ConstantTimeEq;
+
+    /// Maximum age of a request accepted by the receiver.
+    const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+    const SECRET: &str = "webhook_secret";
+    const NOW: u64 = 1_700_000_000;
+    const BODY: &[u8] = br#"{"action":"Created"}"#;
+
+    /// Verification as done by receivers, returns the nonce of a valid signature.
+    fn verify(header: &str, body: &[u8], now: u64) -> Option<String> {
+        let mut timestamp = None;
+        let mut nonce = None;
+        let mut signature = None;
+        for part in header.split(',') {
+            match part.split_once('=')? {
+                ("t", value) => timestamp = value.parse::<u64>().ok(),
+                ("nonce", value) => nonce = Some(value),
+                ("v1", value) => signature = Some(value),
+                _ => {}
+            }
+        }
+
+        let (timestamp, nonce, signature) = (timestamp?, nonce?, signature?);
+        if now.abs_diff(timestamp) > DEFAULT_TOLERANCE.as_secs() {
+            return None;
+        }
+
+        let expected = sign(SECRET, timestamp, nonce, body).ok()?;
+        bool::from(expected.as_bytes().ct_eq(signature.as_bytes())).then(|| nonce.to_string())
+    }
+
+    #[test]
+    fn test_header_value_verifies() -> Result<(), String> {
+        let header = header_value(SECRET, NOW, "nonce1", BODY)?;
+        assert!(header.starts_with(&format!("t={NOW},nonce=nonce1,v1=")));
+        assert_eq!(
+            verify(&header, BODY, NOW + 10).as_deref(),
+            Some("nonce1"),
+            "Signature should be valid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_modified_body_is_rejected() -> Result<(), String> {
+        let header = header_value(SECRET, NOW, "nonce1", BODY)?;
+        assert!(
+            verify(&header, br#"{"action":"Purged"}"#, NOW).is_none(),
+            "Modified body should be rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_modified_timestamp_or_nonce_is_rejected() -> Result<(), String> {
+        let header = header_value(SECRET, NOW, "nonce1", BODY)?;
+        let replayed = header.replace(&format!("t={NOW}"), &format!("t={}", NOW + 600));
+        assert!(
+            verify(&replayed, BODY, NOW + 600).is_none(),
+            "Refreshed timestamp should be rejected"
+        );
+
+        let replayed = header.replace("nonce=nonce1", "nonce=nonce2");
+        assert!(
+            verify(&replayed, BODY, NOW).is_none(),
+            "Changed nonce should be rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_signature_is_rejected() -> Result<(), String> {
+        let header = header_value(SECRET, NOW, "nonce1", BODY)?;
+        assert!(
+            verify(&header, BODY, NOW + DEFAULT_TOLERANCE.as_secs() + 1).is_none(),
+            "Signature outside of the tolerance should be rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_different_secret_is_rejected() -> Result<(), String> {
+        let header = header_value("other_secret", NOW, "nonce1", BODY)?;
+        assert!(
+            verify(&header, BODY, NOW).is_none(),
+            "Signature of another secret should be rejected"
+        );
+        Ok(())
+    }
+}
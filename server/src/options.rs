@@ -1,14 +1,41 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ffi::OsString;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+use serde::Serialize;
 
-use hakanai_lib::utils::{human_size, ip};
+use hakanai_lib::utils::{human_duration, human_size, ip};
+
+use crate::config_file::ConfigFile;
+use crate::listen_address::ListenAddress;
+use crate::observer::{ChatTarget, NotifyEvent, PinDeliveryTarget};
+
+/// Color theme of the web interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiTheme {
+    Light,
+    Dark,
+}
+
+/// Format of the log output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines.
+    #[default]
+    Text,
+
+    /// One JSON object per line, for log aggregation.
+    Json,
+}
 
 /// Parse a size limit for server configuration, returns value in bytes
-fn parse_size_limit_bytes(s: &str) -> Result<usize, String> {
+pub(crate) fn parse_size_limit_bytes(s: &str) -> Result<usize, String> {
     let bytes = human_size::parse(s)?;
     Ok(bytes.max(1) as usize)
 }
@@ -24,6 +51,9 @@ pub struct WebhookArgs {
 
     /// Comma-separated list of HTTP headers to include
     pub headers: Vec<String>,
+
+    /// Secret to sign webhook requests with
+    pub secret: Option<String>,
 }
 
 /// Represents the command-line arguments for the server.
@@ -46,15 +76,17 @@ pub struct Args {
     )]
     pub port: u16,
 
-    /// The network address on which the server will listen.
+    /// The network addresses on which the server will listen.
     #[arg(
         short,
-        long,
+        long = "listen",
+        alias = "listen-address",
         value_name = "LISTEN_ADDRESS",
         env = "HAKANAI_LISTEN_ADDRESS",
-        default_value = "127.0.0.1"
+        default_value = "127.0.0.1",
+        help = "Address to listen on as HOST[:PORT][,tls-cert=PATH,tls-key=PATH], can be specified multiple times (e.g. [::1]:8080 and 0.0.0.0:8080). Without port --port is used, with tls-cert and tls-key the listener serves HTTPS."
     )]
-    pub listen_address: String,
+    pub listen_address: Vec<ListenAddress>,
 
     /// The Data Source Name (DSN) for the Redis database.
     #[arg(
@@ -95,13 +127,58 @@ pub struct Args {
     )]
     pub redis_response_timeout: Option<Duration>,
 
+    /// Prefix for all redis keys, allows multiple instances to share one redis
+    #[arg(
+        long,
+        value_name = "REDIS_KEY_PREFIX",
+        env = "HAKANAI_REDIS_KEY_PREFIX",
+        default_value = "",
+        help = "Prefix for all Redis keys (e.g. staging), allows multiple instances to share one Redis. A trailing ':' is added if missing."
+    )]
+    pub redis_key_prefix: String,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Only renames existing unprefixed Redis keys to use --redis-key-prefix, does not start the server"
+    )]
+    pub migrate_redis_keys: bool,
+
+    /// The number of connections in the redis connection pool
+    #[arg(
+        long,
+        value_name = "REDIS_POOL_SIZE",
+        env = "HAKANAI_REDIS_POOL_SIZE",
+        default_value = "4"
+    )]
+    pub redis_pool_size: usize,
+
+    /// The number of consecutive redis connection failures after which requests are rejected
+    #[arg(
+        long,
+        value_name = "REDIS_CIRCUIT_BREAKER_THRESHOLD",
+        env = "HAKANAI_REDIS_CIRCUIT_BREAKER_THRESHOLD",
+        default_value = "5"
+    )]
+    pub redis_circuit_breaker_threshold: u32,
+
+    /// The duration requests are rejected before redis is probed again
+    #[arg(
+        long,
+        value_name = "REDIS_CIRCUIT_BREAKER_TIMEOUT",
+        env = "HAKANAI_REDIS_CIRCUIT_BREAKER_TIMEOUT",
+        default_value = "10s",
+        value_parser = humantime::parse_duration
+    )]
+    pub redis_circuit_breaker_timeout: Duration,
+
     #[arg(
         long,
         value_name = "STATS_TTL",
         env = "HAKANAI_STATS_TTL",
         default_value = "30d",
         help = "Time to live for stats data in Redis (e.g., 30d, 720h, 2592000s). Defaults to 30 days.",
-        value_parser = humantime::parse_duration
+        value_parser = human_duration::parse
     )]
     pub stats_ttl: Duration,
 
@@ -120,20 +197,55 @@ pub struct Args {
         short,
         long,
         value_name = "CORS_ALLOWED_ORIGINS",
+        value_delimiter = ',',
         env = "HAKANAI_CORS_ALLOWED_ORIGINS",
         help = "Allowed origins for CORS requests. If not set, CORS is disabled."
     )]
     pub cors_allowed_origins: Option<Vec<String>>,
 
+    #[arg(
+        long,
+        value_delimiter = ',',
+        env = "HAKANAI_CORS_ALLOWED_HEADERS",
+        help = "Additional request headers allowed in CORS requests. The headers used by the API are always allowed."
+    )]
+    pub cors_allowed_headers: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        default_value = "1h",
+        env = "HAKANAI_CORS_MAX_AGE",
+        help = "Time browsers may cache the result of CORS preflight requests.",
+        value_parser = humantime::parse_duration
+    )]
+    pub cors_max_age: Duration,
+
     #[arg(
         long,
         default_value = "7d",
         env = "HAKANAI_MAX_TTL",
         help = "Maximum allowed TTL for secrets.",
-        value_parser = humantime::parse_duration,
+        value_parser = human_duration::parse,
     )]
     pub max_ttl: Duration,
 
+    #[arg(
+        long,
+        env = "HAKANAI_MIN_TTL",
+        help = "Minimum allowed TTL for secrets.",
+        value_parser = human_duration::parse,
+    )]
+    pub min_ttl: Option<Duration>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        env = "HAKANAI_TTL_PRESETS",
+        help = "TTL presets offered by the clients (e.g. 1h,1d,7d). Defaults to presets from 5 minutes up to 7 days.",
+        value_parser = human_duration::parse,
+    )]
+    pub ttl_presets: Option<Vec<Duration>>,
+
     #[arg(
         long,
         default_value = "false",
@@ -151,6 +263,22 @@ pub struct Args {
     )]
     pub anonymous_upload_size_limit: usize,
 
+    #[arg(
+        long,
+        env = "HAKANAI_TOKEN_UPLOAD_SIZE_LIMIT",
+        help = "Upload size limit for users authenticated by a token without an own limit or by the identity provider (e.g., 1m, 512k). Not limited if not set.",
+        value_parser = parse_size_limit_bytes
+    )]
+    pub token_upload_size_limit: Option<usize>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_WHITELISTED_UPLOAD_SIZE_LIMIT",
+        help = "Upload size limit for requests from trusted IP ranges (e.g., 100m, 10m). Not limited if not set.",
+        value_parser = parse_size_limit_bytes
+    )]
+    pub whitelisted_upload_size_limit: Option<usize>,
+
     #[arg(
         long,
         default_value = "false",
@@ -173,6 +301,36 @@ pub struct Args {
     )]
     pub reset_user_tokens: bool,
 
+    #[arg(
+        long,
+        default_value = "false",
+        env = "HAKANAI_MAINTENANCE_MODE",
+        help = "Start in maintenance mode: creating secrets is rejected while retrieving them still works. Can be toggled at runtime via the admin API."
+    )]
+    pub maintenance_mode: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        env = "HAKANAI_ENABLE_SHORT_ALIASES",
+        help = "Allow clients to request short human-friendly aliases for secret links (e.g. /s/blue-otter-42). Aliases are easier to guess than IDs, consider rate limiting requests to the secret routes."
+    )]
+    pub enable_short_aliases: bool,
+
+    #[arg(
+        long,
+        env = "HAKANAI_CONFIG",
+        help = "Path to a YAML or TOML (.toml) file with server options keyed by their long name. Precedence is file < environment < flags. Size limits, trusted IP ranges and webhook settings are reloaded on SIGHUP or via admin API."
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_TENANTS_FILE",
+        help = "Path to a YAML file defining tenants identified by hostname or path prefix, each with its own token namespace, limits and branding."
+    )]
+    pub tenants_file: Option<PathBuf>,
+
     /// Path to impressum text file for legal compliance
     #[arg(
         long,
@@ -199,6 +357,14 @@ pub struct Args {
     )]
     pub webhook_token: Option<String>,
 
+    #[arg(
+        long,
+        env = "HAKANAI_WEBHOOK_SECRET",
+        hide_env_values = true,
+        help = "Secret to sign webhook requests with (HMAC-SHA256 in the X-Hakanai-Signature header), so receivers can authenticate events and reject replays"
+    )]
+    pub webhook_secret: Option<String>,
+
     #[arg(
         env = "HAKANAI_WEBHOOK_HEADERS",
         value_delimiter = ',',
@@ -207,6 +373,62 @@ pub struct Args {
     )]
     pub webhook_headers: Vec<String>,
 
+    #[arg(
+        long,
+        env = "HAKANAI_SLACK_WEBHOOK_URL",
+        hide_env_values = true,
+        help = "Incoming webhook URL of a Slack channel to post secret events to"
+    )]
+    pub slack_webhook_url: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_MATRIX_HOMESERVER_URL",
+        help = "Matrix homeserver URL to post secret events to"
+    )]
+    pub matrix_homeserver_url: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_MATRIX_ROOM_ID",
+        help = "ID of the Matrix room to post secret events to (e.g. !abc:example.org)"
+    )]
+    pub matrix_room_id: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_MATRIX_ACCESS_TOKEN",
+        hide_env_values = true,
+        help = "Access token of the Matrix user posting secret events"
+    )]
+    pub matrix_access_token: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_NOTIFY_EVENTS",
+        value_delimiter = ',',
+        default_value = "created,retrieved",
+        help = "Comma-separated list of secret events posted to Slack or Matrix"
+    )]
+    pub notify_events: Vec<NotifyEvent>,
+
+    #[arg(
+        long,
+        default_value = "10s",
+        env = "HAKANAI_OBSERVER_TIMEOUT",
+        help = "Maximum time an observer (webhook, metrics, statistics) may take to handle a secret event. Observers run in the background, events exceeding the timeout are dropped.",
+        value_parser = humantime::parse_duration
+    )]
+    pub observer_timeout: Duration,
+
+    #[arg(
+        long,
+        default_value = "30",
+        env = "HAKANAI_QR_CODE_RATE_LIMIT",
+        help = "Maximum number of QR codes a client IP may generate per minute via /api/v1/qr, 0 disables the limit."
+    )]
+    pub qr_code_rate_limit: u32,
+
     #[arg(
         long,
         default_value = "false",
@@ -215,6 +437,29 @@ pub struct Args {
     )]
     pub show_token_input: bool,
 
+    #[arg(
+        long,
+        env = "HAKANAI_DEFAULT_THEME",
+        help = "Theme of the web interface for users without a saved preference. Follows the system preference if not set."
+    )]
+    pub default_theme: Option<UiTheme>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        env = "HAKANAI_FORCE_HIGH_CONTRAST",
+        help = "Always use the high contrast colors in the web interface, regardless of the system preference."
+    )]
+    pub force_high_contrast: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        env = "HAKANAI_HIDE_BRANDING",
+        help = "Hide the Hakanai branding (project name, version and GitHub link) in the footer of the web interface."
+    )]
+    pub hide_branding: bool,
+
     #[arg(
         long,
         value_delimiter = ',',
@@ -253,17 +498,219 @@ pub struct Args {
     )]
     pub custom_assets_dir: Option<PathBuf>,
 
+    #[arg(
+        long,
+        default_value = "10s",
+        env = "HAKANAI_CUSTOM_ASSETS_RELOAD_INTERVAL",
+        help = "Interval after which custom assets are checked for changes and reloaded. Set to 0s to load custom assets only once.",
+        value_parser = humantime::parse_duration
+    )]
+    pub custom_assets_reload_interval: Duration,
+
     #[arg(
         long,
         default_value = "7d",
         env = "HAKANAI_ONE_TIME_TOKEN_TTL",
         help = "Time to live for one time tokens. Defaults to 1 week.",
-        value_parser = humantime::parse_duration
+        value_parser = human_duration::parse
     )]
     pub one_time_token_ttl: Duration,
+
+    #[arg(
+        long,
+        default_value = "1h",
+        env = "HAKANAI_ADMIN_SESSION_TTL",
+        help = "Time to live for sessions of the admin dashboard. Defaults to 1 hour.",
+        value_parser = human_duration::parse
+    )]
+    pub admin_session_ttl: Duration,
+
+    #[arg(
+        long,
+        env = "HAKANAI_STORAGE_ENCRYPTION_KEY",
+        hide_env_values = true,
+        help = "Base64 encoded 256 bit key to encrypt secrets at rest in Redis (e.g. injected from a KMS). Generate one with: openssl rand -base64 32"
+    )]
+    pub storage_encryption_key: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_STORAGE_ENCRYPTION_KEY_FILE",
+        help = "Path to a file containing the base64 encoded 256 bit key to encrypt secrets at rest in Redis."
+    )]
+    pub storage_encryption_key_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_OIDC_ISSUER",
+        help = "Issuer URL of an OpenID Connect provider. When provided, users can authenticate with bearer tokens (JWT) issued by the provider instead of Hakanai tokens."
+    )]
+    pub oidc_issuer: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_OIDC_AUDIENCE",
+        help = "Audience (client ID) tokens of the OpenID Connect provider must be issued for."
+    )]
+    pub oidc_audience: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_CONTENT_POLICY_COMMAND",
+        help = "Command deciding whether an upload is stored. It receives metadata of the upload as JSON on stdin (never the secret, which is encrypted by the client) and rejects it with a non-zero exit code."
+    )]
+    pub content_policy_command: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "5s",
+        env = "HAKANAI_CONTENT_POLICY_TIMEOUT",
+        help = "Maximum time the content policy command may take, uploads are rejected if exceeded.",
+        value_parser = humantime::parse_duration
+    )]
+    pub content_policy_timeout: Duration,
+
+    #[arg(
+        long,
+        env = "HAKANAI_PIN_WEBHOOK_URL",
+        help = "URL retrieval PINs are posted to (e.g. an SMS gateway), enables PIN protected secrets. The request contains the recipient chosen by the sender and the PIN as JSON."
+    )]
+    pub pin_webhook_url: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_PIN_WEBHOOK_TOKEN",
+        hide_env_values = true,
+        help = "Bearer token for authentication at the PIN webhook"
+    )]
+    pub pin_webhook_token: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_PIN_WEBHOOK_SECRET",
+        hide_env_values = true,
+        help = "Secret to sign PIN webhook requests with (HMAC-SHA256 in the X-Hakanai-Signature header)"
+    )]
+    pub pin_webhook_secret: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_PIN_COMMAND",
+        help = "Command delivering retrieval PINs, enables PIN protected secrets. It receives the recipient chosen by the sender and the PIN as JSON on stdin and confirms the delivery with exit code 0."
+    )]
+    pub pin_command: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "10s",
+        env = "HAKANAI_PIN_DELIVERY_TIMEOUT",
+        help = "Maximum time the delivery of a retrieval PIN may take, the secret is rejected if exceeded.",
+        value_parser = humantime::parse_duration
+    )]
+    pub pin_delivery_timeout: Duration,
+
+    #[arg(
+        long,
+        env = "HAKANAI_CONTENT_SECURITY_POLICY",
+        help = "Content-Security-Policy sent with all responses. Replaces the default policy, e.g. to allow fonts or images of custom assets loaded from other origins."
+    )]
+    pub content_security_policy: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "365d",
+        env = "HAKANAI_HSTS_MAX_AGE",
+        help = "Max age of the Strict-Transport-Security header. Set to 0s to disable HSTS, e.g. when not served via HTTPS.",
+        value_parser = humantime::parse_duration
+    )]
+    pub hsts_max_age: Duration,
+
+    #[arg(
+        long,
+        default_value = "false",
+        env = "HAKANAI_DISABLE_CROSS_ORIGIN_ISOLATION",
+        help = "Do not send the Cross-Origin-Opener-Policy and Cross-Origin-Embedder-Policy headers. Required if custom assets embed resources of other origins without CORP headers."
+    )]
+    pub disable_cross_origin_isolation: bool,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        env = "HAKANAI_LOG_FORMAT",
+        default_value = "text",
+        help = "Format of the log output. Tokens, passphrase hashes and secret keys are redacted in both formats."
+    )]
+    pub log_format: LogFormat,
+
+    /// Command line the arguments were parsed from, used to read the config file again on reload
+    #[arg(skip)]
+    pub command_line: Vec<OsString>,
 }
 
 impl Args {
+    /// Parses the arguments of the process, layered on top of the config file if set.
+    ///
+    /// Exits with an error message on invalid arguments, like `Args::parse`.
+    pub fn parse_layered() -> Self {
+        Self::try_parse_layered_from(std::env::args_os()).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parses the arguments, layered on top of the config file (`--config`) if set.
+    ///
+    /// Settings of the config file are only used for options neither set by environment
+    /// variables nor by flags.
+    pub fn try_parse_layered_from<I, T>(itr: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let command_line: Vec<OsString> = itr.into_iter().map(Into::into).collect();
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(command_line.clone())?;
+        let args = Self::from_arg_matches(&matches)?;
+
+        let Some(path) = &args.config else {
+            return Ok(Self {
+                command_line,
+                ..args
+            });
+        };
+
+        let file_args = ConfigFile::load(path)
+            .and_then(|config| {
+                config
+                    .to_args(&command, |id| {
+                        matches!(
+                            matches.value_source(id),
+                            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+                        )
+                    })
+                    .map_err(|e| format!("invalid config file {}: {e}", path.display()))
+            })
+            .map_err(|e| command.error(ErrorKind::InvalidValue, e))?;
+
+        let mut layered = command_line.clone();
+        layered.extend(file_args);
+        let args = Self::try_parse_from(layered)?;
+
+        Ok(Self {
+            command_line,
+            ..args
+        })
+    }
+
+    /// Returns a validated copy of the arguments with the config file read again, if configured.
+    pub fn reload(&self) -> Result<Args, String> {
+        if self.config.is_none() {
+            self.validate()?;
+            return Ok(self.clone());
+        }
+
+        let args = Self::try_parse_layered_from(&self.command_line).map_err(|e| e.to_string())?;
+        args.validate()?;
+        Ok(args)
+    }
+
     /// Validates configuration parameters for compatibility and logical consistency.
     pub fn validate(&self) -> Result<(), String> {
         if self.anonymous_upload_size_limit > self.upload_size_limit {
@@ -272,13 +719,111 @@ impl Args {
             );
         }
 
+        let min_ttl = self.min_ttl.unwrap_or_default();
+        if min_ttl > self.max_ttl {
+            return Err("--min-ttl cannot be larger than --max-ttl".to_string());
+        }
+
+        if let Some(presets) = &self.ttl_presets {
+            if presets.is_empty() {
+                return Err("--ttl-presets must contain at least one TTL".to_string());
+            }
+
+            if presets
+                .iter()
+                .any(|ttl| *ttl < min_ttl || *ttl > self.max_ttl)
+            {
+                return Err("--ttl-presets must be between --min-ttl and --max-ttl".to_string());
+            }
+        }
+
         if self.enable_admin_token && self.trusted_ip_ranges.is_none() {
             return Err("--enable-admin-token requires --trusted-ip-ranges to be set".to_string());
         }
 
+        if self
+            .redis_key_prefix
+            .contains(['*', '?', '[', ']', '\\', ' '])
+        {
+            return Err(
+                "--redis-key-prefix must not contain glob characters or spaces".to_string(),
+            );
+        }
+
+        if self.migrate_redis_keys && self.redis_key_prefix.is_empty() {
+            return Err("--migrate-redis-keys requires --redis-key-prefix to be set".to_string());
+        }
+
+        if self.redis_pool_size == 0 {
+            return Err("--redis-pool-size must be greater than 0".to_string());
+        }
+
+        if self.redis_circuit_breaker_threshold == 0 {
+            return Err("--redis-circuit-breaker-threshold must be greater than 0".to_string());
+        }
+
+        if self.storage_encryption_key.is_some() && self.storage_encryption_key_file.is_some() {
+            return Err(
+                "--storage-encryption-key cannot be used with --storage-encryption-key-file"
+                    .to_string(),
+            );
+        }
+
+        if let Some(header) = self
+            .cors_allowed_headers
+            .iter()
+            .flatten()
+            .find(|h| actix_web::http::header::HeaderName::from_bytes(h.as_bytes()).is_err())
+        {
+            return Err(format!(
+                "--cors-allowed-headers contains invalid header name: {header}"
+            ));
+        }
+
+        let matrix_args = [
+            self.matrix_homeserver_url.is_some(),
+            self.matrix_room_id.is_some(),
+            self.matrix_access_token.is_some(),
+        ];
+        if matrix_args.contains(&true) && matrix_args.contains(&false) {
+            return Err(
+                "--matrix-homeserver-url, --matrix-room-id and --matrix-access-token must be set together"
+                    .to_string(),
+            );
+        }
+
+        if self.oidc_issuer.is_some() != self.oidc_audience.is_some() {
+            return Err("--oidc-issuer and --oidc-audience must be set together".to_string());
+        }
+
+        if self.pin_webhook_url.is_some() && self.pin_command.is_some() {
+            return Err("--pin-webhook-url cannot be used with --pin-command".to_string());
+        }
+
         Ok(())
     }
 
+    /// Returns the normalized redis key prefix, ending with `:` if not empty
+    pub fn redis_key_prefix(&self) -> String {
+        match self.redis_key_prefix.as_str() {
+            "" => String::new(),
+            prefix if prefix.ends_with(':') => prefix.to_string(),
+            prefix => format!("{prefix}:"),
+        }
+    }
+
+    /// Loads the key for encryption at rest from the argument or file if configured
+    pub fn load_storage_encryption_key(&self) -> std::io::Result<Option<String>> {
+        if let Some(key) = &self.storage_encryption_key {
+            return Ok(Some(key.clone()));
+        }
+
+        match &self.storage_encryption_key_file {
+            Some(path) => std::fs::read_to_string(path).map(|key| Some(key.trim().to_string())),
+            None => Ok(None),
+        }
+    }
+
     /// Loads impressum content from file if configured
     pub fn load_impressum_content(&self) -> std::io::Result<Option<String>> {
         match &self.impressum_file {
@@ -300,8 +845,50 @@ impl Args {
             url: url.clone(),
             token: self.webhook_token.clone(),
             headers: self.webhook_headers.clone(),
+            secret: self.webhook_secret.clone(),
         })
     }
+
+    /// Chat services to post secret events to.
+    pub fn chat_targets(&self) -> Vec<ChatTarget> {
+        let mut targets = Vec::new();
+        if let Some(webhook_url) = &self.slack_webhook_url {
+            targets.push(ChatTarget::Slack {
+                webhook_url: webhook_url.clone(),
+            });
+        }
+
+        if let (Some(homeserver_url), Some(room_id), Some(access_token)) = (
+            &self.matrix_homeserver_url,
+            &self.matrix_room_id,
+            &self.matrix_access_token,
+        ) {
+            targets.push(ChatTarget::Matrix {
+                homeserver_url: homeserver_url.clone(),
+                room_id: room_id.clone(),
+                access_token: access_token.clone(),
+            });
+        }
+
+        targets
+    }
+
+    /// Service delivering retrieval PINs, `None` if PINs are not supported.
+    pub fn pin_delivery_target(&self) -> Option<PinDeliveryTarget> {
+        if let Some(url) = &self.pin_webhook_url {
+            return Some(PinDeliveryTarget::Webhook {
+                url: url.clone(),
+                token: self.pin_webhook_token.clone(),
+                secret: self.pin_webhook_secret.clone(),
+            });
+        }
+
+        self.pin_command
+            .as_ref()
+            .map(|program| PinDeliveryTarget::Command {
+                program: program.clone(),
+            })
+    }
 }
 
 #[cfg(test)]
@@ -312,32 +899,76 @@ mod tests {
     fn create_test_args() -> Args {
         Args {
             port: 8080,
-            listen_address: "127.0.0.1".to_string(),
+            listen_address: vec!["127.0.0.1".must_parse()],
             redis_dsn: "redis://127.0.0.1:6379/".to_string(),
             upload_size_limit: 10 * 1024 * 1024, // 10MB in bytes
             cors_allowed_origins: None,
+            cors_allowed_headers: None,
+            cors_max_age: Duration::from_secs(3600),
             max_ttl: Duration::from_secs(604800),
+            min_ttl: None,
+            ttl_presets: None,
             allow_anonymous: false,
             anonymous_upload_size_limit: 32 * 1024, // 32KB in bytes
+            token_upload_size_limit: None,
+            whitelisted_upload_size_limit: None,
             enable_admin_token: false,
             reset_admin_token: false,
             reset_user_tokens: false,
+            maintenance_mode: false,
+            enable_short_aliases: false,
+            config: None,
+            tenants_file: None,
             impressum_file: None,
             privacy_file: None,
             webhook_url: None,
             webhook_token: None,
+            webhook_secret: None,
             webhook_headers: vec![],
+            slack_webhook_url: None,
+            matrix_homeserver_url: None,
+            matrix_room_id: None,
+            matrix_access_token: None,
+            notify_events: vec![NotifyEvent::Created, NotifyEvent::Retrieved],
+            observer_timeout: Duration::from_secs(10),
+            qr_code_rate_limit: 30,
             show_token_input: false,
+            default_theme: None,
+            force_high_contrast: false,
+            hide_branding: false,
             trusted_ip_ranges: None,
             trusted_ip_header: "x-forwarded-for".to_string(),
             country_header: None,
             asn_header: None,
             custom_assets_dir: None,
+            custom_assets_reload_interval: Duration::from_secs(10),
             stats_ttl: Duration::from_secs(3600),
             one_time_token_ttl: Duration::from_secs(604800),
+            admin_session_ttl: Duration::from_secs(3600),
             redis_connection_timeout: Duration::from_secs(10),
             redis_reconnection_max_delay: Duration::from_millis(10),
             redis_response_timeout: None,
+            redis_key_prefix: String::new(),
+            migrate_redis_keys: false,
+            redis_pool_size: 4,
+            redis_circuit_breaker_threshold: 5,
+            redis_circuit_breaker_timeout: Duration::from_secs(10),
+            storage_encryption_key: None,
+            storage_encryption_key_file: None,
+            oidc_issuer: None,
+            oidc_audience: None,
+            content_policy_command: None,
+            content_policy_timeout: Duration::from_secs(5),
+            pin_webhook_url: None,
+            pin_webhook_token: None,
+            pin_webhook_secret: None,
+            pin_command: None,
+            pin_delivery_timeout: Duration::from_secs(10),
+            content_security_policy: None,
+            hsts_max_age: Duration::from_secs(31536000),
+            disable_cross_origin_isolation: false,
+            log_format: LogFormat::Text,
+            command_line: vec![],
         }
     }
 
@@ -415,6 +1046,268 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_redis_key_prefix_normalized() {
+        let args = |prefix: &str| Args {
+            redis_key_prefix: prefix.to_string(),
+            ..create_test_args()
+        };
+
+        assert_eq!(args("").redis_key_prefix(), "");
+        assert_eq!(args("staging").redis_key_prefix(), "staging:");
+        assert_eq!(args("prod:").redis_key_prefix(), "prod:");
+    }
+
+    #[test]
+    fn test_validate_redis_key_prefix_with_glob() {
+        let args = Args {
+            redis_key_prefix: "staging*".to_string(),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_migrate_redis_keys_requires_prefix() {
+        let args = Args {
+            migrate_redis_keys: true,
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_redis_pool_size_zero() {
+        let args = Args {
+            redis_pool_size: 0,
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_storage_encryption_key_conflict() {
+        let args = Args {
+            storage_encryption_key: Some("key".to_string()),
+            storage_encryption_key_file: Some(PathBuf::from("/path/to/key")),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_min_ttl_larger_than_max_ttl() {
+        let args = Args {
+            min_ttl: Some(Duration::from_secs(7200)),
+            max_ttl: Duration::from_secs(3600),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.contains("--min-ttl cannot be larger than --max-ttl")),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_ttl_presets_out_of_range() {
+        for presets in [
+            vec![Duration::from_secs(60), Duration::from_secs(3600)],
+            vec![Duration::from_secs(3600), Duration::from_secs(30 * 86400)],
+            vec![],
+        ] {
+            let args = Args {
+                min_ttl: Some(Duration::from_secs(300)),
+                ttl_presets: Some(presets.clone()),
+                ..create_test_args()
+            };
+
+            let result = args.validate();
+            assert!(
+                result.is_err(),
+                "Expected validation error for presets {presets:?}, got: {:?}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_ttl_presets_in_range() -> Result<(), String> {
+        let args = Args {
+            min_ttl: Some(Duration::from_secs(3600)),
+            ttl_presets: Some(vec![Duration::from_secs(3600), Duration::from_secs(86400)]),
+            ..create_test_args()
+        };
+
+        args.validate()
+    }
+
+    #[test]
+    fn test_validate_cors_allowed_headers_invalid() {
+        let mut args = create_test_args();
+        args.cors_allowed_headers = Some(vec!["x-custom".to_string(), "x invalid".to_string()]);
+
+        let result = args.validate();
+        assert!(result.is_err(), "Invalid header name should be rejected");
+        assert_eq!(
+            result.unwrap_err(),
+            "--cors-allowed-headers contains invalid header name: x invalid"
+        );
+    }
+
+    #[test]
+    fn test_validate_oidc_issuer_without_audience() {
+        let args = Args {
+            oidc_issuer: Some("https://sso.example.com".to_string()),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_validate_oidc() -> Result<(), String> {
+        let args = Args {
+            oidc_issuer: Some("https://sso.example.com".to_string()),
+            oidc_audience: Some("hakanai".to_string()),
+            ..create_test_args()
+        };
+
+        args.validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_matrix_without_access_token() {
+        let args = Args {
+            matrix_homeserver_url: Some("https://matrix.example.org".to_string()),
+            matrix_room_id: Some("!room:example.org".to_string()),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_chat_targets() -> Result<(), String> {
+        let args = Args {
+            slack_webhook_url: Some("https://hooks.slack.com/services/T/B/X".to_string()),
+            matrix_homeserver_url: Some("https://matrix.example.org".to_string()),
+            matrix_room_id: Some("!room:example.org".to_string()),
+            matrix_access_token: Some("token".to_string()),
+            ..create_test_args()
+        };
+
+        args.validate()?;
+        assert_eq!(
+            args.chat_targets().len(),
+            2,
+            "Slack and Matrix should both be notified"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_pin_webhook_with_command() {
+        let args = Args {
+            pin_webhook_url: Some("https://sms.example.com/pin".to_string()),
+            pin_command: Some(PathBuf::from("/usr/local/bin/send-sms")),
+            ..create_test_args()
+        };
+
+        let result = args.validate();
+        assert!(
+            result.is_err(),
+            "Expected validation error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_pin_delivery_target() -> Result<(), String> {
+        assert!(create_test_args().pin_delivery_target().is_none());
+
+        let args = Args {
+            pin_command: Some(PathBuf::from("/usr/local/bin/send-sms")),
+            ..create_test_args()
+        };
+
+        args.validate()?;
+        assert!(
+            matches!(
+                args.pin_delivery_target(),
+                Some(PinDeliveryTarget::Command { ref program }) if program.ends_with("send-sms")
+            ),
+            "PINs should be delivered by the command"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_storage_encryption_key_from_file() {
+        use std::io::Write;
+
+        let mut temp_file = tempfile::NamedTempFile::new().expect("Failed to create temp file");
+        writeln!(temp_file, "c2VjcmV0").expect("Failed to write to temp file");
+        temp_file.flush().expect("Failed to flush temp file");
+
+        let args = Args {
+            storage_encryption_key_file: Some(temp_file.path().to_path_buf()),
+            ..create_test_args()
+        };
+
+        let key = args
+            .load_storage_encryption_key()
+            .expect("Failed to load storage encryption key");
+        assert_eq!(key, Some("c2VjcmV0".to_string()), "Key should be trimmed");
+    }
+
+    #[test]
+    fn test_load_storage_encryption_key_none() {
+        let key = create_test_args()
+            .load_storage_encryption_key()
+            .expect("Failed to load storage encryption key");
+        assert!(key.is_none());
+    }
+
     #[test]
     fn test_load_impressum_content_none() {
         let args = Args {
@@ -526,4 +1419,76 @@ mod tests {
         let content = result.expect("Result should not be none");
         assert_eq!(content, test_content);
     }
+
+    #[test]
+    fn test_try_parse_layered_from_config_file() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let mut config = tempfile::NamedTempFile::new()?;
+        write!(
+            config,
+            "port: 9090\nlisten-address: 0.0.0.0\nallow_anonymous: true"
+        )?;
+        config.flush()?;
+        let path = config.path().to_string_lossy().to_string();
+
+        let args =
+            Args::try_parse_layered_from(["hakanai-server", "--config", &path, "--port", "8081"])?;
+
+        assert_eq!(
+            args.port, 8081,
+            "Flags should take precedence over the file"
+        );
+        assert_eq!(args.listen_address, vec!["0.0.0.0".must_parse()]);
+        assert!(args.allow_anonymous);
+        assert_eq!(args.command_line.len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_parse_layered_from_invalid_config_file() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let mut config = tempfile::NamedTempFile::new()?;
+        write!(config, "port: 9090\nupload_size_limit: lots")?;
+        config.flush()?;
+
+        let result = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--config",
+            &config.path().to_string_lossy(),
+        ]);
+
+        let error = result.err().map(|e| e.to_string()).unwrap_or_default();
+        assert!(
+            error.contains("'upload_size_limit'"),
+            "Error should name the offending key, got: {error}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_reads_config_file_again() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        let mut config = tempfile::NamedTempFile::new()?;
+        write!(config, "upload_size_limit: 1m")?;
+        config.flush()?;
+        let args = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--config",
+            &config.path().to_string_lossy(),
+        ])?;
+        assert_eq!(args.upload_size_limit, 1024 * 1024);
+
+        write!(config, "\nanonymous_upload_size_limit: 2m")?;
+        config.flush()?;
+
+        assert!(
+            args.reload().is_err(),
+            "Reloaded arguments should be validated"
+        );
+        assert!(create_test_args().reload().is_ok());
+        Ok(())
+    }
 }
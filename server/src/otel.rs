@@ -14,8 +14,12 @@ use opentelemetry_sdk::{
 
 use tracing::warn;
 use tracing_opentelemetry::{MetricsLayer, OpenTelemetryLayer};
+use tracing_subscriber::filter::FilterExt;
 use tracing_subscriber::{EnvFilter, prelude::*};
 
+use crate::logging;
+use crate::options::LogFormat;
+
 /// A handler for OpenTelemetry providers.
 ///
 /// This struct holds the tracer and meter providers. When `shutdown` is called,
@@ -42,7 +46,8 @@ impl Guard {
 
 /// Initializes OpenTelemetry tracing, metrics, and logging.
 ///
-/// This function sets up the global tracer, meter, and logger providers.
+/// Logs are written to stdout in the given format with sensitive values redacted. This function
+/// also sets up the global tracer, meter, and logger providers.
 /// It configures the OTLP exporter to send data to the endpoint specified
 /// by the `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable.
 ///
@@ -54,10 +59,10 @@ impl Guard {
 /// * `Ok(Some(OtelHandler))` - If OpenTelemetry was initialized successfully. The handler can be used to gracefully shut down the providers.
 /// * `Ok(None)` - If the OTLP endpoint is not configured.
 /// * `Err(anyhow::Error)` - If there was an error during initialization.
-pub fn init() -> Result<Option<Guard>> {
+pub fn init(log_format: LogFormat) -> Result<Option<Guard>> {
     let builder = tracing_subscriber::registry()
         .with(EnvFilter::new("info"))
-        .with(tracing_subscriber::fmt::layer());
+        .with(logging::fmt_layer(log_format));
 
     if !is_otel_endpoint_set() {
         tracing::warn!(
@@ -76,10 +81,10 @@ pub fn init() -> Result<Option<Guard>> {
     builder
         .with(
             layer::OpenTelemetryTracingBridge::new(&logger_provider)
-                .with_filter(EnvFilter::new("info")),
+                .with_filter(EnvFilter::new("info").and(logging::export_filter())),
         )
         .with(MetricsLayer::new(meter_provider.clone()))
-        .with(OpenTelemetryLayer::new(tracer))
+        .with(OpenTelemetryLayer::new(tracer).with_filter(logging::export_filter()))
         .init();
 
     Ok(Some(Guard {
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::{ContentPolicy, PolicyError, UploadInfo};
+
+/// Reason returned if the command rejects an upload without printing one.
+const DEFAULT_REJECTION_REASON: &str = "upload not allowed";
+
+/// Content policy delegating the decision to an external command, e.g. a script applying site
+/// specific rules or querying an ICAP server.
+///
+/// The `UploadInfo` is written as JSON to stdin of the command. Exit code 0 accepts the upload,
+/// any other exit code rejects it with the first line written to stdout as reason. Uploads are
+/// rejected as well if the command cannot be run or exceeds the timeout.
+#[derive(Clone, Debug)]
+pub struct CommandPolicy {
+    program: PathBuf,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl CommandPolicy {
+    pub fn new(program: PathBuf, timeout: Duration) -> Self {
+        Self {
+            program,
+            args: Vec::new(),
+            timeout,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_args(mut self, args: &[&str]) -> Self {
+        self.args = args.iter().map(|arg| arg.to_string()).collect();
+        self
+    }
+
+    async fn run(&self, input: &[u8]) -> Result<(), PolicyError> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| self.unavailable(e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // commands deciding without reading the metadata close stdin early
+            match stdin.write_all(input).await {
+                Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(self.unavailable(e)),
+                _ => {}
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| self.unavailable(e))?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        if output.status.code().is_none() {
+            return Err(self.unavailable("terminated by signal"));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let reason = stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or(DEFAULT_REJECTION_REASON);
+        Err(PolicyError::Rejected(reason.to_string()))
+    }
+
+    fn unavailable(&self, err: impl std::fmt::Display) -> PolicyError {
+        PolicyError::Unavailable(format!("{}: {err}", self.program.display()))
+    }
+}
+
+#[async_trait]
+impl ContentPolicy for CommandPolicy {
+    async fn check(&self, upload: &UploadInfo) -> Result<(), PolicyError> {
+        let input = serde_json::to_vec(upload).map_err(|e| self.unavailable(e))?;
+
+        tokio::time::timeout(self.timeout, self.run(&input))
+            .await
+            .map_err(|_| self.unavailable(format!("timed out after {:?}", self.timeout)))?
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use crate::user_type::UserType;
+
+    fn upload() -> UploadInfo {
+        UploadInfo {
+            ciphertext_size: 42,
+            expires_in: Duration::from_secs(3600),
+            user_type: UserType::Anonymous,
+            restricted: false,
+            tenant: None,
+        }
+    }
+
+    fn shell_policy(script: &str) -> CommandPolicy {
+        CommandPolicy::new(PathBuf::from("/bin/sh"), Duration::from_secs(5))
+            .with_args(&["-c", script])
+    }
+
+    #[tokio::test]
+    async fn test_check_accepted() -> Result<(), PolicyError> {
+        shell_policy("cat > /dev/null").check(&upload()).await
+    }
+
+    #[tokio::test]
+    async fn test_check_receives_metadata() -> Result<(), PolicyError> {
+        let script = r#"input=$(cat); echo "$input" | grep -q '"ciphertext_size":42' && echo "$input" | grep -q '"user_type":"anonymous"'"#;
+        shell_policy(script).check(&upload()).await
+    }
+
+    #[tokio::test]
+    async fn test_check_rejected_with_reason() {
+        let result = shell_policy("echo 'secret too large'; exit 1")
+            .check(&upload())
+            .await;
+        assert!(
+            matches!(result, Err(PolicyError::Rejected(ref reason)) if reason == "secret too large"),
+            "Expected rejection with reason, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_rejected_without_reason() {
+        let result = shell_policy("exit 3").check(&upload()).await;
+        assert!(
+            matches!(result, Err(PolicyError::Rejected(ref reason)) if reason == DEFAULT_REJECTION_REASON),
+            "Expected rejection with default reason, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_timeout() {
+        let policy = CommandPolicy::new(PathBuf::from("/bin/sh"), Duration::from_millis(100))
+            .with_args(&["-c", "sleep 5"]);
+
+        let result = policy.check(&upload()).await;
+        assert!(
+            matches!(result, Err(PolicyError::Unavailable(_))),
+            "Expected unavailable policy on timeout, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_missing_command() {
+        let policy = CommandPolicy::new(
+            PathBuf::from("/nonexistent/hakanai-policy"),
+            Duration::from_secs(5),
+        );
+
+        let result = policy.check(&upload()).await;
+        assert!(
+            matches!(result, Err(PolicyError::Unavailable(_))),
+            "Expected unavailable policy for missing command, got: {result:?}"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+
+use super::{PolicyError, UploadInfo};
+
+#[async_trait]
+pub trait ContentPolicy: Send + Sync {
+    /// Checks the upload before the secret is stored, the secret is rejected on error.
+    ///
+    /// Only metadata of the upload is available, the secret itself is encrypted by the client.
+    async fn check(&self, upload: &UploadInfo) -> Result<(), PolicyError>;
+}
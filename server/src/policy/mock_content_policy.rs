@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use super::{ContentPolicy, PolicyError, UploadInfo};
+
+/// Mock implementation of the `ContentPolicy` trait recording the checked uploads.
+#[derive(Clone, Default)]
+pub struct MockContentPolicy {
+    rejection: Option<String>,
+    unavailable: bool,
+    uploads: Arc<Mutex<Vec<UploadInfo>>>,
+}
+
+impl MockContentPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject all uploads with the given reason.
+    pub fn rejecting(mut self, reason: &str) -> Self {
+        self.rejection = Some(reason.to_string());
+        self
+    }
+
+    /// Fail all checks as if the policy was not available.
+    pub fn unavailable(mut self) -> Self {
+        self.unavailable = true;
+        self
+    }
+
+    /// Returns the uploads checked by the policy.
+    pub fn uploads(&self) -> Vec<UploadInfo> {
+        self.uploads.lock().expect("Failed to acquire lock").clone()
+    }
+}
+
+#[async_trait]
+impl ContentPolicy for MockContentPolicy {
+    async fn check(&self, upload: &UploadInfo) -> Result<(), PolicyError> {
+        self.uploads
+            .lock()
+            .expect("Failed to acquire lock")
+            .push(upload.clone());
+
+        if self.unavailable {
+            return Err(PolicyError::Unavailable("mock unavailable".to_string()));
+        }
+
+        match &self.rejection {
+            Some(reason) => Err(PolicyError::Rejected(reason.clone())),
+            None => Ok(()),
+        }
+    }
+}
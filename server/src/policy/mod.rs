@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Content policies deciding whether an uploaded secret is stored.
+//!
+//! Secrets are encrypted by the client, so a policy never sees the plaintext. It decides on the
+//! metadata of the upload (`UploadInfo`) only, e.g. the size of the encrypted data, the TTL or the
+//! type of the user. Scanning the content of a secret (e.g. with an anti-virus) is not possible
+//! on the server, operators have to accept policies based on size and metadata.
+
+mod command_policy;
+mod content_policy;
+mod policy_error;
+mod upload_info;
+
+#[cfg(test)]
+mod mock_content_policy;
+
+pub use command_policy::CommandPolicy;
+pub use content_policy::ContentPolicy;
+pub use policy_error::PolicyError;
+pub use upload_info::UploadInfo;
+
+#[cfg(test)]
+pub use mock_content_policy::MockContentPolicy;
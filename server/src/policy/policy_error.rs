@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+/// Errors of content policies.
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    /// The upload violates the policy and must not be stored.
+    #[error("rejected by content policy: {0}")]
+    Rejected(String),
+
+    /// The policy could not be checked, e.g. the external scanner is not available.
+    #[error("content policy unavailable: {0}")]
+    Unavailable(String),
+}
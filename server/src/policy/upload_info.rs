@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::user_type::UserType;
+
+/// Metadata of an uploaded secret visible to the server, checked by content policies.
+#[derive(Clone, Debug, Serialize)]
+pub struct UploadInfo {
+    /// Size of the encrypted (and base64 encoded) secret in bytes. The size of the plaintext is
+    /// not known to the server.
+    pub ciphertext_size: usize,
+
+    /// Time until the secret expires.
+    #[serde(rename = "expires_in_seconds", serialize_with = "serialize_seconds")]
+    pub expires_in: Duration,
+
+    /// Type of the user uploading the secret.
+    #[serde(serialize_with = "serialize_display")]
+    pub user_type: UserType,
+
+    /// Whether access to the secret is restricted (e.g. by IP or passphrase).
+    pub restricted: bool,
+
+    /// The tenant the secret is uploaded to, `None` for the default tenant.
+    pub tenant: Option<String>,
+}
+
+fn serialize_seconds<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+}
+
+fn serialize_display<S: serde::Serializer>(
+    value: &UserType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
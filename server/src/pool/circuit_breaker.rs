@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Requests are allowed, counting consecutive failures.
+    Closed { failures: u32 },
+
+    /// Requests are rejected until the open duration elapsed.
+    Open { since: Instant },
+
+    /// A single trial request is allowed to probe if the backend recovered.
+    HalfOpen { since: Instant },
+}
+
+/// Circuit breaker which rejects requests after consecutive failures, so callers fail fast
+/// instead of waiting for timeouts while the backend is down.
+///
+/// After `open_duration` a single trial request is let through. A success closes the circuit,
+/// a failure opens it again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Checks if a request is allowed to pass.
+    pub fn allow(&self) -> bool {
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { since } | State::HalfOpen { since }
+                if since.elapsed() >= self.open_duration =>
+            {
+                // a stale trial (e.g. cancelled request) must not block the circuit forever
+                *state = State::HalfOpen {
+                    since: Instant::now(),
+                };
+                true
+            }
+            State::Open { .. } | State::HalfOpen { .. } => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+        *state = State::Closed { failures: 0 };
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().expect("Failed to acquire lock");
+
+        *state = match *state {
+            State::Closed { failures } if failures + 1 < self.failure_threshold => State::Closed {
+                failures: failures + 1,
+            },
+            State::Open { since } => State::Open { since },
+            _ => State::Open {
+                since: Instant::now(),
+            },
+        };
+    }
+
+    /// Checks if requests are currently rejected.
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().expect("Failed to acquire lock");
+        !matches!(*state, State::Closed { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.allow(), "Circuit should be closed below threshold");
+
+        breaker.record_failure();
+        assert!(breaker.is_open(), "Circuit should be open at threshold");
+        assert!(!breaker.allow(), "Requests should be rejected while open");
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+
+        assert!(!breaker.is_open(), "Failures should not be cumulative");
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn test_half_open_allows_single_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(60));
+        *breaker.state.lock().expect("Failed to acquire lock") = State::Open {
+            since: Instant::now() - Duration::from_secs(61),
+        };
+
+        assert!(breaker.allow(), "Trial request should be allowed");
+        assert!(!breaker.allow(), "Only a single trial should be allowed");
+    }
+
+    #[test]
+    fn test_half_open_trial_result() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+
+        breaker.record_failure();
+        assert!(breaker.allow(), "Trial request should be allowed");
+        breaker.record_success();
+        assert!(!breaker.is_open(), "Successful trial should close circuit");
+
+        breaker.record_failure();
+        assert!(breaker.allow(), "Trial request should be allowed");
+        breaker.record_failure();
+        assert!(breaker.is_open(), "Failed trial should open circuit again");
+    }
+}
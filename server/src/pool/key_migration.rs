@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use redis::{AsyncCommands, RedisResult};
+use tracing::{info, warn};
+
+use crate::pool::RedisPool;
+
+/// Patterns of all keys written by the Redis stores (without key prefix).
+const KEY_PATTERNS: &[&str] = &[
+    "secret:*",
+    "accessed:*",
+    "restrictions:*",
+    "metadata:*",
+    "retrieving:*",
+    "reservation:*",
+    "failed_attempts:*",
+    "owner:*",
+    "alias:*",
+    "token:*",
+    "token_used:*",
+    "admin_token",
+    "admin_session:*",
+    "stats:*",
+];
+
+const KEYS_PER_SCAN: usize = 100;
+
+/// Renames all unprefixed keys to use the key prefix, keeping their TTL.
+///
+/// Keys already existing with the prefix are not overwritten. Returns the number of migrated keys.
+pub async fn migrate_keys(pool: &RedisPool, key_prefix: &str) -> RedisResult<usize> {
+    let mut con = pool.clone();
+    let mut migrated = 0;
+
+    for pattern in KEY_PATTERNS {
+        for key in scan_keys(&mut con, pattern).await? {
+            let target = format!("{key_prefix}{key}");
+            let renamed: bool = con.rename_nx(&key, &target).await?;
+
+            if renamed {
+                migrated += 1;
+            } else {
+                warn!("Skipping key {key}, {target} already exists");
+            }
+        }
+    }
+
+    info!("Migrated {migrated} keys to prefix {key_prefix}");
+    Ok(migrated)
+}
+
+pub(crate) async fn scan_keys(con: &mut RedisPool, pattern: &str) -> RedisResult<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut cursor = 0u64;
+
+    loop {
+        let (new_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .cursor_arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(KEYS_PER_SCAN)
+            .query_async(con)
+            .await?;
+
+        keys.extend(batch);
+
+        cursor = new_cursor;
+        if cursor == 0 {
+            break;
+        }
+    }
+
+    // SCAN may return the same key multiple times
+    keys.sort();
+    keys.dedup();
+    Ok(keys)
+}
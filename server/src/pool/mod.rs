@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pooled Redis connections with a health based circuit breaker.
+
+mod circuit_breaker;
+mod key_migration;
+mod redis_pool;
+
+pub use circuit_breaker::CircuitBreaker;
+pub use key_migration::migrate_keys;
+pub(crate) use key_migration::scan_keys;
+pub use redis_pool::{RedisPool, is_connection_error};
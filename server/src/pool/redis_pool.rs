@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use redis::aio::{ConnectionLike, ConnectionManager, ConnectionManagerConfig};
+use redis::{Cmd, Pipeline, RedisError, RedisFuture, RedisResult, Value};
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+use crate::metrics::RedisPoolMetrics;
+use crate::pool::CircuitBreaker;
+
+/// A pool of Redis connections guarded by a circuit breaker.
+///
+/// Requests are distributed round-robin over the connections. Connections failing with
+/// connection level errors are retired and reopened on next use. After consecutive failures
+/// the circuit breaker opens and requests fail immediately with a connection refusal, which
+/// is reported as service unavailable instead of waiting for timeouts.
+///
+/// The pool implements `ConnectionLike`, so it can be used with `AsyncCommands` like a single
+/// connection.
+#[derive(Clone)]
+pub struct RedisPool {
+    client: redis::Client,
+    config: ConnectionManagerConfig,
+    connection_timeout: Duration,
+    slots: Arc<Vec<Mutex<Option<ConnectionManager>>>>,
+    next: Arc<AtomicUsize>,
+    open_connections: Arc<AtomicUsize>,
+    breaker: Arc<CircuitBreaker>,
+    metrics: Option<RedisPoolMetrics>,
+    db: i64,
+}
+
+impl RedisPool {
+    pub fn new(
+        client: redis::Client,
+        config: ConnectionManagerConfig,
+        size: usize,
+        connection_timeout: Duration,
+        breaker: CircuitBreaker,
+    ) -> Self {
+        let slots = (0..size.max(1)).map(|_| Mutex::new(None)).collect();
+
+        Self {
+            client,
+            config,
+            connection_timeout,
+            slots: Arc::new(slots),
+            next: Arc::new(AtomicUsize::new(0)),
+            open_connections: Arc::new(AtomicUsize::new(0)),
+            breaker: Arc::new(breaker),
+            metrics: None,
+            db: 0,
+        }
+    }
+
+    pub fn with_metrics(mut self, metrics: RedisPoolMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Opens all connections of the pool.
+    pub async fn connect(mut self) -> RedisResult<Self> {
+        for slot in self.slots.iter() {
+            let con = self.open().await?;
+            self.db = con.get_db();
+            *slot.lock().await = Some(con);
+        }
+
+        info!("Opened {} Redis connections", self.slots.len());
+        self.record_state();
+        Ok(self)
+    }
+
+    async fn open(&self) -> RedisResult<ConnectionManager> {
+        let con = timeout(
+            self.connection_timeout,
+            ConnectionManager::new_with_config(self.client.clone(), self.config.clone()),
+        )
+        .await
+        .map_err(|_| {
+            RedisError::from(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "timed out connecting to Redis",
+            ))
+        })??;
+
+        self.open_connections.fetch_add(1, Ordering::Relaxed);
+        Ok(con)
+    }
+
+    /// Returns the index of the slot and a connection, opening a new one if the slot was retired.
+    async fn acquire(&self) -> RedisResult<(usize, ConnectionManager)> {
+        if !self.breaker.allow() {
+            if let Some(metrics) = &self.metrics {
+                metrics.rejected_requests_counter.add(1, &[]);
+            }
+            return Err(circuit_open_error());
+        }
+
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        let mut slot = self.slots[idx].lock().await;
+        if let Some(con) = slot.as_ref() {
+            return Ok((idx, con.clone()));
+        }
+
+        match self.open().await {
+            Ok(con) => {
+                *slot = Some(con.clone());
+                self.record_state();
+                Ok((idx, con))
+            }
+            Err(err) => {
+                self.breaker.record_failure();
+                self.record_state();
+                Err(err)
+            }
+        }
+    }
+
+    /// Updates the circuit breaker with the result and retires the connection on connection errors.
+    async fn release<T>(&self, idx: usize, res: &RedisResult<T>) {
+        match res {
+            Err(err) if is_connection_error(err) => {
+                warn!("Retiring Redis connection after error: {err}");
+                self.breaker.record_failure();
+
+                if self.slots[idx].lock().await.take().is_some() {
+                    self.open_connections.fetch_sub(1, Ordering::Relaxed);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.retired_connections_counter.add(1, &[]);
+                    }
+                }
+            }
+            _ => self.breaker.record_success(),
+        }
+
+        self.record_state();
+    }
+
+    fn record_state(&self) {
+        if let Some(metrics) = &self.metrics {
+            let open = self.open_connections.load(Ordering::Relaxed) as u64;
+            metrics.open_connections_gauge.record(open, &[]);
+            metrics
+                .circuit_open_gauge
+                .record(u64::from(self.breaker.is_open()), &[]);
+        }
+    }
+}
+
+impl ConnectionLike for RedisPool {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let (idx, mut con) = self.acquire().await?;
+            let res = con.req_packed_command(cmd).await;
+            self.release(idx, &res).await;
+            res
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let (idx, mut con) = self.acquire().await?;
+            let res = con.req_packed_commands(cmd, offset, count).await;
+            self.release(idx, &res).await;
+            res
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.db
+    }
+}
+
+/// Checks if the error is caused by the connection rather than by the command.
+pub fn is_connection_error(err: &RedisError) -> bool {
+    err.is_io_error()
+        || err.is_connection_dropped()
+        || err.is_connection_refusal()
+        || err.is_timeout()
+}
+
+fn circuit_open_error() -> RedisError {
+    RedisError::from(std::io::Error::new(
+        std::io::ErrorKind::ConnectionRefused,
+        "circuit breaker is open, Redis is unavailable",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_open_error_is_connection_error() {
+        assert!(
+            is_connection_error(&circuit_open_error()),
+            "Open circuit should be reported as connection error"
+        );
+    }
+
+    #[test]
+    fn test_timeout_is_connection_error() {
+        let err = RedisError::from(std::io::Error::new(std::io::ErrorKind::TimedOut, "timeout"));
+        assert!(is_connection_error(&err));
+    }
+}
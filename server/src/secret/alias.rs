@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Short human-friendly aliases for secret links, e.g. `blue-otter-42`.
+//!
+//! Aliases are far easier to guess than ULIDs (about 22 bits), they only map to the ID of the
+//! secret and expire together with it. Secrets stay protected by their key in the link fragment.
+
+use rand::Rng;
+
+/// Number of attempts to mint an alias which is not in use yet.
+pub const MAX_ATTEMPTS: usize = 5;
+
+const MAX_NUMBER: u32 = 1000;
+
+const ADJECTIVES: &[&str] = &[
+    "amber", "autumn", "bold", "brave", "bright", "brisk", "calm", "clever", "cosmic", "crimson",
+    "crisp", "dancing", "daring", "dusty", "eager", "early", "fancy", "fluffy", "fresh", "gentle",
+    "giant", "golden", "happy", "hidden", "humble", "icy", "jolly", "kind", "lively", "lucky",
+    "mellow", "merry", "misty", "noble", "olive", "patient", "plain", "polite", "proud", "purple",
+    "quick", "quiet", "rapid", "rosy", "royal", "rusty", "shiny", "silent", "silver", "sleepy",
+    "snowy", "solid", "sunny", "swift", "tender", "tidy", "tiny", "velvet", "vivid", "warm",
+    "wild", "windy", "wise", "young",
+];
+
+const NOUNS: &[&str] = &[
+    "badger", "beacon", "bear", "bison", "brook", "canyon", "cedar", "comet", "coral", "crane",
+    "delta", "dolphin", "eagle", "ember", "falcon", "fern", "finch", "fjord", "fox", "glacier",
+    "harbor", "hawk", "heron", "island", "jaguar", "kestrel", "koala", "lagoon", "lantern", "lark",
+    "lynx", "maple", "meadow", "meteor", "moose", "nebula", "oasis", "orca", "otter", "owl",
+    "panda", "pebble", "pine", "planet", "puffin", "quartz", "raven", "reef", "river", "robin",
+    "salmon", "sparrow", "summit", "thistle", "tiger", "tulip", "valley", "walrus", "willow",
+    "wolf", "wren", "yak", "zebra", "zephyr",
+];
+
+/// Generates a random alias consisting of an adjective, a noun and a number.
+pub fn generate() -> String {
+    let mut rng = rand::rng();
+    let adjective = ADJECTIVES[rng.next_u32() as usize % ADJECTIVES.len()];
+    let noun = NOUNS[rng.next_u32() as usize % NOUNS.len()];
+    let number = rng.next_u32() % MAX_NUMBER;
+
+    format!("{adjective}-{noun}-{number}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hakanai_lib::models::secret::is_secret_alias;
+
+    #[test]
+    fn test_generate() {
+        for _ in 0..100 {
+            let alias = generate();
+            assert!(
+                is_secret_alias(&alias),
+                "'{alias}' should be accepted by clients"
+            );
+        }
+    }
+
+    #[test]
+    fn test_word_lists_are_valid() {
+        for word in ADJECTIVES.iter().chain(NOUNS) {
+            assert!(
+                !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase()),
+                "'{word}' should only contain lowercase letters"
+            );
+        }
+    }
+}
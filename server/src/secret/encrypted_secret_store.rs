@@ -0,0 +1,368 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use async_trait::async_trait;
+use base64::Engine;
+use rand::TryRng;
+use tracing::instrument;
+use ulid::Ulid;
+
+use hakanai_lib::models::{OwnedSecret, SecretMetadata, SecretRestrictions};
+
+use crate::secret::{SecretStore, SecretStoreError, SecretStorePopResult};
+
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12;
+
+/// A decorator for any `SecretStore` which encrypts the stored secret data with a server-side
+/// AES-256-GCM key (encryption at rest).
+///
+/// Secrets are already end-to-end encrypted by the clients, this layer adds defense in depth
+/// for leaked backend dumps. The secret ID is used as associated data, so stored values cannot
+/// be swapped between IDs.
+///
+/// Values stored before encryption was enabled do not carry the `enc:v1:` prefix (client
+/// ciphertext is base64 and never contains `:`), they are returned unchanged.
+#[derive(Clone)]
+pub struct EncryptedSecretStore<T: SecretStore> {
+    inner: T,
+    cipher: Aes256Gcm,
+}
+
+impl<T: SecretStore> EncryptedSecretStore<T> {
+    /// Creates a new store using the base64 encoded 256 bit key.
+    pub fn new(inner: T, key_base64: &str) -> Result<Self, SecretStoreError> {
+        let key = base64::prelude::BASE64_STANDARD
+            .decode(key_base64.trim())
+            .map_err(|e| SecretStoreError::Encryption(format!("invalid key encoding: {e}")))?;
+        if key.len() != KEY_SIZE {
+            return Err(SecretStoreError::Encryption(format!(
+                "invalid key length: expected {KEY_SIZE} bytes, got {}",
+                key.len()
+            )));
+        }
+
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| SecretStoreError::Encryption(e.to_string()))?;
+        Ok(Self { inner, cipher })
+    }
+
+    fn encrypt(&self, id: Ulid, data: &str) -> Result<String, SecretStoreError> {
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        rand::rng()
+            .try_fill_bytes(&mut nonce_bytes)
+            .map_err(|e| SecretStoreError::Encryption(format!("failed to generate nonce: {e}")))?;
+
+        let mut nonce = Nonce::default();
+        nonce.copy_from_slice(&nonce_bytes);
+
+        let aad = id.to_string();
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: data.as_bytes(),
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|e| SecretStoreError::Encryption(format!("encryption failed: {e}")))?;
+
+        let mut blob = nonce_bytes.to_vec();
+        blob.extend_from_slice(&ciphertext);
+
+        let encoded = base64::prelude::BASE64_STANDARD.encode(blob);
+        Ok(format!("{ENCRYPTED_PREFIX}{encoded}"))
+    }
+
+    fn decrypt(&self, id: Ulid, value: String) -> Result<String, SecretStoreError> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value);
+        };
+
+        let blob = base64::prelude::BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|e| SecretStoreError::Encryption(format!("invalid stored value: {e}")))?;
+        if blob.len() < NONCE_SIZE {
+            return Err(SecretStoreError::Encryption(
+                "stored value too short".to_string(),
+            ));
+        }
+
+        let (nonce_bytes, ciphertext) = blob.split_at(NONCE_SIZE);
+        let mut nonce = Nonce::default();
+        nonce.copy_from_slice(nonce_bytes);
+
+        let aad = id.to_string();
+        let plaintext = self
+            .cipher
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|e| SecretStoreError::Encryption(format!("decryption failed: {e}")))?;
+
+        String::from_utf8(plaintext)
+            .map_err(|e| SecretStoreError::Encryption(format!("invalid stored value: {e}")))
+    }
+}
+
+#[async_trait]
+impl<T: SecretStore> SecretStore for EncryptedSecretStore<T> {
+    #[instrument(skip(self), err)]
+    async fn pop(&self, id: Ulid) -> Result<SecretStorePopResult, SecretStoreError> {
+        match self.inner.pop(id).await? {
+            SecretStorePopResult::Found(value) => {
+                Ok(SecretStorePopResult::Found(self.decrypt(id, value)?))
+            }
+            result => Ok(result),
+        }
+    }
+
+    #[instrument(skip(self, data), err)]
+    async fn put(
+        &self,
+        id: Ulid,
+        data: String,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        let encrypted = self.encrypt(id, &data)?;
+        self.inner.put(id, encrypted, expires_in).await
+    }
+
+    async fn discard(&self, id: Ulid) -> Result<(), SecretStoreError> {
+        self.inner.discard(id).await
+    }
+
+    async fn is_healthy(&self) -> Result<(), SecretStoreError> {
+        self.inner.is_healthy().await
+    }
+
+    async fn is_id_in_use(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        self.inner.is_id_in_use(id).await
+    }
+
+    async fn reserve(
+        &self,
+        id: Ulid,
+        token_hash: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        self.inner.reserve(id, token_hash, expires_in).await
+    }
+
+    async fn is_reserved(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        self.inner.is_reserved(id).await
+    }
+
+    async fn is_reserved_for(&self, id: Ulid, token_hash: &str) -> Result<bool, SecretStoreError> {
+        self.inner.is_reserved_for(id, token_hash).await
+    }
+
+    async fn put_reserved(
+        &self,
+        id: Ulid,
+        data: String,
+        expires_in: Duration,
+        token_hash: &str,
+    ) -> Result<Duration, SecretStoreError> {
+        let encrypted = self.encrypt(id, &data)?;
+        self.inner
+            .put_reserved(id, encrypted, expires_in, token_hash)
+            .await
+    }
+
+    async fn set_restrictions(
+        &self,
+        id: Ulid,
+        restrictions: &SecretRestrictions,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        self.inner
+            .set_restrictions(id, restrictions, expires_in)
+            .await
+    }
+
+    async fn get_restrictions(
+        &self,
+        id: Ulid,
+    ) -> Result<Option<SecretRestrictions>, SecretStoreError> {
+        self.inner.get_restrictions(id).await
+    }
+
+    async fn record_failed_attempt(&self, id: Ulid) -> Result<u32, SecretStoreError> {
+        self.inner.record_failed_attempt(id).await
+    }
+
+    async fn set_metadata(
+        &self,
+        id: Ulid,
+        metadata: &SecretMetadata,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        self.inner.set_metadata(id, metadata, expires_in).await
+    }
+
+    async fn get_metadata(&self, id: Ulid) -> Result<Option<SecretMetadata>, SecretStoreError> {
+        self.inner.get_metadata(id).await
+    }
+
+    async fn set_alias(
+        &self,
+        alias: &str,
+        id: Ulid,
+        expires_in: Duration,
+    ) -> Result<bool, SecretStoreError> {
+        self.inner.set_alias(alias, id, expires_in).await
+    }
+
+    async fn resolve_alias(&self, alias: &str) -> Result<Option<Ulid>, SecretStoreError> {
+        self.inner.resolve_alias(alias).await
+    }
+
+    async fn set_owner(
+        &self,
+        id: Ulid,
+        owner: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        self.inner.set_owner(id, owner, expires_in).await
+    }
+
+    async fn list_owned(&self, owner: &str) -> Result<Vec<OwnedSecret>, SecretStoreError> {
+        self.inner.list_owned(owner).await
+    }
+
+    async fn revoke_owned(&self, owner: &str, id: Ulid) -> Result<bool, SecretStoreError> {
+        self.inner.revoke_owned(owner, id).await
+    }
+
+    async fn count_purgeable(
+        &self,
+        older_than: Option<Duration>,
+    ) -> Result<usize, SecretStoreError> {
+        self.inner.count_purgeable(older_than).await
+    }
+
+    async fn purge(&self, older_than: Option<Duration>) -> Result<usize, SecretStoreError> {
+        self.inner.purge(older_than).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    use crate::secret::MockSecretStore;
+
+    type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+    const TEST_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+    const OTHER_KEY: &str = "ZmVkY2JhOTg3NjU0MzIxMGZlZGNiYTk4NzY1NDMyMTA=";
+
+    #[tokio::test]
+    async fn test_put_and_pop_roundtrip() -> Result<()> {
+        let inner = MockSecretStore::new();
+        let store = EncryptedSecretStore::new(inner.clone(), TEST_KEY)?;
+        let id = Ulid::r#gen();
+
+        store
+            .put(id, "client-ciphertext".to_string(), Duration::from_secs(60))
+            .await?;
+
+        let stored = &inner.get_put_operations()[0].1;
+        assert!(
+            stored.starts_with(ENCRYPTED_PREFIX),
+            "Stored value should be encrypted: {stored}"
+        );
+        assert!(
+            !stored.contains("client-ciphertext"),
+            "Stored value should not contain the plaintext"
+        );
+
+        match store.pop(id).await? {
+            SecretStorePopResult::Found(data) => assert_eq!(data, "client-ciphertext"),
+            other => panic!("Expected Found, got: {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pop_unencrypted_value_is_passed_through() -> Result<()> {
+        let inner = MockSecretStore::new()
+            .with_pop_result(SecretStorePopResult::Found("bGVnYWN5".to_string()));
+        let store = EncryptedSecretStore::new(inner, TEST_KEY)?;
+
+        match store.pop(Ulid::r#gen()).await? {
+            SecretStorePopResult::Found(data) => assert_eq!(data, "bGVnYWN5"),
+            other => panic!("Expected Found, got: {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pop_not_found_is_passed_through() -> Result<()> {
+        let store = EncryptedSecretStore::new(MockSecretStore::new(), TEST_KEY)?;
+
+        let result = store.pop(Ulid::r#gen()).await?;
+        assert!(
+            matches!(result, SecretStorePopResult::NotFound),
+            "Expected NotFound, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pop_with_wrong_key_fails() -> Result<()> {
+        let inner = MockSecretStore::new();
+        let id = Ulid::r#gen();
+        EncryptedSecretStore::new(inner.clone(), TEST_KEY)?
+            .put(id, "secret".to_string(), Duration::from_secs(60))
+            .await?;
+
+        let store = EncryptedSecretStore::new(inner, OTHER_KEY)?;
+        let result = store.pop(id).await;
+        assert!(
+            matches!(result, Err(SecretStoreError::Encryption(_))),
+            "Expected encryption error, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pop_value_stored_for_other_id_fails() -> Result<()> {
+        let store = EncryptedSecretStore::new(MockSecretStore::new(), TEST_KEY)?;
+        let encrypted = store.encrypt(Ulid::r#gen(), "secret")?;
+
+        let result = store.decrypt(Ulid::r#gen(), encrypted);
+        assert!(
+            result.is_err(),
+            "Value bound to another ID should not decrypt, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_invalid_key() {
+        let short_key = base64::prelude::BASE64_STANDARD.encode([0u8; 16]);
+        let result = EncryptedSecretStore::new(MockSecretStore::new(), &short_key);
+        assert!(
+            matches!(result, Err(SecretStoreError::Encryption(_))),
+            "Expected error for short key"
+        );
+
+        let result = EncryptedSecretStore::new(MockSecretStore::new(), "not base64!");
+        assert!(
+            matches!(result, Err(SecretStoreError::Encryption(_))),
+            "Expected error for invalid encoding"
+        );
+    }
+}
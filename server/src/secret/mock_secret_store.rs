@@ -7,14 +7,17 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use async_trait::async_trait;
 use ulid::Ulid;
 
-use hakanai_lib::models::SecretRestrictions;
+use hakanai_lib::models::{OwnedSecret, SecretMetadata, SecretRestrictions};
 
-use super::{SecretStore, SecretStoreError, SecretStorePopResult};
+use super::{SecretStore, SecretStoreError, SecretStorePopResult, is_purgeable};
+
+/// Secrets of each owner with their TTL, keyed by owner.
+type OwnedSecrets = HashMap<String, Vec<(Ulid, Duration)>>;
 
 /// Mock implementation of SecretStore trait for testing.
 ///
@@ -28,6 +31,12 @@ pub struct MockSecretStore {
     secret_count: Arc<Mutex<usize>>,
     /// Whether operations should fail
     should_fail: Arc<Mutex<bool>>,
+    /// Whether failures should be reported as unavailable data store
+    unavailable: Arc<Mutex<bool>>,
+    /// Whether writes of restrictions, metadata and owners should fail
+    records_fail: Arc<Mutex<bool>>,
+    /// Whether IDs should be reported as unused regardless of stored secrets
+    stale_id_check: Arc<Mutex<bool>>,
     /// Stored secrets for retrieval
     stored_secrets: Arc<Mutex<HashMap<String, String>>>,
     /// Secrets marked as accessed
@@ -40,6 +49,16 @@ pub struct MockSecretStore {
     set_restrictions_operations: Arc<Mutex<Vec<(Ulid, SecretRestrictions, Duration)>>>,
     /// Restrictions for secrets
     restrictions: Arc<Mutex<HashMap<String, SecretRestrictions>>>,
+    /// Metadata of secrets
+    metadata: Arc<Mutex<HashMap<String, SecretMetadata>>>,
+    /// Secrets of each owner with their TTL
+    owned_secrets: Arc<Mutex<OwnedSecrets>>,
+    /// Short aliases of secrets with their TTL
+    aliases: Arc<Mutex<HashMap<String, (Ulid, Duration)>>>,
+    /// Upload token hashes of reserved IDs with their TTL
+    reservations: Arc<Mutex<HashMap<String, (String, Duration)>>>,
+    /// Failed attempts to access secrets
+    failed_attempts: Arc<Mutex<HashMap<String, u32>>>,
 }
 
 impl MockSecretStore {
@@ -48,12 +67,20 @@ impl MockSecretStore {
         Self {
             secret_count: Arc::new(Mutex::new(0)),
             should_fail: Arc::new(Mutex::new(false)),
+            unavailable: Arc::new(Mutex::new(false)),
+            records_fail: Arc::new(Mutex::new(false)),
+            stale_id_check: Arc::new(Mutex::new(false)),
             stored_secrets: Arc::new(Mutex::new(HashMap::new())),
             accessed_secrets: Arc::new(Mutex::new(Vec::new())),
             custom_pop_result: Arc::new(Mutex::new(None)),
             put_operations: Arc::new(Mutex::new(Vec::new())),
             set_restrictions_operations: Arc::new(Mutex::new(Vec::new())),
             restrictions: Arc::new(Mutex::new(HashMap::new())),
+            metadata: Arc::new(Mutex::new(HashMap::new())),
+            owned_secrets: Arc::new(Mutex::new(HashMap::new())),
+            aliases: Arc::new(Mutex::new(HashMap::new())),
+            reservations: Arc::new(Mutex::new(HashMap::new())),
+            failed_attempts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -62,6 +89,19 @@ impl MockSecretStore {
         *self.should_fail.lock().expect("Failed to acquire lock")
     }
 
+    fn should_records_fail(&self) -> bool {
+        self.should_fail() || *self.records_fail.lock().expect("Failed to acquire lock")
+    }
+
+    fn failure(&self) -> SecretStoreError {
+        if *self.unavailable.lock().expect("Failed to acquire lock") {
+            let err = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "Mock failure");
+            return SecretStoreError::Redis(err.into());
+        }
+
+        SecretStoreError::InternalError("Mock failure".to_string())
+    }
+
     fn _get_secret_count(&self) -> usize {
         *self.secret_count.lock().expect("Failed to acquire lock")
     }
@@ -116,6 +156,61 @@ impl MockSecretStore {
         self.restrictions.lock().expect("Failed to acquire lock")
     }
 
+    fn get_metadata_mut(&self) -> std::sync::MutexGuard<'_, HashMap<String, SecretMetadata>> {
+        self.metadata.lock().expect("Failed to acquire lock")
+    }
+
+    fn get_owned_secrets_mut(&self) -> std::sync::MutexGuard<'_, OwnedSecrets> {
+        self.owned_secrets.lock().expect("Failed to acquire lock")
+    }
+
+    fn get_aliases_mut(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Ulid, Duration)>> {
+        self.aliases.lock().expect("Failed to acquire lock")
+    }
+
+    fn get_reservations_mut(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<String, (String, Duration)>> {
+        self.reservations.lock().expect("Failed to acquire lock")
+    }
+
+    /// Reserve an ID with the hash of its upload token (for testing)
+    pub fn with_reservation(self, id: Ulid, token_hash: &str) -> Self {
+        self.get_reservations_mut().insert(
+            id.to_string(),
+            (token_hash.to_string(), Duration::from_secs(3600)),
+        );
+        self
+    }
+
+    /// Get the upload token hashes of all reserved IDs for testing verification
+    pub fn get_reservations(&self) -> HashMap<String, String> {
+        self.get_reservations_mut()
+            .iter()
+            .map(|(id, (token_hash, _))| (id.clone(), token_hash.clone()))
+            .collect()
+    }
+
+    /// Get the TTL of all reserved IDs for testing verification
+    pub fn get_reservation_ttls(&self) -> HashMap<String, Duration> {
+        self.get_reservations_mut()
+            .iter()
+            .map(|(id, (_, ttl))| (id.clone(), *ttl))
+            .collect()
+    }
+
+    /// Set an alias for a secret (for testing)
+    pub fn with_alias(self, alias: &str, id: Ulid) -> Self {
+        self.get_aliases_mut()
+            .insert(alias.to_string(), (id, Duration::from_secs(3600)));
+        self
+    }
+
+    /// Get all aliases with their secret ID and TTL for testing verification
+    pub fn get_aliases(&self) -> HashMap<String, (Ulid, Duration)> {
+        self.get_aliases_mut().clone()
+    }
+
     /// Set a custom pop result for testing specific scenarios
     pub fn with_pop_result(self, result: SecretStorePopResult) -> Self {
         self.set_custom_pop_result(Some(result));
@@ -134,6 +229,25 @@ impl MockSecretStore {
         self
     }
 
+    /// Configure writes of restrictions, metadata and owners to fail, while secrets are stored
+    pub fn with_records_error(self) -> Self {
+        *self.records_fail.lock().expect("Failed to acquire lock") = true;
+        self
+    }
+
+    /// Report all IDs as unused, as if a concurrent upload stored a secret after the check
+    pub fn with_stale_id_check(self) -> Self {
+        *self.stale_id_check.lock().expect("Failed to acquire lock") = true;
+        self
+    }
+
+    /// Configure all operations to fail as if the data store was unreachable
+    pub fn with_unavailable_error(self) -> Self {
+        self.set_should_fail(true);
+        *self.unavailable.lock().expect("Failed to acquire lock") = true;
+        self
+    }
+
     /// Get all put operations for testing verification
     pub fn get_put_operations(&self) -> Vec<(Ulid, String, Duration)> {
         self.get_put_operations_mut().clone()
@@ -151,10 +265,52 @@ impl MockSecretStore {
         self
     }
 
+    /// Set metadata for a secret (for testing)
+    pub fn with_metadata(self, id: Ulid, metadata: SecretMetadata) -> Self {
+        self.get_metadata_mut().insert(id.to_string(), metadata);
+        self
+    }
+
+    /// Get the metadata of all secrets for testing verification
+    pub fn get_all_metadata(&self) -> HashMap<String, SecretMetadata> {
+        self.get_metadata_mut().clone()
+    }
+
     /// Get all restrictions for testing verification
     pub fn get_restrictions(&self) -> HashMap<String, SecretRestrictions> {
         self.get_restrictions_mut().clone()
     }
+
+    /// Get the IDs of all stored secrets for testing verification
+    pub fn get_stored_ids(&self) -> Vec<String> {
+        self.get_stored_secrets_mut().keys().cloned().collect()
+    }
+
+    fn store(&self, id: Ulid, data: String, expires_in: Duration) {
+        // Record the put operation for testing verification
+        self.get_put_operations_mut()
+            .push((id, data.clone(), expires_in));
+
+        self.get_stored_secrets_mut().insert(id.to_string(), data);
+
+        // Update secret count
+        let count = self.get_stored_secrets_mut().len();
+        self.set_secret_count_internal(count);
+    }
+
+    fn purgeable_ids(&self, older_than: Option<Duration>) -> Result<Vec<String>, SecretStoreError> {
+        let now = SystemTime::now();
+        let mut ids = Vec::new();
+        for key in self.get_stored_secrets_mut().keys() {
+            let Ok(id) = Ulid::from_string(key) else {
+                continue;
+            };
+            if is_purgeable(id, older_than, now)? {
+                ids.push(key.clone());
+            }
+        }
+        Ok(ids)
+    }
 }
 
 impl Default for MockSecretStore {
@@ -167,7 +323,7 @@ impl Default for MockSecretStore {
 impl SecretStore for MockSecretStore {
     async fn pop(&self, id: Ulid) -> Result<SecretStorePopResult, SecretStoreError> {
         if self.should_fail() {
-            return Err(SecretStoreError::InternalError("Mock failure".to_string()));
+            return Err(self.failure());
         }
 
         // Check if we have a custom pop result
@@ -180,6 +336,7 @@ impl SecretStore for MockSecretStore {
         // Check if secret exists
         if let Some(secret) = self.get_stored_secrets_mut().remove(&id_str) {
             // Mark as accessed
+            self.get_metadata_mut().remove(&id_str);
             self.get_accessed_secrets_mut().push(id_str);
             return Ok(SecretStorePopResult::Found(secret));
         }
@@ -199,37 +356,128 @@ impl SecretStore for MockSecretStore {
         expires_in: Duration,
     ) -> Result<(), SecretStoreError> {
         if self.should_fail() {
-            return Err(SecretStoreError::InternalError("Mock failure".to_string()));
+            return Err(self.failure());
         }
 
-        // Record the put operation for testing verification
-        self.get_put_operations_mut()
-            .push((id, data.clone(), expires_in));
+        let id_str = id.to_string();
+        if self.get_stored_secrets_mut().contains_key(&id_str)
+            || self.get_accessed_secrets_mut().contains(&id_str)
+            || self.get_reservations_mut().contains_key(&id_str)
+        {
+            return Err(SecretStoreError::IdInUse);
+        }
 
-        self.get_stored_secrets_mut().insert(id.to_string(), data);
+        self.store(id, data, expires_in);
+        Ok(())
+    }
 
-        // Update secret count
-        let count = self.get_stored_secrets_mut().len();
-        self.set_secret_count_internal(count);
+    async fn put_reserved(
+        &self,
+        id: Ulid,
+        data: String,
+        expires_in: Duration,
+        token_hash: &str,
+    ) -> Result<Duration, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let id_str = id.to_string();
+        let mut reservations = self.get_reservations_mut();
+        let Some((reserved_for, ttl)) = reservations.get(&id_str).cloned() else {
+            return Err(SecretStoreError::NotReserved);
+        };
+        if reserved_for != token_hash {
+            return Err(SecretStoreError::NotReserved);
+        }
+        if self.get_stored_secrets_mut().contains_key(&id_str)
+            || self.get_accessed_secrets_mut().contains(&id_str)
+        {
+            return Err(SecretStoreError::IdInUse);
+        }
+
+        reservations.remove(&id_str);
+        self.store(id, data, expires_in);
+        Ok(ttl)
+    }
 
+    async fn discard(&self, id: Ulid) -> Result<(), SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let id_str = id.to_string();
+        self.get_stored_secrets_mut().remove(&id_str);
+        self.get_restrictions_mut().remove(&id_str);
+        self.get_metadata_mut().remove(&id_str);
         Ok(())
     }
 
     async fn is_healthy(&self) -> Result<(), SecretStoreError> {
         if self.should_fail() {
-            return Err(SecretStoreError::InternalError("Mock failure".to_string()));
+            return Err(self.failure());
+        }
+        Ok(())
+    }
+
+    async fn is_id_in_use(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        if *self.stale_id_check.lock().expect("Failed to acquire lock") {
+            return Ok(false);
+        }
+
+        let id_str = id.to_string();
+        Ok(self.get_stored_secrets_mut().contains_key(&id_str)
+            || self.get_accessed_secrets_mut().contains(&id_str)
+            || self.get_reservations_mut().contains_key(&id_str))
+    }
+
+    async fn reserve(
+        &self,
+        id: Ulid,
+        token_hash: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
         }
+
+        self.get_reservations_mut()
+            .insert(id.to_string(), (token_hash.to_string(), expires_in));
         Ok(())
     }
 
+    async fn is_reserved(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        Ok(self.get_reservations_mut().contains_key(&id.to_string()))
+    }
+
+    async fn is_reserved_for(&self, id: Ulid, token_hash: &str) -> Result<bool, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        Ok(self
+            .get_reservations_mut()
+            .get(&id.to_string())
+            .map(|(reserved_for, _)| reserved_for.as_str())
+            == Some(token_hash))
+    }
+
     async fn set_restrictions(
         &self,
         id: Ulid,
         restrictions: &SecretRestrictions,
         expires_in: Duration,
     ) -> Result<(), SecretStoreError> {
-        if self.should_fail() {
-            return Err(SecretStoreError::InternalError("Mock failure".to_string()));
+        if self.should_records_fail() {
+            return Err(self.failure());
         }
 
         // Record the set_restrictions operation for testing verification
@@ -249,7 +497,7 @@ impl SecretStore for MockSecretStore {
         id: Ulid,
     ) -> Result<Option<SecretRestrictions>, SecretStoreError> {
         if self.should_fail() {
-            return Err(SecretStoreError::InternalError("Mock failure".to_string()));
+            return Err(self.failure());
         }
 
         // Retrieve the restrictions
@@ -257,4 +505,155 @@ impl SecretStore for MockSecretStore {
 
         Ok(restrictions)
     }
+
+    async fn record_failed_attempt(&self, id: Ulid) -> Result<u32, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let mut failed_attempts = self.failed_attempts.lock().expect("Failed to acquire lock");
+        let attempts = failed_attempts.entry(id.to_string()).or_default();
+        *attempts += 1;
+        Ok(*attempts)
+    }
+
+    async fn set_metadata(
+        &self,
+        id: Ulid,
+        metadata: &SecretMetadata,
+        _expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        if self.should_records_fail() {
+            return Err(self.failure());
+        }
+
+        self.get_metadata_mut()
+            .insert(id.to_string(), metadata.clone());
+        Ok(())
+    }
+
+    async fn get_metadata(&self, id: Ulid) -> Result<Option<SecretMetadata>, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        Ok(self.get_metadata_mut().get(&id.to_string()).cloned())
+    }
+
+    async fn set_alias(
+        &self,
+        alias: &str,
+        id: Ulid,
+        expires_in: Duration,
+    ) -> Result<bool, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let mut aliases = self.get_aliases_mut();
+        if aliases.contains_key(alias) {
+            return Ok(false);
+        }
+
+        aliases.insert(alias.to_string(), (id, expires_in));
+        Ok(true)
+    }
+
+    async fn resolve_alias(&self, alias: &str) -> Result<Option<Ulid>, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        Ok(self.get_aliases_mut().get(alias).map(|(id, _)| *id))
+    }
+
+    async fn set_owner(
+        &self,
+        id: Ulid,
+        owner: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        if self.should_records_fail() {
+            return Err(self.failure());
+        }
+
+        self.get_owned_secrets_mut()
+            .entry(owner.to_string())
+            .or_default()
+            .push((id, expires_in));
+        Ok(())
+    }
+
+    async fn list_owned(&self, owner: &str) -> Result<Vec<OwnedSecret>, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let stored = self.get_stored_secrets_mut().clone();
+        let secrets = self
+            .get_owned_secrets_mut()
+            .get(owner)
+            .into_iter()
+            .flatten()
+            .filter(|(id, _)| stored.contains_key(&id.to_string()))
+            .map(|(id, ttl)| OwnedSecret {
+                id: *id,
+                ttl_remaining_seconds: ttl.as_secs(),
+            })
+            .collect();
+        Ok(secrets)
+    }
+
+    async fn revoke_owned(&self, owner: &str, id: Ulid) -> Result<bool, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let mut owned = self.get_owned_secrets_mut();
+        let Some(secrets) = owned.get_mut(owner) else {
+            return Ok(false);
+        };
+
+        let count = secrets.len();
+        secrets.retain(|(owned_id, _)| *owned_id != id);
+        if secrets.len() == count {
+            return Ok(false);
+        }
+
+        self.get_restrictions_mut().remove(&id.to_string());
+        self.get_metadata_mut().remove(&id.to_string());
+        Ok(self
+            .get_stored_secrets_mut()
+            .remove(&id.to_string())
+            .is_some())
+    }
+
+    async fn count_purgeable(
+        &self,
+        older_than: Option<Duration>,
+    ) -> Result<usize, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        Ok(self.purgeable_ids(older_than)?.len())
+    }
+
+    async fn purge(&self, older_than: Option<Duration>) -> Result<usize, SecretStoreError> {
+        if self.should_fail() {
+            return Err(self.failure());
+        }
+
+        let ids = self.purgeable_ids(older_than)?;
+        for id in &ids {
+            self.get_stored_secrets_mut().remove(id);
+            self.get_restrictions_mut().remove(id);
+            self.get_metadata_mut().remove(id);
+        }
+
+        let count = self.get_stored_secrets_mut().len();
+        self.set_secret_count_internal(count);
+
+        Ok(ids.len())
+    }
 }
@@ -1,13 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod alias;
+mod encrypted_secret_store;
 mod redis_secret_store;
 mod secret_store;
 
 #[cfg(test)]
 mod mock_secret_store;
+#[cfg(test)]
+pub mod storage_invariants;
 
+pub use encrypted_secret_store::EncryptedSecretStore;
 pub use redis_secret_store::RedisSecretStore;
-pub use secret_store::{SecretStore, SecretStoreError, SecretStorePopResult};
+pub(crate) use secret_store::is_purgeable;
+pub use secret_store::{SecretStore, SecretStoreError, SecretStorePopResult, SecretStream};
 
 #[cfg(test)]
 pub use mock_secret_store::MockSecretStore;
@@ -1,48 +1,163 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::Duration;
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime};
 
+use actix_web::web::Bytes;
 use async_trait::async_trait;
-use redis::AsyncCommands;
-use redis::aio::ConnectionManager;
+use futures_util::stream;
+use redis::{AsyncCommands, Script};
 use tracing::instrument;
 use ulid::Ulid;
 
-use hakanai_lib::models::SecretRestrictions;
+use hakanai_lib::models::{OwnedSecret, SecretMetadata, SecretRestrictions};
 use hakanai_lib::utils::timestamp;
 
-use crate::secret::{SecretStore, SecretStoreError, SecretStorePopResult};
+use crate::pool::{RedisPool, scan_keys};
+use crate::secret::{
+    SecretStore, SecretStoreError, SecretStorePopResult, SecretStream, is_purgeable,
+};
 
 const SECRET_PREFIX: &str = "secret:";
 const ACCESSED_PREFIX: &str = "accessed:";
 const RESTRICTIONS_PREFIX: &str = "restrictions:";
+const OWNER_PREFIX: &str = "owner:";
+const ALIAS_PREFIX: &str = "alias:";
+const METADATA_PREFIX: &str = "metadata:";
+const RETRIEVING_PREFIX: &str = "retrieving:";
+const RESERVATION_PREFIX: &str = "reservation:";
+const FAILED_ATTEMPTS_PREFIX: &str = "failed_attempts:";
+
+/// Size of the byte ranges a streamed secret is read from Redis in.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024; // 64 KB
+
+/// Time after which a claimed secret is removed if its download is aborted.
+const RETRIEVING_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Moves a secret to a key only known to the claiming request, so concurrent requests cannot
+/// retrieve it twice, while it is read in byte ranges.
+///
+/// Returns the length of the secret or -1 if it does not exist.
+static CLAIM_SECRET_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+  return -1
+end
+
+redis.call('RENAME', KEYS[1], KEYS[2])
+redis.call('EXPIRE', KEYS[2], ARGV[1])
+return redis.call('STRLEN', KEYS[2])
+"#,
+    )
+});
+
+/// Stores a secret unless its ID is in use by a stored, reserved or already accessed secret, so
+/// concurrent uploads with the same proposed ID cannot overwrite or revive a secret.
+///
+/// Returns 1 if the secret was stored, 0 otherwise.
+static STORE_SECRET_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+if redis.call('EXISTS', KEYS[1], KEYS[2], KEYS[3]) > 0 then
+  return 0
+end
+
+redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+return 1
+"#,
+    )
+});
+
+/// Stores a secret under a reserved ID and deletes the reservation, if the reservation holds
+/// the expected upload token hash and the ID is not in use by a stored or already accessed
+/// secret.
+///
+/// Returns the remaining seconds of the reservation if the secret was stored, -1 if the ID is
+/// in use and -2 if it is not reserved for the upload token.
+static STORE_RESERVED_SECRET_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+if redis.call('GET', KEYS[3]) ~= ARGV[3] then
+  return -2
+end
+
+if redis.call('EXISTS', KEYS[1], KEYS[2]) > 0 then
+  return -1
+end
+
+local ttl = redis.call('TTL', KEYS[3])
+redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+redis.call('DEL', KEYS[3])
+return math.max(ttl, 1)
+"#,
+    )
+});
 
 /// An implementation of the `SecretStore` trait that uses Redis as its backend.
-/// This struct holds a `ConnectionManager` for interacting with the Redis
+/// This struct holds a `RedisPool` for interacting with the Redis
 /// server. It is designed to be cloneable and thread-safe.
 #[derive(Clone)]
 pub struct RedisSecretStore {
-    con: ConnectionManager,
+    con: RedisPool,
     max_ttl: Duration,
+    key_prefix: String,
 }
 
 impl RedisSecretStore {
-    pub fn new(con: ConnectionManager, max_ttl: Duration) -> Self {
-        Self { con, max_ttl }
+    pub fn new(con: RedisPool, max_ttl: Duration) -> Self {
+        Self {
+            con,
+            max_ttl,
+            key_prefix: String::new(),
+        }
+    }
+
+    /// Sets the prefix for all keys, so multiple instances can share one Redis.
+    pub fn with_key_prefix(mut self, key_prefix: &str) -> Self {
+        self.key_prefix = key_prefix.to_string();
+        self
     }
 }
 
 impl RedisSecretStore {
     fn secret_key(&self, id: Ulid) -> String {
-        format!("{SECRET_PREFIX}{id}")
+        format!("{}{SECRET_PREFIX}{id}", self.key_prefix)
     }
 
     fn accessed_key(&self, id: Ulid) -> String {
-        format!("{ACCESSED_PREFIX}{id}")
+        format!("{}{ACCESSED_PREFIX}{id}", self.key_prefix)
     }
 
     fn restrictions_key(&self, id: Ulid) -> String {
-        format!("{RESTRICTIONS_PREFIX}{id}")
+        format!("{}{RESTRICTIONS_PREFIX}{id}", self.key_prefix)
+    }
+
+    fn metadata_key(&self, id: Ulid) -> String {
+        format!("{}{METADATA_PREFIX}{id}", self.key_prefix)
+    }
+
+    /// Key of a secret while it is streamed to the client which claimed it
+    fn retrieving_key(&self, id: Ulid) -> String {
+        format!("{}{RETRIEVING_PREFIX}{id}", self.key_prefix)
+    }
+
+    /// Key holding the upload token hash of a reserved ID
+    fn reservation_key(&self, id: Ulid) -> String {
+        format!("{}{RESERVATION_PREFIX}{id}", self.key_prefix)
+    }
+
+    fn failed_attempts_key(&self, id: Ulid) -> String {
+        format!("{}{FAILED_ATTEMPTS_PREFIX}{id}", self.key_prefix)
+    }
+
+    fn alias_key(&self, alias: &str) -> String {
+        format!("{}{ALIAS_PREFIX}{alias}", self.key_prefix)
+    }
+
+    /// Key of the sorted set holding the secret IDs of an owner scored by expiration timestamp
+    fn owner_key(&self, owner: &str) -> String {
+        format!("{}{OWNER_PREFIX}{owner}", self.key_prefix)
     }
 
     #[instrument(skip(self), err)]
@@ -64,6 +179,50 @@ impl RedisSecretStore {
             .await?;
         Ok(())
     }
+
+    /// Lists the IDs of all stored secrets matching the purge criteria.
+    async fn purgeable_ids(
+        &self,
+        older_than: Option<Duration>,
+    ) -> Result<Vec<Ulid>, SecretStoreError> {
+        let secret_prefix = format!("{}{SECRET_PREFIX}", self.key_prefix);
+        let keys = scan_keys(&mut self.con.clone(), &format!("{secret_prefix}*")).await?;
+        let now = SystemTime::now();
+
+        let mut ids = Vec::new();
+        for key in keys {
+            let Some(id) = key
+                .strip_prefix(&secret_prefix)
+                .and_then(|id| Ulid::from_string(id).ok())
+            else {
+                continue;
+            };
+
+            if is_purgeable(id, older_than, now)? {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+}
+
+/// Reads the chunk of a claimed secret starting at `offset`, returning it with the offset of the
+/// next chunk. The claimed key is deleted once the whole secret was read.
+async fn read_chunk(
+    mut con: RedisPool,
+    key: String,
+    offset: usize,
+    len: usize,
+) -> Result<Option<(Bytes, usize)>, SecretStoreError> {
+    if offset >= len {
+        let _: () = con.del(key).await?;
+        return Ok(None);
+    }
+
+    let end = (offset + STREAM_CHUNK_SIZE).min(len);
+    let chunk: Vec<u8> = con.getrange(key, offset as isize, end as isize - 1).await?;
+    Ok(Some((Bytes::from(chunk), end)))
 }
 
 #[async_trait]
@@ -75,6 +234,7 @@ impl SecretStore for RedisSecretStore {
 
         if let Some(secret) = value {
             self.mark_as_accessed(id).await?;
+            let _: () = self.con.clone().del(self.metadata_key(id)).await?;
             return Ok(SecretStorePopResult::Found(secret));
         }
 
@@ -85,6 +245,40 @@ impl SecretStore for RedisSecretStore {
         Ok(SecretStorePopResult::NotFound)
     }
 
+    #[instrument(skip(self), err)]
+    async fn pop_stream(
+        &self,
+        id: Ulid,
+    ) -> Result<SecretStorePopResult<SecretStream>, SecretStoreError> {
+        let retrieving_key = self.retrieving_key(id);
+        let len: i64 = CLAIM_SECRET_SCRIPT
+            .key(self.secret_key(id))
+            .key(&retrieving_key)
+            .arg(RETRIEVING_TTL.as_secs())
+            .invoke_async(&mut self.con.clone())
+            .await?;
+
+        let Ok(len) = usize::try_from(len) else {
+            if self.was_accessed(id).await? {
+                return Ok(SecretStorePopResult::AlreadyAccessed);
+            }
+            return Ok(SecretStorePopResult::NotFound);
+        };
+
+        self.mark_as_accessed(id).await?;
+        let _: () = self.con.clone().del(self.metadata_key(id)).await?;
+
+        let con = self.con.clone();
+        let chunks = stream::try_unfold(0, move |offset| {
+            read_chunk(con.clone(), retrieving_key.clone(), offset, len)
+        });
+
+        Ok(SecretStorePopResult::Found(SecretStream {
+            len: len as u64,
+            chunks: Box::pin(chunks),
+        }))
+    }
+
     #[instrument(skip(self, data), err)]
     async fn put(
         &self,
@@ -92,12 +286,29 @@ impl SecretStore for RedisSecretStore {
         data: String,
         expires_in: Duration,
     ) -> Result<(), SecretStoreError> {
-        let secret_key = self.secret_key(id);
-        let _: () = self
-            .con
-            .clone()
-            .set_ex(secret_key, data, expires_in.as_secs())
+        let stored: i64 = STORE_SECRET_SCRIPT
+            .key(self.secret_key(id))
+            .key(self.accessed_key(id))
+            .key(self.reservation_key(id))
+            .arg(data)
+            .arg(expires_in.as_secs())
+            .invoke_async(&mut self.con.clone())
             .await?;
+        if stored == 0 {
+            return Err(SecretStoreError::IdInUse);
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn discard(&self, id: Ulid) -> Result<(), SecretStoreError> {
+        let keys = [
+            self.secret_key(id),
+            self.restrictions_key(id),
+            self.metadata_key(id),
+        ];
+        let _: () = self.con.clone().del(&keys).await?;
         Ok(())
     }
 
@@ -108,6 +319,71 @@ impl SecretStore for RedisSecretStore {
     }
 
     #[instrument(skip(self), err)]
+    async fn is_id_in_use(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        let keys = [
+            self.secret_key(id),
+            self.accessed_key(id),
+            self.reservation_key(id),
+        ];
+        let count: usize = self.con.clone().exists(&keys).await?;
+        Ok(count > 0)
+    }
+
+    #[instrument(skip(self, token_hash), err)]
+    async fn reserve(
+        &self,
+        id: Ulid,
+        token_hash: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        let key = self.reservation_key(id);
+        let _: () = self
+            .con
+            .clone()
+            .set_ex(key, token_hash, expires_in.as_secs())
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn is_reserved(&self, id: Ulid) -> Result<bool, SecretStoreError> {
+        let key = self.reservation_key(id);
+        let exists: bool = self.con.clone().exists(key).await?;
+        Ok(exists)
+    }
+
+    #[instrument(skip(self, token_hash), err)]
+    async fn is_reserved_for(&self, id: Ulid, token_hash: &str) -> Result<bool, SecretStoreError> {
+        let key = self.reservation_key(id);
+        let reserved_for: Option<String> = self.con.clone().get(key).await?;
+        Ok(reserved_for.as_deref() == Some(token_hash))
+    }
+
+    #[instrument(skip(self, data, token_hash), err)]
+    async fn put_reserved(
+        &self,
+        id: Ulid,
+        data: String,
+        expires_in: Duration,
+        token_hash: &str,
+    ) -> Result<Duration, SecretStoreError> {
+        let remaining: i64 = STORE_RESERVED_SECRET_SCRIPT
+            .key(self.secret_key(id))
+            .key(self.accessed_key(id))
+            .key(self.reservation_key(id))
+            .arg(data)
+            .arg(expires_in.as_secs())
+            .arg(token_hash)
+            .invoke_async(&mut self.con.clone())
+            .await?;
+        match remaining {
+            -2 => Err(SecretStoreError::NotReserved),
+            -1 => Err(SecretStoreError::IdInUse),
+            secs => Ok(Duration::from_secs(secs as u64)),
+        }
+    }
+
+    #[instrument(skip(self, restrictions), err)]
     async fn set_restrictions(
         &self,
         id: Ulid,
@@ -141,4 +417,208 @@ impl SecretStore for RedisSecretStore {
             None => Ok(None),
         }
     }
+
+    #[instrument(skip(self), err)]
+    async fn record_failed_attempt(&self, id: Ulid) -> Result<u32, SecretStoreError> {
+        let key = self.failed_attempts_key(id);
+        // the counter has to outlive the secret, which expires within max_ttl
+        let (attempts,): (u32,) = redis::pipe()
+            .atomic()
+            .incr(&key, 1)
+            .expire(&key, self.max_ttl.as_secs() as i64)
+            .ignore()
+            .query_async(&mut self.con.clone())
+            .await?;
+        Ok(attempts)
+    }
+
+    #[instrument(skip(self, metadata), err)]
+    async fn set_metadata(
+        &self,
+        id: Ulid,
+        metadata: &SecretMetadata,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        let key = self.metadata_key(id);
+        let json = serde_json::to_string(metadata)?;
+
+        let _: () = self
+            .con
+            .clone()
+            .set_ex(key, json, expires_in.as_secs())
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn get_metadata(&self, id: Ulid) -> Result<Option<SecretMetadata>, SecretStoreError> {
+        let key = self.metadata_key(id);
+        let value: Option<String> = self.con.clone().get(key).await?;
+
+        match value {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self), err)]
+    async fn set_alias(
+        &self,
+        alias: &str,
+        id: Ulid,
+        expires_in: Duration,
+    ) -> Result<bool, SecretStoreError> {
+        let key = self.alias_key(alias);
+        let options = redis::SetOptions::default()
+            .conditional_set(redis::ExistenceCheck::NX)
+            .with_expiration(redis::SetExpiry::EX(expires_in.as_secs()));
+
+        let stored: Option<String> = self
+            .con
+            .clone()
+            .set_options(key, id.to_string(), options)
+            .await?;
+        Ok(stored.is_some())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn resolve_alias(&self, alias: &str) -> Result<Option<Ulid>, SecretStoreError> {
+        let key = self.alias_key(alias);
+        let value: Option<String> = self.con.clone().get(key).await?;
+
+        Ok(value.and_then(|id| Ulid::from_string(&id).ok()))
+    }
+
+    #[instrument(skip(self, owner), err)]
+    async fn set_owner(
+        &self,
+        id: Ulid,
+        owner: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError> {
+        let key = self.owner_key(owner);
+        let expires_at = timestamp::now()? + expires_in.as_secs();
+
+        let _: () = redis::pipe()
+            .zadd(&key, id.to_string(), expires_at)
+            .expire(&key, self.max_ttl.as_secs() as i64)
+            .query_async(&mut self.con.clone())
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, owner), err)]
+    async fn list_owned(&self, owner: &str) -> Result<Vec<OwnedSecret>, SecretStoreError> {
+        let key = self.owner_key(owner);
+        let now = timestamp::now()?;
+        let mut con = self.con.clone();
+
+        let _: () = con.zrembyscore(&key, "-inf", now).await?;
+        let entries: Vec<(String, u64)> = con.zrange_withscores(&key, 0, -1).await?;
+
+        let mut secrets = Vec::new();
+        for (id, expires_at) in entries {
+            let Ok(id) = Ulid::from_string(&id) else {
+                continue;
+            };
+
+            // retrieved secrets are removed lazily from the index
+            let exists: bool = con.exists(self.secret_key(id)).await?;
+            if !exists {
+                let _: () = con.zrem(&key, id.to_string()).await?;
+                continue;
+            }
+
+            secrets.push(OwnedSecret {
+                id,
+                ttl_remaining_seconds: expires_at.saturating_sub(now),
+            });
+        }
+
+        Ok(secrets)
+    }
+
+    #[instrument(skip(self, owner), err)]
+    async fn revoke_owned(&self, owner: &str, id: Ulid) -> Result<bool, SecretStoreError> {
+        let mut con = self.con.clone();
+
+        let removed: usize = con.zrem(self.owner_key(owner), id.to_string()).await?;
+        if removed == 0 {
+            return Ok(false);
+        }
+
+        let deleted: usize = con
+            .del(&[
+                self.secret_key(id),
+                self.restrictions_key(id),
+                self.metadata_key(id),
+            ])
+            .await?;
+        Ok(deleted > 0)
+    }
+
+    #[instrument(skip(self), err)]
+    async fn count_purgeable(
+        &self,
+        older_than: Option<Duration>,
+    ) -> Result<usize, SecretStoreError> {
+        Ok(self.purgeable_ids(older_than).await?.len())
+    }
+
+    #[instrument(skip(self), err)]
+    async fn purge(&self, older_than: Option<Duration>) -> Result<usize, SecretStoreError> {
+        let mut con = self.con.clone();
+        let mut purged = 0;
+
+        for id in self.purgeable_ids(older_than).await? {
+            let (deleted, _, _): (usize, usize, usize) = redis::pipe()
+                .del(self.secret_key(id))
+                .del(self.restrictions_key(id))
+                .del(self.metadata_key(id))
+                .query_async(&mut con)
+                .await?;
+            purged += deleted;
+        }
+
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use redis::aio::ConnectionManagerConfig;
+
+    use crate::pool::CircuitBreaker;
+    use crate::secret::storage_invariants::StorageInvariants;
+
+    /// Environment variable with the DSN of the Redis used by tests requiring a database.
+    const TEST_REDIS_DSN_ENV: &str = "HAKANAI_TEST_REDIS_DSN";
+
+    async fn test_store() -> Result<RedisSecretStore, SecretStoreError> {
+        let dsn = std::env::var(TEST_REDIS_DSN_ENV)
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let client = redis::Client::open(dsn)?;
+        let pool = RedisPool::new(
+            client,
+            ConnectionManagerConfig::new(),
+            1,
+            Duration::from_secs(5),
+            CircuitBreaker::new(3, Duration::from_secs(60)),
+        )
+        .connect()
+        .await?;
+
+        // accessed markers and counters live for max_ttl, so all test keys expire after a minute
+        let key_prefix = format!("hakanai-test:{}:", Ulid::r#gen());
+        Ok(RedisSecretStore::new(pool, Duration::from_secs(60)).with_key_prefix(&key_prefix))
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis, see HAKANAI_TEST_REDIS_DSN"]
+    async fn test_redis_store_upholds_invariants() -> Result<(), SecretStoreError> {
+        let store = test_store().await?;
+        StorageInvariants::new(&store).verify().await
+    }
 }
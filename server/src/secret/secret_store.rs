@@ -1,12 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::Duration;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use actix_web::web::Bytes;
 use async_trait::async_trait;
+use futures_util::{Stream, stream};
 use thiserror::Error;
 use ulid::Ulid;
 
-use hakanai_lib::models::SecretRestrictions;
+use hakanai_lib::models::{OwnedSecret, SecretMetadata, SecretRestrictions};
+
+use crate::pool::is_connection_error;
 
 /// `SecretStoreError` is an enum that represents the possible errors that can occur when accessing secret storage.
 /// It implements the `std::error::Error` trait and can be used to handle errors in a consistent way across the application.
@@ -28,13 +33,38 @@ pub enum SecretStoreError {
 
     #[error("error while JSON processing: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// Represents an error while encrypting or decrypting data at rest.
+    #[error("encryption at rest error: {0}")]
+    Encryption(String),
+
+    /// Represents an attempt to store a secret under the ID of a stored or already accessed
+    /// secret.
+    #[error("secret ID is already in use")]
+    IdInUse,
+
+    /// Represents an attempt to store a secret under an ID which is not reserved for the upload
+    /// token.
+    #[error("secret ID is not reserved for the upload token")]
+    NotReserved,
+}
+
+impl SecretStoreError {
+    /// Checks if the error is caused by the data store being unreachable (e.g. connection
+    /// errors or an open circuit breaker) rather than by the operation itself.
+    pub fn is_unavailable(&self) -> bool {
+        match self {
+            SecretStoreError::Redis(err) => is_connection_error(err),
+            _ => false,
+        }
+    }
 }
 
 /// `SecretStorePopResult` is an enum that represents the possible outcomes of SecretStore::pop operation.
 #[derive(Debug, Clone)]
-pub enum SecretStorePopResult {
+pub enum SecretStorePopResult<T = String> {
     /// Represents a successful retrieval of data from the data store.
-    Found(String),
+    Found(T),
 
     /// Represents a case where the requested data was not found in the data store.
     NotFound,
@@ -43,9 +73,44 @@ pub enum SecretStorePopResult {
     AlreadyAccessed,
 }
 
+impl<T> SecretStorePopResult<T> {
+    /// Maps the retrieved data, keeping the other outcomes.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SecretStorePopResult<U> {
+        match self {
+            SecretStorePopResult::Found(data) => SecretStorePopResult::Found(f(data)),
+            SecretStorePopResult::NotFound => SecretStorePopResult::NotFound,
+            SecretStorePopResult::AlreadyAccessed => SecretStorePopResult::AlreadyAccessed,
+        }
+    }
+}
+
+/// A retrieved secret which is read from the data store in chunks while it is sent to the client.
+pub struct SecretStream {
+    /// The total length of the secret in bytes.
+    pub len: u64,
+
+    /// The chunks of the secret in order.
+    pub chunks: Pin<Box<dyn Stream<Item = Result<Bytes, SecretStoreError>> + Send>>,
+}
+
+impl SecretStream {
+    /// Creates a stream of a secret already held in memory as a single chunk.
+    pub fn from_string(data: String) -> Self {
+        let chunk = Bytes::from(data);
+        Self {
+            len: chunk.len() as u64,
+            chunks: Box::pin(stream::once(async move { Ok(chunk) })),
+        }
+    }
+}
+
 /// `SecretStore` is a trait that defines the contract for a simple, asynchronous,
 /// key-value storage system. Implementations of this trait are expected to be
 /// thread-safe.
+///
+/// Implementations must never deduplicate or correlate secrets: every secret is stored under
+/// keys derived from its ID only, and no hash of the content is computed or stored. The
+/// `storage_invariants` test suite checks these properties.
 #[async_trait]
 pub trait SecretStore: Send + Sync {
     /// Atomically retrieves and removes a value from the data store based on its
@@ -64,6 +129,18 @@ pub trait SecretStore: Send + Sync {
     /// If an error occurs, it returns `SecretStoreError`.
     async fn pop(&self, id: Ulid) -> Result<SecretStorePopResult, SecretStoreError>;
 
+    /// Atomically claims a value like `pop`, but returns it as a stream of chunks, so the memory
+    /// needed to serve a secret is bounded regardless of its size.
+    ///
+    /// The secret counts as accessed once it is claimed, even if the stream is not read to the
+    /// end. The default implementation calls `pop` and returns the value as a single chunk.
+    async fn pop_stream(
+        &self,
+        id: Ulid,
+    ) -> Result<SecretStorePopResult<SecretStream>, SecretStoreError> {
+        Ok(self.pop(id).await?.map(SecretStream::from_string))
+    }
+
     /// Stores a value in the data store with a given `Uuid` and an expiration
     /// duration.
     ///
@@ -78,7 +155,9 @@ pub trait SecretStore: Send + Sync {
     /// # Returns
     ///
     /// A `Result` which is `Ok(())` on successful insertion, or an `Err` if an
-    /// error occurs.
+    /// error occurs. Storing is atomic with checking the ID: if a secret with the ID is stored,
+    /// reserved or was already accessed, nothing is stored and `SecretStoreError::IdInUse` is
+    /// returned.
     async fn put(
         &self,
         id: Ulid,
@@ -86,12 +165,59 @@ pub trait SecretStore: Send + Sync {
         expires_in: Duration,
     ) -> Result<(), SecretStoreError>;
 
+    /// Deletes a stored secret with its restrictions and metadata without marking it as
+    /// accessed, e.g. if its records could not be stored.
+    async fn discard(&self, id: Ulid) -> Result<(), SecretStoreError>;
+
     /// Checks if the data store is healthy and can be accessed.
     ///
     /// # Returns
     /// true if the data store is healthy, false otherwise.
     async fn is_healthy(&self) -> Result<(), SecretStoreError>;
 
+    /// Checks if a secret with the given `Ulid` is stored, reserved or was already accessed.
+    ///
+    /// Used to reject client proposed IDs which would overwrite or revive a secret.
+    async fn is_id_in_use(&self, id: Ulid) -> Result<bool, SecretStoreError>;
+
+    /// Reserves the ID of a secret uploaded later.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The `Ulid` reserved for the secret.
+    /// * `token_hash` - The hash of the token authorizing the upload.
+    /// * `expires_in` - The duration after which the reservation expires if nothing was uploaded.
+    async fn reserve(
+        &self,
+        id: Ulid,
+        token_hash: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError>;
+
+    /// Checks if the ID is reserved and the secret was not uploaded yet.
+    async fn is_reserved(&self, id: Ulid) -> Result<bool, SecretStoreError>;
+
+    /// Checks if the ID is reserved for the hash of the upload token, without redeeming the
+    /// reservation.
+    async fn is_reserved_for(&self, id: Ulid, token_hash: &str) -> Result<bool, SecretStoreError>;
+
+    /// Stores a secret under a reserved ID and redeems the reservation in one atomic operation,
+    /// so a reserved ID can be uploaded to only once.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok` with the remaining time of the redeemed reservation, so it can be
+    /// restored if the upload is discarded. If the ID is not reserved for the hash of the upload
+    /// token, `SecretStoreError::NotReserved` is returned, if a secret with the ID is stored or
+    /// was already accessed, `SecretStoreError::IdInUse` is returned. Nothing is stored then.
+    async fn put_reserved(
+        &self,
+        id: Ulid,
+        data: String,
+        expires_in: Duration,
+        token_hash: &str,
+    ) -> Result<Duration, SecretStoreError>;
+
     /// Stores IP restrictions for a secret with the same TTL as the secret itself.
     ///
     /// # Arguments
@@ -124,4 +250,151 @@ pub trait SecretStore: Send + Sync {
         &self,
         id: Ulid,
     ) -> Result<Option<SecretRestrictions>, SecretStoreError>;
+
+    /// Counts a failed attempt to access a secret, e.g. with a wrong PIN.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of failed attempts for the secret including this one,
+    /// or an `Err` if an error occurs.
+    async fn record_failed_attempt(&self, id: Ulid) -> Result<u32, SecretStoreError>;
+
+    /// Stores the non-sensitive metadata of a secret with the same TTL as the secret itself.
+    ///
+    /// The metadata is deleted together with the secret once it is retrieved.
+    async fn set_metadata(
+        &self,
+        id: Ulid,
+        metadata: &SecretMetadata,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError>;
+
+    /// Retrieves the metadata of a secret, `None` if the secret has no metadata or does not
+    /// exist anymore.
+    async fn get_metadata(&self, id: Ulid) -> Result<Option<SecretMetadata>, SecretStoreError>;
+
+    /// Stores a short alias for a secret with the same TTL as the secret itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The short alias (e.g. `blue-otter-42`).
+    /// * `id` - The `Ulid` of the secret the alias refers to.
+    /// * `expires_in` - The duration after which the alias should expire.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok(true)` if the alias was stored, `Ok(false)` if the alias is
+    /// already in use, or an `Err` if an error occurs.
+    async fn set_alias(
+        &self,
+        alias: &str,
+        id: Ulid,
+        expires_in: Duration,
+    ) -> Result<bool, SecretStoreError>;
+
+    /// Resolves a short alias to the `Ulid` of its secret, `None` if unknown or expired.
+    async fn resolve_alias(&self, alias: &str) -> Result<Option<Ulid>, SecretStoreError>;
+
+    /// Records the owner of a secret, so the owner can list and revoke it later.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The `Ulid` of the secret.
+    /// * `owner` - The hashed identifier of the user who created the secret.
+    /// * `expires_in` - The duration after which the secret expires.
+    async fn set_owner(
+        &self,
+        id: Ulid,
+        owner: &str,
+        expires_in: Duration,
+    ) -> Result<(), SecretStoreError>;
+
+    /// Lists the secrets of an owner which were neither retrieved nor expired yet.
+    async fn list_owned(&self, owner: &str) -> Result<Vec<OwnedSecret>, SecretStoreError>;
+
+    /// Deletes a secret before it is retrieved, if it belongs to the owner.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok(true)` if the secret was deleted, `Ok(false)` if the owner has no
+    /// such outstanding secret, or an `Err` if an error occurs.
+    async fn revoke_owned(&self, owner: &str, id: Ulid) -> Result<bool, SecretStoreError>;
+
+    /// Counts the stored secrets which would be deleted by `purge`.
+    ///
+    /// # Arguments
+    ///
+    /// * `older_than` - Only secrets created longer ago than this duration are counted, all
+    ///   secrets if `None`.
+    async fn count_purgeable(
+        &self,
+        older_than: Option<Duration>,
+    ) -> Result<usize, SecretStoreError>;
+
+    /// Deletes all stored secrets (and their restrictions and metadata) regardless of owner, e.g. in response
+    /// to an incident. The creation time is derived from the timestamp of the `Ulid`.
+    ///
+    /// # Arguments
+    ///
+    /// * `older_than` - Only secrets created longer ago than this duration are deleted, all
+    ///   secrets if `None`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of deleted secrets, or an `Err` if an error occurs.
+    async fn purge(&self, older_than: Option<Duration>) -> Result<usize, SecretStoreError>;
+}
+
+/// Checks if the secret was created before the cutoff defined by `older_than`.
+pub(crate) fn is_purgeable(
+    id: Ulid,
+    older_than: Option<Duration>,
+    now: SystemTime,
+) -> Result<bool, SecretStoreError> {
+    let Some(older_than) = older_than else {
+        return Ok(true);
+    };
+
+    let now_ms = now.duration_since(UNIX_EPOCH)?.as_millis();
+    let cutoff_ms = now_ms.saturating_sub(older_than.as_millis());
+    Ok(u128::from(id.timestamp_ms()) < cutoff_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures_util::TryStreamExt;
+
+    #[tokio::test]
+    async fn test_secret_stream_from_string() -> Result<(), SecretStoreError> {
+        let stream = SecretStream::from_string("ciphertext".to_string());
+        assert_eq!(stream.len, 10);
+
+        let chunks: Vec<Bytes> = stream.chunks.try_collect().await?;
+        assert_eq!(chunks, vec![Bytes::from("ciphertext")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_purgeable() -> Result<(), SecretStoreError> {
+        let now = SystemTime::now();
+        let old = Ulid::from_datetime(now - Duration::from_secs(7200));
+        let recent = Ulid::from_datetime(now - Duration::from_secs(60));
+
+        assert!(is_purgeable(old, None, now)?, "All secrets without cutoff");
+        assert!(
+            is_purgeable(recent, None, now)?,
+            "All secrets without cutoff"
+        );
+        assert!(
+            is_purgeable(old, Some(Duration::from_secs(3600)), now)?,
+            "Secret older than cutoff should be purgeable"
+        );
+        assert!(
+            !is_purgeable(recent, Some(Duration::from_secs(3600)), now)?,
+            "Secret newer than cutoff should not be purgeable"
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test suite for the storage invariants every `SecretStore` has to uphold.
+//!
+//! The server must not be able to tell whether two secrets carry the same ciphertext, and a
+//! leaked data store must not reveal it either:
+//!
+//! - Every secret is stored under its own random ID, identical ciphertexts are never
+//!   deduplicated and retrieving one of them never affects the other.
+//! - Keys are derived from the ID only, no hash or other value derived from the content is
+//!   computed, stored or used for lookups.
+//! - Values persisted by the store are not correlated either, decorators transforming the value
+//!   (e.g. encryption at rest) have to use a fresh nonce per secret.
+//!
+//! Implementations run the suite in their tests, violated invariants are returned as errors:
+//!
+//! ```ignore
+//! StorageInvariants::new(&store).verify().await?;
+//! ```
+
+use std::time::Duration;
+
+use ulid::Ulid;
+
+use super::{SecretStore, SecretStoreError, SecretStorePopResult};
+
+const TTL: Duration = Duration::from_secs(60);
+const CIPHERTEXT: &str = "aWRlbnRpY2FsIGNpcGhlcnRleHQ=";
+
+/// Runs the storage invariant checks against a `SecretStore`.
+pub struct StorageInvariants<'a, S: SecretStore> {
+    store: &'a S,
+}
+
+impl<'a, S: SecretStore> StorageInvariants<'a, S> {
+    pub fn new(store: &'a S) -> Self {
+        Self { store }
+    }
+
+    /// Runs all checks, returning an error for the first violated invariant.
+    pub async fn verify(&self) -> Result<(), SecretStoreError> {
+        self.identical_ciphertexts_are_stored_separately().await?;
+        self.retrieval_only_consumes_requested_secret().await?;
+        self.ids_are_not_derived_from_content().await?;
+        Ok(())
+    }
+
+    /// Identical ciphertexts stored under different IDs are both retrievable.
+    async fn identical_ciphertexts_are_stored_separately(&self) -> Result<(), SecretStoreError> {
+        let (first, second) = (Ulid::r#gen(), Ulid::r#gen());
+        self.store.put(first, CIPHERTEXT.to_string(), TTL).await?;
+        self.store.put(second, CIPHERTEXT.to_string(), TTL).await?;
+
+        for id in [first, second] {
+            let result = self.store.pop(id).await?;
+            ensure(
+                matches!(result, SecretStorePopResult::Found(ref data) if data == CIPHERTEXT),
+                "secrets with identical ciphertext should be stored separately",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Retrieving a secret does not consume or reveal another secret with the same ciphertext.
+    async fn retrieval_only_consumes_requested_secret(&self) -> Result<(), SecretStoreError> {
+        let (retrieved, other) = (Ulid::r#gen(), Ulid::r#gen());
+        self.store
+            .put(retrieved, CIPHERTEXT.to_string(), TTL)
+            .await?;
+        self.store.put(other, CIPHERTEXT.to_string(), TTL).await?;
+
+        self.store.pop(retrieved).await?;
+        ensure(
+            matches!(
+                self.store.pop(retrieved).await?,
+                SecretStorePopResult::AlreadyAccessed
+            ),
+            "retrieved secret should be consumed",
+        )?;
+        ensure(
+            self.store.is_id_in_use(other).await?,
+            "secret with identical ciphertext should not be consumed",
+        )?;
+        ensure(
+            matches!(self.store.pop(other).await?, SecretStorePopResult::Found(_)),
+            "secret with identical ciphertext should still be retrievable",
+        )?;
+        Ok(())
+    }
+
+    /// The content cannot be used to find a secret, only its ID.
+    async fn ids_are_not_derived_from_content(&self) -> Result<(), SecretStoreError> {
+        let id = Ulid::r#gen();
+        self.store.put(id, CIPHERTEXT.to_string(), TTL).await?;
+
+        let unrelated = Ulid::r#gen();
+        ensure(
+            !self.store.is_id_in_use(unrelated).await?,
+            "only the ID of the secret should be in use",
+        )?;
+        ensure(
+            matches!(
+                self.store.pop(unrelated).await?,
+                SecretStorePopResult::NotFound
+            ),
+            "secret should not be found by another ID",
+        )?;
+
+        self.store.pop(id).await?;
+        Ok(())
+    }
+}
+
+fn ensure(invariant: bool, message: &str) -> Result<(), SecretStoreError> {
+    if invariant {
+        return Ok(());
+    }
+
+    Err(SecretStoreError::InternalError(format!(
+        "storage invariant violated: {message}"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::secret::{EncryptedSecretStore, MockSecretStore};
+
+    const TEST_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY=";
+
+    #[tokio::test]
+    async fn test_mock_store_upholds_invariants() -> Result<(), SecretStoreError> {
+        StorageInvariants::new(&MockSecretStore::new())
+            .verify()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_store_upholds_invariants() -> Result<(), SecretStoreError> {
+        let store = EncryptedSecretStore::new(MockSecretStore::new(), TEST_KEY)?;
+        StorageInvariants::new(&store).verify().await
+    }
+
+    #[tokio::test]
+    async fn test_violated_invariant_is_returned() {
+        let result = StorageInvariants::new(
+            &MockSecretStore::new().with_pop_result(SecretStorePopResult::NotFound),
+        )
+        .verify()
+        .await;
+
+        assert!(
+            matches!(result, Err(SecretStoreError::InternalError(ref message)) if message.contains("stored separately")),
+            "Violated invariant should be returned as error"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_store_persists_uncorrelated_values() -> Result<(), SecretStoreError> {
+        let inner = MockSecretStore::new();
+        let store = EncryptedSecretStore::new(inner.clone(), TEST_KEY)?;
+
+        for _ in 0..10 {
+            store
+                .put(Ulid::r#gen(), CIPHERTEXT.to_string(), TTL)
+                .await?;
+        }
+
+        let persisted: HashSet<String> = inner
+            .get_put_operations()
+            .into_iter()
+            .map(|(_, value, _)| value)
+            .collect();
+        assert_eq!(
+            persisted.len(),
+            10,
+            "Identical ciphertexts should be persisted as distinct values"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use ulid::Ulid;
+
+use super::secret_stats::SecretStats;
+use super::stats_store::StatsStore;
+
+/// Mock implementation of StatsStore trait for testing.
+#[derive(Clone, Default)]
+pub struct MockStatsStore {
+    /// Stored stats by secret ID
+    stats: Arc<Mutex<HashMap<Ulid, SecretStats>>>,
+    /// Whether operations should fail
+    should_fail: bool,
+}
+
+impl MockStatsStore {
+    /// Create a new mock stats store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add stats for a secret
+    pub fn with_stats(self, secret_id: Ulid, stats: SecretStats) -> Self {
+        self.stats
+            .lock()
+            .expect("Failed to acquire lock")
+            .insert(secret_id, stats);
+        self
+    }
+
+    /// Configure all operations to fail
+    pub fn with_failure(mut self) -> Self {
+        self.should_fail = true;
+        self
+    }
+
+    fn ensure_not_failing(&self) -> Result<()> {
+        if self.should_fail {
+            return Err(anyhow!("Mock failure"));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StatsStore for MockStatsStore {
+    async fn store_stats(&self, secret_id: Ulid, stats: &SecretStats) -> Result<()> {
+        self.ensure_not_failing()?;
+        self.stats
+            .lock()
+            .expect("Failed to acquire lock")
+            .insert(secret_id, stats.clone());
+        Ok(())
+    }
+
+    async fn update_retrieved_at(&self, secret_id: Ulid) -> Result<Option<SecretStats>> {
+        self.ensure_not_failing()?;
+        let mut stats = self.stats.lock().expect("Failed to acquire lock");
+        Ok(stats.get_mut(&secret_id).map(|stat| {
+            stat.retrieved_at = Some(stat.created_at);
+            stat.clone()
+        }))
+    }
+
+    async fn get_all_stats(&self) -> Result<Vec<SecretStats>> {
+        self.ensure_not_failing()?;
+        Ok(self
+            .stats
+            .lock()
+            .expect("Failed to acquire lock")
+            .values()
+            .cloned()
+            .collect())
+    }
+}
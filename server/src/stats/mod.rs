@@ -4,7 +4,17 @@ mod redis_stats_store;
 mod secret_stats;
 mod stats_observer;
 mod stats_store;
+mod stats_summary;
+
+#[cfg(test)]
+mod mock_stats_store;
 
 pub use redis_stats_store::RedisStatsStore;
 pub use stats_observer::StatsObserver;
 pub use stats_store::StatsStore;
+pub use stats_summary::summarize;
+
+#[cfg(test)]
+pub use mock_stats_store::MockStatsStore;
+#[cfg(test)]
+pub use secret_stats::SecretStats;
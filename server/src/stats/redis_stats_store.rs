@@ -5,10 +5,10 @@ use std::time::Duration;
 use anyhow::Result;
 use async_trait::async_trait;
 use redis::AsyncCommands;
-use redis::aio::ConnectionManager;
 use tracing::warn;
 use ulid::Ulid;
 
+use crate::pool::RedisPool;
 use crate::stats::StatsStore;
 
 use super::secret_stats::SecretStats;
@@ -16,23 +16,34 @@ use super::secret_stats::SecretStats;
 /// Stores and retrieves secret statistics using Redis.
 #[derive(Clone)]
 pub struct RedisStatsStore {
-    con: ConnectionManager,
+    con: RedisPool,
     ttl: Duration,
+    key_prefix: String,
 }
 
 impl RedisStatsStore {
     /// Create a new stats observer with a Redis client.
-    pub fn new(con: ConnectionManager, ttl: Duration) -> Self {
-        Self { con, ttl }
+    pub fn new(con: RedisPool, ttl: Duration) -> Self {
+        Self {
+            con,
+            ttl,
+            key_prefix: String::new(),
+        }
+    }
+
+    /// Sets the prefix for all keys, so multiple instances can share one Redis.
+    pub fn with_key_prefix(mut self, key_prefix: &str) -> Self {
+        self.key_prefix = key_prefix.to_string();
+        self
     }
 
-    fn key(secret_id: Ulid) -> String {
-        format!("stats:{}", secret_id)
+    fn key(&self, secret_id: Ulid) -> String {
+        format!("{}stats:{}", self.key_prefix, secret_id)
     }
 
     /// Retrieve the stats for the given secret ID.
     async fn retrieve_stats(&self, secret_id: Ulid) -> Result<Option<SecretStats>> {
-        let key = Self::key(secret_id);
+        let key = self.key(secret_id);
         let value: Option<String> = self.con.clone().get(key).await?;
 
         if let Some(json) = value {
@@ -48,7 +59,7 @@ impl RedisStatsStore {
 impl StatsStore for RedisStatsStore {
     /// Store the stats for the given secret ID.
     async fn store_stats(&self, secret_id: Ulid, stats: &SecretStats) -> Result<()> {
-        let key = Self::key(secret_id);
+        let key = self.key(secret_id);
         let value = serde_json::to_string(stats)?;
 
         let _: () = self
@@ -95,7 +106,7 @@ impl StatsStore for RedisStatsStore {
             let (new_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
                 .cursor_arg(cursor)
                 .arg("MATCH")
-                .arg("stats:*")
+                .arg(format!("{}stats:*", self.key_prefix))
                 .arg("COUNT")
                 .arg(KEYS_PER_SCAN)
                 .query_async(&mut con)
@@ -13,6 +13,9 @@ pub struct SecretStats {
     pub ttl: u64,
     /// Timestamp of when the secret was retrieved, if it has been retrieved
     pub retrieved_at: Option<u64>,
+    /// Size of the encrypted secret in bytes (not recorded for older stats)
+    #[serde(default)]
+    pub size: Option<u64>,
 }
 
 impl SecretStats {
@@ -27,9 +30,15 @@ impl SecretStats {
             created_at,
             ttl,
             retrieved_at: None,
+            size: None,
         }
     }
 
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
     /// Calculates the lifetime of the secret from creation to retrieval.
     pub fn lifetime(&self) -> Option<u64> {
         if let Some(retrieved) = self.retrieved_at {
@@ -59,6 +68,7 @@ mod tests {
             created_at: 100,
             ttl: 200,
             retrieved_at: Some(250),
+            size: None,
         };
 
         assert_eq!(stats.lifetime(), Some(150));
@@ -70,6 +80,7 @@ mod tests {
             created_at: 100,
             ttl: 200,
             retrieved_at: None,
+            size: None,
         };
 
         assert_eq!(stats_no_retrieved.lifetime(), None);
@@ -86,15 +97,25 @@ mod tests {
         assert!(stats.created_at >= current_time)
     }
 
+    #[test]
+    fn test_deserialize_without_size() {
+        let stats: SecretStats =
+            serde_json::from_str(r#"{"created_at":100,"ttl":200,"retrieved_at":null}"#)
+                .expect("Failed to deserialize stats");
+
+        assert_eq!(stats.size, None, "Older stats should not have a size");
+    }
+
     #[test]
     fn test_has_expired_before_ttl() {
         let stats = SecretStats {
             created_at: 100,
             ttl: 200,
             retrieved_at: None,
+            size: None,
         };
 
-        assert_eq!(stats.has_expired(250), false);
+        assert!(!stats.has_expired(250));
     }
 
     #[test]
@@ -103,9 +124,10 @@ mod tests {
             created_at: 100,
             ttl: 200,
             retrieved_at: None,
+            size: None,
         };
 
-        assert_eq!(stats.has_expired(300), true);
+        assert!(stats.has_expired(300));
     }
 
     #[test]
@@ -114,9 +136,10 @@ mod tests {
             created_at: 100,
             ttl: 200,
             retrieved_at: None,
+            size: None,
         };
 
-        assert_eq!(stats.has_expired(301), true);
+        assert!(stats.has_expired(301));
     }
 
     #[test]
@@ -125,8 +148,9 @@ mod tests {
             created_at: 100,
             ttl: 200,
             retrieved_at: Some(250),
+            size: None,
         };
 
-        assert_eq!(stats.has_expired(301), false);
+        assert!(!stats.has_expired(301));
     }
 }
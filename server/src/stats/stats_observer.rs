@@ -46,9 +46,16 @@ impl<T> SecretObserver for StatsObserver<T>
 where
     T: StatsStore + Clone + Send + Sync + 'static,
 {
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
     #[instrument(skip(self, context))]
     async fn on_secret_created(&self, secret_id: Ulid, context: &SecretEventContext) {
-        let stat = SecretStats::new(context.ttl.unwrap_or_default().as_secs());
+        let mut stat = SecretStats::new(context.ttl.unwrap_or_default().as_secs());
+        if let Some(size) = context.size {
+            stat = stat.with_size(size as u64);
+        }
         let store = self.store.clone();
         tokio::spawn(async move {
             if let Err(e) = store.store_stats(secret_id, &stat).await {
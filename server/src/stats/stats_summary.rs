@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use hakanai_lib::models::{DailyStats, SizeBucket, StatsResponse};
+
+use super::secret_stats::SecretStats;
+
+/// Upper bounds of the size histogram buckets in bytes.
+const SIZE_BUCKETS: &[u64] = &[
+    1024,     // 1KB
+    4096,     // 4KB
+    16384,    // 16KB
+    65536,    // 64KB
+    262144,   // 256KB
+    1048576,  // 1MB
+    4194304,  // 4MB
+    10485760, // 10MB
+];
+
+/// Aggregates the per secret stats to totals, daily counts and a size histogram.
+pub fn summarize(stats: &[SecretStats], current_timestamp: u64) -> StatsResponse {
+    let mut summary = StatsResponse::default();
+    let mut days: BTreeMap<String, DailyStats> = BTreeMap::new();
+    let mut size_counts = vec![0u64; SIZE_BUCKETS.len() + 1];
+
+    for stat in stats {
+        day_entry(&mut days, stat.created_at).created += 1;
+
+        if let Some(retrieved_at) = stat.retrieved_at {
+            summary.retrieved_secrets += 1;
+            day_entry(&mut days, retrieved_at).retrieved += 1;
+        } else if stat.has_expired(current_timestamp) {
+            summary.expired_secrets += 1;
+            day_entry(&mut days, stat.created_at.saturating_add(stat.ttl)).expired += 1;
+        } else {
+            summary.active_secrets += 1;
+        }
+
+        if let Some(size) = stat.size {
+            let idx = SIZE_BUCKETS
+                .iter()
+                .position(|max| size <= *max)
+                .unwrap_or(SIZE_BUCKETS.len());
+            size_counts[idx] += 1;
+        }
+    }
+
+    summary.days = days.into_values().collect();
+    summary.size_histogram = size_counts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, count)| SizeBucket {
+            max_size: SIZE_BUCKETS.get(idx).copied(),
+            count,
+        })
+        .collect();
+    summary
+}
+
+fn day_entry(days: &mut BTreeMap<String, DailyStats>, timestamp: u64) -> &mut DailyStats {
+    let date = format_date(timestamp);
+    days.entry(date.clone()).or_insert_with(|| DailyStats {
+        date,
+        ..Default::default()
+    })
+}
+
+/// Formats the timestamp as UTC date (YYYY-MM-DD).
+fn format_date(timestamp: u64) -> String {
+    let time = UNIX_EPOCH + Duration::from_secs(timestamp);
+    humantime::format_rfc3339_seconds(time).to_string()[..10].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86400;
+    const JAN_1_2025: u64 = 1735689600;
+
+    fn stat(created_at: u64, ttl: u64, retrieved_at: Option<u64>) -> SecretStats {
+        SecretStats {
+            created_at,
+            ttl,
+            retrieved_at,
+            size: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_totals() {
+        let stats = vec![
+            stat(JAN_1_2025, DAY, Some(JAN_1_2025 + 60)),
+            stat(JAN_1_2025, 60, None),
+            stat(JAN_1_2025 + DAY, DAY, None),
+        ];
+
+        let summary = summarize(&stats, JAN_1_2025 + DAY + 1);
+        assert_eq!(summary.retrieved_secrets, 1);
+        assert_eq!(summary.expired_secrets, 1);
+        assert_eq!(summary.active_secrets, 1);
+    }
+
+    #[test]
+    fn test_summarize_days() {
+        let stats = vec![
+            stat(JAN_1_2025, DAY, Some(JAN_1_2025 + DAY + 10)),
+            stat(JAN_1_2025 + 100, 60, None),
+        ];
+
+        let summary = summarize(&stats, JAN_1_2025 + 2 * DAY);
+        assert_eq!(
+            summary.days,
+            vec![
+                DailyStats {
+                    date: "2025-01-01".to_string(),
+                    created: 2,
+                    retrieved: 0,
+                    expired: 1,
+                },
+                DailyStats {
+                    date: "2025-01-02".to_string(),
+                    created: 0,
+                    retrieved: 1,
+                    expired: 0,
+                },
+            ],
+            "Days should be ordered by date"
+        );
+    }
+
+    #[test]
+    fn test_summarize_size_histogram() {
+        let stats = vec![
+            stat(JAN_1_2025, DAY, None).with_size(100),
+            stat(JAN_1_2025, DAY, None).with_size(1024),
+            stat(JAN_1_2025, DAY, None).with_size(2000),
+            stat(JAN_1_2025, DAY, None).with_size(20 * 1024 * 1024),
+            stat(JAN_1_2025, DAY, None),
+        ];
+
+        let summary = summarize(&stats, JAN_1_2025);
+        assert_eq!(summary.size_histogram.len(), SIZE_BUCKETS.len() + 1);
+        assert_eq!(summary.size_histogram[0].count, 2, "Bounds are inclusive");
+        assert_eq!(summary.size_histogram[1].count, 1);
+
+        let larger = summary.size_histogram.last().expect("Missing last bucket");
+        assert_eq!(larger.max_size, None);
+        assert_eq!(larger.count, 1);
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        let summary = summarize(&[], JAN_1_2025);
+        assert_eq!(summary.active_secrets, 0);
+        assert!(summary.days.is_empty());
+        assert!(summary.size_histogram.iter().all(|b| b.count == 0));
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(JAN_1_2025), "2025-01-01");
+        assert_eq!(format_date(JAN_1_2025 + DAY - 1), "2025-01-01");
+        assert_eq!(format_date(0), "1970-01-01");
+    }
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tenants sharing one server instance.
+//!
+//! Tenants are identified by hostname or path prefix and have their own token namespace,
+//! size limits, TTL cap and branding. They are defined in a YAML file (`--tenants-file`),
+//! requests matching no tenant are served by the default configuration.
+
+mod tenant_config;
+mod tenant_resolver;
+
+pub use tenant_config::{Tenant, load_tenants};
+pub use tenant_resolver::{TenantMatch, TenantResolver};
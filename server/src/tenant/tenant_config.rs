@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use hakanai_lib::utils::human_duration;
+
+use crate::options::parse_size_limit_bytes;
+
+/// A tenant served by this instance.
+///
+/// Limits and the TTL cap only lower the limits of the instance, unset settings fall back to the
+/// configuration of the instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tenant {
+    /// Unique identifier, used for the token namespace and in events
+    pub id: String,
+
+    /// Hostnames (lowercase, without port) the tenant is served on
+    pub hosts: Vec<String>,
+
+    /// Path prefix (e.g. `/team-a`) the tenant is served on
+    pub path_prefix: Option<String>,
+
+    /// Upload size limit in bytes
+    pub upload_size_limit: Option<usize>,
+
+    /// Upload size limit for anonymous users in bytes
+    pub anonymous_upload_size_limit: Option<usize>,
+
+    /// Whether anonymous users are allowed to create secrets
+    pub allow_anonymous: Option<bool>,
+
+    /// Maximum TTL for secrets
+    pub max_ttl: Option<Duration>,
+
+    /// Directory with custom assets (logo, CSS, `branding.json`)
+    pub custom_assets_dir: Option<PathBuf>,
+}
+
+impl Tenant {
+    /// Returns the Redis key prefix of the token namespace of the tenant.
+    pub fn redis_key_prefix(&self, instance_prefix: &str) -> String {
+        format!("{instance_prefix}tenant:{}:", self.id)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TenantsFile {
+    tenants: Vec<TenantEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TenantEntry {
+    id: String,
+    #[serde(default)]
+    hosts: Vec<String>,
+    path_prefix: Option<String>,
+    upload_size_limit: Option<String>,
+    anonymous_upload_size_limit: Option<String>,
+    allow_anonymous: Option<bool>,
+    max_ttl: Option<String>,
+    custom_assets_dir: Option<PathBuf>,
+}
+
+impl TryFrom<TenantEntry> for Tenant {
+    type Error = String;
+
+    fn try_from(entry: TenantEntry) -> Result<Self, Self::Error> {
+        let id = entry.id;
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "invalid tenant id '{id}': only lowercase letters, digits, '-' and '_' are allowed"
+            ));
+        }
+
+        if entry.hosts.is_empty() && entry.path_prefix.is_none() {
+            return Err(format!(
+                "tenant '{id}' needs at least one host or a path prefix"
+            ));
+        }
+
+        if let Some(prefix) = &entry.path_prefix
+            && !is_valid_path_prefix(prefix)
+        {
+            return Err(format!(
+                "invalid path prefix '{prefix}' of tenant '{id}': must start with '/' and must not end with '/'"
+            ));
+        }
+
+        let size_limit = |limit: Option<String>| {
+            limit
+                .map(|limit| parse_size_limit_bytes(&limit))
+                .transpose()
+                .map_err(|e| format!("invalid size limit of tenant '{id}': {e}"))
+        };
+        let upload_size_limit = size_limit(entry.upload_size_limit)?;
+        let anonymous_upload_size_limit = size_limit(entry.anonymous_upload_size_limit)?;
+
+        let max_ttl = entry
+            .max_ttl
+            .map(|ttl| human_duration::parse(&ttl))
+            .transpose()
+            .map_err(|e| format!("invalid max TTL of tenant '{id}': {e}"))?;
+
+        Ok(Self {
+            hosts: entry.hosts.iter().map(|host| host.to_lowercase()).collect(),
+            path_prefix: entry.path_prefix,
+            upload_size_limit,
+            anonymous_upload_size_limit,
+            allow_anonymous: entry.allow_anonymous,
+            max_ttl,
+            custom_assets_dir: entry.custom_assets_dir,
+            id,
+        })
+    }
+}
+
+fn is_valid_path_prefix(prefix: &str) -> bool {
+    prefix.len() > 1
+        && prefix.starts_with('/')
+        && !prefix.ends_with('/')
+        && !prefix.contains(['?', '#', ' '])
+}
+
+/// Reads and validates the tenants file.
+pub fn load_tenants(path: &Path) -> Result<Vec<Tenant>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read tenants file {}: {e}", path.display()))?;
+    parse_tenants(&content).map_err(|e| format!("invalid tenants file {}: {e}", path.display()))
+}
+
+fn parse_tenants(content: &str) -> Result<Vec<Tenant>, String> {
+    let file: TenantsFile = serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+    let tenants = file
+        .tenants
+        .into_iter()
+        .map(Tenant::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut ids = HashSet::new();
+    let mut hosts = HashSet::new();
+    let mut prefixes = HashSet::new();
+    for tenant in &tenants {
+        if !ids.insert(&tenant.id) {
+            return Err(format!("duplicate tenant id '{}'", tenant.id));
+        }
+
+        if let Some(host) = tenant.hosts.iter().find(|host| !hosts.insert(*host)) {
+            return Err(format!("host '{host}' is used by multiple tenants"));
+        }
+
+        if let Some(prefix) = &tenant.path_prefix
+            && !prefixes.insert(prefix)
+        {
+            return Err(format!(
+                "path prefix '{prefix}' is used by multiple tenants"
+            ));
+        }
+    }
+
+    Ok(tenants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_tenants() -> Result<(), String> {
+        let tenants = parse_tenants(
+            r#"
+tenants:
+  - id: team-a
+    hosts: [Secrets.Team-A.example.com]
+    upload_size_limit: 1m
+    anonymous_upload_size_limit: 16k
+    allow_anonymous: true
+    max_ttl: 1d
+    custom_assets_dir: /etc/hakanai/team-a
+  - id: team-b
+    path_prefix: /team-b
+"#,
+        )?;
+
+        assert_eq!(
+            tenants,
+            vec![
+                Tenant {
+                    id: "team-a".to_string(),
+                    hosts: vec!["secrets.team-a.example.com".to_string()],
+                    path_prefix: None,
+                    upload_size_limit: Some(1024 * 1024),
+                    anonymous_upload_size_limit: Some(16 * 1024),
+                    allow_anonymous: Some(true),
+                    max_ttl: Some(Duration::from_secs(86400)),
+                    custom_assets_dir: Some(PathBuf::from("/etc/hakanai/team-a")),
+                },
+                Tenant {
+                    id: "team-b".to_string(),
+                    hosts: vec![],
+                    path_prefix: Some("/team-b".to_string()),
+                    upload_size_limit: None,
+                    anonymous_upload_size_limit: None,
+                    allow_anonymous: None,
+                    max_ttl: None,
+                    custom_assets_dir: None,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tenants_invalid() {
+        let invalid = [
+            (
+                "tenants:\n  - id: Team A\n    hosts: [a.example.com]",
+                "invalid id",
+            ),
+            ("tenants:\n  - id: team-a", "missing host and path prefix"),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: team-a",
+                "relative path prefix",
+            ),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: /team-a/",
+                "trailing slash",
+            ),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: /",
+                "root path prefix",
+            ),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: /a\n    max_ttl: forever",
+                "invalid TTL",
+            ),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: /a\n    upload_size_limit: huge",
+                "invalid size",
+            ),
+            (
+                "tenants:\n  - id: team-a\n    path_prefix: /a\n    unknown: 1",
+                "unknown setting",
+            ),
+            (
+                "tenants:\n  - id: a\n    path_prefix: /a\n  - id: a\n    path_prefix: /b",
+                "duplicate id",
+            ),
+            (
+                "tenants:\n  - id: a\n    hosts: [x.example.com]\n  - id: b\n    hosts: [X.example.com]",
+                "duplicate host",
+            ),
+            (
+                "tenants:\n  - id: a\n    path_prefix: /a\n  - id: b\n    path_prefix: /a",
+                "duplicate prefix",
+            ),
+        ];
+
+        for (content, reason) in invalid {
+            assert!(
+                parse_tenants(content).is_err(),
+                "Tenants should be rejected: {reason}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_tenants_from_file() -> Result<(), String> {
+        let mut file = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+        write!(file, "tenants:\n  - id: team-a\n    path_prefix: /team-a")
+            .map_err(|e| e.to_string())?;
+
+        let tenants = load_tenants(file.path())?;
+        assert_eq!(tenants.len(), 1);
+        assert!(load_tenants(Path::new("/nonexistent/tenants.yaml")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_redis_key_prefix() -> Result<(), String> {
+        let tenants = parse_tenants("tenants:\n  - id: team-a\n    path_prefix: /team-a")?;
+
+        assert_eq!(tenants[0].redis_key_prefix(""), "tenant:team-a:");
+        assert_eq!(
+            tenants[0].redis_key_prefix("hakanai:"),
+            "hakanai:tenant:team-a:"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use super::Tenant;
+
+/// Tenant a request was matched to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TenantMatch {
+    /// Matched by the hostname of the request
+    Host(Arc<Tenant>),
+
+    /// Matched by the path prefix, which has to be removed before routing
+    PathPrefix(Arc<Tenant>),
+}
+
+impl TenantMatch {
+    pub fn tenant(&self) -> &Arc<Tenant> {
+        match self {
+            TenantMatch::Host(tenant) | TenantMatch::PathPrefix(tenant) => tenant,
+        }
+    }
+}
+
+/// Resolves the tenant of a request by its hostname or path.
+#[derive(Clone, Debug, Default)]
+pub struct TenantResolver {
+    tenants: Vec<Arc<Tenant>>,
+}
+
+impl TenantResolver {
+    pub fn new(tenants: Vec<Arc<Tenant>>) -> Self {
+        Self { tenants }
+    }
+
+    /// Resolves the tenant, hostnames take precedence over path prefixes.
+    ///
+    /// `host` may contain a port. Path prefixes only match complete path segments, the longest
+    /// matching prefix wins.
+    pub fn resolve(&self, host: &str, path: &str) -> Option<TenantMatch> {
+        if self.tenants.is_empty() {
+            return None;
+        }
+
+        let host = strip_port(host).to_lowercase();
+        if let Some(tenant) = self
+            .tenants
+            .iter()
+            .find(|tenant| tenant.hosts.contains(&host))
+        {
+            return Some(TenantMatch::Host(tenant.clone()));
+        }
+
+        self.tenants
+            .iter()
+            .filter_map(|tenant| {
+                let prefix = tenant.path_prefix.as_deref()?;
+                matches_prefix(path, prefix).then_some((prefix.len(), tenant))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, tenant)| TenantMatch::PathPrefix(tenant.clone()))
+    }
+}
+
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        // IPv6 address, e.g. [::1]:8080
+        return host.find(']').map_or(host, |end| &host[..=end]);
+    }
+
+    host.rsplit_once(':').map_or(host, |(host, _)| host)
+}
+
+fn matches_prefix(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tenant(id: &str, hosts: &[&str], path_prefix: Option<&str>) -> Arc<Tenant> {
+        Arc::new(Tenant {
+            id: id.to_string(),
+            hosts: hosts.iter().map(|host| host.to_string()).collect(),
+            path_prefix: path_prefix.map(str::to_string),
+            upload_size_limit: None,
+            anonymous_upload_size_limit: None,
+            allow_anonymous: None,
+            max_ttl: None,
+            custom_assets_dir: None,
+        })
+    }
+
+    fn resolve(resolver: &TenantResolver, host: &str, path: &str) -> Option<String> {
+        resolver
+            .resolve(host, path)
+            .map(|tenant_match| tenant_match.tenant().id.clone())
+    }
+
+    #[test]
+    fn test_resolve_by_host() {
+        let resolver = TenantResolver::new(vec![tenant("team-a", &["a.example.com"], None)]);
+
+        assert_eq!(
+            resolve(&resolver, "a.example.com", "/"),
+            Some("team-a".to_string())
+        );
+        assert_eq!(
+            resolve(&resolver, "A.Example.com:8443", "/api/v1/secret"),
+            Some("team-a".to_string()),
+            "Host should match case-insensitively and without port"
+        );
+        assert_eq!(resolve(&resolver, "b.example.com", "/"), None);
+    }
+
+    #[test]
+    fn test_resolve_by_path_prefix() {
+        let resolver = TenantResolver::new(vec![
+            tenant("team", &[], Some("/team")),
+            tenant("team-a", &[], Some("/team/a")),
+        ]);
+
+        assert_eq!(
+            resolver.resolve("example.com", "/team/api/v1/secret"),
+            Some(TenantMatch::PathPrefix(tenant("team", &[], Some("/team"))))
+        );
+        assert_eq!(
+            resolve(&resolver, "example.com", "/team/a/api/v1/secret"),
+            Some("team-a".to_string()),
+            "Longest prefix should win"
+        );
+        assert_eq!(
+            resolve(&resolver, "example.com", "/team"),
+            Some("team".to_string())
+        );
+        assert_eq!(
+            resolve(&resolver, "example.com", "/teams/api"),
+            None,
+            "Prefix should only match complete path segments"
+        );
+    }
+
+    #[test]
+    fn test_resolve_host_before_path_prefix() {
+        let resolver = TenantResolver::new(vec![
+            tenant("team-a", &[], Some("/team-a")),
+            tenant("team-b", &["b.example.com"], None),
+        ]);
+
+        assert_eq!(
+            resolver.resolve("b.example.com", "/team-a/api"),
+            Some(TenantMatch::Host(tenant(
+                "team-b",
+                &["b.example.com"],
+                None
+            )))
+        );
+    }
+
+    #[test]
+    fn test_strip_port() {
+        assert_eq!(strip_port("example.com"), "example.com");
+        assert_eq!(strip_port("example.com:8080"), "example.com");
+        assert_eq!(strip_port("[::1]:8080"), "[::1]");
+        assert_eq!(strip_port("[::1]"), "[::1]");
+    }
+}
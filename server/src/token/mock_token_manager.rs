@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 
-use super::{TokenCreator, TokenData, TokenError, TokenValidator};
+use super::{TokenCreator, TokenData, TokenError, TokenInfo, TokenValidator};
 
 /// Mock implementation of TokenValidator and TokenCreator traits with builder pattern.
 ///
@@ -19,10 +19,14 @@ pub struct MockTokenManager {
     user_tokens: Arc<Mutex<HashMap<String, TokenData>>>,
     /// Valid admin tokens
     admin_tokens: Arc<Mutex<Vec<String>>>,
+    /// One-time tokens which have already been used
+    exhausted_tokens: Arc<Mutex<Vec<String>>>,
     /// Whether token creation should fail
     creation_should_fail: Arc<Mutex<bool>>,
     /// Token to return on successful creation
     created_token: Arc<Mutex<String>>,
+    /// Valid admin sessions
+    admin_sessions: Arc<Mutex<Vec<String>>>,
 }
 
 impl MockTokenManager {
@@ -31,8 +35,10 @@ impl MockTokenManager {
         Self {
             user_tokens: Arc::new(Mutex::new(HashMap::new())),
             admin_tokens: Arc::new(Mutex::new(Vec::new())),
+            exhausted_tokens: Arc::new(Mutex::new(Vec::new())),
             creation_should_fail: Arc::new(Mutex::new(false)),
             created_token: Arc::new(Mutex::new("mock_token".to_string())),
+            admin_sessions: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -45,6 +51,10 @@ impl MockTokenManager {
         self.admin_tokens.lock().expect("Failed to acquire lock")
     }
 
+    fn get_admin_sessions_mut(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.admin_sessions.lock().expect("Failed to acquire lock")
+    }
+
     fn creation_should_fail(&self) -> bool {
         *self
             .creation_should_fail
@@ -76,12 +86,27 @@ impl MockTokenManager {
         self
     }
 
+    /// Add a one-time token which has already been used
+    pub fn with_exhausted_token(self, token: &str) -> Self {
+        self.exhausted_tokens
+            .lock()
+            .expect("Failed to acquire lock")
+            .push(token.to_string());
+        self
+    }
+
     /// Add a valid admin token
     pub fn with_admin_token(self, token: &str) -> Self {
         self.get_admin_tokens_mut().push(token.to_string());
         self
     }
 
+    /// Add a valid admin session
+    pub fn with_admin_session(self, session: &str) -> Self {
+        self.get_admin_sessions_mut().push(session.to_string());
+        self
+    }
+
     /// Configure token creation to fail
     pub fn with_creation_failure(self) -> Self {
         self.set_creation_should_fail(true);
@@ -133,6 +158,14 @@ impl Default for MockTokenManager {
 #[async_trait]
 impl TokenValidator for MockTokenManager {
     async fn validate_user_token(&self, token: &str) -> Result<TokenData, TokenError> {
+        let exhausted_tokens = self
+            .exhausted_tokens
+            .lock()
+            .expect("Failed to acquire lock");
+        if exhausted_tokens.contains(&token.to_string()) {
+            return Err(TokenError::TokenExhausted);
+        }
+
         let user_tokens = self.get_user_tokens_mut();
         if let Some(data) = user_tokens.get(token) {
             Ok(data.clone())
@@ -141,6 +174,14 @@ impl TokenValidator for MockTokenManager {
         }
     }
 
+    async fn inspect_user_token(&self, token: &str) -> Result<TokenInfo, TokenError> {
+        let data = self.validate_user_token(token).await?;
+        Ok(TokenInfo {
+            data,
+            expires_in: None,
+        })
+    }
+
     async fn validate_admin_token(&self, token: &str) -> Result<(), TokenError> {
         let admin_tokens = self.get_admin_tokens_mut();
         if admin_tokens.contains(&token.to_string()) {
@@ -149,6 +190,14 @@ impl TokenValidator for MockTokenManager {
             Err(TokenError::InvalidToken)
         }
     }
+
+    async fn validate_admin_session(&self, session: &str) -> Result<(), TokenError> {
+        if self.get_admin_sessions_mut().contains(&session.to_string()) {
+            Ok(())
+        } else {
+            Err(TokenError::InvalidToken)
+        }
+    }
 }
 
 #[async_trait]
@@ -164,6 +213,30 @@ impl TokenCreator for MockTokenManager {
             Ok(self.get_created_token())
         }
     }
+
+    async fn user_token_count(&self) -> Result<usize, TokenError> {
+        Ok(self.get_user_tokens_mut().len())
+    }
+
+    async fn create_admin_session(
+        &self,
+        admin_token: &str,
+        _ttl: Duration,
+    ) -> Result<String, TokenError> {
+        self.validate_admin_token(admin_token).await?;
+        if self.creation_should_fail() {
+            return Err(TokenError::Custom("Mock creation failure".to_string()));
+        }
+
+        let session = format!("session_{}", self.get_admin_sessions_mut().len());
+        self.get_admin_sessions_mut().push(session.clone());
+        Ok(session)
+    }
+
+    async fn revoke_admin_session(&self, session: &str) -> Result<(), TokenError> {
+        self.get_admin_sessions_mut().retain(|s| s != session);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
 
-use super::{TokenData, TokenError, TokenStore};
+use super::{TokenData, TokenError, TokenInfo, TokenStore};
 
 /// Mock implementation of TokenStore trait with builder pattern.
 ///
@@ -23,6 +23,12 @@ pub struct MockTokenStore {
     admin_token: Arc<Mutex<Option<String>>>,
     /// Stored tokens for retrieval
     stored_tokens: Arc<Mutex<HashMap<String, TokenData>>>,
+    /// TTLs of the stored tokens
+    token_ttls: Arc<Mutex<HashMap<String, Duration>>>,
+    /// Hashes of one-time tokens which have already been used
+    used_tokens: Arc<Mutex<HashSet<String>>>,
+    /// Admin sessions mapped to the admin token hash they were created with
+    admin_sessions: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl MockTokenStore {
@@ -32,6 +38,9 @@ impl MockTokenStore {
             should_fail: Arc::new(Mutex::new(false)),
             admin_token: Arc::new(Mutex::new(None)),
             stored_tokens: Arc::new(Mutex::new(HashMap::new())),
+            token_ttls: Arc::new(Mutex::new(HashMap::new())),
+            used_tokens: Arc::new(Mutex::new(HashSet::new())),
+            admin_sessions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -60,6 +69,18 @@ impl MockTokenStore {
         self.stored_tokens.lock().expect("Failed to acquire lock")
     }
 
+    fn get_token_ttls_mut(&self) -> std::sync::MutexGuard<'_, HashMap<String, Duration>> {
+        self.token_ttls.lock().expect("Failed to acquire lock")
+    }
+
+    fn get_used_tokens_mut(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.used_tokens.lock().expect("Failed to acquire lock")
+    }
+
+    fn get_admin_sessions_mut(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.admin_sessions.lock().expect("Failed to acquire lock")
+    }
+
     /// Configure operations to fail
     pub fn with_failures(self) -> Self {
         self.set_should_fail_internal(true);
@@ -97,13 +118,39 @@ impl TokenStore for MockTokenStore {
         if self.should_fail() {
             return Err(TokenError::Custom("Mock failure".to_string()));
         }
-        Ok(self.get_stored_tokens_mut().get(token_hash).cloned())
+
+        let mut stored_tokens = self.get_stored_tokens_mut();
+        match stored_tokens.get(token_hash).cloned() {
+            Some(data) if data.one_time => {
+                stored_tokens.remove(token_hash);
+                self.get_used_tokens_mut().insert(token_hash.to_string());
+                Ok(Some(data))
+            }
+            Some(data) => Ok(Some(data)),
+            None if self.get_used_tokens_mut().contains(token_hash) => {
+                Err(TokenError::TokenExhausted)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn inspect_token(&self, token_hash: &str) -> Result<Option<TokenInfo>, TokenError> {
+        if self.should_fail() {
+            return Err(TokenError::Custom("Mock failure".to_string()));
+        }
+        let data = self.get_stored_tokens_mut().get(token_hash).cloned();
+        if data.is_none() && self.get_used_tokens_mut().contains(token_hash) {
+            return Err(TokenError::TokenExhausted);
+        }
+
+        let expires_in = self.get_token_ttls_mut().get(token_hash).copied();
+        Ok(data.map(|data| TokenInfo { data, expires_in }))
     }
 
     async fn store_token(
         &self,
         token_hash: &str,
-        _ttl: Duration,
+        ttl: Duration,
         token_data: TokenData,
     ) -> Result<(), TokenError> {
         if self.should_fail() {
@@ -111,6 +158,8 @@ impl TokenStore for MockTokenStore {
         }
         self.get_stored_tokens_mut()
             .insert(token_hash.to_string(), token_data);
+        self.get_token_ttls_mut()
+            .insert(token_hash.to_string(), ttl);
         Ok(())
     }
 
@@ -119,6 +168,7 @@ impl TokenStore for MockTokenStore {
             return Err(TokenError::Custom("Mock failure".to_string()));
         }
         self.get_stored_tokens_mut().clear();
+        self.get_token_ttls_mut().clear();
         Ok(())
     }
 
@@ -150,6 +200,35 @@ impl TokenStore for MockTokenStore {
         }
         Ok(self.get_stored_tokens_mut().len())
     }
+
+    async fn store_admin_session(
+        &self,
+        session_hash: &str,
+        admin_token_hash: &str,
+        _ttl: Duration,
+    ) -> Result<(), TokenError> {
+        if self.should_fail() {
+            return Err(TokenError::Custom("Mock failure".to_string()));
+        }
+        self.get_admin_sessions_mut()
+            .insert(session_hash.to_string(), admin_token_hash.to_string());
+        Ok(())
+    }
+
+    async fn get_admin_session(&self, session_hash: &str) -> Result<Option<String>, TokenError> {
+        if self.should_fail() {
+            return Err(TokenError::Custom("Mock failure".to_string()));
+        }
+        Ok(self.get_admin_sessions_mut().get(session_hash).cloned())
+    }
+
+    async fn delete_admin_session(&self, session_hash: &str) -> Result<(), TokenError> {
+        if self.should_fail() {
+            return Err(TokenError::Custom("Mock failure".to_string()));
+        }
+        self.get_admin_sessions_mut().remove(session_hash);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -9,6 +9,7 @@ mod redis_token_store;
 mod token_creator;
 mod token_data;
 mod token_error;
+mod token_info;
 mod token_manager;
 mod token_store;
 mod token_validator;
@@ -22,6 +23,7 @@ pub use redis_token_store::RedisTokenStore;
 pub use token_creator::TokenCreator;
 pub use token_data::TokenData;
 pub use token_error::TokenError;
+pub use token_info::TokenInfo;
 pub use token_manager::TokenManager;
 pub use token_store::TokenStore;
 pub use token_validator::TokenValidator;
@@ -1,65 +1,143 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::LazyLock;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use redis::AsyncCommands;
-use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, Script};
 use tracing::instrument;
 
-use super::{TokenData, TokenError, TokenStore};
+use super::{TokenData, TokenError, TokenInfo, TokenStore};
+use crate::pool::RedisPool;
 
 const ADMIN_TOKEN_KEY: &str = "admin_token";
 const TOKEN_PREFIX: &str = "token:";
+const USED_TOKEN_PREFIX: &str = "token_used:";
+const ADMIN_SESSION_PREFIX: &str = "admin_session:";
+
+/// Fallback TTL in seconds for the marker of used one-time tokens without expiry.
+const USED_TOKEN_MARKER_TTL: u64 = 86400;
+
+/// Reads a token and deletes it in the same step if it is a one-time token, so concurrent
+/// requests cannot use a one-time token twice. A marker with the remaining TTL is left behind
+/// to tell exhausted one-time tokens apart from unknown ones.
+///
+/// Returns `{"found", data}`, `{"exhausted"}` or an empty table if the token is unknown.
+static CONSUME_TOKEN_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+local value = redis.call('GET', KEYS[1])
+if not value then
+  if redis.call('EXISTS', KEYS[2]) == 1 then
+    return {'exhausted'}
+  end
+  return {}
+end
+
+local ok, data = pcall(cjson.decode, value)
+if ok and type(data) == 'table' and data['one_time'] == true then
+  local ttl = redis.call('PTTL', KEYS[1])
+  redis.call('DEL', KEYS[1])
+  if ttl > 0 then
+    redis.call('SET', KEYS[2], '1', 'PX', ttl)
+  else
+    redis.call('SET', KEYS[2], '1', 'EX', ARGV[1])
+  end
+end
+
+return {'found', value}
+"#,
+    )
+});
 
 /// An implementation of the `TokenStore` trait that uses Redis as its backend.
 #[derive(Clone)]
 pub struct RedisTokenStore {
-    con: ConnectionManager,
+    con: RedisPool,
+    key_prefix: String,
 }
 
 impl RedisTokenStore {
-    pub fn new(con: ConnectionManager) -> Self {
-        Self { con }
+    pub fn new(con: RedisPool) -> Self {
+        Self {
+            con,
+            key_prefix: String::new(),
+        }
+    }
+
+    /// Sets the prefix for all keys, so multiple instances can share one Redis.
+    pub fn with_key_prefix(mut self, key_prefix: &str) -> Self {
+        self.key_prefix = key_prefix.to_string();
+        self
     }
 }
 
 impl RedisTokenStore {
     fn token_key(&self, hash: &str) -> String {
-        format!("{TOKEN_PREFIX}{hash}")
+        format!("{}{TOKEN_PREFIX}{hash}", self.key_prefix)
     }
 
-    async fn delete_if_one_time(
-        &self,
-        key: &str,
-        token_data: &TokenData,
-    ) -> Result<(), TokenError> {
-        if !token_data.one_time {
-            return Ok(());
-        }
+    fn used_token_key(&self, hash: &str) -> String {
+        format!("{}{USED_TOKEN_PREFIX}{hash}", self.key_prefix)
+    }
 
-        let _: () = self.con.clone().del(key).await?;
-        Ok(())
+    fn token_pattern(&self) -> String {
+        format!("{}{TOKEN_PREFIX}*", self.key_prefix)
+    }
+
+    fn admin_token_key(&self) -> String {
+        format!("{}{ADMIN_TOKEN_KEY}", self.key_prefix)
+    }
+
+    fn admin_session_key(&self, hash: &str) -> String {
+        format!("{}{ADMIN_SESSION_PREFIX}{hash}", self.key_prefix)
+    }
+
+    async fn is_used_one_time_token(&self, hash: &str) -> Result<bool, TokenError> {
+        let exists: bool = self.con.clone().exists(self.used_token_key(hash)).await?;
+        Ok(exists)
     }
 }
 
 #[async_trait]
 impl TokenStore for RedisTokenStore {
-    #[instrument(skip(self), err)]
+    #[instrument(skip(self, token_hash), err)]
     async fn get_token(&self, token_hash: &str) -> Result<Option<TokenData>, TokenError> {
+        let result: Vec<String> = CONSUME_TOKEN_SCRIPT
+            .key(self.token_key(token_hash))
+            .key(self.used_token_key(token_hash))
+            .arg(USED_TOKEN_MARKER_TTL)
+            .invoke_async(&mut self.con.clone())
+            .await?;
+
+        match result.as_slice() {
+            [status, data] if status == "found" => Ok(Some(TokenData::deserialize(data)?)),
+            [status] if status == "exhausted" => Err(TokenError::TokenExhausted),
+            _ => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, token_hash), err)]
+    async fn inspect_token(&self, token_hash: &str) -> Result<Option<TokenInfo>, TokenError> {
         let key = self.token_key(token_hash);
         let value: Option<String> = self.con.clone().get(&key).await?;
 
-        if let Some(data) = value {
-            let token_data = TokenData::deserialize(&data)?;
-            self.delete_if_one_time(&key, &token_data).await?;
-            return Ok(Some(token_data));
-        }
-
-        Ok(None)
+        let Some(data) = value else {
+            if self.is_used_one_time_token(token_hash).await? {
+                return Err(TokenError::TokenExhausted);
+            }
+            return Ok(None);
+        };
+
+        // TTL returns -1 for keys without expiry and -2 if the key vanished in between
+        let ttl: i64 = self.con.clone().ttl(&key).await?;
+        Ok(Some(TokenInfo {
+            data: TokenData::deserialize(&data)?,
+            expires_in: u64::try_from(ttl).ok().map(Duration::from_secs),
+        }))
     }
 
-    #[instrument(skip(self), err)]
+    #[instrument(skip(self, token_hash), err)]
     async fn store_token(
         &self,
         token_hash: &str,
@@ -74,7 +152,7 @@ impl TokenStore for RedisTokenStore {
 
     #[instrument(skip(self), err)]
     async fn clear_all_user_tokens(&self) -> Result<(), TokenError> {
-        let keys: Vec<String> = self.con.clone().keys(format!("{TOKEN_PREFIX}*")).await?;
+        let keys: Vec<String> = self.con.clone().keys(self.token_pattern()).await?;
         if !keys.is_empty() {
             let _: () = self.con.clone().del(keys).await?;
         }
@@ -83,25 +161,65 @@ impl TokenStore for RedisTokenStore {
 
     #[instrument(skip(self), err)]
     async fn admin_token_exists(&self) -> Result<bool, TokenError> {
-        let exists: bool = self.con.clone().exists(ADMIN_TOKEN_KEY).await?;
+        let exists: bool = self.con.clone().exists(self.admin_token_key()).await?;
         Ok(exists)
     }
 
     #[instrument(skip(self), err)]
     async fn get_admin_token(&self) -> Result<Option<String>, TokenError> {
-        let value: Option<String> = self.con.clone().get(ADMIN_TOKEN_KEY).await?;
+        let value: Option<String> = self.con.clone().get(self.admin_token_key()).await?;
         Ok(value)
     }
 
-    #[instrument(skip(self), err)]
+    #[instrument(skip(self, token_hash), err)]
     async fn store_admin_token(&self, token_hash: &str) -> Result<(), TokenError> {
-        let _: () = self.con.clone().set(ADMIN_TOKEN_KEY, token_hash).await?;
+        let _: () = self
+            .con
+            .clone()
+            .set(self.admin_token_key(), token_hash)
+            .await?;
         Ok(())
     }
 
     #[instrument(skip(self), err)]
     async fn user_token_count(&self) -> Result<usize, TokenError> {
-        let keys: Vec<String> = self.con.clone().keys(format!("{TOKEN_PREFIX}*")).await?;
+        let keys: Vec<String> = self.con.clone().keys(self.token_pattern()).await?;
         Ok(keys.len())
     }
+
+    #[instrument(skip(self, session_hash, admin_token_hash), err)]
+    async fn store_admin_session(
+        &self,
+        session_hash: &str,
+        admin_token_hash: &str,
+        ttl: Duration,
+    ) -> Result<(), TokenError> {
+        let key = self.admin_session_key(session_hash);
+        let _: () = self
+            .con
+            .clone()
+            .set_ex(key, admin_token_hash, ttl.as_secs())
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, session_hash), err)]
+    async fn get_admin_session(&self, session_hash: &str) -> Result<Option<String>, TokenError> {
+        let value: Option<String> = self
+            .con
+            .clone()
+            .get(self.admin_session_key(session_hash))
+            .await?;
+        Ok(value)
+    }
+
+    #[instrument(skip(self, session_hash), err)]
+    async fn delete_admin_session(&self, session_hash: &str) -> Result<(), TokenError> {
+        let _: () = self
+            .con
+            .clone()
+            .del(self.admin_session_key(session_hash))
+            .await?;
+        Ok(())
+    }
 }
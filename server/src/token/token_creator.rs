@@ -12,4 +12,17 @@ pub trait TokenCreator: Send + Sync {
         token_data: TokenData,
         ttl: Duration,
     ) -> Result<String, TokenError>;
+
+    /// Get the number of active user tokens.
+    async fn user_token_count(&self) -> Result<usize, TokenError>;
+
+    /// Create an admin session for a valid admin token, returns the session ID.
+    async fn create_admin_session(
+        &self,
+        admin_token: &str,
+        ttl: Duration,
+    ) -> Result<String, TokenError>;
+
+    /// Revoke an admin session before it expires.
+    async fn revoke_admin_session(&self, session: &str) -> Result<(), TokenError>;
 }
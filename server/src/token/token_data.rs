@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use hakanai_lib::models::TokenScope;
+
 /// Token metadata stored in Redis.
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
 pub struct TokenData {
@@ -11,6 +13,10 @@ pub struct TokenData {
     /// Wether the token is one-time use.
     #[serde(default)]
     pub one_time: bool,
+
+    /// Operations and restrictions the token is scoped to.
+    #[serde(default)]
+    pub scope: TokenScope,
 }
 
 impl TokenData {
@@ -32,14 +38,18 @@ impl TokenData {
         self.upload_size_limit = Some(upload_size_limit);
         self
     }
+
+    #[cfg(test)]
+    pub fn with_scope(mut self, scope: TokenScope) -> Self {
+        self.scope = scope;
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use anyhow::Result;
-    use serde_json;
-    use tokio;
 
     #[tokio::test]
     async fn test_token_data_serialization() -> Result<()> {
@@ -64,4 +74,15 @@ mod tests {
         assert_eq!(deserialized.upload_size_limit, None);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_token_data_without_scope() -> Result<()> {
+        let deserialized =
+            TokenData::deserialize(r#"{"upload_size_limit":null,"one_time":false}"#)?;
+        assert!(
+            deserialized.scope.is_unrestricted(),
+            "Tokens stored before scopes were introduced should be unrestricted"
+        );
+        Ok(())
+    }
 }
@@ -18,4 +18,8 @@ pub enum TokenError {
 
     #[error("token is invalid or expired")]
     InvalidToken,
+
+    /// One-time token which has already been used.
+    #[error("one-time token has already been used")]
+    TokenExhausted,
 }
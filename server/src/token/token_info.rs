@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use super::TokenData;
+
+/// Token metadata together with its remaining lifetime.
+#[derive(Clone, Debug, Default)]
+pub struct TokenInfo {
+    /// The metadata stored for the token.
+    pub data: TokenData,
+
+    /// Remaining lifetime of the token, `None` if it does not expire.
+    pub expires_in: Option<Duration>,
+}
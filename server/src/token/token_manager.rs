@@ -6,7 +6,7 @@ use rand::TryRng;
 
 use hakanai_lib::utils::hashing;
 
-use super::{TokenCreator, TokenData, TokenError, TokenStore, TokenValidator};
+use super::{TokenCreator, TokenData, TokenError, TokenInfo, TokenStore, TokenValidator};
 
 const DEFAULT_TOKEN_TTL: u64 = 60 * 60 * 24 * 365; // 1 year in seconds
 
@@ -109,6 +109,35 @@ impl<T: TokenStore> TokenCreator for TokenManager<T> {
 
         Ok(token)
     }
+
+    /// Get the number of active user tokens.
+    async fn user_token_count(&self) -> Result<usize, TokenError> {
+        self.token_store.user_token_count().await
+    }
+
+    /// Create an admin session for a valid admin token, returns the session ID.
+    async fn create_admin_session(
+        &self,
+        admin_token: &str,
+        ttl: Duration,
+    ) -> Result<String, TokenError> {
+        self.validate_admin_token(admin_token).await?;
+
+        let session = Self::generate_token()?;
+        let session_hash = hashing::sha256_hex_from_string(&session);
+        let admin_token_hash = hashing::sha256_hex_from_string(admin_token);
+        self.token_store
+            .store_admin_session(&session_hash, &admin_token_hash, ttl)
+            .await?;
+
+        Ok(session)
+    }
+
+    /// Revoke an admin session before it expires.
+    async fn revoke_admin_session(&self, session: &str) -> Result<(), TokenError> {
+        let session_hash = hashing::sha256_hex_from_string(session);
+        self.token_store.delete_admin_session(&session_hash).await
+    }
 }
 
 #[async_trait]
@@ -123,6 +152,16 @@ impl<T: TokenStore> TokenValidator for TokenManager<T> {
         }
     }
 
+    /// Return metadata and remaining lifetime of a user token without using it.
+    async fn inspect_user_token(&self, token: &str) -> Result<TokenInfo, TokenError> {
+        let token_hash = hashing::sha256_hex_from_string(token);
+
+        match self.token_store.inspect_token(&token_hash).await? {
+            Some(token_info) => Ok(token_info),
+            None => Err(TokenError::InvalidToken),
+        }
+    }
+
     /// Validate admin token.
     async fn validate_admin_token(&self, token: &str) -> Result<(), TokenError> {
         let token_hash = hashing::sha256_hex_from_string(token);
@@ -132,6 +171,20 @@ impl<T: TokenStore> TokenValidator for TokenManager<T> {
             _ => Err(TokenError::InvalidToken),
         }
     }
+
+    /// Validate admin session, sessions of a replaced admin token are invalid.
+    async fn validate_admin_session(&self, session: &str) -> Result<(), TokenError> {
+        let session_hash = hashing::sha256_hex_from_string(session);
+        let Some(session_token_hash) = self.token_store.get_admin_session(&session_hash).await?
+        else {
+            return Err(TokenError::InvalidToken);
+        };
+
+        match self.token_store.get_admin_token().await? {
+            Some(stored_hash) if stored_hash == session_token_hash => Ok(()),
+            _ => Err(TokenError::InvalidToken),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -339,4 +392,136 @@ mod tests {
         assert!(result.is_none());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_user_token_count() -> Result<()> {
+        let mock_store = MockTokenStore::new()
+            .with_stored_token("hash1", TokenData::default())
+            .with_stored_token("hash2", TokenData::default());
+        let manager = TokenManager::new(mock_store);
+
+        let count = TokenCreator::user_token_count(&manager).await?;
+        assert_eq!(count, 2, "Count should include all stored user tokens");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_inspect_user_token() -> Result<()> {
+        let mock_store = MockTokenStore::new();
+        let manager = TokenManager::new(mock_store);
+
+        let token_data = TokenData {
+            one_time: true,
+            ..Default::default()
+        };
+        let token = manager
+            .create_user_token(token_data, Duration::from_secs(3600))
+            .await?;
+
+        let info = manager.inspect_user_token(&token).await?;
+        assert!(info.data.one_time, "Token data should be returned");
+        assert_eq!(info.expires_in, Some(Duration::from_secs(3600)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_inspect_user_token_not_found() {
+        let manager = TokenManager::new(MockTokenStore::new());
+
+        let result = manager.inspect_user_token("unknown").await;
+        assert!(
+            matches!(result, Err(TokenError::InvalidToken)),
+            "Expected InvalidToken, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_one_time_token_can_only_be_used_once() -> Result<()> {
+        let manager = TokenManager::new(MockTokenStore::new());
+
+        let token_data = TokenData {
+            one_time: true,
+            ..Default::default()
+        };
+        let token = manager
+            .create_user_token(token_data, Duration::from_secs(3600))
+            .await?;
+
+        manager.validate_user_token(&token).await?;
+
+        let result = manager.validate_user_token(&token).await;
+        assert!(
+            matches!(result, Err(TokenError::TokenExhausted)),
+            "Expected TokenExhausted on second use, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_inspect_does_not_consume_one_time_token() -> Result<()> {
+        let manager = TokenManager::new(MockTokenStore::new());
+
+        let token_data = TokenData {
+            one_time: true,
+            ..Default::default()
+        };
+        let token = manager
+            .create_user_token(token_data, Duration::from_secs(3600))
+            .await?;
+
+        manager.inspect_user_token(&token).await?;
+        manager.validate_user_token(&token).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_session() -> Result<()> {
+        let manager = TokenManager::new(MockTokenStore::new());
+        let admin_token = manager.create_admin_token().await?;
+
+        let session = manager
+            .create_admin_session(&admin_token, Duration::from_secs(3600))
+            .await?;
+        assert_ne!(session, admin_token, "Session should differ from the token");
+        manager.validate_admin_session(&session).await?;
+
+        manager.revoke_admin_session(&session).await?;
+        let result = manager.validate_admin_session(&session).await;
+        assert!(
+            matches!(result, Err(TokenError::InvalidToken)),
+            "Expected InvalidToken after revocation, got: {result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_session_requires_admin_token() {
+        let manager = TokenManager::new(MockTokenStore::new().with_admin_token("admin_hash"));
+
+        let result = manager
+            .create_admin_session("wrong", Duration::from_secs(3600))
+            .await;
+        assert!(
+            matches!(result, Err(TokenError::InvalidToken)),
+            "Expected InvalidToken, got: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_session_invalid_after_token_rotation() -> Result<()> {
+        let manager = TokenManager::new(MockTokenStore::new());
+        let admin_token = manager.create_admin_token().await?;
+        let session = manager
+            .create_admin_session(&admin_token, Duration::from_secs(3600))
+            .await?;
+
+        manager.create_admin_token().await?;
+
+        let result = manager.validate_admin_session(&session).await;
+        assert!(
+            matches!(result, Err(TokenError::InvalidToken)),
+            "Sessions of a replaced admin token should be invalid, got: {result:?}"
+        );
+        Ok(())
+    }
 }
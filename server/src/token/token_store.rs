@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 
-use super::{TokenData, TokenError};
+use super::{TokenData, TokenError, TokenInfo};
 
 /// Abstraction for token storage operations.
 #[async_trait]
@@ -12,6 +12,9 @@ pub trait TokenStore: Send + Sync {
     /// Gets token metadata by its hash.
     async fn get_token(&self, token_hash: &str) -> Result<Option<TokenData>, TokenError>;
 
+    /// Gets token metadata and remaining TTL by its hash without consuming one-time tokens.
+    async fn inspect_token(&self, token_hash: &str) -> Result<Option<TokenInfo>, TokenError>;
+
     /// Store token with metadata.
     async fn store_token(
         &self,
@@ -34,4 +37,19 @@ pub trait TokenStore: Send + Sync {
 
     /// Count the number of active user tokens.
     async fn user_token_count(&self) -> Result<usize, TokenError>;
+
+    /// Store an admin session by its hash together with the hash of the admin token it was
+    /// created with.
+    async fn store_admin_session(
+        &self,
+        session_hash: &str,
+        admin_token_hash: &str,
+        ttl: Duration,
+    ) -> Result<(), TokenError>;
+
+    /// Get the hash of the admin token an admin session was created with.
+    async fn get_admin_session(&self, session_hash: &str) -> Result<Option<String>, TokenError>;
+
+    /// Delete an admin session.
+    async fn delete_admin_session(&self, session_hash: &str) -> Result<(), TokenError>;
 }
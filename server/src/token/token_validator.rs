@@ -2,13 +2,19 @@
 
 use async_trait::async_trait;
 
-use crate::token::{TokenData, TokenError};
+use crate::token::{TokenData, TokenError, TokenInfo};
 
 #[async_trait]
 pub trait TokenValidator: Send + Sync {
     /// Validate token and return metadata.
     async fn validate_user_token(&self, token: &str) -> Result<TokenData, TokenError>;
 
+    /// Return metadata and remaining lifetime of a user token without using it.
+    async fn inspect_user_token(&self, token: &str) -> Result<TokenInfo, TokenError>;
+
     /// Validate admin token.
     async fn validate_admin_token(&self, token: &str) -> Result<(), TokenError>;
+
+    /// Validate admin session, sessions of a replaced admin token are invalid.
+    async fn validate_admin_session(&self, session: &str) -> Result<(), TokenError>;
 }
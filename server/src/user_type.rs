@@ -6,6 +6,8 @@ pub enum UserType {
     Anonymous,
     Authenticated,
     Whitelisted,
+    /// Authenticated by an external identity provider (OIDC)
+    Identity,
 }
 
 impl std::fmt::Display for UserType {
@@ -14,6 +16,7 @@ impl std::fmt::Display for UserType {
             UserType::Anonymous => write!(f, "anonymous"),
             UserType::Authenticated => write!(f, "authenticated"),
             UserType::Whitelisted => write!(f, "whitelisted"),
+            UserType::Identity => write!(f, "identity"),
         }
     }
 }
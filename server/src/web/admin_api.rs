@@ -1,24 +1,120 @@
 // SPDX-License-Identifier: Apache-2.0
 
-//! Admin API endpoints for token management.
+//! Admin API endpoints for token management and statistics.
 //!
-//! Provides REST endpoints for administrative operations like creating user tokens.
-//! All endpoints require admin token authentication.
+//! Provides REST endpoints for administrative operations like creating user tokens,
+//! inspecting aggregated secret statistics, toggling the maintenance mode, reloading the
+//! configuration or purging stored secrets.
+//! All endpoints require admin token authentication. Admins of tenants can only manage the
+//! tokens of their tenant, endpoints affecting the whole instance are rejected for them.
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use actix_web::{HttpResponse, Result, web};
-use tracing::info;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::{HttpRequest, HttpResponse, Result, middleware, web};
+use serde::Deserialize;
+use tracing::{error, info, warn};
 
-use hakanai_lib::models::{CreateTokenRequest, CreateTokenResponse};
+use hakanai_lib::models::{
+    CreateTokenRequest, CreateTokenResponse, MaintenanceStatus, PurgePreviewResponse,
+    PurgeResponse, TokenCountResponse,
+};
 
-use super::admin_user::AdminUser;
+use super::admin_user::{AdminUser, SESSION_COOKIE_NAME};
 use super::app_data::AppData;
+use super::csrf;
+use super::purge_confirmation::{self, CONFIRMATION_TTL};
+use crate::stats;
 use crate::token::TokenData;
 
 /// Configure admin API routes
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/admin").route("/tokens", web::post().to(create_token)));
+    cfg.service(
+        web::scope("/admin")
+            .wrap(middleware::from_fn(csrf::protect))
+            .route("/login", web::post().to(login))
+            .route("/logout", web::post().to(logout))
+            .route("/tokens", web::post().to(create_token))
+            .route("/tokens/count", web::get().to(get_token_count))
+            .route("/stats", web::get().to(get_stats))
+            .route("/maintenance", web::get().to(get_maintenance))
+            .route("/maintenance", web::put().to(set_maintenance))
+            .route("/reload", web::post().to(reload_config))
+            .route("/secrets", web::delete().to(purge_secrets)),
+    );
+}
+
+/// Exchange the admin token for a session of the dashboard
+///
+/// POST /api/v1/admin/login
+///
+/// Requires admin authentication via Authorization header.
+/// Sets a short-lived session cookie (HttpOnly, SameSite=Strict), so the dashboard doesn't have
+/// to keep the admin token in the browser.
+pub async fn login(admin_user: AdminUser, app_data: web::Data<AppData>) -> Result<HttpResponse> {
+    if admin_user.is_session() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Login requires the admin token"
+        })));
+    }
+
+    let ttl = app_data.admin_session_ttl;
+    let session = match app_data
+        .token_creator
+        .create_admin_session(admin_user.token(), ttl)
+        .await
+    {
+        Ok(session) => session,
+        Err(e) => {
+            error!("Failed to create admin session: {e}");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create admin session"
+            })));
+        }
+    };
+
+    info!("Admin logged in, session expires in {}s", ttl.as_secs());
+
+    Ok(HttpResponse::NoContent()
+        .cookie(session_cookie(session, ttl))
+        .finish())
+}
+
+/// End the session of the dashboard
+///
+/// POST /api/v1/admin/logout
+///
+/// Requires admin authentication via session cookie or Authorization header.
+/// Revokes the session and removes the cookie.
+pub async fn logout(admin_user: AdminUser, app_data: web::Data<AppData>) -> Result<HttpResponse> {
+    if admin_user.is_session()
+        && let Err(e) = app_data
+            .token_creator
+            .revoke_admin_session(admin_user.token())
+            .await
+    {
+        error!("Failed to revoke admin session: {e}");
+        return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "Failed to revoke admin session"
+        })));
+    }
+
+    let mut cookie = session_cookie(String::new(), Duration::ZERO);
+    cookie.make_removal();
+
+    Ok(HttpResponse::NoContent().cookie(cookie).finish())
+}
+
+fn session_cookie(session: String, ttl: Duration) -> Cookie<'static> {
+    Cookie::build(SESSION_COOKIE_NAME, session)
+        .path("/")
+        .secure(true)
+        .http_only(true)
+        .same_site(SameSite::Strict)
+        .max_age(actix_web::cookie::time::Duration::seconds(
+            i64::try_from(ttl.as_secs()).unwrap_or(i64::MAX),
+        ))
+        .finish()
 }
 
 /// Create a new user token
@@ -37,6 +133,7 @@ pub async fn create_token(
     let mut token_data = TokenData::new();
     token_data.upload_size_limit = request.upload_size_limit;
     token_data.one_time = request.one_time;
+    token_data.scope = request.scope.clone();
 
     let ttl_seconds = request.ttl_seconds;
     let ttl = Duration::from_secs(ttl_seconds);
@@ -61,6 +158,263 @@ pub async fn create_token(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Get the number of active user tokens
+///
+/// GET /api/v1/admin/tokens/count
+///
+/// Requires admin authentication via Authorization header.
+pub async fn get_token_count(
+    admin_user: AdminUser,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    let _ = admin_user; // Ensure admin user is authenticated
+
+    match app_data.token_creator.user_token_count().await {
+        Ok(user_tokens) => Ok(HttpResponse::Ok().json(TokenCountResponse { user_tokens })),
+        Err(e) => {
+            error!("Failed to count user tokens: {e}");
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to count user tokens"
+            })))
+        }
+    }
+}
+
+/// Get aggregated secret statistics
+///
+/// GET /api/v1/admin/stats
+///
+/// Requires admin authentication via Authorization header.
+/// Returns totals, daily counts and a size histogram of the stats retained by the server.
+pub async fn get_stats(
+    admin_user: AdminUser,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    let _ = admin_user; // Ensure admin user is authenticated
+    if let Some(response) = reject_tenant_admin(&app_data) {
+        return Ok(response);
+    }
+
+    let all_stats = match app_data.stats_store.get_all_stats().await {
+        Ok(stats) => stats,
+        Err(e) => {
+            error!("Failed to retrieve stats: {e}");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to retrieve stats"
+            })));
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    Ok(HttpResponse::Ok().json(stats::summarize(&all_stats, now)))
+}
+
+/// Get the maintenance mode of this instance
+///
+/// GET /api/v1/admin/maintenance
+///
+/// Requires admin authentication via Authorization header.
+pub async fn get_maintenance(
+    admin_user: AdminUser,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    let _ = admin_user; // Ensure admin user is authenticated
+    if let Some(response) = reject_tenant_admin(&app_data) {
+        return Ok(response);
+    }
+
+    Ok(HttpResponse::Ok().json(MaintenanceStatus {
+        enabled: app_data.maintenance.is_enabled(),
+    }))
+}
+
+/// Enable or disable the maintenance mode of this instance
+///
+/// PUT /api/v1/admin/maintenance
+///
+/// Requires admin authentication via Authorization header.
+/// In maintenance mode new secrets are rejected with 503, while existing secrets can still be
+/// retrieved. The mode is not persisted and only affects the instance receiving the request.
+pub async fn set_maintenance(
+    admin_user: AdminUser,
+    request: web::Json<MaintenanceStatus>,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    let _ = admin_user; // Ensure admin user is authenticated
+    if let Some(response) = reject_tenant_admin(&app_data) {
+        return Ok(response);
+    }
+
+    app_data.maintenance.set_enabled(request.enabled);
+    if request.enabled {
+        warn!("Admin enabled maintenance mode, new secrets are rejected");
+    } else {
+        info!("Admin disabled maintenance mode");
+    }
+
+    Ok(HttpResponse::Ok().json(request.into_inner()))
+}
+
+/// Reload the configuration of this instance
+///
+/// POST /api/v1/admin/reload
+///
+/// Requires admin authentication via Authorization header.
+/// Re-reads the impressum and privacy files and the config file, same as sending SIGHUP. The
+/// previous configuration is kept if the new one is invalid.
+pub async fn reload_config(
+    admin_user: AdminUser,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    let _ = admin_user; // Ensure admin user is authenticated
+    if let Some(response) = reject_tenant_admin(&app_data) {
+        return Ok(response);
+    }
+
+    let Some(reloader) = &app_data.config_reloader else {
+        return Ok(HttpResponse::NotImplemented().json(serde_json::json!({
+            "error": "Configuration reload is not supported"
+        })));
+    };
+
+    match reloader.reload() {
+        Ok(()) => {
+            info!("Admin reloaded configuration");
+            Ok(HttpResponse::NoContent().finish())
+        }
+        Err(e) => {
+            error!("Failed to reload configuration: {e}");
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to reload configuration: {e}")
+            })))
+        }
+    }
+}
+
+/// Rejects admins of tenants from endpoints affecting the whole instance.
+fn reject_tenant_admin(app_data: &AppData) -> Option<HttpResponse> {
+    app_data.tenant.as_ref().map(|_| {
+        HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Not available for tenants"
+        }))
+    })
+}
+
+/// Query parameters for purging secrets
+#[derive(Debug, Deserialize)]
+pub struct PurgeQuery {
+    /// Only purge secrets created longer ago than this duration (e.g. `24h`), all if not set
+    older_than: Option<String>,
+    /// Confirmation token returned by the preview request
+    confirm: Option<String>,
+}
+
+/// Purge stored secrets, e.g. in response to an incident
+///
+/// DELETE /api/v1/admin/secrets?older_than=...&confirm=...
+///
+/// Requires admin authentication via Authorization header.
+/// Without `confirm` nothing is deleted: the response contains the number of affected secrets
+/// and a confirmation token, which has to be sent with a second request within
+/// `CONFIRMATION_TTL` to actually delete the secrets.
+pub async fn purge_secrets(
+    admin_user: AdminUser,
+    query: web::Query<PurgeQuery>,
+    http_req: HttpRequest,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    if let Some(response) = reject_tenant_admin(&app_data) {
+        return Ok(response);
+    }
+
+    let older_than = match query.older_than.as_deref().map(humantime::parse_duration) {
+        Some(Ok(duration)) => Some(duration),
+        Some(Err(e)) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("Invalid older_than: {e}")
+            })));
+        }
+        None => None,
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let Some(confirm) = query.confirm.as_deref() else {
+        return preview_purge(&admin_user, older_than, now, &app_data).await;
+    };
+
+    if !purge_confirmation::verify(admin_user.token(), older_than, confirm, now) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid or expired confirmation token"
+        })));
+    }
+
+    let purged = match app_data.secret_store.purge(older_than).await {
+        Ok(purged) => purged,
+        Err(e) => {
+            error!("Failed to purge secrets: {e}");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to purge secrets"
+            })));
+        }
+    };
+
+    warn!(
+        purged,
+        older_than = older_than.map(|d| humantime::format_duration(d).to_string()),
+        "Admin purged stored secrets"
+    );
+    app_data.observer_manager.notify_secrets_purged(
+        purged,
+        older_than,
+        &app_data.event_context(&http_req),
+    );
+
+    Ok(HttpResponse::Ok().json(PurgeResponse {
+        purged: purged as u64,
+    }))
+}
+
+async fn preview_purge(
+    admin_user: &AdminUser,
+    older_than: Option<Duration>,
+    now: u64,
+    app_data: &AppData,
+) -> Result<HttpResponse> {
+    let secrets = match app_data.secret_store.count_purgeable(older_than).await {
+        Ok(count) => count,
+        Err(e) => {
+            error!("Failed to count secrets to purge: {e}");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to count secrets to purge"
+            })));
+        }
+    };
+
+    let confirmation_token = match purge_confirmation::create(admin_user.token(), older_than, now) {
+        Ok(token) => token,
+        Err(e) => {
+            error!("Failed to create purge confirmation: {e}");
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create purge confirmation"
+            })));
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(PurgePreviewResponse {
+        secrets: secrets as u64,
+        confirmation_token,
+        expires_in_seconds: CONFIRMATION_TTL.as_secs(),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,10 +422,16 @@ mod tests {
 
     use actix_web::{App, test, web};
 
+    use hakanai_lib::models::StatsResponse;
     use hakanai_lib::utils::test::MustParse;
+    use ulid::Ulid;
 
-    use crate::token::MockTokenManager;
+    use crate::observer::MockObserver;
+    use crate::secret::{MockSecretStore, SecretStore};
+    use crate::stats::{MockStatsStore, SecretStats};
+    use crate::token::{MockTokenManager, TokenValidator};
     use crate::web::app_data::{AnonymousOptions, AppData};
+    use crate::web::maintenance::MaintenanceMode;
 
     fn create_test_app_data(token_manager: MockTokenManager) -> AppData {
         // Configure with localhost trusted IP for tests
@@ -433,6 +793,7 @@ mod tests {
             upload_size_limit: Some(1024),
             ttl_seconds: 3600,
             one_time: false,
+            scope: Default::default(),
         };
 
         let req = test::TestRequest::post()
@@ -569,4 +930,668 @@ mod tests {
         let resp = test::call_service(&app, req).await;
         assert_eq!(resp.status(), 200);
     }
+
+    #[actix_web::test]
+    async fn test_get_stats_success() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let stats_store = MockStatsStore::new()
+            .with_stats(Ulid::r#gen(), SecretStats::new(3600).with_size(512))
+            .with_stats(Ulid::r#gen(), SecretStats::new(3600));
+
+        let app_data = create_test_app_data(token_manager).with_stats_store(Box::new(stats_store));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/stats")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+
+        let response: StatsResponse = test::read_body_json(resp).await;
+        assert_eq!(response.active_secrets, 2);
+        assert_eq!(response.days.len(), 1);
+        assert_eq!(response.days[0].created, 2);
+        assert_eq!(
+            response.size_histogram[0].count, 1,
+            "Only secrets with size should be counted in histogram"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_get_stats_requires_admin_token() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/stats")
+            .insert_header(("Authorization", "Bearer user_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+    }
+
+    #[actix_web::test]
+    async fn test_get_stats_store_failure() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager)
+            .with_stats_store(Box::new(MockStatsStore::new().with_failure()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/stats")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 500);
+    }
+
+    #[actix_web::test]
+    async fn test_get_token_count() {
+        let token_manager = MockTokenManager::new()
+            .with_admin_token("admin_token")
+            .with_unlimited_user_tokens(&["token1", "token2"]);
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/tokens/count")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+
+        let response: TokenCountResponse = test::read_body_json(resp).await;
+        assert_eq!(response.user_tokens, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_login_sets_session_cookie() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/login")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 204);
+
+        let cookie = resp
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == SESSION_COOKIE_NAME)
+            .expect("Session cookie should be set");
+        assert_ne!(
+            cookie.value(),
+            "admin_token",
+            "Token should not be the session"
+        );
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+        assert_eq!(
+            cookie.max_age(),
+            Some(actix_web::cookie::time::Duration::hours(1))
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_login_invalid_admin_token() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/login")
+            .insert_header(("Authorization", "Bearer wrong_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+        assert_eq!(resp.response().cookies().count(), 0);
+    }
+
+    #[actix_web::test]
+    async fn test_session_authenticates_admin() {
+        let token_manager = MockTokenManager::new()
+            .with_admin_session("valid_session")
+            .with_unlimited_user_tokens(&["token1"]);
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/tokens/count")
+            .cookie(Cookie::new(SESSION_COOKIE_NAME, "valid_session"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/tokens/count")
+            .cookie(Cookie::new(SESSION_COOKIE_NAME, "expired_session"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    #[actix_web::test]
+    async fn test_session_requires_csrf_token() {
+        let token_manager = MockTokenManager::new().with_admin_session("valid_session");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/tokens")
+            .cookie(Cookie::new(SESSION_COOKIE_NAME, "valid_session"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .set_json(CreateTokenRequest::new(3600))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/tokens")
+            .cookie(Cookie::new(SESSION_COOKIE_NAME, "valid_session"))
+            .cookie(Cookie::new(csrf::COOKIE_NAME, "csrf_token"))
+            .insert_header((csrf::HEADER_NAME, "csrf_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .set_json(CreateTokenRequest::new(3600))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[actix_web::test]
+    async fn test_logout_revokes_session() {
+        let token_manager = MockTokenManager::new().with_admin_session("valid_session");
+        let app_data = create_test_app_data(token_manager.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/logout")
+            .cookie(Cookie::new(SESSION_COOKIE_NAME, "valid_session"))
+            .cookie(Cookie::new(csrf::COOKIE_NAME, "csrf_token"))
+            .insert_header((csrf::HEADER_NAME, "csrf_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 204);
+
+        let cookie = resp
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == SESSION_COOKIE_NAME)
+            .expect("Session cookie should be removed");
+        assert!(cookie.value().is_empty(), "Session should be cleared");
+
+        let result = token_manager.validate_admin_session("valid_session").await;
+        assert!(result.is_err(), "Session should be revoked");
+    }
+
+    #[actix_web::test]
+    async fn test_get_token_count_requires_admin_token() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/tokens/count")
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    async fn create_purge_test_store() -> MockSecretStore {
+        let store = MockSecretStore::new();
+        let now = SystemTime::now();
+        for age in [Duration::from_secs(60), Duration::from_secs(7200)] {
+            store
+                .put(
+                    Ulid::from_datetime(now - age),
+                    "ciphertext".to_string(),
+                    Duration::from_secs(3600),
+                )
+                .await
+                .expect("Failed to store secret");
+        }
+        store
+    }
+
+    fn purge_request(uri: &str) -> test::TestRequest {
+        test::TestRequest::delete()
+            .uri(uri)
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+    }
+
+    #[actix_web::test]
+    async fn test_purge_secrets_requires_confirmation() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let store = create_purge_test_store().await;
+        let app_data =
+            create_test_app_data(token_manager).with_secret_store(Box::new(store.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let resp =
+            test::call_service(&app, purge_request("/api/v1/admin/secrets").to_request()).await;
+        assert_eq!(resp.status(), 200);
+
+        let response: PurgePreviewResponse = test::read_body_json(resp).await;
+        assert_eq!(response.secrets, 2, "Preview should count all secrets");
+        assert!(!response.confirmation_token.is_empty());
+        assert_eq!(response.expires_in_seconds, CONFIRMATION_TTL.as_secs());
+        assert_eq!(
+            store.get_stored_ids().len(),
+            2,
+            "Secrets should not be deleted without confirmation"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_purge_secrets_with_confirmation() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let store = create_purge_test_store().await;
+        let observer = MockObserver::new();
+        let mut app_data =
+            create_test_app_data(token_manager).with_secret_store(Box::new(store.clone()));
+        app_data
+            .observer_manager
+            .register_observer(Box::new(observer.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            purge_request("/api/v1/admin/secrets?older_than=1h").to_request(),
+        )
+        .await;
+        let preview: PurgePreviewResponse = test::read_body_json(resp).await;
+        assert_eq!(preview.secrets, 1, "Only the old secret should be counted");
+
+        let uri = format!(
+            "/api/v1/admin/secrets?older_than=1h&confirm={}",
+            preview.confirmation_token
+        );
+        let resp = test::call_service(&app, purge_request(&uri).to_request()).await;
+        assert_eq!(resp.status(), 200);
+
+        let response: PurgeResponse = test::read_body_json(resp).await;
+        assert_eq!(response.purged, 1);
+        assert_eq!(
+            store.get_stored_ids().len(),
+            1,
+            "Secrets newer than older_than should be kept"
+        );
+        observer.wait_for_events(1).await;
+        assert_eq!(
+            observer.get_purged_events(),
+            vec![(1, Some(Duration::from_secs(3600)))],
+            "Purge should be reported as audit event"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_purge_secrets_confirmation_bound_to_criteria() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let store = create_purge_test_store().await;
+        let app_data =
+            create_test_app_data(token_manager).with_secret_store(Box::new(store.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let resp = test::call_service(
+            &app,
+            purge_request("/api/v1/admin/secrets?older_than=1h").to_request(),
+        )
+        .await;
+        let preview: PurgePreviewResponse = test::read_body_json(resp).await;
+
+        let uri = format!(
+            "/api/v1/admin/secrets?confirm={}",
+            preview.confirmation_token
+        );
+        let resp = test::call_service(&app, purge_request(&uri).to_request()).await;
+        assert_eq!(
+            resp.status(),
+            400,
+            "Confirmation for old secrets should not purge all secrets"
+        );
+        assert_eq!(store.get_stored_ids().len(), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_purge_secrets_invalid_parameters() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        for uri in [
+            "/api/v1/admin/secrets?older_than=yesterday",
+            "/api/v1/admin/secrets?confirm=invalid",
+        ] {
+            let resp = test::call_service(&app, purge_request(uri).to_request()).await;
+            assert_eq!(resp.status(), 400, "Request {uri} should be rejected");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_purge_secrets_requires_admin_token() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::delete()
+            .uri("/api/v1/admin/secrets")
+            .insert_header(("Authorization", "Bearer user_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+    }
+
+    #[actix_web::test]
+    async fn test_toggle_maintenance() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let maintenance = MaintenanceMode::new(false);
+        let app_data = create_test_app_data(token_manager).with_maintenance(maintenance.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::put()
+            .uri("/api/v1/admin/maintenance")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .set_json(MaintenanceStatus { enabled: true })
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert!(
+            maintenance.is_enabled(),
+            "Maintenance mode should be enabled"
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/maintenance")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+
+        let response: MaintenanceStatus = test::read_body_json(resp).await;
+        assert!(response.enabled);
+    }
+
+    #[actix_web::test]
+    async fn test_toggle_maintenance_requires_admin_token() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let maintenance = MaintenanceMode::new(false);
+        let app_data = create_test_app_data(token_manager).with_maintenance(maintenance.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::put()
+            .uri("/api/v1/admin/maintenance")
+            .insert_header(("Authorization", "Bearer user_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .set_json(MaintenanceStatus { enabled: true })
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+        assert!(!maintenance.is_enabled());
+    }
+
+    #[actix_web::test]
+    async fn test_reload_config() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        use crate::options::Args;
+        use crate::web::runtime_config::ConfigReloader;
+
+        let mut config_file = tempfile::NamedTempFile::new()?;
+        write!(config_file, "trusted_ip_ranges: [127.0.0.0/8]")?;
+        config_file.flush()?;
+
+        let args = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--config",
+            &config_file.path().to_string_lossy(),
+        ])?;
+        let reloader = ConfigReloader::new(args)?;
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager).with_config_reloader(reloader.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        write!(config_file, "\nupload_size_limit: 1m")?;
+        config_file.flush()?;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/reload")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 204);
+        assert_eq!(
+            reloader.config().load().upload_size_limit,
+            1024 * 1024,
+            "Changed config file should be applied"
+        );
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_reload_config_not_supported() {
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let app_data = create_test_app_data(token_manager);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/admin/reload")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 501);
+    }
+
+    #[actix_web::test]
+    async fn test_instance_endpoints_rejected_for_tenant_admin() {
+        use std::sync::Arc;
+
+        use crate::tenant::Tenant;
+
+        let token_manager = MockTokenManager::new().with_admin_token("admin_token");
+        let tenant = Arc::new(Tenant {
+            id: "team-a".to_string(),
+            hosts: vec!["a.example.com".to_string()],
+            path_prefix: None,
+            upload_size_limit: None,
+            anonymous_upload_size_limit: None,
+            allow_anonymous: None,
+            max_ttl: None,
+            custom_assets_dir: None,
+        });
+        let maintenance = MaintenanceMode::new(false);
+        let app_data = create_test_app_data(token_manager)
+            .with_maintenance(maintenance.clone())
+            .with_tenant(tenant);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let requests = [
+            test::TestRequest::get().uri("/api/v1/admin/stats"),
+            test::TestRequest::put()
+                .uri("/api/v1/admin/maintenance")
+                .set_json(MaintenanceStatus { enabled: true }),
+            test::TestRequest::post().uri("/api/v1/admin/reload"),
+            test::TestRequest::delete().uri("/api/v1/admin/secrets"),
+        ];
+        for request in requests {
+            let req = request
+                .insert_header(("Authorization", "Bearer admin_token"))
+                .insert_header(("x-forwarded-for", "127.0.0.1"))
+                .to_request();
+            let path = req.path().to_string();
+
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(
+                resp.status(),
+                403,
+                "{path} should be rejected for tenant admins"
+            );
+        }
+        assert!(!maintenance.is_enabled());
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/admin/tokens/count")
+            .insert_header(("Authorization", "Bearer admin_token"))
+            .insert_header(("x-forwarded-for", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.status(),
+            200,
+            "Tenant admins should manage the tokens of their tenant"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 
@@ -11,9 +12,36 @@ use super::app_data::AppData;
 use super::filters::is_request_from_whitelisted_ip;
 use crate::token::TokenError;
 
+/// Cookie holding the session of the admin dashboard, see `POST /api/v1/admin/login`.
+pub const SESSION_COOKIE_NAME: &str = "__Host-hakanai-admin";
+
 /// Represents an admin user for administrative operations
-#[derive(Clone, Debug)]
-pub struct AdminUser;
+///
+/// Admins authenticate with the admin token in the `Authorization` header or with the session
+/// cookie of the dashboard. The header takes precedence if both are sent.
+#[derive(Clone)]
+pub struct AdminUser {
+    token: String,
+    session: bool,
+}
+
+impl AdminUser {
+    /// The validated admin token or session of the request, used to sign purge confirmations
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Whether the admin authenticated with the session cookie instead of the admin token
+    pub fn is_session(&self) -> bool {
+        self.session
+    }
+}
+
+impl fmt::Debug for AdminUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminUser").finish_non_exhaustive()
+    }
+}
 
 impl FromRequest for AdminUser {
     type Error = Error;
@@ -24,7 +52,10 @@ impl FromRequest for AdminUser {
 
         Box::pin(async move {
             let app_data = get_app_data(&req)?;
-            let token = extract_admin_token_from_header(&req)?;
+            let (token, is_session) = match extract_session_from_cookie(&req) {
+                Some(session) => (session, true),
+                None => (extract_admin_token_from_header(&req)?, false),
+            };
 
             if !is_request_from_whitelisted_ip(&req, &app_data) {
                 return Err(error::ErrorForbidden(
@@ -32,8 +63,23 @@ impl FromRequest for AdminUser {
                 ));
             }
 
-            match app_data.token_validator.validate_admin_token(&token).await {
-                Ok(()) => Ok(AdminUser),
+            let result = if is_session {
+                app_data
+                    .token_validator
+                    .validate_admin_session(&token)
+                    .await
+            } else {
+                app_data.token_validator.validate_admin_token(&token).await
+            };
+
+            match result {
+                Ok(()) => Ok(AdminUser {
+                    token,
+                    session: is_session,
+                }),
+                Err(TokenError::InvalidToken) if is_session => {
+                    Err(error::ErrorUnauthorized("Admin session expired"))
+                }
                 Err(TokenError::InvalidToken) => Err(error::ErrorForbidden("Invalid admin token")),
                 Err(e) => {
                     warn!("Admin token validation failed: {}", e);
@@ -53,6 +99,17 @@ fn get_app_data(req: &HttpRequest) -> Result<actix_web::web::Data<AppData>, Erro
         .cloned()
 }
 
+/// Extract the session from the cookie, unless the request sends an Authorization header
+fn extract_session_from_cookie(req: &HttpRequest) -> Option<String> {
+    if req.headers().contains_key("Authorization") {
+        return None;
+    }
+
+    req.cookie(SESSION_COOKIE_NAME)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|session| !session.is_empty())
+}
+
 /// Extract and clean the admin token from the Authorization header
 fn extract_admin_token_from_header(req: &HttpRequest) -> Result<String, Error> {
     let auth_header = req
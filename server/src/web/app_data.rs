@@ -1,11 +1,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::observer::ObserverManager;
+use actix_web::HttpRequest;
+use arc_swap::ArcSwap;
+
+use hakanai_lib::models::CountryCode;
+
+use super::filters;
+use super::maintenance::MaintenanceMode;
+use super::rate_limiter::RateLimiter;
+use super::runtime_config::{ConfigReloader, RuntimeConfig};
+use crate::auth::IdentityValidator;
+use crate::metrics::{AccessDenial, EventMetrics};
+use crate::observer::{ClientIpHasher, ObserverManager, PinDelivery, SecretEventContext};
+use crate::options::UiTheme;
+use crate::policy::ContentPolicy;
 use crate::secret::SecretStore;
+use crate::stats::StatsStore;
+use crate::tenant::Tenant;
 use crate::token::{TokenCreator, TokenValidator};
 
+/// TTL presets offered by the clients if not configured (5 minutes up to 7 days).
+pub const DEFAULT_TTL_PRESETS: &[Duration] = &[
+    Duration::from_secs(300),
+    Duration::from_secs(1800),
+    Duration::from_secs(3600),
+    Duration::from_secs(7200),
+    Duration::from_secs(43200),
+    Duration::from_secs(86400),
+    Duration::from_secs(604800),
+];
+
 #[derive(Clone, Debug)]
 pub struct AnonymousOptions {
     pub allowed: bool,
@@ -14,28 +41,56 @@ pub struct AnonymousOptions {
     pub upload_size_limit: usize,
 }
 
+/// Instance-level preferences of the web interface, published via `/config.json`.
+#[derive(Clone, Debug, Default)]
+pub struct UiOptions {
+    /// Theme for users without a saved preference, system preference if not set.
+    pub default_theme: Option<UiTheme>,
+
+    /// Whether to always use the high contrast colors.
+    pub force_high_contrast: bool,
+
+    /// Whether to hide the Hakanai branding in the footer.
+    pub hide_branding: bool,
+}
+
 /// AppData stores the application's shared state.
 pub struct AppData {
     /// The data store for persisting application data.
     pub secret_store: Box<dyn SecretStore>,
 
+    /// The stats store for aggregated statistics in admin API.
+    pub stats_store: Box<dyn StatsStore>,
+
     /// The token validator for authentication.
     pub token_validator: Box<dyn TokenValidator>,
 
     /// The token creator for admin API.
     pub token_creator: Box<dyn TokenCreator>,
 
+    /// The validator for tokens of an external identity provider, if configured.
+    pub identity_validator: Option<Box<dyn IdentityValidator>>,
+
+    /// The minimum time-to-live (TTL) for secrets
+    pub min_ttl: Duration,
+
     /// The maximum time-to-live (TTL) for secrets
     pub max_ttl: Duration,
 
-    /// Defines whether the application can be used without authentication and limits for anonymous users.
-    pub anonymous_usage: AnonymousOptions,
+    /// The TTL presets offered by the clients, use `ttl_presets()` to get the accepted ones.
+    pub ttl_presets: Vec<Duration>,
+
+    /// The policy deciding whether uploads are stored, if configured.
+    pub content_policy: Option<Box<dyn ContentPolicy>>,
+
+    /// Delivers retrieval PINs to their recipients, PINs are not supported if `None`.
+    pub pin_delivery: Option<Arc<dyn PinDelivery>>,
 
-    /// Pre-rendered impressum HTML page (built at startup if configured)
-    pub impressum_html: Option<String>,
+    /// Settings which can be reloaded at runtime, use `runtime_config()` to access them.
+    pub runtime_config: Arc<ArcSwap<RuntimeConfig>>,
 
-    /// Pre-rendered privacy policy HTML page (built at startup if configured)
-    pub privacy_html: Option<String>,
+    /// Reloads the runtime configuration, `None` if reloading is not supported.
+    pub config_reloader: Option<ConfigReloader>,
 
     /// The observer manager for secret lifecycle events.
     pub observer_manager: ObserverManager,
@@ -43,8 +98,8 @@ pub struct AppData {
     /// Whether to show the token input field in the web interface
     pub show_token_input: bool,
 
-    /// IP ranges that bypass size limits
-    pub trusted_ip_ranges: Option<Vec<ipnet::IpNet>>,
+    /// Preferences of the web interface
+    pub ui: UiOptions,
 
     /// HTTP header to check for client IP
     pub trusted_ip_header: String,
@@ -55,43 +110,135 @@ pub struct AppData {
     /// HTTP header to check for client ASN (for geo-restrictions)
     pub asn_header: Option<String>,
 
-    /// The maximum upload size allowed for the server, in bytes.
-    pub upload_size_limit: usize,
-
     /// The time-to-live (TTL) for one-time tokens
     pub one_time_token_ttl: Duration,
+
+    /// The time-to-live (TTL) for sessions of the admin dashboard
+    pub admin_session_ttl: Duration,
+
+    /// Whether short aliases can be minted for secret links
+    pub short_aliases: bool,
+
+    /// Whether new secrets are rejected, toggled at runtime via admin API
+    pub maintenance: MaintenanceMode,
+
+    /// Rate limit of the QR code endpoint, shared by all workers
+    pub qr_code_rate_limiter: RateLimiter,
+
+    /// The tenant served with this data, `None` for the default tenant.
+    pub tenant: Option<Arc<Tenant>>,
+
+    /// Hashes client IPs for events, shared by all workers so hashes are comparable
+    pub client_ip_hasher: ClientIpHasher,
+
+    /// Metrics of secret events, `None` if metrics are disabled
+    pub event_metrics: Option<EventMetrics>,
 }
 
 #[cfg(test)]
 impl Default for AppData {
     fn default() -> Self {
         use crate::secret::MockSecretStore;
+        use crate::stats::MockStatsStore;
         use crate::token::MockTokenManager;
 
         Self {
             secret_store: Box::new(MockSecretStore::new()),
+            stats_store: Box::new(MockStatsStore::new()),
             token_validator: Box::new(MockTokenManager::new()),
             token_creator: Box::new(MockTokenManager::new()),
+            identity_validator: None,
+            content_policy: None,
+            pin_delivery: None,
+            min_ttl: Duration::ZERO,
             max_ttl: Duration::from_secs(86400), // 24 hours
-            anonymous_usage: AnonymousOptions {
-                allowed: false,
-                upload_size_limit: 32 * 1024, // 32KB
-            },
-            impressum_html: None,
-            privacy_html: None,
+            ttl_presets: DEFAULT_TTL_PRESETS.to_vec(),
+            runtime_config: Arc::new(ArcSwap::from_pointee(RuntimeConfig::default())),
+            config_reloader: None,
             observer_manager: ObserverManager::new(),
             show_token_input: false,
-            trusted_ip_ranges: None,
+            ui: UiOptions::default(),
             trusted_ip_header: "x-forwarded-for".to_string(),
             country_header: None,
             asn_header: None,
-            upload_size_limit: 10 * 1024 * 1024,           // 10MB
             one_time_token_ttl: Duration::from_secs(3600), // 1 day
+            admin_session_ttl: Duration::from_secs(3600),
+            short_aliases: false,
+            maintenance: MaintenanceMode::default(),
+            qr_code_rate_limiter: RateLimiter::new(0, Duration::from_secs(60)),
+            tenant: None,
+            client_ip_hasher: ClientIpHasher::default(),
+            event_metrics: None,
         }
     }
 }
 
 impl AppData {
+    /// Returns the TTL presets accepted by the server (all presets between `min_ttl` and
+    /// `max_ttl`).
+    pub fn ttl_presets(&self) -> Vec<Duration> {
+        self.ttl_presets
+            .iter()
+            .filter(|ttl| (self.min_ttl..=self.max_ttl).contains(*ttl))
+            .copied()
+            .collect()
+    }
+
+    /// Returns a snapshot of the current runtime configuration.
+    pub fn runtime_config(&self) -> Arc<RuntimeConfig> {
+        self.runtime_config.load_full()
+    }
+
+    /// Creates the context for events of secrets, associated with the tenant if any.
+    ///
+    /// The client is resolved once here, so observers neither parse headers themselves nor see
+    /// the raw IP: it is only passed as hash, country and ASN only if their headers are
+    /// configured.
+    pub fn event_context(&self, http_req: &HttpRequest) -> SecretEventContext {
+        let mut context = SecretEventContext::new(http_req.headers().clone());
+        if let Some(tenant) = &self.tenant {
+            context = context.with_tenant(tenant.id.clone());
+        }
+
+        if let Some(ip) = filters::client_ip(http_req, self) {
+            context = context.with_client_ip_hash(self.client_ip_hasher.hash(ip));
+        }
+
+        if let Some(country) = self
+            .country_header
+            .as_deref()
+            .and_then(|name| filters::extract_header_value(http_req, name))
+            .and_then(|value| CountryCode::new(&value.to_uppercase()).ok())
+        {
+            context = context.with_country(country);
+        }
+
+        if let Some(asn) = self
+            .asn_header
+            .as_deref()
+            .and_then(|name| filters::extract_header_value(http_req, name))
+            .and_then(|value| value.parse::<u32>().ok())
+        {
+            context = context.with_asn(asn);
+        }
+
+        context
+    }
+
+    /// Counts a denied access to a secret, if metrics are enabled.
+    pub fn record_access_denied(&self, cause: AccessDenial) {
+        if let Some(metrics) = &self.event_metrics {
+            metrics.record_access_denied(cause);
+        }
+    }
+
+    #[cfg(test)]
+    fn update_runtime_config(&self, update: impl FnOnce(&mut RuntimeConfig)) {
+        let mut config = RuntimeConfig::clone(&self.runtime_config.load());
+        update(&mut config);
+        self.runtime_config.store(Arc::new(config));
+    }
+
     /// Builder pattern functions for testing
     #[cfg(test)]
     pub fn with_secret_store(mut self, secret_store: Box<dyn SecretStore>) -> Self {
@@ -99,6 +246,12 @@ impl AppData {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_stats_store(mut self, stats_store: Box<dyn StatsStore>) -> Self {
+        self.stats_store = stats_store;
+        self
+    }
+
     #[cfg(test)]
     pub fn with_token_validator(mut self, token_validator: Box<dyn TokenValidator>) -> Self {
         self.token_validator = token_validator;
@@ -111,6 +264,27 @@ impl AppData {
         self
     }
 
+    #[cfg(test)]
+    pub fn with_identity_validator(
+        mut self,
+        identity_validator: Box<dyn IdentityValidator>,
+    ) -> Self {
+        self.identity_validator = Some(identity_validator);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_content_policy(mut self, content_policy: Box<dyn ContentPolicy>) -> Self {
+        self.content_policy = Some(content_policy);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_pin_delivery(mut self, pin_delivery: Arc<dyn PinDelivery>) -> Self {
+        self.pin_delivery = Some(pin_delivery);
+        self
+    }
+
     #[cfg(test)]
     pub fn with_max_ttl(mut self, max_ttl: Duration) -> Self {
         self.max_ttl = max_ttl;
@@ -118,26 +292,71 @@ impl AppData {
     }
 
     #[cfg(test)]
-    pub fn with_anonymous_usage(mut self, anonymous_usage: AnonymousOptions) -> Self {
-        self.anonymous_usage = anonymous_usage;
+    pub fn with_min_ttl(mut self, min_ttl: Duration) -> Self {
+        self.min_ttl = min_ttl;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_ttl_presets(mut self, ttl_presets: Vec<Duration>) -> Self {
+        self.ttl_presets = ttl_presets;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_anonymous_usage(self, anonymous_usage: AnonymousOptions) -> Self {
+        self.update_runtime_config(|config| config.anonymous_usage = anonymous_usage);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_anonymous_allowed(self, allowed: bool) -> Self {
+        self.update_runtime_config(|config| config.anonymous_usage.allowed = allowed);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_upload_size_limit(self, upload_size_limit: usize) -> Self {
+        self.update_runtime_config(|config| config.upload_size_limit = upload_size_limit);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_token_upload_size_limit(self, limit: Option<usize>) -> Self {
+        self.update_runtime_config(|config| config.token_upload_size_limit = limit);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_whitelisted_upload_size_limit(self, limit: Option<usize>) -> Self {
+        self.update_runtime_config(|config| config.whitelisted_upload_size_limit = limit);
         self
     }
 
     #[cfg(test)]
-    pub fn with_impressum_html(mut self, impressum_html: &str) -> Self {
-        self.impressum_html = Some(impressum_html.to_string());
+    pub fn with_impressum_html(self, impressum_html: &str) -> Self {
+        self.update_runtime_config(|config| {
+            config.impressum_html = Some(impressum_html.to_string())
+        });
         self
     }
 
     #[cfg(test)]
-    pub fn with_privacy_html(mut self, privacy_html: &str) -> Self {
-        self.privacy_html = Some(privacy_html.to_string());
+    pub fn with_privacy_html(self, privacy_html: &str) -> Self {
+        self.update_runtime_config(|config| config.privacy_html = Some(privacy_html.to_string()));
         self
     }
 
     #[cfg(test)]
-    pub fn with_trusted_ip_ranges(mut self, trusted_ip_ranges: Option<Vec<ipnet::IpNet>>) -> Self {
-        self.trusted_ip_ranges = trusted_ip_ranges;
+    pub fn with_trusted_ip_ranges(self, trusted_ip_ranges: Option<Vec<ipnet::IpNet>>) -> Self {
+        self.update_runtime_config(|config| config.trusted_ip_ranges = trusted_ip_ranges);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_config_reloader(mut self, config_reloader: ConfigReloader) -> Self {
+        self.runtime_config = config_reloader.config();
+        self.config_reloader = Some(config_reloader);
         self
     }
 
@@ -158,4 +377,148 @@ impl AppData {
         self.asn_header = asn_header;
         self
     }
+
+    #[cfg(test)]
+    pub fn with_ui(mut self, ui: UiOptions) -> Self {
+        self.ui = ui;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_tenant(mut self, tenant: Arc<Tenant>) -> Self {
+        self.tenant = Some(tenant);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_short_aliases(mut self, short_aliases: bool) -> Self {
+        self.short_aliases = short_aliases;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_qr_code_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.qr_code_rate_limiter = rate_limiter;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_ttl_presets_limited_by_max_ttl() {
+        let app_data = AppData::default().with_max_ttl(Duration::from_secs(7200));
+
+        let presets = app_data.ttl_presets();
+        assert_eq!(
+            presets,
+            vec![
+                Duration::from_secs(300),
+                Duration::from_secs(1800),
+                Duration::from_secs(3600),
+                Duration::from_secs(7200),
+            ],
+            "Presets exceeding max TTL should be omitted"
+        );
+    }
+
+    #[test]
+    fn test_ttl_presets_all_below_max_ttl() {
+        let app_data = AppData::default().with_max_ttl(Duration::from_secs(30 * 86400));
+
+        assert_eq!(
+            app_data.ttl_presets(),
+            DEFAULT_TTL_PRESETS.to_vec(),
+            "All presets should be returned if max TTL is large enough"
+        );
+    }
+
+    #[test]
+    fn test_ttl_presets_configured_and_limited_by_min_ttl() {
+        let app_data = AppData::default()
+            .with_min_ttl(Duration::from_secs(3600))
+            .with_ttl_presets(vec![
+                Duration::from_secs(600),
+                Duration::from_secs(3600),
+                Duration::from_secs(14400),
+            ]);
+
+        assert_eq!(
+            app_data.ttl_presets(),
+            vec![Duration::from_secs(3600), Duration::from_secs(14400)],
+            "Presets below min TTL should be omitted"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_event_context_contains_tenant() {
+        let app_data = AppData::default();
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(app_data.event_context(&req).tenant, None);
+
+        let app_data = app_data.with_tenant(Arc::new(Tenant {
+            id: "team-a".to_string(),
+            hosts: vec!["a.example.com".to_string()],
+            path_prefix: None,
+            upload_size_limit: None,
+            anonymous_upload_size_limit: None,
+            allow_anonymous: None,
+            max_ttl: None,
+            custom_assets_dir: None,
+        }));
+        assert_eq!(
+            app_data.event_context(&req).tenant.as_deref(),
+            Some("team-a"),
+            "Events should be associated with the tenant"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_event_context_contains_client() {
+        let app_data = AppData::default()
+            .with_country_header(Some("x-country".to_string()))
+            .with_asn_header(Some("x-asn".to_string()));
+        let req = TestRequest::default()
+            .insert_header(("x-forwarded-for", "192.168.1.1"))
+            .insert_header(("x-country", "de"))
+            .insert_header(("x-asn", "64512"))
+            .to_http_request();
+
+        let context = app_data.event_context(&req);
+        let client_ip_hash = context.client_ip_hash.expect("IP should be hashed");
+        assert!(
+            !client_ip_hash.contains("192.168.1.1"),
+            "Raw IP should not be passed to observers"
+        );
+        assert_eq!(
+            app_data.event_context(&req).client_ip_hash,
+            Some(client_ip_hash),
+            "Hash should be stable for the same client"
+        );
+        assert_eq!(context.country.as_ref().map(|c| c.as_str()), Some("DE"));
+        assert_eq!(context.asn, Some(64512));
+    }
+
+    #[actix_web::test]
+    async fn test_event_context_without_geo_headers_configured() {
+        let app_data = AppData::default();
+        let req = TestRequest::default()
+            .insert_header(("x-country", "DE"))
+            .insert_header(("x-asn", "64512"))
+            .to_http_request();
+
+        let context = app_data.event_context(&req);
+        assert_eq!(context.country, None, "Country header is not configured");
+        assert_eq!(context.asn, None, "ASN header is not configured");
+    }
 }
@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Double-submit CSRF protection for endpoints authenticated by the browser.
+//!
+//! Safe requests (`GET`, `HEAD`, `OPTIONS`) receive a random token in a `SameSite=Strict` cookie
+//! if they don't carry one yet. State-changing requests have to repeat the token of the cookie in
+//! the `X-CSRF-Token` header. Other sites can neither read the cookie nor set the header, so
+//! forged requests are rejected even if the browser attaches session cookies.
+//!
+//! Requests with an `Authorization` header or without any cookies are not checked, as they carry
+//! no credentials the browser attaches on its own. The protection is opt-in per scope:
+//!
+//! ```ignore
+//! web::scope("/admin").wrap(middleware::from_fn(csrf::protect))
+//! ```
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{Error, error, http};
+use base64::Engine;
+use rand::TryRng;
+use subtle::ConstantTimeEq;
+use tracing::{error, warn};
+
+/// Cookie holding the CSRF token, the `__Host-` prefix binds it to the exact origin.
+pub const COOKIE_NAME: &str = "__Host-hakanai-csrf";
+
+/// Header in which state-changing requests repeat the token of the cookie.
+pub const HEADER_NAME: &str = "X-CSRF-Token";
+
+const TOKEN_SIZE: usize = 32;
+
+/// Middleware function issuing and checking the CSRF token, see the module documentation.
+pub async fn protect(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if is_safe_method(req.method()) {
+        let has_token = req.cookie(COOKIE_NAME).is_some();
+        let mut res = next.call(req).await?.map_into_boxed_body();
+        if !has_token {
+            let cookie = token_cookie(generate_token()?);
+            res.response_mut().add_cookie(&cookie)?;
+        }
+        return Ok(res);
+    }
+
+    if is_browser_authenticated(&req) && !is_token_valid(&req) {
+        warn!(
+            "Rejected {} {} without valid CSRF token",
+            req.method(),
+            req.path()
+        );
+        return Ok(req.error_response(error::ErrorForbidden("Missing or invalid CSRF token")));
+    }
+
+    Ok(next.call(req).await?.map_into_boxed_body())
+}
+
+fn is_safe_method(method: &http::Method) -> bool {
+    matches!(
+        *method,
+        http::Method::GET | http::Method::HEAD | http::Method::OPTIONS
+    )
+}
+
+/// Whether the request may carry credentials attached by the browser instead of the caller.
+fn is_browser_authenticated(req: &ServiceRequest) -> bool {
+    !req.headers().contains_key(http::header::AUTHORIZATION)
+        && req.headers().contains_key(http::header::COOKIE)
+}
+
+/// Compares the token of the header with the token of the cookie in constant time.
+fn is_token_valid(req: &ServiceRequest) -> bool {
+    let Some(cookie) = req.cookie(COOKIE_NAME) else {
+        return false;
+    };
+    let Some(header) = req
+        .headers()
+        .get(HEADER_NAME)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    !cookie.value().is_empty() && bool::from(cookie.value().as_bytes().ct_eq(header.as_bytes()))
+}
+
+fn token_cookie(token: String) -> Cookie<'static> {
+    // readable by scripts, as they have to copy the token into the header
+    Cookie::build(COOKIE_NAME, token)
+        .path("/")
+        .secure(true)
+        .http_only(false)
+        .same_site(SameSite::Strict)
+        .finish()
+}
+
+fn generate_token() -> Result<String, Error> {
+    let mut bytes = [0u8; TOKEN_SIZE];
+    rand::rng().try_fill_bytes(&mut bytes).map_err(|e| {
+        error!("Failed to generate CSRF token: {e}");
+        error::ErrorInternalServerError("Operation failed")
+    })?;
+
+    Ok(base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::Request;
+    use actix_web::dev::Service;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, HttpResponse, test, web};
+
+    async fn create_app()
+    -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+        test::init_service(
+            App::new().service(
+                web::scope("/admin")
+                    .wrap(from_fn(protect))
+                    .route("/page", web::get().to(HttpResponse::Ok))
+                    .route("/action", web::post().to(HttpResponse::Ok)),
+            ),
+        )
+        .await
+    }
+
+    async fn status_of(req: Request) -> u16 {
+        let app = create_app().await;
+        test::call_service(&app, req).await.status().as_u16()
+    }
+
+    #[actix_web::test]
+    async fn test_safe_request_issues_token() {
+        let app = create_app().await;
+
+        let req = test::TestRequest::get().uri("/admin/page").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        let cookie = resp
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == COOKIE_NAME)
+            .expect("CSRF cookie should be set");
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.path(), Some("/"));
+        assert!(!cookie.value().is_empty(), "Token should not be empty");
+    }
+
+    #[actix_web::test]
+    async fn test_safe_request_keeps_existing_token() {
+        let app = create_app().await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin/page")
+            .cookie(Cookie::new(COOKIE_NAME, "existing"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.response().cookies().count(),
+            0,
+            "Existing token should not be replaced"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_with_matching_token() {
+        let req = test::TestRequest::post()
+            .uri("/admin/action")
+            .cookie(Cookie::new(COOKIE_NAME, "token"))
+            .insert_header((HEADER_NAME, "token"))
+            .to_request();
+
+        assert_eq!(status_of(req).await, 200);
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_without_token() {
+        let req = test::TestRequest::post()
+            .uri("/admin/action")
+            .cookie(Cookie::new(COOKIE_NAME, "token"))
+            .to_request();
+
+        assert_eq!(status_of(req).await, 403);
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_with_mismatching_token() {
+        let req = test::TestRequest::post()
+            .uri("/admin/action")
+            .cookie(Cookie::new(COOKIE_NAME, "token"))
+            .insert_header((HEADER_NAME, "other"))
+            .to_request();
+
+        assert_eq!(status_of(req).await, 403);
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_with_empty_token() {
+        let req = test::TestRequest::post()
+            .uri("/admin/action")
+            .cookie(Cookie::new(COOKIE_NAME, ""))
+            .insert_header((HEADER_NAME, ""))
+            .to_request();
+
+        assert_eq!(status_of(req).await, 403);
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_without_cookies() {
+        let req = test::TestRequest::post().uri("/admin/action").to_request();
+
+        assert_eq!(
+            status_of(req).await,
+            200,
+            "Requests without cookies carry no credentials to forge"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_unsafe_request_with_authorization_header() {
+        let req = test::TestRequest::post()
+            .uri("/admin/action")
+            .cookie(Cookie::new("session", "value"))
+            .insert_header((http::header::AUTHORIZATION, "Bearer token"))
+            .to_request();
+
+        assert_eq!(
+            status_of(req).await,
+            200,
+            "Bearer authenticated requests should not require a CSRF token"
+        );
+    }
+}
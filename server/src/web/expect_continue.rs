@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Early rejection of uploads announced with `Expect: 100-continue`.
+//!
+//! Clients sending `Expect: 100-continue` (e.g. curl for larger bodies) wait for the server
+//! before transmitting the body. The HTTP dispatcher calls the expect service of the server
+//! before it writes the interim `100 Continue`. Uploads which would be rejected anyway, because
+//! the caller is not authorized or the announced `Content-Length` exceeds the upload limit of the
+//! caller, are answered with the final error response instead, so such clients never send the
+//! body. The connection is closed afterwards, so the server does not have to drain a body sent
+//! regardless.
+//!
+//! The expect service passes the head of the request to a check app with the data and tenant
+//! routing of the worker, so the caller is authorized exactly like by the upload endpoints. The
+//! authorized user is handed over to the request, so one-time tokens are only used once.
+//!
+//! The service is registered when building the HTTP service of a listener:
+//!
+//! ```ignore
+//! HttpService::build()
+//!     .expect(expect_continue::service(check_app.configure(expect_continue::configure)))
+//!     .finish(app)
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_http::Request;
+use actix_service::{ServiceFactory, apply_fn_factory, map_config};
+use actix_web::body::MessageBody;
+use actix_web::dev::{AppConfig, Service, ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::http::{ConnectionType, header};
+use actix_web::{App, Error, HttpMessage, HttpRequest, HttpResponse, web};
+use tracing::debug;
+
+use hakanai_lib::utils::human_size;
+
+use super::size_limit;
+use super::size_limited_multipart;
+use super::user::User;
+
+/// Builds the expect service checking requests with the check app, see the module
+/// documentation.
+pub fn service<T, B>(
+    check_app: App<T>,
+) -> impl ServiceFactory<Request, Config = (), Response = Request, Error = Error, InitError = ()>
+where
+    T: ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse<B>,
+            Error = Error,
+            InitError = (),
+        > + 'static,
+    B: MessageBody + 'static,
+{
+    // the check app never generates URLs, the host and scheme of the listener are not needed
+    let check_app = map_config(check_app, |()| AppConfig::default());
+    apply_fn_factory(check_app, check_request)
+}
+
+/// Registers the checks of the upload endpoints, other requests are continued.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/v1")
+            .route("/secret", web::post().to(check_upload))
+            .route("/secret/raw", web::post().to(check_upload))
+            .route("/secret/multipart", web::post().to(check_upload)),
+    )
+    .default_service(web::to(HttpResponse::Ok));
+}
+
+fn check_request<S, B>(
+    req: Request,
+    check_app: &S,
+) -> Pin<Box<dyn Future<Output = Result<Request, Error>>>>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    let mut head_only = Request::new();
+    *head_only.head_mut() = req.head().clone();
+    let checked = check_app.call(head_only);
+
+    Box::pin(async move {
+        let res = checked.await?;
+        if !res.status().is_success() {
+            debug!("Rejected upload to {} before reading the body", req.path());
+            let mut res = res.into_parts().1.map_into_boxed_body();
+            res.head_mut().set_connection_type(ConnectionType::Close);
+            return Err(InternalError::from_response("Upload rejected", res).into());
+        }
+
+        if let Some(user) = res.response().extensions().get::<User>() {
+            req.extensions_mut().insert(user.clone());
+        }
+
+        Ok(req)
+    })
+}
+
+/// Authorizes the caller and compares the announced body size with its upload limit.
+async fn check_upload(req: HttpRequest, user: User) -> Result<HttpResponse, Error> {
+    if let (Some(upload_size_limit), Some(length)) = (user.upload_size_limit, content_length(&req))
+    {
+        let limit = body_limit(req.path(), upload_size_limit);
+        if length > limit {
+            return Err(actix_web::error::ErrorPayloadTooLarge(format!(
+                "Upload size limit exceeded. Maximum allowed: {}",
+                human_size::format_size(limit as u64)
+            )));
+        }
+    }
+
+    let mut res = HttpResponse::Ok().finish();
+    res.extensions_mut().insert(user);
+    Ok(res)
+}
+
+/// Returns the body limit of the upload endpoint, matching the limits of its extractor.
+fn body_limit(path: &str, upload_size_limit: usize) -> usize {
+    if path.ends_with("/secret/raw") {
+        size_limit::calculate_binary(upload_size_limit)
+    } else if path.ends_with("/secret/multipart") {
+        size_limited_multipart::body_limit(upload_size_limit)
+    } else {
+        size_limit::calculate(upload_size_limit)
+    }
+}
+
+fn content_length(req: &HttpRequest) -> Option<usize> {
+    req.headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    use actix_http::HttpService;
+    use actix_web::dev::{Server, ServerHandle};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use hakanai_lib::models::PostSecretRequest;
+
+    use crate::secret::MockSecretStore;
+    use crate::token::{MockTokenManager, TokenData};
+    use crate::web::app_data::{AnonymousOptions, AppData};
+    use crate::web::web_api;
+
+    /// Starts a server with the upload endpoints on a random port, returning its address.
+    fn start_server(token_manager: MockTokenManager) -> (String, ServerHandle) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind listener");
+        let address = listener.local_addr().unwrap().to_string();
+
+        let server = Server::build()
+            .listen("test", listener, move || {
+                let app_data = web::Data::new(create_test_app_data(token_manager.clone()));
+                let check_app = App::new().app_data(app_data.clone()).configure(configure);
+                let app = App::new()
+                    .app_data(app_data)
+                    .service(web::scope("/api/v1").configure(web_api::configure));
+                HttpService::build()
+                    .expect(service(check_app))
+                    .finish(map_config(app, |()| AppConfig::default()))
+                    .tcp()
+            })
+            .expect("Failed to listen")
+            .workers(1)
+            .disable_signals()
+            .run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        (address, handle)
+    }
+
+    fn create_test_app_data(token_manager: MockTokenManager) -> AppData {
+        AppData::default()
+            .with_secret_store(Box::new(MockSecretStore::new()))
+            .with_token_validator(Box::new(token_manager.clone()))
+            .with_token_creator(Box::new(token_manager))
+            .with_max_ttl(Duration::from_secs(7200))
+            .with_anonymous_usage(AnonymousOptions {
+                allowed: false,
+                upload_size_limit: 32 * 1024,
+            })
+    }
+
+    /// Sends the head of a request and reads until the server closes the connection or waits.
+    async fn send_head(stream: &mut TcpStream, head: &str) -> String {
+        stream.write_all(head.as_bytes()).await.unwrap();
+        read_available(stream).await
+    }
+
+    async fn read_available(stream: &mut TcpStream) -> String {
+        let mut response = Vec::new();
+        let mut buf = [0u8; 1024];
+        while let Ok(Ok(read)) =
+            tokio::time::timeout(Duration::from_millis(500), stream.read(&mut buf)).await
+        {
+            if read == 0 {
+                break;
+            }
+            response.extend_from_slice(&buf[..read]);
+        }
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    #[actix_web::test]
+    async fn test_unauthorized_upload_rejected_without_continue() {
+        let (address, handle) = start_server(MockTokenManager::new());
+
+        let mut stream = TcpStream::connect(&address).await.unwrap();
+        let response = send_head(
+            &mut stream,
+            "POST /api/v1/secret HTTP/1.1\r\nHost: localhost\r\n\
+             Content-Type: application/json\r\nContent-Length: 1024\r\n\
+             Expect: 100-continue\r\n\r\n",
+        )
+        .await;
+
+        assert!(
+            response.starts_with("HTTP/1.1 401"),
+            "Expected 401, got: {response}"
+        );
+        assert!(
+            !response.contains("100 Continue"),
+            "No 100 Continue should be sent for rejected uploads"
+        );
+        handle.stop(false).await;
+    }
+
+    #[actix_web::test]
+    async fn test_oversized_upload_rejected_without_continue() {
+        let token_manager = MockTokenManager::new().with_user_token(
+            "limited_token",
+            TokenData::default().with_upload_size_limit(1024),
+        );
+        let (address, handle) = start_server(token_manager);
+
+        let mut stream = TcpStream::connect(&address).await.unwrap();
+        let response = send_head(
+            &mut stream,
+            "POST /api/v1/secret/raw HTTP/1.1\r\nHost: localhost\r\n\
+             Authorization: Bearer limited_token\r\nContent-Length: 1048576\r\n\
+             Expect: 100-continue\r\n\r\n",
+        )
+        .await;
+
+        assert!(
+            response.starts_with("HTTP/1.1 413"),
+            "Expected 413, got: {response}"
+        );
+        assert!(
+            !response.contains("100 Continue"),
+            "No 100 Continue should be sent for rejected uploads"
+        );
+        handle.stop(false).await;
+    }
+
+    #[actix_web::test]
+    async fn test_authorized_upload_continued() {
+        let token_manager =
+            MockTokenManager::new().with_user_token("valid_token", TokenData::default());
+        let (address, handle) = start_server(token_manager);
+
+        let body = serde_json::to_string(&PostSecretRequest::new(
+            "test_secret".to_string(),
+            Duration::from_secs(3600),
+        ))
+        .unwrap();
+
+        let mut stream = TcpStream::connect(&address).await.unwrap();
+        let response = send_head(
+            &mut stream,
+            &format!(
+                "POST /api/v1/secret HTTP/1.1\r\nHost: localhost\r\n\
+                 Authorization: Bearer valid_token\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\nExpect: 100-continue\r\n\r\n",
+                body.len()
+            ),
+        )
+        .await;
+        assert!(
+            response.starts_with("HTTP/1.1 100 Continue"),
+            "Expected 100 Continue, got: {response}"
+        );
+
+        stream.write_all(body.as_bytes()).await.unwrap();
+        let response = read_available(&mut stream).await;
+        assert!(
+            response.starts_with("HTTP/1.1 200"),
+            "Expected 200, got: {response}"
+        );
+        handle.stop(false).await;
+    }
+
+    #[test]
+    fn test_body_limit_matches_endpoint() {
+        assert_eq!(
+            body_limit("/api/v1/secret", 1024),
+            size_limit::calculate(1024)
+        );
+        assert_eq!(
+            body_limit("/api/v1/secret/raw", 1024),
+            size_limit::calculate_binary(1024)
+        );
+        assert_eq!(
+            body_limit("/api/v1/secret/multipart", 1024),
+            size_limited_multipart::body_limit(1024)
+        );
+    }
+}
@@ -11,7 +11,7 @@ use super::app_data::AppData;
 
 /// Check if the request is from a whitelisted IP range
 pub fn is_request_from_whitelisted_ip(req: &HttpRequest, app_data: &AppData) -> bool {
-    if let Some(ref trusted_ranges) = app_data.trusted_ip_ranges {
+    if let Some(ref trusted_ranges) = app_data.runtime_config().trusted_ip_ranges {
         return is_request_from_ip_range(req, app_data, trusted_ranges);
     };
 
@@ -66,6 +66,11 @@ pub fn is_request_from_asn(req: &HttpRequest, app_data: &AppData, asns: &[u32])
     false
 }
 
+/// Returns the IP address of the client, taken from the trusted header if present
+pub fn client_ip(req: &HttpRequest, app_data: &AppData) -> Option<IpAddr> {
+    extract_client_ip(req, &app_data.trusted_ip_header)
+}
+
 /// Extract client IP from request headers or connection info
 fn extract_client_ip(req: &HttpRequest, trusted_header: &str) -> Option<IpAddr> {
     // First check the configured trusted header (e.g., x-forwarded-for)
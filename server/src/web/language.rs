@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// Language used if the client does not accept any available language.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Language packs of the web UI embedded into the binary.
+///
+/// The packs are shared with the web client, which bundles them at build time.
+const LANGUAGE_PACKS: &[(&str, &[u8])] = &[
+    ("en", include_bytes!("../../../typescript/src/i18n/en.json")),
+    ("de", include_bytes!("../../../typescript/src/i18n/de.json")),
+];
+
+/// Returns the embedded language pack for the language, if available.
+pub fn embedded_pack(lang: &str) -> Option<&'static [u8]> {
+    LANGUAGE_PACKS
+        .iter()
+        .find(|(code, _)| *code == lang)
+        .map(|(_, pack)| *pack)
+}
+
+/// Checks if the language code is a valid ISO 639 code (e.g. `en`, `de`).
+///
+/// Only valid codes are looked up in the custom assets directory.
+pub fn is_valid_code(lang: &str) -> bool {
+    (2..=3).contains(&lang.len()) && lang.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// Merges the custom language pack into the embedded one, custom translations take precedence.
+pub fn merge(embedded: &[u8], custom: &[u8]) -> Result<Vec<u8>, serde_json::Error> {
+    let mut pack: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(embedded)?;
+    let custom: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(custom)?;
+    pack.extend(custom);
+    serde_json::to_vec(&pack)
+}
+
+/// Selects the available language preferred by the client based on the `Accept-Language` header.
+///
+/// Regional variants (e.g. `de-AT`) match their primary language, languages with a quality
+/// value of zero are treated as not accepted.
+pub fn negotiate(accept_language: Option<&str>) -> &'static str {
+    let mut accepted: Vec<(String, f32)> = accept_language
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';').map(str::trim);
+            let lang = params.next().filter(|l| !l.is_empty())?;
+            let quality = params
+                .filter_map(|p| p.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let primary = lang.split('-').next().unwrap_or(lang).to_ascii_lowercase();
+            (quality > 0.0).then_some((primary, quality))
+        })
+        .collect();
+
+    // stable sort keeps the order of the header for equal quality values
+    accepted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    accepted
+        .iter()
+        .find_map(|(lang, _)| {
+            LANGUAGE_PACKS
+                .iter()
+                .find(|(code, _)| code == lang)
+                .map(|(code, _)| *code)
+        })
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(Some("de-DE,de;q=0.9,en;q=0.8")), "de");
+        assert_eq!(
+            negotiate(Some("en;q=0.5, de;q=0.8")),
+            "de",
+            "Language with highest quality should be preferred"
+        );
+        assert_eq!(
+            negotiate(Some("fr-FR, fr;q=0.9, de;q=0.7")),
+            "de",
+            "Unavailable languages should be skipped"
+        );
+        assert_eq!(
+            negotiate(Some("de;q=0, en;q=0.5")),
+            "en",
+            "Languages with quality zero should not be used"
+        );
+        assert_eq!(negotiate(Some("fr")), DEFAULT_LANGUAGE);
+        assert_eq!(negotiate(Some("*")), DEFAULT_LANGUAGE);
+        assert_eq!(negotiate(None), DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn test_is_valid_code() {
+        assert!(is_valid_code("en"));
+        assert!(is_valid_code("fil"));
+        assert!(!is_valid_code("EN"));
+        assert!(!is_valid_code("e"));
+        assert!(!is_valid_code("../x"));
+        assert!(!is_valid_code("de-AT"));
+    }
+
+    #[test]
+    fn test_embedded_packs_are_valid_json() {
+        for (lang, pack) in LANGUAGE_PACKS {
+            let parsed: Result<serde_json::Map<String, serde_json::Value>, _> =
+                serde_json::from_slice(pack);
+            assert!(
+                parsed.is_ok(),
+                "Language pack {lang} should be a JSON object"
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), serde_json::Error> {
+        let merged = merge(
+            br#"{"a":"embedded a","b":"embedded b"}"#,
+            br#"{"b":"custom b","c":"custom c"}"#,
+        )?;
+        let merged: serde_json::Value = serde_json::from_slice(&merged)?;
+        assert_eq!(
+            merged,
+            serde_json::json!({"a": "embedded a", "b": "custom b", "c": "custom c"}),
+            "Custom translations should override and extend embedded ones"
+        );
+
+        assert!(merge(b"{}", b"not json").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_embedded_pack() {
+        assert!(embedded_pack("en").is_some());
+        assert!(embedded_pack("de").is_some());
+        assert!(embedded_pack("fr").is_none());
+    }
+}
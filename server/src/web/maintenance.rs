@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Runtime toggle for the maintenance mode of this server instance.
+///
+/// In maintenance mode no new secrets are accepted, while existing secrets can still be
+/// retrieved, so an instance can be drained before an upgrade. The state is shared by all
+/// workers of the instance and is not persisted.
+#[derive(Clone, Debug, Default)]
+pub struct MaintenanceMode {
+    enabled: Arc<AtomicBool>,
+}
+
+impl MaintenanceMode {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(enabled)),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_is_shared_between_clones() {
+        let maintenance = MaintenanceMode::new(false);
+        let worker = maintenance.clone();
+
+        maintenance.set_enabled(true);
+        assert!(
+            worker.is_enabled(),
+            "All workers should see the enabled maintenance mode"
+        );
+
+        worker.set_enabled(false);
+        assert!(!maintenance.is_enabled());
+    }
+}
@@ -3,9 +3,25 @@
 mod admin_api;
 mod admin_user;
 mod app_data;
+mod csrf;
+mod expect_continue;
 pub mod filters;
+mod language;
+mod maintenance;
+mod passphrase;
+mod pin;
+mod purge_confirmation;
+mod qr_api;
+mod rate_limiter;
+mod runtime_config;
+mod security_headers;
 mod size_limit;
+mod size_limited_body;
 mod size_limited_json;
+mod size_limited_multipart;
+mod systemd;
+mod tenant_routing;
+mod tls;
 mod user;
 mod web_api;
 mod web_assets;
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage and verification of the passphrase hashes of secrets.
+//!
+//! Clients send the SHA-256 hash of the passphrase. Before storing, the server derives an
+//! HMAC-SHA256 of the client hash keyed with a random per-secret salt, so stored restrictions
+//! cannot be matched against precomputed hashes of common passphrases. Restrictions stored
+//! before salting was introduced have no salt and are compared directly.
+
+use base64::Engine;
+use hmac::{Hmac, KeyInit, Mac};
+use rand::TryRng;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+use hakanai_lib::models::SecretRestrictions;
+
+const SALT_SIZE: usize = 16;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Replaces the passphrase hash sent by the client with a salted HMAC.
+pub fn protect(restrictions: &mut SecretRestrictions) -> Result<(), String> {
+    restrictions.passphrase_salt = None;
+
+    let Some(passphrase_hash) = restrictions
+        .passphrase_hash
+        .as_deref()
+        .filter(|hash| !hash.is_empty())
+    else {
+        return Ok(());
+    };
+
+    let salt = generate_salt()?;
+    let hmac = salted_hash(&salt, passphrase_hash)?;
+    restrictions.passphrase_hash = Some(hmac);
+    restrictions.passphrase_salt = Some(base64::prelude::BASE64_STANDARD.encode(salt));
+    Ok(())
+}
+
+/// Verifies the passphrase hash provided by the client in constant time.
+pub fn verify(stored_hash: &str, salt: Option<&str>, provided_hash: &str) -> bool {
+    let Some(salt) = salt else {
+        // legacy restrictions without salt
+        return bool::from(stored_hash.as_bytes().ct_eq(provided_hash.as_bytes()));
+    };
+
+    let Ok(salt) = base64::prelude::BASE64_STANDARD.decode(salt) else {
+        return false;
+    };
+
+    match salted_hash(&salt, provided_hash) {
+        Ok(hash) => bool::from(stored_hash.as_bytes().ct_eq(hash.as_bytes())),
+        Err(_) => false,
+    }
+}
+
+/// Generates a random per-secret salt.
+pub(super) fn generate_salt() -> Result<[u8; SALT_SIZE], String> {
+    let mut salt = [0u8; SALT_SIZE];
+    rand::rng()
+        .try_fill_bytes(&mut salt)
+        .map_err(|e| format!("failed to generate salt: {e}"))?;
+    Ok(salt)
+}
+
+/// Derives the hex encoded HMAC-SHA256 of the value keyed with the salt.
+pub(super) fn salted_hash(salt: &[u8], value: &str) -> Result<String, String> {
+    let mut mac = HmacSha256::new_from_slice(salt).map_err(|e| e.to_string())?;
+    mac.update(value.as_bytes());
+
+    let hash = mac.finalize().into_bytes();
+    Ok(hash.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_HASH: &str = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+
+    fn protected_restrictions() -> Result<SecretRestrictions, String> {
+        let mut restrictions = SecretRestrictions {
+            passphrase_hash: Some(CLIENT_HASH.to_string()),
+            ..Default::default()
+        };
+        protect(&mut restrictions)?;
+        Ok(restrictions)
+    }
+
+    #[test]
+    fn test_protect_and_verify() -> Result<(), String> {
+        let restrictions = protected_restrictions()?;
+        let stored = restrictions.passphrase_hash.as_deref().unwrap_or_default();
+        let salt = restrictions.passphrase_salt.as_deref();
+
+        assert_ne!(stored, CLIENT_HASH, "Client hash should not be stored");
+        assert!(salt.is_some(), "Salt should be stored");
+        assert!(verify(stored, salt, CLIENT_HASH));
+        assert!(
+            !verify(stored, salt, &CLIENT_HASH.to_uppercase()),
+            "Verification should be case sensitive"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_protect_uses_unique_salt() -> Result<(), String> {
+        let first = protected_restrictions()?;
+        let second = protected_restrictions()?;
+
+        assert_ne!(
+            first.passphrase_hash, second.passphrase_hash,
+            "Same passphrase should result in different stored hashes"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_protect_overrides_client_salt() -> Result<(), String> {
+        let mut restrictions = SecretRestrictions {
+            passphrase_salt: Some("c2FsdA==".to_string()),
+            ..Default::default()
+        };
+        protect(&mut restrictions)?;
+
+        assert_eq!(
+            restrictions.passphrase_salt, None,
+            "Salt should be dropped without passphrase"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_legacy_hash() {
+        assert!(verify(CLIENT_HASH, None, CLIENT_HASH));
+        assert!(!verify(CLIENT_HASH, None, "wrong"));
+    }
+
+    #[test]
+    fn test_verify_invalid_salt() {
+        assert!(!verify(CLIENT_HASH, Some("not base64!"), CLIENT_HASH));
+    }
+}
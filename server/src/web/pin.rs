@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retrieval PINs generated by the server and delivered to the recipient out-of-band.
+//!
+//! When the sender requests a PIN, the server generates a short numeric PIN, hands it to the
+//! configured `PinDelivery` (e.g. an SMS gateway) and stores only a salted HMAC of it, like for
+//! passphrases. The recipient of the PIN is passed to the delivery and never stored. As the PIN
+//! space is small, wrong PINs are counted per secret and the secret is deleted after
+//! [`MAX_FAILED_ATTEMPTS`].
+
+use base64::Engine;
+use rand::TryRng;
+
+use hakanai_lib::models::SecretRestrictions;
+
+use super::passphrase;
+
+/// Number of digits of a PIN.
+pub const PIN_LENGTH: usize = 6;
+
+/// Number of wrong PINs after which the secret is deleted.
+pub const MAX_FAILED_ATTEMPTS: u32 = 3;
+
+/// A generated PIN which still has to be delivered.
+pub struct PendingPin {
+    /// Recipient requested by the sender (e.g. a phone number).
+    pub recipient: String,
+
+    /// The generated PIN.
+    pub pin: String,
+}
+
+/// Generates a PIN if the sender requested one and replaces the recipient with the salted hash
+/// of the PIN. Hashes sent by the client are discarded.
+pub fn protect(restrictions: &mut SecretRestrictions) -> Result<Option<PendingPin>, String> {
+    restrictions.pin_hash = None;
+    restrictions.pin_salt = None;
+
+    let Some(recipient) = restrictions
+        .pin_recipient
+        .take()
+        .filter(|recipient| !recipient.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let pin = generate()?;
+    let salt = passphrase::generate_salt()?;
+    restrictions.pin_hash = Some(passphrase::salted_hash(&salt, &pin)?);
+    restrictions.pin_salt = Some(base64::prelude::BASE64_STANDARD.encode(salt));
+
+    Ok(Some(PendingPin { recipient, pin }))
+}
+
+/// Verifies the PIN provided by the client in constant time.
+pub fn verify(stored_hash: &str, salt: Option<&str>, provided_pin: &str) -> bool {
+    // PINs are always salted, unsalted hashes would be compared to the PIN itself
+    if salt.is_none() {
+        return false;
+    }
+
+    passphrase::verify(stored_hash, salt, provided_pin.trim())
+}
+
+/// Generates a PIN of uniformly distributed digits.
+fn generate() -> Result<String, String> {
+    let mut rng = rand::rng();
+    let mut pin = String::with_capacity(PIN_LENGTH);
+    while pin.len() < PIN_LENGTH {
+        let mut byte = [0u8; 1];
+        rng.try_fill_bytes(&mut byte)
+            .map_err(|e| format!("failed to generate PIN: {e}"))?;
+
+        // 250 is the largest multiple of 10 below 256, larger values would bias the digits
+        if byte[0] < 250 {
+            pin.push(char::from(b'0' + byte[0] % 10));
+        }
+    }
+
+    Ok(pin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restrictions_with_pin() -> SecretRestrictions {
+        SecretRestrictions::default().with_pin_recipient("+49123456789")
+    }
+
+    #[test]
+    fn test_protect_and_verify() -> Result<(), String> {
+        let mut restrictions = restrictions_with_pin();
+        let pending = protect(&mut restrictions)?.expect("PIN should be generated");
+
+        assert_eq!(pending.recipient, "+49123456789");
+        assert_eq!(pending.pin.len(), PIN_LENGTH);
+        assert!(pending.pin.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(
+            restrictions.pin_recipient, None,
+            "Recipient should not be stored"
+        );
+
+        let stored = restrictions.pin_hash.as_deref().unwrap_or_default();
+        let salt = restrictions.pin_salt.as_deref();
+        assert_ne!(stored, pending.pin, "PIN should not be stored");
+        assert!(verify(stored, salt, &pending.pin));
+        assert!(
+            verify(stored, salt, &format!(" {}\n", pending.pin)),
+            "Surrounding whitespace should be ignored"
+        );
+        assert!(!verify(stored, salt, "not the pin"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_protect_without_recipient() -> Result<(), String> {
+        let mut restrictions = SecretRestrictions {
+            pin_hash: Some("client hash".to_string()),
+            pin_salt: Some("c2FsdA==".to_string()),
+            ..Default::default()
+        };
+
+        assert!(protect(&mut restrictions)?.is_none());
+        assert_eq!(restrictions.pin_hash, None, "Client hash should be removed");
+        assert_eq!(restrictions.pin_salt, None, "Client salt should be removed");
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_requires_salt() -> Result<(), String> {
+        let mut restrictions = restrictions_with_pin();
+        let pending = protect(&mut restrictions)?.expect("PIN should be generated");
+        let stored = restrictions.pin_hash.unwrap_or_default();
+
+        assert!(!verify(&stored, None, &stored));
+        assert!(!verify(&stored, None, &pending.pin));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_uses_all_digits() -> Result<(), String> {
+        let mut digits = std::collections::HashSet::new();
+        for _ in 0..100 {
+            digits.extend(generate()?.chars());
+        }
+
+        assert_eq!(digits.len(), 10, "All digits should be generated");
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Confirmation tokens for purging secrets.
+//!
+//! Purging is a two-step operation: the admin first requests a preview, which returns the number
+//! of affected secrets and a short-lived confirmation token, and then repeats the request with
+//! the token. The token is an HMAC-SHA256 over the expiration and the purge criteria keyed with
+//! the admin token, so it is stateless, works across multiple server instances and cannot be
+//! reused for a different `older_than` value.
+
+use std::time::Duration;
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+/// Time in which the purge has to be confirmed.
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Creates a confirmation token for purging secrets older than `older_than` (all if `None`).
+///
+/// `now` is the current UNIX timestamp in seconds.
+pub fn create(admin_token: &str, older_than: Option<Duration>, now: u64) -> Result<String, String> {
+    let expires_at = now + CONFIRMATION_TTL.as_secs();
+    let signature = sign(admin_token, older_than, expires_at)?;
+    Ok(format!("{expires_at}.{signature}"))
+}
+
+/// Verifies the confirmation token in constant time.
+///
+/// Returns `false` if the token is malformed, expired or was issued for different criteria.
+pub fn verify(admin_token: &str, older_than: Option<Duration>, token: &str, now: u64) -> bool {
+    let Some((expires_at, signature)) = token.split_once('.') else {
+        return false;
+    };
+
+    let Ok(expires_at) = expires_at.parse::<u64>() else {
+        return false;
+    };
+
+    if expires_at < now {
+        return false;
+    }
+
+    match sign(admin_token, older_than, expires_at) {
+        Ok(expected) => bool::from(expected.as_bytes().ct_eq(signature.as_bytes())),
+        Err(_) => false,
+    }
+}
+
+fn sign(
+    admin_token: &str,
+    older_than: Option<Duration>,
+    expires_at: u64,
+) -> Result<String, String> {
+    let mut mac = HmacSha256::new_from_slice(admin_token.as_bytes()).map_err(|e| e.to_string())?;
+
+    let older_than = older_than
+        .map(|d| d.as_secs().to_string())
+        .unwrap_or_default();
+    mac.update(format!("purge:{older_than}:{expires_at}").as_bytes());
+
+    let hash = mac.finalize().into_bytes();
+    Ok(hash.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN_TOKEN: &str = "admin_token";
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_create_and_verify() -> Result<(), String> {
+        let older_than = Some(Duration::from_secs(3600));
+        let token = create(ADMIN_TOKEN, older_than, NOW)?;
+
+        assert!(verify(ADMIN_TOKEN, older_than, &token, NOW));
+        assert!(
+            verify(
+                ADMIN_TOKEN,
+                older_than,
+                &token,
+                NOW + CONFIRMATION_TTL.as_secs()
+            ),
+            "Token should be valid until expiration"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_expired() -> Result<(), String> {
+        let token = create(ADMIN_TOKEN, None, NOW)?;
+
+        assert!(
+            !verify(
+                ADMIN_TOKEN,
+                None,
+                &token,
+                NOW + CONFIRMATION_TTL.as_secs() + 1
+            ),
+            "Expired token should be rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_different_criteria() -> Result<(), String> {
+        let token = create(ADMIN_TOKEN, Some(Duration::from_secs(3600)), NOW)?;
+
+        assert!(
+            !verify(ADMIN_TOKEN, None, &token, NOW),
+            "Token for older secrets should not confirm purging all secrets"
+        );
+        assert!(
+            !verify(ADMIN_TOKEN, Some(Duration::from_secs(60)), &token, NOW),
+            "Token should be bound to the older_than value"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_different_admin_token() -> Result<(), String> {
+        let token = create(ADMIN_TOKEN, None, NOW)?;
+
+        assert!(!verify("other_admin_token", None, &token, NOW));
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_tampered_expiration() -> Result<(), String> {
+        let token = create(ADMIN_TOKEN, None, NOW)?;
+        let (_, signature) = token.split_once('.').ok_or("invalid token")?;
+        let tampered = format!("{}.{signature}", NOW + 3600);
+
+        assert!(!verify(ADMIN_TOKEN, None, &tampered, NOW + 120));
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_malformed() {
+        for token in ["", "abc", "abc.def", "123"] {
+            assert!(
+                !verify(ADMIN_TOKEN, None, token, NOW),
+                "Malformed token '{token}' should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! QR code endpoint for clients which cannot render QR codes themselves (e.g. the iOS
+//! shortcut).
+//!
+//! The data is sent in the request body instead of the query string, since it usually is a
+//! secret link including the key, which must not end up in access logs of the server or
+//! proxies in between.
+
+use actix_web::{HttpRequest, HttpResponse, Result, error, web};
+use serde::Deserialize;
+use tracing::warn;
+
+use hakanai_lib::utils::qr_code::{self, DEFAULT_MARGIN, QrCodeError};
+
+use super::app_data::AppData;
+use super::filters;
+
+/// Size of the QR code in pixels if not specified.
+const DEFAULT_SIZE: u32 = 256;
+
+/// Image format of the QR code.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QrCodeFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QrCodeRequest {
+    /// Text to encode, usually the secret link.
+    pub data: String,
+
+    #[serde(default)]
+    pub format: QrCodeFormat,
+
+    /// Size of the image in pixels.
+    #[serde(default = "default_size")]
+    pub size: u32,
+
+    /// Margin (quiet zone) around the code in modules.
+    #[serde(default = "default_margin")]
+    pub margin: u32,
+}
+
+fn default_size() -> u32 {
+    DEFAULT_SIZE
+}
+
+fn default_margin() -> u32 {
+    DEFAULT_MARGIN
+}
+
+/// Configure QR code API routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/qr", web::post().to(generate_qr_code));
+}
+
+/// Generate a QR code
+///
+/// POST /api/v1/qr
+///
+/// Renders the data as SVG or PNG image. Requests are rate limited per client IP, requests
+/// without a known client IP are not limited, so they do not share a single limit.
+pub async fn generate_qr_code(
+    http_req: HttpRequest,
+    request: web::Json<QrCodeRequest>,
+    app_data: web::Data<AppData>,
+) -> Result<HttpResponse> {
+    match filters::client_ip(&http_req, &app_data) {
+        Some(client_ip) if !app_data.qr_code_rate_limiter.check(client_ip) => {
+            warn!("QR code rate limit exceeded for {client_ip}");
+            return Err(error::ErrorTooManyRequests(
+                "Too many QR code requests, please try again later",
+            ));
+        }
+        Some(_) => {}
+        None => warn!("Client IP of QR code request is unknown, skipping rate limit"),
+    }
+
+    let request = request.into_inner();
+    match request.format {
+        QrCodeFormat::Svg => {
+            let svg = qr_code::to_svg(&request.data, request.size, request.margin)
+                .map_err(qr_code_error)?;
+            Ok(HttpResponse::Ok().content_type("image/svg+xml").body(svg))
+        }
+        QrCodeFormat::Png => {
+            let png = qr_code::to_png(&request.data, request.size, request.margin)
+                .map_err(qr_code_error)?;
+            Ok(HttpResponse::Ok().content_type("image/png").body(png))
+        }
+    }
+}
+
+fn qr_code_error(err: QrCodeError) -> actix_web::Error {
+    match err {
+        QrCodeError::Png(_) => error::ErrorInternalServerError(err.to_string()),
+        _ => error::ErrorBadRequest(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, test};
+    use std::time::Duration;
+
+    use super::super::rate_limiter::RateLimiter;
+
+    async fn post_qr_code(
+        app_data: AppData,
+        body: serde_json::Value,
+    ) -> actix_web::dev::ServiceResponse {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/v1/qr")
+            .set_json(body)
+            .to_request();
+        test::call_service(&app, req).await
+    }
+
+    #[actix_web::test]
+    async fn test_generate_qr_code_svg() {
+        let resp = post_qr_code(
+            AppData::default(),
+            serde_json::json!({ "data": "https://example.com/s/abc#key" }),
+        )
+        .await;
+
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers()
+                .get("Content-Type")
+                .and_then(|v| v.to_str().ok()),
+            Some("image/svg+xml"),
+            "SVG should be the default format"
+        );
+
+        let body = test::read_body(resp).await;
+        let svg = String::from_utf8_lossy(&body);
+        assert!(
+            svg.contains(r#"width="256" height="256""#),
+            "Default size should be used"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_generate_qr_code_png() {
+        let resp = post_qr_code(
+            AppData::default(),
+            serde_json::json!({ "data": "text", "format": "png", "size": 128, "margin": 2 }),
+        )
+        .await;
+
+        assert_eq!(resp.status(), 200);
+        let body = test::read_body(resp).await;
+        assert_eq!(
+            &body[..8],
+            b"\x89PNG\r\n\x1a\n",
+            "Output should be a PNG image"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_generate_qr_code_invalid_parameters() {
+        for body in [
+            serde_json::json!({ "data": "a".repeat(qr_code::MAX_TEXT_LENGTH + 1) }),
+            serde_json::json!({ "data": "text", "size": qr_code::MAX_SIZE + 1 }),
+            serde_json::json!({ "data": "text", "margin": qr_code::MAX_MARGIN + 1 }),
+        ] {
+            let resp = post_qr_code(AppData::default(), body.clone()).await;
+            assert_eq!(resp.status(), 400, "Request should be rejected: {body}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_generate_qr_code_rate_limited() {
+        let app_data = AppData::default()
+            .with_qr_code_rate_limiter(RateLimiter::new(1, Duration::from_secs(60)));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        let request = || {
+            test::TestRequest::post()
+                .uri("/api/v1/qr")
+                .insert_header(("x-forwarded-for", "192.168.1.1"))
+                .set_json(serde_json::json!({ "data": "text" }))
+                .to_request()
+        };
+
+        let resp = test::call_service(&app, request()).await;
+        assert_eq!(resp.status(), 200);
+
+        let resp = test::call_service(&app, request()).await;
+        assert_eq!(
+            resp.status(),
+            429,
+            "Requests exceeding the rate limit should be rejected"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_generate_qr_code_without_client_ip_not_rate_limited() {
+        let app_data = AppData::default()
+            .with_qr_code_rate_limiter(RateLimiter::new(1, Duration::from_secs(60)));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .service(web::scope("/api/v1").configure(configure_routes)),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let req = test::TestRequest::post()
+                .uri("/api/v1/qr")
+                .set_json(serde_json::json!({ "data": "text" }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(
+                resp.status(),
+                200,
+                "Clients without known IP should not share a rate limit"
+            );
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Limits the number of requests per client within a fixed window.
+///
+/// The counters are kept in memory and shared by all workers of the instance, so the limit
+/// applies per instance and is reset on restart.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Arc<Mutex<HashMap<IpAddr, (Instant, u32)>>>,
+}
+
+impl RateLimiter {
+    /// Allows `limit` requests per client within `window`, `0` disables the limit.
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Counts the request of the client, `false` if the client exceeded the limit.
+    pub fn check(&self, client: IpAddr) -> bool {
+        if self.limit == 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().expect("Failed to acquire lock");
+        windows.retain(|_, (started, _)| now.duration_since(*started) < self.window);
+
+        let (_, count) = windows.entry(client).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 1));
+    const OTHER_CLIENT: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 2));
+
+    #[test]
+    fn test_check_limit_per_client() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+
+        assert!(limiter.check(CLIENT));
+        assert!(limiter.check(CLIENT));
+        assert!(
+            !limiter.check(CLIENT),
+            "Requests exceeding the limit should be rejected"
+        );
+        assert!(
+            limiter.check(OTHER_CLIENT),
+            "Other clients should not be affected"
+        );
+    }
+
+    #[test]
+    fn test_check_window_expired() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(10));
+
+        assert!(limiter.check(CLIENT));
+        assert!(!limiter.check(CLIENT));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(
+            limiter.check(CLIENT),
+            "Requests should be allowed again after the window"
+        );
+    }
+
+    #[test]
+    fn test_check_disabled() {
+        let limiter = RateLimiter::new(0, Duration::from_secs(60));
+
+        for _ in 0..100 {
+            assert!(limiter.check(CLIENT), "Limit 0 should disable the limiter");
+        }
+    }
+}
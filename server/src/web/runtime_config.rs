@@ -0,0 +1,392 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configuration which can be reloaded without restarting the server.
+//!
+//! Handlers access the reloadable settings via `AppData::runtime_config`, which returns a
+//! consistent snapshot. A reload builds the complete new configuration first and swaps it
+//! atomically, so requests never see a partially applied configuration and a failed reload
+//! keeps the previous configuration.
+
+use std::io::Result;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use tracing::{info, instrument, warn};
+
+use hakanai_lib::utils::human_size;
+
+use super::app_data::AnonymousOptions;
+use crate::observer::{ReloadableObserver, WebhookObserver};
+use crate::options::Args;
+use crate::tenant::Tenant;
+
+/// Settings which are reloaded on SIGHUP or via admin API.
+#[derive(Clone, Debug)]
+pub struct RuntimeConfig {
+    /// Pre-rendered impressum HTML page (built if configured)
+    pub impressum_html: Option<String>,
+
+    /// Pre-rendered privacy policy HTML page (built if configured)
+    pub privacy_html: Option<String>,
+
+    /// IP ranges that bypass size limits
+    pub trusted_ip_ranges: Option<Vec<ipnet::IpNet>>,
+
+    /// The maximum upload size allowed for the server, in bytes.
+    pub upload_size_limit: usize,
+
+    /// Defines whether the application can be used without authentication and limits for anonymous users.
+    pub anonymous_usage: AnonymousOptions,
+
+    /// The upload size limit for users authenticated by a token without an own limit or by the
+    /// identity provider, in bytes. `None` if not limited.
+    pub token_upload_size_limit: Option<usize>,
+
+    /// The upload size limit for requests from trusted IP ranges, in bytes. `None` if not limited.
+    pub whitelisted_upload_size_limit: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Builds the configuration from the arguments, reading the impressum and privacy files.
+    pub fn from_args(args: &Args) -> Result<Self> {
+        Ok(Self {
+            impressum_html: build_impressum_html(args)?,
+            privacy_html: build_privacy_html(args)?,
+            trusted_ip_ranges: args.trusted_ip_ranges.clone(),
+            upload_size_limit: args.upload_size_limit,
+            anonymous_usage: AnonymousOptions {
+                allowed: args.allow_anonymous,
+                upload_size_limit: args.anonymous_upload_size_limit,
+            },
+            token_upload_size_limit: args.token_upload_size_limit,
+            whitelisted_upload_size_limit: args.whitelisted_upload_size_limit,
+        })
+    }
+
+    /// Applies the settings of the tenant, limits of the tenant only lower the limits.
+    pub fn for_tenant(&self, tenant: &Tenant) -> Self {
+        let upload_size_limit = tenant
+            .upload_size_limit
+            .map_or(self.upload_size_limit, |limit| {
+                limit.min(self.upload_size_limit)
+            });
+        let anonymous_upload_size_limit = tenant
+            .anonymous_upload_size_limit
+            .unwrap_or(self.anonymous_usage.upload_size_limit)
+            .min(upload_size_limit);
+
+        Self {
+            upload_size_limit,
+            anonymous_usage: AnonymousOptions {
+                allowed: tenant
+                    .allow_anonymous
+                    .unwrap_or(self.anonymous_usage.allowed),
+                upload_size_limit: anonymous_upload_size_limit,
+            },
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            impressum_html: None,
+            privacy_html: None,
+            trusted_ip_ranges: None,
+            upload_size_limit: 10 * 1024 * 1024, // 10MB
+            anonymous_usage: AnonymousOptions {
+                allowed: false,
+                upload_size_limit: 32 * 1024, // 32KB
+            },
+            token_upload_size_limit: None,
+            whitelisted_upload_size_limit: None,
+        }
+    }
+}
+
+/// Reloads the runtime configuration and the webhook observer from the arguments and the
+/// config file. Clones share the configuration, so a reload affects all workers.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    args: Args,
+    config: Arc<ArcSwap<RuntimeConfig>>,
+    tenant_configs: Vec<(Arc<Tenant>, Arc<ArcSwap<RuntimeConfig>>)>,
+    webhook_observer: ReloadableObserver<WebhookObserver>,
+}
+
+impl ConfigReloader {
+    /// Loads the initial configuration, fails if the configuration is invalid.
+    pub fn new(args: Args) -> std::result::Result<Self, String> {
+        let (config, webhook_observer) = load(&args)?;
+        Ok(Self {
+            args,
+            config: Arc::new(ArcSwap::from_pointee(config)),
+            tenant_configs: Vec::new(),
+            webhook_observer: ReloadableObserver::new(webhook_observer),
+        })
+    }
+
+    /// Reloads the configuration of the tenants together with the instance configuration.
+    pub fn with_tenants(mut self, tenants: &[Arc<Tenant>]) -> Self {
+        let config = self.config.load();
+        self.tenant_configs = tenants
+            .iter()
+            .map(|tenant| {
+                let tenant_config = ArcSwap::from_pointee(config.for_tenant(tenant));
+                (tenant.clone(), Arc::new(tenant_config))
+            })
+            .collect();
+        self
+    }
+
+    /// Shared handle to the current configuration.
+    pub fn config(&self) -> Arc<ArcSwap<RuntimeConfig>> {
+        self.config.clone()
+    }
+
+    /// Shared handle to the current configuration of the tenant.
+    pub fn tenant_config(&self, tenant_id: &str) -> Option<Arc<ArcSwap<RuntimeConfig>>> {
+        self.tenant_configs
+            .iter()
+            .find(|(tenant, _)| tenant.id == tenant_id)
+            .map(|(_, config)| config.clone())
+    }
+
+    /// Observer sending webhooks to the currently configured URL.
+    pub fn webhook_observer(&self) -> ReloadableObserver<WebhookObserver> {
+        self.webhook_observer.clone()
+    }
+
+    /// Reloads the configuration, the previous configuration is kept on error.
+    #[instrument(skip(self), err)]
+    pub fn reload(&self) -> std::result::Result<(), String> {
+        let (config, webhook_observer) = load(&self.args)?;
+
+        for (tenant, tenant_config) in &self.tenant_configs {
+            tenant_config.store(Arc::new(config.for_tenant(tenant)));
+        }
+        self.config.store(Arc::new(config));
+        self.webhook_observer.replace(webhook_observer);
+
+        info!("Configuration reloaded");
+        Ok(())
+    }
+}
+
+fn load(args: &Args) -> std::result::Result<(RuntimeConfig, Option<WebhookObserver>), String> {
+    let args = args.reload()?;
+    let config = RuntimeConfig::from_args(&args).map_err(|e| e.to_string())?;
+
+    let webhook_observer = args
+        .webhook_args()
+        .map(|webhook| {
+            WebhookObserver::new(webhook.url, webhook.token, webhook.headers).map(|observer| {
+                match webhook.secret {
+                    Some(secret) => observer.with_signing_secret(secret),
+                    None => observer,
+                }
+            })
+        })
+        .transpose()
+        .map_err(|e| format!("failed to initialize webhook observer: {e}"))?;
+
+    Ok((config, webhook_observer))
+}
+
+/// Reloads the configuration whenever the process receives SIGHUP.
+#[cfg(unix)]
+pub fn reload_on_sighup(reloader: ConfigReloader) -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            info!("Received SIGHUP, reloading configuration");
+            if let Err(e) = reloader.reload() {
+                warn!("Failed to reload configuration, keeping previous configuration: {e}");
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn build_impressum_html(args: &Args) -> Result<Option<String>> {
+    Ok(match args.load_impressum_content()? {
+        Some(content) => {
+            info!(
+                "Building impressum HTML ({} of content)",
+                human_size::format_size(content.len() as u64)
+            );
+            let template = include_str!("../../includes/impressum.html");
+            Some(template.replace(
+                r#"<div id="impressum-content-placeholder"></div>"#,
+                &content,
+            ))
+        }
+        None => None,
+    })
+}
+
+fn build_privacy_html(args: &Args) -> Result<Option<String>> {
+    Ok(match args.load_privacy_content()? {
+        Some(content) => {
+            info!(
+                "Building privacy policy HTML ({} of content)",
+                human_size::format_size(content.len() as u64)
+            );
+            let template = include_str!("../../includes/privacy.html");
+            Some(template.replace(r#"<div id="privacy-content-placeholder"></div>"#, &content))
+        }
+        None => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    fn write_file(file: &mut NamedTempFile, content: &str) {
+        let file = file.as_file_mut();
+        file.set_len(0).expect("Failed to truncate file");
+        std::io::Seek::rewind(file).expect("Failed to rewind file");
+        write!(file, "{content}").expect("Failed to write file");
+        file.flush().expect("Failed to flush file");
+    }
+
+    #[test]
+    fn test_reload_applies_changes() -> std::result::Result<(), String> {
+        let mut impressum = NamedTempFile::new().map_err(|e| e.to_string())?;
+        let mut config_file = NamedTempFile::new().map_err(|e| e.to_string())?;
+        write_file(&mut impressum, "Old Corp");
+        write_file(&mut config_file, "upload_size_limit: 1m");
+
+        let args = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--impressum-file",
+            &impressum.path().to_string_lossy(),
+            "--config",
+            &config_file.path().to_string_lossy(),
+        ])
+        .map_err(|e| e.to_string())?;
+        let reloader = ConfigReloader::new(args)?;
+        let config = reloader.config();
+        assert_eq!(config.load().upload_size_limit, 1024 * 1024);
+
+        write_file(&mut impressum, "New Corp");
+        write_file(
+            &mut config_file,
+            "upload_size_limit: 2m\ntrusted_ip_ranges: [10.0.0.0/8]",
+        );
+        reloader.reload()?;
+
+        let current = config.load();
+        assert_eq!(current.upload_size_limit, 2 * 1024 * 1024);
+        assert_eq!(current.trusted_ip_ranges.as_ref().map(Vec::len), Some(1));
+        assert!(
+            current
+                .impressum_html
+                .as_deref()
+                .is_some_and(|html| html.contains("New Corp")),
+            "Impressum should be rebuilt from the changed file"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_tenant_only_lowers_limits() {
+        let config = RuntimeConfig::default();
+        let mut tenant = Tenant {
+            id: "team-a".to_string(),
+            hosts: vec![],
+            path_prefix: Some("/team-a".to_string()),
+            upload_size_limit: Some(1024),
+            anonymous_upload_size_limit: Some(2048),
+            allow_anonymous: Some(true),
+            max_ttl: None,
+            custom_assets_dir: None,
+        };
+
+        let tenant_config = config.for_tenant(&tenant);
+        assert_eq!(tenant_config.upload_size_limit, 1024);
+        assert_eq!(
+            tenant_config.anonymous_usage.upload_size_limit, 1024,
+            "Anonymous limit should not exceed the upload limit"
+        );
+        assert!(tenant_config.anonymous_usage.allowed);
+
+        tenant.upload_size_limit = Some(usize::MAX);
+        assert_eq!(
+            config.for_tenant(&tenant).upload_size_limit,
+            config.upload_size_limit,
+            "Tenant should not exceed the instance limit"
+        );
+    }
+
+    #[test]
+    fn test_reload_updates_tenants() -> std::result::Result<(), String> {
+        let mut config_file = NamedTempFile::new().map_err(|e| e.to_string())?;
+        write_file(&mut config_file, "upload_size_limit: 1m");
+
+        let args = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--config",
+            &config_file.path().to_string_lossy(),
+        ])
+        .map_err(|e| e.to_string())?;
+        let tenant = Arc::new(Tenant {
+            id: "team-a".to_string(),
+            hosts: vec!["a.example.com".to_string()],
+            path_prefix: None,
+            upload_size_limit: Some(2 * 1024 * 1024),
+            anonymous_upload_size_limit: None,
+            allow_anonymous: None,
+            max_ttl: None,
+            custom_assets_dir: None,
+        });
+        let reloader = ConfigReloader::new(args)?.with_tenants(&[tenant]);
+        let tenant_config = reloader
+            .tenant_config("team-a")
+            .ok_or("tenant config missing")?;
+        assert_eq!(tenant_config.load().upload_size_limit, 1024 * 1024);
+
+        write_file(&mut config_file, "upload_size_limit: 4m");
+        reloader.reload()?;
+
+        assert_eq!(
+            tenant_config.load().upload_size_limit,
+            2 * 1024 * 1024,
+            "Tenant limit should apply after the instance limit was raised"
+        );
+        assert!(reloader.tenant_config("unknown").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_keeps_config_on_error() -> std::result::Result<(), String> {
+        let mut config_file = NamedTempFile::new().map_err(|e| e.to_string())?;
+        write_file(&mut config_file, "upload_size_limit: 1m");
+
+        let args = Args::try_parse_layered_from([
+            "hakanai-server",
+            "--config",
+            &config_file.path().to_string_lossy(),
+        ])
+        .map_err(|e| e.to_string())?;
+        let reloader = ConfigReloader::new(args)?;
+
+        write_file(&mut config_file, "upload_size_limit: [invalid");
+        assert!(reloader.reload().is_err(), "Invalid config should fail");
+        assert_eq!(
+            reloader.config().load().upload_size_limit,
+            1024 * 1024,
+            "Previous configuration should be kept"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use actix_web::middleware::DefaultHeaders;
+
+use crate::options::Args;
+
+/// Content security policy for the web interface, unless overridden by the configuration.
+pub const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; script-src 'self' 'wasm-unsafe-eval'; style-src 'self'; img-src 'self' data: blob:; connect-src 'self'; font-src 'self'; object-src 'none'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'; upgrade-insecure-requests";
+
+/// Options for the security headers sent with every response.
+#[derive(Clone, Debug)]
+pub struct SecurityHeadersOptions {
+    /// Value of the Content-Security-Policy header
+    pub content_security_policy: String,
+
+    /// Max age of the Strict-Transport-Security header, zero disables HSTS
+    pub hsts_max_age: Duration,
+
+    /// Whether the COOP/COEP headers isolating the pages from other origins are sent
+    pub cross_origin_isolation: bool,
+}
+
+impl Default for SecurityHeadersOptions {
+    fn default() -> Self {
+        Self {
+            content_security_policy: DEFAULT_CONTENT_SECURITY_POLICY.to_string(),
+            hsts_max_age: Duration::from_secs(31536000),
+            cross_origin_isolation: true,
+        }
+    }
+}
+
+impl From<&Args> for SecurityHeadersOptions {
+    fn from(args: &Args) -> Self {
+        Self {
+            content_security_policy: args
+                .content_security_policy
+                .clone()
+                .unwrap_or_else(|| DEFAULT_CONTENT_SECURITY_POLICY.to_string()),
+            hsts_max_age: args.hsts_max_age,
+            cross_origin_isolation: !args.disable_cross_origin_isolation,
+        }
+    }
+}
+
+/// Creates the middleware adding the security headers to all responses.
+///
+/// Headers already set by a handler are not overwritten.
+pub fn middleware(options: &SecurityHeadersOptions) -> DefaultHeaders {
+    let mut headers = DefaultHeaders::new()
+        .add(("X-Frame-Options", "DENY"))
+        .add(("X-Content-Type-Options", "nosniff"))
+        .add((
+            "Content-Security-Policy",
+            options.content_security_policy.clone(),
+        ))
+        .add(("Referrer-Policy", "strict-origin-when-cross-origin"))
+        .add((
+            "Permissions-Policy",
+            "geolocation=(), microphone=(), camera=()",
+        ));
+
+    if !options.hsts_max_age.is_zero() {
+        headers = headers.add((
+            "Strict-Transport-Security",
+            format!(
+                "max-age={}; includeSubDomains",
+                options.hsts_max_age.as_secs()
+            ),
+        ));
+    }
+
+    if options.cross_origin_isolation {
+        headers = headers
+            .add(("Cross-Origin-Opener-Policy", "same-origin"))
+            .add(("Cross-Origin-Embedder-Policy", "require-corp"));
+    }
+
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::dev::ServiceResponse;
+    use actix_web::{App, HttpResponse, test, web};
+
+    async fn call_with(options: SecurityHeadersOptions) -> ServiceResponse {
+        let app = test::init_service(
+            App::new()
+                .wrap(middleware(&options))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/").to_request();
+        test::call_service(&app, req).await
+    }
+
+    fn header<'a>(resp: &'a ServiceResponse, name: &str) -> Option<&'a str> {
+        resp.headers().get(name).and_then(|v| v.to_str().ok())
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_defaults() {
+        let resp = call_with(SecurityHeadersOptions::default()).await;
+
+        assert_eq!(
+            header(&resp, "Content-Security-Policy"),
+            Some(DEFAULT_CONTENT_SECURITY_POLICY)
+        );
+        assert_eq!(header(&resp, "X-Content-Type-Options"), Some("nosniff"));
+        assert_eq!(
+            header(&resp, "Referrer-Policy"),
+            Some("strict-origin-when-cross-origin")
+        );
+        assert_eq!(
+            header(&resp, "Strict-Transport-Security"),
+            Some("max-age=31536000; includeSubDomains")
+        );
+        assert_eq!(
+            header(&resp, "Cross-Origin-Opener-Policy"),
+            Some("same-origin")
+        );
+        assert_eq!(
+            header(&resp, "Cross-Origin-Embedder-Policy"),
+            Some("require-corp")
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_custom_policy() {
+        let options = SecurityHeadersOptions {
+            content_security_policy: "default-src 'self' https://cdn.example.com".to_string(),
+            ..Default::default()
+        };
+        let resp = call_with(options).await;
+
+        assert_eq!(
+            header(&resp, "Content-Security-Policy"),
+            Some("default-src 'self' https://cdn.example.com"),
+            "Configured policy should replace the default"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_hsts_disabled() {
+        let options = SecurityHeadersOptions {
+            hsts_max_age: Duration::ZERO,
+            ..Default::default()
+        };
+        let resp = call_with(options).await;
+
+        assert_eq!(
+            header(&resp, "Strict-Transport-Security"),
+            None,
+            "HSTS should not be sent with max age of zero"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_middleware_without_cross_origin_isolation() {
+        let options = SecurityHeadersOptions {
+            cross_origin_isolation: false,
+            ..Default::default()
+        };
+        let resp = call_with(options).await;
+
+        assert_eq!(header(&resp, "Cross-Origin-Opener-Policy"), None);
+        assert_eq!(header(&resp, "Cross-Origin-Embedder-Policy"), None);
+        assert_eq!(
+            header(&resp, "X-Content-Type-Options"),
+            Some("nosniff"),
+            "Other headers should still be sent"
+        );
+    }
+}
@@ -1,5 +1,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::runtime_config::RuntimeConfig;
+use crate::user_type::UserType;
+
+// Returns the upload size limit of the user class in bytes before encryption, `None` if the
+// class is not limited
+//
+// Limits of tokens take precedence over the limit of their class. The server only sees
+// encrypted data, so limits cannot depend on whether the secret is a text or a file.
+pub fn for_user_type(user_type: &UserType, config: &RuntimeConfig) -> Option<usize> {
+    match user_type {
+        UserType::Anonymous => Some(config.anonymous_usage.upload_size_limit),
+        UserType::Authenticated | UserType::Identity => config.token_upload_size_limit,
+        UserType::Whitelisted => config.whitelisted_upload_size_limit,
+    }
+}
+
 // Calculates the size limit accounting for the overhead of a secret
 //
 // The 1.5x factor accounts for:
@@ -15,10 +31,50 @@ pub fn calculate(configured_limit: usize) -> usize {
     configured_limit.saturating_mul(3).saturating_div(2)
 }
 
+// Calculates the size limit for secrets uploaded as binary
+//
+// Binary uploads are not base64 encoded, so the limit is the decoded size of the
+// limit for JSON uploads (3 bytes for every 4 bytes of base64).
+pub fn calculate_binary(configured_limit: usize) -> usize {
+    calculate(configured_limit)
+        .saturating_div(4)
+        .saturating_mul(3)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_for_user_type() {
+        let config = RuntimeConfig {
+            token_upload_size_limit: Some(1024 * 1024),
+            whitelisted_upload_size_limit: None,
+            ..RuntimeConfig::default()
+        };
+
+        assert_eq!(
+            for_user_type(&UserType::Anonymous, &config),
+            Some(32 * 1024),
+            "Anonymous users should be limited by the anonymous limit"
+        );
+        assert_eq!(
+            for_user_type(&UserType::Authenticated, &config),
+            Some(1024 * 1024),
+            "Token users should be limited by the token limit"
+        );
+        assert_eq!(
+            for_user_type(&UserType::Identity, &config),
+            Some(1024 * 1024),
+            "Identity users should be limited by the token limit"
+        );
+        assert_eq!(
+            for_user_type(&UserType::Whitelisted, &config),
+            None,
+            "Whitelisted users should not be limited without a configured limit"
+        );
+    }
+
     #[test]
     fn test_calculate_typical_sizes() {
         // Test common size limits
@@ -40,6 +96,21 @@ mod tests {
         ); // 100MB -> 150MB
     }
 
+    #[test]
+    fn test_calculate_binary() {
+        assert_eq!(
+            calculate_binary(1024),
+            1152,
+            "1KB should calculate to the decoded size of 1.5KB base64"
+        );
+        assert_eq!(calculate_binary(0), 0, "Zero input should return zero");
+        assert_eq!(
+            calculate_binary(usize::MAX),
+            usize::MAX / 2 / 4 * 3,
+            "Large limits should not overflow"
+        );
+    }
+
     #[test]
     fn test_calculate_small_sizes() {
         assert_eq!(calculate(0), 0, "Zero input should return zero");
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::header;
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{Error, FromRequest, HttpRequest, error};
+use futures_util::StreamExt;
+
+use hakanai_lib::utils::human_size;
+
+use super::size_limit;
+use super::user::User;
+
+/// Upper bound of the buffer allocated in advance based on the Content-Length header.
+const MAX_PREALLOCATION: usize = 1024 * 1024; // 1MB
+
+/// Raw body extractor that enforces size limits based on user's upload limit
+///
+/// Used for binary uploads, the limit does not include the base64 overhead of JSON uploads.
+pub struct SizeLimitedBody(pub Bytes);
+
+impl SizeLimitedBody {
+    pub fn into_inner(self) -> Bytes {
+        self.0
+    }
+}
+
+impl FromRequest for SizeLimitedBody {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let mut payload = payload.take();
+
+        Box::pin(async move {
+            let user = User::extract(&req).await?;
+            let size_limit = user.upload_size_limit.map(size_limit::calculate_binary);
+
+            let body = read(&req, &mut payload, size_limit).await?;
+            Ok(SizeLimitedBody(body))
+        })
+    }
+}
+
+/// Reads the request body while enforcing the size limit.
+///
+/// Requests announcing a larger body via Content-Length are rejected before reading any data.
+/// Otherwise the body is read chunk by chunk and rejected as soon as the limit is crossed, so
+/// oversized bodies are never buffered completely.
+pub async fn read(
+    req: &HttpRequest,
+    payload: &mut Payload,
+    size_limit: Option<usize>,
+) -> Result<Bytes, Error> {
+    let content_length = content_length(req);
+    if let (Some(limit), Some(length)) = (size_limit, content_length) {
+        ensure_within_limit(length, limit)?;
+    }
+
+    let capacity = content_length
+        .unwrap_or_default()
+        .min(size_limit.unwrap_or(usize::MAX))
+        .min(MAX_PREALLOCATION);
+    let mut body = BytesMut::with_capacity(capacity);
+
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk
+            .map_err(|e| error::ErrorBadRequest(format!("Failed to read request body: {e}")))?;
+
+        if let Some(limit) = size_limit {
+            ensure_within_limit(body.len() + chunk.len(), limit)?;
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+fn content_length(req: &HttpRequest) -> Option<usize> {
+    req.headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+fn ensure_within_limit(size: usize, limit: usize) -> Result<(), Error> {
+    if size > limit {
+        return Err(error::ErrorPayloadTooLarge(format!(
+            "Upload size limit exceeded. Maximum allowed: {}",
+            human_size::format_size(limit as u64)
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test;
+
+    async fn read_body(req: test::TestRequest, limit: Option<usize>) -> Result<Bytes, Error> {
+        let (req, mut payload) = req.to_http_parts();
+        read(&req, &mut payload, limit).await
+    }
+
+    #[actix_web::test]
+    async fn test_read_within_limit() -> Result<(), Error> {
+        let req = test::TestRequest::post().set_payload("hello");
+
+        let body = read_body(req, Some(5)).await?;
+        assert_eq!(body, Bytes::from_static(b"hello"));
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_read_without_limit() -> Result<(), Error> {
+        let req = test::TestRequest::post().set_payload("x".repeat(2048));
+
+        let body = read_body(req, None).await?;
+        assert_eq!(body.len(), 2048);
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_read_rejects_announced_size() {
+        let req = test::TestRequest::post()
+            .set_payload("x")
+            .insert_header((header::CONTENT_LENGTH, "1000000"));
+
+        let err = read_body(req, Some(1024))
+            .await
+            .expect_err("Announced size above limit should be rejected");
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_read_rejects_body_exceeding_announced_size() {
+        let req = test::TestRequest::post()
+            .set_payload("x".repeat(2048))
+            .insert_header((header::CONTENT_LENGTH, "10"));
+
+        let err = read_body(req, Some(1024))
+            .await
+            .expect_err("Body above limit should be rejected while reading");
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+}
@@ -5,19 +5,20 @@ use std::pin::Pin;
 
 use actix_web::dev::Payload;
 use actix_web::{Error, FromRequest, HttpRequest, error};
-use futures_util::StreamExt;
 use serde::de::DeserializeOwned;
 
 use super::size_limit;
+use super::size_limited_body;
 use super::user::User;
 
 /// Custom JSON extractor that enforces size limits based on user's upload limit
 ///
 /// This extractor:
 /// 1. Extracts the User to get the size limit
-/// 2. Reads the payload while enforcing the size limit during streaming
-/// 3. Fails fast if the size limit is exceeded
-/// 4. Parses the JSON after the complete payload is validated
+/// 2. Rejects requests announcing a body larger than the limit via Content-Length
+/// 3. Reads the payload while enforcing the size limit during streaming
+/// 4. Fails fast if the size limit is exceeded
+/// 5. Parses the JSON after the complete payload is validated
 pub struct SizeLimitedJson<T>(pub T);
 
 impl<T> SizeLimitedJson<T> {
@@ -41,27 +42,7 @@ where
             let user = User::extract(&req).await?;
             let size_limit = user.upload_size_limit.map(size_limit::calculate);
 
-            // Stream the payload and enforce size limit during upload
-            let mut body = actix_web::web::BytesMut::new();
-            let mut total_size = 0usize;
-
-            while let Some(chunk) = payload.next().await {
-                let chunk = chunk.map_err(|e| {
-                    error::ErrorBadRequest(format!("Failed to read request body: {e}"))
-                })?;
-
-                total_size += chunk.len();
-
-                if let Some(limit) = size_limit
-                    && total_size > limit
-                {
-                    return Err(error::ErrorPayloadTooLarge(format!(
-                        "Upload size limit exceeded. Maximum allowed: {limit} bytes"
-                    )));
-                }
-
-                body.extend_from_slice(&chunk);
-            }
+            let body = size_limited_body::read(&req, &mut payload, size_limit).await?;
 
             let json = serde_json::from_slice::<T>(&body)
                 .map_err(|e| error::ErrorBadRequest(format!("Invalid JSON: {e}")))?;
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_multipart::Multipart;
+use actix_web::dev::Payload;
+use actix_web::error::PayloadError;
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{Error, FromRequest, HttpRequest, error};
+use futures_util::{StreamExt, stream};
+
+use super::size_limit;
+use super::size_limited_body;
+use super::user::User;
+
+/// Allowance for boundaries, part headers and metadata fields of multipart uploads.
+const MULTIPART_OVERHEAD: usize = 16 * 1024; // 16KB
+
+/// Multipart form extractor that enforces size limits based on user's upload limit
+///
+/// The complete body is read with the limit of binary uploads (plus the multipart overhead)
+/// before the form is parsed, so oversized uploads are rejected without being buffered.
+/// Fields are returned by name, duplicate fields are rejected.
+pub struct SizeLimitedMultipart(pub HashMap<String, Bytes>);
+
+impl SizeLimitedMultipart {
+    pub fn into_inner(self) -> HashMap<String, Bytes> {
+        self.0
+    }
+}
+
+impl FromRequest for SizeLimitedMultipart {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let mut payload = payload.take();
+
+        Box::pin(async move {
+            let user = User::extract(&req).await?;
+            let size_limit = user.upload_size_limit.map(body_limit);
+
+            let body = size_limited_body::read(&req, &mut payload, size_limit).await?;
+            let fields = parse(&req, body).await?;
+            Ok(SizeLimitedMultipart(fields))
+        })
+    }
+}
+
+/// Returns the limit of the whole form for the upload size limit of the user.
+pub fn body_limit(upload_size_limit: usize) -> usize {
+    size_limit::calculate_binary(upload_size_limit).saturating_add(MULTIPART_OVERHEAD)
+}
+
+/// Parses the fields of the multipart form in the body.
+async fn parse(req: &HttpRequest, body: Bytes) -> Result<HashMap<String, Bytes>, Error> {
+    let body = stream::once(async move { Ok::<_, PayloadError>(body) });
+    let mut multipart = Multipart::new(req.headers(), body);
+
+    let mut fields = HashMap::new();
+    while let Some(field) = multipart.next().await {
+        let mut field = field.map_err(invalid_form)?;
+        let name = field
+            .name()
+            .ok_or_else(|| error::ErrorBadRequest("Invalid multipart form: unnamed field"))?
+            .to_string();
+
+        let mut value = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            value.extend_from_slice(&chunk.map_err(invalid_form)?);
+        }
+
+        if fields.insert(name.clone(), value.freeze()).is_some() {
+            return Err(error::ErrorBadRequest(format!(
+                "Invalid multipart form: duplicate field {name}"
+            )));
+        }
+    }
+
+    Ok(fields)
+}
+
+fn invalid_form(e: impl std::fmt::Display) -> Error {
+    error::ErrorBadRequest(format!("Invalid multipart form: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test;
+
+    const BOUNDARY: &str = "hakanai-boundary";
+
+    fn form(fields: &[(&str, &str)]) -> String {
+        let mut body = String::new();
+        for (name, value) in fields {
+            body.push_str(&format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            ));
+        }
+        body.push_str(&format!("--{BOUNDARY}--\r\n"));
+        body
+    }
+
+    async fn parse_form(body: String) -> Result<HashMap<String, Bytes>, Error> {
+        let req = test::TestRequest::post()
+            .insert_header((
+                "Content-Type",
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            ))
+            .to_http_request();
+        parse(&req, Bytes::from(body)).await
+    }
+
+    #[actix_web::test]
+    async fn test_parse_fields() -> Result<(), Error> {
+        let fields = parse_form(form(&[("secret", "ciphertext"), ("ttl", "3600")])).await?;
+
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields["secret"], Bytes::from_static(b"ciphertext"));
+        assert_eq!(fields["ttl"], Bytes::from_static(b"3600"));
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_parse_duplicate_field() {
+        let result = parse_form(form(&[("ttl", "3600"), ("ttl", "60")])).await;
+
+        assert!(
+            result.is_err(),
+            "Duplicate fields should be rejected to avoid ambiguity"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_parse_not_multipart() {
+        let req = test::TestRequest::post()
+            .insert_header(("Content-Type", "application/json"))
+            .to_http_request();
+
+        let result = parse(&req, Bytes::from_static(b"{}")).await;
+        assert!(result.is_err(), "Non-multipart bodies should be rejected");
+    }
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration with systemd service management.
+//!
+//! Supports socket activation (listening sockets passed via `LISTEN_FDS`) and the notify
+//! protocol (`Type=notify`) including the watchdog (`WatchdogSec=`). Without systemd all functions
+//! are no-ops.
+
+use std::io::Result;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use listenfd::ListenFd;
+use tracing::info;
+
+/// Returns the TCP listeners passed by systemd socket activation, empty if not socket activated.
+pub fn inherited_listeners() -> Result<Vec<TcpListener>> {
+    let mut listen_fd = ListenFd::from_env();
+    let mut listeners = Vec::with_capacity(listen_fd.len());
+
+    for index in 0..listen_fd.len() {
+        if let Some(listener) = listen_fd.take_tcp_listener(index)? {
+            info!(
+                "Using socket {} passed by systemd",
+                listener
+                    .local_addr()
+                    .map_or("(unknown)".to_string(), |addr| addr.to_string())
+            );
+            listeners.push(listener);
+        }
+    }
+
+    Ok(listeners)
+}
+
+/// Notifies systemd that the server is ready to accept connections and starts the watchdog.
+#[cfg(unix)]
+pub fn notify_ready() {
+    use sd_notify::NotifyState;
+
+    notify(&[NotifyState::Ready, NotifyState::Status("Serving requests")]);
+
+    let mut usec = 0;
+    if !sd_notify::watchdog_enabled(false, &mut usec) {
+        return;
+    }
+
+    let interval = watchdog_interval(usec);
+    info!("systemd watchdog enabled, notifying every {interval:?}");
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            notify(&[NotifyState::Watchdog]);
+        }
+    });
+}
+
+/// Notifies systemd that the server is shutting down.
+#[cfg(unix)]
+pub fn notify_stopping() {
+    notify(&[sd_notify::NotifyState::Stopping]);
+}
+
+#[cfg(unix)]
+fn notify(state: &[sd_notify::NotifyState]) {
+    if let Err(e) = sd_notify::notify(false, state) {
+        tracing::debug!("Failed to notify systemd: {e}");
+    }
+}
+
+#[cfg(not(unix))]
+pub fn notify_ready() {}
+
+#[cfg(not(unix))]
+pub fn notify_stopping() {}
+
+/// Returns the interval of watchdog notifications, half of the timeout as recommended by systemd.
+fn watchdog_interval(timeout_usec: u64) -> Duration {
+    Duration::from_micros(timeout_usec / 2).max(Duration::from_millis(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inherited_listeners_without_socket_activation() -> Result<()> {
+        assert!(
+            inherited_listeners()?.is_empty(),
+            "No listeners should be inherited without LISTEN_FDS"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_watchdog_interval() {
+        assert_eq!(watchdog_interval(30_000_000), Duration::from_secs(15));
+        assert_eq!(watchdog_interval(0), Duration::from_millis(1));
+    }
+}
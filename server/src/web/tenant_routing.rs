@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routing of requests to tenants.
+//!
+//! Every tenant has its own `AppData` and `AssetManager`, built per worker like the data of the
+//! default tenant. Requests matching a tenant get the data of the tenant added to their data
+//! resolution set, so handlers transparently use the token namespace, limits and branding of the
+//! tenant. Path prefixes are removed before routing, e.g. `/team-a/api/v1/secret` is routed as
+//! `/api/v1/secret`.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use actix_web::dev::{Extensions, ServiceRequest};
+use actix_web::http::Uri;
+use actix_web::http::uri::PathAndQuery;
+use actix_web::web;
+use tracing::warn;
+
+use super::app_data::AppData;
+use super::web_assets::AssetManager;
+use crate::tenant::{TenantMatch, TenantResolver};
+
+/// Routes requests to the data of the matching tenant.
+#[derive(Default)]
+pub struct TenantRouting {
+    resolver: TenantResolver,
+    data: HashMap<String, Rc<Extensions>>,
+}
+
+impl TenantRouting {
+    pub fn new(resolver: TenantResolver) -> Self {
+        Self {
+            resolver,
+            data: HashMap::new(),
+        }
+    }
+
+    /// Registers the data of the tenant referenced by `app_data.tenant`.
+    pub fn add_tenant(&mut self, app_data: AppData, asset_manager: AssetManager) {
+        let Some(tenant_id) = app_data.tenant.as_ref().map(|tenant| tenant.id.clone()) else {
+            return;
+        };
+
+        let mut extensions = Extensions::new();
+        extensions.insert(web::Data::new(app_data));
+        extensions.insert(web::Data::new(asset_manager));
+        self.data.insert(tenant_id, Rc::new(extensions));
+    }
+
+    /// Resolves the tenant of the request and adds its data, if any.
+    pub fn route(&self, req: &mut ServiceRequest) {
+        let host = req.connection_info().host().to_string();
+        let Some(tenant_match) = self.resolver.resolve(&host, req.path()) else {
+            return;
+        };
+
+        if let TenantMatch::PathPrefix(tenant) = &tenant_match
+            && let Some(prefix) = &tenant.path_prefix
+        {
+            strip_path_prefix(req, prefix);
+        }
+
+        match self.data.get(&tenant_match.tenant().id) {
+            Some(data) => req.add_data_container(data.clone()),
+            None => warn!("No data registered for tenant {}", tenant_match.tenant().id),
+        }
+    }
+}
+
+fn strip_path_prefix(req: &mut ServiceRequest, prefix: &str) {
+    let uri = req.uri();
+    let path = uri.path().strip_prefix(prefix).unwrap_or(uri.path());
+    let path = if path.is_empty() { "/" } else { path };
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = path_and_query.parse::<PathAndQuery>().ok();
+    match Uri::from_parts(parts) {
+        Ok(uri) => {
+            req.match_info_mut().get_mut().update(&uri);
+            req.head_mut().uri = uri;
+        }
+        Err(e) => warn!("Failed to remove path prefix {prefix}: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use actix_web::dev::Service;
+    use actix_web::{App, HttpRequest, test};
+
+    use crate::tenant::Tenant;
+
+    fn tenant() -> Arc<Tenant> {
+        Arc::new(Tenant {
+            id: "team-a".to_string(),
+            hosts: vec!["a.example.com".to_string()],
+            path_prefix: Some("/team-a".to_string()),
+            upload_size_limit: None,
+            anonymous_upload_size_limit: None,
+            allow_anonymous: None,
+            max_ttl: None,
+            custom_assets_dir: None,
+        })
+    }
+
+    async fn whoami(req: HttpRequest, app_data: web::Data<AppData>) -> String {
+        let tenant = app_data
+            .tenant
+            .as_ref()
+            .map_or("default".to_string(), |tenant| tenant.id.clone());
+        format!("{tenant} {}", req.uri())
+    }
+
+    async fn call(host: &str, uri: &str) -> String {
+        let tenant = tenant();
+        let mut routing = TenantRouting::new(TenantResolver::new(vec![tenant.clone()]));
+        routing.add_tenant(
+            AppData::default().with_tenant(tenant),
+            AssetManager::new(None),
+        );
+        let routing = Rc::new(routing);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppData::default()))
+                .wrap_fn(move |mut req, srv| {
+                    routing.route(&mut req);
+                    srv.call(req)
+                })
+                .route("/whoami", web::get().to(whoami)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Host", host))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200, "{uri} on {host} should be routed");
+        String::from_utf8(test::read_body(resp).await.to_vec()).unwrap_or_default()
+    }
+
+    #[actix_web::test]
+    async fn test_route_default_tenant() {
+        assert_eq!(call("example.com", "/whoami").await, "default /whoami");
+    }
+
+    #[actix_web::test]
+    async fn test_route_by_host() {
+        assert_eq!(
+            call("a.example.com", "/whoami?x=1").await,
+            "team-a /whoami?x=1"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_route_by_path_prefix() {
+        assert_eq!(
+            call("example.com", "/team-a/whoami?x=1").await,
+            "team-a /whoami?x=1",
+            "Path prefix should be removed before routing"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::{Error, Result};
+use std::sync::Arc;
+
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+use crate::listen_address::TlsFiles;
+
+/// Builds the TLS configuration of a listener from its PEM certificate and key files.
+pub fn load_server_config(tls: &TlsFiles) -> Result<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(&tls.cert_file)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| {
+            Error::other(format!(
+                "failed to read TLS certificate {}: {e}",
+                tls.cert_file.display()
+            ))
+        })?;
+    if certs.is_empty() {
+        return Err(Error::other(format!(
+            "no certificate found in {}",
+            tls.cert_file.display()
+        )));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(&tls.key_file).map_err(|e| {
+        Error::other(format!(
+            "failed to read TLS private key {}: {e}",
+            tls.key_file.display()
+        ))
+    })?;
+
+    let mut config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(Error::other)?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| {
+                Error::other(format!(
+                    "invalid TLS certificate or key {}: {e}",
+                    tls.cert_file.display()
+                ))
+            })?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_server_config_missing_files() {
+        let tls = TlsFiles {
+            cert_file: PathBuf::from("/nonexistent/cert.pem"),
+            key_file: PathBuf::from("/nonexistent/key.pem"),
+        };
+
+        let error = load_server_config(&tls).err().map(|e| e.to_string());
+        assert!(
+            error.is_some_and(|e| e.contains("/nonexistent/cert.pem")),
+            "Error should name the missing file"
+        );
+    }
+
+    #[test]
+    fn test_load_server_config_without_certificate() -> Result<()> {
+        let mut cert_file = tempfile::NamedTempFile::new()?;
+        write!(cert_file, "not a certificate")?;
+        cert_file.flush()?;
+        let tls = TlsFiles {
+            cert_file: cert_file.path().to_path_buf(),
+            key_file: cert_file.path().to_path_buf(),
+        };
+
+        assert!(
+            load_server_config(&tls).is_err(),
+            "File without certificate should be rejected"
+        );
+        Ok(())
+    }
+}
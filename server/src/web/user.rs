@@ -4,14 +4,22 @@ use std::future::Future;
 use std::pin::Pin;
 
 use actix_web::dev::Payload;
-use actix_web::{Error, FromRequest, HttpRequest, error};
-use tracing::warn;
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, error};
+use tracing::{debug, warn};
+
+use hakanai_lib::models::{TokenScope, secret};
+use hakanai_lib::utils::hashing;
 
 use super::app_data::AppData;
+use super::size_limit;
+use crate::auth::{AuthError, Identity};
 use crate::token::TokenError;
 use crate::user_type::UserType;
 use crate::web::filters::is_request_from_whitelisted_ip;
 
+/// Error code returned if a one-time token has already been used.
+pub const TOKEN_EXHAUSTED_ERROR: &str = "token_exhausted";
+
 /// Represents a user in the system, either authenticated or anonymous
 #[derive(Clone, Debug)]
 pub struct User {
@@ -19,6 +27,12 @@ pub struct User {
     pub upload_size_limit: Option<usize>,
     /// The type of user (anonymous or authenticated)
     pub user_type: UserType,
+    /// The scope of the token used to authenticate (unrestricted for non token users)
+    pub scope: TokenScope,
+    /// Name of the user, if authenticated by an identity provider
+    pub name: Option<String>,
+    /// Hashed identifier used to track the secrets of authenticated users
+    pub owner: Option<String>,
 }
 
 impl User {
@@ -27,22 +41,54 @@ impl User {
         Self {
             upload_size_limit,
             user_type: UserType::Authenticated,
+            scope: TokenScope::default(),
+            name: None,
+            owner: None,
+        }
+    }
+
+    /// Create a user authenticated by an identity provider with the upload limit of its class
+    pub fn identity(identity: &Identity, upload_size_limit: Option<usize>) -> Self {
+        Self {
+            upload_size_limit,
+            user_type: UserType::Identity,
+            scope: TokenScope::default(),
+            name: Some(identity.display_name().to_string()),
+            owner: Some(owner_id("oidc", &identity.subject)),
         }
     }
 
+    /// Track the secrets of the user by the token used to authenticate
+    pub fn with_token_owner(mut self, token: &str) -> Self {
+        self.owner = Some(owner_id("token", token));
+        self
+    }
+
+    /// Restrict the user to the scope of its token
+    pub fn with_scope(mut self, scope: TokenScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
     /// Create an anonymous user with a specific upload limit
     pub fn anonymous(upload_size_limit: usize) -> Self {
         Self {
             upload_size_limit: Some(upload_size_limit),
             user_type: UserType::Anonymous,
+            scope: TokenScope::default(),
+            name: None,
+            owner: None,
         }
     }
 
-    /// Create an whitelisted user without a specific upload limit
-    pub fn whitelisted() -> Self {
+    /// Create a whitelisted user with the upload limit of its class
+    pub fn whitelisted(upload_size_limit: Option<usize>) -> Self {
         Self {
-            upload_size_limit: None,
+            upload_size_limit,
             user_type: UserType::Whitelisted,
+            scope: TokenScope::default(),
+            name: None,
+            owner: None,
         }
     }
 }
@@ -55,17 +101,34 @@ impl FromRequest for User {
         let req = req.clone();
 
         Box::pin(async move {
+            // the user is extracted by the expect service and extractors of the same request, a
+            // one-time token must only be validated once
+            if let Some(user) = req.extensions().get::<User>() {
+                return Ok(user.clone());
+            }
+
             let app_data = get_app_data(&req)?;
             let token = extract_token_from_header(&req);
 
-            match token {
+            let user = match token {
+                Some(token) if is_identity_token(&token, &app_data) => {
+                    handle_identity_request(token, app_data).await
+                }
                 Some(token) => handle_authenticated_request(token, app_data).await,
-                None => handle_anonymous_request(app_data, req),
-            }
+                None => handle_anonymous_request(app_data, req.clone()),
+            }?;
+
+            req.extensions_mut().insert(user.clone());
+            Ok(user)
         })
     }
 }
 
+/// Derive the owner identifier, so no credentials or personal data are stored with secrets
+fn owner_id(kind: &str, value: &str) -> String {
+    hashing::sha256_hex_from_string(&format!("{kind}:{value}"))
+}
+
 /// Extract the application data from the request
 fn get_app_data(req: &HttpRequest) -> Result<actix_web::web::Data<AppData>, Error> {
     req.app_data::<actix_web::web::Data<AppData>>()
@@ -74,7 +137,7 @@ fn get_app_data(req: &HttpRequest) -> Result<actix_web::web::Data<AppData>, Erro
 }
 
 /// Extract and clean the token from the Authorization header
-fn extract_token_from_header(req: &HttpRequest) -> Option<String> {
+pub(super) fn extract_token_from_header(req: &HttpRequest) -> Option<String> {
     req.headers()
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
@@ -88,10 +151,16 @@ async fn handle_authenticated_request(
 ) -> Result<User, Error> {
     match app_data.token_validator.validate_user_token(&token).await {
         Ok(token_data) => {
-            let upload_size_limit = extract_upload_limit(token_data);
-            Ok(User::authenticated(upload_size_limit))
+            let scope = token_data.scope.clone();
+            let upload_size_limit = extract_upload_limit(token_data).or_else(|| {
+                size_limit::for_user_type(&UserType::Authenticated, &app_data.runtime_config())
+            });
+            Ok(User::authenticated(upload_size_limit)
+                .with_scope(scope)
+                .with_token_owner(&token))
         }
         Err(TokenError::InvalidToken) => Err(error::ErrorForbidden("Invalid token")),
+        Err(TokenError::TokenExhausted) => Err(error::ErrorForbidden(TOKEN_EXHAUSTED_ERROR)),
         Err(e) => {
             warn!("Token validation failed: {}", e);
             Err(error::ErrorInternalServerError("Token validation failed"))
@@ -99,6 +168,44 @@ async fn handle_authenticated_request(
     }
 }
 
+/// Checks if the token has to be validated by the identity provider
+fn is_identity_token(token: &str, app_data: &AppData) -> bool {
+    app_data
+        .identity_validator
+        .as_ref()
+        .is_some_and(|validator| validator.is_identity_token(token))
+}
+
+/// Handle a request with a token issued by the identity provider
+async fn handle_identity_request(
+    token: String,
+    app_data: actix_web::web::Data<AppData>,
+) -> Result<User, Error> {
+    let Some(validator) = app_data.identity_validator.as_ref() else {
+        return Err(error::ErrorInternalServerError(
+            "Identity provider not configured",
+        ));
+    };
+
+    match validator.validate_identity(&token).await {
+        Ok(identity) => {
+            let upload_size_limit =
+                size_limit::for_user_type(&UserType::Identity, &app_data.runtime_config());
+            Ok(User::identity(&identity, upload_size_limit))
+        }
+        Err(AuthError::InvalidToken(reason)) => {
+            debug!("Identity token rejected: {reason}");
+            Err(error::ErrorForbidden("Invalid token"))
+        }
+        Err(e) => {
+            warn!("Identity token validation failed: {}", e);
+            Err(error::ErrorServiceUnavailable(
+                "Identity provider unavailable",
+            ))
+        }
+    }
+}
+
 /// Extract the upload size limit from token data
 fn extract_upload_limit(token_data: crate::token::TokenData) -> Option<usize> {
     token_data.upload_size_limit.map(|limit| limit as usize)
@@ -110,11 +217,17 @@ fn handle_anonymous_request(
     req: HttpRequest,
 ) -> Result<User, Error> {
     if is_request_from_whitelisted_ip(&req, &app_data) {
-        return Ok(User::whitelisted());
+        let upload_size_limit =
+            size_limit::for_user_type(&UserType::Whitelisted, &app_data.runtime_config());
+        return Ok(User::whitelisted(upload_size_limit));
     }
 
-    if app_data.anonymous_usage.allowed {
-        Ok(User::anonymous(app_data.anonymous_usage.upload_size_limit))
+    let anonymous_usage = &app_data.runtime_config().anonymous_usage;
+    // uploads to a reserved ID (e.g. replies) are authorized by the upload token, which is
+    // redeemed when the secret is created
+    let has_upload_token = req.headers().contains_key(secret::UPLOAD_TOKEN_HEADER_NAME);
+    if anonymous_usage.allowed || has_upload_token {
+        Ok(User::anonymous(anonymous_usage.upload_size_limit))
     } else {
         Err(error::ErrorUnauthorized("Authorization token required"))
     }
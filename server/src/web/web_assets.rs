@@ -1,8 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use thiserror::Error;
@@ -11,11 +14,130 @@ use thiserror::Error;
 pub enum AssetError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Content encodings of the pre-compressed embedded assets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Brotli,
+    Gzip,
+}
+
+impl ContentEncoding {
+    /// Value of the encoding in the Content-Encoding header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// Selects the preferred encoding accepted by the client (brotli before gzip).
+    ///
+    /// Encodings with a quality value of zero are treated as not accepted.
+    pub fn negotiate(accept_encoding: Option<&str>) -> Option<Self> {
+        let accepted: Vec<&str> = accept_encoding
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';').map(str::trim);
+                let encoding = params.next()?;
+                let rejected = params
+                    .filter_map(|p| p.strip_prefix("q="))
+                    .any(|q| q.parse::<f32>().is_ok_and(|q| q <= 0.0));
+                (!rejected).then_some(encoding)
+            })
+            .collect();
+
+        [ContentEncoding::Brotli, ContentEncoding::Gzip]
+            .into_iter()
+            .find(|encoding| {
+                accepted
+                    .iter()
+                    .any(|a| a.eq_ignore_ascii_case(encoding.as_str()))
+            })
+    }
+}
+
+/// Asset embedded into the binary with variants pre-compressed at build time.
+pub struct EmbeddedAsset {
+    pub content: &'static [u8],
+    pub brotli: &'static [u8],
+    pub gzip: &'static [u8],
+}
+
+/// Embeds an asset of the includes directory with its pre-compressed variants.
+macro_rules! embedded_asset {
+    ($name:literal) => {
+        $crate::web::web_assets::EmbeddedAsset {
+            content: include_bytes!(concat!("../../includes/", $name)),
+            brotli: include_bytes!(concat!(env!("OUT_DIR"), "/", $name, ".br")),
+            gzip: include_bytes!(concat!(env!("OUT_DIR"), "/", $name, ".gz")),
+        }
+    };
+}
+pub(crate) use embedded_asset;
+
+/// Asset content in the encoding to serve to the client.
+pub struct EncodedAsset {
+    pub content: Cow<'static, [u8]>,
+    pub encoding: Option<ContentEncoding>,
+}
+
+impl EmbeddedAsset {
+    fn encoded(&self, encoding: Option<ContentEncoding>) -> EncodedAsset {
+        let content = match encoding {
+            Some(ContentEncoding::Brotli) => self.brotli,
+            Some(ContentEncoding::Gzip) => self.gzip,
+            None => self.content,
+        };
+
+        EncodedAsset {
+            content: Cow::Borrowed(content),
+            encoding,
+        }
+    }
+}
+
+/// Name of the custom asset defining the branding of the instance.
+const BRANDING_ASSET_NAME: &str = "branding.json";
+
+/// Interval after which custom assets are checked for modifications by default.
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Branding of the instance, loaded from `branding.json` in the custom assets directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Branding {
+    /// Name of the instance shown instead of the project name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_name: Option<String>,
+
+    /// Additional links shown in the footer.
+    #[serde(default)]
+    pub footer_links: Vec<FooterLink>,
+}
+
+/// Link shown in the footer of all pages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FooterLink {
+    pub label: String,
+    pub url: String,
+}
+
+struct CachedAsset {
+    content: Vec<u8>,
+    modified: Option<SystemTime>,
+    checked_at: Instant,
 }
 
 pub struct AssetManager {
     custom_dir: Option<PathBuf>,
-    cache: RwLock<HashMap<String, Vec<u8>>>,
+    reload_interval: Duration,
+    cache: RwLock<HashMap<String, CachedAsset>>,
 }
 
 impl AssetManager {
@@ -23,26 +145,45 @@ impl AssetManager {
     pub fn new(custom_dir: Option<PathBuf>) -> Self {
         AssetManager {
             custom_dir,
+            reload_interval: DEFAULT_RELOAD_INTERVAL,
             cache: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Set the interval after which cached custom assets are checked for modifications.
+    ///
+    /// A zero interval disables hot reloading, custom assets are then loaded only once.
+    pub fn with_reload_interval(mut self, reload_interval: Duration) -> Self {
+        self.reload_interval = reload_interval;
+        self
+    }
+
+    /// Get the custom asset if it exists.
+    pub async fn get_custom_asset(&self, name: &str) -> Result<Option<Vec<u8>>, AssetError> {
+        if let Some(cached) = self.asset_from_cache(name).await? {
+            return Ok(Some(cached));
+        }
+
+        if let Some((content, modified)) = self.get_custom(name).await? {
+            self.insert_into_cache(name, content.clone(), modified)
+                .await;
+            return Ok(Some(content));
+        }
+
+        Ok(None)
+    }
+
     /// Get the embedded asset or an custom asset if it exists.
     pub async fn get_embedded_asset_or_custom(
         &self,
         name: &str,
         original_content: &[u8],
     ) -> Result<Vec<u8>, AssetError> {
-        if let Some(cached) = self.asset_from_cache(name).await? {
-            return Ok(cached.clone());
-        }
-
-        if let Some(content) = self.get_custom(name).await? {
-            self.insert_into_cache(name, content.clone()).await?;
-            return Ok(content);
-        }
-
-        Ok(original_content.to_vec())
+        let content = self
+            .get_custom_asset(name)
+            .await?
+            .unwrap_or_else(|| original_content.to_vec());
+        Ok(content)
     }
 
     /// Get the embedded asset and append any custom content if it exists.
@@ -51,44 +192,126 @@ impl AssetManager {
         name: &str,
         original_content: &[u8],
     ) -> Result<Vec<u8>, AssetError> {
-        if let Some(cached) = self.asset_from_cache(name).await? {
-            return Ok(cached.clone());
-        }
-
         let mut result_content = original_content.to_vec();
 
-        if let Some(content) = self.get_custom(name).await? {
+        if let Some(content) = self.get_custom_asset(name).await? {
             result_content.push(b'\n');
             result_content.extend_from_slice(&content);
-            self.insert_into_cache(name, result_content.clone()).await?;
         }
 
         Ok(result_content)
     }
 
+    /// Get the branding of the instance, defaults if no `branding.json` exists.
+    pub async fn get_branding(&self) -> Result<Branding, AssetError> {
+        match self.get_custom_asset(BRANDING_ASSET_NAME).await? {
+            Some(content) => Ok(serde_json::from_slice(&content)?),
+            None => Ok(Branding::default()),
+        }
+    }
+
+    /// Get the embedded asset in the preferred encoding accepted by the client.
+    pub fn get_embedded_asset_encoded(
+        &self,
+        asset: &EmbeddedAsset,
+        accept_encoding: Option<&str>,
+    ) -> EncodedAsset {
+        asset.encoded(ContentEncoding::negotiate(accept_encoding))
+    }
+
+    /// Get the embedded asset in the preferred encoding accepted by the client and append any
+    /// custom content if it exists.
+    ///
+    /// Assets with custom content are served uncompressed, as only the embedded asset is
+    /// pre-compressed.
+    pub async fn get_embedded_asset_encoded_append_custom(
+        &self,
+        name: &str,
+        asset: &EmbeddedAsset,
+        accept_encoding: Option<&str>,
+    ) -> Result<EncodedAsset, AssetError> {
+        let content = self
+            .get_embedded_asset_append_custom(name, asset.content)
+            .await?;
+        if content != asset.content {
+            return Ok(EncodedAsset {
+                content: Cow::Owned(content),
+                encoding: None,
+            });
+        }
+
+        Ok(self.get_embedded_asset_encoded(asset, accept_encoding))
+    }
+
     async fn asset_from_cache(&self, name: &str) -> Result<Option<Vec<u8>>, AssetError> {
-        let cache = self.cache.read().await;
-        Ok(cache.get(name).cloned())
+        let modified = {
+            let cache = self.cache.read().await;
+            let Some(cached) = cache.get(name) else {
+                return Ok(None);
+            };
+
+            if self.reload_interval.is_zero() || cached.checked_at.elapsed() < self.reload_interval
+            {
+                return Ok(Some(cached.content.clone()));
+            }
+
+            cached.modified
+        };
+
+        let current = self.custom_modified(name).await?;
+        let mut cache = self.cache.write().await;
+        match cache.get_mut(name) {
+            Some(cached) if current.is_some() && current == modified => {
+                cached.checked_at = Instant::now();
+                Ok(Some(cached.content.clone()))
+            }
+            _ => {
+                cache.remove(name);
+                Ok(None)
+            }
+        }
     }
 
-    async fn insert_into_cache(&self, name: &str, content: Vec<u8>) -> Result<(), AssetError> {
+    async fn insert_into_cache(&self, name: &str, content: Vec<u8>, modified: Option<SystemTime>) {
         let mut cache = self.cache.write().await;
-        cache.insert(name.to_string(), content);
-        Ok(())
+        cache.insert(
+            name.to_string(),
+            CachedAsset {
+                content,
+                modified,
+                checked_at: Instant::now(),
+            },
+        );
     }
 
-    async fn get_custom(&self, name: &str) -> Result<Option<Vec<u8>>, std::io::Error> {
+    async fn get_custom(
+        &self,
+        name: &str,
+    ) -> Result<Option<(Vec<u8>, Option<SystemTime>)>, std::io::Error> {
         if let Some(dir) = &self.custom_dir {
             let path = dir.join(name);
 
             if path.exists() {
+                let modified = tokio::fs::metadata(&path).await?.modified().ok();
                 let content = tokio::fs::read(path).await?;
-                return Ok(Some(content));
+                return Ok(Some((content, modified)));
             }
         }
 
         Ok(None)
     }
+
+    async fn custom_modified(&self, name: &str) -> Result<Option<SystemTime>, std::io::Error> {
+        let Some(dir) = &self.custom_dir else {
+            return Ok(None);
+        };
+
+        match tokio::fs::metadata(dir.join(name)).await {
+            Ok(metadata) => Ok(metadata.modified().ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -265,4 +488,191 @@ mod tests {
         );
         Ok(())
     }
+
+    fn set_modified(path: &PathBuf, modified: SystemTime) -> std::io::Result<()> {
+        fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(modified)
+    }
+
+    #[tokio::test]
+    async fn test_get_or_custom_reloads_modified_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = create_test_file(&temp_dir, "logo.svg", b"first logo")?;
+
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()))
+            .with_reload_interval(Duration::from_millis(1));
+        let original = b"original logo";
+
+        let result1 = manager
+            .get_embedded_asset_or_custom("logo.svg", original)
+            .await?;
+        assert_eq!(result1, b"first logo");
+
+        fs::write(&path, b"second logo")?;
+        set_modified(&path, SystemTime::now() + Duration::from_secs(60))?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let result2 = manager
+            .get_embedded_asset_or_custom("logo.svg", original)
+            .await?;
+        assert_eq!(
+            result2, b"second logo",
+            "Modified file should be reloaded after the reload interval"
+        );
+
+        fs::remove_file(&path)?;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let result3 = manager
+            .get_embedded_asset_or_custom("logo.svg", original)
+            .await?;
+        assert_eq!(
+            result3, original,
+            "Deleted file should be evicted from cache after the reload interval"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_or_custom_reload_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = create_test_file(&temp_dir, "logo.svg", b"first logo")?;
+
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()))
+            .with_reload_interval(Duration::ZERO);
+
+        manager.get_custom_asset("logo.svg").await?;
+        fs::write(&path, b"second logo")?;
+        set_modified(&path, SystemTime::now() + Duration::from_secs(60))?;
+
+        let result = manager.get_custom_asset("logo.svg").await?;
+        assert_eq!(
+            result.as_deref(),
+            Some(b"first logo".as_slice()),
+            "Custom assets should not be reloaded with a zero reload interval"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_custom_asset_missing() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()));
+
+        let result = manager.get_custom_asset("favicon.ico").await?;
+        assert!(result.is_none(), "Missing custom asset should return None");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_branding() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_file(
+            &temp_dir,
+            "branding.json",
+            br#"{"instanceName":"ACME Secrets","footerLinks":[{"label":"Support","url":"https://example.com/support"}]}"#,
+        )?;
+
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()));
+        let branding = manager.get_branding().await?;
+        assert_eq!(branding.instance_name.as_deref(), Some("ACME Secrets"));
+        assert_eq!(
+            branding.footer_links,
+            vec![FooterLink {
+                label: "Support".to_string(),
+                url: "https://example.com/support".to_string(),
+            }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_branding_defaults() -> Result<()> {
+        let manager = AssetManager::new(None);
+
+        let branding = manager.get_branding().await?;
+        assert_eq!(
+            branding,
+            Branding::default(),
+            "Branding should default when no branding.json exists"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_branding_invalid() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_file(&temp_dir, "branding.json", b"not json")?;
+
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()));
+        let result = manager.get_branding().await;
+        assert!(
+            matches!(result, Err(AssetError::Json(_))),
+            "Invalid branding.json should return a JSON error"
+        );
+        Ok(())
+    }
+
+    const TEST_ASSET: EmbeddedAsset = EmbeddedAsset {
+        content: b"/* base */",
+        brotli: b"brotli",
+        gzip: b"gzip",
+    };
+
+    #[test]
+    fn test_negotiate_encoding() {
+        assert_eq!(
+            ContentEncoding::negotiate(Some("gzip, deflate, br, zstd")),
+            Some(ContentEncoding::Brotli),
+            "Brotli should be preferred"
+        );
+        assert_eq!(
+            ContentEncoding::negotiate(Some("gzip, deflate")),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate(Some("br;q=0, GZIP;q=0.5")),
+            Some(ContentEncoding::Gzip),
+            "Encodings with quality zero should not be used"
+        );
+        assert_eq!(ContentEncoding::negotiate(Some("identity")), None);
+        assert_eq!(ContentEncoding::negotiate(None), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_encoded_without_custom() -> Result<()> {
+        let manager = AssetManager::new(None);
+
+        let asset = manager
+            .get_embedded_asset_encoded_append_custom("style.css", &TEST_ASSET, Some("br"))
+            .await?;
+        assert_eq!(asset.encoding, Some(ContentEncoding::Brotli));
+        assert_eq!(asset.content.as_ref(), b"brotli");
+
+        let asset = manager
+            .get_embedded_asset_encoded_append_custom("style.css", &TEST_ASSET, None)
+            .await?;
+        assert_eq!(asset.encoding, None);
+        assert_eq!(asset.content.as_ref(), b"/* base */");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_encoded_with_custom() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_file(&temp_dir, "style.css", b"/* custom */")?;
+
+        let manager = AssetManager::new(Some(temp_dir.path().to_path_buf()));
+        let asset = manager
+            .get_embedded_asset_encoded_append_custom("style.css", &TEST_ASSET, Some("br, gzip"))
+            .await?;
+        assert_eq!(
+            asset.encoding, None,
+            "Assets with custom content should be served uncompressed"
+        );
+        assert_eq!(asset.content.as_ref(), b"/* base */\n/* custom */");
+        Ok(())
+    }
 }
@@ -1,16 +1,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use actix_web::http::header;
-use actix_web::{HttpRequest, HttpResponse, Responder, web};
-use tracing::error;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder, web};
+use tracing::{error, warn};
+
+use hakanai_lib::models::CountryCode;
+use hakanai_lib::utils::hashing;
 
 use super::app_data::AppData;
 use super::filters;
-use super::web_assets::AssetManager;
+use super::language;
+use super::user::User;
+use super::web_assets::{AssetManager, Branding, EmbeddedAsset, EncodedAsset, embedded_asset};
 
 const DEFAULT_CACHE_MAX_AGE: u64 = 604800; // 7 days
 const VOLATILE_CACHE_MAX_AGE: u64 = 86400; // 1 day
 const HIGHLY_VOLATILE_CACHE_MAX_AGE: u64 = 300; // 5 minutes
+const API_VERSIONS: &[&str] = &["v1"];
+
+static STYLE_CSS: EmbeddedAsset = embedded_asset!("style.min.css");
+static GET_SECRET_JS: EmbeddedAsset = embedded_asset!("get-secret.js");
+static CREATE_SECRET_JS: EmbeddedAsset = embedded_asset!("create-secret.js");
+static SHARE_JS: EmbeddedAsset = embedded_asset!("share.js");
+static ONE_TIME_TOKEN_JS: EmbeddedAsset = embedded_asset!("one-time-token.js");
+static ADMIN_JS: EmbeddedAsset = embedded_asset!("admin.js");
+static COMMON_JS: EmbeddedAsset = embedded_asset!("common.js");
+static SERVICE_WORKER_JS: EmbeddedAsset = embedded_asset!("sw.js");
+static WASM_JS: EmbeddedAsset = embedded_asset!("hakanai_wasm.js");
+static WASM_BINARY: EmbeddedAsset = embedded_asset!("hakanai_wasm_bg.wasm");
 
 /// Configures the Actix Web services for the application.
 ///
@@ -27,10 +44,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .route("/create", web::get().to(serve_create_secret_html))
         .route("/create-secret.js", web::get().to(serve_create_secret_js))
         .route("/docs", web::get().to(serve_docs_html))
+        .route("/favicon.ico", web::get().to(serve_favicon))
         .route("/get", web::get().to(serve_get_secret_html))
         .route("/get-secret.js", web::get().to(serve_get_secret_js))
         .route("/hakanai_wasm.js", web::get().to(serve_wasm_js))
         .route("/hakanai_wasm_bg.wasm", web::get().to(serve_wasm_binary))
+        .route("/i18n.json", web::get().to(serve_negotiated_language_pack))
+        .route("/i18n/{lang}.json", web::get().to(serve_language_pack))
         .route("/icon.svg", web::get().to(serve_icon))
         .route("/impressum", web::get().to(serve_impressum))
         .route("/logo.svg", web::get().to(serve_logo))
@@ -47,43 +67,132 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .route("/sw.js", web::get().to(serve_service_worker));
 }
 
-fn serve_with_caching_header(content: &[u8], content_type: &str, max_age: u64) -> HttpResponse {
-    static ETAG: &str = concat!("\"", env!("CARGO_PKG_VERSION"), "\"");
+/// Configures the routes of the admin dashboard.
+///
+/// Only registered if the admin token is enabled, the page itself is only served to trusted IPs.
+pub fn configure_admin(cfg: &mut web::ServiceConfig) {
+    cfg.route("/admin", web::get().to(serve_admin_html))
+        .route("/admin.js", web::get().to(serve_admin_js));
+}
+
+/// Serves the content with caching headers and an ETag derived from the content.
+///
+/// Returns 304 Not Modified if the ETag matches the `If-None-Match` header of the request.
+fn serve_with_caching_header(
+    req: &HttpRequest,
+    content: &[u8],
+    content_type: &str,
+    max_age: u64,
+) -> HttpResponse {
+    let etag = format!(
+        "\"{}\"",
+        hashing::sha256_truncated_base64_from_bytes(content)
+    );
+    let cache_control = format!("public, max-age={max_age}");
+
+    if is_etag_matching(req, &etag) {
+        return HttpResponse::NotModified()
+            .insert_header((header::CACHE_CONTROL, cache_control))
+            .insert_header((header::ETAG, etag))
+            .finish();
+    }
 
     HttpResponse::Ok()
         .content_type(content_type)
-        .insert_header((header::CACHE_CONTROL, format!("public, max-age={max_age}")))
-        .insert_header((header::ETAG, ETAG))
+        .insert_header((header::CACHE_CONTROL, cache_control))
+        .insert_header((header::ETAG, etag))
         .body(content.to_vec())
 }
 
+/// Serves the embedded asset in the preferred encoding accepted by the client.
+fn serve_embedded_asset(
+    req: &HttpRequest,
+    asset_manager: &AssetManager,
+    asset: &EmbeddedAsset,
+    content_type: &str,
+    max_age: u64,
+) -> HttpResponse {
+    let encoded = asset_manager.get_embedded_asset_encoded(asset, accept_encoding(req).as_deref());
+    serve_encoded_with_caching_header(req, &encoded, content_type, max_age)
+}
+
+/// Serves the encoded content with caching headers, see `serve_with_caching_header`.
+///
+/// The ETag differs per encoding, as each encoding is a different representation of the asset.
+fn serve_encoded_with_caching_header(
+    req: &HttpRequest,
+    asset: &EncodedAsset,
+    content_type: &str,
+    max_age: u64,
+) -> HttpResponse {
+    let mut resp = serve_with_caching_header(req, &asset.content, content_type, max_age);
+    let modified = resp.status().is_success();
+
+    let headers = resp.headers_mut();
+    headers.insert(
+        header::VARY,
+        header::HeaderValue::from_static("Accept-Encoding"),
+    );
+    if let Some(encoding) = asset.encoding
+        && modified
+    {
+        headers.insert(
+            header::CONTENT_ENCODING,
+            header::HeaderValue::from_static(encoding.as_str()),
+        );
+    }
+
+    resp
+}
+
+fn accept_encoding(req: &HttpRequest) -> Option<String> {
+    filters::extract_header_value(req, header::ACCEPT_ENCODING.as_str())
+}
+
+/// Checks the `If-None-Match` header of the request against the ETag (weak comparison).
+fn is_etag_matching(req: &HttpRequest, etag: &str) -> bool {
+    let Some(if_none_match) = filters::extract_header_value(req, header::IF_NONE_MATCH.as_str())
+    else {
+        return false;
+    };
+
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag.trim_start_matches("W/")
+    })
+}
+
 /// Serves the HTML page for getting a secret
-pub async fn serve_get_secret_html() -> HttpResponse {
+pub async fn serve_get_secret_html(req: HttpRequest) -> HttpResponse {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/get-secret.html"),
         "text/html",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_create_secret_html() -> HttpResponse {
+async fn serve_create_secret_html(req: HttpRequest) -> HttpResponse {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/create-secret.html"),
         "text/html",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_css(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_css(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
     let asset_res = asset_manager
-        .get_embedded_asset_append_custom(
+        .get_embedded_asset_encoded_append_custom(
             "style.css",
-            include_bytes!("../../includes/style.min.css"),
+            &STYLE_CSS,
+            accept_encoding(&req).as_deref(),
         )
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "text/css", VOLATILE_CACHE_MAX_AGE),
+        Ok(asset) => {
+            serve_encoded_with_caching_header(&req, &asset, "text/css", VOLATILE_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load CSS asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -91,13 +200,15 @@ async fn serve_css(asset_manager: web::Data<AssetManager>) -> impl Responder {
     }
 }
 
-async fn serve_banner(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_banner(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom("banner.svg", include_bytes!("../../../banner.svg"))
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load banner asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -105,13 +216,28 @@ async fn serve_banner(asset_manager: web::Data<AssetManager>) -> impl Responder
     }
 }
 
-async fn serve_logo(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_favicon(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
+    match asset_manager.get_custom_asset("favicon.ico").await {
+        Ok(Some(content)) => {
+            serve_with_caching_header(&req, &content, "image/x-icon", DEFAULT_CACHE_MAX_AGE)
+        }
+        Ok(None) => HttpResponse::NotFound().body("No favicon configured"),
+        Err(e) => {
+            error!("Failed to load favicon asset: {e}");
+            HttpResponse::InternalServerError().body("Internal Server Error")
+        }
+    }
+}
+
+async fn serve_logo(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom("logo.svg", include_bytes!("../../../logo.svg"))
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load logo asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -119,13 +245,15 @@ async fn serve_logo(asset_manager: web::Data<AssetManager>) -> impl Responder {
     }
 }
 
-async fn serve_icon(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_icon(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom("icon.svg", include_bytes!("../../../icons/icon.svg"))
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load icon asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -133,7 +261,10 @@ async fn serve_icon(asset_manager: web::Data<AssetManager>) -> impl Responder {
     }
 }
 
-async fn serve_app_icon(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_app_icon(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom(
             "app-icon.svg",
@@ -142,7 +273,9 @@ async fn serve_app_icon(asset_manager: web::Data<AssetManager>) -> impl Responde
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/svg+xml", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load app-icon asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -150,7 +283,10 @@ async fn serve_app_icon(asset_manager: web::Data<AssetManager>) -> impl Responde
     }
 }
 
-async fn serve_app_icon_192(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_app_icon_192(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom(
             "app-icon-192.png",
@@ -159,7 +295,9 @@ async fn serve_app_icon_192(asset_manager: web::Data<AssetManager>) -> impl Resp
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/png", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/png", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load app-icon (192x192) asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -167,7 +305,10 @@ async fn serve_app_icon_192(asset_manager: web::Data<AssetManager>) -> impl Resp
     }
 }
 
-async fn serve_app_icon_512(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_app_icon_512(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom(
             "app-icon-512.png",
@@ -176,7 +317,9 @@ async fn serve_app_icon_512(asset_manager: web::Data<AssetManager>) -> impl Resp
         .await;
 
     match asset_res {
-        Ok(content) => serve_with_caching_header(&content, "image/png", DEFAULT_CACHE_MAX_AGE),
+        Ok(content) => {
+            serve_with_caching_header(&req, &content, "image/png", DEFAULT_CACHE_MAX_AGE)
+        }
         Err(e) => {
             error!("Failed to load app-icon (512x512) asset: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -184,47 +327,63 @@ async fn serve_app_icon_512(asset_manager: web::Data<AssetManager>) -> impl Resp
     }
 }
 
-async fn serve_get_secret_js() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/get-secret.js"),
+async fn serve_get_secret_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &GET_SECRET_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_create_secret_js() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/create-secret.js"),
+async fn serve_create_secret_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &CREATE_SECRET_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_docs_html() -> impl Responder {
+async fn serve_docs_html(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_str!("../../includes/docs.html").as_bytes(),
         "text/html",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_openapi_yaml() -> impl Responder {
+async fn serve_openapi_yaml(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_str!("../../includes/openapi.yaml").as_bytes(),
         "application/yaml",
         DEFAULT_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_index() -> HttpResponse {
+async fn serve_index(req: HttpRequest) -> HttpResponse {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/index.html"),
         "text/html",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_manifest(asset_manager: web::Data<AssetManager>) -> impl Responder {
+async fn serve_manifest(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
     let asset_res = asset_manager
         .get_embedded_asset_or_custom(
             "manifest.json",
@@ -233,9 +392,12 @@ async fn serve_manifest(asset_manager: web::Data<AssetManager>) -> impl Responde
         .await;
 
     match asset_res {
-        Ok(content) => {
-            serve_with_caching_header(&content, "application/manifest+json", DEFAULT_CACHE_MAX_AGE)
-        }
+        Ok(content) => serve_with_caching_header(
+            &req,
+            &content,
+            "application/manifest+json",
+            DEFAULT_CACHE_MAX_AGE,
+        ),
         Err(e) => {
             error!("Failed to load PWA manifest: {e}");
             HttpResponse::InternalServerError().body("Internal Server Error")
@@ -243,145 +405,302 @@ async fn serve_manifest(asset_manager: web::Data<AssetManager>) -> impl Responde
     }
 }
 
-async fn serve_robots_txt() -> impl Responder {
+async fn serve_language_pack(
+    req: HttpRequest,
+    path: web::Path<String>,
+    asset_manager: web::Data<AssetManager>,
+) -> HttpResponse {
+    serve_language(&req, &asset_manager, &path.into_inner()).await
+}
+
+/// Serves the language pack preferred by the client based on the `Accept-Language` header.
+async fn serve_negotiated_language_pack(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> HttpResponse {
+    let accept_language = filters::extract_header_value(&req, header::ACCEPT_LANGUAGE.as_str());
+    let lang = language::negotiate(accept_language.as_deref());
+
+    let mut resp = serve_language(&req, &asset_manager, lang).await;
+    resp.headers_mut().insert(
+        header::VARY,
+        header::HeaderValue::from_static("Accept-Language"),
+    );
+    resp
+}
+
+/// Serves the language pack, custom packs in the `i18n` directory of the custom assets
+/// extend or override the embedded translations.
+async fn serve_language(
+    req: &HttpRequest,
+    asset_manager: &AssetManager,
+    lang: &str,
+) -> HttpResponse {
+    if !language::is_valid_code(lang) {
+        return HttpResponse::NotFound().body("Language not found");
+    }
+
+    let custom = match asset_manager
+        .get_custom_asset(&format!("i18n/{lang}.json"))
+        .await
+    {
+        Ok(custom) => custom,
+        Err(e) => {
+            error!("Failed to load custom language pack {lang}: {e}");
+            return HttpResponse::InternalServerError().body("Internal Server Error");
+        }
+    };
+
+    let pack = match (language::embedded_pack(lang), custom) {
+        (Some(embedded), Some(custom)) => language::merge(embedded, &custom).unwrap_or_else(|e| {
+            warn!("Ignoring invalid custom language pack {lang}: {e}");
+            embedded.to_vec()
+        }),
+        (Some(embedded), None) => embedded.to_vec(),
+        (None, Some(custom)) => custom,
+        (None, None) => return HttpResponse::NotFound().body("Language not found"),
+    };
+
+    let mut resp =
+        serve_with_caching_header(req, &pack, "application/json", VOLATILE_CACHE_MAX_AGE);
+    if let Ok(value) = header::HeaderValue::from_str(lang) {
+        resp.headers_mut().insert(header::CONTENT_LANGUAGE, value);
+    }
+    resp
+}
+
+async fn serve_robots_txt(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/robots.txt"),
         "text/plain",
         DEFAULT_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_impressum(app_data: web::Data<AppData>) -> impl Responder {
-    match &app_data.impressum_html {
-        Some(html) => HttpResponse::Ok()
-            .content_type("text/html; charset=utf-8")
-            .insert_header((
-                header::CACHE_CONTROL,
-                format!("public, max-age={DEFAULT_CACHE_MAX_AGE}"),
-            ))
-            .insert_header((
-                header::ETAG,
-                concat!("\"", env!("CARGO_PKG_VERSION"), "-impressum\""),
-            ))
-            .body(html.clone()),
+async fn serve_impressum(req: HttpRequest, app_data: web::Data<AppData>) -> impl Responder {
+    match &app_data.runtime_config().impressum_html {
+        Some(html) => serve_with_caching_header(
+            &req,
+            html.as_bytes(),
+            "text/html; charset=utf-8",
+            DEFAULT_CACHE_MAX_AGE,
+        ),
         None => HttpResponse::NotFound().body("No impressum configured"),
     }
 }
 
-async fn serve_privacy(app_data: web::Data<AppData>) -> impl Responder {
-    match &app_data.privacy_html {
-        Some(html) => HttpResponse::Ok()
-            .content_type("text/html; charset=utf-8")
-            .insert_header((
-                header::CACHE_CONTROL,
-                format!("public, max-age={DEFAULT_CACHE_MAX_AGE}"),
-            ))
-            .insert_header((
-                header::ETAG,
-                concat!("\"", env!("CARGO_PKG_VERSION"), "-privacy\""),
-            ))
-            .body(html.clone()),
+async fn serve_privacy(req: HttpRequest, app_data: web::Data<AppData>) -> impl Responder {
+    match &app_data.runtime_config().privacy_html {
+        Some(html) => serve_with_caching_header(
+            &req,
+            html.as_bytes(),
+            "text/html; charset=utf-8",
+            DEFAULT_CACHE_MAX_AGE,
+        ),
         None => HttpResponse::NotFound().body("No privacy policy configured"),
     }
 }
 
-async fn serve_config(app_data: web::Data<AppData>, req: HttpRequest) -> impl Responder {
-    let whitelisted = filters::is_request_from_whitelisted_ip(&req, &app_data);
-    let size_limit = if whitelisted {
-        app_data.upload_size_limit
-    } else if app_data.anonymous_usage.allowed {
-        app_data.anonymous_usage.upload_size_limit
+async fn serve_config(
+    app_data: web::Data<AppData>,
+    asset_manager: web::Data<AssetManager>,
+    req: HttpRequest,
+) -> impl Responder {
+    let runtime_config = app_data.runtime_config();
+    // the effective limit of the caller, 0 if the caller is not allowed to create secrets
+    let size_limit = match User::extract(&req).await {
+        Ok(user) => user
+            .upload_size_limit
+            .unwrap_or(runtime_config.upload_size_limit),
+        Err(_) => 0,
+    };
+
+    let ttl_presets: Vec<u64> = app_data
+        .ttl_presets()
+        .iter()
+        .map(|ttl| ttl.as_secs())
+        .collect();
+
+    let branding = asset_manager.get_branding().await.unwrap_or_else(|e| {
+        warn!("Failed to load branding, using defaults: {e}");
+        Branding::default()
+    });
+
+    // offered in the country restriction of the web UI
+    let countries: Vec<serde_json::Value> = if app_data.country_header.is_some() {
+        CountryCode::all()
+            .map(|code| serde_json::json!({ "code": code.as_str(), "name": code.name() }))
+            .collect()
     } else {
-        0
+        Vec::new()
     };
 
     let config = serde_json::json!({
-        "showTokenInput": app_data.show_token_input || !app_data.anonymous_usage.allowed,
+        "showTokenInput": app_data.show_token_input || !runtime_config.anonymous_usage.allowed,
         "features": {
-            "impressum": app_data.impressum_html.is_some(),
-            "privacy": app_data.privacy_html.is_some(),
+            "impressum": runtime_config.impressum_html.is_some(),
+            "privacy": runtime_config.privacy_html.is_some(),
             "restrictions": {
               "country": app_data.country_header.is_some(),
               "asn": app_data.asn_header.is_some(),
-            }
+              "pin": app_data.pin_delivery.is_some(),
+            },
+            "rawUpload": true,
+            "multipartUpload": true,
+            "shortAliases": app_data.short_aliases,
+            "clientIds": true,
+            "secretMetadata": true,
+            "replies": true,
         },
         "secretSizeLimit": size_limit,
+        "apiVersions": API_VERSIONS,
+        "version": env!("CARGO_PKG_VERSION"),
+        "minTtl": app_data.min_ttl.as_secs(),
+        "maxTtl": app_data.max_ttl.as_secs(),
+        "anonymousAllowed": runtime_config.anonymous_usage.allowed,
+        "ttlPresets": ttl_presets,
+        "countries": countries,
+        "branding": branding,
+        "ui": {
+            "defaultTheme": app_data.ui.default_theme,
+            "forceHighContrast": app_data.ui.force_high_contrast,
+            "hideBranding": app_data.ui.hide_branding,
+        },
     });
 
     HttpResponse::Ok()
         .content_type("application/json")
-        .insert_header((header::CACHE_CONTROL, "public, max-age=300")) // 5 minutes cache
+        // 5 minutes cache, private as the size limit depends on the caller
+        .insert_header((header::CACHE_CONTROL, "private, max-age=300"))
+        .insert_header((header::VARY, "Authorization"))
         .insert_header((header::ETAG, env!("CARGO_PKG_VERSION")))
         .json(config)
 }
 
-async fn serve_share_html() -> impl Responder {
+async fn serve_share_html(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/share.html"),
         "text/html",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_share_js() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/share.js"),
+async fn serve_share_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &SHARE_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_one_time_token_html() -> impl Responder {
+async fn serve_one_time_token_html(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../includes/one-time-token.html"),
         "text/html",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_one_time_token_js() -> impl Responder {
+async fn serve_one_time_token_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &ONE_TIME_TOKEN_JS,
+        "application/javascript",
+        VOLATILE_CACHE_MAX_AGE,
+    )
+}
+
+async fn serve_admin_html(app_data: web::Data<AppData>, req: HttpRequest) -> HttpResponse {
+    if !filters::is_request_from_whitelisted_ip(&req, &app_data) {
+        return HttpResponse::Forbidden().body("Request IP not allowed to access admin dashboard");
+    }
+
     serve_with_caching_header(
-        include_bytes!("../../includes/one-time-token.js"),
+        &req,
+        include_bytes!("../../includes/admin.html"),
+        "text/html",
+        HIGHLY_VOLATILE_CACHE_MAX_AGE,
+    )
+}
+
+async fn serve_admin_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &ADMIN_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_shortcut() -> impl Responder {
+async fn serve_shortcut(req: HttpRequest) -> impl Responder {
     serve_with_caching_header(
+        &req,
         include_bytes!("../../../share.shortcut"),
         "application/octet-stream",
         DEFAULT_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_common_js() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/common.js"),
+async fn serve_common_js(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &COMMON_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_service_worker() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/sw.js"),
+async fn serve_service_worker(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &SERVICE_WORKER_JS,
         "application/javascript",
         VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_wasm_js() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/hakanai_wasm.js"),
+async fn serve_wasm_js(req: HttpRequest, asset_manager: web::Data<AssetManager>) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &WASM_JS,
         "application/javascript",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
 }
 
-async fn serve_wasm_binary() -> impl Responder {
-    serve_with_caching_header(
-        include_bytes!("../../includes/hakanai_wasm_bg.wasm"),
+async fn serve_wasm_binary(
+    req: HttpRequest,
+    asset_manager: web::Data<AssetManager>,
+) -> impl Responder {
+    serve_embedded_asset(
+        &req,
+        &asset_manager,
+        &WASM_BINARY,
         "application/wasm",
         HIGHLY_VOLATILE_CACHE_MAX_AGE,
     )
@@ -392,9 +711,11 @@ mod tests {
     use super::*;
     use actix_web::{App, test, web};
 
+    use hakanai_lib::models::ServerConfig;
     use hakanai_lib::utils::test::MustParse;
 
-    use crate::web::app_data::{AnonymousOptions, AppData};
+    use crate::options::UiTheme;
+    use crate::web::app_data::{AnonymousOptions, AppData, UiOptions};
 
     fn create_test_app_data() -> AppData {
         AppData::default()
@@ -411,6 +732,7 @@ mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -422,6 +744,40 @@ mod tests {
         let body: serde_json::Value = test::read_body_json(resp).await;
         assert_eq!(body["features"]["impressum"], false);
         assert_eq!(body["features"]["privacy"], false);
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_countries() {
+        for (country_header, expected) in [(None, 0), (Some("x-country".to_string()), 249)] {
+            let app_data = create_test_app_data().with_country_header(country_header);
+            let app = test::init_service(
+                App::new()
+                    .app_data(web::Data::new(app_data))
+                    .app_data(web::Data::new(AssetManager::new(None)))
+                    .route("/config.json", web::get().to(serve_config)),
+            )
+            .await;
+
+            let req = test::TestRequest::get().uri("/config.json").to_request();
+            let resp = test::call_service(&app, req).await;
+            let body: serde_json::Value = test::read_body_json(resp).await;
+
+            let countries = body["countries"]
+                .as_array()
+                .expect("countries should be an array");
+            assert_eq!(
+                countries.len(),
+                expected,
+                "Countries should only be listed if country restrictions are supported"
+            );
+            if expected > 0 {
+                assert_eq!(
+                    countries[0],
+                    serde_json::json!({ "code": "AD", "name": "Andorra" })
+                );
+            }
+        }
     }
 
     #[actix_web::test]
@@ -430,6 +786,7 @@ mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -448,6 +805,7 @@ mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -505,6 +863,7 @@ mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -558,13 +917,13 @@ mod tests {
     #[actix_web::test]
     async fn test_serve_config_token_input_hidden_with_anonymous() {
         // show_token_input = false, anonymous allowed
-        let mut app_data = create_test_app_data();
+        let mut app_data = create_test_app_data().with_anonymous_allowed(true);
         app_data.show_token_input = false;
-        app_data.anonymous_usage.allowed = true;
 
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -578,13 +937,13 @@ mod tests {
     #[actix_web::test]
     async fn test_serve_config_token_input_explicitly_shown() {
         // show_token_input = true, anonymous allowed
-        let mut app_data = create_test_app_data();
+        let mut app_data = create_test_app_data().with_anonymous_allowed(true);
         app_data.show_token_input = true;
-        app_data.anonymous_usage.allowed = true;
 
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -598,13 +957,13 @@ mod tests {
     #[actix_web::test]
     async fn test_serve_config_token_input_forced_without_anonymous() {
         // show_token_input = false, anonymous NOT allowed (should force show)
-        let mut app_data = create_test_app_data();
+        let mut app_data = create_test_app_data().with_anonymous_allowed(false);
         app_data.show_token_input = false;
-        app_data.anonymous_usage.allowed = false;
 
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -617,7 +976,7 @@ mod tests {
 
     #[actix_web::test]
     async fn test_serve_config_secret_size_limit_anonymous() {
-        let expected = 1024 as usize;
+        let expected = 1024_usize;
         let app_data = create_test_app_data().with_anonymous_usage(AnonymousOptions {
             allowed: true,
             upload_size_limit: expected,
@@ -626,6 +985,7 @@ mod tests {
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -636,21 +996,50 @@ mod tests {
         assert_eq!(body["secretSizeLimit"], expected);
     }
 
+    #[actix_web::test]
+    async fn test_serve_config_secret_size_limit_whitelisted_class_limit() {
+        let app_data = create_test_app_data()
+            .with_trusted_ip_header("x-real-ip".to_string())
+            .with_trusted_ip_ranges(Some(vec!["127.0.0.1/32".must_parse()]))
+            .with_upload_size_limit(2048)
+            .with_whitelisted_upload_size_limit(Some(1536));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/config.json")
+            .insert_header(("x-real-ip", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body["secretSizeLimit"], 1536,
+            "Limit of the whitelisted class should be reported"
+        );
+    }
+
     #[actix_web::test]
     async fn test_serve_config_secret_size_limit_whitelisted() {
-        let limit = 1024 as usize;
-        let mut app_data = create_test_app_data()
+        let limit = 1024_usize;
+        let app_data = create_test_app_data()
             .with_anonymous_usage(AnonymousOptions {
                 allowed: true,
                 upload_size_limit: limit,
             })
             .with_trusted_ip_header("x-real-ip".to_string())
-            .with_trusted_ip_ranges(Some(vec!["127.0.0.1/32".must_parse()]));
-        app_data.upload_size_limit = 2048;
+            .with_trusted_ip_ranges(Some(vec!["127.0.0.1/32".must_parse()]))
+            .with_upload_size_limit(2048);
 
         let app = test::init_service(
             App::new()
                 .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
                 .route("/config.json", web::get().to(serve_config)),
         )
         .await;
@@ -663,4 +1052,409 @@ mod tests {
         let body: serde_json::Value = test::read_body_json(resp).await;
         assert_eq!(body["secretSizeLimit"], 2048);
     }
+
+    #[actix_web::test]
+    async fn test_serve_config_parses_as_server_config() -> Result<(), Box<dyn std::error::Error>> {
+        let app_data = create_test_app_data().with_country_header(Some("CF-IPCountry".to_string()));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let config: ServerConfig = test::read_body_json(resp).await;
+
+        assert_eq!(config.secret_size_limit, Some(32 * 1024));
+        assert_eq!(config.api_versions, vec!["v1".to_string()]);
+        assert_eq!(config.min_ttl, Some(std::time::Duration::ZERO));
+        assert_eq!(config.max_ttl, Some(std::time::Duration::from_secs(7200)));
+        assert_eq!(config.anonymous_allowed, Some(true));
+        assert_eq!(config.ttl_presets.len(), 4);
+        assert!(
+            config.features.restrictions.country,
+            "Country restrictions should be reported as supported"
+        );
+        assert!(
+            !config.features.restrictions.asn,
+            "ASN restrictions should be reported as unsupported"
+        );
+        assert!(
+            !config.features.restrictions.pin,
+            "PINs should be reported as unsupported without delivery"
+        );
+        assert!(
+            config.features.raw_upload,
+            "Binary uploads should be reported as supported"
+        );
+        assert!(
+            config.features.multipart_upload,
+            "Multipart uploads should be reported as supported"
+        );
+        assert!(
+            !config.features.short_aliases,
+            "Short aliases should be disabled by default"
+        );
+        assert!(
+            config.features.client_ids,
+            "Client proposed IDs should be reported as supported"
+        );
+        assert!(
+            config.features.secret_metadata,
+            "Secret metadata should be reported as supported"
+        );
+        assert!(
+            config.features.replies,
+            "Replies should be reported as supported"
+        );
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_ttl_policy() {
+        let app_data = create_test_app_data();
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(body["maxTtl"], 7200);
+        assert_eq!(body["anonymousAllowed"], true);
+        assert_eq!(
+            body["ttlPresets"],
+            serde_json::json!([300, 1800, 3600, 7200])
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_anonymous_not_allowed() {
+        let app_data = create_test_app_data().with_anonymous_usage(AnonymousOptions {
+            allowed: false,
+            upload_size_limit: 1024,
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(body["anonymousAllowed"], false);
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_ui_defaults() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(create_test_app_data()))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(
+            body["ui"]["defaultTheme"],
+            serde_json::Value::Null,
+            "Theme should follow the system preference by default"
+        );
+        assert_eq!(body["ui"]["forceHighContrast"], false);
+        assert_eq!(body["ui"]["hideBranding"], false);
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_ui_options() {
+        let app_data = create_test_app_data().with_ui(UiOptions {
+            default_theme: Some(UiTheme::Dark),
+            force_high_contrast: true,
+            hide_branding: true,
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(body["ui"]["defaultTheme"], "dark");
+        assert_eq!(body["ui"]["forceHighContrast"], true);
+        assert_eq!(body["ui"]["hideBranding"], true);
+    }
+
+    #[actix_web::test]
+    async fn test_serve_config_branding() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            temp_dir.path().join("branding.json"),
+            r#"{"instanceName":"ACME Secrets","footerLinks":[{"label":"Support","url":"/support"}]}"#,
+        )?;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(create_test_app_data()))
+                .app_data(web::Data::new(AssetManager::new(Some(
+                    temp_dir.path().to_path_buf(),
+                ))))
+                .route("/config.json", web::get().to(serve_config)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/config.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        let body: serde_json::Value = test::read_body_json(resp).await;
+
+        assert_eq!(body["branding"]["instanceName"], "ACME Secrets");
+        assert_eq!(body["branding"]["footerLinks"][0]["label"], "Support");
+        assert_eq!(body["branding"]["footerLinks"][0]["url"], "/support");
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_serve_favicon() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        std::fs::write(temp_dir.path().join("favicon.ico"), b"custom favicon")?;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AssetManager::new(Some(
+                    temp_dir.path().to_path_buf(),
+                ))))
+                .route("/favicon.ico", web::get().to(serve_favicon)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/favicon.ico").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers()
+                .get(header::CONTENT_TYPE)
+                .expect("Missing Content-Type"),
+            "image/x-icon"
+        );
+        assert_eq!(test::read_body(resp).await.as_ref(), b"custom favicon");
+
+        std::fs::remove_file(temp_dir.path().join("favicon.ico"))?;
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AssetManager::new(Some(
+                    temp_dir.path().to_path_buf(),
+                ))))
+                .route("/favicon.ico", web::get().to(serve_favicon)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/favicon.ico").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.status(),
+            404,
+            "Favicon should not be found without custom asset"
+        );
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_serve_language_pack() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        std::fs::create_dir(temp_dir.path().join("i18n"))?;
+        std::fs::write(
+            temp_dir.path().join("i18n/de.json"),
+            r#"{"page.create.title":"ACME - Secret erstellen"}"#,
+        )?;
+        std::fs::write(
+            temp_dir.path().join("i18n/fr.json"),
+            r#"{"page.create.title":"ACME - Créer un secret"}"#,
+        )?;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AssetManager::new(Some(
+                    temp_dir.path().to_path_buf(),
+                ))))
+                .configure(configure),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/i18n/de.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers()
+                .get(header::CONTENT_LANGUAGE)
+                .expect("Missing Content-Language"),
+            "de"
+        );
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body["page.create.title"], "ACME - Secret erstellen",
+            "Custom translations should override embedded ones"
+        );
+        assert_eq!(body["page.get.title"], "Hakanai - Secret abrufen");
+
+        let req = test::TestRequest::get().uri("/i18n/fr.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(
+            resp.status().is_success(),
+            "Custom languages should be served"
+        );
+
+        let req = test::TestRequest::get().uri("/i18n/es.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 404);
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_serve_negotiated_language_pack() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AssetManager::new(None)))
+                .configure(configure),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/i18n.json")
+            .insert_header((header::ACCEPT_LANGUAGE, "de-AT,de;q=0.9,en;q=0.8"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers()
+                .get(header::CONTENT_LANGUAGE)
+                .expect("Missing Content-Language"),
+            "de"
+        );
+        assert_eq!(
+            resp.headers().get(header::VARY).expect("Missing Vary"),
+            "Accept-Language"
+        );
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["page.get.title"], "Hakanai - Secret abrufen");
+    }
+
+    #[actix_web::test]
+    async fn test_serve_admin_from_trusted_ip() {
+        let app_data = create_test_app_data()
+            .with_trusted_ip_header("x-real-ip".to_string())
+            .with_trusted_ip_ranges(Some(vec!["127.0.0.1/32".must_parse()]));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .configure(configure_admin),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin")
+            .insert_header(("x-real-ip", "127.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_serve_admin_from_untrusted_ip() {
+        let app_data = create_test_app_data()
+            .with_trusted_ip_header("x-real-ip".to_string())
+            .with_trusted_ip_ranges(Some(vec!["127.0.0.1/32".must_parse()]));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .configure(configure_admin),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin")
+            .insert_header(("x-real-ip", "10.0.0.1"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(
+            resp.status(),
+            403,
+            "Admin page should only be served to trusted IPs"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_serve_with_caching_header_etag_from_content() {
+        let req = test::TestRequest::get().to_http_request();
+
+        let first = serve_with_caching_header(&req, b"content", "text/plain", 60);
+        let second = serve_with_caching_header(&req, b"other content", "text/plain", 60);
+
+        let etag = first.headers().get(header::ETAG).expect("Missing ETag");
+        assert_ne!(
+            Some(etag),
+            second.headers().get(header::ETAG),
+            "Different content should result in different ETags"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_serve_not_modified() {
+        let app =
+            test::init_service(App::new().route("/robots.txt", web::get().to(serve_robots_txt)))
+                .await;
+
+        let req = test::TestRequest::get().uri("/robots.txt").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let etag = resp
+            .headers()
+            .get(header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .expect("Missing ETag")
+            .to_string();
+
+        let req = test::TestRequest::get()
+            .uri("/robots.txt")
+            .insert_header((header::IF_NONE_MATCH, format!("\"outdated\", W/{etag}")))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.status(),
+            304,
+            "Matching ETag should not be served again"
+        );
+        let body = test::read_body(resp).await;
+        assert!(
+            body.is_empty(),
+            "Not modified response should not have a body"
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/robots.txt")
+            .insert_header((header::IF_NONE_MATCH, "\"outdated\""))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200, "Outdated ETag should be served again");
+    }
 }
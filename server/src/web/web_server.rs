@@ -2,27 +2,52 @@
 
 use core::option::Option;
 use std::io::Result;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use actix_cors::Cors;
+use actix_http::HttpService;
+use actix_service::map_config;
+use actix_web::dev::{AppConfig, Server, Service};
 use actix_web::middleware::{DefaultHeaders, Logger};
-use actix_web::{App, HttpResponse, HttpServer, Responder, http, web};
+use actix_web::{App, HttpResponse, Responder, http, web};
 use opentelemetry_instrumentation_actix_web::{RequestMetrics, RequestTracing};
 
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
+
+use hakanai_lib::utils::human_size;
 
 use super::admin_api;
-use super::app_data::{AnonymousOptions, AppData};
+use super::app_data::{AppData, DEFAULT_TTL_PRESETS, UiOptions};
+use super::expect_continue;
+use super::maintenance::MaintenanceMode;
+use super::qr_api;
+use super::rate_limiter::RateLimiter;
+use super::runtime_config::ConfigReloader;
+use super::security_headers::{self, SecurityHeadersOptions};
 use super::size_limit;
+use super::systemd;
+use super::tenant_routing::TenantRouting;
+use super::tls;
 use super::web_api;
 use super::web_assets::AssetManager;
 use super::web_routes;
+use crate::auth::{IdentityValidator, OidcValidator};
+use crate::listen_address::ListenAddress;
 use crate::metrics::{EventMetrics, MetricsObserver};
-use crate::observer::{ObserverManager, WebhookObserver};
-use crate::options::{Args, WebhookArgs};
+use crate::observer::{ChatNotifierObserver, ClientIpHasher, ObserverManager, build_pin_delivery};
+use crate::options::Args;
+use crate::policy::{CommandPolicy, ContentPolicy};
 use crate::secret::SecretStore;
 use crate::stats::{RedisStatsStore, StatsObserver};
+use crate::tenant::{Tenant, TenantResolver};
 use crate::token::{TokenCreator, TokenValidator};
 
+/// Time a client has to shut down the connection after the response was sent, like the default
+/// of `HttpServer`.
+const CLIENT_DISCONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub struct WebServerOptions {
     args: Args,
     event_metrics: Option<EventMetrics>,
@@ -45,9 +70,12 @@ impl WebServerOptions {
 }
 
 /// Starts the web server with the provided data store and tokens.
+///
+/// `tenants` contains the tenants with the token manager of their token namespace.
 pub async fn run_server<D, T>(
     secret_store: D,
     token_manager: T,
+    tenants: Vec<(Arc<Tenant>, T)>,
     options: WebServerOptions,
 ) -> Result<()>
 where
@@ -55,67 +83,188 @@ where
     T: TokenValidator + TokenCreator + Clone + 'static,
 {
     let args = options.args;
-    info!("Starting server on {}:{}", args.listen_address, args.port);
+    let listeners = systemd::inherited_listeners()?;
+    let default_port = args.port;
+    let listen_addresses: Vec<ListenAddress> = args
+        .listen_address
+        .iter()
+        .map(|address| address.with_default_port(default_port))
+        .collect();
+    if listeners.is_empty() {
+        for address in &listen_addresses {
+            info!("Starting server on {address}");
+        }
+    }
+    info!(
+        "Upload size limit: {} (anonymous: {})",
+        human_size::format_size(args.upload_size_limit as u64),
+        human_size::format_size(args.anonymous_upload_size_limit as u64)
+    );
 
-    let anonymous_usage = AnonymousOptions {
-        allowed: args.allow_anonymous,
-        upload_size_limit: args.anonymous_upload_size_limit,
-    };
+    let tenant_list: Vec<Arc<Tenant>> = tenants.iter().map(|(tenant, _)| tenant.clone()).collect();
+    for tenant in &tenant_list {
+        info!("Serving tenant {}", tenant.id);
+    }
+    let tenant_resolver = TenantResolver::new(tenant_list.clone());
 
-    let impressum_html = build_impressum_html(&args)?;
-    let privacy_html = build_privacy_html(&args)?;
+    // shared by all workers, so a reload affects the whole instance
+    let config_reloader = ConfigReloader::new(args.clone())
+        .map_err(std::io::Error::other)?
+        .with_tenants(&tenant_list);
+    #[cfg(unix)]
+    super::runtime_config::reload_on_sighup(config_reloader.clone())?;
 
-    let webhook_args_opt = args.webhook_args().clone();
+    let oidc_validator = build_oidc_validator(&args)?;
+    let security_headers_options = SecurityHeadersOptions::from(&args);
 
-    HttpServer::new(move || {
-        let mut observer_manager = ObserverManager::new();
-        if let Some(ref webhook_args) = webhook_args_opt {
-            add_webhook_observer(&mut observer_manager, webhook_args);
-        }
-        if let Some(event_metrics) = &options.event_metrics {
-            let metrics_observer = MetricsObserver::new(event_metrics.clone());
-            observer_manager.register_observer(Box::new(metrics_observer));
-        }
+    // shared by all workers, so toggling via admin API affects the whole instance
+    let maintenance = MaintenanceMode::new(args.maintenance_mode);
+    if args.maintenance_mode {
+        warn!("Maintenance mode enabled, new secrets are rejected");
+    }
 
-        let mut stats_observer = StatsObserver::new(options.stats_store.clone());
-        if let Some(event_metrics) = options.event_metrics.clone() {
-            stats_observer = stats_observer.with_event_metrics(event_metrics);
-        }
-        observer_manager.register_observer(Box::new(stats_observer));
+    // shared by all workers, so the limit applies to the whole instance
+    let qr_code_rate_limiter = RateLimiter::new(args.qr_code_rate_limit, Duration::from_secs(60));
 
-        let asset_manager = AssetManager::new(args.custom_assets_dir.clone());
-        let app_data = AppData {
+    // shared by all workers, so events of the same client have the same hash
+    let client_ip_hasher = ClientIpHasher::default();
+
+    let chat_notifiers = args
+        .chat_targets()
+        .into_iter()
+        .map(|target| ChatNotifierObserver::new(target, args.notify_events.clone()))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(std::io::Error::other)?;
+
+    let pin_delivery = args
+        .pin_delivery_target()
+        .map(|target| build_pin_delivery(target, args.pin_delivery_timeout))
+        .transpose()
+        .map_err(std::io::Error::other)?;
+    if let Some(pin_delivery) = &pin_delivery {
+        info!("Retrieval PINs are delivered via {}", pin_delivery.name());
+    }
+
+    let app_factory = move || {
+        let build_observer_manager = || {
+            let mut observer_manager = ObserverManager::new().with_timeout(args.observer_timeout);
+            observer_manager.register_observer(Box::new(config_reloader.webhook_observer()));
+            for notifier in &chat_notifiers {
+                observer_manager.register_observer(Box::new(notifier.clone()));
+            }
+            if let Some(event_metrics) = &options.event_metrics {
+                observer_manager = observer_manager.with_event_metrics(event_metrics.clone());
+                let metrics_observer = MetricsObserver::new(event_metrics.clone());
+                observer_manager.register_observer(Box::new(metrics_observer));
+            }
+
+            let mut stats_observer = StatsObserver::new(options.stats_store.clone());
+            if let Some(event_metrics) = options.event_metrics.clone() {
+                stats_observer = stats_observer.with_event_metrics(event_metrics);
+            }
+            observer_manager.register_observer(Box::new(stats_observer));
+            observer_manager
+        };
+
+        let build_app_data = |token_manager: &T, tenant: Option<&Arc<Tenant>>| AppData {
             secret_store: Box::new(secret_store.clone()),
+            stats_store: Box::new(options.stats_store.clone()),
             token_validator: Box::new(token_manager.clone()),
             token_creator: Box::new(token_manager.clone()),
-            max_ttl: args.max_ttl,
-            anonymous_usage: anonymous_usage.clone(),
-            impressum_html: impressum_html.clone(),
-            privacy_html: privacy_html.clone(),
-            observer_manager,
+            identity_validator: oidc_validator
+                .clone()
+                .map(|v| Box::new(v) as Box<dyn IdentityValidator>),
+            content_policy: args.content_policy_command.clone().map(|command| {
+                Box::new(CommandPolicy::new(command, args.content_policy_timeout))
+                    as Box<dyn ContentPolicy>
+            }),
+            pin_delivery: pin_delivery.clone(),
+            min_ttl: args.min_ttl.unwrap_or_default(),
+            max_ttl: tenant
+                .and_then(|tenant| tenant.max_ttl)
+                .map_or(args.max_ttl, |ttl| ttl.min(args.max_ttl)),
+            ttl_presets: args
+                .ttl_presets
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TTL_PRESETS.to_vec()),
+            runtime_config: tenant
+                .and_then(|tenant| config_reloader.tenant_config(&tenant.id))
+                .unwrap_or_else(|| config_reloader.config()),
+            config_reloader: Some(config_reloader.clone()),
+            observer_manager: build_observer_manager(),
             show_token_input: args.show_token_input,
-            trusted_ip_ranges: args.trusted_ip_ranges.clone(),
+            ui: UiOptions {
+                default_theme: args.default_theme,
+                force_high_contrast: args.force_high_contrast,
+                hide_branding: args.hide_branding,
+            },
             trusted_ip_header: args.trusted_ip_header.clone(),
             country_header: args.country_header.clone(),
             asn_header: args.asn_header.clone(),
-            upload_size_limit: args.upload_size_limit,
             one_time_token_ttl: args.one_time_token_ttl,
+            admin_session_ttl: args.admin_session_ttl,
+            short_aliases: args.enable_short_aliases,
+            maintenance: maintenance.clone(),
+            qr_code_rate_limiter: qr_code_rate_limiter.clone(),
+            tenant: tenant.cloned(),
+            client_ip_hasher: client_ip_hasher.clone(),
+            event_metrics: options.event_metrics.clone(),
         };
-        let size_limit = size_limit::calculate(args.upload_size_limit);
-        App::new()
-            .app_data(web::Data::new(app_data))
+
+        let build_asset_manager = |tenant: Option<&Arc<Tenant>>| {
+            let custom_assets_dir = tenant
+                .and_then(|tenant| tenant.custom_assets_dir.clone())
+                .or_else(|| args.custom_assets_dir.clone());
+            AssetManager::new(custom_assets_dir)
+                .with_reload_interval(args.custom_assets_reload_interval)
+        };
+
+        let mut tenant_routing = TenantRouting::new(tenant_resolver.clone());
+        for (tenant, tenant_token_manager) in &tenants {
+            tenant_routing.add_tenant(
+                build_app_data(tenant_token_manager, Some(tenant)),
+                build_asset_manager(Some(tenant)),
+            );
+        }
+        let tenant_routing = Rc::new(tenant_routing);
+
+        let app_data = web::Data::new(build_app_data(&token_manager, None));
+        let asset_manager = build_asset_manager(None);
+        // the body limit of the extractors is fixed when the worker is started
+        let size_limit = size_limit::calculate(config_reloader.config().load().upload_size_limit);
+
+        let check_routing = tenant_routing.clone();
+        let check_app = App::new()
+            .app_data(app_data.clone())
+            .wrap_fn(move |mut req, srv| {
+                check_routing.route(&mut req);
+                srv.call(req)
+            })
+            .configure(expect_continue::configure);
+
+        let app = App::new()
+            .app_data(app_data)
             .app_data(web::PayloadConfig::new(size_limit))
             .app_data(web::JsonConfig::default().limit(size_limit))
             .app_data(web::Data::new(asset_manager))
+            .wrap_fn(move |mut req, srv| {
+                tenant_routing.route(&mut req);
+                srv.call(req)
+            })
             .wrap(Logger::new("%a %{X-Forwarded-For}i %t \"%r\" %s %b %Ts"))
             .wrap(RequestTracing::new())
             .wrap(RequestMetrics::default())
-            .wrap(default_headers())
-            .wrap(cors_config(args.cors_allowed_origins.clone()))
+            .wrap(security_headers::middleware(&security_headers_options))
+            .wrap(cors_config(&args))
             .route("/s/{id}", web::get().to(get_secret_short))
             .route("/healthy", web::get().to(healthy))
             .route("/ready", web::get().to(ready))
             .configure(web_routes::configure)
+            .configure(|cfg| {
+                if args.enable_admin_token {
+                    web_routes::configure_admin(cfg);
+                }
+            })
             .service(
                 web::scope("/api/v1")
                     .wrap(DefaultHeaders::new().add((
@@ -124,94 +273,110 @@ where
                     )))
                     .configure(|cfg| {
                         web_api::configure(cfg);
+                        qr_api::configure_routes(cfg);
                         if args.enable_admin_token {
                             admin_api::configure_routes(cfg);
                         }
                     }),
-            )
-    })
-    .bind((args.listen_address, args.port))?
-    .run()
-    .await
-}
+            );
 
-fn add_webhook_observer(observer_manager: &mut ObserverManager, webhook_args: &WebhookArgs) {
-    let res = WebhookObserver::new(
-        webhook_args.url.clone(),
-        webhook_args.token.clone(),
-        webhook_args.headers.clone(),
-    );
-    match res {
-        Ok(observer) => {
-            observer_manager.register_observer(Box::new(observer));
+        // the app never generates URLs, the host and scheme of the listener are not needed
+        (
+            map_config(app, |()| AppConfig::default()),
+            expect_continue::service(check_app),
+        )
+    };
+
+    let plain_service = {
+        let app_factory = app_factory.clone();
+        move || {
+            let (app, expect) = app_factory();
+            HttpService::build()
+                .client_disconnect_timeout(CLIENT_DISCONNECT_TIMEOUT)
+                .expect(expect)
+                .finish(app)
+                .tcp()
         }
-        Err(e) => {
-            error!("Failed to initialize webhook observer: {e}");
+    };
+    let tls_service = |tls_config: rustls::ServerConfig| {
+        let app_factory = app_factory.clone();
+        move || {
+            let (app, expect) = app_factory();
+            HttpService::build()
+                .client_disconnect_timeout(CLIENT_DISCONNECT_TIMEOUT)
+                .secure()
+                .expect(expect)
+                .finish(app)
+                .rustls_0_23(tls_config.clone())
         }
-    }
-}
+    };
 
-fn build_impressum_html(args: &Args) -> Result<Option<String>> {
-    Ok(match args.load_impressum_content()? {
-        Some(content) => {
-            info!(
-                "Building impressum HTML ({} bytes of content)",
-                content.len()
-            );
-            let template = include_str!("../../includes/impressum.html");
-            Some(template.replace(
-                r#"<div id="impressum-content-placeholder"></div>"#,
-                &content,
-            ))
-        }
-        None => None,
-    })
-}
+    let server = if listeners.is_empty() {
+        listen_addresses
+            .iter()
+            .try_fold(Server::build(), |server, address| {
+                let socket_address = (address.host.as_str(), address.port.unwrap_or(default_port));
+                match &address.tls {
+                    Some(tls_files) => server.bind(
+                        "hakanai-tls",
+                        socket_address,
+                        tls_service(tls::load_server_config(tls_files)?),
+                    ),
+                    None => server.bind("hakanai", socket_address, plain_service.clone()),
+                }
+            })?
+    } else {
+        listeners
+            .into_iter()
+            .try_fold(Server::build(), |server, listener| {
+                server.listen("hakanai", listener, plain_service.clone())
+            })?
+    };
 
-fn build_privacy_html(args: &Args) -> Result<Option<String>> {
-    Ok(match args.load_privacy_content()? {
-        Some(content) => {
-            info!(
-                "Building privacy policy HTML ({} bytes of content)",
-                content.len()
-            );
-            let template = include_str!("../../includes/privacy.html");
-            Some(template.replace(r#"<div id="privacy-content-placeholder"></div>"#, &content))
-        }
-        None => None,
-    })
+    let server = server.run();
+    systemd::notify_ready();
+    let result = server.await;
+    systemd::notify_stopping();
+    result
 }
 
-fn default_headers() -> DefaultHeaders {
-    DefaultHeaders::new()
-        .add(("X-Frame-Options", "DENY"))
-        .add(("X-Content-Type-Options", "nosniff"))
-        .add((
-            "Strict-Transport-Security",
-            "max-age=31536000; includeSubDomains",
-        ))
-        .add(("Content-Security-Policy", "default-src 'self'; script-src 'self' 'wasm-unsafe-eval'; style-src 'self'; img-src 'self' data: blob:; connect-src 'self'; font-src 'self'; object-src 'none'; base-uri 'self'; form-action 'self'; frame-ancestors 'none'; upgrade-insecure-requests"))
-        .add(("Referrer-Policy", "strict-origin-when-cross-origin"))
-        .add((
-            "Permissions-Policy",
-            "geolocation=(), microphone=(), camera=()",
-        ))
+fn build_oidc_validator(args: &Args) -> Result<Option<OidcValidator>> {
+    let (Some(issuer), Some(audience)) = (&args.oidc_issuer, &args.oidc_audience) else {
+        return Ok(None);
+    };
+
+    info!("OIDC authentication enabled for issuer {issuer}");
+    OidcValidator::new(issuer, audience)
+        .map(Some)
+        .map_err(std::io::Error::other)
 }
 
-fn cors_config(allowed_origins: Option<Vec<String>>) -> Cors {
+/// Creates the CORS middleware allowing browser applications of the configured origins to use the API.
+fn cors_config(args: &Args) -> Cors {
     let mut cors = Cors::default()
-        .allowed_methods(vec![http::Method::GET, http::Method::POST])
+        .allowed_methods(vec![
+            http::Method::GET,
+            http::Method::POST,
+            http::Method::DELETE,
+        ])
         .allowed_headers(vec![
             http::header::CONTENT_TYPE,
             http::header::ACCEPT,
             http::header::AUTHORIZATION,
+            http::header::HeaderName::from_static("x-request-id"),
+            http::header::HeaderName::from_static("x-secret-passphrase"),
+            http::header::HeaderName::from_static("x-secret-restrictions"),
+            http::header::HeaderName::from_static("x-secret-ttl"),
         ])
+        .max_age(Some(args.cors_max_age.as_secs() as usize))
         .supports_credentials();
 
-    if let Some(allowed_origins) = &allowed_origins {
-        for origin in allowed_origins {
-            cors = cors.allowed_origin(origin);
-        }
+    for header in args.cors_allowed_headers.iter().flatten() {
+        cors = cors.allowed_header(header.as_str());
+    }
+
+    for origin in args.cors_allowed_origins.iter().flatten() {
+        cors = cors.allowed_origin(origin);
     }
 
     cors
@@ -222,7 +387,7 @@ async fn get_secret_short(
     http_req: actix_web::HttpRequest,
     req: web::Path<String>,
     app_data: web::Data<AppData>,
-) -> impl Responder {
+) -> HttpResponse {
     let user_agent = http_req
         .headers()
         .get("User-Agent")
@@ -230,14 +395,26 @@ async fn get_secret_short(
         .unwrap_or_default();
     info!("Received request for secret: {}", req);
 
-    if !user_agent.starts_with("hakanai-") {
-        return web_routes::serve_get_secret_html().await;
-    }
+    let mut resp = if !user_agent.starts_with("hakanai-") {
+        web_routes::serve_get_secret_html(http_req).await
+    } else {
+        web_api::get_secret_from_request(http_req, req, app_data)
+            .await
+            .unwrap_or_else(|e| e.error_response())
+    };
 
-    match web_api::get_secret_from_request(http_req, req, app_data).await {
-        Ok(secret) => HttpResponse::Ok().body(secret),
-        Err(e) => e.error_response(),
-    }
+    // the same URL serves the page or the ciphertext depending on the client, neither may be cached
+    let headers = resp.headers_mut();
+    headers.remove(http::header::ETAG);
+    headers.insert(
+        http::header::CACHE_CONTROL,
+        http::header::HeaderValue::from_static("no-cache, no-store, must-revalidate, no-transform"),
+    );
+    headers.insert(
+        http::header::VARY,
+        http::header::HeaderValue::from_static("User-Agent"),
+    );
+    resp
 }
 
 async fn healthy(app_data: web::Data<AppData>) -> impl Responder {
@@ -245,6 +422,10 @@ async fn healthy(app_data: web::Data<AppData>) -> impl Responder {
 
     match res {
         Ok(()) => HttpResponse::Ok().body("healthy"),
+        Err(e) if e.is_unavailable() => {
+            error!("Health check failed: {e}");
+            HttpResponse::ServiceUnavailable().body("unhealthy")
+        }
         Err(e) => {
             error!("Health check failed: {e}");
             HttpResponse::InternalServerError().body("unhealthy")
@@ -255,3 +436,128 @@ async fn healthy(app_data: web::Data<AppData>) -> impl Responder {
 async fn ready() -> impl Responder {
     HttpResponse::Ok().body("ready")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test;
+    use clap::Parser;
+
+    use crate::secret::{MockSecretStore, SecretStorePopResult};
+
+    const ORIGIN: &str = "https://app.example.com";
+
+    async fn get_short(user_agent: &str) -> actix_web::dev::ServiceResponse {
+        let secret_store = MockSecretStore::new()
+            .with_pop_result(SecretStorePopResult::Found("ciphertext".to_string()));
+        let app_data = AppData::default().with_secret_store(Box::new(secret_store));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_data))
+                .route("/s/{id}", web::get().to(get_secret_short)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/s/{}", ulid::Ulid::r#gen()))
+            .insert_header(("User-Agent", user_agent))
+            .to_request();
+        test::call_service(&app, req).await
+    }
+
+    #[actix_web::test]
+    async fn test_get_secret_short_not_cached() {
+        for user_agent in ["Mozilla/5.0", "hakanai-cli/3.0.0"] {
+            let resp = get_short(user_agent).await;
+            assert!(
+                resp.status().is_success(),
+                "Request from {user_agent} should succeed"
+            );
+            assert_eq!(
+                resp.headers()
+                    .get("Cache-Control")
+                    .and_then(|v| v.to_str().ok()),
+                Some("no-cache, no-store, must-revalidate, no-transform"),
+                "Response for {user_agent} must not be cached"
+            );
+            assert_eq!(
+                resp.headers().get("Vary").and_then(|v| v.to_str().ok()),
+                Some("User-Agent"),
+                "Response should vary by client"
+            );
+            assert!(
+                resp.headers().get("ETag").is_none(),
+                "Response for {user_agent} should not be revalidated"
+            );
+        }
+    }
+
+    async fn preflight(
+        args: &Args,
+        headers: &str,
+    ) -> actix_web::dev::ServiceResponse<impl actix_web::body::MessageBody> {
+        let app = test::init_service(
+            App::new()
+                .wrap(cors_config(args))
+                .route("/api/v1/secret", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::default()
+            .method(http::Method::OPTIONS)
+            .uri("/api/v1/secret")
+            .insert_header(("Origin", ORIGIN))
+            .insert_header(("Access-Control-Request-Method", "POST"))
+            .insert_header(("Access-Control-Request-Headers", headers))
+            .to_request();
+        test::call_service(&app, req).await
+    }
+
+    #[actix_web::test]
+    async fn test_cors_preflight_allowed_origin() {
+        let args = Args::parse_from(["hakanai-server", "--cors-allowed-origins", ORIGIN]);
+
+        let resp = preflight(&args, "content-type,x-request-id,x-secret-passphrase").await;
+        assert!(resp.status().is_success(), "Preflight should succeed");
+        assert_eq!(
+            resp.headers()
+                .get("Access-Control-Allow-Origin")
+                .and_then(|v| v.to_str().ok()),
+            Some(ORIGIN)
+        );
+        assert_eq!(
+            resp.headers()
+                .get("Access-Control-Max-Age")
+                .and_then(|v| v.to_str().ok()),
+            Some("3600")
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_cors_preflight_additional_header() {
+        let args = Args::parse_from([
+            "hakanai-server",
+            "--cors-allowed-origins",
+            ORIGIN,
+            "--cors-allowed-headers",
+            "x-tenant",
+        ]);
+
+        let resp = preflight(&args, "x-tenant").await;
+        assert!(
+            resp.status().is_success(),
+            "Configured header should be allowed"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_cors_preflight_unknown_origin() {
+        let args = Args::parse_from(["hakanai-server"]);
+
+        let resp = preflight(&args, "content-type").await;
+        assert!(
+            resp.headers().get("Access-Control-Allow-Origin").is_none(),
+            "Origin should not be allowed without configuration"
+        );
+    }
+}
@@ -1,9 +1,21 @@
 import typescript from "@rollup/plugin-typescript";
 import resolve from "@rollup/plugin-node-resolve";
 
+// Bundles JSON files (language packs) as ES modules
+const json = () => ({
+  name: "json",
+  transform(code, id) {
+    if (!id.endsWith(".json")) {
+      return null;
+    }
+    return { code: `export default ${code};`, map: { mappings: "" } };
+  },
+});
+
 const commonConfig = {
   plugins: [
     resolve(),
+    json(),
     typescript({
       tsconfig: "./tsconfig.json",
       sourceMap: false,
@@ -27,6 +39,6 @@ const createBundle = (fileName) => ({
   ...commonConfig,
 });
 
-const bundles = ["create-secret", "get-secret", "share", "common", "sw", "one-time-token"];
+const bundles = ["create-secret", "get-secret", "share", "common", "sw", "one-time-token", "admin"];
 
 export default bundles.map((fileName) => createBundle(fileName));
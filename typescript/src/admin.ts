@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Admin dashboard showing token counts, secret statistics and user token creation.
+ * The admin token is exchanged for a short-lived HttpOnly session cookie and never persisted.
+ */
+import { HakanaiError, HakanaiErrorCodes } from "./hakanai-client";
+import { initI18n, I18nKeys } from "./core/i18n";
+import { initTheme } from "./core/theme";
+import { initFeatures } from "./core/app-config";
+import { ErrorHandler, handleAPIError } from "./core/error";
+import { expandView, generateRandomId, hideElement, showElement } from "./core/dom-utils";
+import { formatFileSize } from "./core/formatters";
+import { displayErrorMessage } from "./components/error-display";
+import { createLabeledInputWithCopy } from "./core/result-utils";
+
+interface DailyStats {
+  date: string;
+  created: number;
+  retrieved: number;
+  expired: number;
+}
+
+interface SizeBucket {
+  max_size: number | null;
+  count: number;
+}
+
+interface StatsResponse {
+  active_secrets: number;
+  retrieved_secrets: number;
+  expired_secrets: number;
+  days: DailyStats[];
+  size_histogram: SizeBucket[];
+}
+
+interface TokenCountResponse {
+  user_tokens: number;
+}
+
+interface CreateTokenResponse {
+  token: string;
+}
+
+class AdminErrorHandler implements ErrorHandler {
+  displayError(message: string): void {
+    showError(message);
+  }
+
+  onAuthenticationError(): void {
+    hideElement(document.getElementById("dashboard")!);
+    showElement(document.getElementById("admin-login-form")!);
+  }
+}
+
+const errorHandler = new AdminErrorHandler();
+
+const CSRF_COOKIE_NAME = "__Host-hakanai-csrf";
+
+/**
+ * Read the CSRF token the server issued on a previous GET request
+ */
+function getCsrfToken(): string {
+  const cookie = document.cookie.split("; ").find((entry) => entry.startsWith(`${CSRF_COOKIE_NAME}=`));
+  return cookie ? cookie.substring(CSRF_COOKIE_NAME.length + 1) : "";
+}
+
+function checkAdminResponse(response: Response): void {
+  if (response.status === 401 || response.status === 403) {
+    throw new HakanaiError(HakanaiErrorCodes.INVALID_TOKEN, "Invalid admin token", response.status);
+  }
+
+  if (!response.ok) {
+    throw new HakanaiError(
+      HakanaiErrorCodes.REQUEST_FAILED,
+      `Failed: ${response.status} ${response.statusText}`,
+      response.status,
+    );
+  }
+}
+
+/**
+ * Exchange the admin token for a session cookie, the token itself is discarded afterwards
+ */
+async function login(adminToken: string): Promise<void> {
+  const response = await fetch(`${window.location.origin}/api/v1/admin/login`, {
+    method: "POST",
+    headers: {
+      Authorization: `Bearer ${adminToken}`,
+      "X-Request-Id": crypto.randomUUID(),
+    },
+  });
+  checkAdminResponse(response);
+}
+
+async function adminRequest<T>(path: string, body?: unknown): Promise<T> {
+  const headers: Record<string, string> = {
+    "X-Request-Id": crypto.randomUUID(),
+  };
+  if (body !== undefined) {
+    headers["Content-Type"] = "application/json";
+    headers["X-CSRF-Token"] = getCsrfToken();
+  }
+
+  const response = await fetch(`${window.location.origin}/api/v1/admin${path}`, {
+    method: body === undefined ? "GET" : "POST",
+    headers,
+    body: body === undefined ? undefined : JSON.stringify(body),
+    credentials: "same-origin",
+  });
+  checkAdminResponse(response);
+
+  return (await response.json()) as T;
+}
+
+function setupFormHandlers(): void {
+  const loginForm = document.getElementById("admin-login-form") as HTMLFormElement | null;
+  loginForm?.addEventListener("submit", (event) => {
+    event.preventDefault();
+
+    const input = document.getElementById("adminToken") as HTMLInputElement;
+    const adminToken = input.value.trim();
+    input.value = "";
+    if (adminToken) {
+      loginAndLoadDashboard(adminToken);
+    }
+  });
+
+  const tokenForm = document.getElementById("create-token-form") as HTMLFormElement | null;
+  tokenForm?.addEventListener("submit", (event) => {
+    event.preventDefault();
+    createToken();
+  });
+}
+
+async function loginAndLoadDashboard(adminToken: string): Promise<void> {
+  try {
+    await login(adminToken);
+    await loadDashboard();
+  } catch (error: unknown) {
+    handleAPIError(error, window.i18n.t(I18nKeys.Admin.LoadFailed), errorHandler);
+  }
+}
+
+async function loadDashboard(): Promise<void> {
+  const [stats, tokens] = await Promise.all([
+    adminRequest<StatsResponse>("/stats"),
+    adminRequest<TokenCountResponse>("/tokens/count"),
+  ]);
+
+  clearResult();
+  hideElement(document.getElementById("admin-login-form")!);
+  showElement(document.getElementById("dashboard")!);
+
+  renderStats(stats, tokens);
+  expandView();
+}
+
+function renderStats(stats: StatsResponse, tokens: TokenCountResponse): void {
+  setText("statUserTokens", tokens.user_tokens);
+  setText("statActiveSecrets", stats.active_secrets);
+  setText("statRetrievedSecrets", stats.retrieved_secrets);
+  setText("statExpiredSecrets", stats.expired_secrets);
+
+  renderDailyChart(document.getElementById("dailyChart")!, stats.days);
+  renderSizeChart(document.getElementById("sizeChart")!, stats.size_histogram);
+}
+
+function setText(id: string, value: number): void {
+  const element = document.getElementById(id);
+  if (element) {
+    element.textContent = value.toString();
+  }
+}
+
+function renderDailyChart(container: HTMLElement, days: DailyStats[]): void {
+  container.innerHTML = "";
+  if (days.length === 0) {
+    renderNoData(container);
+    return;
+  }
+
+  const max = Math.max(...days.map((day) => Math.max(day.created, day.retrieved, day.expired)), 1);
+  for (const day of days) {
+    container.appendChild(
+      createChartRow(day.date, [
+        createBar(day.created, max, "created", I18nKeys.Admin.Created),
+        createBar(day.retrieved, max, "retrieved", I18nKeys.Admin.Retrieved),
+        createBar(day.expired, max, "expired", I18nKeys.Admin.Expired),
+      ]),
+    );
+  }
+}
+
+function renderSizeChart(container: HTMLElement, buckets: SizeBucket[]): void {
+  container.innerHTML = "";
+  if (buckets.every((bucket) => bucket.count === 0)) {
+    renderNoData(container);
+    return;
+  }
+
+  const max = Math.max(...buckets.map((bucket) => bucket.count), 1);
+  for (const bucket of buckets) {
+    const label =
+      bucket.max_size === null ? window.i18n.t(I18nKeys.Admin.Larger) : `≤ ${formatFileSize(bucket.max_size)}`;
+    container.appendChild(createChartRow(label, [createBar(bucket.count, max, "created", I18nKeys.Admin.Created)]));
+  }
+}
+
+function renderNoData(container: HTMLElement): void {
+  const empty = document.createElement("p");
+  empty.className = "input-helper";
+  empty.textContent = window.i18n.t(I18nKeys.Admin.NoData);
+  container.appendChild(empty);
+}
+
+function createChartRow(label: string, bars: HTMLElement[]): HTMLDivElement {
+  const row = document.createElement("div");
+  row.className = "admin-chart-row";
+
+  const labelElement = document.createElement("span");
+  labelElement.className = "admin-chart-label";
+  labelElement.textContent = label;
+  row.appendChild(labelElement);
+
+  const barsContainer = document.createElement("div");
+  barsContainer.className = "admin-chart-bars";
+  bars.forEach((bar) => barsContainer.appendChild(bar));
+  row.appendChild(barsContainer);
+
+  return row;
+}
+
+function createBar(value: number, max: number, kind: string, titleKey: string): HTMLDivElement {
+  const bar = document.createElement("div");
+  bar.className = `admin-chart-bar admin-chart-bar--${kind}`;
+  bar.style.width = `${(value / max) * 100}%`;
+  bar.title = `${window.i18n.t(titleKey)}: ${value}`;
+  bar.textContent = value > 0 ? value.toString() : "";
+  return bar;
+}
+
+async function createToken(): Promise<void> {
+  const ttlDays = parseInt((document.getElementById("tokenTtlDays") as HTMLInputElement).value, 10);
+  const sizeLimitKb = parseInt((document.getElementById("tokenSizeLimit") as HTMLInputElement).value, 10);
+  const oneTime = (document.getElementById("tokenOneTime") as HTMLInputElement).checked;
+
+  if (!ttlDays || ttlDays <= 0) {
+    showError(window.i18n.t(I18nKeys.Validation.InvalidTtl));
+    return;
+  }
+
+  try {
+    const response = await adminRequest<CreateTokenResponse>("/tokens", {
+      ttl_seconds: ttlDays * 86400,
+      upload_size_limit: sizeLimitKb > 0 ? sizeLimitKb * 1024 : null,
+      one_time: oneTime,
+    });
+
+    showToken(response.token);
+
+    const tokens = await adminRequest<TokenCountResponse>("/tokens/count");
+    setText("statUserTokens", tokens.user_tokens);
+  } catch (error: unknown) {
+    handleAPIError(error, window.i18n.t(I18nKeys.Admin.CreateTokenFailed), errorHandler);
+  }
+}
+
+function clearResult(): void {
+  const container = document.getElementById("result")!;
+  container.className = "";
+  container.innerHTML = "";
+}
+
+function showError(message: string): void {
+  const resultContainer = document.getElementById("result")!;
+  displayErrorMessage(message, resultContainer);
+}
+
+function showToken(token: string): void {
+  const container = document.getElementById("result")!;
+  container.className = "result success";
+  container.innerHTML = "";
+
+  const title = document.createElement("h3");
+  title.textContent = window.i18n.t(I18nKeys.Msg.SuccessTitle);
+  container.appendChild(title);
+
+  const div = document.createElement("div");
+  div.className = "url-container";
+  createLabeledInputWithCopy(div, "Token", generateRandomId(), token, "Copy token to clipboard");
+  container.appendChild(div);
+}
+
+document.addEventListener("DOMContentLoaded", () => {
+  initI18n();
+  initTheme();
+  initFeatures();
+
+  setupFormHandlers();
+
+  // resume a session of a previous visit, the login form stays visible otherwise
+  loadDashboard().catch(() => {});
+});
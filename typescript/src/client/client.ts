@@ -19,6 +19,11 @@ import { type PayloadData, PayloadDataImpl } from "./payload";
 import { SecureMemory } from "./secure-memory";
 import { type DataTransferObserver } from "./progress-observer";
 
+/**
+ * Prefix of the associated data binding the ciphertext to the secret ID (see the Rust client)
+ */
+const ENVELOPE_V2_AAD_PREFIX = "hakanai:v2:";
+
 interface SecretResponse {
   id: string;
 }
@@ -38,6 +43,16 @@ interface SecretRequest {
   data: string;
   expires_in: number;
   restrictions?: SecretRestrictions;
+  file?: boolean;
+}
+
+/**
+ * Non-sensitive metadata of a secret, available before the one-time retrieval
+ */
+interface SecretMetadata {
+  file: boolean;
+  size_class: number;
+  has_passphrase: boolean;
 }
 
 /**
@@ -389,6 +404,7 @@ class HakanaiClient {
     authToken?: string,
     progressObserver?: DataTransferObserver,
     restrictions?: SecretRestrictions,
+    file?: boolean,
   ): Promise<string> {
     const requestBody: SecretRequest = {
       data: encryptedData,
@@ -399,6 +415,10 @@ class HakanaiClient {
       requestBody.restrictions = restrictions;
     }
 
+    if (file) {
+      requestBody.file = true;
+    }
+
     const bodyData = JSON.stringify(requestBody);
     const requestId = crypto.randomUUID();
 
@@ -459,7 +479,14 @@ class HakanaiClient {
       // Clear payload bytes after encryption
       SecureMemory.clearUint8Array(payloadBytes);
 
-      const secretId = await this.sendEncryptedData(encryptedData, ttl, authToken, progressObserver, restrictions);
+      const secretId = await this.sendEncryptedData(
+        encryptedData,
+        ttl,
+        authToken,
+        progressObserver,
+        restrictions,
+        payload.filename !== undefined,
+      );
 
       return `${this.baseUrl}/s/${secretId}#${cryptoContext.getKeyBase64()}:${hash}`;
     } finally {
@@ -475,9 +502,10 @@ class HakanaiClient {
     secretId: string;
     key: Uint8Array;
     hash?: string;
+    boundId?: string;
   } {
     // Use URL parser for parsing
-    const { secretId, secretKey, hash } = UrlParser.parseSecretUrl(url);
+    const { secretId, secretKey, hash, boundId } = UrlParser.parseSecretUrl(url);
 
     // Convert validated key string to bytes
     let key: Uint8Array;
@@ -487,7 +515,7 @@ class HakanaiClient {
       throw new HakanaiError(HakanaiErrorCodes.BASE64_ERROR, "Invalid decryption key in URL");
     }
 
-    return { secretId, key, hash };
+    return { secretId, key, hash, boundId };
   }
 
   /**
@@ -547,7 +575,7 @@ class HakanaiClient {
     progressObserver?: DataTransferObserver,
     passphrase?: string,
   ): Promise<PayloadData> {
-    const { secretId, key, hash } = this.validateAndParseReceiveUrl(url);
+    const { secretId, key, hash, boundId } = this.validateAndParseReceiveUrl(url);
     const requestId = crypto.randomUUID();
 
     const headers: Record<string, string> = {
@@ -576,7 +604,8 @@ class HakanaiClient {
 
     const cryptoContext = await CryptoContext.fromKey(key);
     try {
-      const decryptedBytes = await cryptoContext.decrypt(encryptedData);
+      const additionalData = boundId ? new TextEncoder().encode(`${ENVELOPE_V2_AAD_PREFIX}${boundId}`) : undefined;
+      const decryptedBytes = await cryptoContext.decrypt(encryptedData, additionalData);
 
       if (hash) {
         await this.verifyHash(decryptedBytes.buffer as ArrayBuffer, hash);
@@ -593,6 +622,26 @@ class HakanaiClient {
     }
   }
 
+  /**
+   * Fetch the non-sensitive metadata of a secret without consuming it
+   * @param secretId - ID or short alias of the secret
+   * @returns Metadata of the secret, null if not available (e.g. older servers or unknown secrets)
+   */
+  async getSecretMetadata(secretId: string): Promise<SecretMetadata | null> {
+    try {
+      const response = await fetch(`${this.baseUrl}/api/v1/secret/${encodeURIComponent(secretId)}/meta`, {
+        headers: { "X-Request-Id": crypto.randomUUID() },
+      });
+      if (!response.ok) {
+        return null;
+      }
+
+      return (await response.json()) as SecretMetadata;
+    } catch {
+      return null;
+    }
+  }
+
   /**
    * Create a new PayloadData object for building payloads
    * @param filename - Optional filename for file payloads
@@ -639,6 +688,7 @@ export {
   ContentAnalysis,
   type PayloadData,
   type DataTransferObserver,
+  SecretMetadata,
   SecretRequest,
   SecretResponse,
   SecretRestrictions,
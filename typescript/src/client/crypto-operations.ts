@@ -141,10 +141,11 @@ class CryptoContext {
   /**
    * Decrypt AES-256-GCM encrypted data
    * @param encryptedData - Base64-encoded ciphertext with nonce
+   * @param additionalData - Associated data the ciphertext is bound to (optional)
    * @returns Decrypted plaintext as bytes
    * @throws {Error} If decryption fails or context is disposed
    */
-  async decrypt(encryptedData: string): Promise<Uint8Array> {
+  async decrypt(encryptedData: string, additionalData?: Uint8Array): Promise<Uint8Array> {
     this.checkDisposed();
 
     if (typeof encryptedData !== "string") {
@@ -173,6 +174,7 @@ class CryptoContext {
         {
           name: "AES-GCM",
           iv: nonce.buffer,
+          ...(additionalData && { additionalData: additionalData.buffer as ArrayBuffer }),
         },
         this.cryptoKey,
         ciphertext.buffer,
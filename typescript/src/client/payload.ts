@@ -1,11 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-import { encode, decode } from "@msgpack/msgpack";
+import { encode, decodeMulti } from "@msgpack/msgpack";
 import { HakanaiError, HakanaiErrorCodes } from "./errors";
 
 interface PayloadData {
   readonly data: Uint8Array;
   readonly filename?: string;
+  readonly contentType?: string;
+  readonly description?: string;
 
   /**
    * Set data from raw bytes (for binary files or text converted to bytes)
@@ -44,10 +46,14 @@ interface PayloadData {
 class PayloadDataImpl implements PayloadData {
   private _data: Uint8Array = new Uint8Array();
   private _filename?: string;
+  private _contentType?: string;
+  private _description?: string;
 
-  constructor(data: Uint8Array = new Uint8Array(), filename?: string) {
+  constructor(data: Uint8Array = new Uint8Array(), filename?: string, contentType?: string, description?: string) {
     this._data = data;
     this._filename = filename;
+    this._contentType = contentType;
+    this._description = description;
   }
 
   get data(): Uint8Array {
@@ -58,6 +64,14 @@ class PayloadDataImpl implements PayloadData {
     return this._filename;
   }
 
+  get contentType(): string | undefined {
+    return this._contentType;
+  }
+
+  get description(): string | undefined {
+    return this._description;
+  }
+
   setFromBytes(bytes: ArrayBuffer): void {
     this._data = new Uint8Array(bytes);
   }
@@ -96,14 +110,26 @@ class PayloadDataImpl implements PayloadData {
   /**
    * Serialize the payload to MessagePack format.
    * The payload is serialized as a 2-element array: [data, filename]
+   * If metadata is set, it is serialized as a 4-element array: [data, filename, contentType, description]
    */
   serialize(): Uint8Array {
+    if (this._contentType !== undefined || this._description !== undefined) {
+      const payload: [Uint8Array, string | null, string | null, string | null] = [
+        this._data,
+        this._filename ?? null,
+        this._contentType ?? null,
+        this._description ?? null,
+      ];
+      return new Uint8Array(encode(payload));
+    }
+
     const payload: [Uint8Array, string | null] = [this._data, this._filename ?? null];
     return new Uint8Array(encode(payload));
   }
 
   /**
    * Deserialize a MessagePack payload.
+   * Only the first value is decoded, so padding appended by the sender to hide the size is ignored.
    *
    * @param bytes - MessagePack-encoded bytes
    * @returns PayloadDataImpl instance
@@ -112,17 +138,21 @@ class PayloadDataImpl implements PayloadData {
   static deserialize(bytes: ArrayBuffer): PayloadDataImpl {
     let decoded: unknown;
     try {
-      decoded = decode(bytes);
+      decoded = decodeMulti(bytes).next().value;
     } catch {
       throw new HakanaiError(HakanaiErrorCodes.INVALID_PAYLOAD, "Failed to decode MessagePack payload");
     }
 
-    // Validate the decoded structure is a 2-element array
-    if (!Array.isArray(decoded) || decoded.length !== 2) {
-      throw new HakanaiError(HakanaiErrorCodes.INVALID_PAYLOAD, "Invalid payload structure: expected 2-element array");
+    // Validate the decoded structure is a 2-element array (4 elements if metadata is included, 5 with checksum,
+    // 6 with a reply slot, which is only supported by the CLI and ignored here)
+    if (!Array.isArray(decoded) || ![2, 4, 5, 6].includes(decoded.length)) {
+      throw new HakanaiError(
+        HakanaiErrorCodes.INVALID_PAYLOAD,
+        "Invalid payload structure: expected 2-, 4-, 5- or 6-element array",
+      );
     }
 
-    const [data, filename] = decoded;
+    const [data, filename, contentType, description] = decoded;
 
     // Validate data is Uint8Array or can be converted
     if (!Array.isArray(data) && !(data instanceof Uint8Array)) {
@@ -134,7 +164,19 @@ class PayloadDataImpl implements PayloadData {
       throw new HakanaiError(HakanaiErrorCodes.INVALID_PAYLOAD, "Invalid payload: filename must be string or null");
     }
 
-    return new PayloadDataImpl(new Uint8Array(data), filename ?? undefined);
+    // Validate optional metadata is string or null
+    for (const value of [contentType, description]) {
+      if (value !== undefined && value !== null && typeof value !== "string") {
+        throw new HakanaiError(HakanaiErrorCodes.INVALID_PAYLOAD, "Invalid payload: metadata must be string or null");
+      }
+    }
+
+    return new PayloadDataImpl(
+      new Uint8Array(data),
+      filename ?? undefined,
+      contentType ?? undefined,
+      description ?? undefined,
+    );
   }
 }
 
@@ -11,13 +11,14 @@ class UrlParser {
   /**
    * Parse and validate a secret URL, returning its components
    * @param url - Complete secret URL
-   * @returns Object with validated secretId, secretKey and hash (optional)
+   * @returns Object with validated secretId, secretKey, hash and the secret ID bound to the key (optional)
    * @throws {HakanaiError} If URL or its components are invalid
    */
   static parseSecretUrl(url: string): {
     secretId: string;
     secretKey: string;
     hash?: string;
+    boundId?: string;
   } {
     // Basic URL validation
     if (typeof url !== "string" || !url.trim()) {
@@ -65,7 +66,22 @@ class UrlParser {
     }
     InputValidation.validateHash(hash);
 
-    return { secretId, secretKey, hash };
+    // Links of newer clients bind the ciphertext to the secret ID
+    const boundId = fragmentParts[2];
+    if (boundId === undefined) {
+      return { secretId, secretKey, hash };
+    }
+    if (!/^[0-9A-HJKMNP-TV-Z]{26}$/.test(boundId)) {
+      throw new HakanaiError(HakanaiErrorCodes.INVALID_URL_FORMAT, "Invalid secret ID in URL fragment");
+    }
+    if (/^[0-9A-HJKMNP-TV-Z]{26}$/.test(secretId) && secretId !== boundId) {
+      throw new HakanaiError(
+        HakanaiErrorCodes.INVALID_URL_FORMAT,
+        "Secret ID of the link does not match the ID bound to the key",
+      );
+    }
+
+    return { secretId, secretKey, hash, boundId };
   }
 }
 
@@ -90,8 +90,8 @@ class InputValidation {
       throw new HakanaiError(HakanaiErrorCodes.MISSING_SECRET_ID, "Secret ID cannot be empty");
     }
 
-    // Validate ULID format
-    if (!/^[0-9A-HJKMNP-TV-Z]{26}$/.test(id)) {
+    // Validate ULID or short alias format (e.g. blue-otter-42)
+    if (!/^[0-9A-HJKMNP-TV-Z]{26}$/.test(id) && !/^(?:[a-z]+-)+[0-9]+$/.test(id)) {
       throw new HakanaiError(HakanaiErrorCodes.INVALID_SECRET_ID, "Secret ID must be a valid ULID");
     }
   }
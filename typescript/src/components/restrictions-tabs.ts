@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 import { showElement, hideElement } from "../core/dom-utils";
-import { fetchAppConfig } from "../core/app-config";
+import { fetchAppConfig, type CountryOption } from "../core/app-config";
 import { RestrictionData } from "../core/restriction-data";
 import { I18nKeys } from "../core/i18n";
 import { InputValidation } from "../client/validation";
@@ -356,11 +356,49 @@ export class RestrictionsTabs {
 
     const showCountry = config?.features?.restrictions?.country ?? false;
     this.setTabVisible("country", showCountry);
+    if (showCountry) {
+      this.initializeCountryPicker(config?.countries ?? []);
+    }
 
     const showASN = config?.features?.restrictions?.asn ?? false;
     this.setTabVisible("asn", showASN);
   }
 
+  /**
+   * Fill the country dropdown, selected countries are appended to the country list
+   */
+  private initializeCountryPicker(countries: CountryOption[]): void {
+    const picker = this.container.querySelector("#countryPicker") as HTMLSelectElement | null;
+    const countryInput = this.container.querySelector("#allowedCountries") as HTMLTextAreaElement | null;
+    if (!picker || !countryInput || countries.length === 0) {
+      return;
+    }
+
+    for (const country of countries) {
+      const option = document.createElement("option");
+      option.value = country.code;
+      option.textContent = `${country.name} (${country.code})`;
+      picker.appendChild(option);
+    }
+
+    picker.addEventListener("change", () => {
+      const code = picker.value;
+      picker.value = "";
+      if (!code) {
+        return;
+      }
+
+      const selected = countryInput.value
+        .split("\n")
+        .map((line) => line.trim().toUpperCase())
+        .filter((line) => line.length > 0);
+      if (!selected.includes(code)) {
+        countryInput.value = [...selected, code].join("\n");
+      }
+    });
+    showElement(picker);
+  }
+
   /**
    * Configure tab visibility
    */
@@ -119,6 +119,25 @@ export class TTLSelector {
     }
   }
 
+  /**
+   * Remove preset options outside of the TTL range accepted by the server
+   */
+  limitToTTLRange(minTtl: number, maxTtl: number): void {
+    const options = Array.from(this.selectElement.options);
+    for (const option of options) {
+      const ttl = parseInt(option.value);
+      if (option.value !== "custom" && (ttl < minTtl || ttl > maxTtl)) {
+        option.remove();
+      }
+    }
+
+    if (this.currentValue > maxTtl) {
+      this.setValue(maxTtl);
+    } else if (this.currentValue < minTtl) {
+      this.setValue(minTtl);
+    }
+  }
+
   /**
    * Enable or disable the selector
    */
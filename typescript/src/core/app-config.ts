@@ -1,10 +1,37 @@
 // SPDX-License-Identifier: Apache-2.0
 
 import { showElement, hideElement } from "./dom-utils";
+import { applyDefaultTheme, type Theme } from "./theme";
+
+export interface FooterLink {
+  label: string;
+  url: string;
+}
+
+export interface Branding {
+  instanceName?: string;
+  footerLinks?: FooterLink[];
+}
+
+export interface CountryOption {
+  code: string;
+  name: string;
+}
+
+export interface UiPreferences {
+  defaultTheme?: Theme | null;
+  forceHighContrast?: boolean;
+  hideBranding?: boolean;
+}
 
 export interface AppConfig {
   showTokenInput: boolean;
   secretSizeLimit?: number;
+  minTtl?: number;
+  maxTtl?: number;
+  anonymousAllowed?: boolean;
+  ttlPresets?: number[];
+  countries?: CountryOption[];
   features: {
     impressum: boolean;
     privacy: boolean;
@@ -13,8 +40,12 @@ export interface AppConfig {
       asn: boolean;
     };
   };
+  branding?: Branding;
+  ui?: UiPreferences;
 }
 
+const PROJECT_NAME = "Hakanai";
+
 let cachedConfig: AppConfig | null | undefined;
 
 /**
@@ -76,4 +107,88 @@ export async function initFeatures(): Promise<void> {
 
   await initializeOptionalFeature("impressum-link", config.features?.impressum ?? false);
   await initializeOptionalFeature("privacy-link", config.features?.privacy ?? false);
+  applyBranding(config.branding);
+  applyUiPreferences(config.ui, config.branding);
+}
+
+function isSafeLinkUrl(url: string): boolean {
+  try {
+    const parsed = new URL(url, window.location.origin);
+    return parsed.protocol === "https:" || parsed.protocol === "http:";
+  } catch {
+    return false;
+  }
+}
+
+function applyInstanceName(instanceName: string): void {
+  document.querySelectorAll<HTMLElement>(".project-name").forEach((element) => {
+    element.textContent = instanceName;
+  });
+
+  if (document.title.startsWith(PROJECT_NAME)) {
+    document.title = instanceName + document.title.slice(PROJECT_NAME.length);
+  } else if (!document.title.startsWith(instanceName)) {
+    document.title = document.title ? `${instanceName} - ${document.title}` : instanceName;
+  }
+}
+
+function applyFooterLinks(links: FooterLink[]): void {
+  const container = document.getElementById("footer-links");
+  if (!container) {
+    return;
+  }
+
+  for (const link of links) {
+    if (!link.label || !isSafeLinkUrl(link.url)) {
+      continue;
+    }
+
+    const span = document.createElement("span");
+    span.className = "custom-footer-link";
+    span.appendChild(document.createTextNode("• "));
+
+    const anchor = document.createElement("a");
+    anchor.href = link.url;
+    anchor.textContent = link.label;
+    span.appendChild(anchor);
+
+    container.appendChild(span);
+  }
+}
+
+/**
+ * Apply instance branding (name and footer links) configured on the server
+ */
+function applyBranding(branding?: Branding): void {
+  if (!branding) {
+    return;
+  }
+
+  if (branding.instanceName) {
+    applyInstanceName(branding.instanceName);
+  }
+
+  if (branding.footerLinks) {
+    applyFooterLinks(branding.footerLinks);
+  }
+}
+
+/**
+ * Apply instance-level UI preferences (default theme, high contrast, branding) configured on the server
+ */
+function applyUiPreferences(ui?: UiPreferences, branding?: Branding): void {
+  if (!ui) {
+    return;
+  }
+
+  if (ui.defaultTheme) {
+    applyDefaultTheme(ui.defaultTheme);
+  }
+
+  document.body.classList.toggle("high-contrast", ui.forceHighContrast ?? false);
+  document.body.classList.toggle("hide-branding", ui.hideBranding ?? false);
+
+  if (ui.hideBranding && !branding?.instanceName) {
+    document.querySelectorAll<HTMLElement>(".project-name").forEach(hideElement);
+  }
 }
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * File checks before upload (hashing and size limits) using WebAssembly
+ *
+ * Shares the implementation with the Rust client, falls back to plain JS checks
+ * if the WASM module is not available.
+ */
+
+import { loadWasmModule } from "./wasm-loader";
+
+/**
+ * Hash the file content using SHA-256
+ * @param file - File to hash
+ * @returns Hexadecimal SHA-256 hash (as printed by `hakanai send --checksum`) or null if hashing is not available
+ */
+export async function hashFile(file: Blob): Promise<string | null> {
+  const module = await loadWasmModule();
+  if (!module) return null;
+
+  try {
+    const content = new Uint8Array(await file.arrayBuffer());
+    return module.hash_file(content);
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Check whether the total size of the selected files fits the size limit of the server
+ * @param totalSize - Total size in bytes
+ * @param secretSizeLimit - Size limit of the server config (no limit if not set or 0)
+ * @returns true if the size is allowed
+ */
+export async function isSizeAllowed(totalSize: number, secretSizeLimit?: number): Promise<boolean> {
+  if (!secretSizeLimit) return true;
+
+  const module = await loadWasmModule();
+  if (!module) {
+    return totalSize <= secretSizeLimit;
+  }
+
+  return module.is_size_allowed(totalSize, secretSizeLimit);
+}
@@ -1,5 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
+import en from "../i18n/en.json";
+import de from "../i18n/de.json";
+
 declare global {
   interface Window {
     i18n: {
@@ -16,11 +19,13 @@ const I18nKeys = {
     PrivacyTitle: "page.privacy.title",
     ShareTitle: "page.share.title",
     OneTimeTokenTitle: "page.oneTimeToken.title",
+    AdminTitle: "page.admin.title",
   },
 
   Label: {
     AllowedIPs: "label.allowedIPs",
     AllowedCountries: "label.allowedCountries",
+    AddCountry: "label.addCountry",
     AllowedASNs: "label.allowedASNs",
     ContentPreview: "label.contentPreview",
     Expires: "label.expires",
@@ -135,6 +140,9 @@ const I18nKeys = {
     RetrieveNote: "msg.retrieveNote",
     RetrieveCTA: "msg.retrieveCTA",
     Retrieving: "msg.retrieving",
+    SecretMetaFile: "msg.secretMetaFile",
+    SecretMetaPassphrase: "msg.secretMetaPassphrase",
+    SecretMetaText: "msg.secretMetaText",
     ShareInstructions: "msg.shareInstructions",
     ShareSuccess: "msg.shareSuccess",
     ShareTitle: "msg.shareTitle",
@@ -205,6 +213,31 @@ const I18nKeys = {
     Privacy: "footer.privacy",
   },
 
+  Admin: {
+    ActiveSecrets: "admin.stats.activeSecrets",
+    Created: "admin.stats.created",
+    CreateTokenButton: "admin.createToken.button",
+    CreateTokenFailed: "admin.msg.createTokenFailed",
+    CreateTokenTitle: "admin.createToken.title",
+    DailyTitle: "admin.stats.daily",
+    Expired: "admin.stats.expired",
+    ExpiredSecrets: "admin.stats.expiredSecrets",
+    Larger: "admin.stats.larger",
+    LoadButton: "admin.button.load",
+    LoadFailed: "admin.msg.loadFailed",
+    NoData: "admin.msg.noData",
+    OneTime: "admin.createToken.oneTime",
+    Retrieved: "admin.stats.retrieved",
+    RetrievedSecrets: "admin.stats.retrievedSecrets",
+    SizeLimit: "admin.createToken.sizeLimit",
+    SizeLimitHelper: "admin.createToken.sizeLimitHelper",
+    SizesTitle: "admin.stats.sizes",
+    TokenHelper: "admin.token.helper",
+    TokenLabel: "admin.token.label",
+    Ttl: "admin.createToken.ttl",
+    UserTokens: "admin.stats.userTokens",
+  },
+
   Error: {
     AccessDenied: "error.ACCESS_DENIED",
     AuthenticationRequired: "error.AUTHENTICATION_REQUIRED",
@@ -280,498 +313,8 @@ type Translations = {
   [lang in LanguageCode]: TranslationDictionary;
 };
 
-const translations: Translations = {
-  en: {
-    [I18nKeys.Page.CreateTitle]: "Hakanai - Create Secret",
-    [I18nKeys.Page.GetTitle]: "Hakanai - Retrieve Secret",
-    [I18nKeys.Page.HomepageTitle]: "Hakanai - One-Time Secret Sharing",
-    [I18nKeys.Page.ShareTitle]: "Hakanai - Share Data",
-    [I18nKeys.Page.OneTimeTokenTitle]: "Hakanai - One-Time Token",
-
-    [I18nKeys.Label.AllowedIPs]: "IP Address Restrictions:",
-    [I18nKeys.Label.AllowedCountries]: "Country Restrictions:",
-    [I18nKeys.Label.AllowedASNs]: "Network (ASN) Restrictions:",
-    [I18nKeys.Label.ContentPreview]: "Content Preview",
-    [I18nKeys.Label.ExpiresIn]: "Expires in:",
-    [I18nKeys.Label.Expires]: "Expires after:",
-    [I18nKeys.Label.FileSelect]: "Select files to share:",
-    [I18nKeys.Label.File]: "🗂️ File",
-    [I18nKeys.Label.Filename]: "Filename:",
-    [I18nKeys.Label.Key]: "Decryption Key:",
-    [I18nKeys.Label.RestrictAccess]: "Restrict access to this secret",
-    [I18nKeys.Label.SaveToken]: "Remember authentication token",
-    [I18nKeys.Label.SecretType]: "Secret Type:",
-    [I18nKeys.Label.Secret]: "Message:",
-    [I18nKeys.Label.SeparateKey]: "Show Key separately",
-    [I18nKeys.Label.Size]: "Size:",
-    [I18nKeys.Label.Text]: "📝 Text Message",
-    [I18nKeys.Label.Token]: "Token:",
-    [I18nKeys.Label.Url]: "Secret URL:",
-    [I18nKeys.Label.Passphrase]: "Passphrase Protection:",
-    [I18nKeys.Label.PassphraseInput]: "Passphrase:",
-
-    [I18nKeys.Placeholder.Secret]: "Enter your secret message here...",
-    [I18nKeys.Placeholder.Passphrase]: "Enter passphrase to protect this secret",
-    [I18nKeys.Placeholder.Token]: "Enter authentication token here...",
-
-    [I18nKeys.Restrictions.Legend]: "Access Restrictions (Optional):",
-    [I18nKeys.Restrictions.TabPassphrase]: "Passphrase",
-    [I18nKeys.Restrictions.TabIP]: "IP",
-    [I18nKeys.Restrictions.TabCountry]: "Country",
-    [I18nKeys.Restrictions.TabASN]: "Network",
-    [I18nKeys.Restrictions.Applied]: "Access Restrictions Applied:",
-
-    [I18nKeys.Helper.AllowedIPs]:
-      "Enter IP addresses or CIDR ranges (one per line) that can access this secret. Leave empty to allow access from any IP address.",
-    [I18nKeys.Helper.AllowedCountries]:
-      "Enter ISO 3166-1 alpha-2 country codes (one per line) that can access this secret. Leave empty to allow access from any country.",
-    [I18nKeys.Helper.AllowedASNs]:
-      "Enter Autonomous System Numbers (ASNs) (one per line) that can access this secret. Leave empty to allow access from any ASN.",
-    [I18nKeys.Helper.Url]:
-      "Enter the URL to access the secret.\nThe decryption key after # is never sent to the server",
-    [I18nKeys.Helper.Secret]: "Your message will be encrypted before leaving your browser",
-    [I18nKeys.Helper.FileSelect]: "Files will be encrypted before upload.",
-    [I18nKeys.Helper.Token]: "Leave empty if no authentication is required",
-    [I18nKeys.Helper.Key]: "Base64-encoded decryption key with optional hash (shared separately)",
-    [I18nKeys.Helper.SeparateKey]:
-      "When enabled, the URL and decryption key are displayed separately, allowing you to share them through different channels for enhanced security.",
-    [I18nKeys.Helper.RestrictAccess]: "Limit who can access this secret.",
-    [I18nKeys.Helper.SaveToken]:
-      "Token will be stored securely in your browser for the current session only. You will need to re-enter it when you start a new browser session.",
-    [I18nKeys.Helper.Expires]: "Secret will self-destruct after this time or first view",
-    [I18nKeys.Helper.Passphrase]:
-      "Require a passphrase to access this secret. The passphrase can be shared through a different channel (e.g., phone call) for enhanced security.",
-    [I18nKeys.Helper.PassphraseInput]: "This secret requires a passphrase to access",
-    [I18nKeys.Helper.OneTimeToken]:
-      "Creates a single use token to be used to allow one time exceptions of upload limits",
-
-    [I18nKeys.Aria.Passphrase]: "Enter a passphrase that will be required to access this secret",
-    [I18nKeys.Aria.PassphraseInput]: "Enter the passphrase for this protected secret",
-
-    [I18nKeys.Time.FiveMin]: "5 minutes",
-    [I18nKeys.Time.ThirtyMin]: "30 minutes",
-    [I18nKeys.Time.OneHour]: "1 hour",
-    [I18nKeys.Time.TwoHours]: "2 hours",
-    [I18nKeys.Time.TwelveHours]: "12 hours",
-    [I18nKeys.Time.TwentyFourHours]: "24 hours",
-    [I18nKeys.Time.SevenDays]: "7 days",
-    [I18nKeys.Time.Custom]: "Custom...",
-    [I18nKeys.Time.Minutes]: "minutes",
-    [I18nKeys.Time.Hours]: "hours",
-    [I18nKeys.Time.Days]: "days",
-    [I18nKeys.Time.Seconds]: "seconds",
-
-    [I18nKeys.Button.Create]: "🛡️ Create Secret",
-    [I18nKeys.Button.Retrieve]: "📖 Retrieve Secret",
-    [I18nKeys.Button.RetrieveAnother]: "📖 Retrieve Another",
-    [I18nKeys.Button.Copy]: "📋 Copy",
-    [I18nKeys.Button.Copied]: "Copied!",
-    [I18nKeys.Button.Share]: "🔗 Share",
-    [I18nKeys.Button.Close]: "Close",
-    [I18nKeys.Button.ShowQrCode]: "Show QR Code",
-    [I18nKeys.Button.Download]: "💾 Download",
-    [I18nKeys.Button.Preview]: "👁️ Show",
-    [I18nKeys.Button.ChooseFile]: "📁 Choose File",
-    [I18nKeys.Button.ReadClipboard]: "📄 Read Clipboard",
-    [I18nKeys.Button.CreateOneTimeToken]: "🔑 Create One-Time Token",
-
-    [I18nKeys.Dropzone.Primary]: "Drop files here or click to select",
-    [I18nKeys.Dropzone.Secondary]: "Supports all file types",
-    [I18nKeys.Dropzone.Helper]: "File will be encrypted before upload.",
-
-    [I18nKeys.Msg.Creating]: "Creating secret...",
-    [I18nKeys.Msg.Retrieving]: "Retrieving secret...",
-    [I18nKeys.Msg.JsRequired]: "JavaScript Required",
-    [I18nKeys.Msg.JsRequiredDetail]:
-      "This application requires JavaScript to encrypt secrets securely in your browser.",
-    [I18nKeys.Msg.EmptySecret]: "Please enter a secret to share",
-    [I18nKeys.Msg.EmptyFile]: "Please select a file to share",
-    [I18nKeys.Msg.FileSizeExceeded]: "File size ({{fileSize}}) exceeds the {{limit}} limit. Please use a smaller file.",
-    [I18nKeys.Msg.CreateFailed]: "Failed to create secret",
-    [I18nKeys.Msg.CreateOneTimeTokenFailed]: "Failed to create one-time token",
-    [I18nKeys.Msg.FileReadError]: "Error reading file",
-    [I18nKeys.Msg.InvalidFilename]: "Invalid filename. Please select a file with a valid name.",
-    [I18nKeys.Msg.EmptyUrl]: "Please enter a valid secret URL",
-    [I18nKeys.Msg.InvalidUrl]: "Invalid URL format. Please include the full URL with the secret key after #",
-    [I18nKeys.Msg.MissingKey]: "Please enter the decryption key",
-    [I18nKeys.Msg.RetrieveFailed]: "Failed to retrieve secret",
-    [I18nKeys.Msg.SuccessTitle]: "Success",
-    [I18nKeys.Msg.ErrorTitle]: "Error",
-    [I18nKeys.Msg.CopyFailed]: "Failed to copy. Please select and copy manually.",
-    [I18nKeys.Msg.CreateNote]: "The secret will be deleted after the first access or when it expires.",
-    [I18nKeys.Msg.ShareInstructions]:
-      "Share this URL with the intended recipient.\nThe secret is encrypted and can only be accessed once.",
-    [I18nKeys.Msg.ClipboardError]: "Clipboard Error",
-    [I18nKeys.Msg.ClipboardRequired]: "Clipboard Access Required",
-    [I18nKeys.Msg.ClipboardRequiredDetail]: "Click the button below to read from clipboard.",
-    [I18nKeys.Msg.ClipboardPermissionDenied]: "Clipboard access denied. Please grant permission and try again.",
-    [I18nKeys.Msg.ClipboardInvalidJson]: "Clipboard does not contain valid JSON",
-    [I18nKeys.Msg.ClipboardEmpty]: "Clipboard is empty",
-    [I18nKeys.Msg.ReadingClipboard]: "Reading clipboard...",
-    [I18nKeys.Msg.CreatingSecret]: "Creating secret...",
-    [I18nKeys.Msg.ShareSuccess]: "Your secret has been created and the URL copied to clipboard:",
-    [I18nKeys.Msg.ShareTitle]: "Hakanai Secret",
-    [I18nKeys.Msg.ShareText]: "Here is a secure one-time secret for you. It will self-destruct after viewing.",
-    [I18nKeys.Msg.Shared]: "Secret shared successfully",
-    [I18nKeys.Msg.ExpectedJsonFormat]: "Expected JSON format:",
-    [I18nKeys.Msg.RetrieveNote]: "This secret has been deleted from the server and cannot be accessed again.",
-    [I18nKeys.Msg.RetrieveCTA]: "Learn more and share your own secrets securely",
-    [I18nKeys.Msg.Downloaded]: "Secret downloaded as text file",
-    [I18nKeys.Msg.BinaryDetected]: "Use download button to save the file.",
-
-    [I18nKeys.Aria.ClosePreview]: "Close preview",
-    [I18nKeys.Aria.CopySecret]: "Copy secret to clipboard",
-    [I18nKeys.Aria.ShareSecret]: "Share via system share dialog",
-    [I18nKeys.Aria.DownloadSecret]: "Download secret as file",
-    [I18nKeys.Aria.DownloadQRCode]: "Download QR code as SVG file",
-    [I18nKeys.Aria.PreviewContent]: "Preview content",
-    [I18nKeys.Aria.AllowedIPs]: "Enter IP addresses or CIDR ranges, one per line, to restrict access to this secret",
-    [I18nKeys.Aria.AllowedCountries]:
-      "Enter 2-letter country codes, one per line, to restrict access to this secret by geographic location",
-    [I18nKeys.Aria.AllowedASNs]:
-      "Enter ASN numbers, one per line, to restrict access to this secret by network provider",
-    [I18nKeys.Aria.SecretInput]: "Enter the secret message you want to share securely",
-    [I18nKeys.Aria.FileInput]: "Select a file to share securely. The file will be encrypted before being sent.",
-    [I18nKeys.Aria.TokenInput]: "Enter the authentication token if required by the server",
-    [I18nKeys.Aria.ExpiresSelect]: "Select how long the secret should be available before it expires",
-    [I18nKeys.Aria.UrlInput]: "Enter the full URL including the secret key after the hash",
-    [I18nKeys.Aria.KeyInput]: "Enter the base64-encoded decryption key",
-    [I18nKeys.Aria.ThemeToggle]: "Switch between light and dark mode",
-    [I18nKeys.Aria.SwitchToLight]: "Switch to light mode",
-    [I18nKeys.Aria.SwitchToDark]: "Switch to dark mode",
-    [I18nKeys.Aria.LogoHome]: "Go to home page",
-
-    [I18nKeys.Meta.Create]: "Create and share one-time secrets securely with Hakanai - zero-knowledge secret sharing",
-    [I18nKeys.Meta.Get]: "Retrieve your one-time secret securely with Hakanai - zero-knowledge secret sharing",
-    [I18nKeys.Meta.Homepage]: "Hakanai - Zero-knowledge one-time secret sharing service",
-
-    [I18nKeys.Homepage.Tagline]: "Share secrets securely with zero-knowledge encryption",
-    [I18nKeys.Homepage.CreateDescription]:
-      "Share text messages or files securely. All encryption happens in your browser.",
-    [I18nKeys.Homepage.CreateButton]: "✨ Create Secret",
-    [I18nKeys.Homepage.RetrieveDescription]:
-      "Have a secret URL? Enter it here to decrypt and view your one-time secret.",
-    [I18nKeys.Homepage.RetrieveButton]: "📨 Retrieve Secret",
-    [I18nKeys.Homepage.Feature1Title]: "Zero-Knowledge",
-    [I18nKeys.Homepage.Feature1Description]: "Your secrets are encrypted in your browser before being sent",
-    [I18nKeys.Homepage.Feature2Title]: "One-Time",
-    [I18nKeys.Homepage.Feature2Description]: "Secrets are destroyed after being viewed once",
-    [I18nKeys.Homepage.Feature3Title]: "Secure",
-    [I18nKeys.Homepage.Feature3Description]: "AES-256-GCM encryption with SHA-256 based content integrity verification",
-    [I18nKeys.Homepage.Feature4Title]: "Private",
-    [I18nKeys.Homepage.Feature4Description]: "The server never sees your unencrypted data",
-    [I18nKeys.Homepage.Feature5Title]: "Open Source",
-    [I18nKeys.Homepage.Feature5Description]: "Fully open source and auditable on GitHub",
-    [I18nKeys.Homepage.DocsLink]: "View API Documentation",
-
-    [I18nKeys.Footer.Privacy]: "Privacy Policy",
-
-    [I18nKeys.Page.PrivacyTitle]: "Privacy Policy",
-
-    [I18nKeys.Error.AccessDenied]: "Access denied - you are not allowed to access the secret",
-    [I18nKeys.Error.RequestFailed]: "Request failed",
-    [I18nKeys.Error.AuthenticationRequired]: "Authentication required - Please enter your authentication token",
-    [I18nKeys.Error.InvalidToken]: "Invalid authentication token - Please check your token and try again",
-    [I18nKeys.Error.SecretNotFound]: "Secret not found or has expired",
-    [I18nKeys.Error.SecretAlreadyAccessed]: "Secret has been accessed and is no longer available",
-    [I18nKeys.Error.RetrieveFailed]: "Failed to retrieve secret",
-    [I18nKeys.Error.MissingDecryptionKey]: "No decryption key found in URL",
-    [I18nKeys.Error.MissingHash]: "No content integrity verification hash found in URL",
-    [I18nKeys.Error.PassphraseRequired]: "This secret is protected and requires a passphrase to access",
-    [I18nKeys.Error.PassphraseTooShort]: "Passphrase must be at least 8 characters long",
-    [I18nKeys.Error.InvalidIPAddress]: "Invalid IP address or CIDR notation",
-    [I18nKeys.Error.InvalidCountryCode]: "Invalid country code. Must be a 2-letter uppercase code (e.g., US, DE, CA)",
-    [I18nKeys.Error.InvalidASN]: "Invalid ASN. Must be between 1 and 4294967295",
-    [I18nKeys.Error.ASNMustBeNumber]: "ASN must be a number",
-    [I18nKeys.Error.PayloadTooLarge]: "Secret size exceeds the limit",
-    [I18nKeys.Error.HashValidationFailed]: "Hash validation failed - data may be tempered or corrupted",
-    [I18nKeys.Error.NotSupported]: "Feature not supported - the server does not support this operation",
-    [I18nKeys.Error.WebShareNotSupported]: "Web Share API is not supported in this browser",
-    [I18nKeys.Error.FileShareNotSupported]: "File sharing is not supported in this browser",
-    [I18nKeys.Error.ShareOptionsMissing]: "At least one of title, text, url, or files must be provided",
-    [I18nKeys.Error.CannotShareFiles]: "Browser cannot share these files",
-    [I18nKeys.Error.ExpectedUint8Array]: "Input must be a Uint8Array (binary data)",
-    [I18nKeys.Error.ExpectedString]: "Input must be a string (text data)",
-    [I18nKeys.Error.InvalidInputFormat]: "Input contains invalid characters or format",
-    [I18nKeys.Error.MissingKey]: "Secret key is required",
-    [I18nKeys.Error.InvalidKey]: "Secret key has invalid length or format",
-    [I18nKeys.Error.InvalidHash]: "Hash format is invalid (must be 22 characters long)",
-    [I18nKeys.Error.CryptoApiUnavailable]: "Web Crypto API is not available in this browser",
-    [I18nKeys.Error.InvalidTtl]: "TTL value must be a positive integer",
-    [I18nKeys.Error.MissingAuthToken]: "Authentication token is required",
-    [I18nKeys.Error.InvalidAuthToken]: "Authentication token format is invalid",
-    [I18nKeys.Error.Base64Error]: "Base64 encoding/decoding failed",
-    [I18nKeys.Error.InvalidEncryptedData]: "Encrypted data is corrupted or too short",
-    [I18nKeys.Error.DecryptionFailed]: "Decryption failed: invalid key or corrupted data",
-    [I18nKeys.Error.InvalidUrlFormat]: "Invalid URL format",
-    [I18nKeys.Error.MissingSecretId]: "URL is missing secret ID",
-    [I18nKeys.Error.InvalidSecretId]: "Secret ID format is invalid",
-    [I18nKeys.Error.InvalidPayload]: "Payload object is invalid or malformed",
-    [I18nKeys.Error.InvalidRestrictions]: "IP restrictions are invalid or malformed",
-    [I18nKeys.Error.InvalidServerResponse]: "Server response is missing required data",
-    [I18nKeys.Error.CryptoContextDisposed]: "Crypto context has been disposed and cannot be reused",
-
-    [I18nKeys.Validation.MissingData]: "Missing or invalid data field",
-    [I18nKeys.Validation.InvalidFilename]: "Invalid filename field - must be text",
-    [I18nKeys.Validation.InvalidToken]: "Invalid token field - must be text",
-    [I18nKeys.Validation.InvalidTtl]: "Invalid expiration time - must be a positive number",
-    [I18nKeys.Validation.EmptyJson]: "Clipboard content is empty",
-    [I18nKeys.Validation.InvalidJsonFormat]: "Invalid clipboard format - not valid JSON",
-
-    [I18nKeys.FileList.BundleNotice]: "Will be bundled as TAR archive",
-    [I18nKeys.FileList.OneFileSelected]: "1 file selected",
-    [I18nKeys.FileList.FilesSelected]: "files selected",
-  },
-  de: {
-    [I18nKeys.Page.CreateTitle]: "Hakanai - Secret erstellen",
-    [I18nKeys.Page.GetTitle]: "Hakanai - Secret abrufen",
-    [I18nKeys.Page.HomepageTitle]: "Hakanai - Einmal-Secret-Sharing",
-    [I18nKeys.Page.ShareTitle]: "Hakanai - Daten teilen",
-    [I18nKeys.Page.OneTimeTokenTitle]: "Hakanai - Einmal-Token erzeugen",
-
-    [I18nKeys.Label.AllowedIPs]: "IP-Adress-Beschränkungen:",
-    [I18nKeys.Label.AllowedCountries]: "Länder-Beschränkungen:",
-    [I18nKeys.Label.AllowedASNs]: "Netzwerk-(ASN-)Beschränkungen:",
-    [I18nKeys.Label.Secret]: "Text:",
-    [I18nKeys.Label.SecretType]: "Secret-Typ:",
-    [I18nKeys.Label.Text]: "📝 Text-Nachricht",
-    [I18nKeys.Label.File]: "🗂️ Datei",
-    [I18nKeys.Label.FileSelect]: "Dateien zum Teilen auswählen:",
-    [I18nKeys.Label.Token]: "Token:",
-    [I18nKeys.Label.Expires]: "Läuft ab nach:",
-    [I18nKeys.Label.Url]: "Secret-URL:",
-    [I18nKeys.Label.Key]: "Geheimer Schlüssel:",
-    [I18nKeys.Label.RestrictAccess]: "Zugriff auf dieses Secret beschränken",
-    [I18nKeys.Label.SeparateKey]: "Schlüssel separat anzeigen",
-    [I18nKeys.Label.SaveToken]: "Token merken",
-    [I18nKeys.Label.Filename]: "Dateiname:",
-    [I18nKeys.Label.Size]: "Größe:",
-    [I18nKeys.Label.ExpiresIn]: "Läuft ab in:",
-    [I18nKeys.Label.ContentPreview]: "Inhaltsvorschau",
-    [I18nKeys.Label.Passphrase]: "Passphrase-Schutz:",
-    [I18nKeys.Label.PassphraseInput]: "Passphrase:",
-
-    [I18nKeys.Placeholder.Secret]: "Hier wird gen geheime Text eingegeben...",
-    [I18nKeys.Placeholder.Passphrase]: "Passphrase zum Schutz dieses Secrets eingeben",
-    [I18nKeys.Placeholder.Token]: "Authentifizierungs-Token eingeben",
-
-    [I18nKeys.Restrictions.Legend]: "Zugriffsbeschränkungen (Optional):",
-    [I18nKeys.Restrictions.TabPassphrase]: "Passphrase",
-    [I18nKeys.Restrictions.TabIP]: "IP",
-    [I18nKeys.Restrictions.TabCountry]: "Land",
-    [I18nKeys.Restrictions.TabASN]: "Netzwerk",
-    [I18nKeys.Restrictions.Applied]: "Zugriffsbeschränkungen:",
-
-    [I18nKeys.Helper.AllowedIPs]:
-      "IP-Adressen oder CIDR-Bereiche (eine pro Zeile), die auf dieses Secret zugreifen können. Leer lassen, um Zugriff von jeder IP-Adresse zu ermöglichen.",
-    [I18nKeys.Helper.AllowedCountries]:
-      "ISO 3166-1 alpha-2 Ländercodes (eine pro Zeile), die auf dieses Secret zugreifen können. Leer lassen, um Zugriff aus jedem Land zu ermöglichen.",
-    [I18nKeys.Helper.AllowedASNs]:
-      "Autonome Systemnummern (ASNs) (eine pro Zeile), die auf dieses Secret zugreifen können. Leer lassen um Zugriff aus allen autonomen Systemen zu ermöglichen.",
-    [I18nKeys.Helper.Url]:
-      "URL eigeben um auf das Secret zuzugreifen.\nDer geheime Schlüssel nach dem # wird niemals an den Server gesendet",
-    [I18nKeys.Helper.Secret]: "Die Nachricht wird verschlüsselt, bevor sie den Browser verlässt",
-    [I18nKeys.Helper.FileSelect]: "Die Dateien werden vor dem Upload verschlüsselt.",
-    [I18nKeys.Helper.Token]: "Kann leer gelassen werden, wenn keine Authentifizierung erforderlich ist",
-    [I18nKeys.Helper.Key]: "Base64-kodierter geheimer Schlüssel mit optionalen Hash-Informationen (separat geteilt)",
-    [I18nKeys.Helper.SeparateKey]:
-      "Wenn aktiviert, werden URL und geheimer Schlüssel separat angezeigt, so dass sie über verschiedene Kanäle für erweiterte Sicherheit geteilt werden können.",
-    [I18nKeys.Helper.RestrictAccess]:
-      "Zugriff auf dieses Secret auf IP-Adresse, Land oder Netzwerkanbieter beschränken.",
-    [I18nKeys.Helper.SaveToken]:
-      "Token wird sicher für die Session im Browser gespeichert. Nach dem Schließen des Tabs muss dieses neu eigegeben werden.",
-    [I18nKeys.Helper.Expires]: "Das Secret wird nach dieser Zeit oder beim ersten Zugriff selbst zerstört",
-    [I18nKeys.Helper.Passphrase]:
-      "Erfordert eine Passphrase zum Zugriff auf dieses Secret. Für erhöhte Sicheheit kann die Passphrase über einen anderen Kanal (z.B. Telefonanruf) geteilt werden.",
-    [I18nKeys.Helper.PassphraseInput]: "Dieses Secret erfordert eine Passphrase für den Zugriff",
-    [I18nKeys.Helper.OneTimeToken]:
-      "Generiert ein Einmal-Token, welches einmalig das erstellen eines Secrets über die geltenden Upload-Limits erlaubt",
-
-    [I18nKeys.Aria.Passphrase]: "Passphrase eingeben, die für den Zugriff auf dieses Secret erforderlich ist",
-    [I18nKeys.Aria.PassphraseInput]: "Passphrase für dieses geschützte Secret eingeben",
-
-    [I18nKeys.Time.FiveMin]: "5 Minuten",
-    [I18nKeys.Time.ThirtyMin]: "30 Minuten",
-    [I18nKeys.Time.OneHour]: "1 Stunde",
-    [I18nKeys.Time.TwoHours]: "2 Stunden",
-    [I18nKeys.Time.TwelveHours]: "12 Stunden",
-    [I18nKeys.Time.TwentyFourHours]: "24 Stunden",
-    [I18nKeys.Time.SevenDays]: "7 Tage",
-    [I18nKeys.Time.Custom]: "Benutzerdefiniert...",
-    [I18nKeys.Time.Minutes]: "Minuten",
-    [I18nKeys.Time.Hours]: "Stunden",
-    [I18nKeys.Time.Days]: "Tage",
-    [I18nKeys.Time.Seconds]: "Sekunden",
-
-    [I18nKeys.Button.Create]: "🛡️ Secret erstellen",
-    [I18nKeys.Button.Retrieve]: "📖 Secret abrufen",
-    [I18nKeys.Button.RetrieveAnother]: "📖 Weiteres abrufen",
-    [I18nKeys.Button.Copy]: "📋 Kopieren",
-    [I18nKeys.Button.Copied]: "Kopiert!",
-    [I18nKeys.Button.Share]: "🔗 Teilen",
-    [I18nKeys.Button.Close]: "Schließen",
-    [I18nKeys.Button.ShowQrCode]: "QR-Code anzeigen",
-    [I18nKeys.Button.Download]: "💾 Speichern",
-    [I18nKeys.Button.Preview]: "👁️ Vorschau",
-    [I18nKeys.Button.ChooseFile]: "📁 Datei auswählen",
-    [I18nKeys.Button.ReadClipboard]: "📄 Zwischenablage lesen",
-    [I18nKeys.Button.CreateOneTimeToken]: "🔑 One-Time Token erzeugen",
-
-    [I18nKeys.Dropzone.Primary]: "Dateien hier ablegen oder zum Auswählen klicken",
-    [I18nKeys.Dropzone.Secondary]: "Unterstützt alle Dateitypen",
-    [I18nKeys.Dropzone.Helper]: "Die Datei wird vor dem Upload verschlüsselt.",
-
-    [I18nKeys.Msg.Creating]: "Secret wird erstellt...",
-    [I18nKeys.Msg.Retrieving]: "Secret wird abgerufen...",
-    [I18nKeys.Msg.JsRequired]: "JavaScript erforderlich",
-    [I18nKeys.Msg.JsRequiredDetail]:
-      "Diese Anwendung benötigt JavaScript, um Secrets sicher im Browser zu verschlüsseln.",
-    [I18nKeys.Msg.EmptySecret]: "Bitte den Text für das Secret eingeben",
-    [I18nKeys.Msg.EmptyFile]: "Bitte eine Datei zum Teilen auswählen",
-    [I18nKeys.Msg.FileSizeExceeded]:
-      "Dateigröße ({{fileSize}}) überschreitet das {{limit}} Limit. Bitte eine kleinere Datei verwenden.",
-    [I18nKeys.Msg.CreateFailed]: "Fehler beim Erstellen des Secrets",
-    [I18nKeys.Msg.CreateOneTimeTokenFailed]: "Fehler beim Erstellen des Tokens",
-    [I18nKeys.Msg.FileReadError]: "Fehler beim Lesen der Datei",
-    [I18nKeys.Msg.InvalidFilename]: "Ungültiger Dateiname. Bitte eine Datei mit einem gültigen Namen auswählen.",
-    [I18nKeys.Msg.EmptyUrl]: "Bitte eine gültige Secret-URL eingeben",
-    [I18nKeys.Msg.InvalidUrl]:
-      "Ungültiges URL-Format. Bitte vollständige URL einschließlich des Teils nach dem # eingeben",
-    [I18nKeys.Msg.MissingKey]: "Bitte den geheimen Schlüssel eingeben",
-    [I18nKeys.Msg.RetrieveFailed]: "Fehler beim Abrufen des Secrets",
-    [I18nKeys.Msg.SuccessTitle]: "Erfolg",
-    [I18nKeys.Msg.ErrorTitle]: "Fehler",
-    [I18nKeys.Msg.CopyFailed]: "Kopieren fehlgeschlagen. Bitte manuell auswählen und kopieren.",
-    [I18nKeys.Msg.CreateNote]: "Das Secret wird nach dem ersten Zugriff oder bei Ablauf gelöscht.",
-    [I18nKeys.Msg.ShareInstructions]:
-      "Diese URL kann nun mit dem vorgesehenen Empfänger geteilt werden.\nDas Secret ist verschlüsselt und kann nur einmal abgerufen werden.",
-    [I18nKeys.Msg.ClipboardError]: "Zwischenablage-Fehler",
-    [I18nKeys.Msg.ClipboardRequired]: "Zwischenablage-Zugriff erforderlich",
-    [I18nKeys.Msg.ClipboardRequiredDetail]: "Bitte den Button klicken, um aus der Zwischenablage zu lesen.",
-    [I18nKeys.Msg.ClipboardPermissionDenied]:
-      "Zwischenablage-Zugriff verweigert. Bitte Berechtigung erteilen und erneut versuchen.",
-    [I18nKeys.Msg.ClipboardInvalidJson]: "Zwischenablage enthält kein gültiges JSON",
-    [I18nKeys.Msg.ClipboardEmpty]: "Zwischenablage ist leer",
-    [I18nKeys.Msg.ReadingClipboard]: "Zwischenablage wird gelesen...",
-    [I18nKeys.Msg.CreatingSecret]: "Secret wird erstellt...",
-    [I18nKeys.Msg.ShareSuccess]: "Das Secret wurde erstellt und die URL in die Zwischenablage kopiert:",
-    [I18nKeys.Msg.ShareTitle]: "Hakanai Secret",
-    [I18nKeys.Msg.ShareText]: "Hier ein sicheres Einmal-Secret. Es wird nach dem Ansehen gelöscht.",
-    [I18nKeys.Msg.Shared]: "Secret erfolgreich geteilt",
-    [I18nKeys.Msg.ExpectedJsonFormat]: "Erwartetes JSON-Format:",
-    [I18nKeys.Msg.RetrieveNote]: "Dieses Secret wurde vom Server gelöscht und kann nicht erneut abgerufen werden.",
-    [I18nKeys.Msg.RetrieveCTA]: "Mehr erfahren und eigene Secrets sicher teilen",
-    [I18nKeys.Msg.Downloaded]: "Secret als Textdatei heruntergeladen",
-    [I18nKeys.Msg.BinaryDetected]: "Bitte Download-Button verwenden, um die Datei zu speichern.",
-
-    [I18nKeys.Aria.AllowedIPs]:
-      "IP-Adressen oder CIDR-Bereiche eingeben, eine pro Zeile, um den Zugriff auf dieses Secret zu beschränken",
-    [I18nKeys.Aria.AllowedCountries]:
-      "2-Buchstaben-Ländercodes eingeben, eine pro Zeile, um den Zugriff auf dieses Secret nach geografischer Lage zu beschränken",
-    [I18nKeys.Aria.AllowedASNs]:
-      "ASN-Nummern eingeben, eine pro Zeile, um den Zugriff auf dieses Secret nach Netzwerkanbieter zu beschränken",
-    [I18nKeys.Aria.ClosePreview]: "Vorschau schließen",
-    [I18nKeys.Aria.CopySecret]: "Secret in die Zwischenablage kopieren",
-    [I18nKeys.Aria.ShareSecret]: "Über System-Dialog teilen",
-    [I18nKeys.Aria.DownloadSecret]: "Secret als Datei herunterladen",
-    [I18nKeys.Aria.DownloadQRCode]: "QR-Code als SVG-Datei herunterladen",
-    [I18nKeys.Aria.PreviewContent]: "Inhalt anzeigen",
-    [I18nKeys.Aria.SecretInput]: "Bitte die geheime Nachricht eingeben, die sicher geteult werden soll",
-    [I18nKeys.Aria.FileInput]: "Datei zum sicheren Teilen auswählen. Die Datei wird vor dem Versenden verschlüsselt.",
-    [I18nKeys.Aria.TokenInput]: "Bitte den Authentifizierungs-Token eingeben, falls vom Server erforderlich",
-    [I18nKeys.Aria.ExpiresSelect]: "Bitte die Zeit auswählen, nach der das Secret abläuft",
-    [I18nKeys.Aria.UrlInput]: "Bitte die vollständige URL einschließlich des Schlüssels nach dem Hash eingeben",
-    [I18nKeys.Aria.KeyInput]: "Bitte den Base64-kodierten geheimen Schlüssel eingeben",
-    [I18nKeys.Aria.ThemeToggle]: "Zwischen hellem und dunklem Modus wechseln",
-    [I18nKeys.Aria.SwitchToLight]: "Zum hellen Modus wechseln",
-    [I18nKeys.Aria.SwitchToDark]: "Zum dunklen Modus wechseln",
-    [I18nKeys.Aria.LogoHome]: "Zur Startseite gehen",
-
-    [I18nKeys.Meta.Create]: "One-Time-Secrets sicher erstellen und teilen mit Hakanai",
-    [I18nKeys.Meta.Get]: "One-Time-Secrets sicher teilen mit Hakanai",
-    [I18nKeys.Meta.Homepage]: "Hakanai - Zero-Knowledge One-Time Secret-Sharing Service",
-
-    [I18nKeys.Homepage.Tagline]: "Secrets sicher teilen mit Zero-Knowledge-Verschlüsselung",
-    [I18nKeys.Homepage.CreateDescription]:
-      "Textnachrichten oder Dateien sicher teilen. Die Verschlüsselung erfolgt im Browser.",
-    [I18nKeys.Homepage.CreateButton]: "✨ Secret erstellen",
-    [I18nKeys.Homepage.RetrieveDescription]:
-      "Hier kann der Secret-Link eingegeben werden, um das Einmal-Secret zu entschlüsseln und anzuzeigen.",
-    [I18nKeys.Homepage.RetrieveButton]: "📨 Secret abrufen",
-    [I18nKeys.Homepage.Feature1Title]: "Zero-Knowledge",
-    [I18nKeys.Homepage.Feature1Description]: "Die Secrets werden im Browser verschlüsselt, bevor sie gesendet werden",
-    [I18nKeys.Homepage.Feature2Title]: "Einmalig",
-    [I18nKeys.Homepage.Feature2Description]: "Secrets werden nach dem ersten Abruf gelöscht",
-    [I18nKeys.Homepage.Feature3Title]: "Sicher",
-    [I18nKeys.Homepage.Feature3Description]: "AES-256-GCM-Verschlüsselung mit SHA-256 basierter Integritätsprüfung",
-    [I18nKeys.Homepage.Feature4Title]: "Privat",
-    [I18nKeys.Homepage.Feature4Description]: "Der Server sieht niemals die unverschlüsselten Daten",
-    [I18nKeys.Homepage.Feature5Title]: "Open Source",
-    [I18nKeys.Homepage.Feature5Description]: "Vollständig Open Source und auf GitHub auditierbar",
-    [I18nKeys.Homepage.DocsLink]: "API-Dokumentation anzeigen",
-
-    [I18nKeys.Footer.Privacy]: "Datenschutzerklärung",
-
-    [I18nKeys.Page.PrivacyTitle]: "Datenschutzerklärung",
-
-    [I18nKeys.Error.AccessDenied]: "Zugriff verweigert - Keine Berechtigung auf das Secret zuzugreifen",
-    [I18nKeys.Error.RequestFailed]: "Fehler beim Verarbeiten der Anfrage",
-    [I18nKeys.Error.AuthenticationRequired]: "Authentifizierung erforderlich - Bitte Authentifizierungs-Token eingeben",
-    [I18nKeys.Error.InvalidToken]: "Ungültiges Authentifizierungs-Token - Bitte Token überprüfen und erneut versuchen",
-    [I18nKeys.Error.SecretNotFound]: "Secret nicht gefunden oder abgelaufen",
-    [I18nKeys.Error.SecretAlreadyAccessed]: "Secret wurde bereits abgerufen und ist nicht mehr verfügbar",
-    [I18nKeys.Error.RetrieveFailed]: "Fehler beim Abrufen des Secrets",
-    [I18nKeys.Error.MissingDecryptionKey]: "Kein Schlüssel in der URL gefunden",
-    [I18nKeys.Error.MissingHash]: "Kein Verifizierungs-Hash-Code in der URL gefunden",
-    [I18nKeys.Error.PassphraseRequired]: "Dieses Secret ist geschützt und erfordert eine Passphrase zum Zugriff",
-    [I18nKeys.Error.PassphraseTooShort]: "Passphrase muss mindestens 8 Zeichen lang sein",
-    [I18nKeys.Error.InvalidIPAddress]: "Ungültige IP-Adresse oder CIDR-Notation",
-    [I18nKeys.Error.InvalidCountryCode]:
-      "Ungültiger Ländercode. Muss ein 2-stelliger Großbuchstaben-Code sein (z.B. US, DE, CA)",
-    [I18nKeys.Error.InvalidASN]: "Ungültige ASN. Muss zwischen 1 und 4294967295 liegen",
-    [I18nKeys.Error.ASNMustBeNumber]: "ASN muss eine Zahl sein",
-    [I18nKeys.Error.ExpectedUint8Array]: "Eingabe muss ein Uint8Array (binäre Daten) sein",
-    [I18nKeys.Error.ExpectedString]: "Eingabe muss ein String (Textdaten) sein",
-    [I18nKeys.Error.InvalidInputFormat]: "Eingabe enthält ungültige Zeichen oder Format",
-    [I18nKeys.Error.MissingKey]: "Geheimer Schlüssel ist erforderlich",
-    [I18nKeys.Error.InvalidKey]: "Verschlüsselungsschlüssel hat ungültige Länge oder Format",
-    [I18nKeys.Error.InvalidHash]: "Hash-Format ist ungültig (muss 22 Zeichen lang sein)",
-    [I18nKeys.Error.CryptoApiUnavailable]: "Web Crypto API ist in diesem Browser nicht verfügbar",
-    [I18nKeys.Error.InvalidTtl]: "TTL-Wert muss eine positive Ganzzahl sein",
-    [I18nKeys.Error.MissingAuthToken]: "Authentifizierungs-Token ist erforderlich",
-    [I18nKeys.Error.InvalidAuthToken]: "Authentifizierungs-Token-Format ist ungültig",
-    [I18nKeys.Error.Base64Error]: "Base64-Kodierung/Dekodierung fehlgeschlagen",
-    [I18nKeys.Error.InvalidEncryptedData]: "Verschlüsselte Daten sind beschädigt oder ungültig",
-    [I18nKeys.Error.DecryptionFailed]: "Entschlüsselung fehlgeschlagen: ungültiger Schlüssel oder beschädigte Daten",
-    [I18nKeys.Error.InvalidUrlFormat]: "Ungültiges URL-Format",
-    [I18nKeys.Error.MissingSecretId]: "URL fehlt die Secret-ID",
-    [I18nKeys.Error.InvalidSecretId]: "Secret-ID-Format ist ungültig",
-    [I18nKeys.Error.InvalidPayload]: "Payload-Objekt ist ungültig oder fehlerhaft",
-    [I18nKeys.Error.InvalidRestrictions]: "IP-Beschränkungen sind ungültig oder fehlerhaft",
-    [I18nKeys.Error.InvalidServerResponse]: "Server-Antwort fehlt erforderliche Daten",
-    [I18nKeys.Error.CryptoContextDisposed]: "Crypto-Kontext wurde entsorgt und kann nicht wiederverwendet werden",
-    [I18nKeys.Error.HashValidationFailed]:
-      "Validierung fehlgeschlagen - Daten könnten beschädigt oder verändert worden sein",
-    [I18nKeys.Error.NotSupported]: "Funktion nicht unterstützt - der Server unterstützt diese Operation nicht",
-    [I18nKeys.Error.WebShareNotSupported]: "Web Share API wird in diesem Browser nicht unterstützt",
-    [I18nKeys.Error.FileShareNotSupported]: "Dateifreigabe wird in diesem Browser nicht unterstützt",
-    [I18nKeys.Error.ShareOptionsMissing]:
-      "Mindestens eine der Optionen title, text, url oder files muss angegeben werden",
-    [I18nKeys.Error.CannotShareFiles]: "Browser kann diese Dateien nicht teilen",
-    [I18nKeys.Error.PayloadTooLarge]: "Secret-Größe überschreitet das Limit",
-
-    [I18nKeys.Validation.MissingData]: "Fehlende oder ungültige Daten",
-    [I18nKeys.Validation.InvalidFilename]: "Ungültiger Dateiname - muss Text sein",
-    [I18nKeys.Validation.InvalidToken]: "Ungültiger Token - muss Text sein",
-    [I18nKeys.Validation.InvalidTtl]: "Ungültige Ablaufzeit - muss eine positive Zahl sein",
-    [I18nKeys.Validation.EmptyJson]: "Zwischenablage ist leer",
-    [I18nKeys.Validation.InvalidJsonFormat]: "Ungültiges Format der Zwischenablage - kein gültiges JSON",
-
-    [I18nKeys.FileList.BundleNotice]: "Wird zu einem TAR-Archiv zusammengeführt",
-    [I18nKeys.FileList.OneFileSelected]: "1 Datei ausgewählt",
-    [I18nKeys.FileList.FilesSelected]: "Dateien ausgewählt",
-  },
-};
+// Language packs are shared with the server, which serves them at /i18n/{lang}.json
+const translations: Translations = { en, de };
 
 const LANGUAGE_STORAGE_KEY = "hakanai-lang";
 
@@ -4,15 +4,9 @@
  * QR Code generator using WebAssembly
  */
 
-// Type definitions for the WASM module
-interface QrGeneratorWasm {
-  generate_svg(text: string, size: number): string;
-}
+import { loadWasmModule, type HakanaiWasm } from "./wasm-loader";
 
-interface WasmModule {
-  default(): Promise<void>;
-  QrGenerator: new () => QrGeneratorWasm;
-}
+type QrGeneratorWasm = InstanceType<HakanaiWasm["QrGenerator"]>;
 
 export class QRCodeGenerator {
   private static generator: QrGeneratorWasm | null = null;
@@ -32,17 +26,8 @@ export class QRCodeGenerator {
    * Load the WASM QR code module
    */
   private static async loadWasm(): Promise<void> {
-    try {
-      // Dynamic import of the WASM module
-      const module = (await import("/hakanai_wasm.js")) as unknown as WasmModule;
-
-      // Initialize the WASM module
-      await module.default();
-
-      this.generator = new module.QrGenerator();
-    } catch (error) {
-      this.generator = null;
-    }
+    const module = await loadWasmModule();
+    this.generator = module ? new module.QrGenerator() : null;
   }
 
   /**
@@ -61,6 +46,38 @@ export class QRCodeGenerator {
     }
   }
 
+  /**
+   * Generate QR code PNG image for the given URL
+   * @param url - URL to encode in QR code
+   * @param size - Minimum size of the QR code in pixels (default: 200)
+   * @returns PNG image data or null if generation failed
+   */
+  static generateQRCodePng(url: string, size: number = 200): Uint8Array | null {
+    if (!this.generator) return null;
+
+    try {
+      return this.generator.generate_png(url, size);
+    } catch (error) {
+      return null;
+    }
+  }
+
+  /**
+   * Set the margin (quiet zone) around generated QR codes
+   * @param margin - Margin in modules (0-16, default: 4)
+   * @returns true if the margin was applied
+   */
+  static setMargin(margin: number): boolean {
+    if (!this.generator) return false;
+
+    try {
+      this.generator.set_margin(margin);
+      return true;
+    } catch (error) {
+      return false;
+    }
+  }
+
   /**
    * Check if QR code generation is available
    */
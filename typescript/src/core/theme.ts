@@ -4,7 +4,7 @@ import { I18nKeys } from "./i18n";
 
 const THEME_KEY = "hakanai-theme";
 
-type Theme = "light" | "dark";
+export type Theme = "light" | "dark";
 
 function isValidTheme(theme: unknown): theme is Theme {
   return theme === "light" || theme === "dark";
@@ -50,6 +50,19 @@ export function applyTheme(theme: Theme | null): void {
   }
 }
 
+/**
+ * Apply the default theme of the instance if the user has no saved preference
+ * @param theme - Default theme configured on the server
+ */
+export function applyDefaultTheme(theme: Theme): void {
+  if (!isValidTheme(theme) || getTheme() !== null) {
+    return;
+  }
+
+  applyTheme(theme);
+  updateThemeToggleButton();
+}
+
 /**
  * Toggle between light and dark theme
  */
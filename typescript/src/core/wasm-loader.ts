@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Loader of the Hakanai WebAssembly module (QR codes, file checks, passphrase generation and link parsing)
+ */
+
+// Type definitions for the exports of the WASM module
+export interface HakanaiWasm {
+  default(): Promise<void>;
+  QrGenerator: new () => {
+    set_margin(margin: number): void;
+    generate_svg(text: string, size: number): string;
+    generate_png(text: string, size: number): Uint8Array;
+  };
+  hash_file(content: Uint8Array): string;
+  hash_content(content: Uint8Array): string;
+  is_size_allowed(totalSize: number, secretSizeLimit?: number): boolean;
+  generate_passphrase(wordCount?: number): string;
+  parse_secret_link(link: string): {
+    id: string;
+    key?: string;
+    hash?: string;
+    boundId?: string;
+  };
+}
+
+let loadPromise: Promise<HakanaiWasm | null> | null = null;
+
+async function importWasm(): Promise<HakanaiWasm | null> {
+  try {
+    // Dynamic import of the WASM module
+    const module = (await import("/hakanai_wasm.js")) as unknown as HakanaiWasm;
+
+    // Initialize the WASM module
+    await module.default();
+    return module;
+  } catch (error) {
+    return null;
+  }
+}
+
+/**
+ * Load the WASM module (loads once, cached for reuse)
+ * @returns Initialized module or null if loading failed
+ */
+export function loadWasmModule(): Promise<HakanaiWasm | null> {
+  if (!loadPromise) {
+    loadPromise = importWasm();
+  }
+  return loadPromise;
+}
+
+/**
+ * Reset the cached module (for testing)
+ */
+export function resetWasmModule(): void {
+  loadPromise = null;
+}
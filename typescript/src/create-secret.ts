@@ -19,6 +19,7 @@ import { RestrictionData, toSecretRestrictions } from "./core/restriction-data";
 import { SizeLimitIndicator } from "./components/size-limit";
 import { registerServiceWorker } from "./core/service-worker";
 import { sanitizeFileName, readFileAsArrayBuffer } from "./core/file-utils";
+import { hashFile, isSizeAllowed } from "./core/file-checks";
 import { FileListComponent } from "./components/file-list";
 import { TarBuilder } from "./core/tar-builder";
 
@@ -333,6 +334,18 @@ function showFileInfo(file: File, fileName: string, elements: FileElements): voi
   fileSizeSpan.textContent = `(${formatFileSize(file.size)})`;
   showElement(fileInfoDiv);
   fileInfoDiv.className = "file-info";
+  void showFileChecksum(file);
+}
+
+async function showFileChecksum(file: File): Promise<void> {
+  const checksumSpan = document.getElementById("fileChecksum");
+  if (!checksumSpan) return;
+
+  checksumSpan.textContent = "";
+  const checksum = await hashFile(file);
+  if (checksum) {
+    checksumSpan.textContent = `SHA-256: ${checksum}`;
+  }
 }
 
 function hideFileInfo(elements: FileElements): void {
@@ -369,7 +382,7 @@ async function validateFileSize(file: File): Promise<boolean> {
   const config = await fetchAppConfig();
   const limit = config?.secretSizeLimit || 0;
 
-  if (limit > 0 && file.size > limit) {
+  if (limit > 0 && !(await isSizeAllowed(file.size, limit))) {
     const limitInKB = Math.round(limit / 1024);
     const fileSizeInKB = Math.round(file.size / 1024);
     showError(
@@ -566,6 +579,15 @@ function initTTLSelector(): void {
   }
 
   ttlSelector = new TTLSelector(ttlContainer);
+
+  fetchAppConfig().then((config) => {
+    if (config?.minTtl || config?.maxTtl) {
+      ttlSelector?.limitToTTLRange(
+        config.minTtl ?? 0,
+        config.maxTtl ?? Number.MAX_SAFE_INTEGER,
+      );
+    }
+  });
 }
 
 function initKeyboardShortcuts(): void {
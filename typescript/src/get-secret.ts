@@ -7,6 +7,7 @@ import { debounce, hideElement, secureInputClear, showElement, unexpandView } fr
 import { displayErrorMessage } from "./components/error-display";
 import { ProgressBar } from "./components/progress-bar";
 import { initTheme } from "./core/theme";
+import { formatFileSize } from "./core/formatters";
 import { ErrorHandler, handleAPIError, isHakanaiError } from "./core/error";
 import { initFeatures } from "./core/app-config";
 import { showSecret } from "./components/get-result";
@@ -27,6 +28,7 @@ function getElements() {
     keyInputGroup: document.getElementById("keyInputGroup") as HTMLElement,
     passphraseInput: document.getElementById("passphraseInput") as HTMLInputElement,
     passphraseInputGroup: document.getElementById("passphraseInputGroup") as HTMLElement,
+    metaInfo: document.getElementById("secretMetaInfo") as HTMLElement,
     resultDiv: document.getElementById("result") as HTMLElement,
     button: document.getElementById("retrieveBtn") as HTMLButtonElement,
   };
@@ -134,6 +136,7 @@ async function performRetrieval(url: string, passphrase?: string): Promise<void>
     clearInputs();
     updateKeyInputVisibility();
     hidePassphraseInput();
+    hideMetadata();
     hideForm();
   } catch (error: unknown) {
     handleRetrieveError(error, url);
@@ -190,6 +193,33 @@ function hidePassphraseInput(): void {
   passphraseInput.required = false;
 }
 
+/**
+ * Shows what to expect before the one-time retrieval, if the server provides secret metadata
+ */
+async function loadMetadata(): Promise<void> {
+  const match = window.location.pathname.match(/^\/s\/([^\/]+)$/);
+  if (!match) return;
+
+  const metadata = await client.getSecretMetadata(match[1]);
+  const { metaInfo } = getElements();
+  if (!metadata || !metaInfo) return;
+
+  const key = metadata.file ? I18nKeys.Msg.SecretMetaFile : I18nKeys.Msg.SecretMetaText;
+  let text = window.i18n.t(key, { size: formatFileSize(metadata.size_class) });
+  if (metadata.has_passphrase) {
+    text += " " + window.i18n.t(I18nKeys.Msg.SecretMetaPassphrase);
+    showPassphraseInput();
+  }
+
+  metaInfo.textContent = text;
+  showElement(metaInfo);
+}
+
+function hideMetadata(): void {
+  const { metaInfo } = getElements();
+  if (metaInfo) hideElement(metaInfo);
+}
+
 function hideForm(): void {
   const form = document.getElementById("secretForm");
   if (!form) return;
@@ -342,6 +372,7 @@ document.addEventListener("DOMContentLoaded", async () => {
   initFeatures();
   initKeyboardShortcuts();
   setupSmartFocus();
+  await loadMetadata();
 
   await registerServiceWorker();
 });
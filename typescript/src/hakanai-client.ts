@@ -21,4 +21,4 @@ export { CryptoContext } from "./client/crypto-operations";
 export { SecureMemory } from "./client/secure-memory";
 export { type PayloadData, PayloadDataImpl } from "./client/payload";
 export { type DataTransferObserver } from "./client/progress-observer";
-export { HakanaiClient, SecretRequest, SecretResponse, SecretRestrictions } from "./client/client";
+export { HakanaiClient, SecretMetadata, SecretRequest, SecretResponse, SecretRestrictions } from "./client/client";
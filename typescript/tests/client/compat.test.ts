@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Compatibility tests against the test vectors generated by hakanai-lib
+ * (lib/fixtures/test-vectors.json), verifying that secrets created by the Rust client
+ * can be decrypted by the TypeScript client.
+ */
+
+import { Base64UrlSafe, CryptoContext, HashUtils, PayloadDataImpl, UrlParser } from "../../src/hakanai-client";
+import vectors from "../../../lib/fixtures/test-vectors.json";
+
+interface TestVector {
+  description: string;
+  key: string;
+  nonce: string;
+  payload: {
+    data: string;
+    filename: string | null;
+    contentType: string | null;
+    description: string | null;
+    checksum: string | null;
+  };
+  padded: boolean;
+  plaintext: string;
+  hash: string;
+  envelope: string;
+  fragment: string;
+}
+
+function decodeBase64(encoded: string): Uint8Array {
+  return new Uint8Array(Buffer.from(encoded, "base64"));
+}
+
+describe("Compatibility with hakanai-lib test vectors", () => {
+  const testVectors = vectors as TestVector[];
+
+  test("fixture contains test vectors", () => {
+    expect(testVectors.length).toBeGreaterThan(0);
+  });
+
+  describe.each(testVectors.map((vector) => [vector.description, vector] as const))("%s", (_, vector) => {
+    test("decrypts envelope to plaintext", async () => {
+      const context = await CryptoContext.fromKey(Base64UrlSafe.decode(vector.key));
+
+      const plaintext = await context.decrypt(vector.envelope);
+
+      expect(Array.from(plaintext)).toEqual(Array.from(decodeBase64(vector.plaintext)));
+    });
+
+    test("hash of plaintext matches", async () => {
+      const plaintext = decodeBase64(vector.plaintext);
+
+      const hash = await HashUtils.hashContent(plaintext.buffer as ArrayBuffer);
+
+      expect(hash).toBe(vector.hash);
+    });
+
+    test("deserializes payload", () => {
+      const plaintext = decodeBase64(vector.plaintext);
+
+      const payload = PayloadDataImpl.deserialize(plaintext.buffer as ArrayBuffer);
+
+      expect(Array.from(payload.data)).toEqual(Array.from(decodeBase64(vector.payload.data)));
+      expect(payload.filename).toBe(vector.payload.filename ?? undefined);
+      expect(payload.contentType).toBe(vector.payload.contentType ?? undefined);
+      expect(payload.description).toBe(vector.payload.description ?? undefined);
+    });
+
+    test("parses key and hash from fragment", () => {
+      const url = `https://example.com/s/01ARZ3NDEKTSV4RRFFQ69G5FAV#${vector.fragment}`;
+
+      const parsed = UrlParser.parseSecretUrl(url);
+
+      expect(parsed.secretKey).toBe(vector.key);
+      expect(parsed.hash).toBe(vector.hash);
+    });
+  });
+});
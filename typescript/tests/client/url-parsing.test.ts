@@ -53,6 +53,27 @@ describe("UrlParser", () => {
       }
     });
 
+    test("parses the secret ID bound to the key", () => {
+      const url =
+        "https://example.com/s/01KF0SR30C1X5CASYPDAJ0G6GB#AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA:47DEQpj8HBSa-_TImW-5JA:01KF0SR30C1X5CASYPDAJ0G6GB";
+
+      const result = UrlParser.parseSecretUrl(url);
+      expect(result.boundId).toBe("01KF0SR30C1X5CASYPDAJ0G6GB");
+    });
+
+    test("rejects bound secret IDs not matching the link", () => {
+      const url =
+        "https://example.com/s/01KF0SR30C1X5CASYPDAJ0G6GB#AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA:47DEQpj8HBSa-_TImW-5JA:01KF0SR30C1X5CASYPDAJ0G6GC";
+
+      try {
+        UrlParser.parseSecretUrl(url);
+        fail("Expected an error");
+      } catch (error: any) {
+        expect(error.code).toBe(HakanaiErrorCodes.INVALID_URL_FORMAT);
+        expect(error.message).toBe("Secret ID of the link does not match the ID bound to the key");
+      }
+    });
+
     test("handles valid base64url hash characters", () => {
       const urls = [
         "https://example.com/s/01KF0SR30C1X5CASYPDAJ0G6GB#AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA:47DEQpj8HBSa-_TImW-5JA",
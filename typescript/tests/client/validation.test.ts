@@ -132,11 +132,18 @@ describe("InputValidation", () => {
       expect(() => InputValidation.validateSecretId(validUlid)).not.toThrow();
     });
 
+    test("accepts short aliases", () => {
+      expect(() => InputValidation.validateSecretId("blue-otter-42")).not.toThrow();
+      expect(() => InputValidation.validateSecretId("Blue-otter-42")).toThrow();
+      expect(() => InputValidation.validateSecretId("blue-otter")).toThrow();
+    });
+
     test("rejects invalid ULID formats", () => {
       const invalids = [
         "01KF0SR30C1X5CASYPDAJ0G6G", // Too short
         "01KF0SR30C1X5CASYPDAJ0G6GBA", // Too long
         "01KF0SR30C1X5CISYPDAJ0G6GB", // Invalid character
+        "blue--otter-42", // Invalid alias
       ];
 
       for (const id of invalids) {
@@ -15,7 +15,10 @@ describe("app-config", () => {
     document.body.innerHTML = `
       <div id="impressum-link" class="hidden"></div>
       <div id="privacy-link" class="hidden"></div>
+      <span class="project-name">Hakanai</span>
+      <div id="footer-links"></div>
     `;
+    document.title = "Hakanai - Create Secret";
 
     // Mock fetch
     fetchMock = jest.fn();
@@ -264,5 +267,103 @@ describe("app-config", () => {
       expect(config3?.features?.restrictions).toBe(undefined);
       expect(config3?.showTokenInput).toBe(false);
     });
+
+    it("should apply branding from config", async () => {
+      fetchMock.mockResolvedValueOnce({
+        ok: true,
+        json: async () => ({
+          features: {
+            impressum: false,
+            privacy: false,
+          },
+          branding: {
+            instanceName: "ACME Secrets",
+            footerLinks: [
+              { label: "Support", url: "https://example.com/support" },
+              { label: "Evil", url: "javascript:alert(1)" },
+            ],
+          },
+        }),
+      });
+
+      await initFeatures();
+
+      expect(document.querySelector(".project-name")?.textContent).toBe("ACME Secrets");
+      expect(document.title).toBe("ACME Secrets - Create Secret");
+
+      const links = document.querySelectorAll<HTMLAnchorElement>("#footer-links a");
+      expect(links).toHaveLength(1);
+      expect(links[0].textContent).toBe("Support");
+      expect(links[0].href).toBe("https://example.com/support");
+    });
+
+    it("should keep defaults without branding", async () => {
+      fetchMock.mockResolvedValueOnce({
+        ok: true,
+        json: async () => ({
+          features: {
+            impressum: false,
+            privacy: false,
+          },
+        }),
+      });
+
+      await initFeatures();
+
+      expect(document.querySelector(".project-name")?.textContent).toBe("Hakanai");
+      expect(document.title).toBe("Hakanai - Create Secret");
+      expect(document.querySelectorAll("#footer-links a")).toHaveLength(0);
+    });
+
+    it("should apply UI preferences from config", async () => {
+      localStorage.removeItem("hakanai-theme");
+      fetchMock.mockResolvedValueOnce({
+        ok: true,
+        json: async () => ({
+          features: {
+            impressum: false,
+            privacy: false,
+          },
+          ui: {
+            defaultTheme: "dark",
+            forceHighContrast: true,
+            hideBranding: true,
+          },
+        }),
+      });
+
+      await initFeatures();
+
+      expect(document.body.getAttribute("data-theme")).toBe("dark");
+      expect(document.body.classList.contains("high-contrast")).toBe(true);
+      expect(document.body.classList.contains("hide-branding")).toBe(true);
+      expect(document.querySelector(".project-name")?.classList.contains("hidden")).toBe(true);
+    });
+
+    it("should not override saved theme with default theme", async () => {
+      localStorage.setItem("hakanai-theme", "light");
+      document.body.setAttribute("data-theme", "light");
+      fetchMock.mockResolvedValueOnce({
+        ok: true,
+        json: async () => ({
+          features: {
+            impressum: false,
+            privacy: false,
+          },
+          ui: {
+            defaultTheme: "dark",
+            forceHighContrast: false,
+            hideBranding: false,
+          },
+        }),
+      });
+
+      await initFeatures();
+
+      expect(document.body.getAttribute("data-theme")).toBe("light");
+      expect(document.body.classList.contains("high-contrast")).toBe(false);
+      expect(document.body.classList.contains("hide-branding")).toBe(false);
+      localStorage.removeItem("hakanai-theme");
+    });
   });
 });
@@ -1,8 +1,8 @@
 #!/usr/bin/env bash
 set -e
 
-# Build the WASM module
-wasm-pack build --target web --out-dir pkg --release
+# Build the WASM module, simd128 enables the SIMD backend of SHA-256 (supported by all current browsers)
+RUSTFLAGS='--cfg getrandom_backend="wasm_js" -C target-feature=+simd128' wasm-pack build --target web --out-dir pkg --release
 
 # Copy the generated files to the server's includes directory
 mkdir -p ../server/includes
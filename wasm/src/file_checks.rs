@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use wasm_bindgen::prelude::*;
+
+use hakanai_lib::models::ServerConfig;
+use hakanai_lib::utils::hashing;
+
+/// Hash the file content using SHA-256 and return the hexadecimal representation
+///
+/// Matches the checksum printed by `hakanai send --checksum`.
+#[wasm_bindgen]
+pub fn hash_file(content: &[u8]) -> String {
+    hashing::sha256_hex_from_bytes(content)
+}
+
+/// Hash the content using SHA-256 and return the truncated base64url representation
+///
+/// Matches the content hash appended to secret links.
+#[wasm_bindgen]
+pub fn hash_content(content: &[u8]) -> String {
+    hashing::sha256_truncated_base64_from_bytes(content)
+}
+
+/// Check whether the total size in bytes fits the size limit of the server
+///
+/// The limit is `secretSizeLimit` of `/config.json`, no limit is assumed if not set.
+#[wasm_bindgen]
+pub fn is_size_allowed(total_size: f64, secret_size_limit: Option<f64>) -> bool {
+    let config = ServerConfig {
+        secret_size_limit: secret_size_limit.map(|limit| limit as u64),
+        ..Default::default()
+    };
+
+    config.is_size_allowed(total_size as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_file() {
+        assert_eq!(
+            hash_file(b"hello"),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_hash_content() {
+        assert_eq!(
+            hash_content(b"hello").len(),
+            22,
+            "Truncated hash should be 22 characters"
+        );
+    }
+
+    #[test]
+    fn test_hashes_match_test_vectors() {
+        use base64::Engine;
+        use hakanai_lib::compat::{TEST_VECTORS_JSON, TestVector};
+
+        let vectors: Vec<TestVector> =
+            serde_json::from_str(TEST_VECTORS_JSON).expect("Failed to parse test vectors");
+        for vector in vectors {
+            let plaintext = base64::prelude::BASE64_STANDARD
+                .decode(&vector.plaintext)
+                .expect("Failed to decode plaintext");
+            assert_eq!(
+                hash_content(&plaintext),
+                vector.hash,
+                "Content hash of '{}' should match the test vector",
+                vector.description
+            );
+
+            if let Some(checksum) = vector.payload.checksum {
+                let data = base64::prelude::BASE64_STANDARD
+                    .decode(&vector.payload.data)
+                    .expect("Failed to decode data");
+                assert_eq!(hash_file(&data), checksum);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_size_allowed() {
+        assert!(
+            is_size_allowed(1024.0, None),
+            "No limit should allow any size"
+        );
+        assert!(is_size_allowed(1024.0, Some(1024.0)));
+        assert!(!is_size_allowed(1025.0, Some(1024.0)));
+    }
+}
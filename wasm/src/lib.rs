@@ -1,38 +1,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use qrcode::{QrCode, render::svg};
+mod file_checks;
+mod passphrase;
+mod secret_link;
+
+use hakanai_lib::utils::qr_code::{self, DEFAULT_MARGIN};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct QrGenerator;
+pub struct QrGenerator {
+    margin: u32,
+}
 
 #[wasm_bindgen]
 impl QrGenerator {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        QrGenerator
+        QrGenerator {
+            margin: DEFAULT_MARGIN,
+        }
     }
 
-    /// Generate a QR code as SVG string from the given text
+    /// Set the margin (quiet zone) around the QR code in modules
     #[wasm_bindgen]
-    pub fn generate_svg(&self, text: &str, size: u32) -> Result<String, JsValue> {
-        if text.len() > 256 {
-            return Err(JsValue::from_str("Input text is too long"));
-        }
-
-        if size > 250 {
-            return Err(JsValue::from_str("Size is too large"));
-        }
+    pub fn set_margin(&mut self, margin: u32) -> Result<(), JsValue> {
+        qr_code::ensure_margin_is_valid(margin).map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-        let code = QrCode::new(text)
-            .map_err(|e| JsValue::from_str(&format!("Failed to generate QR code: {e}")))?;
+        self.margin = margin;
+        Ok(())
+    }
 
-        let svg = code
-            .render::<svg::Color>()
-            .min_dimensions(size, size)
-            .build();
+    /// Generate a QR code as SVG string from the given text
+    #[wasm_bindgen]
+    pub fn generate_svg(&self, text: &str, size: u32) -> Result<String, JsValue> {
+        qr_code::to_svg(text, size, self.margin).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 
-        Ok(svg)
+    /// Generate a QR code as PNG image from the given text
+    #[wasm_bindgen]
+    pub fn generate_png(&self, text: &str, size: u32) -> Result<Vec<u8>, JsValue> {
+        qr_code::to_png(text, size, self.margin).map_err(|e| JsValue::from_str(&e.to_string()))
     }
 }
 
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use wasm_bindgen::prelude::*;
+
+use hakanai_lib::utils::wordlist;
+
+/// Maximum number of words of a generated passphrase.
+const MAX_WORD_COUNT: u32 = 16;
+
+/// Generate a diceware-style passphrase of random words separated by `-`
+///
+/// Uses 6 words if no word count is given, matching `hakanai send --require-passphrase auto`.
+#[wasm_bindgen]
+pub fn generate_passphrase(word_count: Option<u32>) -> Result<String, JsValue> {
+    generate(word_count).map_err(|e| JsValue::from_str(&e))
+}
+
+fn generate(word_count: Option<u32>) -> Result<String, String> {
+    let word_count = word_count.map_or(wordlist::DEFAULT_WORD_COUNT, |count| count as usize);
+    if word_count == 0 || word_count > MAX_WORD_COUNT as usize {
+        return Err("Word count is out of range".to_string());
+    }
+
+    Ok(wordlist::generate_passphrase(word_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_default_word_count() -> Result<(), String> {
+        let passphrase = generate(None)?;
+        assert_eq!(
+            passphrase.split(wordlist::SEPARATOR).count(),
+            wordlist::DEFAULT_WORD_COUNT
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_limits() {
+        assert!(
+            generate(Some(0)).is_err(),
+            "Empty passphrase should be rejected"
+        );
+        assert!(generate(Some(MAX_WORD_COUNT + 1)).is_err());
+        assert!(generate(Some(MAX_WORD_COUNT)).is_ok());
+    }
+}
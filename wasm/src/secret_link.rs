@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use wasm_bindgen::prelude::*;
+
+use hakanai_lib::link::SecretLink;
+
+/// Components of a secret link parsed by `parse_secret_link`
+#[wasm_bindgen(getter_with_clone)]
+pub struct ParsedSecretLink {
+    /// ID or short alias of the secret
+    pub id: String,
+
+    /// Key encoded as base64url, undefined if the link carries no key
+    pub key: Option<String>,
+
+    /// Hash of the plaintext, undefined for links of old clients
+    pub hash: Option<String>,
+
+    /// Secret ID bound to the key, if any
+    #[wasm_bindgen(js_name = boundId)]
+    pub bound_id: Option<String>,
+}
+
+/// Parse and validate a secret link (`https://{host}/s/{id}#{key}:{hash}:{id}`)
+///
+/// Links of older clients pointing to `/api/v1/secret/{id}` are accepted as well.
+#[wasm_bindgen]
+pub fn parse_secret_link(link: &str) -> Result<ParsedSecretLink, JsValue> {
+    parse(link).map_err(|e| JsValue::from_str(&e))
+}
+
+fn parse(link: &str) -> Result<ParsedSecretLink, String> {
+    let link = link.parse::<SecretLink>().map_err(|e| e.to_string())?;
+
+    Ok(ParsedSecretLink {
+        id: link.id().to_string(),
+        key: link.key().map(str::to_string),
+        hash: link.hash().map(str::to_string),
+        bound_id: link.bound_id().map(|id| id.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+
+    #[test]
+    fn test_parse() -> Result<(), String> {
+        let link = parse(&format!("https://example.com/s/{ID}#key:hash:{ID}"))?;
+
+        assert_eq!(link.id, ID);
+        assert_eq!(link.key.as_deref(), Some("key"));
+        assert_eq!(link.hash.as_deref(), Some("hash"));
+        assert_eq!(link.bound_id.as_deref(), Some(ID));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("not a link").is_err());
+        assert!(
+            parse(&format!("https://example.com/admin/{ID}#key")).is_err(),
+            "Links to other paths should be rejected"
+        );
+    }
+}
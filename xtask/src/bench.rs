@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Load test creating and retrieving secrets via the API of a server.
+//!
+//! The secrets are random bytes of the requested size instead of real ciphertexts, the server
+//! cannot tell the difference. The numbers therefore cover the server and Redis only.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use base64::Engine;
+use clap::Parser;
+
+use hakanai_lib::models::{PostSecretRequest, PostSecretResponse};
+
+use crate::e2e::{self, Environment};
+
+/// Arguments of the `bench` task.
+#[derive(Debug, Parser)]
+pub struct BenchArgs {
+    #[arg(
+        long,
+        requires = "token",
+        help = "Server to run against, a local server is started if not set."
+    )]
+    pub server_url: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_TOKEN",
+        help = "Token to create the secrets with on the given server."
+    )]
+    pub token: Option<String>,
+
+    #[arg(
+        long,
+        env = "HAKANAI_E2E_REDIS_DSN",
+        help = "Redis to run the local server against, an ephemeral redis-server is started if not set."
+    )]
+    pub redis_dsn: Option<String>,
+
+    #[arg(
+        long,
+        help = "Use a debug build of the server instead of a release build."
+    )]
+    pub debug: bool,
+
+    #[arg(
+        long,
+        help = "Skip building the binaries, e.g. if they were built before."
+    )]
+    pub no_build: bool,
+
+    #[arg(
+        short,
+        long,
+        default_value_t = 1000,
+        help = "Number of secrets to create and retrieve."
+    )]
+    pub requests: usize,
+
+    #[arg(
+        short,
+        long,
+        default_value_t = 8,
+        help = "Number of concurrent clients."
+    )]
+    pub concurrency: usize,
+
+    #[arg(
+        short,
+        long,
+        default_value_t = 1024,
+        help = "Size of the secrets in bytes (before base64 encoding)."
+    )]
+    pub size: usize,
+}
+
+/// Starts a local server (unless a server is given) and runs the load test against it.
+pub fn run(args: BenchArgs) -> Result<()> {
+    let _env;
+    let (server_url, token) = match (&args.server_url, &args.token) {
+        (Some(server_url), Some(token)) => (normalize(server_url), token.clone()),
+        _ => {
+            let release = !args.debug;
+            if !args.no_build {
+                e2e::build(release)?;
+            }
+
+            let bin_dir = e2e::target_dir().join(if release { "release" } else { "debug" });
+            let env = Environment::start(&bin_dir, args.redis_dsn.as_deref())?;
+            let server = (env.server_url().to_string(), env.user_token().to_string());
+            _env = env;
+            server
+        }
+    };
+
+    println!(
+        "Running {} requests of {} bytes with {} clients against {server_url}",
+        args.requests, args.size, args.concurrency
+    );
+
+    let data = base64::prelude::BASE64_STANDARD.encode(vec![0x42u8; args.size]);
+    let remaining = AtomicUsize::new(args.requests);
+    let results = Mutex::new(Results::default());
+
+    let started = Instant::now();
+    std::thread::scope(|scope| {
+        for _ in 0..args.concurrency.max(1) {
+            scope.spawn(|| {
+                let client = reqwest::blocking::Client::new();
+                while remaining
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok()
+                {
+                    let result = round_trip(&client, &server_url, &token, &data);
+                    results
+                        .lock()
+                        .expect("Failed to acquire lock")
+                        .record(result);
+                }
+            });
+        }
+    });
+    let elapsed = started.elapsed();
+
+    let results = results.into_inner().expect("Failed to acquire lock");
+    results.print(elapsed);
+
+    if results.failures > 0 {
+        bail!("{} of {} requests failed", results.failures, args.requests);
+    }
+
+    Ok(())
+}
+
+/// Latencies of the requests creating and retrieving a secret.
+struct RoundTrip {
+    post: Duration,
+    get: Duration,
+}
+
+fn round_trip(
+    client: &reqwest::blocking::Client,
+    server_url: &str,
+    token: &str,
+    data: &str,
+) -> Result<RoundTrip> {
+    let request = PostSecretRequest::new(data.to_string(), Duration::from_secs(300));
+
+    let started = Instant::now();
+    let resp = client
+        .post(format!("{server_url}api/v1/secret"))
+        .bearer_auth(token)
+        .json(&request)
+        .send()?;
+    if !resp.status().is_success() {
+        bail!("Failed to create secret: {}", resp.status());
+    }
+    let id = resp.json::<PostSecretResponse>()?.id;
+    let post = started.elapsed();
+
+    let started = Instant::now();
+    let resp = client
+        .get(format!("{server_url}api/v1/secret/{id}"))
+        .send()?;
+    if !resp.status().is_success() {
+        bail!("Failed to retrieve secret: {}", resp.status());
+    }
+    let secret = resp.text()?;
+    let get = started.elapsed();
+
+    if secret != data {
+        bail!("Retrieved secret does not match");
+    }
+
+    Ok(RoundTrip { post, get })
+}
+
+#[derive(Default)]
+struct Results {
+    post: Vec<Duration>,
+    get: Vec<Duration>,
+    failures: usize,
+    last_error: Option<String>,
+}
+
+impl Results {
+    fn record(&mut self, result: Result<RoundTrip>) {
+        match result {
+            Ok(round_trip) => {
+                self.post.push(round_trip.post);
+                self.get.push(round_trip.get);
+            }
+            Err(err) => {
+                self.failures += 1;
+                self.last_error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn print(&self, elapsed: Duration) {
+        let round_trips = self.post.len();
+        println!(
+            "\n{round_trips} round trips in {:.2}s ({:.1}/s), {} failed",
+            elapsed.as_secs_f64(),
+            round_trips as f64 / elapsed.as_secs_f64(),
+            self.failures
+        );
+        if let Some(err) = &self.last_error {
+            println!("last error: {err}");
+        }
+
+        println!("\n          p50       p90       p99       max");
+        print_latencies("POST", &self.post);
+        print_latencies("GET", &self.get);
+    }
+}
+
+fn print_latencies(name: &str, latencies: &[Duration]) {
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+
+    println!(
+        "{name:<6}{:>8.2}ms{:>8.2}ms{:>8.2}ms{:>8.2}ms",
+        millis(percentile(&sorted, 50)),
+        millis(percentile(&sorted, 90)),
+        millis(percentile(&sorted, 99)),
+        millis(sorted.last().copied().unwrap_or_default()),
+    );
+}
+
+/// Returns the percentile of the sorted latencies (nearest rank).
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = (sorted.len() * percentile).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn normalize(server_url: &str) -> String {
+    if server_url.ends_with('/') {
+        server_url.to_string()
+    } else {
+        format!("{server_url}/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+
+        assert_eq!(percentile(&sorted, 50), Duration::from_millis(50));
+        assert_eq!(percentile(&sorted, 99), Duration::from_millis(99));
+        assert_eq!(percentile(&sorted, 100), Duration::from_millis(100));
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("http://localhost:8080"), "http://localhost:8080/");
+        assert_eq!(
+            normalize("http://localhost:8080/"),
+            "http://localhost:8080/"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use hakanai_lib::models::{CreateTokenRequest, CreateTokenResponse};
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const USER_TOKEN_LOG_PREFIX: &str = "Default user token: ";
+const ADMIN_TOKEN_LOG_PREFIX: &str = "Admin token: ";
+
+/// Output of a CLI invocation.
+pub struct CliOutput {
+    pub success: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: String,
+}
+
+impl CliOutput {
+    /// Returns the output as error if the command failed.
+    pub fn ok(self) -> Result<Self> {
+        if !self.success {
+            bail!("command failed: {}", self.stderr.trim());
+        }
+        Ok(self)
+    }
+
+    /// Returns the first link printed to stdout.
+    pub fn link(&self) -> Result<String> {
+        String::from_utf8_lossy(&self.stdout)
+            .split_whitespace()
+            .find(|word| word.starts_with("http://"))
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("no link in output"))
+    }
+}
+
+/// Kills the process when dropped.
+struct ChildGuard(Child);
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// A server backed by Redis and the CLI to run the scenarios with.
+pub struct Environment {
+    cli: PathBuf,
+    server_url: String,
+    user_token: String,
+    admin_token: String,
+    work_dir: PathBuf,
+    server_logs: Arc<Mutex<Vec<String>>>,
+    _server: ChildGuard,
+    _redis: Option<ChildGuard>,
+}
+
+impl Environment {
+    /// Starts Redis (unless a DSN is given) and the server, waiting until both are ready.
+    pub fn start(bin_dir: &Path, redis_dsn: Option<&str>) -> Result<Self> {
+        let run_id = format!("{}-{}", std::process::id(), unix_millis());
+
+        let (redis, redis_dsn) = match redis_dsn {
+            Some(dsn) => (None, dsn.to_string()),
+            None => {
+                let port = free_port()?;
+                (
+                    Some(start_redis(port)?),
+                    format!("redis://127.0.0.1:{port}/"),
+                )
+            }
+        };
+
+        let port = free_port()?;
+        let server_url = format!("http://127.0.0.1:{port}/");
+        let server = Command::new(bin_dir.join("hakanai-server"))
+            .args(["--listen-address", "127.0.0.1", "--port", &port.to_string()])
+            .args(["--redis-dsn", &redis_dsn])
+            .args(["--redis-key-prefix", &format!("e2e-{run_id}")])
+            .args(["--enable-admin-token", "--trusted-ip-ranges", "127.0.0.0/8"])
+            .env("NO_COLOR", "1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to start hakanai-server, was it built?")?;
+        let mut server = ChildGuard(server);
+
+        let server_logs = Arc::new(Mutex::new(Vec::new()));
+        if let Some(stdout) = server.0.stdout.take() {
+            collect_lines(stdout, server_logs.clone());
+        }
+        if let Some(stderr) = server.0.stderr.take() {
+            collect_lines(stderr, server_logs.clone());
+        }
+
+        let (user_token, admin_token) = wait_for_server(&mut server, &server_url, &server_logs)?;
+
+        let work_dir = std::env::temp_dir().join(format!("hakanai-e2e-{run_id}"));
+        std::fs::create_dir_all(&work_dir)?;
+
+        Ok(Self {
+            cli: bin_dir.join("hakanai"),
+            server_url,
+            user_token,
+            admin_token,
+            work_dir,
+            server_logs,
+            _server: server,
+            _redis: redis,
+        })
+    }
+
+    pub fn server_url(&self) -> &str {
+        &self.server_url
+    }
+
+    pub fn user_token(&self) -> &str {
+        &self.user_token
+    }
+
+    pub fn admin_token(&self) -> &str {
+        &self.admin_token
+    }
+
+    /// Returns a directory for files of the scenarios, removed after the run.
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    pub fn server_logs(&self) -> String {
+        self.server_logs
+            .lock()
+            .map(|logs| logs.join("\n"))
+            .unwrap_or_default()
+    }
+
+    /// Returns the server logs without the tokens printed on startup.
+    pub fn server_logs_without_generated_tokens(&self) -> String {
+        self.server_logs()
+            .lines()
+            .filter(|line| {
+                !line.contains(USER_TOKEN_LOG_PREFIX) && !line.contains(ADMIN_TOKEN_LOG_PREFIX)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Creates a user token via the admin API.
+    pub fn create_token(&self, request: &CreateTokenRequest, admin_token: &str) -> Result<String> {
+        let resp = reqwest::blocking::Client::new()
+            .post(format!("{}api/v1/admin/tokens", self.server_url))
+            .bearer_auth(admin_token)
+            .json(request)
+            .send()?;
+        if !resp.status().is_success() {
+            bail!("Failed to create token: {}", resp.status());
+        }
+
+        let response = resp.json::<CreateTokenResponse>()?;
+        Ok(response.token.clone())
+    }
+
+    /// Runs the CLI with the arguments, the token and the input on stdin.
+    ///
+    /// `HAKANAI_*` variables of the calling environment are not passed to the CLI.
+    pub fn cli(&self, args: &[&str], token: Option<&str>, stdin: &[u8]) -> Result<CliOutput> {
+        let mut cmd = Command::new(&self.cli);
+        for (name, _) in std::env::vars_os() {
+            if name.to_string_lossy().starts_with("HAKANAI_") {
+                cmd.env_remove(name);
+            }
+        }
+        if let Some(token) = token {
+            cmd.env("HAKANAI_TOKEN", token);
+        }
+
+        let mut child = cmd
+            .args(args)
+            .env("HAKANAI_SERVER", &self.server_url)
+            .env("HAKANAI_LANG", "en")
+            .env("NO_COLOR", "1")
+            .current_dir(&self.work_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to start hakanai CLI, was it built?")?;
+
+        if let Some(mut input) = child.stdin.take() {
+            input.write_all(stdin)?;
+        }
+
+        let output = child.wait_with_output()?;
+        Ok(CliOutput {
+            success: output.status.success(),
+            stdout: output.stdout,
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+}
+
+impl Drop for Environment {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.work_dir);
+    }
+}
+
+fn start_redis(port: u16) -> Result<ChildGuard> {
+    let redis = Command::new("redis-server")
+        .args(["--port", &port.to_string(), "--bind", "127.0.0.1"])
+        .args(["--save", "", "--appendonly", "no"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start redis-server, install it or pass --redis-dsn")?;
+    let redis = ChildGuard(redis);
+
+    let start = Instant::now();
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        if start.elapsed() > STARTUP_TIMEOUT {
+            bail!("Timeout waiting for redis-server");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    Ok(redis)
+}
+
+/// Waits until the server responds and has logged the user and admin tokens.
+fn wait_for_server(
+    server: &mut ChildGuard,
+    server_url: &str,
+    logs: &Mutex<Vec<String>>,
+) -> Result<(String, String)> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = server.0.try_wait()? {
+            let logs = logs.lock().map(|l| l.join("\n")).unwrap_or_default();
+            bail!("hakanai-server exited with {status}:\n{logs}");
+        }
+
+        let ready = reqwest::blocking::get(format!("{server_url}config.json"))
+            .is_ok_and(|resp| resp.status().is_success());
+        let user_token = find_logged_value(logs, USER_TOKEN_LOG_PREFIX);
+        let admin_token = find_logged_value(logs, ADMIN_TOKEN_LOG_PREFIX);
+        if let (true, Some(user_token), Some(admin_token)) = (ready, user_token, admin_token) {
+            return Ok((user_token, admin_token));
+        }
+
+        if start.elapsed() > STARTUP_TIMEOUT {
+            bail!("Timeout waiting for hakanai-server");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn find_logged_value(logs: &Mutex<Vec<String>>, prefix: &str) -> Option<String> {
+    let logs = logs.lock().ok()?;
+    logs.iter().find_map(|line| {
+        line.split_once(prefix)
+            .map(|(_, value)| value.trim().to_string())
+    })
+}
+
+/// Collects the lines of the output in the background, stripping ANSI escape sequences.
+fn collect_lines<R: Read + Send + 'static>(output: R, lines: Arc<Mutex<Vec<String>>>) {
+    thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            if let Ok(mut lines) = lines.lock() {
+                lines.push(strip_ansi(&line));
+            }
+        }
+    });
+}
+
+fn strip_ansi(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the control sequence up to its final byte
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(
+            strip_ansi("\x1b[2m2026-01-01\x1b[0m \x1b[32m INFO\x1b[0m Admin token: abc"),
+            "2026-01-01  INFO Admin token: abc"
+        );
+    }
+
+    #[test]
+    fn test_link() -> Result<()> {
+        let output = CliOutput {
+            success: true,
+            stdout:
+                b"Secret sent successfully!\n\nSecret link: http://127.0.0.1:1234/s/abc#key:hash\n"
+                    .to_vec(),
+            stderr: String::new(),
+        };
+        assert_eq!(output.link()?, "http://127.0.0.1:1234/s/abc#key:hash");
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! End-to-end tests running the CLI against a local server backed by an ephemeral Redis.
+
+mod environment;
+mod scenarios;
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Result, anyhow, bail};
+use clap::Parser;
+
+pub use environment::Environment;
+
+/// Arguments of the `e2e` task.
+#[derive(Debug, Parser)]
+pub struct E2eArgs {
+    #[arg(
+        long,
+        env = "HAKANAI_E2E_REDIS_DSN",
+        help = "Redis to run the server against, an ephemeral redis-server is started if not set. Keys are isolated by a random prefix."
+    )]
+    pub redis_dsn: Option<String>,
+
+    #[arg(long, help = "Use release builds of the binaries.")]
+    pub release: bool,
+
+    #[arg(
+        long,
+        help = "Skip building the binaries, e.g. if they were built before."
+    )]
+    pub no_build: bool,
+
+    #[arg(long, help = "Only run scenarios containing the filter in their name.")]
+    pub filter: Option<String>,
+
+    #[arg(long, help = "Print the output of the server after the tests.")]
+    pub server_logs: bool,
+}
+
+/// Builds the binaries, starts the environment and runs the scenarios.
+pub fn run(args: E2eArgs) -> Result<()> {
+    if !args.no_build {
+        build(args.release)?;
+    }
+
+    let bin_dir = target_dir().join(if args.release { "release" } else { "debug" });
+    let env = Environment::start(&bin_dir, args.redis_dsn.as_deref())?;
+
+    let mut failed = Vec::new();
+    let scenarios = scenarios::all().into_iter().filter(|(name, _)| {
+        args.filter
+            .as_ref()
+            .is_none_or(|f| name.contains(f.as_str()))
+    });
+    for (name, scenario) in scenarios {
+        match scenario(&env) {
+            Ok(()) => println!("ok      {name}"),
+            Err(err) => {
+                println!("FAILED  {name}: {err:#}");
+                failed.push(name);
+            }
+        }
+    }
+
+    if args.server_logs {
+        println!("\n--- server logs ---\n{}", env.server_logs());
+    }
+
+    if !failed.is_empty() {
+        bail!("{} scenario(s) failed: {}", failed.len(), failed.join(", "));
+    }
+
+    println!("\nAll scenarios passed.");
+    Ok(())
+}
+
+/// Builds the CLI and the server.
+pub fn build(release: bool) -> Result<()> {
+    let mut cmd = Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()));
+    cmd.args(["build", "--bin", "hakanai", "--bin", "hakanai-server"]);
+    if release {
+        cmd.arg("--release");
+    }
+
+    let status = cmd.current_dir(workspace_dir()).status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to build binaries: {status}"));
+    }
+
+    Ok(())
+}
+
+fn workspace_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
+
+pub fn target_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_dir().join("target"))
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, bail, ensure};
+
+use hakanai_lib::models::CreateTokenRequest;
+use hakanai_lib::utils::hashing;
+
+use super::environment::Environment;
+
+type Scenario = fn(&Environment) -> Result<()>;
+
+/// Returns all scenarios with their names in the order they are run.
+pub fn all() -> Vec<(&'static str, Scenario)> {
+    vec![
+        ("text round trip", text_round_trip),
+        ("secret is retrievable once", one_time_retrieval),
+        ("file round trip", file_round_trip),
+        ("anonymous send is rejected", anonymous_send_rejected),
+        ("allowed IP can retrieve", allowed_ip_can_retrieve),
+        ("disallowed IP is denied", disallowed_ip_denied),
+        ("passphrase is required", passphrase_required),
+        ("token upload size limit", token_upload_size_limit),
+        ("admin API rejects user token", admin_api_rejects_user_token),
+        (
+            "logs contain no sensitive values",
+            logs_contain_no_sensitive_values,
+        ),
+    ]
+}
+
+fn send(env: &Environment, args: &[&str], secret: &[u8]) -> Result<String> {
+    let mut send_args = vec!["send"];
+    send_args.extend_from_slice(args);
+    env.cli(&send_args, Some(env.user_token()), secret)?
+        .ok()?
+        .link()
+}
+
+fn get(env: &Environment, link: &str, args: &[&str]) -> Result<Vec<u8>> {
+    let mut get_args = vec!["get", link, "--to-stdout"];
+    get_args.extend_from_slice(args);
+    Ok(env.cli(&get_args, None, &[])?.ok()?.stdout)
+}
+
+fn text_round_trip(env: &Environment) -> Result<()> {
+    let link = send(env, &[], b"e2e text secret")?;
+
+    let secret = get(env, &link, &[])?;
+
+    ensure!(
+        secret == b"e2e text secret",
+        "unexpected secret: {secret:?}"
+    );
+    Ok(())
+}
+
+fn one_time_retrieval(env: &Environment) -> Result<()> {
+    let link = send(env, &[], b"read me once")?;
+    get(env, &link, &[])?;
+
+    let second = env.cli(&["get", &link, "--to-stdout"], None, &[])?;
+
+    ensure!(!second.success, "secret was retrieved twice");
+    Ok(())
+}
+
+fn file_round_trip(env: &Environment) -> Result<()> {
+    let content: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+    let file = env.work_dir().join("e2e-upload.bin");
+    std::fs::write(&file, &content)?;
+    let link = send(env, &["-f", &file.to_string_lossy()], &[])?;
+
+    let output_dir = env.work_dir().join("download");
+    std::fs::create_dir_all(&output_dir)?;
+    env.cli(
+        &["get", &link, "--output-dir", &output_dir.to_string_lossy()],
+        None,
+        &[],
+    )?
+    .ok()?;
+
+    let downloaded = std::fs::read(output_dir.join("e2e-upload.bin"))?;
+    ensure!(downloaded == content, "downloaded file differs from upload");
+    Ok(())
+}
+
+fn anonymous_send_rejected(env: &Environment) -> Result<()> {
+    let output = env.cli(&["send"], None, b"anonymous secret")?;
+
+    ensure!(!output.success, "anonymous send succeeded");
+    Ok(())
+}
+
+fn allowed_ip_can_retrieve(env: &Environment) -> Result<()> {
+    let link = send(env, &["--allow-ip", "127.0.0.1/32"], b"local only")?;
+
+    let secret = get(env, &link, &[])?;
+
+    ensure!(secret == b"local only", "unexpected secret: {secret:?}");
+    Ok(())
+}
+
+fn disallowed_ip_denied(env: &Environment) -> Result<()> {
+    let link = send(env, &["--allow-ip", "192.0.2.0/24"], b"not for us")?;
+
+    let output = env.cli(&["get", &link, "--to-stdout"], None, &[])?;
+
+    ensure!(!output.success, "secret was retrieved from disallowed IP");
+    Ok(())
+}
+
+fn passphrase_required(env: &Environment) -> Result<()> {
+    let link = send(
+        env,
+        &["--require-passphrase", "correct horse"],
+        b"protected",
+    )?;
+
+    let without = env.cli(&["get", &link, "--to-stdout"], None, &[])?;
+    ensure!(!without.success, "secret was retrieved without passphrase");
+    let wrong = env.cli(
+        &["get", &link, "--to-stdout", "--passphrase", "wrong horse"],
+        None,
+        &[],
+    )?;
+    ensure!(!wrong.success, "secret was retrieved with wrong passphrase");
+
+    let secret = get(env, &link, &["--passphrase", "correct horse"])?;
+    ensure!(secret == b"protected", "unexpected secret: {secret:?}");
+    Ok(())
+}
+
+fn token_upload_size_limit(env: &Environment) -> Result<()> {
+    let mut request = CreateTokenRequest::new(3600);
+    request.upload_size_limit = Some(1024);
+    let token = env.create_token(&request, env.admin_token())?;
+
+    let small = env.cli(&["send"], Some(&token), b"small enough")?;
+    ensure!(small.success, "small secret rejected: {}", small.stderr);
+
+    let large = env.cli(&["send"], Some(&token), &vec![b'x'; 64 * 1024])?;
+    ensure!(!large.success, "secret above token limit was accepted");
+    Ok(())
+}
+
+fn admin_api_rejects_user_token(env: &Environment) -> Result<()> {
+    let request = CreateTokenRequest::new(3600);
+    if env.create_token(&request, env.user_token()).is_ok() {
+        bail!("token created with user token");
+    }
+    Ok(())
+}
+
+/// Runs flows handling tokens, passphrases and keys and scans the server logs for them.
+///
+/// Runs last to cover the logs of all previous scenarios as well.
+fn logs_contain_no_sensitive_values(env: &Environment) -> Result<()> {
+    let token = env.create_token(&CreateTokenRequest::new(3600), env.admin_token())?;
+    let passphrase = "e2e log passphrase";
+    let link = env
+        .cli(
+            &["send", "--require-passphrase", passphrase],
+            Some(&token),
+            b"logged secret",
+        )?
+        .ok()?
+        .link()?;
+    env.cli(&["get", &link, "--to-stdout"], None, &[])?;
+    get(env, &link, &["--passphrase", passphrase])?;
+    env.cli(&["send"], Some("e2e-invalid-token"), b"rejected")?;
+
+    let Some((_, key)) = link.split_once('#') else {
+        bail!("link without key: {link}");
+    };
+    let markers = [
+        ("user token", env.user_token().to_string()),
+        ("admin token", env.admin_token().to_string()),
+        ("created token", token.clone()),
+        ("token hash", hashing::sha256_hex_from_string(&token)),
+        ("invalid token", "e2e-invalid-token".to_string()),
+        ("passphrase", passphrase.to_string()),
+        (
+            "passphrase hash",
+            hashing::sha256_hex_from_bytes(passphrase.as_bytes()),
+        ),
+        ("secret key", key.to_string()),
+    ];
+
+    let logs = env.server_logs_without_generated_tokens();
+    let leaked: Vec<&str> = markers
+        .iter()
+        .filter(|(_, value)| logs.contains(value.as_str()))
+        .map(|(name, _)| *name)
+        .collect();
+    ensure!(
+        leaked.is_empty(),
+        "server logs contain: {}",
+        leaked.join(", ")
+    );
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod bench;
+mod e2e;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+/// Development tasks of the Hakanai workspace, run via `cargo xtask <task>`.
+#[derive(Debug, Parser)]
+#[command(name = "xtask")]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs the end-to-end tests of the CLI against a local server.
+    E2e(e2e::E2eArgs),
+
+    /// Runs a load test creating and retrieving secrets against a local server.
+    Bench(bench::BenchArgs),
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let result = match args.command {
+        Command::E2e(e2e_args) => e2e::run(e2e_args),
+        Command::Bench(bench_args) => bench::run(bench_args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}